@@ -0,0 +1,6 @@
+-- Journal of recently published realtime events, replayed to reconnecting SSE clients
+CREATE TABLE realtime_events (
+    id BIGSERIAL PRIMARY KEY,
+    payload JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
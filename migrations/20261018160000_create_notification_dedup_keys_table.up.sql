@@ -0,0 +1,9 @@
+-- Keys of notifications that must only be sent once per user, such as match reminders
+CREATE TABLE notification_dedup_keys (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    dedup_key TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, dedup_key)
+);
+
+CREATE INDEX idx_notification_dedup_keys_created_at ON notification_dedup_keys (created_at);
@@ -0,0 +1,5 @@
+DROP INDEX IF EXISTS idx_matches_court_scheduled;
+ALTER TABLE matches DROP COLUMN IF EXISTS court_id;
+DROP TABLE IF EXISTS tournament_venues;
+DROP TABLE IF EXISTS courts;
+DROP TABLE IF EXISTS venues;
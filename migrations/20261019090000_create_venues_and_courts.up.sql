@@ -0,0 +1,43 @@
+-- Venues, their courts/tables/fields, and the venues each tournament uses
+CREATE TABLE venues (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(255) NOT NULL,
+    address TEXT,
+    city VARCHAR(255),
+    country VARCHAR(255),
+    timezone VARCHAR(64) NOT NULL DEFAULT 'UTC',
+    -- [{"weekday": "Sat", "opens": "08:00:00", "closes": "22:00:00"}, ...]; empty = always open
+    opening_hours JSONB NOT NULL DEFAULT '[]',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE courts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    venue_id UUID NOT NULL REFERENCES venues(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    kind TEXT NOT NULL CHECK (kind IN ('court', 'table', 'field')),
+    surface TEXT CHECK (surface IN ('hard', 'clay', 'grass', 'artificial_grass', 'carpet', 'wood', 'sand', 'other')),
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    -- [{"starts_at": ..., "ends_at": ...}, ...]; empty = whenever the venue is open
+    availability JSONB NOT NULL DEFAULT '[]',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (venue_id, name)
+);
+
+CREATE TABLE tournament_venues (
+    tournament_id UUID NOT NULL REFERENCES tournaments(id) ON DELETE CASCADE,
+    venue_id UUID NOT NULL REFERENCES venues(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (tournament_id, venue_id)
+);
+
+CREATE INDEX idx_tournament_venues_venue_id ON tournament_venues (venue_id);
+
+-- Matches keep their free-text venue and court_number, filled from the court when assigned
+ALTER TABLE matches ADD COLUMN court_id UUID REFERENCES courts(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_matches_court_scheduled ON matches (court_id, scheduled_date)
+WHERE
+    court_id IS NOT NULL;
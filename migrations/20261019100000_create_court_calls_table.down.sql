@@ -0,0 +1,15 @@
+DROP TABLE IF EXISTS court_calls;
+
+-- Enum values can't be dropped; rebuild the type without 'court_call'
+UPDATE notifications SET notification_type = 'match_reminder' WHERE notification_type = 'court_call';
+ALTER TYPE notification_type RENAME TO notification_type_old;
+CREATE TYPE notification_type AS ENUM (
+    'tournament_update',
+    'match_reminder',
+    'result_posted',
+    'registration_confirmed'
+);
+ALTER TABLE notifications
+    ALTER COLUMN notification_type TYPE notification_type
+    USING notification_type::text::notification_type;
+DROP TYPE notification_type_old;
@@ -0,0 +1,21 @@
+-- Calls summoning a match's participants to its court; one per match, replaced by each new call
+CREATE TABLE court_calls (
+    match_id UUID PRIMARY KEY REFERENCES matches(id) ON DELETE CASCADE,
+    court_id UUID NOT NULL REFERENCES courts(id) ON DELETE CASCADE,
+    status TEXT NOT NULL CHECK (status IN ('called', 'ready', 'forfeited', 'closed')),
+    call_count INTEGER NOT NULL DEFAULT 1,
+    max_calls INTEGER NOT NULL,
+    first_called_at TIMESTAMPTZ NOT NULL,
+    last_called_at TIMESTAMPTZ NOT NULL,
+    respond_by TIMESTAMPTZ NOT NULL,
+    participant1_present BOOLEAN NOT NULL DEFAULT FALSE,
+    participant2_present BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_court_calls_waiting ON court_calls (respond_by)
+WHERE
+    status = 'called';
+
+ALTER TYPE notification_type ADD VALUE IF NOT EXISTS 'court_call';
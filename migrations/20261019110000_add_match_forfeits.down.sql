@@ -0,0 +1,3 @@
+ALTER TABLE matches
+    DROP COLUMN IF EXISTS defaulting_participant,
+    DROP COLUMN IF EXISTS forfeit_kind;
@@ -0,0 +1,4 @@
+-- How a forfeited match was decided and which side defaulted (NULL with a kind: both sides)
+ALTER TABLE matches
+    ADD COLUMN forfeit_kind TEXT CHECK (forfeit_kind IN ('walkover', 'retirement')),
+    ADD COLUMN defaulting_participant INTEGER CHECK (defaulting_participant IN (1, 2));
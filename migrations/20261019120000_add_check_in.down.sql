@@ -0,0 +1,6 @@
+ALTER TABLE tournament_registrations
+    DROP COLUMN IF EXISTS checked_in_by,
+    DROP COLUMN IF EXISTS check_in_method,
+    DROP COLUMN IF EXISTS checked_in_at;
+
+DROP TABLE IF EXISTS check_in_windows;
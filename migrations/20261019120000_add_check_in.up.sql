@@ -0,0 +1,21 @@
+-- One check-in window per category; closed_at is set once no-shows have been settled
+CREATE TABLE IF NOT EXISTS check_in_windows (
+    category_id UUID PRIMARY KEY REFERENCES tournament_categories(id) ON DELETE CASCADE,
+    opens_at TIMESTAMPTZ NOT NULL,
+    closes_at TIMESTAMPTZ NOT NULL,
+    no_show_action TEXT NOT NULL DEFAULT 'withdraw'
+        CHECK (no_show_action IN ('withdraw', 'waitlist')),
+    promote_waitlist BOOLEAN NOT NULL DEFAULT TRUE,
+    closed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (closes_at > opens_at)
+);
+
+CREATE INDEX IF NOT EXISTS idx_check_in_windows_due
+    ON check_in_windows (closes_at) WHERE closed_at IS NULL;
+
+ALTER TABLE tournament_registrations
+    ADD COLUMN checked_in_at TIMESTAMPTZ,
+    ADD COLUMN check_in_method TEXT CHECK (check_in_method IN ('self', 'qr', 'desk')),
+    ADD COLUMN checked_in_by UUID REFERENCES users(id) ON DELETE SET NULL;
@@ -0,0 +1,12 @@
+-- Spectator tickets; a ticket admits once, when admitted_at is set at the door
+CREATE TABLE IF NOT EXISTS match_tickets (
+    id UUID PRIMARY KEY,
+    match_id UUID NOT NULL REFERENCES matches(id) ON DELETE CASCADE,
+    holder_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    issued_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    admitted_at TIMESTAMPTZ,
+    admitted_by UUID REFERENCES users(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_match_tickets_holder ON match_tickets (holder_id, issued_at DESC);
+CREATE INDEX IF NOT EXISTS idx_match_tickets_match ON match_tickets (match_id);
@@ -0,0 +1,3 @@
+-- IANA zone the tournament is played in; dates stay stored in UTC
+ALTER TABLE tournaments
+    ADD COLUMN timezone TEXT NOT NULL DEFAULT 'UTC';
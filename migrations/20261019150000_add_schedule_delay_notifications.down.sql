@@ -0,0 +1,14 @@
+-- Enum values can't be dropped; rebuild the type without 'schedule_delay'
+UPDATE notifications SET notification_type = 'match_reminder' WHERE notification_type = 'schedule_delay';
+ALTER TYPE notification_type RENAME TO notification_type_old;
+CREATE TYPE notification_type AS ENUM (
+    'tournament_update',
+    'match_reminder',
+    'result_posted',
+    'registration_confirmed',
+    'court_call'
+);
+ALTER TABLE notifications
+    ALTER COLUMN notification_type TYPE notification_type
+    USING notification_type::text::notification_type;
+DROP TYPE notification_type_old;
@@ -0,0 +1,2 @@
+-- Notices that a match is running late on its court
+ALTER TYPE notification_type ADD VALUE IF NOT EXISTS 'schedule_delay';
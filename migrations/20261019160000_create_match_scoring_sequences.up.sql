@@ -0,0 +1,17 @@
+-- Sequence numbers of live scoring actions, so two scorekeepers can't record the same one.
+-- The actions stay in match_results.scoring_data; deleting a game's result frees its numbers.
+CREATE TABLE IF NOT EXISTS match_scoring_sequences (
+    match_id UUID NOT NULL REFERENCES matches(id) ON DELETE CASCADE,
+    sequence BIGINT NOT NULL,
+    match_result_id UUID NOT NULL REFERENCES match_results(id) ON DELETE CASCADE,
+    PRIMARY KEY (match_id, sequence)
+);
+
+CREATE INDEX IF NOT EXISTS idx_match_scoring_sequences_result ON match_scoring_sequences (match_result_id);
+
+INSERT INTO match_scoring_sequences (match_id, sequence, match_result_id)
+SELECT r.match_id, (e->>'sequence')::BIGINT, r.id
+FROM match_results r
+CROSS JOIN LATERAL jsonb_array_elements(r.scoring_data->'events') AS e
+WHERE jsonb_typeof(r.scoring_data->'events') = 'array'
+ON CONFLICT DO NOTHING;
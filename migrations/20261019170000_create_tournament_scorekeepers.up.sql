@@ -0,0 +1,9 @@
+-- Users an organizer has allowed to score a tournament's matches, e.g. court-side referees
+CREATE TABLE IF NOT EXISTS tournament_scorekeepers (
+    tournament_id UUID NOT NULL REFERENCES tournaments(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (tournament_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_tournament_scorekeepers_user ON tournament_scorekeepers (user_id);
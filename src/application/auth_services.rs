@@ -8,8 +8,8 @@ use crate::shared::{google, jwt, AppError};
 /// Auth services (Google OAuth login, etc.)
 pub struct AuthServices<U, T>
 where
-    U: UserRepository,
-    T: TokenRepository,
+    U: UserRepository + ?Sized,
+    T: TokenRepository + ?Sized,
 {
     user_repo: Arc<U>,
     token_repo: Arc<T>,
@@ -17,8 +17,8 @@ where
 
 impl<U, T> AuthServices<U, T>
 where
-    U: UserRepository,
-    T: TokenRepository,
+    U: UserRepository + ?Sized,
+    T: TokenRepository + ?Sized,
 {
     pub fn new(user_repo: Arc<U>, token_repo: Arc<T>) -> Self {
         Self {
@@ -0,0 +1,196 @@
+use chrono::{DateTime, Duration, Utc};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::calendar::{match_event, CalendarFeed, CalendarFeedKind};
+use crate::domain::match_domain::{MatchRepository, MatchScheduleItem};
+use crate::domain::scheduling::estimated_duration;
+use crate::domain::tournament::{
+    Tournament, TournamentCategory, TournamentCategoryRepository, TournamentRepository,
+};
+use crate::domain::venue::VenueRepository;
+use crate::shared::ical::Calendar;
+use crate::shared::AppError;
+
+/// How far back a user's or court's feed keeps matches already played
+const PAST_DAYS: i64 = 30;
+
+/// How far ahead a court's feed looks
+const COURT_DAYS_AHEAD: i64 = 365;
+
+/// Calendar services - renders match schedules as iCalendar feeds. Feeds are built from
+/// the current schedule on every fetch, so rescheduled and postponed matches show up the
+/// next time a calendar app refreshes.
+pub struct CalendarServices<M, C, T, V>
+where
+    M: MatchRepository + ?Sized,
+    C: TournamentCategoryRepository + ?Sized,
+    T: TournamentRepository + ?Sized,
+    V: VenueRepository + ?Sized,
+{
+    match_repo: Arc<M>,
+    category_repo: Arc<C>,
+    tournament_repo: Arc<T>,
+    venue_repo: Arc<V>,
+}
+
+impl<M, C, T, V> CalendarServices<M, C, T, V>
+where
+    M: MatchRepository + ?Sized,
+    C: TournamentCategoryRepository + ?Sized,
+    T: TournamentRepository + ?Sized,
+    V: VenueRepository + ?Sized,
+{
+    pub fn new(
+        match_repo: Arc<M>,
+        category_repo: Arc<C>,
+        tournament_repo: Arc<T>,
+        venue_repo: Arc<V>,
+    ) -> Self {
+        Self {
+            match_repo,
+            category_repo,
+            tournament_repo,
+            venue_repo,
+        }
+    }
+
+    /// The feed's calendar name, or `None` when what it follows does not exist
+    pub async fn feed_name(&self, feed: &CalendarFeed) -> Result<Option<String>, AppError> {
+        match feed.kind {
+            CalendarFeedKind::User => Ok(Some("My matches".to_string())),
+            CalendarFeedKind::Tournament => Ok(self
+                .tournament_repo
+                .get_by_id(feed.id)
+                .await?
+                .map(|t| t.name)),
+            CalendarFeedKind::Category => {
+                let Some(category) = self.category_repo.get_by_id(feed.id).await? else {
+                    return Ok(None);
+                };
+                Ok(self
+                    .tournament_repo
+                    .get_by_id(category.tournament_id)
+                    .await?
+                    .map(|t| format!("{} - {}", t.name, category.name)))
+            }
+            CalendarFeedKind::Court => {
+                let Some(court) = self.venue_repo.find_court(feed.id).await? else {
+                    return Ok(None);
+                };
+                Ok(match self.venue_repo.find_by_id(court.venue_id).await? {
+                    Some(venue) => Some(format!("{} - {}", venue.name, court.name)),
+                    None => Some(court.name),
+                })
+            }
+        }
+    }
+
+    /// The feed as an .ics document, or `None` when what it follows does not exist
+    pub async fn render_feed(
+        &self,
+        feed: &CalendarFeed,
+        now: DateTime<Utc>,
+    ) -> Result<Option<String>, AppError> {
+        let Some(name) = self.feed_name(feed).await? else {
+            return Ok(None);
+        };
+        let items = self.feed_items(feed, now).await?;
+
+        let mut tournaments: HashMap<Uuid, Option<Tournament>> = HashMap::new();
+        let mut categories: HashMap<Uuid, Option<TournamentCategory>> = HashMap::new();
+        let mut calendar = Calendar::new(name);
+        for item in &items {
+            if let Entry::Vacant(slot) = tournaments.entry(item.tournament_id) {
+                slot.insert(self.tournament_repo.get_by_id(item.tournament_id).await?);
+            }
+            if let Entry::Vacant(slot) = categories.entry(item.tournament_category_id) {
+                slot.insert(
+                    self.category_repo
+                        .get_by_id(item.tournament_category_id)
+                        .await?,
+                );
+            }
+            let Some(Some(tournament)) = tournaments.get(&item.tournament_id) else {
+                continue;
+            };
+            let category = categories
+                .get(&item.tournament_category_id)
+                .and_then(Option::as_ref);
+            let duration = estimated_duration(tournament.sport_type, category);
+            calendar.push(match_event(item, duration));
+        }
+        Ok(Some(calendar.to_ics(now)))
+    }
+
+    async fn feed_items(
+        &self,
+        feed: &CalendarFeed,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<MatchScheduleItem>, AppError> {
+        let since = now - Duration::days(PAST_DAYS);
+        match feed.kind {
+            CalendarFeedKind::User => {
+                let mut items: Vec<MatchScheduleItem> = self
+                    .match_repo
+                    .find_user_match_history(feed.id)
+                    .await?
+                    .into_iter()
+                    .filter(|i| i.scheduled_date >= since)
+                    .collect();
+                items.extend(self.match_repo.find_user_upcoming_matches(feed.id).await?);
+                items.sort_by_key(|i| i.scheduled_date);
+                Ok(items)
+            }
+            CalendarFeedKind::Tournament => {
+                self.match_repo.find_schedule_by_tournament(feed.id).await
+            }
+            CalendarFeedKind::Category => {
+                let Some(category) = self.category_repo.get_by_id(feed.id).await? else {
+                    return Ok(Vec::new());
+                };
+                Ok(self
+                    .match_repo
+                    .find_schedule_by_tournament(category.tournament_id)
+                    .await?
+                    .into_iter()
+                    .filter(|i| i.tournament_category_id == feed.id)
+                    .collect())
+            }
+            CalendarFeedKind::Court => {
+                let matches = self
+                    .match_repo
+                    .find_by_court(feed.id, since, now + Duration::days(COURT_DAYS_AHEAD))
+                    .await?;
+                let ids: HashSet<Uuid> = matches.iter().map(|m| m.id).collect();
+                // Schedule items carry the names; fetch them once per tournament
+                let mut tournament_ids = Vec::new();
+                for category_id in matches
+                    .iter()
+                    .map(|m| m.tournament_category_id)
+                    .collect::<HashSet<_>>()
+                {
+                    if let Some(category) = self.category_repo.get_by_id(category_id).await? {
+                        if !tournament_ids.contains(&category.tournament_id) {
+                            tournament_ids.push(category.tournament_id);
+                        }
+                    }
+                }
+                let mut items = Vec::new();
+                for tournament_id in tournament_ids {
+                    items.extend(
+                        self.match_repo
+                            .find_schedule_by_tournament(tournament_id)
+                            .await?
+                            .into_iter()
+                            .filter(|i| ids.contains(&i.id)),
+                    );
+                }
+                items.sort_by_key(|i| i.scheduled_date);
+                Ok(items)
+            }
+        }
+    }
+}
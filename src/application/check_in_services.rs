@@ -0,0 +1,517 @@
+use chrono::{DateTime, Duration, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::check_in::{
+    plan_closure, CheckIn, CheckInEntry, CheckInMethod, CheckInSheet, CheckInWindow,
+    CheckInWindowRepository, CheckInWindowRequest, NoShowAction, RegistrationVerification,
+    WindowClosure,
+};
+use crate::domain::outbox::DomainEvent;
+use crate::domain::participant::{PlayerRepository, TeamMemberRepository};
+use crate::domain::realtime::{EventPublisher, RealtimeEvent};
+use crate::domain::tournament::{
+    EditableTournamentRegistration, RegistrationStatus, RegistrationWithDetails,
+    TournamentCategoryRepository, TournamentRegistration, TournamentRegistrationRepository,
+};
+use crate::domain::unit_of_work::UnitOfWorkFactory;
+use crate::shared::config::CheckInConfig;
+use crate::shared::AppError;
+
+/// How long a check-in pass lasts when its category has no window yet
+const PASS_DAYS_WITHOUT_WINDOW: i64 = 30;
+
+fn with_status(status: RegistrationStatus) -> EditableTournamentRegistration {
+    EditableTournamentRegistration {
+        registration_status: Some(status),
+        payment_status: None,
+        payment_amount: None,
+        payment_reference: None,
+        notes: None,
+        metadata: None,
+    }
+}
+
+/// Tournament-day check-in: windows per category, self, QR and desk check-in, and closing
+/// windows on the entries that never showed up
+pub struct CheckInServices<W, R, C, P, M, U>
+where
+    W: CheckInWindowRepository + ?Sized,
+    R: TournamentRegistrationRepository + ?Sized,
+    C: TournamentCategoryRepository + ?Sized,
+    P: PlayerRepository + ?Sized,
+    M: TeamMemberRepository + ?Sized,
+    U: UnitOfWorkFactory + ?Sized,
+{
+    window_repo: Arc<W>,
+    registration_repo: Arc<R>,
+    category_repo: Arc<C>,
+    player_repo: Arc<P>,
+    team_member_repo: Arc<M>,
+    uow: Arc<U>,
+    events: Arc<dyn EventPublisher>,
+}
+
+impl<W, R, C, P, M, U> CheckInServices<W, R, C, P, M, U>
+where
+    W: CheckInWindowRepository + ?Sized,
+    R: TournamentRegistrationRepository + ?Sized,
+    C: TournamentCategoryRepository + ?Sized,
+    P: PlayerRepository + ?Sized,
+    M: TeamMemberRepository + ?Sized,
+    U: UnitOfWorkFactory + ?Sized,
+{
+    pub fn new(
+        window_repo: Arc<W>,
+        registration_repo: Arc<R>,
+        category_repo: Arc<C>,
+        player_repo: Arc<P>,
+        team_member_repo: Arc<M>,
+        uow: Arc<U>,
+        events: Arc<dyn EventPublisher>,
+    ) -> Self {
+        Self {
+            window_repo,
+            registration_repo,
+            category_repo,
+            player_repo,
+            team_member_repo,
+            uow,
+            events,
+        }
+    }
+
+    // ==================== Windows ====================
+
+    /// Sets the category's window; saving a closed window opens it again.
+    /// Returns `None` when the category does not exist.
+    pub async fn set_window(
+        &self,
+        category_id: Uuid,
+        request: CheckInWindowRequest,
+    ) -> Result<Option<CheckInWindow>, AppError> {
+        if request.closes_at <= request.opens_at {
+            return Err(AppError::ValidationError(
+                "closes_at must be after opens_at".into(),
+            ));
+        }
+        if self.category_repo.get_by_id(category_id).await?.is_none() {
+            return Ok(None);
+        }
+        let now = Utc::now();
+        let window = CheckInWindow {
+            category_id,
+            opens_at: request.opens_at,
+            closes_at: request.closes_at,
+            no_show_action: request.no_show_action.unwrap_or_default(),
+            promote_waitlist: request.promote_waitlist.unwrap_or(true),
+            closed_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+        Ok(Some(self.window_repo.save(window).await?))
+    }
+
+    pub async fn delete_window(
+        &self,
+        category_id: Uuid,
+    ) -> Result<Option<CheckInWindow>, AppError> {
+        self.window_repo.delete(category_id).await
+    }
+
+    /// The category's window with its approved and waitlisted entries and who is present
+    pub async fn get_sheet(&self, category_id: Uuid) -> Result<Option<CheckInSheet>, AppError> {
+        let Some(category) = self.category_repo.get_by_id(category_id).await? else {
+            return Ok(None);
+        };
+        let window = self.window_repo.find_by_category(category_id).await?;
+        let details = self
+            .registration_repo
+            .get_by_tournament(category.tournament_id)
+            .await?;
+        let mut registrations = self
+            .registration_repo
+            .get_by_tournament_category(category_id)
+            .await?;
+        registrations.retain(|r| {
+            matches!(
+                r.registration_status,
+                RegistrationStatus::Approved | RegistrationStatus::Waitlisted
+            )
+        });
+        registrations.sort_by_key(|r| (r.registration_date, r.id));
+
+        let approved = || {
+            registrations
+                .iter()
+                .filter(|r| r.registration_status == RegistrationStatus::Approved)
+        };
+        let expected = approved().count();
+        let checked_in = approved().filter(|r| r.checked_in_at.is_some()).count();
+        let entries = registrations
+            .iter()
+            .map(|r| {
+                let names = details.iter().find(|d| d.id == r.id);
+                let participant_name = names
+                    .and_then(RegistrationWithDetails::display_name)
+                    .unwrap_or_else(|| "TBD".to_string());
+                CheckInEntry {
+                    registration_id: r.id,
+                    participant_id: r.team_id.or(r.player_id),
+                    participant_name,
+                    registration_status: r.registration_status,
+                    checked_in_at: r.checked_in_at,
+                    check_in_method: r.check_in_method,
+                }
+            })
+            .collect();
+        Ok(Some(CheckInSheet {
+            category_id,
+            window,
+            expected,
+            checked_in,
+            entries,
+        }))
+    }
+
+    // ==================== Checking in ====================
+
+    /// The registration a user may carry a check-in pass for, and when the pass should run
+    /// out: at the close of the category's window. `None` when the registration does not
+    /// exist.
+    pub async fn check_in_pass(
+        &self,
+        user_id: Uuid,
+        registration_id: Uuid,
+    ) -> Result<Option<(TournamentRegistration, DateTime<Utc>)>, AppError> {
+        let Some(registration) = self.registration_repo.get_by_id(registration_id).await? else {
+            return Ok(None);
+        };
+        if !self.is_entrant(user_id, &registration).await? {
+            return Err(AppError::Forbidden(
+                "Only the registered participants can check in".into(),
+            ));
+        }
+        let expires_at = match self
+            .window_repo
+            .find_by_category(registration.tournament_category_id)
+            .await?
+        {
+            Some(window) => window.closes_at,
+            None => Utc::now() + Duration::days(PASS_DAYS_WITHOUT_WINDOW),
+        };
+        Ok(Some((registration, expires_at)))
+    }
+
+    /// A participant checking in through the API while the window is open
+    pub async fn self_check_in(
+        &self,
+        user_id: Uuid,
+        registration_id: Uuid,
+    ) -> Result<Option<TournamentRegistration>, AppError> {
+        let Some(registration) = self.registration_repo.get_by_id(registration_id).await? else {
+            return Ok(None);
+        };
+        if !self.is_entrant(user_id, &registration).await? {
+            return Err(AppError::Forbidden(
+                "Only the registered participants can check in".into(),
+            ));
+        }
+        self.check_in(registration, CheckInMethod::SelfService, None)
+            .await
+    }
+
+    /// Check-in with a scanned QR code; the caller has already verified its token
+    pub async fn scan_check_in(
+        &self,
+        registration_id: Uuid,
+    ) -> Result<Option<TournamentRegistration>, AppError> {
+        let Some(registration) = self.registration_repo.get_by_id(registration_id).await? else {
+            return Ok(None);
+        };
+        self.check_in(registration, CheckInMethod::Qr, None).await
+    }
+
+    /// Desk staff scanning a registration's QR code: checks it in the way the desk would,
+    /// recorded as a QR check-in, and returns what the desk needs to see. The caller has
+    /// already verified the code's token.
+    pub async fn verify_registration(
+        &self,
+        staff_id: Uuid,
+        registration_id: Uuid,
+    ) -> Result<Option<RegistrationVerification>, AppError> {
+        let Some(registration) = self.registration_repo.get_by_id(registration_id).await? else {
+            return Ok(None);
+        };
+        let Some(category) = self
+            .category_repo
+            .get_by_id(registration.tournament_category_id)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let already_checked_in = registration.checked_in_at.is_some();
+        let Some(registration) = self
+            .check_in(registration, CheckInMethod::Qr, Some(staff_id))
+            .await?
+        else {
+            return Ok(None);
+        };
+        let details = self
+            .registration_repo
+            .get_by_tournament(category.tournament_id)
+            .await?
+            .into_iter()
+            .find(|d| d.id == registration_id);
+        Ok(Some(RegistrationVerification {
+            participant_name: details
+                .as_ref()
+                .and_then(RegistrationWithDetails::display_name)
+                .unwrap_or_else(|| "TBD".to_string()),
+            tournament_name: details
+                .as_ref()
+                .map(|d| d.tournament_name.clone())
+                .unwrap_or_default(),
+            category_name: category.name,
+            registration,
+            already_checked_in,
+        }))
+    }
+
+    /// Staff checking a participant in at the desk, which also works before the window opens
+    pub async fn desk_check_in(
+        &self,
+        staff_id: Uuid,
+        registration_id: Uuid,
+    ) -> Result<Option<TournamentRegistration>, AppError> {
+        let Some(registration) = self.registration_repo.get_by_id(registration_id).await? else {
+            return Ok(None);
+        };
+        self.check_in(registration, CheckInMethod::Desk, Some(staff_id))
+            .await
+    }
+
+    /// Takes back a check-in made in error; not possible once the window has closed
+    pub async fn undo_check_in(
+        &self,
+        registration_id: Uuid,
+    ) -> Result<Option<TournamentRegistration>, AppError> {
+        let Some(registration) = self.registration_repo.get_by_id(registration_id).await? else {
+            return Ok(None);
+        };
+        let window = self
+            .window_repo
+            .find_by_category(registration.tournament_category_id)
+            .await?;
+        if window.as_ref().is_some_and(CheckInWindow::is_closed) {
+            return Err(AppError::ValidationError(
+                "Check-in for this category has closed".into(),
+            ));
+        }
+        let registration = self
+            .registration_repo
+            .record_check_in(registration_id, None)
+            .await?;
+        if let Some(registration) = &registration {
+            self.publish_registration_update(registration, "check_in_undone")
+                .await;
+        }
+        Ok(registration)
+    }
+
+    async fn check_in(
+        &self,
+        registration: TournamentRegistration,
+        method: CheckInMethod,
+        by: Option<Uuid>,
+    ) -> Result<Option<TournamentRegistration>, AppError> {
+        if !matches!(
+            registration.registration_status,
+            RegistrationStatus::Approved | RegistrationStatus::Waitlisted
+        ) {
+            return Err(AppError::ValidationError(
+                "Only approved or waitlisted registrations can check in".into(),
+            ));
+        }
+        let now = Utc::now();
+        let window = self
+            .window_repo
+            .find_by_category(registration.tournament_category_id)
+            .await?;
+        // Staff can check people in before the window opens; participants cannot
+        let staffed = by.is_some();
+        let allowed = match &window {
+            Some(window) if staffed => !window.is_closed(),
+            Some(window) => window.is_open(now),
+            None => staffed,
+        };
+        if !allowed {
+            return Err(AppError::ValidationError(
+                "Check-in is not open for this category".into(),
+            ));
+        }
+        if registration.checked_in_at.is_some() {
+            return Ok(Some(registration));
+        }
+
+        let check_in = CheckIn {
+            at: now,
+            method,
+            by,
+        };
+        let registration = self
+            .registration_repo
+            .record_check_in(registration.id, Some(check_in))
+            .await?;
+        if let Some(registration) = &registration {
+            self.publish_registration_update(registration, "checked_in")
+                .await;
+        }
+        Ok(registration)
+    }
+
+    /// Whether the user plays for the registration: as its player or partner, or as a
+    /// member of its team
+    async fn is_entrant(
+        &self,
+        user_id: Uuid,
+        registration: &TournamentRegistration,
+    ) -> Result<bool, AppError> {
+        let Some(player) = self.player_repo.find_by_user_id(user_id).await? else {
+            return Ok(false);
+        };
+        if registration.player_id == Some(player.id)
+            || registration.partner_player_id == Some(player.id)
+        {
+            return Ok(true);
+        }
+        match registration.team_id {
+            Some(team_id) => Ok(self
+                .team_member_repo
+                .get_by_id(team_id, player.id)
+                .await?
+                .is_some()),
+            None => Ok(false),
+        }
+    }
+
+    // ==================== Closing ====================
+
+    /// Closes the category's window now: approved entries that never checked in are
+    /// withdrawn or waitlisted, and waitlisted entries that did check in take their places.
+    /// Returns `None` when the category has no window.
+    pub async fn close_window(
+        &self,
+        category_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<Option<WindowClosure>, AppError> {
+        let work = self.uow.begin().await?;
+        let Some(mut window) = work
+            .check_in_windows()
+            .find_by_category(category_id)
+            .await?
+        else {
+            work.rollback().await?;
+            return Ok(None);
+        };
+        if window.is_closed() {
+            work.rollback().await?;
+            return Err(AppError::Conflict(
+                "Check-in for this category has already closed".into(),
+            ));
+        }
+        let category = work.categories().get_by_id(category_id).await?;
+        let Some(category) = category else {
+            work.rollback().await?;
+            return Ok(None);
+        };
+        let registrations = work
+            .registrations()
+            .get_by_tournament_category(category_id)
+            .await?;
+        let closure = plan_closure(&window, &registrations, category.max_participants);
+
+        let no_show_status = match window.no_show_action {
+            NoShowAction::Withdraw => RegistrationStatus::Withdrawn,
+            NoShowAction::Waitlist => RegistrationStatus::Waitlisted,
+        };
+        let mut changed = Vec::new();
+        for id in &closure.no_shows {
+            let update = work
+                .registrations()
+                .update(*id, with_status(no_show_status));
+            changed.extend(update.await?);
+        }
+        for id in &closure.promoted {
+            let update = work
+                .registrations()
+                .update(*id, with_status(RegistrationStatus::Approved));
+            if let Some(registration) = update.await? {
+                work.outbox()
+                    .enqueue(&DomainEvent::registration_approved(
+                        &registration,
+                        Some(category.tournament_id),
+                    ))
+                    .await?;
+                changed.push(registration);
+            }
+        }
+        window.closed_at = Some(now);
+        work.check_in_windows().save(window).await?;
+        work.commit().await?;
+
+        for registration in &changed {
+            let status = format!("{:?}", registration.registration_status);
+            self.publish_registration_update(registration, &status)
+                .await;
+        }
+        Ok(Some(closure))
+    }
+
+    /// Closes every window whose time ran out at `now`; returns how many were closed
+    pub async fn close_due_windows(&self, now: DateTime<Utc>) -> Result<usize, AppError> {
+        let mut closed = 0;
+        for window in self.window_repo.find_due(now).await? {
+            if self.close_window(window.category_id, now).await?.is_some() {
+                closed += 1;
+            }
+        }
+        Ok(closed)
+    }
+
+    /// Closes due windows on every poll until the process exits
+    pub async fn run(self: Arc<Self>, config: CheckInConfig) {
+        let mut interval = tokio::time::interval(config.poll_interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.close_due_windows(Utc::now()).await {
+                eprintln!("Closing check-in windows failed: {}", e);
+            }
+        }
+    }
+
+    // ==================== Events ====================
+
+    async fn publish_registration_update(
+        &self,
+        registration: &TournamentRegistration,
+        status: &str,
+    ) {
+        let tournament_id = match self
+            .category_repo
+            .get_by_id(registration.tournament_category_id)
+            .await
+        {
+            Ok(category) => category.map(|c| c.tournament_id),
+            Err(_) => None,
+        };
+        self.events
+            .publish(RealtimeEvent::RegistrationUpdate {
+                registration_id: registration.id,
+                tournament_id,
+                category_id: registration.tournament_category_id,
+                status: Some(status.to_string()),
+            })
+            .await;
+    }
+}
@@ -0,0 +1,422 @@
+use chrono::{DateTime, Duration, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::match_services::record_forfeit;
+use crate::domain::court_queue::{
+    queue_window, split_queue, CallRequest, CallStatus, CourtCall, CourtCallRepository, CourtQueue,
+    DeskCourt, QueuedMatch,
+};
+use crate::domain::match_domain::{Forfeit, ForfeitKind, Match, MatchRepository, MatchStatus};
+use crate::domain::outbox::DomainEvent;
+use crate::domain::realtime::{EventPublisher, RealtimeEvent};
+use crate::domain::tournament::TournamentCategoryRepository;
+use crate::domain::unit_of_work::UnitOfWorkFactory;
+use crate::domain::venue::{Court, Venue, VenueRepository};
+use crate::shared::config::CourtCallConfig;
+use crate::shared::AppError;
+
+const DEFAULT_RESPONSE_MINUTES: i64 = 10;
+const DEFAULT_MAX_CALLS: i32 = 3;
+
+/// What is playing and next up on a court, as a realtime event
+pub(crate) async fn court_queue_update<M: MatchRepository + ?Sized>(
+    match_repo: &M,
+    court_id: Uuid,
+    tournament_id: Option<Uuid>,
+) -> Result<RealtimeEvent, AppError> {
+    let (from, until) = queue_window(Utc::now());
+    let (current, queue) = split_queue(match_repo.find_by_court(court_id, from, until).await?);
+    Ok(RealtimeEvent::CourtQueueUpdate {
+        court_id,
+        tournament_id,
+        current_match_id: current.map(|m| m.id),
+        next_match_id: queue.first().map(|m| m.id),
+    })
+}
+
+/// Tournament-day court management: per-court queues, calls to court and no-show handling
+pub struct CourtQueueServices<M, K, C, V, U>
+where
+    M: MatchRepository + ?Sized,
+    K: CourtCallRepository + ?Sized,
+    C: TournamentCategoryRepository + ?Sized,
+    V: VenueRepository + ?Sized,
+    U: UnitOfWorkFactory + ?Sized,
+{
+    match_repo: Arc<M>,
+    call_repo: Arc<K>,
+    category_repo: Arc<C>,
+    venue_repo: Arc<V>,
+    uow: Arc<U>,
+    events: Arc<dyn EventPublisher>,
+}
+
+impl<M, K, C, V, U> CourtQueueServices<M, K, C, V, U>
+where
+    M: MatchRepository + ?Sized,
+    K: CourtCallRepository + ?Sized,
+    C: TournamentCategoryRepository + ?Sized,
+    V: VenueRepository + ?Sized,
+    U: UnitOfWorkFactory + ?Sized,
+{
+    pub fn new(
+        match_repo: Arc<M>,
+        call_repo: Arc<K>,
+        category_repo: Arc<C>,
+        venue_repo: Arc<V>,
+        uow: Arc<U>,
+        events: Arc<dyn EventPublisher>,
+    ) -> Self {
+        Self {
+            match_repo,
+            call_repo,
+            category_repo,
+            venue_repo,
+            uow,
+            events,
+        }
+    }
+
+    // ==================== Queues ====================
+
+    pub async fn get_court_queue(&self, court_id: Uuid) -> Result<Option<CourtQueue>, AppError> {
+        let Some(court) = self.venue_repo.find_court(court_id).await? else {
+            return Ok(None);
+        };
+        let venue = self.venue_of(&court).await?;
+        Ok(Some(self.build_queue(&venue, &court).await?))
+    }
+
+    /// Current and next match of every active court at the tournament's venues
+    pub async fn get_desk(&self, tournament_id: Uuid) -> Result<Vec<DeskCourt>, AppError> {
+        let mut desk = Vec::new();
+        for venue in self.venue_repo.find_by_tournament(tournament_id).await? {
+            for court in self.venue_repo.find_courts(venue.id).await? {
+                if court.is_active {
+                    desk.push(DeskCourt::from(self.build_queue(&venue, &court).await?));
+                }
+            }
+        }
+        Ok(desk)
+    }
+
+    async fn venue_of(&self, court: &Court) -> Result<Venue, AppError> {
+        self.venue_repo
+            .find_by_id(court.venue_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Venue {} not found", court.venue_id)))
+    }
+
+    async fn build_queue(&self, venue: &Venue, court: &Court) -> Result<CourtQueue, AppError> {
+        let (from, until) = queue_window(Utc::now());
+        let (current, queue) =
+            split_queue(self.match_repo.find_by_court(court.id, from, until).await?);
+        let current = match current {
+            Some(m) => Some(self.queued(&m).await?),
+            None => None,
+        };
+        let mut queued = Vec::with_capacity(queue.len());
+        for m in &queue {
+            queued.push(self.queued(m).await?);
+        }
+        Ok(CourtQueue {
+            court_id: court.id,
+            court_name: court.name.clone(),
+            venue_id: venue.id,
+            venue_name: venue.name.clone(),
+            current,
+            queue: queued,
+        })
+    }
+
+    async fn queued(&self, m: &Match) -> Result<QueuedMatch, AppError> {
+        let details = self
+            .match_repo
+            .find_with_participants(m.id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Match {} not found", m.id)))?;
+        let call = if m.match_status == MatchStatus::Scheduled {
+            self.call_repo
+                .find_by_match(m.id)
+                .await?
+                .filter(CourtCall::is_open)
+        } else {
+            None
+        };
+        Ok(QueuedMatch { details, call })
+    }
+
+    // ==================== Calls ====================
+
+    /// Calls the participants of a scheduled match to its court, or calls them again if
+    /// they were already called
+    pub async fn call_to_court(
+        &self,
+        match_id: Uuid,
+        request: CallRequest,
+    ) -> Result<Option<CourtCall>, AppError> {
+        let respond_within = request
+            .respond_within_minutes
+            .unwrap_or(DEFAULT_RESPONSE_MINUTES);
+        let max_calls = request.max_calls.unwrap_or(DEFAULT_MAX_CALLS);
+        if !(1..=120).contains(&respond_within) || !(1..=10).contains(&max_calls) {
+            return Err(AppError::ValidationError(
+                "respond_within_minutes must be 1 to 120 and max_calls 1 to 10".into(),
+            ));
+        }
+        let Some(m) = self.match_repo.find_by_id(match_id).await? else {
+            return Ok(None);
+        };
+        if m.match_status != MatchStatus::Scheduled {
+            return Err(AppError::ValidationError(
+                "Only scheduled matches can be called to court".into(),
+            ));
+        }
+        let court_id = m.court_id.ok_or_else(|| {
+            AppError::ValidationError("Assign the match to a court before calling it".into())
+        })?;
+        let court = self
+            .venue_repo
+            .find_court(court_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Court {} not found", court_id)))?;
+        let venue = self.venue_of(&court).await?;
+        let (from, until) = queue_window(Utc::now());
+        let (current, _) = split_queue(self.match_repo.find_by_court(court_id, from, until).await?);
+        if let Some(current) = current {
+            return Err(AppError::Conflict(format!(
+                "{} is still in use by match {}",
+                court.name, current.id
+            )));
+        }
+
+        let now = Utc::now();
+        let call = match self
+            .call_repo
+            .find_by_match(match_id)
+            .await?
+            .filter(|c| c.is_open() && c.court_id == court_id)
+        {
+            Some(mut call) => {
+                call.repeat(now);
+                if request.respond_within_minutes.is_some() {
+                    call.respond_by = now + Duration::minutes(respond_within);
+                }
+                if request.max_calls.is_some() {
+                    call.max_calls = max_calls.max(call.call_count);
+                }
+                call
+            }
+            None => CourtCall::new(
+                match_id,
+                court_id,
+                max_calls,
+                now,
+                now + Duration::minutes(respond_within),
+            ),
+        };
+        let call = self.announce(&m, call, (venue.name, court.name)).await?;
+        Ok(Some(call))
+    }
+
+    /// Saves a call and notifies the participants in one transaction
+    async fn announce(
+        &self,
+        m: &Match,
+        call: CourtCall,
+        location: (String, String),
+    ) -> Result<CourtCall, AppError> {
+        let tournament_id = self.tournament_of(m.tournament_category_id).await?;
+        let work = self.uow.begin().await?;
+        let call = work.court_calls().save(call).await?;
+        work.outbox()
+            .enqueue(&DomainEvent::match_called(
+                m,
+                tournament_id,
+                location,
+                &call,
+                call.is_final(),
+            ))
+            .await?;
+        work.commit().await?;
+        self.publish_call(m, tournament_id, &call).await;
+        Ok(call)
+    }
+
+    /// Records that side 1 or 2 reported to the court
+    pub async fn mark_present(
+        &self,
+        match_id: Uuid,
+        participant: i32,
+    ) -> Result<Option<CourtCall>, AppError> {
+        if !(1..=2).contains(&participant) {
+            return Err(AppError::ValidationError(
+                "participant must be 1 or 2".into(),
+            ));
+        }
+        let Some(m) = self.match_repo.find_by_id(match_id).await? else {
+            return Ok(None);
+        };
+        let mut call = self
+            .call_repo
+            .find_by_match(match_id)
+            .await?
+            .filter(CourtCall::is_open)
+            .ok_or_else(|| AppError::NotFound("The match has not been called".into()))?;
+        call.mark_present(participant, Utc::now());
+        let call = self.call_repo.save(call).await?;
+        let tournament_id = self.tournament_of(m.tournament_category_id).await?;
+        self.publish_call(&m, tournament_id, &call).await;
+        Ok(Some(call))
+    }
+
+    /// Withdraws an open call, e.g. one made by mistake
+    pub async fn withdraw_call(&self, match_id: Uuid) -> Result<Option<CourtCall>, AppError> {
+        let Some(m) = self.match_repo.find_by_id(match_id).await? else {
+            return Ok(None);
+        };
+        let Some(mut call) = self
+            .call_repo
+            .find_by_match(match_id)
+            .await?
+            .filter(CourtCall::is_open)
+        else {
+            return Ok(None);
+        };
+        call.status = CallStatus::Closed;
+        let call = self.call_repo.save(call).await?;
+        let tournament_id = self.tournament_of(m.tournament_category_id).await?;
+        self.publish_call(&m, tournament_id, &call).await;
+        Ok(Some(call))
+    }
+
+    // ==================== No-shows ====================
+
+    /// Handles the calls that ran out at `now` without everyone reporting: calls again
+    /// until the call limit, then forfeits the match to the side that did report, or to
+    /// neither. Returns how many calls were escalated.
+    pub async fn escalate_no_shows(&self, now: DateTime<Utc>) -> Result<usize, AppError> {
+        let mut escalated = 0;
+        for call in self.call_repo.find_expired(now).await? {
+            // One bad call must not hold up every other court's
+            let match_id = call.match_id;
+            match self.escalate(call, now).await {
+                Ok(true) => escalated += 1,
+                Ok(false) => {}
+                Err(e) => eprintln!("No-show check for match {} failed: {}", match_id, e),
+            }
+        }
+        Ok(escalated)
+    }
+
+    /// Calls again or forfeits; false when the call was closed instead
+    async fn escalate(&self, mut call: CourtCall, now: DateTime<Utc>) -> Result<bool, AppError> {
+        let m = self
+            .match_repo
+            .find_by_id(call.match_id)
+            .await?
+            .filter(|m| {
+                m.match_status == MatchStatus::Scheduled && m.court_id == Some(call.court_id)
+            });
+        let court = match &m {
+            Some(_) => self.venue_repo.find_court(call.court_id).await?,
+            None => None,
+        };
+        let (Some(m), Some(court)) = (m, court) else {
+            // Started, finished or moved since it was called, or the court is gone
+            call.status = CallStatus::Closed;
+            self.call_repo.save(call).await?;
+            return Ok(false);
+        };
+
+        if !call.is_final() {
+            let venue = self.venue_of(&court).await?;
+            call.repeat(now);
+            self.announce(&m, call, (venue.name, court.name)).await?;
+        } else {
+            self.forfeit(&m, call).await?;
+        }
+        Ok(true)
+    }
+
+    async fn forfeit(&self, m: &Match, mut call: CourtCall) -> Result<(), AppError> {
+        let reason = match call.present_side() {
+            Some(side) => format!(
+                "participant {} did not report to court after {} calls",
+                3 - side,
+                call.call_count
+            ),
+            None => format!(
+                "neither participant reported to court after {} calls",
+                call.call_count
+            ),
+        };
+        let forfeit = Forfeit {
+            kind: ForfeitKind::Walkover,
+            defaulting_participant: call.present_side().map(|side| 3 - side),
+            reason,
+        };
+        call.status = CallStatus::Forfeited;
+        let work = self.uow.begin().await?;
+        let recorded = record_forfeit(work.as_ref(), m, &forfeit).await?;
+        let call = work.court_calls().save(call).await?;
+        work.commit().await?;
+
+        let tournament_id = self.tournament_of(m.tournament_category_id).await?;
+        self.publish_call(m, tournament_id, &call).await;
+        if let Some(recorded) = recorded {
+            let forfeited = recorded.m;
+            self.events
+                .publish(RealtimeEvent::MatchUpdate {
+                    match_id: forfeited.id,
+                    tournament_id,
+                    category_id: Some(forfeited.tournament_category_id),
+                    status: Some(format!("{:?}", forfeited.match_status)),
+                })
+                .await;
+            if let Some(bracket) = recorded.bracket {
+                self.events
+                    .publish(RealtimeEvent::BracketUpdate {
+                        tournament_id: bracket.tournament_id,
+                        category_id: bracket.category_id,
+                    })
+                    .await;
+            }
+        }
+        let update = court_queue_update(self.match_repo.as_ref(), call.court_id, tournament_id);
+        self.events.publish(update.await?).await;
+        Ok(())
+    }
+
+    /// Escalates no-shows on every poll until the process exits
+    pub async fn run(self: Arc<Self>, config: CourtCallConfig) {
+        let mut interval = tokio::time::interval(config.poll_interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.escalate_no_shows(Utc::now()).await {
+                eprintln!("No-show check failed: {}", e);
+            }
+        }
+    }
+
+    // ==================== Events ====================
+
+    async fn tournament_of(&self, category_id: Uuid) -> Result<Option<Uuid>, AppError> {
+        Ok(self
+            .category_repo
+            .get_by_id(category_id)
+            .await?
+            .map(|c| c.tournament_id))
+    }
+
+    async fn publish_call(&self, m: &Match, tournament_id: Option<Uuid>, call: &CourtCall) {
+        self.events
+            .publish(RealtimeEvent::CourtCall {
+                tournament_id,
+                category_id: m.tournament_category_id,
+                call: call.clone(),
+            })
+            .await;
+    }
+}
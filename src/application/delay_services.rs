@@ -0,0 +1,283 @@
+use chrono::{DateTime, Duration, Utc};
+use chrono_tz::Tz;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::NotificationServices;
+use crate::domain::match_domain::{MatchRepository, MatchScheduleItem};
+use crate::domain::notification::{
+    NewNotification, NotificationDeliveryRepository, NotificationPreferences,
+    NotificationRepository, NotificationType, PushSubscriptionRepository, SubscriptionPreferences,
+};
+use crate::domain::scheduling::{
+    estimate_starts, estimated_duration, historical_duration, StartEstimate,
+};
+use crate::domain::tournament::{TournamentCategoryRepository, TournamentRepository};
+use crate::domain::unit_of_work::UnitOfWorkFactory;
+use crate::domain::user::UserProfileRepository;
+use crate::shared::config::DelayConfig;
+use crate::shared::timezone::stored_timezone;
+use crate::shared::AppError;
+
+/// How long before now a delayed match may have been scheduled and still not started
+const DELAY_LOOKBACK_HOURS: i64 = 12;
+/// How far ahead delays are announced
+const DELAY_HORIZON_HOURS: i64 = 24;
+
+/// Estimated starts for a tournament's matches, keyed by match. Each category's matches
+/// are expected to last as long as its finished ones have, once there are enough of them
+/// to go on, and as configured until then.
+pub(crate) async fn tournament_estimates<M, C, T>(
+    match_repo: &M,
+    category_repo: &C,
+    tournament_repo: &T,
+    tournament_id: Uuid,
+    now: DateTime<Utc>,
+) -> Result<HashMap<Uuid, StartEstimate>, AppError>
+where
+    M: MatchRepository + ?Sized,
+    C: TournamentCategoryRepository + ?Sized,
+    T: TournamentRepository + ?Sized,
+{
+    let Some(tournament) = tournament_repo.get_by_id(tournament_id).await? else {
+        return Ok(HashMap::new());
+    };
+    let matches = match_repo.find_by_tournament(tournament_id).await?;
+    let durations: HashMap<Uuid, Duration> = category_repo
+        .get_by_tournament(tournament_id)
+        .await?
+        .iter()
+        .map(|category| {
+            let played = matches
+                .iter()
+                .filter(|m| m.tournament_category_id == category.id);
+            let duration = historical_duration(played)
+                .unwrap_or_else(|| estimated_duration(tournament.sport_type, Some(category)));
+            (category.id, duration)
+        })
+        .collect();
+    let fallback = estimated_duration(tournament.sport_type, None);
+
+    Ok(estimate_starts(
+        &matches,
+        |m| {
+            durations
+                .get(&m.tournament_category_id)
+                .copied()
+                .unwrap_or(fallback)
+        },
+        now,
+    )
+    .into_iter()
+    .map(|estimate| (estimate.match_id, estimate))
+    .collect())
+}
+
+/// Fills in `estimated_start_date` and `delay_minutes` on schedule items
+pub(crate) async fn with_estimates<M, C, T>(
+    match_repo: &M,
+    category_repo: &C,
+    tournament_repo: &T,
+    mut items: Vec<MatchScheduleItem>,
+    now: DateTime<Utc>,
+) -> Result<Vec<MatchScheduleItem>, AppError>
+where
+    M: MatchRepository + ?Sized,
+    C: TournamentCategoryRepository + ?Sized,
+    T: TournamentRepository + ?Sized,
+{
+    let tournaments: BTreeSet<Uuid> = items.iter().map(|item| item.tournament_id).collect();
+    let mut estimates = HashMap::new();
+    for tournament_id in tournaments {
+        estimates.extend(
+            tournament_estimates(
+                match_repo,
+                category_repo,
+                tournament_repo,
+                tournament_id,
+                now,
+            )
+            .await?,
+        );
+    }
+    for item in &mut items {
+        if let Some(estimate) = estimates.get(&item.id) {
+            item.estimated_start_date = Some(estimate.estimated_start_date);
+            item.delay_minutes = Some(estimate.delay_minutes);
+        }
+    }
+    Ok(items)
+}
+
+/// Tells players, team members and subscribers when a match they are waiting for has
+/// slipped by the configured threshold, and again each time the slip grows by another
+/// threshold's worth.
+pub struct DelayNotifier<M, C, T, F, N, D, S, U>
+where
+    M: MatchRepository + ?Sized,
+    C: TournamentCategoryRepository + ?Sized,
+    T: TournamentRepository + ?Sized,
+    F: UserProfileRepository + ?Sized,
+    N: NotificationRepository + ?Sized,
+    D: NotificationDeliveryRepository + ?Sized,
+    S: PushSubscriptionRepository + ?Sized,
+    U: UnitOfWorkFactory + ?Sized,
+{
+    match_repo: Arc<M>,
+    category_repo: Arc<C>,
+    tournament_repo: Arc<T>,
+    profile_repo: Arc<F>,
+    notifications: Arc<NotificationServices<N, D, S, F, U>>,
+    config: DelayConfig,
+}
+
+impl<M, C, T, F, N, D, S, U> DelayNotifier<M, C, T, F, N, D, S, U>
+where
+    M: MatchRepository + ?Sized,
+    C: TournamentCategoryRepository + ?Sized,
+    T: TournamentRepository + ?Sized,
+    F: UserProfileRepository + ?Sized,
+    N: NotificationRepository + ?Sized,
+    D: NotificationDeliveryRepository + ?Sized,
+    S: PushSubscriptionRepository + ?Sized,
+    U: UnitOfWorkFactory + ?Sized,
+{
+    pub fn new(
+        match_repo: Arc<M>,
+        category_repo: Arc<C>,
+        tournament_repo: Arc<T>,
+        profile_repo: Arc<F>,
+        notifications: Arc<NotificationServices<N, D, S, F, U>>,
+        config: DelayConfig,
+    ) -> Self {
+        Self {
+            match_repo,
+            category_repo,
+            tournament_repo,
+            profile_repo,
+            notifications,
+            config,
+        }
+    }
+
+    /// Sends the delay notices due at `now`; returns how many went out. Users inside
+    /// their quiet hours are left for a later scan.
+    pub async fn scan(&self, now: DateTime<Utc>) -> Result<usize, AppError> {
+        let threshold = (self.config.threshold.as_secs() / 60).max(1) as i64;
+        let waiting = self
+            .match_repo
+            .find_scheduled_between(
+                now - Duration::hours(DELAY_LOOKBACK_HOURS),
+                now + Duration::hours(DELAY_HORIZON_HOURS),
+            )
+            .await?;
+        let delayed = with_estimates(
+            &*self.match_repo,
+            &*self.category_repo,
+            &*self.tournament_repo,
+            waiting,
+            now,
+        )
+        .await?;
+
+        let mut sent = 0;
+        for item in &delayed {
+            let (Some(estimated), Some(delay)) = (item.estimated_start_date, item.delay_minutes)
+            else {
+                continue;
+            };
+            if delay < threshold {
+                continue;
+            }
+            // A rescheduled match, or a further slip past the next step, is announced afresh
+            let key = format!(
+                "delay:{}:{}:{}",
+                item.id,
+                item.scheduled_date.timestamp(),
+                delay / threshold
+            );
+
+            let mut audience = self.match_repo.find_participant_users(item.id).await?;
+            audience.extend(
+                self.match_repo
+                    .find_subscriptions(item.id)
+                    .await?
+                    .into_iter()
+                    .filter(|s| {
+                        SubscriptionPreferences::from_value(&s.notification_preferences)
+                            .wants(&NotificationType::ScheduleDelay)
+                    })
+                    .map(|s| s.user_id),
+            );
+            audience.sort();
+            audience.dedup();
+
+            for user_id in audience {
+                let profile = self.profile_repo.find_by_user_id(user_id).await?;
+                let timezone = profile
+                    .as_ref()
+                    .and_then(|p| p.timezone.as_deref())
+                    .map(stored_timezone)
+                    .unwrap_or(Tz::UTC);
+                let preferences = NotificationPreferences::from_value(
+                    profile
+                        .as_ref()
+                        .and_then(|p| p.notification_preferences.as_ref()),
+                );
+                if preferences
+                    .quiet_hours
+                    .is_some_and(|quiet| quiet.contains(now.with_timezone(&timezone).time()))
+                {
+                    continue;
+                }
+
+                let notice = NewNotification {
+                    user_id,
+                    title: format!(
+                        "Running late: {} vs {}",
+                        item.participant1_name, item.participant2_name
+                    ),
+                    message: format!(
+                        "{} - {} is now expected to start around {} instead of {} ({} min late).",
+                        item.tournament_name,
+                        item.category_name,
+                        local_time(estimated, timezone),
+                        local_time(item.scheduled_date, timezone),
+                        delay
+                    ),
+                    notification_type: NotificationType::ScheduleDelay,
+                    tournament_id: Some(item.tournament_id),
+                    match_id: Some(item.id),
+                };
+                if self
+                    .notifications
+                    .send_notification_once(notice, std::slice::from_ref(&key))
+                    .await?
+                    .is_some()
+                {
+                    sent += 1;
+                }
+            }
+        }
+        Ok(sent)
+    }
+
+    /// Scans on every poll until the process exits
+    pub async fn run(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(self.config.poll_interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.scan(Utc::now()).await {
+                eprintln!("Delay scan failed: {}", e);
+            }
+        }
+    }
+}
+
+/// e.g. "Sat 14 Mar 10:30 CET"
+fn local_time(at: DateTime<Utc>, timezone: Tz) -> String {
+    at.with_timezone(&timezone)
+        .format("%a %-d %b %H:%M %Z")
+        .to_string()
+}
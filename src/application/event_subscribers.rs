@@ -0,0 +1,548 @@
+use async_trait::async_trait;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::NotificationServices;
+use crate::domain::notification::{
+    push_payload, render_email, supports_push, DeliveryAttempt, DeliveryChannel, DeliveryStatus,
+    EmailSender, NewNotification, NotificationDeliveryRepository, NotificationPreferences,
+    NotificationRepository, NotificationType, PushOutcome, PushSender, PushSubscriptionRepository,
+};
+use crate::domain::outbox::{DomainEvent, DomainEventSubscriber};
+use crate::domain::participant::{PlayerRepository, TeamMemberRepository};
+use crate::domain::realtime::{EventPublisher, RealtimeEvent};
+use crate::domain::unit_of_work::UnitOfWorkFactory;
+use crate::domain::user::{UserProfileRepository, UserRepository};
+use crate::shared::jwt::generate_unsubscribe_token;
+use crate::shared::AppError;
+
+/// Notifies the users behind the players and teams a domain event concerns
+pub struct NotificationSubscriber<N, D, S, F, U, P, M>
+where
+    N: NotificationRepository + ?Sized,
+    D: NotificationDeliveryRepository + ?Sized,
+    S: PushSubscriptionRepository + ?Sized,
+    F: UserProfileRepository + ?Sized,
+    U: UnitOfWorkFactory + ?Sized,
+    P: PlayerRepository + ?Sized,
+    M: TeamMemberRepository + ?Sized,
+{
+    notifications: Arc<NotificationServices<N, D, S, F, U>>,
+    player_repo: Arc<P>,
+    team_member_repo: Arc<M>,
+}
+
+impl<N, D, S, F, U, P, M> NotificationSubscriber<N, D, S, F, U, P, M>
+where
+    N: NotificationRepository + ?Sized,
+    D: NotificationDeliveryRepository + ?Sized,
+    S: PushSubscriptionRepository + ?Sized,
+    F: UserProfileRepository + ?Sized,
+    U: UnitOfWorkFactory + ?Sized,
+    P: PlayerRepository + ?Sized,
+    M: TeamMemberRepository + ?Sized,
+{
+    pub fn new(
+        notifications: Arc<NotificationServices<N, D, S, F, U>>,
+        player_repo: Arc<P>,
+        team_member_repo: Arc<M>,
+    ) -> Self {
+        Self {
+            notifications,
+            player_repo,
+            team_member_repo,
+        }
+    }
+
+    /// Accounts linked to the players and team members; players without one are skipped
+    async fn users_of(
+        &self,
+        team_ids: &[Uuid],
+        player_ids: &[Uuid],
+    ) -> Result<BTreeSet<Uuid>, AppError> {
+        let mut users = BTreeSet::new();
+        for player_id in player_ids {
+            if let Some(user_id) = self
+                .player_repo
+                .find_by_id(*player_id)
+                .await?
+                .and_then(|p| p.user_id)
+            {
+                users.insert(user_id);
+            }
+        }
+        for team_id in team_ids {
+            let members = self.team_member_repo.get_by_team(*team_id).await?;
+            users.extend(members.into_iter().filter_map(|m| m.user_id));
+        }
+        Ok(users)
+    }
+}
+
+#[async_trait]
+impl<N, D, S, F, U, P, M> DomainEventSubscriber for NotificationSubscriber<N, D, S, F, U, P, M>
+where
+    N: NotificationRepository + ?Sized,
+    D: NotificationDeliveryRepository + ?Sized,
+    S: PushSubscriptionRepository + ?Sized,
+    F: UserProfileRepository + ?Sized,
+    U: UnitOfWorkFactory + ?Sized,
+    P: PlayerRepository + ?Sized,
+    M: TeamMemberRepository + ?Sized,
+{
+    fn name(&self) -> &'static str {
+        "notifications"
+    }
+
+    async fn handle(&self, event: &DomainEvent) -> Result<(), AppError> {
+        let notifications = match event {
+            DomainEvent::MatchCompleted {
+                match_id,
+                tournament_id,
+                team_ids,
+                player_ids,
+                ..
+            } => self
+                .users_of(team_ids, player_ids)
+                .await?
+                .into_iter()
+                .map(|user_id| NewNotification {
+                    user_id,
+                    title: "Result posted".to_string(),
+                    message: "The result of your match has been posted.".to_string(),
+                    notification_type: NotificationType::ResultPosted,
+                    tournament_id: *tournament_id,
+                    match_id: Some(*match_id),
+                })
+                .collect(),
+            DomainEvent::RegistrationApproved {
+                tournament_id,
+                team_id,
+                player_ids,
+                ..
+            } => {
+                let team_ids: Vec<Uuid> = team_id.iter().copied().collect();
+                self.users_of(&team_ids, player_ids)
+                    .await?
+                    .into_iter()
+                    .map(|user_id| NewNotification {
+                        user_id,
+                        title: "Registration approved".to_string(),
+                        message: "Your tournament registration has been approved.".to_string(),
+                        notification_type: NotificationType::RegistrationConfirmed,
+                        tournament_id: *tournament_id,
+                        match_id: None,
+                    })
+                    .collect()
+            }
+            DomainEvent::PaymentSucceeded {
+                user_id,
+                tournament_id,
+                amount,
+                currency,
+                ..
+            } => vec![NewNotification {
+                user_id: *user_id,
+                title: "Payment received".to_string(),
+                message: format!("We received your payment of {} {}.", amount, currency),
+                notification_type: NotificationType::TournamentUpdate,
+                tournament_id: Some(*tournament_id),
+                match_id: None,
+            }],
+            DomainEvent::MatchCalled {
+                match_id,
+                tournament_id,
+                venue_name,
+                court_name,
+                call_number,
+                final_call,
+                respond_within_minutes,
+                team_ids,
+                player_ids,
+                ..
+            } => {
+                let title = match (*final_call, *call_number) {
+                    (true, _) => format!("Final call: {}", court_name),
+                    (false, 1) => format!("Please go to {}", court_name),
+                    (false, n) => format!("Call {}: {}", n, court_name),
+                };
+                let message = format!(
+                    "Your match is about to start. Please report to {} at {} within {} minutes.",
+                    court_name, venue_name, respond_within_minutes
+                );
+                self.users_of(team_ids, player_ids)
+                    .await?
+                    .into_iter()
+                    .map(|user_id| NewNotification {
+                        user_id,
+                        title: title.clone(),
+                        message: message.clone(),
+                        notification_type: NotificationType::CourtCall,
+                        tournament_id: *tournament_id,
+                        match_id: Some(*match_id),
+                    })
+                    .collect()
+            }
+            DomainEvent::NotificationCreated { .. } => Vec::new(),
+        };
+        if !notifications.is_empty() {
+            self.notifications
+                .send_bulk_notifications(notifications)
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+/// Tells live viewers to refresh standings once a match result is final
+pub struct StandingsSubscriber {
+    events: Arc<dyn EventPublisher>,
+}
+
+impl StandingsSubscriber {
+    pub fn new(events: Arc<dyn EventPublisher>) -> Self {
+        Self { events }
+    }
+}
+
+#[async_trait]
+impl DomainEventSubscriber for StandingsSubscriber {
+    fn name(&self) -> &'static str {
+        "standings"
+    }
+
+    async fn handle(&self, event: &DomainEvent) -> Result<(), AppError> {
+        if let DomainEvent::MatchCompleted {
+            tournament_id: Some(tournament_id),
+            category_id,
+            ..
+        } = event
+        {
+            self.events
+                .publish(RealtimeEvent::StandingsUpdate {
+                    tournament_id: *tournament_id,
+                    category_id: Some(*category_id),
+                })
+                .await;
+        }
+        Ok(())
+    }
+}
+
+/// Signs the one-click unsubscribe links put in notification emails
+#[derive(Debug, Clone)]
+pub struct UnsubscribeLinks {
+    /// Where the link points; the token is appended as `token=...`
+    pub url: String,
+    pub secret: String,
+}
+
+impl UnsubscribeLinks {
+    /// Link that stops emails of this type for the user
+    pub fn link(
+        &self,
+        user_id: Uuid,
+        notification_type: &NotificationType,
+    ) -> Result<String, AppError> {
+        let token =
+            generate_unsubscribe_token(&self.secret, user_id, Some(notification_type.key()))
+                .map_err(|e| {
+                    AppError::InternalError(format!("Failed to sign unsubscribe link: {}", e))
+                })?;
+        let separator = if self.url.contains('?') { '&' } else { '?' };
+        Ok(format!("{}{}token={}", self.url, separator, token))
+    }
+}
+
+/// Emails new notifications to users who have not turned email off for their type.
+/// Each attempt is recorded as the notification's email delivery; a failed send is
+/// returned as an error so the outbox retries it.
+pub struct EmailNotificationSubscriber<N, D, U, P>
+where
+    N: NotificationRepository + ?Sized,
+    D: NotificationDeliveryRepository + ?Sized,
+    U: UserRepository + ?Sized,
+    P: UserProfileRepository + ?Sized,
+{
+    notification_repo: Arc<N>,
+    delivery_repo: Arc<D>,
+    user_repo: Arc<U>,
+    profile_repo: Arc<P>,
+    sender: Arc<dyn EmailSender>,
+    unsubscribe: Option<UnsubscribeLinks>,
+}
+
+impl<N, D, U, P> EmailNotificationSubscriber<N, D, U, P>
+where
+    N: NotificationRepository + ?Sized,
+    D: NotificationDeliveryRepository + ?Sized,
+    U: UserRepository + ?Sized,
+    P: UserProfileRepository + ?Sized,
+{
+    pub fn new(
+        notification_repo: Arc<N>,
+        delivery_repo: Arc<D>,
+        user_repo: Arc<U>,
+        profile_repo: Arc<P>,
+        sender: Arc<dyn EmailSender>,
+        unsubscribe: Option<UnsubscribeLinks>,
+    ) -> Self {
+        Self {
+            notification_repo,
+            delivery_repo,
+            user_repo,
+            profile_repo,
+            sender,
+            unsubscribe,
+        }
+    }
+
+    async fn record(
+        &self,
+        notification_id: Uuid,
+        status: DeliveryStatus,
+        recipient: Option<String>,
+        error: Option<String>,
+    ) -> Result<(), AppError> {
+        self.delivery_repo
+            .record(DeliveryAttempt {
+                notification_id,
+                channel: DeliveryChannel::Email,
+                status,
+                recipient,
+                error,
+            })
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<N, D, U, P> DomainEventSubscriber for EmailNotificationSubscriber<N, D, U, P>
+where
+    N: NotificationRepository + ?Sized,
+    D: NotificationDeliveryRepository + ?Sized,
+    U: UserRepository + ?Sized,
+    P: UserProfileRepository + ?Sized,
+{
+    fn name(&self) -> &'static str {
+        "email"
+    }
+
+    async fn handle(&self, event: &DomainEvent) -> Result<(), AppError> {
+        let DomainEvent::NotificationCreated {
+            notification_id,
+            user_id,
+        } = event
+        else {
+            return Ok(());
+        };
+        let notification_id = *notification_id;
+        let existing = self
+            .delivery_repo
+            .find(notification_id, DeliveryChannel::Email)
+            .await?;
+        if existing.is_some_and(|d| d.status == DeliveryStatus::Sent) {
+            return Ok(());
+        }
+        // Deleted before it could be delivered
+        let Some(notification) = self.notification_repo.get_by_id(notification_id).await? else {
+            return Ok(());
+        };
+
+        let profile = self.profile_repo.find_by_user_id(*user_id).await?;
+        let preferences = NotificationPreferences::from_value(
+            profile
+                .as_ref()
+                .and_then(|p| p.notification_preferences.as_ref()),
+        );
+        let user = self.user_repo.find_by_id(*user_id).await?;
+        let user = match user {
+            Some(user)
+                if preferences.allows(
+                    DeliveryChannel::Email,
+                    &notification.notification_type,
+                    notification.tournament_id,
+                ) =>
+            {
+                user
+            }
+            _ => {
+                return self
+                    .record(notification_id, DeliveryStatus::Skipped, None, None)
+                    .await
+            }
+        };
+
+        let unsubscribe_url = match &self.unsubscribe {
+            Some(links) => Some(links.link(user.id, &notification.notification_type)?),
+            None => None,
+        };
+        let email = render_email(
+            &notification,
+            &user.email,
+            user.name.as_deref(),
+            profile.as_ref().and_then(|p| p.language.as_deref()),
+            unsubscribe_url.as_deref(),
+        );
+        match self.sender.send(&email).await {
+            Ok(()) => {
+                self.record(notification_id, DeliveryStatus::Sent, Some(email.to), None)
+                    .await
+            }
+            Err(e) => {
+                self.record(
+                    notification_id,
+                    DeliveryStatus::Failed,
+                    Some(email.to),
+                    Some(e.to_string()),
+                )
+                .await?;
+                Err(e)
+            }
+        }
+    }
+}
+
+/// Pushes match reminders and results to the user's subscribed browsers. Subscriptions
+/// the push service reports as gone are deleted; a send that fails on every browser is
+/// returned as an error so the outbox retries it.
+pub struct PushNotificationSubscriber<N, D, S, P>
+where
+    N: NotificationRepository + ?Sized,
+    D: NotificationDeliveryRepository + ?Sized,
+    S: PushSubscriptionRepository + ?Sized,
+    P: UserProfileRepository + ?Sized,
+{
+    notification_repo: Arc<N>,
+    delivery_repo: Arc<D>,
+    subscription_repo: Arc<S>,
+    profile_repo: Arc<P>,
+    sender: Arc<dyn PushSender>,
+}
+
+impl<N, D, S, P> PushNotificationSubscriber<N, D, S, P>
+where
+    N: NotificationRepository + ?Sized,
+    D: NotificationDeliveryRepository + ?Sized,
+    S: PushSubscriptionRepository + ?Sized,
+    P: UserProfileRepository + ?Sized,
+{
+    pub fn new(
+        notification_repo: Arc<N>,
+        delivery_repo: Arc<D>,
+        subscription_repo: Arc<S>,
+        profile_repo: Arc<P>,
+        sender: Arc<dyn PushSender>,
+    ) -> Self {
+        Self {
+            notification_repo,
+            delivery_repo,
+            subscription_repo,
+            profile_repo,
+            sender,
+        }
+    }
+
+    async fn record(
+        &self,
+        notification_id: Uuid,
+        status: DeliveryStatus,
+        error: Option<String>,
+    ) -> Result<(), AppError> {
+        self.delivery_repo
+            .record(DeliveryAttempt {
+                notification_id,
+                channel: DeliveryChannel::Push,
+                status,
+                recipient: None,
+                error,
+            })
+            .await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<N, D, S, P> DomainEventSubscriber for PushNotificationSubscriber<N, D, S, P>
+where
+    N: NotificationRepository + ?Sized,
+    D: NotificationDeliveryRepository + ?Sized,
+    S: PushSubscriptionRepository + ?Sized,
+    P: UserProfileRepository + ?Sized,
+{
+    fn name(&self) -> &'static str {
+        "push"
+    }
+
+    async fn handle(&self, event: &DomainEvent) -> Result<(), AppError> {
+        let DomainEvent::NotificationCreated {
+            notification_id,
+            user_id,
+        } = event
+        else {
+            return Ok(());
+        };
+        let notification_id = *notification_id;
+        let Some(notification) = self.notification_repo.get_by_id(notification_id).await? else {
+            return Ok(());
+        };
+        if !supports_push(&notification.notification_type) {
+            return Ok(());
+        }
+        let existing = self
+            .delivery_repo
+            .find(notification_id, DeliveryChannel::Push)
+            .await?;
+        if existing.is_some_and(|d| d.status == DeliveryStatus::Sent) {
+            return Ok(());
+        }
+
+        let profile = self.profile_repo.find_by_user_id(*user_id).await?;
+        let preferences = NotificationPreferences::from_value(
+            profile
+                .as_ref()
+                .and_then(|p| p.notification_preferences.as_ref()),
+        );
+        if !preferences.allows(
+            DeliveryChannel::Push,
+            &notification.notification_type,
+            notification.tournament_id,
+        ) {
+            return self
+                .record(notification_id, DeliveryStatus::Skipped, None)
+                .await;
+        }
+
+        let payload = push_payload(&notification);
+        let mut delivered = 0;
+        let mut errors = Vec::new();
+        for subscription in self.subscription_repo.find_by_user_id(*user_id).await? {
+            match self.sender.send(&subscription, &payload).await {
+                Ok(PushOutcome::Delivered) => delivered += 1,
+                Ok(PushOutcome::Gone) => {
+                    self.subscription_repo
+                        .delete_by_endpoint(&subscription.endpoint)
+                        .await?;
+                }
+                Err(e) => errors.push(e.to_string()),
+            }
+        }
+
+        let error = (!errors.is_empty()).then(|| errors.join("; "));
+        if delivered > 0 {
+            // Retrying would repeat the message on the browsers that got it
+            self.record(notification_id, DeliveryStatus::Sent, error)
+                .await
+        } else if let Some(error) = error {
+            self.record(notification_id, DeliveryStatus::Failed, Some(error.clone()))
+                .await?;
+            Err(AppError::InternalError(error))
+        } else {
+            // No browsers, or every subscription had expired
+            self.record(notification_id, DeliveryStatus::Skipped, None)
+                .await
+        }
+    }
+}
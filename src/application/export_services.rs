@@ -0,0 +1,866 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use chrono::NaiveDate;
+use chrono_tz::Tz;
+use serde_json::Value as JsonValue;
+
+use crate::domain::match_domain::{
+    Match, MatchRepository, MatchResult, MatchResultRepository, MatchScheduleItem,
+};
+use crate::domain::payment::{Payment, PaymentRepository};
+use crate::domain::tournament::{
+    bracket_nodes, BracketNode, ExportData, ExportEntity, ExportFile, ExportFormat, ExportRequest,
+    Tournament, TournamentBracketRepository, TournamentCategoryRepository,
+    TournamentRegistrationRepository, TournamentRepository, TournamentStandingsRepository,
+};
+use crate::shared::csv::{opt_cell, serde_label, CsvTable};
+use crate::shared::pdf::{BracketSlot, PdfBuilder, A4_LANDSCAPE, A4_PORTRAIT};
+use crate::shared::timezone::{parse_timezone, stored_timezone, to_local_rfc3339};
+use crate::shared::zip::ZipWriter;
+use crate::shared::AppError;
+
+const PAYMENT_PAGE_SIZE: i64 = 500;
+
+/// Minimum number of set columns printed on a scoresheet
+const SCORESHEET_SETS: usize = 5;
+
+const REGISTRATION_COLUMNS: &[&str] = &[
+    "id",
+    "tournament_name",
+    "category_name",
+    "player_name",
+    "partner_name",
+    "team_name",
+    "registration_status",
+    "payment_status",
+    "registration_date",
+];
+
+const MATCH_COLUMNS: &[&str] = &[
+    "id",
+    "category_id",
+    "round_number",
+    "match_number",
+    "match_type",
+    "match_status",
+    "scheduled_date",
+    "venue",
+    "court_number",
+    "participant1_name",
+    "participant2_name",
+    "participant1_sets_won",
+    "participant2_sets_won",
+    "set_scores",
+    "winner",
+    "is_draw",
+];
+
+const STANDINGS_COLUMNS: &[&str] = &[
+    "position",
+    "participant_id",
+    "participant_name",
+    "participant_type",
+    "category_id",
+    "points",
+    "matches_played",
+    "matches_won",
+    "matches_lost",
+    "matches_drawn",
+    "sets_won",
+    "sets_lost",
+    "games_won",
+    "games_lost",
+    "goal_difference",
+    "is_eliminated",
+];
+
+const PAYMENT_COLUMNS: &[&str] = &[
+    "id",
+    "user_id",
+    "amount",
+    "currency",
+    "payment_method",
+    "status",
+    "transaction_id",
+    "payment_provider",
+    "refunded_amount",
+    "failure_reason",
+    "created_at",
+    "processed_at",
+];
+
+/// Export services - renders tournament data as JSON, CSV, ZIP bundles or printable PDFs
+pub struct ExportServices<T, C, R, B, S, M, MR, P>
+where
+    T: TournamentRepository + ?Sized,
+    C: TournamentCategoryRepository + ?Sized,
+    R: TournamentRegistrationRepository + ?Sized,
+    B: TournamentBracketRepository + ?Sized,
+    S: TournamentStandingsRepository + ?Sized,
+    M: MatchRepository + ?Sized,
+    MR: MatchResultRepository + ?Sized,
+    P: PaymentRepository + ?Sized,
+{
+    tournament_repo: Arc<T>,
+    category_repo: Arc<C>,
+    registration_repo: Arc<R>,
+    bracket_repo: Arc<B>,
+    standings_repo: Arc<S>,
+    match_repo: Arc<M>,
+    result_repo: Arc<MR>,
+    payment_repo: Arc<P>,
+}
+
+impl<T, C, R, B, S, M, MR, P> ExportServices<T, C, R, B, S, M, MR, P>
+where
+    T: TournamentRepository + ?Sized,
+    C: TournamentCategoryRepository + ?Sized,
+    R: TournamentRegistrationRepository + ?Sized,
+    B: TournamentBracketRepository + ?Sized,
+    S: TournamentStandingsRepository + ?Sized,
+    M: MatchRepository + ?Sized,
+    MR: MatchResultRepository + ?Sized,
+    P: PaymentRepository + ?Sized,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        tournament_repo: Arc<T>,
+        category_repo: Arc<C>,
+        registration_repo: Arc<R>,
+        bracket_repo: Arc<B>,
+        standings_repo: Arc<S>,
+        match_repo: Arc<M>,
+        result_repo: Arc<MR>,
+        payment_repo: Arc<P>,
+    ) -> Self {
+        Self {
+            tournament_repo,
+            category_repo,
+            registration_repo,
+            bracket_repo,
+            standings_repo,
+            match_repo,
+            result_repo,
+            payment_repo,
+        }
+    }
+
+    /// Full JSON snapshot of a tournament with its categories and registrations.
+    pub async fn export_tournament_json(&self, id: Uuid) -> Result<ExportData, AppError> {
+        let tournament = self.get_tournament(id).await?;
+        let categories = self.category_repo.get_by_tournament(id).await?;
+        let registrations = self.registration_repo.get_by_tournament(id).await?;
+
+        let mut export_data = serde_json::Map::new();
+        export_data.insert("tournament".to_string(), to_json(&tournament)?);
+        export_data.insert("categories".to_string(), to_json(&categories)?);
+        export_data.insert("registrations".to_string(), to_json(&registrations)?);
+        export_data.insert("exported_at".to_string(), to_json(&chrono::Utc::now())?);
+
+        Ok(ExportData {
+            format: "json".to_string(),
+            data: JsonValue::Object(export_data),
+            filename: format!("{}_export.json", file_stem(&tournament.name)),
+            content_type: "application/json".to_string(),
+        })
+    }
+
+    /// Renders the requested entities as a single CSV, a ZIP of CSVs when several
+    /// entities are requested or `format=zip`, or one PDF with a section per entity.
+    /// Dates are written in the requested zone, else the tournament's.
+    pub async fn export_tournament(
+        &self,
+        id: Uuid,
+        request: ExportRequest,
+    ) -> Result<ExportFile, AppError> {
+        let tournament = self.get_tournament(id).await?;
+        let stem = file_stem(&tournament.name);
+        let tz = export_timezone(&tournament, request.timezone.as_deref())?;
+
+        let entities = if request.entities.is_empty() {
+            vec![ExportEntity::Registrations]
+        } else {
+            request.entities.clone()
+        };
+
+        let mut tables = Vec::with_capacity(entities.len());
+        for entity in &entities {
+            let table = self.build_table(&tournament, *entity, tz).await?;
+            let columns = columns_for(*entity, &request.columns, entities.len() == 1);
+            let table = if columns.is_empty() {
+                table
+            } else {
+                table.select_columns(&columns)?
+            };
+            tables.push((*entity, table));
+        }
+
+        match request.format {
+            ExportFormat::Csv if tables.len() == 1 => {
+                let (entity, table) = &tables[0];
+                Ok(ExportFile {
+                    filename: format!("{}_{}.csv", stem, entity.as_str()),
+                    content_type: "text/csv; charset=utf-8".to_string(),
+                    bytes: table.to_csv_string().into_bytes(),
+                })
+            }
+            ExportFormat::Csv | ExportFormat::Zip => {
+                let mut zip = ZipWriter::new();
+                for (entity, table) in &tables {
+                    zip.add_file(
+                        &format!("{}.csv", entity.as_str()),
+                        table.to_csv_string().as_bytes(),
+                    );
+                }
+                Ok(ExportFile {
+                    filename: format!("{}_export.zip", stem),
+                    content_type: "application/zip".to_string(),
+                    bytes: zip.finish(),
+                })
+            }
+            ExportFormat::Pdf => {
+                let mut pdf = PdfBuilder::new(&tournament.name, A4_LANDSCAPE);
+                pdf.heading(&tournament.name);
+                for (entity, table) in &tables {
+                    pdf.subheading(&title_case(entity.as_str()));
+                    pdf.table(table);
+                }
+                Ok(pdf_file(format!("{}_export.pdf", stem), pdf))
+            }
+            ExportFormat::Json => Err(AppError::BadRequest(
+                "JSON exports are served by export_tournament_json".to_string(),
+            )),
+        }
+    }
+
+    // ==================== Printable PDFs ====================
+
+    /// Bracket diagram for a category, drawn from `bracket_data` when it holds
+    /// bracket nodes, otherwise from the category's matches grouped by round.
+    pub async fn bracket_pdf(&self, category_id: Uuid) -> Result<ExportFile, AppError> {
+        let category = self
+            .category_repo
+            .get_by_id(category_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Category not found".into()))?;
+        let tournament = self.get_tournament(category.tournament_id).await?;
+
+        let nodes = self
+            .bracket_repo
+            .get_by_category_id(category_id)
+            .await?
+            .and_then(|b| b.bracket_data)
+            .map(|data| bracket_nodes(&data))
+            .unwrap_or_default();
+
+        let slots = if nodes.is_empty() {
+            self.bracket_slots_from_matches(category_id).await?
+        } else {
+            bracket_slots_from_nodes(&nodes)
+        };
+
+        let title = format!("{} - {} bracket", tournament.name, category.name);
+        let mut pdf = PdfBuilder::new(&title, A4_LANDSCAPE);
+        pdf.bracket(&title, &slots);
+        Ok(pdf_file(
+            format!(
+                "{}_{}_bracket.pdf",
+                file_stem(&tournament.name),
+                file_stem(&category.name)
+            ),
+            pdf,
+        ))
+    }
+
+    async fn bracket_slots_from_matches(
+        &self,
+        category_id: Uuid,
+    ) -> Result<Vec<BracketSlot>, AppError> {
+        let mut matches = self.match_repo.find_by_category(category_id).await?;
+        matches.sort_by_key(|m| (m.round_number, m.match_number, m.scheduled_date));
+
+        let mut positions: BTreeMap<i32, i32> = BTreeMap::new();
+        let mut slots = Vec::with_capacity(matches.len());
+        for m in matches {
+            let round = m.round_number.unwrap_or(1).max(1);
+            let position = positions.entry(round).or_insert(0);
+            let (top, bottom) = self.participant_names(&m).await?;
+            slots.push(BracketSlot {
+                round,
+                position: *position,
+                top,
+                bottom,
+                winner: m.winner_participant,
+            });
+            *position += 1;
+        }
+        Ok(slots)
+    }
+
+    /// Daily schedule grouped by court. Without a date, every scheduled day is printed.
+    /// Days and times are local to `timezone`, else to the tournament's zone.
+    pub async fn schedule_pdf(
+        &self,
+        tournament_id: Uuid,
+        date: Option<NaiveDate>,
+        timezone: Option<&str>,
+    ) -> Result<ExportFile, AppError> {
+        let tournament = self.get_tournament(tournament_id).await?;
+        let tz = export_timezone(&tournament, timezone)?;
+        let local_day =
+            |item: &MatchScheduleItem| item.scheduled_date.with_timezone(&tz).date_naive();
+        let items: Vec<MatchScheduleItem> = self
+            .match_repo
+            .find_schedule_by_tournament(tournament_id)
+            .await?
+            .into_iter()
+            .filter(|i| date.is_none_or(|d| local_day(i) == d))
+            .collect();
+
+        let mut days: BTreeMap<NaiveDate, BTreeMap<String, Vec<&MatchScheduleItem>>> =
+            BTreeMap::new();
+        for item in &items {
+            let court = item
+                .court_number
+                .clone()
+                .filter(|c| !c.trim().is_empty())
+                .unwrap_or_else(|| "Unassigned".to_string());
+            days.entry(local_day(item))
+                .or_default()
+                .entry(court)
+                .or_default()
+                .push(item);
+        }
+
+        let title = format!("{} - schedule", tournament.name);
+        let mut pdf = PdfBuilder::new(&title, A4_PORTRAIT);
+        if days.is_empty() {
+            pdf.heading(&title);
+            pdf.text_line("No matches scheduled.");
+        }
+        for (day, courts) in &days {
+            for (court, court_items) in courts {
+                pdf.new_page();
+                pdf.heading(&tournament.name);
+                pdf.subheading(&format!("{} - Court {}", day.format("%A %d %B %Y"), court));
+                let time = format!("Time ({})", tz.name());
+                let mut table =
+                    CsvTable::new(&[time.as_str(), "Category", "Round", "Match", "Status"]);
+                for item in court_items {
+                    table.push_row(vec![
+                        item.scheduled_date
+                            .with_timezone(&tz)
+                            .format("%H:%M")
+                            .to_string(),
+                        item.category_name.clone(),
+                        opt_cell(&item.round_number),
+                        format!("{} vs {}", item.participant1_name, item.participant2_name),
+                        serde_label(&item.match_status),
+                    ]);
+                }
+                pdf.table(&table);
+            }
+        }
+
+        let suffix = date.map(|d| format!("_{}", d)).unwrap_or_default();
+        Ok(pdf_file(
+            format!("{}_schedule{}.pdf", file_stem(&tournament.name), suffix),
+            pdf,
+        ))
+    }
+
+    /// Scoresheet for one match; `filled` prints recorded set scores.
+    pub async fn scoresheet_pdf(
+        &self,
+        match_id: Uuid,
+        filled: bool,
+    ) -> Result<ExportFile, AppError> {
+        let m = self
+            .match_repo
+            .find_by_id(match_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Match not found".into()))?;
+        let mut pdf = PdfBuilder::new("Scoresheet", A4_PORTRAIT);
+        self.render_scoresheet(&mut pdf, &m, filled).await?;
+        Ok(pdf_file(format!("scoresheet_{}.pdf", m.id), pdf))
+    }
+
+    /// Scoresheets for every match in a category, one page each.
+    pub async fn category_scoresheets_pdf(
+        &self,
+        category_id: Uuid,
+        filled: bool,
+    ) -> Result<ExportFile, AppError> {
+        let category = self
+            .category_repo
+            .get_by_id(category_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Category not found".into()))?;
+        let mut matches = self.match_repo.find_by_category(category_id).await?;
+        matches.sort_by_key(|m| (m.round_number, m.match_number, m.scheduled_date));
+
+        let mut pdf = PdfBuilder::new(&format!("{} scoresheets", category.name), A4_PORTRAIT);
+        for m in &matches {
+            self.render_scoresheet(&mut pdf, m, filled).await?;
+        }
+        Ok(pdf_file(
+            format!("{}_scoresheets.pdf", file_stem(&category.name)),
+            pdf,
+        ))
+    }
+
+    async fn render_scoresheet(
+        &self,
+        pdf: &mut PdfBuilder,
+        m: &Match,
+        filled: bool,
+    ) -> Result<(), AppError> {
+        let category = self
+            .category_repo
+            .get_by_id(m.tournament_category_id)
+            .await?;
+        let (tournament_name, tz) = match &category {
+            Some(c) => {
+                let tournament = self.get_tournament(c.tournament_id).await?;
+                let tz = stored_timezone(&tournament.timezone);
+                (tournament.name, tz)
+            }
+            None => (String::new(), Tz::UTC),
+        };
+        let (p1, p2) = self.participant_names(m).await?;
+
+        let mut results: Vec<MatchResult> = if filled {
+            self.result_repo.find_by_match(m.id).await?
+        } else {
+            Vec::new()
+        };
+        results.sort_by_key(|r| (r.set_number, r.period_number));
+
+        let set_count = results.len().max(SCORESHEET_SETS);
+        let mut sets: Vec<(String, String)> = results
+            .iter()
+            .map(|r| {
+                (
+                    opt_cell(&r.participant1_score),
+                    opt_cell(&r.participant2_score),
+                )
+            })
+            .collect();
+        sets.resize(set_count, (String::new(), String::new()));
+
+        let (p1_sets, p2_sets) = if filled && !results.is_empty() {
+            (
+                results
+                    .iter()
+                    .filter(|r| r.participant1_score > r.participant2_score)
+                    .count()
+                    .to_string(),
+                results
+                    .iter()
+                    .filter(|r| r.participant2_score > r.participant1_score)
+                    .count()
+                    .to_string(),
+            )
+        } else {
+            (String::new(), String::new())
+        };
+
+        pdf.new_page();
+        pdf.heading(&tournament_name);
+        pdf.subheading(&format!(
+            "{} - {}",
+            category.map(|c| c.name).unwrap_or_default(),
+            title_case(&serde_label(&m.match_type))
+        ));
+        pdf.text_line(&format!(
+            "Round: {}    Match: {}    Scheduled: {}",
+            opt_cell(&m.round_number),
+            opt_cell(&m.match_number),
+            m.scheduled_date
+                .with_timezone(&tz)
+                .format("%Y-%m-%d %H:%M %Z")
+        ));
+        pdf.text_line(&format!(
+            "Venue: {}    Court: {}",
+            m.venue.clone().unwrap_or_default(),
+            m.court_number.clone().unwrap_or_default()
+        ));
+        pdf.text_line(&format!(
+            "Referee: {}    Umpire: {}",
+            m.referee_name.clone().unwrap_or_default(),
+            m.umpire_name.clone().unwrap_or_default()
+        ));
+        pdf.spacer(10.0);
+        pdf.score_grid([&p1, &p2], &sets, [&p1_sets, &p2_sets]);
+
+        let winner = match (filled, m.winner_participant) {
+            (true, Some(1)) => p1.clone(),
+            (true, Some(2)) => p2.clone(),
+            _ => String::new(),
+        };
+        pdf.text_line(&format!("Winner: {}", winner));
+        pdf.spacer(20.0);
+        pdf.signature_lines(&["Referee", "Participant 1", "Participant 2"]);
+        Ok(())
+    }
+
+    /// Final standings per category.
+    pub async fn standings_pdf(&self, tournament_id: Uuid) -> Result<ExportFile, AppError> {
+        let tournament = self.get_tournament(tournament_id).await?;
+        let categories = self.category_repo.get_by_tournament(tournament_id).await?;
+        let mut standings = self
+            .standings_repo
+            .get_by_tournament_id(tournament_id)
+            .await?;
+        standings.sort_by_key(|s| (s.category_id, s.position));
+
+        let mut by_category: BTreeMap<Option<Uuid>, Vec<_>> = BTreeMap::new();
+        for s in standings {
+            by_category.entry(s.category_id).or_default().push(s);
+        }
+
+        let title = format!("{} - final standings", tournament.name);
+        let mut pdf = PdfBuilder::new(&title, A4_PORTRAIT);
+        pdf.heading(&title);
+        if by_category.is_empty() {
+            pdf.text_line("No standings recorded.");
+        }
+        for (category_id, rows) in &by_category {
+            let name = category_id
+                .and_then(|id| categories.iter().find(|c| c.id == id))
+                .map(|c| c.name.clone())
+                .unwrap_or_else(|| "Overall".to_string());
+            pdf.subheading(&name);
+            let mut table = CsvTable::new(&[
+                "Pos",
+                "Participant",
+                "P",
+                "W",
+                "D",
+                "L",
+                "Sets",
+                "Games",
+                "Pts",
+            ]);
+            for s in rows {
+                table.push_row(vec![
+                    s.position.to_string(),
+                    s.participant_name.clone(),
+                    s.matches_played.to_string(),
+                    s.matches_won.to_string(),
+                    s.matches_drawn.to_string(),
+                    s.matches_lost.to_string(),
+                    format!("{}-{}", s.sets_won, s.sets_lost),
+                    format!("{}-{}", s.games_won, s.games_lost),
+                    s.points.to_string(),
+                ]);
+            }
+            pdf.table(&table);
+        }
+
+        Ok(pdf_file(
+            format!("{}_standings.pdf", file_stem(&tournament.name)),
+            pdf,
+        ))
+    }
+
+    async fn participant_names(&self, m: &Match) -> Result<(String, String), AppError> {
+        Ok(self
+            .match_repo
+            .find_with_participants(m.id)
+            .await?
+            .map(|n| (n.participant1_name, n.participant2_name))
+            .unwrap_or_else(|| ("TBD".to_string(), "TBD".to_string())))
+    }
+
+    async fn get_tournament(&self, id: Uuid) -> Result<Tournament, AppError> {
+        self.tournament_repo
+            .get_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Tournament not found".into()))
+    }
+
+    async fn build_table(
+        &self,
+        tournament: &Tournament,
+        entity: ExportEntity,
+        tz: Tz,
+    ) -> Result<CsvTable, AppError> {
+        match entity {
+            ExportEntity::Registrations => self.registrations_table(tournament.id, tz).await,
+            ExportEntity::Matches => self.matches_table(tournament.id, tz).await,
+            ExportEntity::Standings => self.standings_table(tournament.id).await,
+            ExportEntity::Payments => self.payments_table(tournament.id, tz).await,
+        }
+    }
+
+    pub async fn registrations_table(
+        &self,
+        tournament_id: Uuid,
+        tz: Tz,
+    ) -> Result<CsvTable, AppError> {
+        let registrations = self
+            .registration_repo
+            .get_by_tournament(tournament_id)
+            .await?;
+        let mut table = CsvTable::new(REGISTRATION_COLUMNS);
+        for r in registrations {
+            table.push_row(vec![
+                r.id.to_string(),
+                r.tournament_name,
+                r.category_name,
+                r.player_name.unwrap_or_default(),
+                r.partner_name.unwrap_or_default(),
+                r.team_name.unwrap_or_default(),
+                serde_label(&r.registration_status),
+                serde_label(&r.payment_status),
+                to_local_rfc3339(r.registration_date, tz),
+            ]);
+        }
+        Ok(table)
+    }
+
+    pub async fn matches_table(&self, tournament_id: Uuid, tz: Tz) -> Result<CsvTable, AppError> {
+        let mut matches = self.match_repo.find_by_tournament(tournament_id).await?;
+        matches.sort_by_key(|m| (m.round_number, m.match_number, m.scheduled_date));
+
+        // Names and results come a category at a time rather than per match
+        let mut names = HashMap::new();
+        let mut results_by_match: HashMap<Uuid, Vec<MatchResult>> = HashMap::new();
+        for category in self.category_repo.get_by_tournament(tournament_id).await? {
+            for n in self
+                .match_repo
+                .find_with_participants_by_category(category.id)
+                .await?
+            {
+                names.insert(n.id, (n.participant1_name, n.participant2_name));
+            }
+            for r in self.result_repo.find_by_category(category.id).await? {
+                results_by_match.entry(r.match_id).or_default().push(r);
+            }
+        }
+
+        let mut table = CsvTable::new(MATCH_COLUMNS);
+        for m in matches {
+            let (p1_name, p2_name) = names.remove(&m.id).unwrap_or_default();
+
+            let mut results = results_by_match.remove(&m.id).unwrap_or_default();
+            results.sort_by_key(|r| (r.set_number, r.period_number));
+            let set_scores = results
+                .iter()
+                .map(|r| {
+                    format!(
+                        "{}-{}",
+                        opt_cell(&r.participant1_score),
+                        opt_cell(&r.participant2_score)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            let p1_sets = results
+                .iter()
+                .filter(|r| r.participant1_score > r.participant2_score)
+                .count();
+            let p2_sets = results
+                .iter()
+                .filter(|r| r.participant2_score > r.participant1_score)
+                .count();
+
+            let winner = match m.winner_participant {
+                Some(1) => p1_name.clone(),
+                Some(2) => p2_name.clone(),
+                _ => String::new(),
+            };
+
+            table.push_row(vec![
+                m.id.to_string(),
+                m.tournament_category_id.to_string(),
+                opt_cell(&m.round_number),
+                opt_cell(&m.match_number),
+                serde_label(&m.match_type),
+                serde_label(&m.match_status),
+                to_local_rfc3339(m.scheduled_date, tz),
+                m.venue.unwrap_or_default(),
+                m.court_number.unwrap_or_default(),
+                p1_name,
+                p2_name,
+                p1_sets.to_string(),
+                p2_sets.to_string(),
+                set_scores,
+                winner,
+                m.is_draw.to_string(),
+            ]);
+        }
+        Ok(table)
+    }
+
+    pub async fn standings_table(&self, tournament_id: Uuid) -> Result<CsvTable, AppError> {
+        let mut standings = self
+            .standings_repo
+            .get_by_tournament_id(tournament_id)
+            .await?;
+        standings.sort_by_key(|s| (s.category_id, s.position));
+
+        let mut table = CsvTable::new(STANDINGS_COLUMNS);
+        for s in standings {
+            table.push_row(vec![
+                s.position.to_string(),
+                s.participant_id.to_string(),
+                s.participant_name,
+                s.participant_type,
+                opt_cell(&s.category_id),
+                s.points.to_string(),
+                s.matches_played.to_string(),
+                s.matches_won.to_string(),
+                s.matches_lost.to_string(),
+                s.matches_drawn.to_string(),
+                s.sets_won.to_string(),
+                s.sets_lost.to_string(),
+                s.games_won.to_string(),
+                s.games_lost.to_string(),
+                opt_cell(&s.goal_difference),
+                s.is_eliminated.to_string(),
+            ]);
+        }
+        Ok(table)
+    }
+
+    pub async fn payments_table(&self, tournament_id: Uuid, tz: Tz) -> Result<CsvTable, AppError> {
+        let payments = self.all_tournament_payments(tournament_id).await?;
+        let mut table = CsvTable::new(PAYMENT_COLUMNS);
+        for p in payments {
+            table.push_row(vec![
+                p.id.to_string(),
+                p.user_id.to_string(),
+                p.amount.to_string(),
+                p.currency,
+                serde_label(&p.payment_method),
+                serde_label(&p.status),
+                p.transaction_id.unwrap_or_default(),
+                p.payment_provider.unwrap_or_default(),
+                opt_cell(&p.refunded_amount),
+                p.failure_reason.unwrap_or_default(),
+                to_local_rfc3339(p.created_at, tz),
+                p.processed_at
+                    .map(|d| to_local_rfc3339(d, tz))
+                    .unwrap_or_default(),
+            ]);
+        }
+        Ok(table)
+    }
+
+    async fn all_tournament_payments(&self, tournament_id: Uuid) -> Result<Vec<Payment>, AppError> {
+        let mut payments = Vec::new();
+        let mut offset = 0;
+        loop {
+            let page = self
+                .payment_repo
+                .find_by_tournament_id(tournament_id, PAYMENT_PAGE_SIZE, offset)
+                .await?;
+            let fetched = page.len() as i64;
+            payments.extend(page);
+            if fetched < PAYMENT_PAGE_SIZE {
+                break;
+            }
+            offset += fetched;
+        }
+        Ok(payments)
+    }
+}
+
+/// Picks the columns requested for `entity`. Qualified names (`matches.id`) always
+/// apply; bare names only apply when a single entity is exported.
+fn columns_for(entity: ExportEntity, requested: &[String], single_entity: bool) -> Vec<String> {
+    let prefix = format!("{}.", entity.as_str());
+    requested
+        .iter()
+        .filter_map(|c| match c.strip_prefix(&prefix) {
+            Some(column) => Some(column.to_string()),
+            None if single_entity && !c.contains('.') => Some(c.clone()),
+            None => None,
+        })
+        .collect()
+}
+
+/// Turns a tournament name into a filename-safe stem.
+pub fn file_stem(name: &str) -> String {
+    let stem: String = name
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if stem.is_empty() {
+        "tournament".to_string()
+    } else {
+        stem
+    }
+}
+
+fn bracket_slots_from_nodes(nodes: &[BracketNode]) -> Vec<BracketSlot> {
+    nodes
+        .iter()
+        .map(|n| {
+            let winner = match n.winner_id {
+                Some(w) if Some(w) == n.participant1_id => Some(1),
+                Some(w) if Some(w) == n.participant2_id => Some(2),
+                _ => None,
+            };
+            BracketSlot {
+                round: n.round.max(1),
+                position: n.position.max(0),
+                top: n
+                    .participant1_name
+                    .clone()
+                    .unwrap_or_else(|| "TBD".to_string()),
+                bottom: n
+                    .participant2_name
+                    .clone()
+                    .unwrap_or_else(|| "TBD".to_string()),
+                winner,
+            }
+        })
+        .collect()
+}
+
+fn pdf_file(filename: String, pdf: PdfBuilder) -> ExportFile {
+    ExportFile {
+        filename,
+        content_type: "application/pdf".to_string(),
+        bytes: pdf.finish(),
+    }
+}
+
+/// The zone an export shows dates in: `requested` when given, else the tournament's
+fn export_timezone(tournament: &Tournament, requested: Option<&str>) -> Result<Tz, AppError> {
+    match requested {
+        Some(name) => parse_timezone(name),
+        None => Ok(stored_timezone(&tournament.timezone)),
+    }
+}
+
+fn title_case(value: &str) -> String {
+    value
+        .split(['_', ' '])
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let mut chars = w.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn to_json<V: serde::Serialize>(value: &V) -> Result<JsonValue, AppError> {
+    serde_json::to_value(value).map_err(|e| AppError::InternalError(e.to_string()))
+}
@@ -0,0 +1,774 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use serde_json::json;
+
+use crate::domain::import::{
+    EntrantRow, ImportOptions, ImportPlan, ImportReport, ImportRepository, ImportRowReport,
+    ImportRowStatus, PlannedMember, PlannedRef, PlannedRegistration, Resolution,
+    ResolvedParticipant,
+};
+use crate::domain::participant::{CreatePlayer, NewTeam, Player, TeamWithMembers};
+use crate::domain::realtime::{EventPublisher, RealtimeEvent};
+use crate::domain::tournament::{
+    RegistrationStatus, TeamComposition, Tournament, TournamentCategory,
+    TournamentCategoryRepository, TournamentRegistration, TournamentRegistrationRepository,
+    TournamentRepository, TournamentStatus,
+};
+use crate::shared::csv::{parse_csv, serde_label};
+use crate::shared::xlsx;
+use crate::shared::AppError;
+
+/// Upper bound on entrant rows per upload
+const MAX_IMPORT_ROWS: usize = 5000;
+
+/// Import services - bulk creation of players, teams and registrations from a sheet
+pub struct ImportServices<T, C, R, I>
+where
+    T: TournamentRepository + ?Sized,
+    C: TournamentCategoryRepository + ?Sized,
+    R: TournamentRegistrationRepository + ?Sized,
+    I: ImportRepository + ?Sized,
+{
+    tournament_repo: Arc<T>,
+    category_repo: Arc<C>,
+    registration_repo: Arc<R>,
+    import_repo: Arc<I>,
+    events: Arc<dyn EventPublisher>,
+}
+
+impl<T, C, R, I> ImportServices<T, C, R, I>
+where
+    T: TournamentRepository + ?Sized,
+    C: TournamentCategoryRepository + ?Sized,
+    R: TournamentRegistrationRepository + ?Sized,
+    I: ImportRepository + ?Sized,
+{
+    pub fn new(
+        tournament_repo: Arc<T>,
+        category_repo: Arc<C>,
+        registration_repo: Arc<R>,
+        import_repo: Arc<I>,
+        events: Arc<dyn EventPublisher>,
+    ) -> Self {
+        Self {
+            tournament_repo,
+            category_repo,
+            registration_repo,
+            import_repo,
+            events,
+        }
+    }
+
+    /// Validates a CSV/XLSX sheet of entrants against the tournament and, unless this is a
+    /// dry run, writes it in one transaction. Any invalid row blocks the whole commit.
+    pub async fn import_entrants(
+        &self,
+        tournament_id: Uuid,
+        file: &[u8],
+        options: ImportOptions,
+    ) -> Result<ImportReport, AppError> {
+        let tournament = self
+            .tournament_repo
+            .get_by_id(tournament_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Tournament not found".into()))?;
+        ensure_accepting_registrations(&tournament)?;
+
+        let rows = EntrantRow::from_sheet(read_sheet(file)?)?;
+        if rows.is_empty() {
+            return Err(AppError::BadRequest(
+                "Import sheet has no entrant rows".into(),
+            ));
+        }
+        if rows.len() > MAX_IMPORT_ROWS {
+            return Err(AppError::BadRequest(format!(
+                "Import sheet has {} rows; the limit is {}",
+                rows.len(),
+                MAX_IMPORT_ROWS
+            )));
+        }
+
+        let categories = self.category_repo.get_by_tournament(tournament_id).await?;
+        if let Some(id) = options.category_id {
+            if !categories.iter().any(|c| c.id == id) {
+                return Err(AppError::BadRequest(
+                    "category_id does not belong to this tournament".into(),
+                ));
+            }
+        }
+
+        let mut registrations = Vec::new();
+        for category in &categories {
+            registrations.extend(
+                self.registration_repo
+                    .get_by_tournament_category(category.id)
+                    .await?,
+            );
+        }
+
+        let (emails, names, team_names) = lookup_keys(&rows);
+        let players_by_email = self.import_repo.find_players_by_emails(&emails).await?;
+        let players_by_name = self.import_repo.find_players_by_names(&names).await?;
+        let teams = self.import_repo.find_teams_by_names(&team_names).await?;
+
+        let mut planner = ImportPlanner::new(&categories, &registrations, options.category_id);
+        for p in players_by_email {
+            planner
+                .by_email
+                .insert(p.email.trim().to_lowercase(), p.player);
+        }
+        for p in players_by_name {
+            planner
+                .by_name
+                .entry(name_key(&p.name))
+                .or_default()
+                .push(p);
+        }
+        for team in teams {
+            planner.add_existing_team(team);
+        }
+
+        let row_reports: Vec<ImportRowReport> = rows.iter().map(|r| planner.plan_row(r)).collect();
+        let invalid_rows = row_reports
+            .iter()
+            .filter(|r| r.status == ImportRowStatus::Invalid)
+            .count();
+
+        let plan = planner.plan;
+        let mut report = ImportReport {
+            dry_run: options.dry_run,
+            committed: false,
+            total_rows: row_reports.len(),
+            valid_rows: row_reports.len() - invalid_rows,
+            invalid_rows,
+            players_matched: planner.matched.len(),
+            players_to_create: plan.players.len(),
+            teams_to_create: plan.teams.len(),
+            members_to_add: plan.members.len(),
+            registrations_to_create: plan.registrations.len(),
+            rows: row_reports,
+            result: None,
+        };
+
+        if !options.dry_run && invalid_rows == 0 {
+            let result = self.import_repo.commit(plan).await?;
+            for registration in &result.registrations {
+                self.events
+                    .publish(RealtimeEvent::RegistrationUpdate {
+                        registration_id: registration.id,
+                        tournament_id: Some(tournament_id),
+                        category_id: registration.tournament_category_id,
+                        status: Some(format!("{:?}", registration.registration_status)),
+                    })
+                    .await;
+            }
+            report.result = Some(result);
+            report.committed = true;
+        }
+        Ok(report)
+    }
+}
+
+fn ensure_accepting_registrations(tournament: &Tournament) -> Result<(), AppError> {
+    match tournament.status {
+        TournamentStatus::Draft
+        | TournamentStatus::Upcoming
+        | TournamentStatus::RegistrationOpen => Ok(()),
+        status => Err(AppError::BadRequest(format!(
+            "Tournament is {} and no longer accepts registrations",
+            serde_label(&status)
+        ))),
+    }
+}
+
+/// XLSX is detected by its ZIP signature; anything else is read as UTF-8 CSV.
+fn read_sheet(file: &[u8]) -> Result<Vec<Vec<String>>, AppError> {
+    if xlsx::is_zip(file) {
+        return xlsx::read_first_sheet(file);
+    }
+    let text = std::str::from_utf8(file)
+        .map_err(|_| AppError::BadRequest("CSV files must be UTF-8 encoded".into()))?;
+    Ok(parse_csv(text))
+}
+
+fn name_key(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+fn is_valid_email(email: &str) -> bool {
+    match email.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !email.contains(char::is_whitespace)
+        }
+        None => false,
+    }
+}
+
+/// Distinct emails, player names and team names referenced by the sheet
+fn lookup_keys(rows: &[EntrantRow]) -> (Vec<String>, Vec<String>, Vec<String>) {
+    let mut emails = HashSet::new();
+    let mut names = HashSet::new();
+    let mut teams = HashSet::new();
+    for row in rows {
+        for email in [&row.player_email, &row.partner_email]
+            .into_iter()
+            .flatten()
+        {
+            emails.insert(email.trim().to_lowercase());
+        }
+        for name in [&row.player_name, &row.partner_name].into_iter().flatten() {
+            names.insert(name_key(name));
+        }
+        if let Some(team) = &row.team_name {
+            teams.insert(name_key(team));
+        }
+    }
+    (
+        emails.into_iter().collect(),
+        names.into_iter().collect(),
+        teams.into_iter().collect(),
+    )
+}
+
+// ==================== Planning ====================
+
+/// A sheet participant before it is added to the plan
+enum Candidate {
+    Existing {
+        id: Uuid,
+        name: String,
+        matched_by: &'static str,
+    },
+    New {
+        keys: Vec<String>,
+        name: String,
+    },
+}
+
+impl Candidate {
+    fn same_as(&self, other: &Candidate) -> bool {
+        match (self, other) {
+            (Candidate::Existing { id: a, .. }, Candidate::Existing { id: b, .. }) => a == b,
+            (Candidate::New { keys: a, .. }, Candidate::New { keys: b, .. }) => {
+                a.iter().any(|k| b.contains(k))
+            }
+            _ => false,
+        }
+    }
+
+    fn name(&self) -> &str {
+        match self {
+            Candidate::Existing { name, .. } | Candidate::New { name, .. } => name,
+        }
+    }
+}
+
+/// Registered participants are tracked per category, keyed by kind and reference
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum Entrant {
+    Player(PlannedRef),
+    Team(PlannedRef),
+}
+
+struct ImportPlanner<'a> {
+    categories: &'a [TournamentCategory],
+    default_category: Option<Uuid>,
+    by_email: HashMap<String, Player>,
+    by_name: HashMap<String, Vec<Player>>,
+    teams: HashMap<String, TeamWithMembers>,
+    registered: HashSet<(Uuid, Entrant)>,
+    /// Teams whose registration is created by this import
+    planned_team_registrations: HashSet<(Uuid, PlannedRef)>,
+    existing_members: HashSet<(PlannedRef, PlannedRef)>,
+    planned_members: HashSet<(PlannedRef, PlannedRef)>,
+    occupied: HashMap<Uuid, usize>,
+    new_players: HashMap<String, usize>,
+    new_teams: HashMap<String, usize>,
+    matched: HashSet<Uuid>,
+    plan: ImportPlan,
+}
+
+impl<'a> ImportPlanner<'a> {
+    fn new(
+        categories: &'a [TournamentCategory],
+        registrations: &[TournamentRegistration],
+        default_category: Option<Uuid>,
+    ) -> Self {
+        let mut registered = HashSet::new();
+        let mut occupied: HashMap<Uuid, usize> = HashMap::new();
+        for r in registrations {
+            if matches!(
+                r.registration_status,
+                RegistrationStatus::Rejected | RegistrationStatus::Withdrawn
+            ) {
+                continue;
+            }
+            let category = r.tournament_category_id;
+            for id in [r.player_id, r.partner_player_id].into_iter().flatten() {
+                registered.insert((category, Entrant::Player(PlannedRef::Existing(id))));
+            }
+            if let Some(id) = r.team_id {
+                registered.insert((category, Entrant::Team(PlannedRef::Existing(id))));
+            }
+            if r.registration_status != RegistrationStatus::Waitlisted {
+                *occupied.entry(category).or_default() += 1;
+            }
+        }
+
+        Self {
+            categories,
+            default_category,
+            by_email: HashMap::new(),
+            by_name: HashMap::new(),
+            teams: HashMap::new(),
+            registered,
+            planned_team_registrations: HashSet::new(),
+            existing_members: HashSet::new(),
+            planned_members: HashSet::new(),
+            occupied,
+            new_players: HashMap::new(),
+            new_teams: HashMap::new(),
+            matched: HashSet::new(),
+            plan: ImportPlan::default(),
+        }
+    }
+
+    fn add_existing_team(&mut self, team: TeamWithMembers) {
+        let team_ref = PlannedRef::Existing(team.team.id);
+        for member in &team.members {
+            self.existing_members
+                .insert((team_ref, PlannedRef::Existing(member.id)));
+        }
+        self.teams.insert(name_key(&team.team.name), team);
+    }
+
+    fn plan_row(&mut self, row: &EntrantRow) -> ImportRowReport {
+        let mut errors = row.errors.clone();
+
+        let category = match self.resolve_category(row.category.as_deref()) {
+            Ok(c) => Some(c),
+            Err(e) => {
+                errors.push(e);
+                None
+            }
+        };
+
+        let player = self.resolve_player(
+            row.player_name.as_deref(),
+            row.player_email.as_deref(),
+            "Player",
+        );
+        let partner = if row.partner_name.is_some() || row.partner_email.is_some() {
+            Some(self.resolve_player(
+                row.partner_name.as_deref(),
+                row.partner_email.as_deref(),
+                "Partner",
+            ))
+        } else {
+            None
+        };
+        let team = row.team_name.as_deref().map(|name| self.resolve_team(name));
+
+        let player = match player {
+            Ok(p) => Some(p),
+            Err(e) => {
+                errors.push(e);
+                None
+            }
+        };
+        let partner = match partner {
+            Some(Ok(p)) => Some(p),
+            Some(Err(e)) => {
+                errors.push(e);
+                None
+            }
+            None => None,
+        };
+
+        if let Some(category) = category {
+            let has_partner = row.partner_name.is_some() || row.partner_email.is_some();
+            match category.team_composition {
+                TeamComposition::Singles => {
+                    if has_partner {
+                        errors.push(format!(
+                            "'{}' is a singles category; remove the partner",
+                            category.name
+                        ));
+                    }
+                    if team.is_some() {
+                        errors.push(format!(
+                            "'{}' is a singles category; remove the team",
+                            category.name
+                        ));
+                    }
+                }
+                TeamComposition::Doubles | TeamComposition::MixedDoubles => {
+                    if !has_partner {
+                        errors.push(format!(
+                            "'{}' is a doubles category and needs a partner",
+                            category.name
+                        ));
+                    }
+                    if team.is_some() {
+                        errors.push(format!(
+                            "'{}' is a doubles category; remove the team",
+                            category.name
+                        ));
+                    }
+                }
+                TeamComposition::Team => {
+                    if team.is_none() {
+                        errors.push(format!(
+                            "'{}' is a team category and needs a team_name",
+                            category.name
+                        ));
+                    }
+                    if has_partner {
+                        errors.push(format!(
+                            "'{}' is a team category; list partners as separate rows",
+                            category.name
+                        ));
+                    }
+                }
+            }
+        }
+
+        if let (Some(p), Some(q)) = (&player, &partner) {
+            if p.same_as(q) {
+                errors.push("Player and partner are the same person".into());
+            }
+        }
+
+        if errors.is_empty() {
+            if let (Some(category), Some(player)) = (category, &player) {
+                match &team {
+                    Some(team) => self.check_team_row(category, team, player, &mut errors),
+                    None => self.check_player_row(category, player, partner.as_ref(), &mut errors),
+                }
+            }
+        }
+
+        let mut report = ImportRowReport {
+            row: row.row,
+            status: ImportRowStatus::Invalid,
+            category_id: category.map(|c| c.id),
+            player: player.as_ref().map(describe),
+            partner: partner.as_ref().map(describe),
+            team: team.as_ref().map(describe),
+            errors,
+        };
+        if !report.errors.is_empty() {
+            return report;
+        }
+
+        // Row is valid: add everything it needs to the plan
+        let (Some(category), Some(player)) = (category, player) else {
+            return report;
+        };
+        let player_ref = self.materialize_player(&player);
+
+        match team {
+            Some(team) => {
+                let team_ref = self.materialize_team(&team);
+
+                if !self.existing_members.contains(&(team_ref, player_ref)) {
+                    self.planned_members.insert((team_ref, player_ref));
+                    self.plan.members.push(PlannedMember {
+                        team: team_ref,
+                        player: player_ref,
+                        is_captain: row.is_captain.unwrap_or(false),
+                        jersey_number: row.jersey_number,
+                    });
+                }
+                if self
+                    .planned_team_registrations
+                    .insert((category.id, team_ref))
+                {
+                    self.registered
+                        .insert((category.id, Entrant::Team(team_ref)));
+                    self.push_registration(category, Some(team_ref), None, None, row);
+                }
+            }
+            None => {
+                let partner_ref = partner.as_ref().map(|p| self.materialize_player(p));
+                for r in std::iter::once(player_ref).chain(partner_ref) {
+                    self.registered.insert((category.id, Entrant::Player(r)));
+                }
+                self.push_registration(category, None, Some(player_ref), partner_ref, row);
+            }
+        }
+
+        report.status = ImportRowStatus::Valid;
+        report
+    }
+
+    fn check_player_row(
+        &self,
+        category: &TournamentCategory,
+        player: &Candidate,
+        partner: Option<&Candidate>,
+        errors: &mut Vec<String>,
+    ) {
+        for candidate in std::iter::once(player).chain(partner) {
+            if let Some(r) = self.peek_player(candidate) {
+                if self.registered.contains(&(category.id, Entrant::Player(r))) {
+                    errors.push(format!(
+                        "'{}' is already registered in '{}'",
+                        candidate.name(),
+                        category.name
+                    ));
+                }
+            }
+        }
+        self.check_capacity(category, errors);
+    }
+
+    fn check_team_row(
+        &self,
+        category: &TournamentCategory,
+        team: &Candidate,
+        player: &Candidate,
+        errors: &mut Vec<String>,
+    ) {
+        let team_ref = self.peek_team(team);
+        let player_ref = self.peek_player(player);
+
+        if let (Some(t), Some(p)) = (team_ref, player_ref) {
+            if self.planned_members.contains(&(t, p)) {
+                errors.push(format!(
+                    "'{}' is listed twice for team '{}'",
+                    player.name(),
+                    team.name()
+                ));
+            }
+        }
+
+        let registered_here =
+            team_ref.is_some_and(|t| self.planned_team_registrations.contains(&(category.id, t)));
+        if registered_here {
+            return;
+        }
+        if team_ref.is_some_and(|t| self.registered.contains(&(category.id, Entrant::Team(t)))) {
+            errors.push(format!(
+                "Team '{}' is already registered in '{}'",
+                team.name(),
+                category.name
+            ));
+        }
+        self.check_capacity(category, errors);
+    }
+
+    fn check_capacity(&self, category: &TournamentCategory, errors: &mut Vec<String>) {
+        if let Some(max) = category.max_participants {
+            let taken = self.occupied.get(&category.id).copied().unwrap_or(0);
+            if taken >= max.max(0) as usize {
+                errors.push(format!(
+                    "Category '{}' is full ({} places)",
+                    category.name, max
+                ));
+            }
+        }
+    }
+
+    fn push_registration(
+        &mut self,
+        category: &TournamentCategory,
+        team: Option<PlannedRef>,
+        player: Option<PlannedRef>,
+        partner: Option<PlannedRef>,
+        row: &EntrantRow,
+    ) {
+        *self.occupied.entry(category.id).or_default() += 1;
+        self.plan.registrations.push(PlannedRegistration {
+            tournament_category_id: category.id,
+            team,
+            player,
+            partner,
+            notes: row.notes.clone(),
+            metadata: Some(json!({ "source": "import", "row": row.row })),
+        });
+    }
+
+    fn resolve_category(&self, value: Option<&str>) -> Result<&'a TournamentCategory, String> {
+        let categories = self.categories;
+        match value {
+            Some(value) => {
+                let key = name_key(value);
+                categories
+                    .iter()
+                    .find(|c| c.id.to_string() == value || name_key(&c.name) == key)
+                    .ok_or_else(|| format!("Unknown category '{}'", value))
+            }
+            None => match (self.default_category, categories) {
+                (Some(id), _) => categories
+                    .iter()
+                    .find(|c| c.id == id)
+                    .ok_or_else(|| "Default category not found".to_string()),
+                (None, [only]) => Ok(only),
+                _ => Err("Row has no category and no default category_id was given".into()),
+            },
+        }
+    }
+
+    fn resolve_player(
+        &self,
+        name: Option<&str>,
+        email: Option<&str>,
+        role: &str,
+    ) -> Result<Candidate, String> {
+        let email = email.map(|e| e.trim().to_lowercase());
+        if let Some(email) = &email {
+            if !is_valid_email(email) {
+                return Err(format!("{} email '{}' is not valid", role, email));
+            }
+            if let Some(player) = self.by_email.get(email) {
+                return Ok(Candidate::Existing {
+                    id: player.id,
+                    name: player.name.clone(),
+                    matched_by: "email",
+                });
+            }
+        }
+
+        let name = name.map(str::trim).filter(|n| !n.is_empty());
+        if let Some(name) = name {
+            match self.by_name.get(&name_key(name)).map(Vec::as_slice) {
+                Some([player]) => {
+                    return Ok(Candidate::Existing {
+                        id: player.id,
+                        name: player.name.clone(),
+                        matched_by: "name",
+                    })
+                }
+                Some(players) if players.len() > 1 && email.is_none() => {
+                    return Err(format!(
+                        "{} '{}' matches {} existing players; add an email to pick one",
+                        role,
+                        name,
+                        players.len()
+                    ))
+                }
+                _ => {}
+            }
+        }
+
+        let Some(name) = name else {
+            return Err(match email {
+                Some(email) => format!(
+                    "{} '{}' has no existing player; a name is required to create one",
+                    role, email
+                ),
+                None => format!("{} name or email is required", role),
+            });
+        };
+
+        let mut keys = vec![format!("name:{}", name_key(name))];
+        if let Some(email) = email {
+            keys.insert(0, format!("email:{}", email));
+        }
+        Ok(Candidate::New {
+            keys,
+            name: name.to_string(),
+        })
+    }
+
+    fn resolve_team(&self, name: &str) -> Candidate {
+        let key = name_key(name);
+        match self.teams.get(&key) {
+            Some(team) => Candidate::Existing {
+                id: team.team.id,
+                name: team.team.name.clone(),
+                matched_by: "name",
+            },
+            None => Candidate::New {
+                keys: vec![key],
+                name: name.trim().to_string(),
+            },
+        }
+    }
+
+    /// The reference a candidate would use, if it is already known to the plan
+    fn peek_player(&self, candidate: &Candidate) -> Option<PlannedRef> {
+        peek(candidate, &self.new_players)
+    }
+
+    fn peek_team(&self, candidate: &Candidate) -> Option<PlannedRef> {
+        peek(candidate, &self.new_teams)
+    }
+
+    fn materialize_player(&mut self, candidate: &Candidate) -> PlannedRef {
+        if let Some(r) = self.peek_player(candidate) {
+            if let PlannedRef::Existing(id) = r {
+                self.matched.insert(id);
+            }
+            return r;
+        }
+        let Candidate::New { keys, name } = candidate else {
+            unreachable!("existing candidates always peek");
+        };
+        let index = self.plan.players.len();
+        self.plan.players.push(CreatePlayer {
+            name: name.clone(),
+            user_id: None,
+        });
+        for key in keys {
+            self.new_players.insert(key.clone(), index);
+        }
+        PlannedRef::New(index)
+    }
+
+    fn materialize_team(&mut self, candidate: &Candidate) -> PlannedRef {
+        if let Some(r) = self.peek_team(candidate) {
+            return r;
+        }
+        let Candidate::New { keys, name } = candidate else {
+            unreachable!("existing candidates always peek");
+        };
+        let index = self.plan.teams.len();
+        self.plan.teams.push(NewTeam { name: name.clone() });
+        for key in keys {
+            self.new_teams.insert(key.clone(), index);
+        }
+        PlannedRef::New(index)
+    }
+}
+
+fn describe(candidate: &Candidate) -> ResolvedParticipant {
+    match candidate {
+        Candidate::Existing {
+            id,
+            name,
+            matched_by,
+        } => ResolvedParticipant {
+            name: name.clone(),
+            id: Some(*id),
+            resolution: Resolution::Existing,
+            matched_by: Some(matched_by.to_string()),
+        },
+        Candidate::New { name, .. } => ResolvedParticipant {
+            name: name.clone(),
+            id: None,
+            resolution: Resolution::New,
+            matched_by: None,
+        },
+    }
+}
+
+fn peek(candidate: &Candidate, planned: &HashMap<String, usize>) -> Option<PlannedRef> {
+    match candidate {
+        Candidate::Existing { id, .. } => Some(PlannedRef::Existing(*id)),
+        Candidate::New { keys, .. } => keys
+            .iter()
+            .find_map(|k| planned.get(k))
+            .map(|&i| PlannedRef::New(i)),
+    }
+}
@@ -1,40 +1,439 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use chrono_tz::Tz;
+use serde_json::json;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
+use crate::application::court_queue_services::court_queue_update;
+use crate::application::delay_services::with_estimates;
 use crate::domain::match_domain::{
-    EditableMatch, EditableMatchResult, LiveMatchUpdate, Match, MatchAnalytics, MatchComment,
-    MatchMedia, MatchRepository, MatchResult, MatchResultRepository, MatchScheduleItem,
-    MatchScoreSummary, MatchStatistics, MatchStatus, MatchSubscription, MatchWithParticipants,
-    NewMatch, NewMatchResult, RescheduleMatchRequest,
+    EditableMatch, EditableMatchResult, Forfeit, ForfeitKind, LiveMatchUpdate, LiveScore,
+    LocalScheduleItem, Match, MatchAnalytics, MatchComment, MatchMedia, MatchRepository,
+    MatchResult, MatchResultRepository, MatchScheduleItem, MatchScoreSummary, MatchStatistics,
+    MatchStatus, MatchSubscription, MatchWithParticipants, NewMatch, NewMatchResult,
+    RescheduleMatchRequest, RetirementRequest, ScoringAction, ScoringEntry, ScoringLog,
+    ScoringRules, ScoringSnapshot, ScoringUpdate, WalkoverRequest, WithdrawalRequest,
 };
+use crate::domain::notification::SubscriptionPreferences;
+use crate::domain::outbox::DomainEvent;
+use crate::domain::participant::TeamMemberRepository;
+use crate::domain::realtime::{EventPublisher, RealtimeEvent};
+use crate::domain::scheduling::{
+    estimated_duration, occupied_interval, participants_of, MatchSlot, ScheduleConflict,
+};
+use crate::domain::tournament::{
+    advance_winner, bracket_nodes, with_nodes, EditableTournamentBracket,
+    EditableTournamentRegistration, RegistrationStatus, TournamentBracket,
+    TournamentCategoryRepository, TournamentRepository,
+};
+use crate::domain::unit_of_work::{UnitOfWork, UnitOfWorkFactory};
+use crate::domain::venue::VenueRepository;
+use crate::shared::timezone::{local_day, stored_timezone};
 use crate::shared::AppError;
 
+/// How far before a time window to look for matches that may still be running in it
+const CONFLICT_LOOKBACK_HOURS: i64 = 24;
+
+/// Match durations and team members looked up while checking one set of matches
+#[derive(Default)]
+pub(crate) struct SlotLookups {
+    durations: HashMap<Uuid, Duration>,
+    members: HashMap<Uuid, Vec<Uuid>>,
+}
+
+impl SlotLookups {
+    /// Estimated length of matches in a category
+    async fn category_duration<C, T>(
+        &mut self,
+        category_repo: &C,
+        tournament_repo: &T,
+        category_id: Uuid,
+    ) -> Result<Duration, AppError>
+    where
+        C: TournamentCategoryRepository + ?Sized,
+        T: TournamentRepository + ?Sized,
+    {
+        if let Some(duration) = self.durations.get(&category_id) {
+            return Ok(*duration);
+        }
+        let category = category_repo
+            .get_by_id(category_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Category not found".into()))?;
+        let tournament = tournament_repo
+            .get_by_id(category.tournament_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Tournament not found".into()))?;
+        let duration = estimated_duration(tournament.sport_type, Some(&category));
+        self.durations.insert(category_id, duration);
+        Ok(duration)
+    }
+
+    /// Teams and players on either side of a match, with the members of its teams
+    pub(crate) async fn participants<P>(
+        &mut self,
+        team_member_repo: &P,
+        m: &Match,
+    ) -> Result<Vec<Uuid>, AppError>
+    where
+        P: TeamMemberRepository + ?Sized,
+    {
+        let mut participants = participants_of(m);
+        for team_id in [m.participant1_team_id, m.participant2_team_id]
+            .into_iter()
+            .flatten()
+        {
+            if let Some(members) = self.members.get(&team_id) {
+                participants.extend(members);
+                continue;
+            }
+            let members: Vec<Uuid> = team_member_repo
+                .get_by_team(team_id)
+                .await?
+                .into_iter()
+                .map(|p| p.id)
+                .collect();
+            participants.extend(&members);
+            self.members.insert(team_id, members);
+        }
+        participants.sort();
+        participants.dedup();
+        Ok(participants)
+    }
+}
+
+async fn match_slot<C, T, P>(
+    category_repo: &C,
+    tournament_repo: &T,
+    team_member_repo: &P,
+    m: &Match,
+    lookups: &mut SlotLookups,
+) -> Result<MatchSlot, AppError>
+where
+    C: TournamentCategoryRepository + ?Sized,
+    T: TournamentRepository + ?Sized,
+    P: TeamMemberRepository + ?Sized,
+{
+    let duration = lookups
+        .category_duration(category_repo, tournament_repo, m.tournament_category_id)
+        .await?;
+    let (starts_at, ends_at) = occupied_interval(m, duration);
+    Ok(MatchSlot {
+        match_id: Some(m.id),
+        starts_at,
+        ends_at,
+        participants: lookups.participants(team_member_repo, m).await?,
+        court_id: m.court_id,
+        court_label: MatchSlot::label(m.venue.as_deref(), m.court_number.as_deref()),
+        officials: MatchSlot::officials(&[m.referee_name.as_deref(), m.umpire_name.as_deref()]),
+    })
+}
+
+/// Clashes between `candidates`, as they would be saved, and every other match in the same
+/// period, and among the candidates themselves. `is_new` marks unsaved matches.
+pub(crate) async fn find_conflicts<M, C, T, P>(
+    match_repo: &M,
+    category_repo: &C,
+    tournament_repo: &T,
+    team_member_repo: &P,
+    candidates: &[Match],
+    is_new: bool,
+) -> Result<Vec<ScheduleConflict>, AppError>
+where
+    M: MatchRepository + ?Sized,
+    C: TournamentCategoryRepository + ?Sized,
+    T: TournamentRepository + ?Sized,
+    P: TeamMemberRepository + ?Sized,
+{
+    let mut lookups = SlotLookups::default();
+    let mut slots = Vec::new();
+    for m in candidates.iter().filter(|m| holds_time(m)) {
+        let mut slot = match_slot(
+            category_repo,
+            tournament_repo,
+            team_member_repo,
+            m,
+            &mut lookups,
+        )
+        .await?;
+        if is_new {
+            slot.match_id = None;
+        }
+        slots.push(slot);
+    }
+    let (Some(from), Some(until)) = (
+        slots.iter().map(|s| s.starts_at).min(),
+        slots.iter().map(|s| s.ends_at).max(),
+    ) else {
+        return Ok(Vec::new());
+    };
+
+    let mut others = Vec::new();
+    for m in match_repo
+        .find_in_period(from - Duration::hours(CONFLICT_LOOKBACK_HOURS), until)
+        .await?
+    {
+        if holds_time(&m) && !candidates.iter().any(|c| c.id == m.id) {
+            others.push(
+                match_slot(
+                    category_repo,
+                    tournament_repo,
+                    team_member_repo,
+                    &m,
+                    &mut lookups,
+                )
+                .await?,
+            );
+        }
+    }
+    let mut conflicts = Vec::new();
+    for (i, slot) in slots.iter().enumerate() {
+        for other in others.iter().chain(&slots[..i]) {
+            conflicts.extend(slot.conflicts_with(other));
+        }
+    }
+    Ok(conflicts)
+}
+
 /// Match domain services
-pub struct MatchServices<M, R>
+pub struct MatchServices<M, R, C, T, V, P, U>
 where
-    M: MatchRepository,
-    R: MatchResultRepository,
+    M: MatchRepository + ?Sized,
+    R: MatchResultRepository + ?Sized,
+    C: TournamentCategoryRepository + ?Sized,
+    T: TournamentRepository + ?Sized,
+    V: VenueRepository + ?Sized,
+    P: TeamMemberRepository + ?Sized,
+    U: UnitOfWorkFactory + ?Sized,
 {
     match_repo: Arc<M>,
     result_repo: Arc<R>,
+    category_repo: Arc<C>,
+    tournament_repo: Arc<T>,
+    venue_repo: Arc<V>,
+    team_member_repo: Arc<P>,
+    uow: Arc<U>,
+    events: Arc<dyn EventPublisher>,
 }
 
-impl<M, R> MatchServices<M, R>
+impl<M, R, C, T, V, P, U> MatchServices<M, R, C, T, V, P, U>
 where
-    M: MatchRepository,
-    R: MatchResultRepository,
+    M: MatchRepository + ?Sized,
+    R: MatchResultRepository + ?Sized,
+    C: TournamentCategoryRepository + ?Sized,
+    T: TournamentRepository + ?Sized,
+    V: VenueRepository + ?Sized,
+    P: TeamMemberRepository + ?Sized,
+    U: UnitOfWorkFactory + ?Sized,
 {
-    pub fn new(match_repo: Arc<M>, result_repo: Arc<R>) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        match_repo: Arc<M>,
+        result_repo: Arc<R>,
+        category_repo: Arc<C>,
+        tournament_repo: Arc<T>,
+        venue_repo: Arc<V>,
+        team_member_repo: Arc<P>,
+        uow: Arc<U>,
+        events: Arc<dyn EventPublisher>,
+    ) -> Self {
         Self {
             match_repo,
             result_repo,
+            category_repo,
+            tournament_repo,
+            venue_repo,
+            team_member_repo,
+            uow,
+            events,
+        }
+    }
+
+    // ==================== Events ====================
+
+    async fn tournament_of(&self, category_id: Uuid) -> Option<Uuid> {
+        match self.category_repo.get_by_id(category_id).await {
+            Ok(category) => category.map(|c| c.tournament_id),
+            Err(_) => None,
+        }
+    }
+
+    /// Publishes a match update tagged with its tournament, so tournament subscribers receive it
+    async fn publish_match_update(&self, m: &Match, status: String) {
+        let tournament_id = self.tournament_of(m.tournament_category_id).await;
+        self.events
+            .publish(RealtimeEvent::MatchUpdate {
+                match_id: m.id,
+                tournament_id,
+                category_id: Some(m.tournament_category_id),
+                status: Some(status),
+            })
+            .await;
+    }
+
+    async fn publish_status_change(&self, m: &Option<Match>) {
+        if let Some(m) = m {
+            self.publish_match_update(m, format!("{:?}", m.match_status))
+                .await;
+            if let Some(court_id) = m.court_id {
+                self.publish_court_queue(court_id, m).await;
+            }
+        }
+    }
+
+    /// What is on and next up on the match's court; a finished match promotes the next one
+    async fn publish_court_queue(&self, court_id: Uuid, m: &Match) {
+        let tournament_id = self.tournament_of(m.tournament_category_id).await;
+        if let Ok(update) =
+            court_queue_update(self.match_repo.as_ref(), court_id, tournament_id).await
+        {
+            self.events.publish(update).await;
+        }
+    }
+
+    /// Status, court queue and bracket updates for a recorded forfeit
+    async fn publish_forfeit(&self, recorded: RecordedForfeit) -> Match {
+        if let Some(bracket) = &recorded.bracket {
+            self.events
+                .publish(RealtimeEvent::BracketUpdate {
+                    tournament_id: bracket.tournament_id,
+                    category_id: bracket.category_id,
+                })
+                .await;
+        }
+        self.publish_status_change(&Some(recorded.m.clone())).await;
+        recorded.m
+    }
+
+    async fn publish_result_update(&self, match_id: Uuid) {
+        let category_id = match self.match_repo.find_by_id(match_id).await {
+            Ok(m) => m.map(|m| m.tournament_category_id),
+            Err(_) => None,
+        };
+        let tournament_id = match category_id {
+            Some(category_id) => self.tournament_of(category_id).await,
+            None => None,
+        };
+        self.events
+            .publish(RealtimeEvent::ResultUpdate {
+                match_id,
+                tournament_id,
+                category_id,
+            })
+            .await;
+    }
+
+    // ==================== Courts ====================
+
+    /// Venue and court names for a court a match is being assigned to
+    async fn court_location(&self, court_id: Uuid) -> Result<(String, String), AppError> {
+        let court =
+            self.venue_repo.find_court(court_id).await?.ok_or_else(|| {
+                AppError::ValidationError(format!("Court {} not found", court_id))
+            })?;
+        if !court.is_active {
+            return Err(AppError::ValidationError(format!(
+                "Court '{}' is not in use",
+                court.name
+            )));
+        }
+        let venue = self
+            .venue_repo
+            .find_by_id(court.venue_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Venue {} not found", court.venue_id)))?;
+        Ok((venue.name, court.name))
+    }
+
+    async fn assign_court(&self, data: &mut EditableMatch) -> Result<(), AppError> {
+        if let Some(court_id) = data.court_id {
+            let (venue, court) = self.court_location(court_id).await?;
+            data.venue = Some(venue);
+            data.court_number = Some(court);
+        }
+        Ok(())
+    }
+
+    // ==================== Conflicts ====================
+
+    async fn find_conflicts(
+        &self,
+        candidates: &[Match],
+        is_new: bool,
+    ) -> Result<Vec<ScheduleConflict>, AppError> {
+        find_conflicts(
+            &*self.match_repo,
+            &*self.category_repo,
+            &*self.tournament_repo,
+            &*self.team_member_repo,
+            candidates,
+            is_new,
+        )
+        .await
+    }
+
+    async fn ensure_no_conflicts(
+        &self,
+        candidates: &[Match],
+        is_new: bool,
+    ) -> Result<(), AppError> {
+        let conflicts = self.find_conflicts(candidates, is_new).await?;
+        if conflicts.is_empty() {
+            return Ok(());
+        }
+        Err(AppError::ConflictWithDetails(
+            format!(
+                "{} scheduling conflict(s); set allow_conflicts to save anyway",
+                conflicts.len()
+            ),
+            json!({ "conflicts": conflicts }),
+        ))
+    }
+
+    /// Clashes of a saved match with other matches; `None` when the match does not exist
+    pub async fn get_match_conflicts(
+        &self,
+        match_id: Uuid,
+    ) -> Result<Option<Vec<ScheduleConflict>>, AppError> {
+        match self.match_repo.find_by_id(match_id).await? {
+            Some(m) => self.find_conflicts(&[m], false).await.map(Some),
+            None => Ok(None),
         }
     }
 
+    /// Matches on a court scheduled in `[from, until)`, earliest first
+    pub async fn get_court_matches(
+        &self,
+        court_id: Uuid,
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<Match>, AppError> {
+        self.match_repo.find_by_court(court_id, from, until).await
+    }
+
     // ==================== Match CRUD ====================
 
-    pub async fn create_match(&self, data: NewMatch) -> Result<Match, AppError> {
-        self.match_repo.create(data).await
+    /// Refuses a match that clashes with another unless `allow_conflicts` is set
+    pub async fn create_match(
+        &self,
+        mut data: NewMatch,
+        allow_conflicts: bool,
+    ) -> Result<Match, AppError> {
+        if let Some(court_id) = data.court_id {
+            let (venue, court) = self.court_location(court_id).await?;
+            data.venue = Some(venue);
+            data.court_number = Some(court);
+        }
+        if !allow_conflicts {
+            self.ensure_no_conflicts(&[draft_match(&data)], true)
+                .await?;
+        }
+        let m = self.match_repo.create(data).await?;
+        self.publish_match_update(&m, format!("{:?}", m.match_status))
+            .await;
+        Ok(m)
     }
 
     pub async fn get_match(&self, match_id: Uuid) -> Result<Option<Match>, AppError> {
@@ -44,9 +443,12 @@ where
     pub async fn update_match(
         &self,
         match_id: Uuid,
-        data: EditableMatch,
+        mut data: EditableMatch,
     ) -> Result<Option<Match>, AppError> {
-        self.match_repo.update(match_id, data).await
+        self.assign_court(&mut data).await?;
+        let m = self.match_repo.update(match_id, data).await?;
+        self.publish_status_change(&m).await;
+        Ok(m)
     }
 
     pub async fn delete_match(&self, match_id: Uuid) -> Result<Option<Match>, AppError> {
@@ -67,7 +469,61 @@ where
     }
 
     pub async fn get_match_schedule(&self) -> Result<Vec<MatchScheduleItem>, AppError> {
-        self.match_repo.find_scheduled().await
+        let items = self.match_repo.find_scheduled().await?;
+        self.with_estimates(items).await
+    }
+
+    /// Scheduled matches starting on the local calendar day `date` in `tz`
+    pub async fn get_match_schedule_on(
+        &self,
+        date: NaiveDate,
+        tz: Tz,
+    ) -> Result<Vec<MatchScheduleItem>, AppError> {
+        let (from, until) = local_day(date, tz);
+        let items = self.match_repo.find_scheduled_between(from, until).await?;
+        self.with_estimates(items).await
+    }
+
+    /// Adds estimated start times, carrying overruns down each court's queue
+    async fn with_estimates(
+        &self,
+        items: Vec<MatchScheduleItem>,
+    ) -> Result<Vec<MatchScheduleItem>, AppError> {
+        with_estimates(
+            &*self.match_repo,
+            &*self.category_repo,
+            &*self.tournament_repo,
+            items,
+            Utc::now(),
+        )
+        .await
+    }
+
+    /// Adds local start times to schedule items: in `tz` when the caller asked for a
+    /// zone, otherwise in the zone of each item's tournament
+    pub async fn localize_schedule(
+        &self,
+        items: Vec<MatchScheduleItem>,
+        tz: Option<Tz>,
+    ) -> Result<Vec<LocalScheduleItem>, AppError> {
+        let mut zones: HashMap<Uuid, Tz> = HashMap::new();
+        let mut localized = Vec::with_capacity(items.len());
+        for item in items {
+            let zone = match (tz, zones.entry(item.tournament_id)) {
+                (Some(tz), _) => tz,
+                (None, Entry::Occupied(zone)) => *zone.get(),
+                (None, Entry::Vacant(slot)) => {
+                    let tournament = self.tournament_repo.get_by_id(item.tournament_id).await?;
+                    *slot.insert(
+                        tournament
+                            .map(|t| stored_timezone(&t.timezone))
+                            .unwrap_or(Tz::UTC),
+                    )
+                }
+            };
+            localized.push(LocalScheduleItem::new(item, zone));
+        }
+        Ok(localized)
     }
 
     pub async fn get_match_with_participants(
@@ -84,11 +540,19 @@ where
         match_id: Uuid,
         status: MatchStatus,
     ) -> Result<Option<Match>, AppError> {
-        self.match_repo.update_status(match_id, status).await
+        let work = self.uow.begin().await?;
+        let before = work.matches().find_by_id(match_id).await?;
+        let m = work.matches().update_status(match_id, status).await?;
+        enqueue_if_completed(work.as_ref(), before.as_ref(), &m).await?;
+        work.commit().await?;
+        self.publish_status_change(&m).await;
+        Ok(m)
     }
 
     pub async fn start_match(&self, match_id: Uuid) -> Result<Option<Match>, AppError> {
-        self.match_repo.start_match(match_id).await
+        let m = self.match_repo.start_match(match_id).await?;
+        self.publish_status_change(&m).await;
+        Ok(m)
     }
 
     pub async fn complete_match(
@@ -97,9 +561,16 @@ where
         winner: i32,
         is_draw: bool,
     ) -> Result<Option<Match>, AppError> {
-        self.match_repo
+        let work = self.uow.begin().await?;
+        let before = work.matches().find_by_id(match_id).await?;
+        let m = work
+            .matches()
             .complete_match(match_id, winner, is_draw)
-            .await
+            .await?;
+        enqueue_if_completed(work.as_ref(), before.as_ref(), &m).await?;
+        work.commit().await?;
+        self.publish_status_change(&m).await;
+        Ok(m)
     }
 
     pub async fn cancel_match(
@@ -107,19 +578,201 @@ where
         match_id: Uuid,
         reason: &str,
     ) -> Result<Option<Match>, AppError> {
-        self.match_repo.cancel_match(match_id, reason).await
+        let m = self.match_repo.cancel_match(match_id, reason).await?;
+        self.publish_status_change(&m).await;
+        Ok(m)
+    }
+
+    // ==================== Forfeits ====================
+
+    /// Awards a match that was never played to the side that turned up, or to neither
+    pub async fn walkover(
+        &self,
+        match_id: Uuid,
+        request: WalkoverRequest,
+    ) -> Result<Option<Match>, AppError> {
+        if request
+            .defaulting_participant
+            .is_some_and(|side| !(1..=2).contains(&side))
+        {
+            return Err(AppError::ValidationError(
+                "defaulting_participant must be 1 or 2".into(),
+            ));
+        }
+        let forfeit = Forfeit {
+            kind: ForfeitKind::Walkover,
+            defaulting_participant: request.defaulting_participant,
+            reason: request.reason,
+        };
+        self.forfeit(match_id, forfeit).await
+    }
+
+    /// Ends a match in progress with one side retiring; the games played so far are kept
+    pub async fn retire(
+        &self,
+        match_id: Uuid,
+        request: RetirementRequest,
+    ) -> Result<Option<Match>, AppError> {
+        if !(1..=2).contains(&request.retiring_participant) {
+            return Err(AppError::ValidationError(
+                "retiring_participant must be 1 or 2".into(),
+            ));
+        }
+        let forfeit = Forfeit {
+            kind: ForfeitKind::Retirement,
+            defaulting_participant: Some(request.retiring_participant),
+            reason: request.reason,
+        };
+        self.forfeit(match_id, forfeit).await
+    }
+
+    async fn forfeit(&self, match_id: Uuid, forfeit: Forfeit) -> Result<Option<Match>, AppError> {
+        let work = self.uow.begin().await?;
+        let Some(m) = work.matches().find_by_id(match_id).await? else {
+            work.rollback().await?;
+            return Ok(None);
+        };
+        let allowed = match forfeit.kind {
+            ForfeitKind::Walkover => {
+                matches!(
+                    m.match_status,
+                    MatchStatus::Scheduled | MatchStatus::Postponed
+                )
+            }
+            ForfeitKind::Retirement => m.match_status == MatchStatus::InProgress,
+        };
+        if !allowed {
+            work.rollback().await?;
+            return Err(AppError::ValidationError(match forfeit.kind {
+                ForfeitKind::Walkover => {
+                    "A walkover is only for a match that has not started; record a retirement"
+                        .into()
+                }
+                ForfeitKind::Retirement => {
+                    "Only a match in progress can end in a retirement".into()
+                }
+            }));
+        }
+        let recorded = record_forfeit(work.as_ref(), &m, &forfeit).await?;
+        work.commit().await?;
+        match recorded {
+            Some(recorded) => Ok(Some(self.publish_forfeit(recorded).await)),
+            None => Ok(None),
+        }
+    }
+
+    /// Withdraws a team or player from a category. Their registration is marked withdrawn
+    /// and each of their unplayed matches with a known opponent becomes a walkover, all in
+    /// one transaction. Returns those matches.
+    pub async fn withdraw_participant(
+        &self,
+        category_id: Uuid,
+        request: WithdrawalRequest,
+    ) -> Result<Vec<Match>, AppError> {
+        let work = self.uow.begin().await?;
+        let registration = work
+            .registrations()
+            .get_by_tournament_category(category_id)
+            .await?
+            .into_iter()
+            .find(|r| r.team_id.or(r.player_id) == Some(request.participant_id));
+        let Some(registration) = registration else {
+            work.rollback().await?;
+            return Err(AppError::NotFound(format!(
+                "{} is not registered in category {}",
+                request.participant_id, category_id
+            )));
+        };
+        work.registrations()
+            .update(
+                registration.id,
+                EditableTournamentRegistration {
+                    registration_status: Some(RegistrationStatus::Withdrawn),
+                    payment_status: None,
+                    payment_amount: None,
+                    payment_reference: None,
+                    notes: None,
+                    metadata: None,
+                },
+            )
+            .await?;
+
+        let mut recorded = Vec::new();
+        for m in work.matches().find_by_category(category_id).await? {
+            if !matches!(
+                m.match_status,
+                MatchStatus::Scheduled | MatchStatus::Postponed
+            ) {
+                continue;
+            }
+            let Some(side) = m.side_of(request.participant_id) else {
+                continue;
+            };
+            if m.participant_id(3 - side).is_none() {
+                continue;
+            }
+            let forfeit = Forfeit {
+                kind: ForfeitKind::Walkover,
+                defaulting_participant: Some(side),
+                reason: format!("withdrew ({})", request.reason),
+            };
+            recorded.extend(record_forfeit(work.as_ref(), &m, &forfeit).await?);
+        }
+        work.commit().await?;
+
+        let tournament_id = self.tournament_of(category_id).await;
+        self.events
+            .publish(RealtimeEvent::RegistrationUpdate {
+                registration_id: registration.id,
+                tournament_id,
+                category_id,
+                status: Some(format!("{:?}", RegistrationStatus::Withdrawn)),
+            })
+            .await;
+        let mut forfeited = Vec::with_capacity(recorded.len());
+        for recorded in recorded {
+            forfeited.push(self.publish_forfeit(recorded).await);
+        }
+        Ok(forfeited)
     }
 
     pub async fn postpone_match(&self, match_id: Uuid) -> Result<Option<Match>, AppError> {
-        self.match_repo.postpone_match(match_id).await
+        let m = self.match_repo.postpone_match(match_id).await?;
+        self.publish_status_change(&m).await;
+        Ok(m)
     }
 
     pub async fn reschedule_match(
         &self,
         match_id: Uuid,
-        request: RescheduleMatchRequest,
+        mut request: RescheduleMatchRequest,
+        allow_conflicts: bool,
     ) -> Result<Option<Match>, AppError> {
-        self.match_repo.reschedule_match(match_id, request).await
+        if let Some(court_id) = request.new_court_id {
+            let (venue, court) = self.court_location(court_id).await?;
+            request.new_venue = Some(venue);
+            request.new_court_number = Some(court);
+        }
+        if !allow_conflicts {
+            let Some(mut m) = self.match_repo.find_by_id(match_id).await? else {
+                return Ok(None);
+            };
+            m.scheduled_date = request.new_scheduled_date;
+            m.match_status = MatchStatus::Scheduled;
+            if request.new_venue.is_some() {
+                m.venue = request.new_venue.clone();
+            }
+            if request.new_court_number.is_some() {
+                m.court_number = request.new_court_number.clone();
+            }
+            if request.new_court_id.is_some() {
+                m.court_id = request.new_court_id;
+            }
+            self.ensure_no_conflicts(&[m], false).await?;
+        }
+        let m = self.match_repo.reschedule_match(match_id, request).await?;
+        self.publish_status_change(&m).await;
+        Ok(m)
     }
 
     // ==================== User-specific ====================
@@ -128,14 +781,16 @@ where
         &self,
         user_id: Uuid,
     ) -> Result<Vec<MatchScheduleItem>, AppError> {
-        self.match_repo.find_user_upcoming_matches(user_id).await
+        let items = self.match_repo.find_user_upcoming_matches(user_id).await?;
+        self.with_estimates(items).await
     }
 
     pub async fn get_user_match_history(
         &self,
         user_id: Uuid,
     ) -> Result<Vec<MatchScheduleItem>, AppError> {
-        self.match_repo.find_user_match_history(user_id).await
+        let items = self.match_repo.find_user_match_history(user_id).await?;
+        self.with_estimates(items).await
     }
 
     // ==================== Live ====================
@@ -149,16 +804,251 @@ where
         match_id: Uuid,
         update: LiveMatchUpdate,
     ) -> Result<Option<Match>, AppError> {
-        self.match_repo.update_live_match(match_id, update).await
+        let m = self.match_repo.update_live_match(match_id, update).await?;
+        if let Some(m) = &m {
+            self.publish_match_update(m, "live_update".to_string())
+                .await;
+        }
+        Ok(m)
+    }
+
+    // ==================== Live Scoring ====================
+
+    /// Scoring is up to the organizer of the match's tournament and the scorekeepers
+    /// they assigned to it
+    pub async fn ensure_can_score(&self, user_id: Uuid, match_id: Uuid) -> Result<(), AppError> {
+        let m = self
+            .match_repo
+            .find_by_id(match_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Match not found".to_string()))?;
+        let tournament = match self
+            .category_repo
+            .get_by_id(m.tournament_category_id)
+            .await?
+        {
+            Some(category) => {
+                self.tournament_repo
+                    .get_by_id(category.tournament_id)
+                    .await?
+            }
+            None => None,
+        };
+        let allowed = match tournament {
+            Some(t) if t.organizer_id == user_id => true,
+            Some(t) => self
+                .tournament_repo
+                .get_scorekeepers(t.id)
+                .await?
+                .contains(&user_id),
+            None => false,
+        };
+        if !allowed {
+            return Err(AppError::Forbidden(
+                "Only the tournament organizer or its scorekeepers can score this match"
+                    .to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Current score of a match scored point by point
+    pub async fn get_live_score(&self, match_id: Uuid) -> Result<LiveScore, AppError> {
+        let scoring = self.read_scoring(match_id).await?;
+        scoring.log.score(&scoring.rules)
+    }
+
+    /// Every scoring action recorded for a match, with the score they add up to
+    pub async fn get_scoring_log(&self, match_id: Uuid) -> Result<ScoringSnapshot, AppError> {
+        let scoring = self.read_scoring(match_id).await?;
+        Ok(ScoringSnapshot {
+            score: scoring.log.score(&scoring.rules)?,
+            entries: scoring.log.entries,
+        })
+    }
+
+    /// The scoring log as last committed; reads take no lock, unlike recording
+    async fn read_scoring(&self, match_id: Uuid) -> Result<ScoringContext, AppError> {
+        load_scoring(
+            self.match_repo.as_ref(),
+            self.category_repo.as_ref(),
+            self.tournament_repo.as_ref(),
+            self.result_repo.as_ref(),
+            match_id,
+        )
+        .await
+    }
+
+    /// Validates a scorekeeper action against the sport's rules and stores it in the
+    /// scoring data of the game it was recorded in, rederiving every game's score.
+    /// With `expected_sequence`, the action is rejected if others were recorded since
+    /// the scorekeeper last saw the score. The match row stays locked until the action is
+    /// saved, so concurrent scorekeepers take turns.
+    pub async fn record_scoring_action(
+        &self,
+        match_id: Uuid,
+        action: ScoringAction,
+        expected_sequence: Option<i64>,
+    ) -> Result<ScoringUpdate, AppError> {
+        let work = self.uow.begin().await?;
+        work.matches().lock(match_id).await?;
+        let ScoringContext {
+            m,
+            tournament_id,
+            rules,
+            mut log,
+            results,
+        } = load_scoring(
+            work.matches(),
+            work.categories(),
+            work.tournaments(),
+            work.match_results(),
+            match_id,
+        )
+        .await?;
+        if m.match_status != MatchStatus::InProgress {
+            return Err(AppError::ValidationError(
+                "Match is not in progress".to_string(),
+            ));
+        }
+        let current = log.entries.last().map_or(0, |e| e.sequence);
+        if expected_sequence.is_some_and(|seq| seq != current) {
+            return Err(AppError::Conflict(format!(
+                "Score has moved on to sequence {}",
+                current
+            )));
+        }
+
+        let (entry, score) = log.record(&rules, action)?;
+        let last_game = score.game_number().max(entry.game_number);
+        let mut entry_result = None;
+        for game_number in 1..=last_game {
+            let events: Vec<&ScoringEntry> = log
+                .entries
+                .iter()
+                .filter(|e| e.game_number == game_number)
+                .collect();
+            let game = score.games.get(game_number as usize - 1);
+            let (score1, score2) = game.map_or((0, 0), |g| (g.participant1, g.participant2));
+            let scoring_data = json!({ "events": events });
+            let saved = match results.iter().find(|r| r.set_number == Some(game_number)) {
+                Some(existing) => work
+                    .match_results()
+                    .update(
+                        existing.id,
+                        EditableMatchResult {
+                            participant1_score: Some(score1),
+                            participant2_score: Some(score2),
+                            scoring_data: Some(scoring_data),
+                            participant1_stats: None,
+                            participant2_stats: None,
+                        },
+                    )
+                    .await?
+                    .map(|r| r.id),
+                None if !events.is_empty() => Some(
+                    work.match_results()
+                        .create(NewMatchResult {
+                            match_id,
+                            set_number: Some(game_number),
+                            participant1_score: Some(score1),
+                            participant2_score: Some(score2),
+                            period_number: None,
+                            period_name: None,
+                            scoring_data: Some(scoring_data),
+                            participant1_stats: None,
+                            participant2_stats: None,
+                        })
+                        .await?
+                        .id,
+                ),
+                None => None,
+            };
+            if game_number == entry.game_number {
+                entry_result = saved;
+            }
+        }
+        // The primary key backs up the row lock
+        let claimed = match entry_result {
+            Some(result_id) => {
+                work.match_results()
+                    .claim_scoring_sequence(match_id, entry.sequence, result_id)
+                    .await?
+            }
+            None => false,
+        };
+        if !claimed {
+            return Err(AppError::Conflict(format!(
+                "Sequence {} has already been recorded",
+                entry.sequence
+            )));
+        }
+        work.commit().await?;
+
+        let update = ScoringUpdate {
+            match_id,
+            tournament_id,
+            category_id: m.tournament_category_id,
+            entry,
+            score,
+        };
+        self.events
+            .publish(RealtimeEvent::score_update(&update))
+            .await;
+        Ok(update)
     }
 
     // ==================== Analytics ====================
 
+    /// Analytics of live-scored matches are derived from their scoring log
     pub async fn get_match_analytics(
         &self,
         match_id: Uuid,
     ) -> Result<Option<MatchAnalytics>, AppError> {
-        self.match_repo.get_match_analytics(match_id).await
+        let recorded = self.match_repo.get_match_analytics(match_id).await?;
+        let scoring = match self.read_scoring(match_id).await {
+            Ok(scoring) if !scoring.log.entries.is_empty() => scoring,
+            Ok(_) | Err(AppError::BadRequest(_)) | Err(AppError::NotFound(_)) => {
+                return Ok(recorded)
+            }
+            Err(e) => return Err(e),
+        };
+
+        let score = scoring.log.score(&scoring.rules)?;
+        let stats = scoring.log.rally_stats(&scoring.rules)?;
+        let duration = recorded
+            .as_ref()
+            .and_then(|a| a.total_duration_minutes)
+            .or_else(|| {
+                let first = scoring.log.entries.first()?.recorded_at;
+                let last = scoring.log.entries.last()?.recorded_at;
+                Some((last - first).num_minutes() as i32)
+            });
+        let played: Vec<_> = score
+            .games
+            .iter()
+            .filter(|g| g.participant1 + g.participant2 > 0)
+            .collect();
+        let rally_stats = serde_json::to_value(&stats)
+            .map_err(|e| AppError::InternalError(format!("Failed to encode rally stats: {}", e)))?;
+
+        Ok(Some(MatchAnalytics {
+            match_id,
+            total_duration_minutes: duration,
+            sets_played: Some(score.games_played()),
+            participant1_score: Some(json!({
+                "games_won": score.participant1_games,
+                "points": stats.participant1.points_won,
+                "games": played.iter().map(|g| g.participant1).collect::<Vec<_>>(),
+            })),
+            participant2_score: Some(json!({
+                "games_won": score.participant2_games,
+                "points": stats.participant2.points_won,
+                "games": played.iter().map(|g| g.participant2).collect::<Vec<_>>(),
+            })),
+            rally_stats: Some(rally_stats),
+            performance_metrics: recorded.and_then(|a| a.performance_metrics),
+        }))
     }
 
     pub async fn get_match_statistics(
@@ -205,12 +1095,21 @@ where
 
     // ==================== Subscriptions ====================
 
+    /// Subscribes to everything unless `notification_preferences` narrows it down;
+    /// subscribing again replaces the preferences
     pub async fn subscribe_to_match(
         &self,
         match_id: Uuid,
         user_id: Uuid,
+        notification_preferences: Option<serde_json::Value>,
     ) -> Result<MatchSubscription, AppError> {
-        self.match_repo.subscribe_to_match(match_id, user_id).await
+        let preferences = match &notification_preferences {
+            Some(value) => SubscriptionPreferences::parse(value)?,
+            None => SubscriptionPreferences::default(),
+        };
+        self.match_repo
+            .subscribe_to_match(match_id, user_id, preferences.to_value())
+            .await
     }
 
     pub async fn unsubscribe_from_match(
@@ -225,28 +1124,62 @@ where
 
     // ==================== Bulk ====================
 
+    /// Updates every match or none of them. Refuses updates that leave matches clashing with
+    /// other matches or with each other unless `allow_conflicts` is set.
     pub async fn bulk_update_matches(
         &self,
         match_ids: Vec<Uuid>,
-        updates: EditableMatch,
+        mut updates: EditableMatch,
+        allow_conflicts: bool,
     ) -> Result<Vec<Match>, AppError> {
-        self.match_repo
+        self.assign_court(&mut updates).await?;
+        if !allow_conflicts {
+            let mut candidates = Vec::new();
+            for id in &match_ids {
+                if let Some(m) = self.match_repo.find_by_id(*id).await? {
+                    candidates.push(apply_update(m, &updates));
+                }
+            }
+            self.ensure_no_conflicts(&candidates, false).await?;
+        }
+        let work = self.uow.begin().await?;
+        let matches = work
+            .matches()
             .bulk_update_matches(match_ids, updates)
-            .await
+            .await?;
+        work.commit().await?;
+        for m in &matches {
+            self.publish_match_update(m, format!("{:?}", m.match_status))
+                .await;
+        }
+        Ok(matches)
     }
 
+    /// Cancels every match or none of them.
     pub async fn bulk_cancel_matches(
         &self,
         match_ids: Vec<Uuid>,
         reason: &str,
     ) -> Result<Vec<Match>, AppError> {
-        self.match_repo.bulk_cancel_matches(match_ids, reason).await
+        let work = self.uow.begin().await?;
+        let matches = work
+            .matches()
+            .bulk_cancel_matches(match_ids, reason)
+            .await?;
+        work.commit().await?;
+        for m in &matches {
+            self.publish_match_update(m, format!("{:?}", m.match_status))
+                .await;
+        }
+        Ok(matches)
     }
 
     // ==================== Match Results ====================
 
     pub async fn create_match_result(&self, data: NewMatchResult) -> Result<MatchResult, AppError> {
-        self.result_repo.create(data).await
+        let result = self.result_repo.create(data).await?;
+        self.publish_result_update(result.match_id).await;
+        Ok(result)
     }
 
     pub async fn get_match_result(&self, result_id: Uuid) -> Result<Option<MatchResult>, AppError> {
@@ -258,14 +1191,33 @@ where
         result_id: Uuid,
         data: EditableMatchResult,
     ) -> Result<Option<MatchResult>, AppError> {
-        self.result_repo.update(result_id, data).await
+        let derives_scores = data.participant1_score.is_some()
+            || data.participant2_score.is_some()
+            || data.scoring_data.is_some();
+        if derives_scores {
+            let existing = self.result_repo.find_by_id(result_id).await?;
+            if existing.as_ref().and_then(scoring_events).is_some() {
+                return Err(AppError::ValidationError(
+                    "Scores of live-scored games are derived from the scoring log".to_string(),
+                ));
+            }
+        }
+        let result = self.result_repo.update(result_id, data).await?;
+        if let Some(result) = &result {
+            self.publish_result_update(result.match_id).await;
+        }
+        Ok(result)
     }
 
     pub async fn delete_match_result(
         &self,
         result_id: Uuid,
     ) -> Result<Option<MatchResult>, AppError> {
-        self.result_repo.delete(result_id).await
+        let result = self.result_repo.delete(result_id).await?;
+        if let Some(result) = &result {
+            self.publish_result_update(result.match_id).await;
+        }
+        Ok(result)
     }
 
     pub async fn get_match_results(&self, match_id: Uuid) -> Result<Vec<MatchResult>, AppError> {
@@ -288,22 +1240,35 @@ where
     }
 
     pub async fn delete_all_match_results(&self, match_id: Uuid) -> Result<u64, AppError> {
-        self.result_repo.delete_by_match(match_id).await
+        let deleted = self.result_repo.delete_by_match(match_id).await?;
+        if deleted > 0 {
+            self.publish_result_update(match_id).await;
+        }
+        Ok(deleted)
     }
 
     pub async fn count_match_results(&self, match_id: Uuid) -> Result<i64, AppError> {
         self.result_repo.count_by_match(match_id).await
     }
 
+    /// Inserts all results or none of them.
     pub async fn bulk_create_match_results(
         &self,
         items: Vec<NewMatchResult>,
     ) -> Result<Vec<MatchResult>, AppError> {
+        let work = self.uow.begin().await?;
         let mut results = Vec::with_capacity(items.len());
         for item in items {
-            let r = self.result_repo.create(item).await?;
+            let r = work.match_results().create(item).await?;
             results.push(r);
         }
+        work.commit().await?;
+        let mut match_ids: Vec<Uuid> = results.iter().map(|r| r.match_id).collect();
+        match_ids.sort();
+        match_ids.dedup();
+        for match_id in match_ids {
+            self.publish_result_update(match_id).await;
+        }
         Ok(results)
     }
 
@@ -324,3 +1289,256 @@ where
         Ok(true)
     }
 }
+
+/// A forfeited match and the bracket its winner moved up in
+pub(crate) struct RecordedForfeit {
+    pub m: Match,
+    pub bracket: Option<TournamentBracket>,
+}
+
+/// Forfeits a match within `work`: records who defaulted, queues the result like a
+/// completed match's, and moves the winner on to the next match of the category's bracket
+pub(crate) async fn record_forfeit(
+    work: &dyn UnitOfWork,
+    m: &Match,
+    forfeit: &Forfeit,
+) -> Result<Option<RecordedForfeit>, AppError> {
+    let Some(forfeited) = work.matches().forfeit_match(m.id, forfeit).await? else {
+        return Ok(None);
+    };
+    let tournament_id = work
+        .categories()
+        .get_by_id(forfeited.tournament_category_id)
+        .await?
+        .map(|c| c.tournament_id);
+    work.outbox()
+        .enqueue(&DomainEvent::match_completed(&forfeited, tournament_id))
+        .await?;
+    let bracket = match forfeit.winner() {
+        Some(side) => advance_in_bracket(work, &forfeited, side).await?,
+        None => None,
+    };
+    Ok(Some(RecordedForfeit {
+        m: forfeited,
+        bracket,
+    }))
+}
+
+/// Marks the winning side as the winner of the match's bracket node and seats them in the
+/// node, and match, it feeds. Returns the bracket if the match is in it.
+async fn advance_in_bracket(
+    work: &dyn UnitOfWork,
+    m: &Match,
+    side: i32,
+) -> Result<Option<TournamentBracket>, AppError> {
+    let Some(winner_id) = m.participant_id(side) else {
+        return Ok(None);
+    };
+    let Some(bracket) = work
+        .brackets()
+        .get_by_category_id(m.tournament_category_id)
+        .await?
+    else {
+        return Ok(None);
+    };
+    let Some(data) = bracket.bracket_data.as_ref() else {
+        return Ok(None);
+    };
+    let mut nodes = bracket_nodes(data);
+    if !nodes.iter().any(|n| n.match_id == Some(m.id)) {
+        return Ok(None);
+    }
+    if let Some(advancement) = advance_winner(&mut nodes, m.id, winner_id) {
+        if let Some(next_match_id) = advancement.next_match_id {
+            let (team_id, player_id, partner_id) = if side == 1 {
+                (
+                    m.participant1_team_id,
+                    m.participant1_player_id,
+                    m.participant1_partner_id,
+                )
+            } else {
+                (
+                    m.participant2_team_id,
+                    m.participant2_player_id,
+                    m.participant2_partner_id,
+                )
+            };
+            work.matches()
+                .assign_participant(
+                    next_match_id,
+                    advancement.side,
+                    team_id,
+                    player_id,
+                    partner_id,
+                )
+                .await?;
+        }
+    }
+    work.brackets()
+        .update(
+            bracket.id,
+            EditableTournamentBracket {
+                status: None,
+                current_round: None,
+                bracket_data: Some(with_nodes(data, &nodes)),
+                settings: None,
+            },
+        )
+        .await
+}
+
+/// Records `MatchCompleted` in the outbox when a status change within `work` is the one
+/// that completed the match
+async fn enqueue_if_completed(
+    work: &dyn UnitOfWork,
+    before: Option<&Match>,
+    after: &Option<Match>,
+) -> Result<(), AppError> {
+    let was_completed = before.is_some_and(|m| m.match_status == MatchStatus::Completed);
+    match after {
+        Some(m) if m.match_status == MatchStatus::Completed && !was_completed => {
+            let tournament_id = work
+                .categories()
+                .get_by_id(m.tournament_category_id)
+                .await?
+                .map(|c| c.tournament_id);
+            work.outbox()
+                .enqueue(&DomainEvent::match_completed(m, tournament_id))
+                .await?;
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// A match being scored point by point
+struct ScoringContext {
+    m: Match,
+    tournament_id: Uuid,
+    rules: ScoringRules,
+    log: ScoringLog,
+    results: Vec<MatchResult>,
+}
+
+/// Loads a match with its tournament, scoring rules and the scoring log kept in its results
+async fn load_scoring<M, C, T, R>(
+    matches: &M,
+    categories: &C,
+    tournaments: &T,
+    match_results: &R,
+    match_id: Uuid,
+) -> Result<ScoringContext, AppError>
+where
+    M: MatchRepository + ?Sized,
+    C: TournamentCategoryRepository + ?Sized,
+    T: TournamentRepository + ?Sized,
+    R: MatchResultRepository + ?Sized,
+{
+    let m = matches
+        .find_by_id(match_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Match not found".to_string()))?;
+    let category = categories
+        .get_by_id(m.tournament_category_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Tournament category not found".to_string()))?;
+    let tournament = tournaments
+        .get_by_id(category.tournament_id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Tournament not found".to_string()))?;
+    let rules = ScoringRules::for_sport(tournament.sport_type).ok_or_else(|| {
+        AppError::BadRequest(format!(
+            "Live scoring is not supported for {:?}",
+            tournament.sport_type
+        ))
+    })?;
+
+    let results = match_results.find_by_match(match_id).await?;
+    let entries = results
+        .iter()
+        .filter_map(scoring_events)
+        .flatten()
+        .collect();
+    Ok(ScoringContext {
+        m,
+        tournament_id: tournament.id,
+        rules,
+        log: ScoringLog::new(entries),
+        results,
+    })
+}
+
+/// Scoring actions stored with a game's result, if it was scored live
+fn scoring_events(result: &MatchResult) -> Option<Vec<ScoringEntry>> {
+    let events = result.scoring_data.as_ref()?.get("events")?.clone();
+    serde_json::from_value(events).ok()
+}
+
+/// Whether a match takes up its participants, court and officials at its time
+fn holds_time(m: &Match) -> bool {
+    matches!(
+        m.match_status,
+        MatchStatus::Scheduled | MatchStatus::InProgress | MatchStatus::Completed
+    )
+}
+
+/// The match a `NewMatch` would create, for conflict checks before it is saved
+fn draft_match(data: &NewMatch) -> Match {
+    let now = Utc::now();
+    Match {
+        id: Uuid::nil(),
+        tournament_category_id: data.tournament_category_id,
+        participant1_team_id: data.participant1_team_id,
+        participant1_player_id: data.participant1_player_id,
+        participant1_partner_id: data.participant1_partner_id,
+        participant2_team_id: data.participant2_team_id,
+        participant2_player_id: data.participant2_player_id,
+        participant2_partner_id: data.participant2_partner_id,
+        match_type: data.match_type,
+        match_status: MatchStatus::Scheduled,
+        round_number: data.round_number,
+        match_number: data.match_number,
+        scheduled_date: data.scheduled_date,
+        actual_start_date: None,
+        actual_end_date: None,
+        venue: data.venue.clone(),
+        court_number: data.court_number.clone(),
+        court_id: data.court_id,
+        winner_participant: None,
+        is_draw: false,
+        forfeit_kind: None,
+        defaulting_participant: None,
+        referee_name: data.referee_name.clone(),
+        umpire_name: data.umpire_name.clone(),
+        notes: None,
+        metadata: None,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+/// A match as it would be after `updates`
+fn apply_update(mut m: Match, updates: &EditableMatch) -> Match {
+    if let Some(status) = updates.match_status {
+        m.match_status = status;
+    }
+    if let Some(scheduled_date) = updates.scheduled_date {
+        m.scheduled_date = scheduled_date;
+    }
+    if updates.venue.is_some() {
+        m.venue = updates.venue.clone();
+    }
+    if updates.court_number.is_some() {
+        m.court_number = updates.court_number.clone();
+    }
+    if updates.court_id.is_some() {
+        m.court_id = updates.court_id;
+    }
+    if updates.referee_name.is_some() {
+        m.referee_name = updates.referee_name.clone();
+    }
+    if updates.umpire_name.is_some() {
+        m.umpire_name = updates.umpire_name.clone();
+    }
+    m
+}
@@ -1,19 +1,42 @@
 // Application layer - services (orchestrate domain logic)
 
 pub mod auth_services;
+pub mod calendar_services;
+pub mod check_in_services;
+pub mod court_queue_services;
+pub mod delay_services;
+pub mod event_subscribers;
+pub mod export_services;
+pub mod import_services;
 pub mod match_services;
 pub mod notification_services;
+pub mod outbox_services;
 pub mod participant_services;
 pub mod payment_services;
+pub mod reminder_services;
+pub mod scheduling_services;
 pub mod statistics_services;
+pub mod ticket_services;
 pub mod tournament_services;
 pub mod user_services;
+pub mod venue_services;
 
 pub use auth_services::AuthServices;
+pub use calendar_services::CalendarServices;
+pub use check_in_services::CheckInServices;
+pub use court_queue_services::CourtQueueServices;
+pub use delay_services::DelayNotifier;
+pub use export_services::ExportServices;
+pub use import_services::ImportServices;
 pub use match_services::MatchServices;
 pub use notification_services::NotificationServices;
+pub use outbox_services::OutboxDispatcher;
 pub use participant_services::ParticipantServices;
 pub use payment_services::PaymentServices;
+pub use reminder_services::ReminderScheduler;
+pub use scheduling_services::SchedulingServices;
 pub use statistics_services::StatisticsServices;
+pub use ticket_services::TicketServices;
 pub use tournament_services::TournamentServices;
 pub use user_services::UserServices;
+pub use venue_services::VenueServices;
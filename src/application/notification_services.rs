@@ -1,23 +1,106 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::domain::notification::{NewNotification, Notification, NotificationRepository};
+use crate::domain::notification::{
+    DeliveryChannel, NewNotification, NewPushSubscription, Notification, NotificationDelivery,
+    NotificationDeliveryRepository, NotificationPreferences, NotificationRepository,
+    NotificationType, PushSubscription, PushSubscriptionRepository,
+};
+use crate::domain::outbox::DomainEvent;
+use crate::domain::realtime::{EventPublisher, RealtimeEvent};
+use crate::domain::unit_of_work::{UnitOfWork, UnitOfWorkFactory};
+use crate::domain::user::{NewUserProfile, UserProfileRepository};
 use crate::shared::AppError;
 
-/// Notification domain services
-pub struct NotificationServices<R>
+/// Notification domain services. Every send path consults the recipient's
+/// `NotificationPreferences`: notifications nobody would receive are dropped, and ones
+/// the user only wants by email or push are kept out of the unread inbox.
+pub struct NotificationServices<R, D, S, P, U>
 where
-    R: NotificationRepository,
+    R: NotificationRepository + ?Sized,
+    D: NotificationDeliveryRepository + ?Sized,
+    S: PushSubscriptionRepository + ?Sized,
+    P: UserProfileRepository + ?Sized,
+    U: UnitOfWorkFactory + ?Sized,
 {
     notification_repo: Arc<R>,
+    delivery_repo: Arc<D>,
+    push_subscription_repo: Arc<S>,
+    profile_repo: Arc<P>,
+    uow: Arc<U>,
+    events: Arc<dyn EventPublisher>,
 }
 
-impl<R> NotificationServices<R>
+impl<R, D, S, P, U> NotificationServices<R, D, S, P, U>
 where
-    R: NotificationRepository,
+    R: NotificationRepository + ?Sized,
+    D: NotificationDeliveryRepository + ?Sized,
+    S: PushSubscriptionRepository + ?Sized,
+    P: UserProfileRepository + ?Sized,
+    U: UnitOfWorkFactory + ?Sized,
 {
-    pub fn new(notification_repo: Arc<R>) -> Self {
-        Self { notification_repo }
+    pub fn new(
+        notification_repo: Arc<R>,
+        delivery_repo: Arc<D>,
+        push_subscription_repo: Arc<S>,
+        profile_repo: Arc<P>,
+        uow: Arc<U>,
+        events: Arc<dyn EventPublisher>,
+    ) -> Self {
+        Self {
+            notification_repo,
+            delivery_repo,
+            push_subscription_repo,
+            profile_repo,
+            uow,
+            events,
+        }
+    }
+
+    /// The user's preferences; defaults when they have no profile yet
+    pub async fn get_preferences(
+        &self,
+        user_id: Uuid,
+    ) -> Result<NotificationPreferences, AppError> {
+        let profile = self.profile_repo.find_by_user_id(user_id).await?;
+        Ok(NotificationPreferences::from_value(
+            profile
+                .as_ref()
+                .and_then(|p| p.notification_preferences.as_ref()),
+        ))
+    }
+
+    /// Marks a new notification read when the user turned the inbox off for it, and
+    /// records `NotificationCreated` so the other channels pick it up
+    async fn record_created(
+        &self,
+        work: &dyn UnitOfWork,
+        notification: Notification,
+        in_app: bool,
+    ) -> Result<Notification, AppError> {
+        let notification = if in_app {
+            notification
+        } else {
+            work.notifications()
+                .mark_as_read(notification.id)
+                .await?
+                .unwrap_or(notification)
+        };
+        work.outbox()
+            .enqueue(&DomainEvent::notification_created(&notification))
+            .await?;
+        Ok(notification)
+    }
+
+    async fn publish_created(&self, notification: &Notification) {
+        self.events
+            .publish(RealtimeEvent::Notification {
+                user_id: notification.user_id,
+                notification_id: notification.id,
+            })
+            .await;
     }
 
     pub async fn get_notifications(
@@ -46,11 +129,29 @@ where
         &self,
         notification_id: Uuid,
     ) -> Result<Option<Notification>, AppError> {
-        self.notification_repo.mark_as_read(notification_id).await
+        let notification = self.notification_repo.mark_as_read(notification_id).await?;
+        if let Some(notification) = &notification {
+            self.events
+                .publish(RealtimeEvent::NotificationsRead {
+                    user_id: notification.user_id,
+                    notification_id: Some(notification.id),
+                })
+                .await;
+        }
+        Ok(notification)
     }
 
     pub async fn mark_all_as_read(&self, user_id: Uuid) -> Result<u64, AppError> {
-        self.notification_repo.mark_all_as_read(user_id).await
+        let updated = self.notification_repo.mark_all_as_read(user_id).await?;
+        if updated > 0 {
+            self.events
+                .publish(RealtimeEvent::NotificationsRead {
+                    user_id,
+                    notification_id: None,
+                })
+                .await;
+        }
+        Ok(updated)
     }
 
     pub async fn delete_notification(
@@ -64,15 +165,100 @@ where
         self.notification_repo.get_unread_count(user_id).await
     }
 
-    pub async fn send_notification(&self, data: NewNotification) -> Result<Notification, AppError> {
-        self.notification_repo.create(data).await
+    /// Writes the notification and records `NotificationCreated` in the outbox, which
+    /// hands it to the other delivery channels. `None` when the user opted out of it on
+    /// every channel.
+    pub async fn send_notification(
+        &self,
+        data: NewNotification,
+    ) -> Result<Option<Notification>, AppError> {
+        self.send_notification_once(data, &[]).await
     }
 
+    /// Like `send_notification`, but skipped when every one of `dedup_keys` was already
+    /// claimed for the user; claiming and sending happen in one transaction
+    pub async fn send_notification_once(
+        &self,
+        data: NewNotification,
+        dedup_keys: &[String],
+    ) -> Result<Option<Notification>, AppError> {
+        let preferences = self.get_preferences(data.user_id).await?;
+        if !preferences.wants(&data.notification_type, data.tournament_id) {
+            return Ok(None);
+        }
+        let in_app = preferences.allows(
+            DeliveryChannel::InApp,
+            &data.notification_type,
+            data.tournament_id,
+        );
+
+        let work = self.uow.begin().await?;
+        let mut claimed = dedup_keys.is_empty();
+        for key in dedup_keys {
+            claimed |= work
+                .notifications()
+                .claim_dedup_key(data.user_id, key)
+                .await?;
+        }
+        if !claimed {
+            work.rollback().await?;
+            return Ok(None);
+        }
+        let notification = work.notifications().create(data).await?;
+        let notification = self
+            .record_created(work.as_ref(), notification, in_app)
+            .await?;
+        work.commit().await?;
+        if in_app {
+            self.publish_created(&notification).await;
+        }
+        Ok(Some(notification))
+    }
+
+    /// Sends what each recipient's preferences allow; returns the notifications created
     pub async fn send_bulk_notifications(
         &self,
         notifications: Vec<NewNotification>,
     ) -> Result<Vec<Notification>, AppError> {
-        self.notification_repo.create_bulk(notifications).await
+        let mut preferences = HashMap::new();
+        for notification in &notifications {
+            if let Entry::Vacant(entry) = preferences.entry(notification.user_id) {
+                entry.insert(self.get_preferences(notification.user_id).await?);
+            }
+        }
+        let wanted: Vec<NewNotification> = notifications
+            .into_iter()
+            .filter(|n| preferences[&n.user_id].wants(&n.notification_type, n.tournament_id))
+            .collect();
+        if wanted.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let work = self.uow.begin().await?;
+        let created = work.notifications().create_bulk(wanted).await?;
+        let mut sent = Vec::with_capacity(created.len());
+        for notification in created {
+            let in_app = preferences[&notification.user_id].allows(
+                DeliveryChannel::InApp,
+                &notification.notification_type,
+                notification.tournament_id,
+            );
+            sent.push((
+                self.record_created(work.as_ref(), notification, in_app)
+                    .await?,
+                in_app,
+            ));
+        }
+        work.commit().await?;
+        for (notification, in_app) in &sent {
+            if *in_app {
+                self.publish_created(notification).await;
+            }
+        }
+        Ok(sent
+            .into_iter()
+            .map(|(notification, _)| notification)
+            .collect())
     }
 
     pub async fn get_notification(
@@ -81,4 +267,70 @@ where
     ) -> Result<Option<Notification>, AppError> {
         self.notification_repo.get_by_id(notification_id).await
     }
+
+    /// Delivery status per channel of one of the user's notifications; `None` when the
+    /// notification does not exist or belongs to someone else
+    pub async fn get_deliveries(
+        &self,
+        user_id: Uuid,
+        notification_id: Uuid,
+    ) -> Result<Option<Vec<NotificationDelivery>>, AppError> {
+        match self.notification_repo.get_by_id(notification_id).await? {
+            Some(notification) if notification.user_id == user_id => Ok(Some(
+                self.delivery_repo
+                    .find_by_notification(notification_id)
+                    .await?,
+            )),
+            _ => Ok(None),
+        }
+    }
+
+    /// Registers a browser for Web Push; subscribing again refreshes its keys
+    pub async fn subscribe_push(
+        &self,
+        subscription: NewPushSubscription,
+    ) -> Result<PushSubscription, AppError> {
+        subscription.validate()?;
+        self.push_subscription_repo.upsert(subscription).await
+    }
+
+    pub async fn unsubscribe_push(&self, user_id: Uuid, endpoint: &str) -> Result<bool, AppError> {
+        self.push_subscription_repo
+            .delete_for_user(user_id, endpoint)
+            .await
+    }
+
+    pub async fn update_preferences(
+        &self,
+        user_id: Uuid,
+        preferences: NotificationPreferences,
+    ) -> Result<NotificationPreferences, AppError> {
+        let value = preferences.to_value();
+        let updated = self
+            .profile_repo
+            .update_notification_preferences(user_id, value.clone())
+            .await?;
+        if updated.is_none() {
+            self.profile_repo
+                .create(NewUserProfile {
+                    user_id,
+                    notification_preferences: Some(value),
+                    ..NewUserProfile::default()
+                })
+                .await?;
+        }
+        Ok(preferences)
+    }
+
+    /// Turns email off for one notification type, or for everything; used by the
+    /// unsubscribe link in each email
+    pub async fn unsubscribe_email(
+        &self,
+        user_id: Uuid,
+        notification_type: Option<NotificationType>,
+    ) -> Result<NotificationPreferences, AppError> {
+        let mut preferences = self.get_preferences(user_id).await?;
+        preferences.opt_out(DeliveryChannel::Email, notification_type);
+        self.update_preferences(user_id, preferences).await
+    }
 }
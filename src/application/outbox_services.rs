@@ -0,0 +1,120 @@
+use chrono::Utc;
+use std::sync::Arc;
+
+use crate::domain::outbox::{DomainEventSubscriber, OutboxMessage, OutboxRepository};
+use crate::shared::config::OutboxConfig;
+use crate::shared::AppError;
+
+/// Delivers outbox events to in-process subscribers, at least once. Each subscriber
+/// that handles an event is recorded, so a retry only runs the ones that failed.
+pub struct OutboxDispatcher<O>
+where
+    O: OutboxRepository + ?Sized,
+{
+    outbox: Arc<O>,
+    subscribers: Vec<Arc<dyn DomainEventSubscriber>>,
+    config: OutboxConfig,
+}
+
+impl<O> OutboxDispatcher<O>
+where
+    O: OutboxRepository + ?Sized,
+{
+    pub fn new(outbox: Arc<O>, config: OutboxConfig) -> Self {
+        Self {
+            outbox,
+            subscribers: Vec::new(),
+            config,
+        }
+    }
+
+    pub fn subscribe(mut self, subscriber: Arc<dyn DomainEventSubscriber>) -> Self {
+        self.subscribers.push(subscriber);
+        self
+    }
+
+    /// Claims and delivers one batch of due events; returns how many were claimed
+    pub async fn dispatch_batch(&self) -> Result<usize, AppError> {
+        let lease_until = Utc::now()
+            + chrono::Duration::from_std(self.config.lease).unwrap_or(chrono::Duration::minutes(1));
+        let messages = self
+            .outbox
+            .claim_due(self.config.batch_size, lease_until)
+            .await?;
+        for message in &messages {
+            self.deliver(message).await?;
+        }
+        Ok(messages.len())
+    }
+
+    /// Polls the outbox until the process exits, draining full batches back to back
+    pub async fn run(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(self.config.poll_interval);
+        loop {
+            interval.tick().await;
+            loop {
+                match self.dispatch_batch().await {
+                    Ok(claimed) if claimed as i64 >= self.config.batch_size => continue,
+                    Ok(_) => break,
+                    Err(e) => {
+                        eprintln!("Outbox dispatch failed: {}", e);
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    async fn deliver(&self, message: &OutboxMessage) -> Result<(), AppError> {
+        let event = match message.event() {
+            Ok(event) => event,
+            // Retrying will not make it decodable
+            Err(e) => {
+                return self
+                    .outbox
+                    .mark_failed(message.id, &message.delivered_to, &e.to_string(), None)
+                    .await
+            }
+        };
+
+        let mut delivered = message.delivered_to.clone();
+        let mut errors = Vec::new();
+        for subscriber in &self.subscribers {
+            let name = subscriber.name();
+            if delivered.iter().any(|d| d == name) {
+                continue;
+            }
+            match subscriber.handle(&event).await {
+                Ok(()) => delivered.push(name.to_string()),
+                Err(e) => errors.push(format!("{}: {}", name, e)),
+            }
+        }
+
+        if errors.is_empty() {
+            return self.outbox.mark_processed(message.id, &delivered).await;
+        }
+        let error = errors.join("; ");
+        let retry_at = (message.attempts < self.config.max_attempts)
+            .then(|| Utc::now() + self.retry_delay(message.attempts));
+        if retry_at.is_none() {
+            eprintln!(
+                "Outbox event {} ({}) failed after {} attempts: {}",
+                message.id, message.event_type, message.attempts, error
+            );
+        }
+        self.outbox
+            .mark_failed(message.id, &delivered, &error, retry_at)
+            .await
+    }
+
+    /// Exponential backoff: `retry_base` after the first attempt, doubling up to an hour
+    fn retry_delay(&self, attempts: i32) -> chrono::Duration {
+        let factor = 2u32.saturating_pow(attempts.saturating_sub(1).max(0) as u32);
+        let delay = self
+            .config
+            .retry_base
+            .saturating_mul(factor)
+            .min(std::time::Duration::from_secs(3600));
+        chrono::Duration::from_std(delay).unwrap_or(chrono::Duration::hours(1))
+    }
+}
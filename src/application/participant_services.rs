@@ -11,9 +11,9 @@ use crate::shared::AppError;
 /// Participant domain services (players and teams)
 pub struct ParticipantServices<P, T, M>
 where
-    P: PlayerRepository,
-    T: TeamRepository,
-    M: TeamMemberRepository,
+    P: PlayerRepository + ?Sized,
+    T: TeamRepository + ?Sized,
+    M: TeamMemberRepository + ?Sized,
 {
     player_repo: Arc<P>,
     team_repo: Arc<T>,
@@ -22,9 +22,9 @@ where
 
 impl<P, T, M> ParticipantServices<P, T, M>
 where
-    P: PlayerRepository,
-    T: TeamRepository,
-    M: TeamMemberRepository,
+    P: PlayerRepository + ?Sized,
+    T: TeamRepository + ?Sized,
+    M: TeamMemberRepository + ?Sized,
 {
     pub fn new(player_repo: Arc<P>, team_repo: Arc<T>, member_repo: Arc<M>) -> Self {
         Self {
@@ -2,25 +2,30 @@ use rust_decimal::Decimal;
 use std::sync::Arc;
 use uuid::Uuid;
 
+use crate::domain::outbox::DomainEvent;
 use crate::domain::payment::{
     NewPayment, Payment, PaymentRepository, PaymentStatus, PaymentSummary,
 };
+use crate::domain::unit_of_work::UnitOfWorkFactory;
 use crate::shared::AppError;
 
 /// Payment domain services
-pub struct PaymentServices<R>
+pub struct PaymentServices<R, U>
 where
-    R: PaymentRepository,
+    R: PaymentRepository + ?Sized,
+    U: UnitOfWorkFactory + ?Sized,
 {
     payment_repo: Arc<R>,
+    uow: Arc<U>,
 }
 
-impl<R> PaymentServices<R>
+impl<R, U> PaymentServices<R, U>
 where
-    R: PaymentRepository,
+    R: PaymentRepository + ?Sized,
+    U: UnitOfWorkFactory + ?Sized,
 {
-    pub fn new(payment_repo: Arc<R>) -> Self {
-        Self { payment_repo }
+    pub fn new(payment_repo: Arc<R>, uow: Arc<U>) -> Self {
+        Self { payment_repo, uow }
     }
 
     pub async fn process_payment(&self, data: NewPayment) -> Result<Payment, AppError> {
@@ -53,12 +58,28 @@ where
             .await
     }
 
+    /// Completing a payment records `PaymentSucceeded` in the outbox with the change
     pub async fn update_payment_status(
         &self,
         payment_id: Uuid,
         status: PaymentStatus,
     ) -> Result<Option<Payment>, AppError> {
-        self.payment_repo.update_status(payment_id, status).await
+        let work = self.uow.begin().await?;
+        let before = work.payments().find_by_id(payment_id).await?;
+        let payment = work.payments().update_status(payment_id, status).await?;
+        let succeeded = payment.as_ref().filter(|p| {
+            p.status == PaymentStatus::Completed
+                && before
+                    .as_ref()
+                    .is_some_and(|b| b.status != PaymentStatus::Completed)
+        });
+        if let Some(payment) = succeeded {
+            work.outbox()
+                .enqueue(&DomainEvent::payment_succeeded(payment))
+                .await?;
+        }
+        work.commit().await?;
+        Ok(payment)
     }
 
     pub async fn refund_payment(
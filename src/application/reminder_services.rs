@@ -0,0 +1,277 @@
+use chrono::{DateTime, Duration, Timelike, Utc};
+use chrono_tz::Tz;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::NotificationServices;
+use crate::domain::match_domain::{MatchRepository, MatchScheduleItem};
+use crate::domain::notification::{
+    NewNotification, NotificationDeliveryRepository, NotificationPreferences,
+    NotificationRepository, NotificationType, PushSubscriptionRepository, ReminderMode,
+    SubscriptionPreferences,
+};
+use crate::domain::unit_of_work::UnitOfWorkFactory;
+use crate::domain::user::UserProfileRepository;
+use crate::shared::config::ReminderConfig;
+use crate::shared::AppError;
+
+/// How far ahead a daily digest looks
+const DIGEST_WINDOW_HOURS: i64 = 24;
+
+/// Reminds players, team members and subscribers of their upcoming matches, either
+/// at each configured offset or as one daily digest. Every reminder is claimed under
+/// a dedup key, so rescans and restarts never send it twice.
+pub struct ReminderScheduler<M, F, N, D, S, U>
+where
+    M: MatchRepository + ?Sized,
+    F: UserProfileRepository + ?Sized,
+    N: NotificationRepository + ?Sized,
+    D: NotificationDeliveryRepository + ?Sized,
+    S: PushSubscriptionRepository + ?Sized,
+    U: UnitOfWorkFactory + ?Sized,
+{
+    match_repo: Arc<M>,
+    profile_repo: Arc<F>,
+    notifications: Arc<NotificationServices<N, D, S, F, U>>,
+    config: ReminderConfig,
+}
+
+impl<M, F, N, D, S, U> ReminderScheduler<M, F, N, D, S, U>
+where
+    M: MatchRepository + ?Sized,
+    F: UserProfileRepository + ?Sized,
+    N: NotificationRepository + ?Sized,
+    D: NotificationDeliveryRepository + ?Sized,
+    S: PushSubscriptionRepository + ?Sized,
+    U: UnitOfWorkFactory + ?Sized,
+{
+    pub fn new(
+        match_repo: Arc<M>,
+        profile_repo: Arc<F>,
+        notifications: Arc<NotificationServices<N, D, S, F, U>>,
+        config: ReminderConfig,
+    ) -> Self {
+        Self {
+            match_repo,
+            profile_repo,
+            notifications,
+            config,
+        }
+    }
+
+    /// Sends the reminders and digests due at `now`; returns how many notifications
+    /// went out. Users inside their quiet hours are left for a later scan.
+    pub async fn scan(&self, now: DateTime<Utc>) -> Result<usize, AppError> {
+        let horizon = self
+            .config
+            .offsets
+            .iter()
+            .filter_map(|offset| Duration::from_std(*offset).ok())
+            .max()
+            .unwrap_or_default()
+            .max(Duration::hours(DIGEST_WINDOW_HOURS));
+        let upcoming = self
+            .match_repo
+            .find_scheduled_between(now, now + horizon)
+            .await?;
+
+        let mut by_user: BTreeMap<Uuid, Vec<&MatchScheduleItem>> = BTreeMap::new();
+        for item in &upcoming {
+            let mut audience = self.match_repo.find_participant_users(item.id).await?;
+            audience.extend(
+                self.match_repo
+                    .find_subscriptions(item.id)
+                    .await?
+                    .into_iter()
+                    .filter(|s| {
+                        SubscriptionPreferences::from_value(&s.notification_preferences)
+                            .wants(&NotificationType::MatchReminder)
+                    })
+                    .map(|s| s.user_id),
+            );
+            audience.sort();
+            audience.dedup();
+            for user_id in audience {
+                by_user.entry(user_id).or_default().push(item);
+            }
+        }
+
+        let mut sent = 0;
+        for (user_id, matches) in by_user {
+            let profile = self.profile_repo.find_by_user_id(user_id).await?;
+            let timezone = profile
+                .as_ref()
+                .and_then(|p| p.timezone.as_deref())
+                .and_then(|tz| tz.parse::<Tz>().ok())
+                .unwrap_or(Tz::UTC);
+            let preferences = NotificationPreferences::from_value(
+                profile
+                    .as_ref()
+                    .and_then(|p| p.notification_preferences.as_ref()),
+            );
+            let local_now = now.with_timezone(&timezone);
+            if preferences
+                .quiet_hours
+                .is_some_and(|quiet| quiet.contains(local_now.time()))
+            {
+                continue;
+            }
+
+            sent += match preferences.reminder_mode() {
+                ReminderMode::Individual => {
+                    self.send_reminders(user_id, &matches, now, timezone)
+                        .await?
+                }
+                ReminderMode::Digest => {
+                    self.send_digest(user_id, &matches, &preferences, now, timezone)
+                        .await?
+                }
+            };
+        }
+        Ok(sent)
+    }
+
+    /// Scans on every poll until the process exits
+    pub async fn run(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(self.config.poll_interval);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.scan(Utc::now()).await {
+                eprintln!("Reminder scan failed: {}", e);
+            }
+        }
+    }
+
+    /// One reminder per match, covering every offset that has come due since the last
+    /// one; the keys include the start time so a rescheduled match is reminded afresh
+    async fn send_reminders(
+        &self,
+        user_id: Uuid,
+        matches: &[&MatchScheduleItem],
+        now: DateTime<Utc>,
+        timezone: Tz,
+    ) -> Result<usize, AppError> {
+        let mut sent = 0;
+        for item in matches {
+            let keys: Vec<String> = self
+                .config
+                .offsets
+                .iter()
+                .filter(|offset| {
+                    Duration::from_std(**offset)
+                        .is_ok_and(|offset| now >= item.scheduled_date - offset)
+                })
+                .map(|offset| {
+                    format!(
+                        "match:{}:{}:{}",
+                        item.id,
+                        item.scheduled_date.timestamp(),
+                        offset.as_secs() / 60
+                    )
+                })
+                .collect();
+            if keys.is_empty() {
+                continue;
+            }
+
+            let reminder = NewNotification {
+                user_id,
+                title: format!(
+                    "Upcoming match: {} vs {}",
+                    item.participant1_name, item.participant2_name
+                ),
+                message: format!(
+                    "{} - {} starts on {}{}.",
+                    item.tournament_name,
+                    item.category_name,
+                    local_start(item, timezone),
+                    location(item)
+                ),
+                notification_type: NotificationType::MatchReminder,
+                tournament_id: Some(item.tournament_id),
+                match_id: Some(item.id),
+            };
+            if self
+                .notifications
+                .send_notification_once(reminder, &keys)
+                .await?
+                .is_some()
+            {
+                sent += 1;
+            }
+        }
+        Ok(sent)
+    }
+
+    /// The day's summary, sent once per local date from `digest_hour` onwards
+    async fn send_digest(
+        &self,
+        user_id: Uuid,
+        matches: &[&MatchScheduleItem],
+        preferences: &NotificationPreferences,
+        now: DateTime<Utc>,
+        timezone: Tz,
+    ) -> Result<usize, AppError> {
+        let local_now = now.with_timezone(&timezone);
+        if local_now.hour() < self.config.digest_hour {
+            return Ok(0);
+        }
+        let until = now + Duration::hours(DIGEST_WINDOW_HOURS);
+        let lines: Vec<String> = matches
+            .iter()
+            .filter(|item| item.scheduled_date < until)
+            .filter(|item| !preferences.muted_tournaments.contains(&item.tournament_id))
+            .map(|item| {
+                format!(
+                    "{}: {} vs {} ({} - {}){}",
+                    local_start(item, timezone),
+                    item.participant1_name,
+                    item.participant2_name,
+                    item.tournament_name,
+                    item.category_name,
+                    location(item)
+                )
+            })
+            .collect();
+        if lines.is_empty() {
+            return Ok(0);
+        }
+
+        let digest = NewNotification {
+            user_id,
+            title: match lines.len() {
+                1 => "Your match in the next 24 hours".to_string(),
+                n => format!("Your {} matches in the next 24 hours", n),
+            },
+            message: lines.join("\n"),
+            notification_type: NotificationType::MatchReminder,
+            tournament_id: None,
+            match_id: None,
+        };
+        let key = format!("digest:{}", local_now.date_naive());
+        Ok(self
+            .notifications
+            .send_notification_once(digest, &[key])
+            .await?
+            .map_or(0, |_| 1))
+    }
+}
+
+/// Start time in the user's timezone, e.g. "Sat 14 Mar 10:30 CET"
+fn local_start(item: &MatchScheduleItem, timezone: Tz) -> String {
+    item.scheduled_date
+        .with_timezone(&timezone)
+        .format("%a %-d %b %H:%M %Z")
+        .to_string()
+}
+
+/// " at Central Park, court 3", or as much of it as is known
+fn location(item: &MatchScheduleItem) -> String {
+    match (&item.venue, &item.court_number) {
+        (Some(venue), Some(court)) => format!(" at {}, court {}", venue, court),
+        (Some(venue), None) => format!(" at {}", venue),
+        (None, Some(court)) => format!(" on court {}", court),
+        (None, None) => String::new(),
+    }
+}
@@ -0,0 +1,362 @@
+use chrono::Duration;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::match_services::{find_conflicts, SlotLookups};
+use crate::domain::match_domain::{EditableMatch, Match, MatchRepository, MatchStatus};
+use crate::domain::participant::TeamMemberRepository;
+use crate::domain::realtime::{EventPublisher, RealtimeEvent};
+use crate::domain::scheduling::{
+    estimated_duration, occupied_interval, stage_of, Booking, PendingMatch, SchedulePlanner,
+    ScheduleReport, ScheduleRequest,
+};
+use crate::domain::tournament::{
+    SportType, TournamentCategory, TournamentCategoryRepository, TournamentRepository,
+};
+use crate::domain::unit_of_work::UnitOfWorkFactory;
+use crate::domain::venue::{Court, Venue, VenueRepository};
+use crate::shared::AppError;
+
+const DEFAULT_REST_MINUTES: i64 = 30;
+const DEFAULT_SLOT_MINUTES: i64 = 5;
+
+/// Automatic scheduling - assigns courts and start times to a tournament's matches
+pub struct SchedulingServices<T, C, M, V, P, U>
+where
+    T: TournamentRepository + ?Sized,
+    C: TournamentCategoryRepository + ?Sized,
+    M: MatchRepository + ?Sized,
+    V: VenueRepository + ?Sized,
+    P: TeamMemberRepository + ?Sized,
+    U: UnitOfWorkFactory + ?Sized,
+{
+    tournament_repo: Arc<T>,
+    category_repo: Arc<C>,
+    match_repo: Arc<M>,
+    venue_repo: Arc<V>,
+    team_member_repo: Arc<P>,
+    uow: Arc<U>,
+    events: Arc<dyn EventPublisher>,
+}
+
+impl<T, C, M, V, P, U> SchedulingServices<T, C, M, V, P, U>
+where
+    T: TournamentRepository + ?Sized,
+    C: TournamentCategoryRepository + ?Sized,
+    M: MatchRepository + ?Sized,
+    V: VenueRepository + ?Sized,
+    P: TeamMemberRepository + ?Sized,
+    U: UnitOfWorkFactory + ?Sized,
+{
+    pub fn new(
+        tournament_repo: Arc<T>,
+        category_repo: Arc<C>,
+        match_repo: Arc<M>,
+        venue_repo: Arc<V>,
+        team_member_repo: Arc<P>,
+        uow: Arc<U>,
+        events: Arc<dyn EventPublisher>,
+    ) -> Self {
+        Self {
+            tournament_repo,
+            category_repo,
+            match_repo,
+            venue_repo,
+            team_member_repo,
+            uow,
+            events,
+        }
+    }
+
+    /// Proposes courts and start times for the tournament's scheduled matches that are not yet
+    /// on a court and, unless this is a dry run, saves them in one transaction. Nothing is
+    /// saved when any match could not be placed or the proposal clashes with another match.
+    pub async fn schedule_tournament(
+        &self,
+        tournament_id: Uuid,
+        request: ScheduleRequest,
+        dry_run: bool,
+    ) -> Result<ScheduleReport, AppError> {
+        let tournament = self
+            .tournament_repo
+            .get_by_id(tournament_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Tournament not found".into()))?;
+        let starts_at = request.starts_at.unwrap_or(tournament.start_date);
+        let ends_at = request.ends_at.unwrap_or(tournament.end_date);
+        if ends_at <= starts_at {
+            return Err(AppError::ValidationError(
+                "The scheduling window must end after it starts".into(),
+            ));
+        }
+        let rest_minutes = request.min_rest_minutes.unwrap_or(DEFAULT_REST_MINUTES);
+        let slot_minutes = request.slot_minutes.unwrap_or(DEFAULT_SLOT_MINUTES);
+        if rest_minutes < 0 || !(1..=240).contains(&slot_minutes) {
+            return Err(AppError::ValidationError(
+                "min_rest_minutes must not be negative and slot_minutes must be 1 to 240".into(),
+            ));
+        }
+
+        let categories: HashMap<Uuid, TournamentCategory> = self
+            .category_repo
+            .get_by_tournament(tournament_id)
+            .await?
+            .into_iter()
+            .map(|c| (c.id, c))
+            .collect();
+        if let Some(ids) = &request.category_ids {
+            if let Some(id) = ids.iter().find(|id| !categories.contains_key(id)) {
+                return Err(AppError::ValidationError(format!(
+                    "Category {} does not belong to this tournament",
+                    id
+                )));
+            }
+        }
+        let courts = self
+            .courts(tournament_id, request.court_ids.as_deref())
+            .await?;
+        if courts.is_empty() {
+            return Err(AppError::ValidationError(
+                "No courts to schedule on; link a venue with active courts to the tournament"
+                    .into(),
+            ));
+        }
+
+        let duration = |m: &Match| {
+            estimated_duration(
+                tournament.sport_type,
+                categories.get(&m.tournament_category_id),
+            )
+        };
+        let mut planner = SchedulePlanner::new(
+            &courts,
+            starts_at,
+            ends_at,
+            Duration::minutes(rest_minutes),
+            Duration::minutes(slot_minutes),
+        );
+        let mut lookups = SlotLookups::default();
+        let mut pending = Vec::new();
+        let mut unscheduled = HashMap::new();
+        let mut booked = Vec::new();
+        for m in self.match_repo.find_by_tournament(tournament_id).await? {
+            let wanted = request
+                .category_ids
+                .as_ref()
+                .is_none_or(|ids| ids.contains(&m.tournament_category_id));
+            if is_unscheduled(&m) {
+                if wanted {
+                    pending.push(PendingMatch {
+                        match_id: m.id,
+                        category_id: m.tournament_category_id,
+                        stage: stage_of(m.match_type, m.round_number),
+                        match_number: m.match_number,
+                        participants: lookups.participants(&*self.team_member_repo, &m).await?,
+                        duration: duration(&m),
+                    });
+                    unscheduled.insert(m.id, m);
+                }
+            } else if occupies_time(&m) {
+                booked.push(m.id);
+                let participants = lookups.participants(&*self.team_member_repo, &m).await?;
+                planner.book(booking(
+                    &m,
+                    Some(m.tournament_category_id),
+                    participants,
+                    duration(&m),
+                ));
+            }
+        }
+        // Matches of other tournaments already on these courts
+        let lookback = Duration::days(1);
+        for (_, court) in &courts {
+            for m in self
+                .match_repo
+                .find_by_court(court.id, starts_at - lookback, ends_at)
+                .await?
+            {
+                if booked.contains(&m.id) || !occupies_time(&m) {
+                    continue;
+                }
+                booked.push(m.id);
+                let length = self.duration_elsewhere(&m, tournament.sport_type).await?;
+                let participants = lookups.participants(&*self.team_member_repo, &m).await?;
+                planner.book(booking(&m, None, participants, length));
+            }
+        }
+
+        let (slots, unplaced) = planner.plan(pending);
+        // The planner only sees these courts; the conflict check also covers matches elsewhere
+        let planned: Vec<Match> = slots
+            .iter()
+            .filter_map(|slot| {
+                let mut m = unscheduled.remove(&slot.match_id)?;
+                m.scheduled_date = slot.starts_at;
+                m.venue = Some(slot.venue_name.clone());
+                m.court_number = Some(slot.court_name.clone());
+                m.court_id = Some(slot.court_id);
+                Some(m)
+            })
+            .collect();
+        let conflicts = find_conflicts(
+            &*self.match_repo,
+            &*self.category_repo,
+            &*self.tournament_repo,
+            &*self.team_member_repo,
+            &planned,
+            false,
+        )
+        .await?;
+        let mut report = ScheduleReport {
+            dry_run,
+            committed: false,
+            starts_at,
+            ends_at,
+            min_rest_minutes: rest_minutes,
+            slots,
+            unplaced,
+            conflicts,
+        };
+
+        if !dry_run
+            && report.unplaced.is_empty()
+            && report.conflicts.is_empty()
+            && !report.slots.is_empty()
+        {
+            let work = self.uow.begin().await?;
+            for slot in &report.slots {
+                work.matches()
+                    .update(
+                        slot.match_id,
+                        EditableMatch {
+                            scheduled_date: Some(slot.starts_at),
+                            venue: Some(slot.venue_name.clone()),
+                            court_number: Some(slot.court_name.clone()),
+                            court_id: Some(slot.court_id),
+                            ..EditableMatch::default()
+                        },
+                    )
+                    .await?;
+            }
+            work.commit().await?;
+            for slot in &report.slots {
+                self.events
+                    .publish(RealtimeEvent::MatchUpdate {
+                        match_id: slot.match_id,
+                        tournament_id: Some(tournament_id),
+                        category_id: Some(slot.category_id),
+                        status: Some(format!("{:?}", MatchStatus::Scheduled)),
+                    })
+                    .await;
+            }
+            report.committed = true;
+        }
+        Ok(report)
+    }
+
+    /// The given courts, or every active court at the tournament's venues, with their venues
+    async fn courts(
+        &self,
+        tournament_id: Uuid,
+        court_ids: Option<&[Uuid]>,
+    ) -> Result<Vec<(Venue, Court)>, AppError> {
+        let mut courts = Vec::new();
+        match court_ids {
+            Some(ids) => {
+                let mut venues: HashMap<Uuid, Venue> = HashMap::new();
+                for id in ids {
+                    let court = self
+                        .venue_repo
+                        .find_court(*id)
+                        .await?
+                        .filter(|c| c.is_active)
+                        .ok_or_else(|| {
+                            AppError::ValidationError(format!(
+                                "Court {} does not exist or is not in use",
+                                id
+                            ))
+                        })?;
+                    if !venues.contains_key(&court.venue_id) {
+                        let venue = self
+                            .venue_repo
+                            .find_by_id(court.venue_id)
+                            .await?
+                            .ok_or_else(|| AppError::NotFound("Venue not found".into()))?;
+                        venues.insert(venue.id, venue);
+                    }
+                    courts.push((venues[&court.venue_id].clone(), court));
+                }
+            }
+            None => {
+                for venue in self.venue_repo.find_by_tournament(tournament_id).await? {
+                    for court in self.venue_repo.find_courts(venue.id).await? {
+                        if court.is_active {
+                            courts.push((venue.clone(), court));
+                        }
+                    }
+                }
+            }
+        }
+        Ok(courts)
+    }
+
+    /// Estimated length of a match from another tournament, by `fallback`'s standard when its
+    /// tournament is gone
+    async fn duration_elsewhere(
+        &self,
+        m: &Match,
+        fallback: SportType,
+    ) -> Result<Duration, AppError> {
+        let category = self
+            .category_repo
+            .get_by_id(m.tournament_category_id)
+            .await?;
+        let sport = match &category {
+            Some(c) => self
+                .tournament_repo
+                .get_by_id(c.tournament_id)
+                .await?
+                .map(|t| t.sport_type),
+            None => None,
+        };
+        Ok(estimated_duration(
+            sport.unwrap_or(fallback),
+            category.as_ref(),
+        ))
+    }
+}
+
+/// Scheduled but not yet on a court
+fn is_unscheduled(m: &Match) -> bool {
+    m.match_status == MatchStatus::Scheduled && m.court_id.is_none()
+}
+
+/// Whether a match holds its court and participants at its time
+fn occupies_time(m: &Match) -> bool {
+    match m.match_status {
+        MatchStatus::InProgress | MatchStatus::Completed => true,
+        MatchStatus::Scheduled => m.court_id.is_some(),
+        MatchStatus::Cancelled
+        | MatchStatus::Postponed
+        | MatchStatus::Forfeited
+        | MatchStatus::Bye => false,
+    }
+}
+
+fn booking(
+    m: &Match,
+    category_id: Option<Uuid>,
+    participants: Vec<Uuid>,
+    duration: Duration,
+) -> Booking {
+    let (starts_at, ends_at) = occupied_interval(m, duration);
+    Booking {
+        category_id,
+        stage: stage_of(m.match_type, m.round_number),
+        court_id: m.court_id,
+        participants,
+        starts_at,
+        ends_at,
+    }
+}
@@ -11,8 +11,8 @@ use crate::shared::AppError;
 /// Statistics domain services (read-only analytics)
 pub struct StatisticsServices<R, P>
 where
-    R: StatisticsRepository,
-    P: PlayerRepository,
+    R: StatisticsRepository + ?Sized,
+    P: PlayerRepository + ?Sized,
 {
     stats_repo: Arc<R>,
     player_repo: Arc<P>,
@@ -20,8 +20,8 @@ where
 
 impl<R, P> StatisticsServices<R, P>
 where
-    R: StatisticsRepository,
-    P: PlayerRepository,
+    R: StatisticsRepository + ?Sized,
+    P: PlayerRepository + ?Sized,
 {
     pub fn new(stats_repo: Arc<R>, player_repo: Arc<P>) -> Self {
         Self {
@@ -0,0 +1,153 @@
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::match_domain::{Match, MatchRepository, MatchStatus};
+use crate::domain::ticket::{
+    spectator_tickets_enabled, ticket_expiry, MatchTicket, MatchTicketRepository, TicketAdmission,
+};
+use crate::domain::tournament::{Tournament, TournamentCategoryRepository, TournamentRepository};
+use crate::shared::AppError;
+
+/// Spectator tickets: issuing them for single matches and admitting them at the door
+pub struct TicketServices<K, M, C, T>
+where
+    K: MatchTicketRepository + ?Sized,
+    M: MatchRepository + ?Sized,
+    C: TournamentCategoryRepository + ?Sized,
+    T: TournamentRepository + ?Sized,
+{
+    ticket_repo: Arc<K>,
+    match_repo: Arc<M>,
+    category_repo: Arc<C>,
+    tournament_repo: Arc<T>,
+}
+
+impl<K, M, C, T> TicketServices<K, M, C, T>
+where
+    K: MatchTicketRepository + ?Sized,
+    M: MatchRepository + ?Sized,
+    C: TournamentCategoryRepository + ?Sized,
+    T: TournamentRepository + ?Sized,
+{
+    pub fn new(
+        ticket_repo: Arc<K>,
+        match_repo: Arc<M>,
+        category_repo: Arc<C>,
+        tournament_repo: Arc<T>,
+    ) -> Self {
+        Self {
+            ticket_repo,
+            match_repo,
+            category_repo,
+            tournament_repo,
+        }
+    }
+
+    async fn tournament_of(&self, m: &Match) -> Result<Option<Tournament>, AppError> {
+        match self
+            .category_repo
+            .get_by_id(m.tournament_category_id)
+            .await?
+        {
+            Some(category) => self.tournament_repo.get_by_id(category.tournament_id).await,
+            None => Ok(None),
+        }
+    }
+
+    /// Issues the user a ticket to the match, with the time its token should expire.
+    /// Returns `None` when the match does not exist.
+    pub async fn issue_ticket(
+        &self,
+        holder_id: Uuid,
+        match_id: Uuid,
+    ) -> Result<Option<(MatchTicket, DateTime<Utc>)>, AppError> {
+        let Some(m) = self.match_repo.find_by_id(match_id).await? else {
+            return Ok(None);
+        };
+        let Some(tournament) = self.tournament_of(&m).await? else {
+            return Ok(None);
+        };
+        if !spectator_tickets_enabled(&tournament) {
+            return Err(AppError::Forbidden(
+                "Spectator tickets are not enabled for this tournament".into(),
+            ));
+        }
+        if matches!(
+            m.match_status,
+            MatchStatus::Completed | MatchStatus::Cancelled | MatchStatus::Forfeited
+        ) {
+            return Err(AppError::ValidationError(
+                "Tickets are only issued for matches still to be played".into(),
+            ));
+        }
+        let ticket = self
+            .ticket_repo
+            .create(MatchTicket::new(match_id, holder_id, Utc::now()))
+            .await?;
+        Ok(Some((ticket, ticket_expiry(&m, &tournament))))
+    }
+
+    /// One of the user's tickets, with the time its token should expire
+    pub async fn get_ticket(
+        &self,
+        holder_id: Uuid,
+        ticket_id: Uuid,
+    ) -> Result<Option<(MatchTicket, DateTime<Utc>)>, AppError> {
+        let Some(ticket) = self.ticket_repo.get_by_id(ticket_id).await? else {
+            return Ok(None);
+        };
+        if ticket.holder_id != holder_id {
+            return Err(AppError::Forbidden(
+                "This ticket belongs to someone else".into(),
+            ));
+        }
+        let Some(m) = self.match_repo.find_by_id(ticket.match_id).await? else {
+            return Ok(None);
+        };
+        let Some(tournament) = self.tournament_of(&m).await? else {
+            return Ok(None);
+        };
+        let expires_at = ticket_expiry(&m, &tournament);
+        Ok(Some((ticket, expires_at)))
+    }
+
+    pub async fn get_my_tickets(&self, holder_id: Uuid) -> Result<Vec<MatchTicket>, AppError> {
+        self.ticket_repo.get_by_holder(holder_id).await
+    }
+
+    /// Admits a scanned ticket. A ticket admits once: scanning it again reports when it was
+    /// used. `match_id` comes from the verified token and must be the ticket's match.
+    pub async fn admit(
+        &self,
+        staff_id: Uuid,
+        ticket_id: Uuid,
+        match_id: Uuid,
+    ) -> Result<Option<TicketAdmission>, AppError> {
+        let Some(ticket) = self.ticket_repo.get_by_id(ticket_id).await? else {
+            return Ok(None);
+        };
+        if ticket.match_id != match_id {
+            return Err(AppError::BadRequest(
+                "The ticket is for another match".into(),
+            ));
+        }
+        let (ticket, already_admitted) = match self
+            .ticket_repo
+            .admit(ticket_id, staff_id, Utc::now())
+            .await?
+        {
+            Some(admitted) => (admitted, false),
+            None => {
+                let current = self.ticket_repo.get_by_id(ticket_id).await?;
+                (current.unwrap_or(ticket), true)
+            }
+        };
+        let match_details = self.match_repo.find_with_participants(match_id).await?;
+        Ok(Some(TicketAdmission {
+            ticket,
+            match_details,
+            already_admitted,
+        }))
+    }
+}
@@ -1,44 +1,56 @@
+use std::collections::HashSet;
 use std::sync::Arc;
 use uuid::Uuid;
 
 use chrono::Duration;
 use serde_json::Value as JsonValue;
 
+use crate::domain::check_in::in_draw;
+use crate::domain::outbox::DomainEvent;
+use crate::domain::realtime::{EventPublisher, RealtimeEvent};
 use crate::domain::tournament::{
-    BracketType, EditableTournament, EditableTournamentBracket, EditableTournamentCategory,
-    EditableTournamentRegistration, EditableTournamentStandings, ExportData, NewTournament,
-    NewTournamentBracket, NewTournamentCategory, NewTournamentRegistration, NewTournamentStandings,
-    RegistrationWithDetails, SportType, Tournament, TournamentBracket, TournamentBracketRepository,
-    TournamentCategory, TournamentCategoryRepository, TournamentDashboard, TournamentFormat,
-    TournamentRegistration, TournamentRegistrationRepository, TournamentRepository,
-    TournamentSearchQuery, TournamentStandings, TournamentStandingsRepository, TournamentStats,
-    TournamentStatus, TournamentTemplate,
+    compute_standings, single_elimination, BracketType, EditableTournament,
+    EditableTournamentBracket, EditableTournamentCategory, EditableTournamentRegistration,
+    EditableTournamentStandings, NewTournament, NewTournamentBracket, NewTournamentCategory,
+    NewTournamentRegistration, NewTournamentStandings, RegistrationStatus, RegistrationWithDetails,
+    SportType, StandingsMatch, StandingsRules, Tournament, TournamentBracket,
+    TournamentBracketRepository, TournamentCategory, TournamentCategoryRepository,
+    TournamentDashboard, TournamentFormat, TournamentRegistration,
+    TournamentRegistrationRepository, TournamentRepository, TournamentSearchQuery,
+    TournamentStandings, TournamentStandingsRepository, TournamentStats, TournamentStatus,
+    TournamentTemplate,
 };
+use crate::domain::unit_of_work::UnitOfWorkFactory;
+use crate::shared::timezone::{parse_date_filter, parse_timezone, DayBound, DEFAULT_TIMEZONE};
 use crate::shared::AppError;
 
 /// Tournament domain services
-pub struct TournamentServices<T, C, R, B, S>
+pub struct TournamentServices<T, C, R, B, S, U>
 where
-    T: TournamentRepository,
-    C: TournamentCategoryRepository,
-    R: TournamentRegistrationRepository,
-    B: TournamentBracketRepository,
-    S: TournamentStandingsRepository,
+    T: TournamentRepository + ?Sized,
+    C: TournamentCategoryRepository + ?Sized,
+    R: TournamentRegistrationRepository + ?Sized,
+    B: TournamentBracketRepository + ?Sized,
+    S: TournamentStandingsRepository + ?Sized,
+    U: UnitOfWorkFactory + ?Sized,
 {
     tournament_repo: Arc<T>,
     category_repo: Arc<C>,
     registration_repo: Arc<R>,
     bracket_repo: Arc<B>,
     standings_repo: Arc<S>,
+    uow: Arc<U>,
+    events: Arc<dyn EventPublisher>,
 }
 
-impl<T, C, R, B, S> TournamentServices<T, C, R, B, S>
+impl<T, C, R, B, S, U> TournamentServices<T, C, R, B, S, U>
 where
-    T: TournamentRepository,
-    C: TournamentCategoryRepository,
-    R: TournamentRegistrationRepository,
-    B: TournamentBracketRepository,
-    S: TournamentStandingsRepository,
+    T: TournamentRepository + ?Sized,
+    C: TournamentCategoryRepository + ?Sized,
+    R: TournamentRegistrationRepository + ?Sized,
+    B: TournamentBracketRepository + ?Sized,
+    S: TournamentStandingsRepository + ?Sized,
+    U: UnitOfWorkFactory + ?Sized,
 {
     pub fn new(
         tournament_repo: Arc<T>,
@@ -46,6 +58,8 @@ where
         registration_repo: Arc<R>,
         bracket_repo: Arc<B>,
         standings_repo: Arc<S>,
+        uow: Arc<U>,
+        events: Arc<dyn EventPublisher>,
     ) -> Self {
         Self {
             tournament_repo,
@@ -53,12 +67,60 @@ where
             registration_repo,
             bracket_repo,
             standings_repo,
+            uow,
+            events,
         }
     }
 
+    // ==================== Events ====================
+
+    async fn publish_registration_update(
+        &self,
+        registration: &TournamentRegistration,
+        status: String,
+    ) {
+        let tournament_id = match self
+            .category_repo
+            .get_by_id(registration.tournament_category_id)
+            .await
+        {
+            Ok(category) => category.map(|c| c.tournament_id),
+            Err(_) => None,
+        };
+        self.events
+            .publish(RealtimeEvent::RegistrationUpdate {
+                registration_id: registration.id,
+                tournament_id,
+                category_id: registration.tournament_category_id,
+                status: Some(status),
+            })
+            .await;
+    }
+
+    async fn publish_bracket_update(&self, bracket: &TournamentBracket) {
+        self.events
+            .publish(RealtimeEvent::BracketUpdate {
+                tournament_id: bracket.tournament_id,
+                category_id: bracket.category_id,
+            })
+            .await;
+    }
+
+    async fn publish_standings_update(&self, tournament_id: Uuid, category_id: Option<Uuid>) {
+        self.events
+            .publish(RealtimeEvent::StandingsUpdate {
+                tournament_id,
+                category_id,
+            })
+            .await;
+    }
+
     // ==================== Tournament CRUD ====================
 
-    pub async fn create_tournament(&self, data: NewTournament) -> Result<Tournament, AppError> {
+    pub async fn create_tournament(&self, mut data: NewTournament) -> Result<Tournament, AppError> {
+        if let Some(timezone) = &data.timezone {
+            data.timezone = Some(parse_timezone(timezone)?.name().to_string());
+        }
         self.tournament_repo.create(data).await
     }
 
@@ -87,8 +149,11 @@ where
     pub async fn update_tournament(
         &self,
         id: Uuid,
-        data: EditableTournament,
+        mut data: EditableTournament,
     ) -> Result<Option<Tournament>, AppError> {
+        if let Some(timezone) = &data.timezone {
+            data.timezone = Some(parse_timezone(timezone)?.name().to_string());
+        }
         self.tournament_repo.update(id, data).await
     }
 
@@ -96,10 +161,20 @@ where
         self.tournament_repo.delete(id).await
     }
 
+    /// Searches tournaments. Date filters without an offset are read in the query's `tz`
+    /// and handed to the repository as UTC RFC 3339 timestamps.
     pub async fn search_tournaments(
         &self,
-        query: TournamentSearchQuery,
+        mut query: TournamentSearchQuery,
     ) -> Result<Vec<Tournament>, AppError> {
+        let tz = parse_timezone(query.tz.as_deref().unwrap_or(DEFAULT_TIMEZONE))?;
+        if let Some(date_from) = &query.date_from {
+            query.date_from =
+                Some(parse_date_filter(date_from, tz, DayBound::Start)?.to_rfc3339());
+        }
+        if let Some(date_to) = &query.date_to {
+            query.date_to = Some(parse_date_filter(date_to, tz, DayBound::End)?.to_rfc3339());
+        }
         self.tournament_repo.search(query).await
     }
 
@@ -166,64 +241,8 @@ where
         self.registration_repo.get_by_tournament(id).await
     }
 
-    pub async fn export_tournament(
-        &self,
-        id: Uuid,
-        format: String,
-    ) -> Result<ExportData, AppError> {
-        let tournament = self
-            .tournament_repo
-            .get_by_id(id)
-            .await?
-            .ok_or_else(|| AppError::NotFound("Tournament not found".into()))?;
-        let categories = self.category_repo.get_by_tournament(id).await?;
-        let registrations = self.registration_repo.get_by_tournament(id).await?;
-
-        let mut export_data = serde_json::Map::new();
-        export_data.insert(
-            "tournament".to_string(),
-            serde_json::to_value(&tournament)
-                .map_err(|e| AppError::InternalError(e.to_string()))?,
-        );
-        export_data.insert(
-            "categories".to_string(),
-            serde_json::to_value(&categories)
-                .map_err(|e| AppError::InternalError(e.to_string()))?,
-        );
-        export_data.insert(
-            "registrations".to_string(),
-            serde_json::to_value(&registrations)
-                .map_err(|e| AppError::InternalError(e.to_string()))?,
-        );
-        export_data.insert(
-            "exported_at".to_string(),
-            serde_json::to_value(chrono::Utc::now())
-                .map_err(|e| AppError::InternalError(e.to_string()))?,
-        );
-
-        let (content_type, filename) = match format.as_str() {
-            "csv" => (
-                "text/csv".to_string(),
-                format!("{}_export.csv", tournament.name),
-            ),
-            "pdf" => (
-                "application/pdf".to_string(),
-                format!("{}_export.pdf", tournament.name),
-            ),
-            _ => (
-                "application/json".to_string(),
-                format!("{}_export.json", tournament.name),
-            ),
-        };
-
-        Ok(ExportData {
-            format: format.clone(),
-            data: JsonValue::Object(export_data),
-            filename,
-            content_type,
-        })
-    }
-
+    /// Copies a tournament one week later, together with its categories. The copy is
+    /// written in one transaction so a failure never leaves a half-duplicated tournament.
     pub async fn duplicate_tournament(
         &self,
         id: Uuid,
@@ -234,6 +253,7 @@ where
             .get_by_id(id)
             .await?
             .ok_or_else(|| AppError::NotFound("Tournament not found".into()))?;
+        let categories = self.category_repo.get_by_tournament(id).await?;
 
         let week = Duration::days(7);
         let new_tournament = NewTournament {
@@ -251,8 +271,28 @@ where
             prize_pool: original.prize_pool,
             rules: original.rules.clone(),
             organizer_id: original.organizer_id,
+            timezone: Some(original.timezone.clone()),
         };
-        self.tournament_repo.create(new_tournament).await
+
+        let work = self.uow.begin().await?;
+        let copy = work.tournaments().create(new_tournament).await?;
+        for category in categories {
+            let new_category = NewTournamentCategory {
+                tournament_id: copy.id,
+                name: category.name,
+                description: category.description,
+                team_composition: category.team_composition,
+                min_participants: Some(category.min_participants),
+                max_participants: category.max_participants,
+                entry_fee: category.entry_fee,
+                prize_distribution: category.prize_distribution,
+                rules: category.rules,
+                constraints: category.constraints,
+            };
+            work.categories().create(new_category).await?;
+        }
+        work.commit().await?;
+        Ok(copy)
     }
 
     pub async fn get_tournament_templates(&self) -> Result<Vec<TournamentTemplate>, AppError> {
@@ -290,7 +330,7 @@ where
         mut data: NewTournament,
     ) -> Result<Tournament, AppError> {
         data.format = TournamentFormat::Elimination;
-        self.tournament_repo.create(data).await
+        self.create_tournament(data).await
     }
 
     pub async fn get_tournament_dashboard(
@@ -327,6 +367,57 @@ where
         self.tournament_repo.update(id, data).await
     }
 
+    // ==================== Scorekeepers ====================
+
+    /// Scorekeepers may score any of the tournament's matches; only the organizer
+    /// manages them
+    pub async fn get_scorekeepers(
+        &self,
+        organizer_id: Uuid,
+        tournament_id: Uuid,
+    ) -> Result<Vec<Uuid>, AppError> {
+        self.ensure_organizer(organizer_id, tournament_id).await?;
+        self.tournament_repo.get_scorekeepers(tournament_id).await
+    }
+
+    pub async fn add_scorekeeper(
+        &self,
+        organizer_id: Uuid,
+        tournament_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<(), AppError> {
+        self.ensure_organizer(organizer_id, tournament_id).await?;
+        self.tournament_repo
+            .add_scorekeeper(tournament_id, user_id)
+            .await
+    }
+
+    pub async fn remove_scorekeeper(
+        &self,
+        organizer_id: Uuid,
+        tournament_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<bool, AppError> {
+        self.ensure_organizer(organizer_id, tournament_id).await?;
+        self.tournament_repo
+            .remove_scorekeeper(tournament_id, user_id)
+            .await
+    }
+
+    async fn ensure_organizer(&self, user_id: Uuid, tournament_id: Uuid) -> Result<(), AppError> {
+        let tournament = self
+            .tournament_repo
+            .get_by_id(tournament_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound("Tournament not found".into()))?;
+        if tournament.organizer_id != user_id {
+            return Err(AppError::Forbidden(
+                "Only the tournament organizer can manage scorekeepers".into(),
+            ));
+        }
+        Ok(())
+    }
+
     // ==================== Category ====================
 
     pub async fn create_category(
@@ -368,7 +459,19 @@ where
         &self,
         data: NewTournamentRegistration,
     ) -> Result<TournamentRegistration, AppError> {
-        self.registration_repo.create(data).await
+        let registration = self.registration_repo.create(data).await.map_err(|e| {
+            if e.is_unique_violation() {
+                AppError::Conflict("Already registered in this category".into())
+            } else {
+                e
+            }
+        })?;
+        self.publish_registration_update(
+            &registration,
+            format!("{:?}", registration.registration_status),
+        )
+        .await;
+        Ok(registration)
     }
 
     pub async fn get_registration_by_id(
@@ -410,19 +513,55 @@ where
         self.registration_repo.get_by_team(team_id).await
     }
 
+    /// Approving a registration records `RegistrationApproved` in the outbox with the change
     pub async fn update_registration(
         &self,
         id: Uuid,
         data: EditableTournamentRegistration,
     ) -> Result<Option<TournamentRegistration>, AppError> {
-        self.registration_repo.update(id, data).await
+        let work = self.uow.begin().await?;
+        let before = work.registrations().get_by_id(id).await?;
+        let registration = work.registrations().update(id, data).await?;
+        let approved = registration.as_ref().filter(|r| {
+            r.registration_status == RegistrationStatus::Approved
+                && before
+                    .as_ref()
+                    .is_some_and(|b| b.registration_status != RegistrationStatus::Approved)
+        });
+        if let Some(registration) = approved {
+            let tournament_id = work
+                .categories()
+                .get_by_id(registration.tournament_category_id)
+                .await?
+                .map(|c| c.tournament_id);
+            work.outbox()
+                .enqueue(&DomainEvent::registration_approved(
+                    registration,
+                    tournament_id,
+                ))
+                .await?;
+        }
+        work.commit().await?;
+        if let Some(registration) = &registration {
+            self.publish_registration_update(
+                registration,
+                format!("{:?}", registration.registration_status),
+            )
+            .await;
+        }
+        Ok(registration)
     }
 
     pub async fn delete_registration(
         &self,
         id: Uuid,
     ) -> Result<Option<TournamentRegistration>, AppError> {
-        self.registration_repo.delete(id).await
+        let registration = self.registration_repo.delete(id).await?;
+        if let Some(registration) = &registration {
+            self.publish_registration_update(registration, "deleted".to_string())
+                .await;
+        }
+        Ok(registration)
     }
 
     // ==================== Bracket ====================
@@ -431,7 +570,9 @@ where
         &self,
         data: NewTournamentBracket,
     ) -> Result<TournamentBracket, AppError> {
-        self.bracket_repo.create(data).await
+        let bracket = self.bracket_repo.create(data).await?;
+        self.publish_bracket_update(&bracket).await;
+        Ok(bracket)
     }
 
     pub async fn get_brackets_by_tournament(
@@ -449,10 +590,16 @@ where
     }
 
     /// Generate a default bracket for a tournament (single elimination, 0 rounds).
+    /// The existence check and insert share a transaction.
     pub async fn generate_bracket(
         &self,
         tournament_id: Uuid,
     ) -> Result<TournamentBracket, AppError> {
+        let work = self.uow.begin().await?;
+        if work.tournaments().get_by_id(tournament_id).await?.is_none() {
+            work.rollback().await?;
+            return Err(AppError::NotFound("Tournament not found".into()));
+        }
         let data = NewTournamentBracket {
             tournament_id,
             category_id: None,
@@ -461,7 +608,70 @@ where
             bracket_data: None,
             settings: None,
         };
-        self.bracket_repo.create(data).await
+        let bracket = work.brackets().create(data).await?;
+        work.commit().await?;
+        self.publish_bracket_update(&bracket).await;
+        Ok(bracket)
+    }
+
+    /// Generate a category's single-elimination draw from the entries in it: approved
+    /// registrations, and only the checked-in ones once the category has a check-in window.
+    /// Entries are seeded in registration order. Returns `None` when the category does not
+    /// exist.
+    pub async fn generate_category_bracket(
+        &self,
+        category_id: Uuid,
+    ) -> Result<Option<TournamentBracket>, AppError> {
+        let work = self.uow.begin().await?;
+        let Some(category) = work.categories().get_by_id(category_id).await? else {
+            work.rollback().await?;
+            return Ok(None);
+        };
+        if work.brackets().get_by_category_id(category_id).await?.is_some() {
+            work.rollback().await?;
+            return Err(AppError::Conflict("The category already has a bracket".into()));
+        }
+        let window = work.check_in_windows().find_by_category(category_id).await?;
+        let mut entries: Vec<TournamentRegistration> = work
+            .registrations()
+            .get_by_tournament_category(category_id)
+            .await?
+            .into_iter()
+            .filter(|r| in_draw(r, window.as_ref()))
+            .collect();
+        entries.sort_by_key(|r| (r.registration_date, r.id));
+        if entries.len() < 2 {
+            work.rollback().await?;
+            return Err(AppError::ValidationError(
+                "A bracket needs at least two entries in the draw".into(),
+            ));
+        }
+
+        let details = work.registrations().get_by_tournament(category.tournament_id).await?;
+        let entrants: Vec<(Uuid, Option<String>)> = entries
+            .iter()
+            .filter_map(|r| {
+                let name = details
+                    .iter()
+                    .find(|d| d.id == r.id)
+                    .and_then(RegistrationWithDetails::display_name);
+                r.team_id.or(r.player_id).map(|id| (id, name))
+            })
+            .collect();
+        let nodes = single_elimination(&entrants);
+        let total_rounds = nodes.iter().map(|n| n.round).max().unwrap_or(0);
+        let data = NewTournamentBracket {
+            tournament_id: category.tournament_id,
+            category_id: Some(category_id),
+            bracket_type: BracketType::SingleElimination,
+            total_rounds,
+            bracket_data: Some(serde_json::json!({ "nodes": nodes })),
+            settings: None,
+        };
+        let bracket = work.brackets().create(data).await?;
+        work.commit().await?;
+        self.publish_bracket_update(&bracket).await;
+        Ok(Some(bracket))
     }
 
     pub async fn update_bracket(
@@ -469,11 +679,19 @@ where
         id: Uuid,
         data: EditableTournamentBracket,
     ) -> Result<Option<TournamentBracket>, AppError> {
-        self.bracket_repo.update(id, data).await
+        let bracket = self.bracket_repo.update(id, data).await?;
+        if let Some(bracket) = &bracket {
+            self.publish_bracket_update(bracket).await;
+        }
+        Ok(bracket)
     }
 
     pub async fn delete_bracket(&self, id: Uuid) -> Result<Option<TournamentBracket>, AppError> {
-        self.bracket_repo.delete(id).await
+        let bracket = self.bracket_repo.delete(id).await?;
+        if let Some(bracket) = &bracket {
+            self.publish_bracket_update(bracket).await;
+        }
+        Ok(bracket)
     }
 
     // ==================== Standings ====================
@@ -482,7 +700,10 @@ where
         &self,
         data: NewTournamentStandings,
     ) -> Result<TournamentStandings, AppError> {
-        self.standings_repo.create(data).await
+        let standings = self.standings_repo.create(data).await?;
+        self.publish_standings_update(standings.tournament_id, standings.category_id)
+            .await;
+        Ok(standings)
     }
 
     pub async fn get_standings_by_tournament(
@@ -506,14 +727,61 @@ where
         id: Uuid,
         data: EditableTournamentStandings,
     ) -> Result<Option<TournamentStandings>, AppError> {
-        self.standings_repo.update(id, data).await
+        let standings = self.standings_repo.update(id, data).await?;
+        if let Some(standings) = &standings {
+            self.publish_standings_update(standings.tournament_id, standings.category_id)
+                .await;
+        }
+        Ok(standings)
     }
 
-    pub async fn recalculate_standings(&self, tournament_id: Uuid) -> Result<u64, AppError> {
-        // First delete existing standings, then they would need to be recalculated
-        // This is a placeholder - actual implementation would involve complex calculations
-        self.standings_repo
-            .delete_by_tournament(tournament_id)
-            .await
+    /// Rebuilds every category's standings from its completed and forfeited matches,
+    /// scoring forfeits and withdrawals by the category's rules
+    pub async fn recalculate_standings(
+        &self,
+        tournament_id: Uuid,
+    ) -> Result<Vec<TournamentStandings>, AppError> {
+        let work = self.uow.begin().await?;
+        if work.tournaments().get_by_id(tournament_id).await?.is_none() {
+            work.rollback().await?;
+            return Err(AppError::NotFound("Tournament not found".into()));
+        }
+        let mut computed = Vec::new();
+        for category in work.categories().get_by_tournament(tournament_id).await? {
+            let withdrawn: HashSet<Uuid> = work
+                .registrations()
+                .get_by_tournament_category(category.id)
+                .await?
+                .into_iter()
+                .filter(|r| r.registration_status == RegistrationStatus::Withdrawn)
+                .filter_map(|r| r.team_id.or(r.player_id))
+                .collect();
+            let mut matches = Vec::new();
+            for m in work.matches().find_by_category(category.id).await? {
+                let names = work
+                    .matches()
+                    .find_with_participants(m.id)
+                    .await?
+                    .map(|p| (p.participant1_name, p.participant2_name))
+                    .unwrap_or_default();
+                let results = work.match_results().find_by_match(m.id).await?;
+                matches.push(StandingsMatch { m, results, names });
+            }
+            computed.extend(compute_standings(
+                tournament_id,
+                category.id,
+                &StandingsRules::for_category(&category),
+                &matches,
+                &withdrawn,
+            ));
+        }
+        // Replaced in the same transaction, so a failed write leaves the old standings
+        work.standings().delete_by_tournament(tournament_id).await?;
+        let mut standings = work.standings().bulk_upsert(computed).await?;
+        work.commit().await?;
+
+        standings.sort_by_key(|s| (s.category_id, s.position));
+        self.publish_standings_update(tournament_id, None).await;
+        Ok(standings)
     }
 }
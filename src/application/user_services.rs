@@ -2,6 +2,7 @@ use serde_json::Value;
 use std::sync::Arc;
 use uuid::Uuid;
 
+use crate::domain::notification::NotificationPreferences;
 use crate::domain::user::{
     EditableUser, NewUser, NewUserProfile, PublicUserProfile, UpdateUserProfile, User, UserProfile,
     UserProfileRepository, UserRepository,
@@ -11,8 +12,8 @@ use crate::shared::AppError;
 /// User and profile services (CRUD and profile operations)
 pub struct UserServices<U, P>
 where
-    U: UserRepository,
-    P: UserProfileRepository,
+    U: UserRepository + ?Sized,
+    P: UserProfileRepository + ?Sized,
 {
     user_repo: Arc<U>,
     profile_repo: Arc<P>,
@@ -20,8 +21,8 @@ where
 
 impl<U, P> UserServices<U, P>
 where
-    U: UserRepository,
-    P: UserProfileRepository,
+    U: UserRepository + ?Sized,
+    P: UserProfileRepository + ?Sized,
 {
     pub fn new(user_repo: Arc<U>, profile_repo: Arc<P>) -> Self {
         Self {
@@ -99,13 +100,15 @@ where
             .await
     }
 
+    /// Rejects preferences that do not fit the `NotificationPreferences` schema
     pub async fn update_notification_preferences(
         &self,
         user_id: Uuid,
         notification_preferences: Value,
     ) -> Result<Option<UserProfile>, AppError> {
+        let preferences = NotificationPreferences::parse(&notification_preferences)?;
         self.profile_repo
-            .update_notification_preferences(user_id, notification_preferences)
+            .update_notification_preferences(user_id, preferences.to_value())
             .await
     }
 
@@ -0,0 +1,184 @@
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::venue::{
+    AvailabilityWindow, Court, EditableCourt, EditableVenue, NewCourt, NewVenue, OpeningHours,
+    Venue, VenueRepository,
+};
+use crate::shared::timezone::parse_timezone;
+use crate::shared::AppError;
+
+/// Venue and court management
+pub struct VenueServices<V>
+where
+    V: VenueRepository + ?Sized,
+{
+    venue_repo: Arc<V>,
+}
+
+impl<V> VenueServices<V>
+where
+    V: VenueRepository + ?Sized,
+{
+    pub fn new(venue_repo: Arc<V>) -> Self {
+        Self { venue_repo }
+    }
+
+    // ==================== Venues ====================
+
+    pub async fn create_venue(&self, data: NewVenue) -> Result<Venue, AppError> {
+        validate_name(&data.name)?;
+        parse_timezone(&data.timezone)?;
+        validate_opening_hours(&data.opening_hours)?;
+        self.venue_repo.create(data).await
+    }
+
+    pub async fn get_venue(&self, venue_id: Uuid) -> Result<Option<Venue>, AppError> {
+        self.venue_repo.find_by_id(venue_id).await
+    }
+
+    pub async fn list_venues(&self, limit: i64, offset: i64) -> Result<Vec<Venue>, AppError> {
+        self.venue_repo.find_all(limit, offset).await
+    }
+
+    pub async fn update_venue(
+        &self,
+        venue_id: Uuid,
+        data: EditableVenue,
+    ) -> Result<Option<Venue>, AppError> {
+        if let Some(name) = &data.name {
+            validate_name(name)?;
+        }
+        if let Some(timezone) = &data.timezone {
+            parse_timezone(timezone)?;
+        }
+        if let Some(opening_hours) = &data.opening_hours {
+            validate_opening_hours(opening_hours)?;
+        }
+        self.venue_repo.update(venue_id, data).await
+    }
+
+    /// Deletes the venue and its courts; matches on them keep their venue and court names
+    pub async fn delete_venue(&self, venue_id: Uuid) -> Result<Option<Venue>, AppError> {
+        self.venue_repo.delete(venue_id).await
+    }
+
+    // ==================== Courts ====================
+
+    /// `None` when the venue does not exist
+    pub async fn add_court(
+        &self,
+        venue_id: Uuid,
+        data: NewCourt,
+    ) -> Result<Option<Court>, AppError> {
+        validate_name(&data.name)?;
+        validate_availability(&data.availability)?;
+        if self.venue_repo.find_by_id(venue_id).await?.is_none() {
+            return Ok(None);
+        }
+        self.venue_repo.create_court(venue_id, data).await.map(Some)
+    }
+
+    pub async fn get_court(&self, court_id: Uuid) -> Result<Option<Court>, AppError> {
+        self.venue_repo.find_court(court_id).await
+    }
+
+    pub async fn get_courts(&self, venue_id: Uuid) -> Result<Vec<Court>, AppError> {
+        self.venue_repo.find_courts(venue_id).await
+    }
+
+    pub async fn update_court(
+        &self,
+        court_id: Uuid,
+        data: EditableCourt,
+    ) -> Result<Option<Court>, AppError> {
+        if let Some(name) = &data.name {
+            validate_name(name)?;
+        }
+        if let Some(availability) = &data.availability {
+            validate_availability(availability)?;
+        }
+        self.venue_repo.update_court(court_id, data).await
+    }
+
+    pub async fn delete_court(&self, court_id: Uuid) -> Result<Option<Court>, AppError> {
+        self.venue_repo.delete_court(court_id).await
+    }
+
+    /// Whether the court is in use, inside one of its availability windows and within its
+    /// venue's opening hours for the whole of `[start, end)`; `None` when the court does not exist
+    pub async fn court_available(
+        &self,
+        court_id: Uuid,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Option<bool>, AppError> {
+        let Some(court) = self.venue_repo.find_court(court_id).await? else {
+            return Ok(None);
+        };
+        if !court.is_available_between(start, end) {
+            return Ok(Some(false));
+        }
+        let venue = self.venue_repo.find_by_id(court.venue_id).await?;
+        Ok(Some(venue.is_some_and(|v| v.is_open_between(start, end))))
+    }
+
+    // ==================== Tournament Venues ====================
+
+    pub async fn add_tournament_venue(
+        &self,
+        tournament_id: Uuid,
+        venue_id: Uuid,
+    ) -> Result<(), AppError> {
+        self.venue_repo
+            .add_to_tournament(tournament_id, venue_id)
+            .await
+    }
+
+    pub async fn remove_tournament_venue(
+        &self,
+        tournament_id: Uuid,
+        venue_id: Uuid,
+    ) -> Result<bool, AppError> {
+        self.venue_repo
+            .remove_from_tournament(tournament_id, venue_id)
+            .await
+    }
+
+    pub async fn get_tournament_venues(&self, tournament_id: Uuid) -> Result<Vec<Venue>, AppError> {
+        self.venue_repo.find_by_tournament(tournament_id).await
+    }
+}
+
+fn validate_name(name: &str) -> Result<(), AppError> {
+    if name.trim().is_empty() {
+        return Err(AppError::ValidationError("Name must not be empty".into()));
+    }
+    Ok(())
+}
+
+fn validate_opening_hours(opening_hours: &[OpeningHours]) -> Result<(), AppError> {
+    match opening_hours
+        .iter()
+        .find(|hours| hours.opens >= hours.closes)
+    {
+        Some(hours) => Err(AppError::ValidationError(format!(
+            "Opening hours on {} must close after they open",
+            hours.weekday
+        ))),
+        None => Ok(()),
+    }
+}
+
+fn validate_availability(availability: &[AvailabilityWindow]) -> Result<(), AppError> {
+    if availability
+        .iter()
+        .any(|window| window.starts_at >= window.ends_at)
+    {
+        return Err(AppError::ValidationError(
+            "Availability windows must end after they start".into(),
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,106 @@
+// Calendar feeds - match schedules as iCalendar events for calendar apps to subscribe to
+
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::match_domain::{MatchScheduleItem, MatchStatus};
+use crate::shared::csv::serde_label;
+use crate::shared::ical::{CalendarEvent, EventStatus};
+
+/// Whose schedule a feed follows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CalendarFeedKind {
+    /// A user's own matches
+    User,
+    Tournament,
+    Category,
+    Court,
+}
+
+impl CalendarFeedKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CalendarFeedKind::User => "user",
+            CalendarFeedKind::Tournament => "tournament",
+            CalendarFeedKind::Category => "category",
+            CalendarFeedKind::Court => "court",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "user" => Some(CalendarFeedKind::User),
+            "tournament" => Some(CalendarFeedKind::Tournament),
+            "category" => Some(CalendarFeedKind::Category),
+            "court" => Some(CalendarFeedKind::Court),
+            _ => None,
+        }
+    }
+}
+
+/// A feed: its kind and the user, tournament, category or court it follows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CalendarFeed {
+    pub kind: CalendarFeedKind,
+    pub id: Uuid,
+}
+
+/// Where to subscribe to a feed. The URLs carry a secret token in place of a login.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalendarLink {
+    pub feed: CalendarFeed,
+    pub name: String,
+    pub url: String,
+    /// The same URL with the `webcal://` scheme, which calendar apps open as a subscription
+    pub webcal_url: String,
+}
+
+/// A scheduled match as a calendar event lasting `duration`. Cancelled matches stay in
+/// the feed as cancelled events and postponed ones as tentative, so subscribers see the
+/// change rather than the event silently disappearing.
+pub fn match_event(item: &MatchScheduleItem, duration: Duration) -> CalendarEvent {
+    let status = match item.match_status {
+        MatchStatus::Cancelled => EventStatus::Cancelled,
+        MatchStatus::Postponed => EventStatus::Tentative,
+        _ => EventStatus::Confirmed,
+    };
+    let mut summary = format!("{} vs {}", item.participant1_name, item.participant2_name);
+    if item.match_status == MatchStatus::Postponed {
+        summary = format!("Postponed: {}", summary);
+    }
+    let mut stage = serde_label(&item.match_type).replace('_', " ");
+    if let Some(round) = item.round_number {
+        stage = format!("{}, round {}", stage, round);
+    }
+    let location: Vec<String> = [
+        court_label(item.court_number.as_deref()),
+        item.venue.clone(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    CalendarEvent {
+        uid: format!("match-{}@tournament-server", item.id),
+        summary,
+        description: Some(format!(
+            "{} - {}\n{}",
+            item.tournament_name, item.category_name, stage
+        )),
+        location: (!location.is_empty()).then(|| location.join(", ")),
+        starts_at: item.scheduled_date,
+        ends_at: item.scheduled_date + duration,
+        status,
+    }
+}
+
+/// "Court 3" for a bare court number; court names are kept as they are
+fn court_label(court: Option<&str>) -> Option<String> {
+    let court = court.map(str::trim).filter(|c| !c.is_empty())?;
+    if court.chars().all(|c| c.is_ascii_digit()) {
+        Some(format!("Court {}", court))
+    } else {
+        Some(court.to_string())
+    }
+}
@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// What happens to approved entries that have not checked in when the window closes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NoShowAction {
+    /// Their registration is withdrawn
+    #[default]
+    Withdraw,
+    /// They move to the waitlist
+    Waitlist,
+}
+
+impl NoShowAction {
+    pub fn key(&self) -> &'static str {
+        match self {
+            NoShowAction::Withdraw => "withdraw",
+            NoShowAction::Waitlist => "waitlist",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "withdraw" => Some(NoShowAction::Withdraw),
+            "waitlist" => Some(NoShowAction::Waitlist),
+            _ => None,
+        }
+    }
+}
+
+/// The period a category's participants check in during on tournament day
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckInWindow {
+    pub category_id: Uuid,
+    pub opens_at: DateTime<Utc>,
+    pub closes_at: DateTime<Utc>,
+    pub no_show_action: NoShowAction,
+    /// Whether waitlisted entries that checked in take the places no-shows leave
+    pub promote_waitlist: bool,
+    /// When the no-shows were dealt with; nobody checks in after that
+    pub closed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl CheckInWindow {
+    /// Whether participants can check themselves in at `now`
+    pub fn is_open(&self, now: DateTime<Utc>) -> bool {
+        !self.is_closed() && self.opens_at <= now && now < self.closes_at
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed_at.is_some()
+    }
+}
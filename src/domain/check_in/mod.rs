@@ -0,0 +1,13 @@
+// Check-in domain module - participants confirming they are present on tournament day, and
+// what happens to entries that never do
+
+pub mod entity;
+pub mod repository;
+pub mod value_objects;
+
+pub use entity::{CheckInWindow, NoShowAction};
+pub use repository::CheckInWindowRepository;
+pub use value_objects::{
+    in_draw, plan_closure, CheckIn, CheckInEntry, CheckInMethod, CheckInPass, CheckInSheet,
+    CheckInWindowRequest, RegistrationVerification, ScanRequest, WindowClosure,
+};
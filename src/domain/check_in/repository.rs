@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::entity::CheckInWindow;
+use crate::shared::AppError;
+
+/// Repository trait for check-in windows; a category has at most one
+#[async_trait]
+pub trait CheckInWindowRepository: Send + Sync {
+    /// Inserts the category's window, or replaces the one it had
+    async fn save(&self, window: CheckInWindow) -> Result<CheckInWindow, AppError>;
+    async fn find_by_category(&self, category_id: Uuid) -> Result<Option<CheckInWindow>, AppError>;
+    async fn delete(&self, category_id: Uuid) -> Result<Option<CheckInWindow>, AppError>;
+    /// Windows not closed yet whose closing time is at or before `now`
+    async fn find_due(&self, now: DateTime<Utc>) -> Result<Vec<CheckInWindow>, AppError>;
+}
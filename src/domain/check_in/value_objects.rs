@@ -0,0 +1,162 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::entity::{CheckInWindow, NoShowAction};
+use crate::domain::tournament::{RegistrationStatus, TournamentRegistration};
+
+/// How a registration was checked in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckInMethod {
+    /// By the participant through the API
+    #[serde(rename = "self")]
+    SelfService,
+    /// By scanning the registration's QR code
+    Qr,
+    /// By staff at the tournament desk
+    Desk,
+}
+
+impl CheckInMethod {
+    pub fn key(&self) -> &'static str {
+        match self {
+            CheckInMethod::SelfService => "self",
+            CheckInMethod::Qr => "qr",
+            CheckInMethod::Desk => "desk",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "self" => Some(CheckInMethod::SelfService),
+            "qr" => Some(CheckInMethod::Qr),
+            "desk" => Some(CheckInMethod::Desk),
+            _ => None,
+        }
+    }
+}
+
+/// A registration's check-in as stored with it
+#[derive(Debug, Clone)]
+pub struct CheckIn {
+    pub at: DateTime<Utc>,
+    pub method: CheckInMethod,
+    /// The staff member, for desk check-ins
+    pub by: Option<Uuid>,
+}
+
+/// Body of PUT /tournament_categories/{id}/check_in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CheckInWindowRequest {
+    pub opens_at: DateTime<Utc>,
+    pub closes_at: DateTime<Utc>,
+    /// Default: withdraw
+    pub no_show_action: Option<NoShowAction>,
+    /// Default: true
+    pub promote_waitlist: Option<bool>,
+}
+
+/// Body of POST /check_in/scan and POST /credentials/verify
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanRequest {
+    /// The token in the scanned QR code
+    pub token: String,
+}
+
+/// The signed token a registration's QR code carries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckInPass {
+    pub registration_id: Uuid,
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// What desk staff see when a registration's code is scanned; the scan checks it in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistrationVerification {
+    pub registration: TournamentRegistration,
+    pub participant_name: String,
+    pub tournament_name: String,
+    pub category_name: String,
+    /// It was checked in before this scan, which changed nothing
+    pub already_checked_in: bool,
+}
+
+/// One registration on a category's check-in sheet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckInEntry {
+    pub registration_id: Uuid,
+    /// Team or player
+    pub participant_id: Option<Uuid>,
+    pub participant_name: String,
+    pub registration_status: RegistrationStatus,
+    pub checked_in_at: Option<DateTime<Utc>>,
+    pub check_in_method: Option<CheckInMethod>,
+}
+
+/// Who has checked in to a category, for the desk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckInSheet {
+    pub category_id: Uuid,
+    pub window: Option<CheckInWindow>,
+    /// Approved registrations
+    pub expected: usize,
+    /// Approved registrations that checked in
+    pub checked_in: usize,
+    /// Approved and waitlisted registrations, in registration order
+    pub entries: Vec<CheckInEntry>,
+}
+
+/// The outcome of closing a category's window
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WindowClosure {
+    /// Approved registrations that never checked in
+    pub no_shows: Vec<Uuid>,
+    /// Waitlisted registrations that checked in and were approved in their place
+    pub promoted: Vec<Uuid>,
+}
+
+/// Whether a registration goes into the category's draw: approved, and checked in when the
+/// category has a check-in window
+pub fn in_draw(registration: &TournamentRegistration, window: Option<&CheckInWindow>) -> bool {
+    registration.registration_status == RegistrationStatus::Approved
+        && (window.is_none() || registration.checked_in_at.is_some())
+}
+
+/// Works out the no-shows of a closing window and which waitlisted entries replace them.
+/// Waitlisted entries that checked in are promoted in registration order, up to the
+/// category's `max_participants` or, without a limit, one for each no-show.
+pub fn plan_closure(
+    window: &CheckInWindow,
+    registrations: &[TournamentRegistration],
+    max_participants: Option<i32>,
+) -> WindowClosure {
+    let approved = registrations
+        .iter()
+        .filter(|r| r.registration_status == RegistrationStatus::Approved);
+    let (present, no_shows): (Vec<_>, Vec<_>) = approved.partition(|r| r.checked_in_at.is_some());
+    if !window.promote_waitlist {
+        return WindowClosure {
+            no_shows: no_shows.iter().map(|r| r.id).collect(),
+            promoted: Vec::new(),
+        };
+    }
+
+    let places = match max_participants {
+        Some(max) => (max as usize).saturating_sub(present.len()),
+        None => no_shows.len(),
+    };
+    let mut waiting: Vec<&TournamentRegistration> = registrations
+        .iter()
+        .filter(|r| {
+            r.registration_status == RegistrationStatus::Waitlisted && r.checked_in_at.is_some()
+        })
+        .collect();
+    waiting.sort_by_key(|r| (r.registration_date, r.id));
+    WindowClosure {
+        no_shows: no_shows.iter().map(|r| r.id).collect(),
+        promoted: waiting.iter().take(places).map(|r| r.id).collect(),
+    }
+}
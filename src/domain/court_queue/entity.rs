@@ -0,0 +1,100 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CallStatus {
+    /// Waiting for the participants to report
+    Called,
+    /// Both sides reported to the court
+    Ready,
+    /// Nobody, or only one side, reported before the final call ran out
+    Forfeited,
+    /// Withdrawn by the desk, or the match went ahead or was taken off the court
+    Closed,
+}
+
+/// Participants of a match being summoned to its court
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CourtCall {
+    pub match_id: Uuid,
+    pub court_id: Uuid,
+    pub status: CallStatus,
+    /// 1 for the first call; each repeat raises it
+    pub call_count: i32,
+    /// Calls made before a no-show forfeits the match
+    pub max_calls: i32,
+    pub first_called_at: DateTime<Utc>,
+    pub last_called_at: DateTime<Utc>,
+    /// When the latest call runs out
+    pub respond_by: DateTime<Utc>,
+    pub participant1_present: bool,
+    pub participant2_present: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl CourtCall {
+    /// A first call, answerable until `respond_by`
+    pub fn new(
+        match_id: Uuid,
+        court_id: Uuid,
+        max_calls: i32,
+        now: DateTime<Utc>,
+        respond_by: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            match_id,
+            court_id,
+            status: CallStatus::Called,
+            call_count: 1,
+            max_calls,
+            first_called_at: now,
+            last_called_at: now,
+            respond_by,
+            participant1_present: false,
+            participant2_present: false,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        matches!(self.status, CallStatus::Called | CallStatus::Ready)
+    }
+
+    pub fn is_final(&self) -> bool {
+        self.call_count >= self.max_calls
+    }
+
+    /// Calls again with the same time to respond as the previous call
+    pub fn repeat(&mut self, now: DateTime<Utc>) {
+        let window = self.respond_by - self.last_called_at;
+        self.call_count += 1;
+        self.last_called_at = now;
+        self.respond_by = now + window;
+        self.updated_at = now;
+    }
+
+    /// Records that side 1 or 2 reported; the call is ready once both have
+    pub fn mark_present(&mut self, participant: i32, now: DateTime<Utc>) {
+        match participant {
+            1 => self.participant1_present = true,
+            _ => self.participant2_present = true,
+        }
+        if self.participant1_present && self.participant2_present {
+            self.status = CallStatus::Ready;
+        }
+        self.updated_at = now;
+    }
+
+    /// The side that reported when the other did not; `None` when neither or both did
+    pub fn present_side(&self) -> Option<i32> {
+        match (self.participant1_present, self.participant2_present) {
+            (true, false) => Some(1),
+            (false, true) => Some(2),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,12 @@
+// Court queue domain module - the order matches are played in on each court, and the
+// calls that summon their participants
+
+pub mod entity;
+pub mod repository;
+pub mod value_objects;
+
+pub use entity::{CallStatus, CourtCall};
+pub use repository::CourtCallRepository;
+pub use value_objects::{
+    queue_window, split_queue, CallRequest, CourtQueue, DeskCourt, PresenceRequest, QueuedMatch,
+};
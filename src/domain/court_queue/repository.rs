@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::entity::CourtCall;
+use crate::shared::AppError;
+
+/// Repository trait for court calls; a match has at most one, replaced by each new call
+#[async_trait]
+pub trait CourtCallRepository: Send + Sync {
+    /// Inserts the call, or replaces the match's previous one
+    async fn save(&self, call: CourtCall) -> Result<CourtCall, AppError>;
+    async fn find_by_match(&self, match_id: Uuid) -> Result<Option<CourtCall>, AppError>;
+    /// Calls still waiting for participants whose time ran out at or before `now`
+    async fn find_expired(&self, now: DateTime<Utc>) -> Result<Vec<CourtCall>, AppError>;
+}
@@ -0,0 +1,105 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::entity::CourtCall;
+use crate::domain::match_domain::{Match, MatchStatus, MatchWithParticipants};
+
+/// Matches scheduled this long ago that never started are still waiting in the queue
+const QUEUE_LOOKBACK_HOURS: i64 = 12;
+/// How far ahead a court's queue reaches
+const QUEUE_HORIZON_HOURS: i64 = 24;
+
+/// Period of scheduled dates a court's queue is built from
+pub fn queue_window(now: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+    (
+        now - Duration::hours(QUEUE_LOOKBACK_HOURS),
+        now + Duration::hours(QUEUE_HORIZON_HOURS),
+    )
+}
+
+/// The match being played on a court, and the scheduled ones in the order they go on:
+/// by scheduled time, then match number. Postponed, cancelled and finished matches drop out.
+pub fn split_queue(matches: Vec<Match>) -> (Option<Match>, Vec<Match>) {
+    let mut current: Option<Match> = None;
+    let mut queue = Vec::new();
+    for m in matches {
+        match m.match_status {
+            MatchStatus::InProgress
+                if current
+                    .as_ref()
+                    .is_none_or(|c| c.actual_start_date < m.actual_start_date) =>
+            {
+                current = Some(m);
+            }
+            MatchStatus::Scheduled => queue.push(m),
+            _ => {}
+        }
+    }
+    queue.sort_by_key(|m| (m.scheduled_date, m.match_number.unwrap_or(i32::MAX), m.id));
+    (current, queue)
+}
+
+/// A match on a court's queue with its open call, if any
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedMatch {
+    #[serde(flatten)]
+    pub details: MatchWithParticipants,
+    pub call: Option<CourtCall>,
+}
+
+/// Everything lined up on one court
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CourtQueue {
+    pub court_id: Uuid,
+    pub court_name: String,
+    pub venue_id: Uuid,
+    pub venue_name: String,
+    /// In progress
+    pub current: Option<QueuedMatch>,
+    /// Scheduled, in playing order; the first is next up
+    pub queue: Vec<QueuedMatch>,
+}
+
+/// One court on the desk view: what is on and what comes next
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeskCourt {
+    pub court_id: Uuid,
+    pub court_name: String,
+    pub venue_name: String,
+    pub current: Option<QueuedMatch>,
+    pub next: Option<QueuedMatch>,
+    /// Scheduled matches after the next one
+    pub waiting: usize,
+}
+
+impl From<CourtQueue> for DeskCourt {
+    fn from(queue: CourtQueue) -> Self {
+        let waiting = queue.queue.len().saturating_sub(1);
+        DeskCourt {
+            court_id: queue.court_id,
+            court_name: queue.court_name,
+            venue_name: queue.venue_name,
+            current: queue.current,
+            next: queue.queue.into_iter().next(),
+            waiting,
+        }
+    }
+}
+
+/// Body of POST /matches/{id}/call; every field is optional
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CallRequest {
+    /// Time the participants have to report after each call
+    pub respond_within_minutes: Option<i64>,
+    /// Calls before a no-show forfeits the match
+    pub max_calls: Option<i32>,
+}
+
+/// Body of POST /matches/{id}/call/present
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresenceRequest {
+    /// 1 or 2
+    pub participant: i32,
+}
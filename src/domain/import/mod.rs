@@ -0,0 +1,11 @@
+// Import domain module - bulk entrant import (players, teams, registrations)
+
+pub mod repository;
+pub mod value_objects;
+
+pub use repository::ImportRepository;
+pub use value_objects::{
+    EntrantRow, ImportCommitResult, ImportOptions, ImportPlan, ImportReport, ImportRowReport,
+    ImportRowStatus, PlannedMember, PlannedRef, PlannedRegistration, PlayerWithEmail, Resolution,
+    ResolvedParticipant,
+};
@@ -0,0 +1,22 @@
+use async_trait::async_trait;
+
+use super::value_objects::{ImportCommitResult, ImportPlan, PlayerWithEmail};
+use crate::domain::participant::{Player, TeamWithMembers};
+use crate::shared::AppError;
+
+/// Repository trait for bulk entrant imports
+#[async_trait]
+pub trait ImportRepository: Send + Sync {
+    /// Players linked to a user whose email matches one of `emails` (case-insensitive)
+    async fn find_players_by_emails(
+        &self,
+        emails: &[String],
+    ) -> Result<Vec<PlayerWithEmail>, AppError>;
+    /// Players whose name matches one of `names` (case-insensitive, trimmed)
+    async fn find_players_by_names(&self, names: &[String]) -> Result<Vec<Player>, AppError>;
+    /// Teams whose name matches one of `names` (case-insensitive, trimmed), with members
+    async fn find_teams_by_names(&self, names: &[String])
+        -> Result<Vec<TeamWithMembers>, AppError>;
+    /// Writes the whole plan in a single transaction; nothing is kept if any insert fails.
+    async fn commit(&self, plan: ImportPlan) -> Result<ImportCommitResult, AppError>;
+}
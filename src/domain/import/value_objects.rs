@@ -0,0 +1,255 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use uuid::Uuid;
+
+use crate::domain::participant::{CreatePlayer, NewTeam, Player, Team, TeamMember};
+use crate::domain::tournament::TournamentRegistration;
+use crate::shared::AppError;
+
+/// Options for POST /tournaments/{id}/import
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct ImportOptions {
+    /// Category used for rows without a `category` column value
+    pub category_id: Option<Uuid>,
+    /// Validate and preview only; nothing is written
+    pub dry_run: bool,
+}
+
+/// One entrant row read from an import sheet, after header mapping
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EntrantRow {
+    /// Sheet row number (the header is row 1)
+    pub row: usize,
+    /// Category name or id
+    pub category: Option<String>,
+    pub player_name: Option<String>,
+    pub player_email: Option<String>,
+    pub partner_name: Option<String>,
+    pub partner_email: Option<String>,
+    pub team_name: Option<String>,
+    pub is_captain: Option<bool>,
+    pub jersey_number: Option<i32>,
+    pub notes: Option<String>,
+    /// Problems found while reading the row (bad numbers, flags, ...)
+    pub errors: Vec<String>,
+}
+
+#[derive(Clone, Copy)]
+enum EntrantColumn {
+    Category,
+    PlayerName,
+    PlayerEmail,
+    PartnerName,
+    PartnerEmail,
+    TeamName,
+    IsCaptain,
+    JerseyNumber,
+    Notes,
+}
+
+impl EntrantColumn {
+    fn from_header(header: &str) -> Option<Self> {
+        let key: String = header
+            .trim()
+            .to_lowercase()
+            .chars()
+            .map(|c| if c == ' ' || c == '-' { '_' } else { c })
+            .collect();
+        match key.as_str() {
+            "category" | "category_name" | "category_id" => Some(Self::Category),
+            "player_name" | "player" | "name" => Some(Self::PlayerName),
+            "player_email" | "email" => Some(Self::PlayerEmail),
+            "partner_name" | "partner" => Some(Self::PartnerName),
+            "partner_email" => Some(Self::PartnerEmail),
+            "team_name" | "team" => Some(Self::TeamName),
+            "is_captain" | "captain" => Some(Self::IsCaptain),
+            "jersey_number" | "jersey" | "number" => Some(Self::JerseyNumber),
+            "notes" | "note" => Some(Self::Notes),
+            _ => None,
+        }
+    }
+}
+
+impl EntrantRow {
+    /// Maps a sheet (header row first) to entrant rows. Unknown columns are ignored;
+    /// the sheet must at least identify a player by name or email.
+    pub fn from_sheet(sheet: Vec<Vec<String>>) -> Result<Vec<EntrantRow>, AppError> {
+        let mut rows = sheet.into_iter();
+        let header = rows
+            .next()
+            .ok_or_else(|| AppError::BadRequest("Import sheet is empty".into()))?;
+        let columns: Vec<Option<EntrantColumn>> = header
+            .iter()
+            .map(|h| EntrantColumn::from_header(h))
+            .collect();
+
+        let has = |wanted: fn(&EntrantColumn) -> bool| columns.iter().flatten().any(wanted);
+        if !has(|c| matches!(c, EntrantColumn::PlayerName | EntrantColumn::PlayerEmail)) {
+            return Err(AppError::BadRequest(
+                "Import sheet needs a player_name or player_email column".into(),
+            ));
+        }
+
+        let mut entrants = Vec::new();
+        for (index, cells) in rows.enumerate() {
+            if cells.iter().all(|c| c.trim().is_empty()) {
+                continue;
+            }
+            let mut entrant = EntrantRow {
+                row: index + 2,
+                ..Default::default()
+            };
+            for (column, cell) in columns.iter().zip(cells.iter()) {
+                let Some(column) = column else { continue };
+                let value = cell.trim();
+                if value.is_empty() {
+                    continue;
+                }
+                let text = Some(value.to_string());
+                match column {
+                    EntrantColumn::Category => entrant.category = text,
+                    EntrantColumn::PlayerName => entrant.player_name = text,
+                    EntrantColumn::PlayerEmail => entrant.player_email = text,
+                    EntrantColumn::PartnerName => entrant.partner_name = text,
+                    EntrantColumn::PartnerEmail => entrant.partner_email = text,
+                    EntrantColumn::TeamName => entrant.team_name = text,
+                    EntrantColumn::Notes => entrant.notes = text,
+                    EntrantColumn::IsCaptain => match parse_flag(value) {
+                        Some(flag) => entrant.is_captain = Some(flag),
+                        None => entrant
+                            .errors
+                            .push(format!("is_captain '{}' is not yes/no", value)),
+                    },
+                    // Spreadsheets store whole numbers as "7" or "7.0"
+                    EntrantColumn::JerseyNumber => match value.parse::<f64>() {
+                        Ok(n) if n.fract() == 0.0 && (0.0..=9999.0).contains(&n) => {
+                            entrant.jersey_number = Some(n as i32)
+                        }
+                        _ => entrant
+                            .errors
+                            .push(format!("jersey_number '{}' is not a whole number", value)),
+                    },
+                }
+            }
+            entrants.push(entrant);
+        }
+        Ok(entrants)
+    }
+}
+
+fn parse_flag(value: &str) -> Option<bool> {
+    match value.to_lowercase().as_str() {
+        "1" | "true" | "yes" | "y" | "x" => Some(true),
+        "0" | "false" | "no" | "n" => Some(false),
+        _ => None,
+    }
+}
+
+/// Existing player together with the email of their linked user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerWithEmail {
+    pub player: Player,
+    pub email: String,
+}
+
+// ==================== Plan ====================
+
+/// Reference to an entity that either exists or is created by the same plan
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlannedRef {
+    Existing(Uuid),
+    /// Index into `ImportPlan::players` or `ImportPlan::teams`
+    New(usize),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlannedMember {
+    pub team: PlannedRef,
+    pub player: PlannedRef,
+    pub is_captain: bool,
+    pub jersey_number: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PlannedRegistration {
+    pub tournament_category_id: Uuid,
+    pub team: Option<PlannedRef>,
+    pub player: Option<PlannedRef>,
+    pub partner: Option<PlannedRef>,
+    pub notes: Option<String>,
+    pub metadata: Option<JsonValue>,
+}
+
+/// Everything an import writes, in dependency order: players, teams, members, registrations
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ImportPlan {
+    pub players: Vec<CreatePlayer>,
+    pub teams: Vec<NewTeam>,
+    pub members: Vec<PlannedMember>,
+    pub registrations: Vec<PlannedRegistration>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportCommitResult {
+    pub players: Vec<Player>,
+    pub teams: Vec<Team>,
+    pub members: Vec<TeamMember>,
+    pub registrations: Vec<TournamentRegistration>,
+}
+
+// ==================== Report ====================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportRowStatus {
+    Valid,
+    Invalid,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Resolution {
+    /// Matched an existing record
+    Existing,
+    /// Will be (or was) created by the import
+    New,
+}
+
+/// How a name/email in the sheet was resolved
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolvedParticipant {
+    pub name: String,
+    pub id: Option<Uuid>,
+    pub resolution: Resolution,
+    /// `email` or `name` for existing matches
+    pub matched_by: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportRowReport {
+    pub row: usize,
+    pub status: ImportRowStatus,
+    pub category_id: Option<Uuid>,
+    pub player: Option<ResolvedParticipant>,
+    pub partner: Option<ResolvedParticipant>,
+    pub team: Option<ResolvedParticipant>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub committed: bool,
+    pub total_rows: usize,
+    pub valid_rows: usize,
+    pub invalid_rows: usize,
+    pub players_matched: usize,
+    pub players_to_create: usize,
+    pub teams_to_create: usize,
+    pub members_to_add: usize,
+    pub registrations_to_create: usize,
+    pub rows: Vec<ImportRowReport>,
+    /// Created records, present once the import has been committed
+    pub result: Option<ImportCommitResult>,
+}
@@ -1,9 +1,10 @@
 use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use uuid::Uuid;
 
-use super::value_objects::{MatchStatus, MatchType};
+use super::value_objects::{ForfeitKind, MatchStatus, MatchType};
 
 /// Core match entity
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,8 +26,14 @@ pub struct Match {
     pub actual_end_date: Option<DateTime<Utc>>,
     pub venue: Option<String>,
     pub court_number: Option<String>,
+    /// Court the match is assigned to; `venue` and `court_number` then hold its names
+    pub court_id: Option<Uuid>,
     pub winner_participant: Option<i32>,
     pub is_draw: bool,
+    /// Set on `forfeited` matches
+    pub forfeit_kind: Option<ForfeitKind>,
+    /// Side that defaulted; `None` on a forfeited match means both sides did
+    pub defaulting_participant: Option<i32>,
     pub referee_name: Option<String>,
     pub umpire_name: Option<String>,
     pub notes: Option<String>,
@@ -35,6 +42,22 @@ pub struct Match {
     pub updated_at: DateTime<Utc>,
 }
 
+impl Match {
+    /// Team of side 1 or 2, or its player when it is not a team
+    pub fn participant_id(&self, side: i32) -> Option<Uuid> {
+        match side {
+            1 => self.participant1_team_id.or(self.participant1_player_id),
+            2 => self.participant2_team_id.or(self.participant2_player_id),
+            _ => None,
+        }
+    }
+
+    /// Which side the team or player plays on
+    pub fn side_of(&self, participant_id: Uuid) -> Option<i32> {
+        (1..=2).find(|side| self.participant_id(*side) == Some(participant_id))
+    }
+}
+
 /// Match with resolved participant names for display
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MatchWithParticipants {
@@ -47,6 +70,7 @@ pub struct MatchWithParticipants {
     pub scheduled_date: DateTime<Utc>,
     pub venue: Option<String>,
     pub court_number: Option<String>,
+    pub court_id: Option<Uuid>,
     pub winner_participant: Option<i32>,
 }
 
@@ -55,6 +79,7 @@ pub struct MatchWithParticipants {
 pub struct MatchScheduleItem {
     pub id: Uuid,
     pub tournament_category_id: Uuid,
+    pub tournament_id: Uuid,
     pub tournament_name: String,
     pub category_name: String,
     pub participant1_name: String,
@@ -65,6 +90,39 @@ pub struct MatchScheduleItem {
     pub venue: Option<String>,
     pub court_number: Option<String>,
     pub round_number: Option<i32>,
+    /// When the match is now expected to start, after delays on its court; the actual
+    /// start once it has begun. `None` for matches that will not be played.
+    #[serde(default)]
+    pub estimated_start_date: Option<DateTime<Utc>>,
+    /// Minutes `estimated_start_date` is behind `scheduled_date`
+    #[serde(default)]
+    pub delay_minutes: Option<i64>,
+}
+
+/// A schedule item with its start time also given in the timezone it is shown in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalScheduleItem {
+    #[serde(flatten)]
+    pub item: MatchScheduleItem,
+    /// IANA name of the zone `local_scheduled_date` is in
+    pub timezone: String,
+    /// `scheduled_date` with the zone's offset, e.g. `2026-10-19T18:30:00+02:00`
+    pub local_scheduled_date: String,
+    /// `estimated_start_date` with the zone's offset
+    pub local_estimated_start_date: Option<String>,
+}
+
+impl LocalScheduleItem {
+    pub fn new(item: MatchScheduleItem, tz: Tz) -> Self {
+        Self {
+            timezone: tz.name().to_string(),
+            local_scheduled_date: item.scheduled_date.with_timezone(&tz).to_rfc3339(),
+            local_estimated_start_date: item
+                .estimated_start_date
+                .map(|at| at.with_timezone(&tz).to_rfc3339()),
+            item,
+        }
+    }
 }
 
 /// Match result/score entity
@@ -3,15 +3,21 @@
 
 pub mod entity;
 pub mod repository;
+pub mod scoring;
 pub mod value_objects;
 
 pub use entity::{
-    Match, MatchAnalytics, MatchComment, MatchMedia, MatchResult, MatchScheduleItem,
-    MatchStatistics, MatchSubscription, MatchWithParticipants,
+    LocalScheduleItem, Match, MatchAnalytics, MatchComment, MatchMedia, MatchResult,
+    MatchScheduleItem, MatchStatistics, MatchSubscription, MatchWithParticipants,
 };
 pub use repository::{MatchRepository, MatchResultRepository};
+pub use scoring::{
+    LiveScore, RecordScoringRequest, ScoringAction, ScoringEntry, ScoringLog, ScoringRules,
+    ScoringSnapshot, ScoringUpdate,
+};
 pub use value_objects::{
     AddMatchCommentRequest, BulkCancelMatchesRequest, CancelMatchRequest, CompleteMatchRequest,
-    EditableMatch, EditableMatchResult, LiveMatchUpdate, MatchScoreSummary, MatchStatus, MatchType,
-    NewMatch, NewMatchResult, RescheduleMatchRequest, UpdateMatchStatusRequest,
+    EditableMatch, EditableMatchResult, Forfeit, ForfeitKind, LiveMatchUpdate, MatchScoreSummary,
+    MatchStatus, MatchType, NewMatch, NewMatchResult, RescheduleMatchRequest, RetirementRequest,
+    SubscribeToMatchRequest, UpdateMatchStatusRequest, WalkoverRequest, WithdrawalRequest,
 };
@@ -1,4 +1,6 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::Value as JsonValue;
 use uuid::Uuid;
 
 use super::entity::{
@@ -6,7 +8,7 @@ use super::entity::{
     MatchStatistics, MatchSubscription, MatchWithParticipants,
 };
 use super::value_objects::{
-    EditableMatch, EditableMatchResult, LiveMatchUpdate, MatchScoreSummary, MatchStatus,
+    EditableMatch, EditableMatchResult, Forfeit, LiveMatchUpdate, MatchScoreSummary, MatchStatus,
     NewMatch, NewMatchResult, RescheduleMatchRequest,
 };
 use crate::shared::AppError;
@@ -24,13 +26,22 @@ pub trait MatchRepository: Send + Sync {
     async fn find_by_tournament(&self, tournament_id: Uuid) -> Result<Vec<Match>, AppError>;
     async fn find_by_category(&self, category_id: Uuid) -> Result<Vec<Match>, AppError>;
     async fn find_scheduled(&self) -> Result<Vec<MatchScheduleItem>, AppError>;
+    async fn find_scheduled_between(&self, from: DateTime<Utc>, until: DateTime<Utc>) -> Result<Vec<MatchScheduleItem>, AppError>;
+    async fn find_schedule_by_tournament(&self, tournament_id: Uuid) -> Result<Vec<MatchScheduleItem>, AppError>;
     async fn find_with_participants(&self, match_id: Uuid) -> Result<Option<MatchWithParticipants>, AppError>;
+    async fn find_with_participants_by_category(&self, category_id: Uuid) -> Result<Vec<MatchWithParticipants>, AppError>;
+    async fn find_by_court(&self, court_id: Uuid, from: DateTime<Utc>, until: DateTime<Utc>) -> Result<Vec<Match>, AppError>;
+    async fn find_in_period(&self, from: DateTime<Utc>, until: DateTime<Utc>) -> Result<Vec<Match>, AppError>;
     
     // Status management
     async fn update_status(&self, match_id: Uuid, status: MatchStatus) -> Result<Option<Match>, AppError>;
     async fn start_match(&self, match_id: Uuid) -> Result<Option<Match>, AppError>;
     async fn complete_match(&self, match_id: Uuid, winner: i32, is_draw: bool) -> Result<Option<Match>, AppError>;
     async fn cancel_match(&self, match_id: Uuid, reason: &str) -> Result<Option<Match>, AppError>;
+    /// Ends the match as forfeited, won by the side that did not default
+    async fn forfeit_match(&self, match_id: Uuid, forfeit: &Forfeit) -> Result<Option<Match>, AppError>;
+    /// Puts a team or player into side 1 or 2 of a match, e.g. a winner moving up the bracket
+    async fn assign_participant(&self, match_id: Uuid, side: i32, team_id: Option<Uuid>, player_id: Option<Uuid>, partner_id: Option<Uuid>) -> Result<Option<Match>, AppError>;
     async fn postpone_match(&self, match_id: Uuid) -> Result<Option<Match>, AppError>;
     async fn reschedule_match(&self, match_id: Uuid, request: RescheduleMatchRequest) -> Result<Option<Match>, AppError>;
 
@@ -40,6 +51,8 @@ pub trait MatchRepository: Send + Sync {
     
     // Live match
     async fn find_live_matches(&self) -> Result<Vec<Match>, AppError>;
+    /// Holds the match row until the unit of work ends, so concurrent writers take turns
+    async fn lock(&self, match_id: Uuid) -> Result<(), AppError>;
     async fn update_live_match(&self, match_id: Uuid, update: LiveMatchUpdate) -> Result<Option<Match>, AppError>;
     
     // Analytics
@@ -55,8 +68,11 @@ pub trait MatchRepository: Send + Sync {
     async fn add_match_comment(&self, match_id: Uuid, user_id: Uuid, comment: &str) -> Result<MatchComment, AppError>;
     
     // Subscriptions
-    async fn subscribe_to_match(&self, match_id: Uuid, user_id: Uuid) -> Result<MatchSubscription, AppError>;
+    async fn subscribe_to_match(&self, match_id: Uuid, user_id: Uuid, notification_preferences: JsonValue) -> Result<MatchSubscription, AppError>;
     async fn unsubscribe_from_match(&self, match_id: Uuid, user_id: Uuid) -> Result<(), AppError>;
+    async fn find_subscriptions(&self, match_id: Uuid) -> Result<Vec<MatchSubscription>, AppError>;
+    /// Linked accounts of the match's players and team members
+    async fn find_participant_users(&self, match_id: Uuid) -> Result<Vec<Uuid>, AppError>;
     
     // Bulk operations
     async fn bulk_update_matches(&self, match_ids: Vec<Uuid>, updates: EditableMatch) -> Result<Vec<Match>, AppError>;
@@ -71,8 +87,13 @@ pub trait MatchResultRepository: Send + Sync {
     async fn update(&self, result_id: Uuid, result_data: EditableMatchResult) -> Result<Option<MatchResult>, AppError>;
     async fn delete(&self, result_id: Uuid) -> Result<Option<MatchResult>, AppError>;
     async fn find_by_match(&self, match_id: Uuid) -> Result<Vec<MatchResult>, AppError>;
+    /// Results of every match in the category, in set order within each match
+    async fn find_by_category(&self, category_id: Uuid) -> Result<Vec<MatchResult>, AppError>;
     async fn get_match_score_summary(&self, match_id: Uuid) -> Result<Option<MatchScoreSummary>, AppError>;
     async fn find_by_set(&self, match_id: Uuid, set_number: i32) -> Result<Vec<MatchResult>, AppError>;
     async fn delete_by_match(&self, match_id: Uuid) -> Result<u64, AppError>;
     async fn count_by_match(&self, match_id: Uuid) -> Result<i64, AppError>;
+    /// Claims a live scoring sequence number for an action kept in `result_id`'s scoring data;
+    /// false when the match already has an action with that number
+    async fn claim_scoring_sequence(&self, match_id: Uuid, sequence: i64, result_id: Uuid) -> Result<bool, AppError>;
 }
@@ -0,0 +1,561 @@
+// Live scoring - a per-match log of point-by-point events, validated against a sport's
+// scoring rules. Game scores and rally statistics are derived by replaying the log.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::tournament::SportType;
+use crate::shared::AppError;
+
+/// How games (sets) and matches are won in a rally-scored sport
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScoringRules {
+    /// Points needed to take a game
+    pub game_points: i32,
+    /// Required lead to take a game
+    pub win_by: i32,
+    /// Score at which the next point takes the game regardless of the lead
+    pub point_cap: Option<i32>,
+    /// Games needed to take the match
+    pub games_to_win: i32,
+    /// Points needed in the deciding game, when it is played shorter
+    pub deciding_game_points: Option<i32>,
+    /// Timeouts each participant may call per game
+    pub timeouts_per_game: Option<i32>,
+    /// Timeouts each participant may call per match
+    pub timeouts_per_match: Option<i32>,
+}
+
+impl ScoringRules {
+    /// Rules for sports scored point by point; `None` for sports that are not
+    pub fn for_sport(sport: SportType) -> Option<Self> {
+        match sport {
+            SportType::TableTennis => Some(Self {
+                game_points: 11,
+                win_by: 2,
+                point_cap: None,
+                games_to_win: 3,
+                deciding_game_points: None,
+                timeouts_per_game: None,
+                timeouts_per_match: Some(1),
+            }),
+            SportType::Badminton => Some(Self {
+                game_points: 21,
+                win_by: 2,
+                point_cap: Some(30),
+                games_to_win: 2,
+                deciding_game_points: None,
+                timeouts_per_game: None,
+                timeouts_per_match: Some(0),
+            }),
+            SportType::Volleyball => Some(Self {
+                game_points: 25,
+                win_by: 2,
+                point_cap: None,
+                games_to_win: 3,
+                deciding_game_points: Some(15),
+                timeouts_per_game: Some(2),
+                timeouts_per_match: None,
+            }),
+            _ => None,
+        }
+    }
+
+    fn points_needed(&self, game_number: i32) -> i32 {
+        let deciding_game = game_number == self.games_to_win * 2 - 1;
+        match self.deciding_game_points {
+            Some(points) if deciding_game => points,
+            _ => self.game_points,
+        }
+    }
+
+    fn game_winner(&self, game_number: i32, game: &GameScore) -> Option<i32> {
+        let (leader, high, low) = if game.participant1 >= game.participant2 {
+            (1, game.participant1, game.participant2)
+        } else {
+            (2, game.participant2, game.participant1)
+        };
+        let capped = self.point_cap.is_some_and(|cap| high >= cap);
+        let won = high >= self.points_needed(game_number) && (high - low >= self.win_by || capped);
+        won.then_some(leader)
+    }
+}
+
+/// How a serve was hit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServeType {
+    Forehand,
+    Backhand,
+    Short,
+    Long,
+    Flick,
+    Float,
+    Jump,
+    Underhand,
+}
+
+/// Fault that ended a rally, committed by the side that lost it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FaultKind {
+    Service,
+    Net,
+    Out,
+    DoubleHit,
+    Rotation,
+    Obstruction,
+    Other,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CardColor {
+    /// Warning
+    Yellow,
+    /// Penalty point to the opponent
+    Red,
+}
+
+/// A scorekeeper action
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScoringAction {
+    /// A rally won by `participant`
+    PointWon {
+        participant: i32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        server: Option<i32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        serve_type: Option<ServeType>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        fault: Option<FaultKind>,
+    },
+    Let,
+    Timeout {
+        participant: i32,
+    },
+    SideSwitch,
+    Card {
+        participant: i32,
+        card: CardColor,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        player_id: Option<Uuid>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+    },
+    Substitution {
+        participant: i32,
+        player_out: Uuid,
+        player_in: Uuid,
+    },
+    /// Replaces the action recorded as `sequence`, e.g. a point credited to the wrong side
+    Correction {
+        sequence: i64,
+        action: Box<ScoringAction>,
+    },
+    /// Reverts the most recent action that has not been undone
+    Undo,
+}
+
+/// An accepted action, in the order it was recorded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoringEntry {
+    pub sequence: i64,
+    /// Game in progress when the action was recorded
+    pub game_number: i32,
+    #[serde(flatten)]
+    pub action: ScoringAction,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Points in one game
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameScore {
+    pub participant1: i32,
+    pub participant2: i32,
+    pub winner: Option<i32>,
+    #[serde(skip)]
+    timeouts: [i32; 2],
+}
+
+/// Score derived from a scoring log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LiveScore {
+    /// Sequence of the last accepted action, 0 before the first
+    pub sequence: i64,
+    /// Every game started so far; the last one is in progress unless the match is won
+    pub games: Vec<GameScore>,
+    pub participant1_games: i32,
+    pub participant2_games: i32,
+    pub winner: Option<i32>,
+    /// Whether the participants are on the opposite ends from where they started
+    pub ends_switched: bool,
+    #[serde(skip)]
+    match_timeouts: [i32; 2],
+}
+
+impl LiveScore {
+    fn new() -> Self {
+        Self {
+            sequence: 0,
+            games: vec![GameScore::default()],
+            participant1_games: 0,
+            participant2_games: 0,
+            winner: None,
+            ends_switched: false,
+            match_timeouts: [0, 0],
+        }
+    }
+
+    /// Game in progress, numbered from 1
+    pub fn game_number(&self) -> i32 {
+        self.games.len() as i32
+    }
+
+    /// Games in which at least one point was scored
+    pub fn games_played(&self) -> i32 {
+        self.games
+            .iter()
+            .filter(|g| g.participant1 + g.participant2 > 0)
+            .count() as i32
+    }
+
+    fn apply(&mut self, rules: &ScoringRules, action: &ScoringAction) -> Result<(), AppError> {
+        if self.winner.is_some() {
+            return Err(AppError::ValidationError(
+                "The match has already been won".to_string(),
+            ));
+        }
+        match action {
+            ScoringAction::PointWon {
+                participant,
+                server,
+                ..
+            } => {
+                if let Some(server) = server {
+                    side(*server)?;
+                }
+                self.award_point(rules, side(*participant)?);
+            }
+            ScoringAction::Timeout { participant } => {
+                let side = side(*participant)?;
+                let game = self.games.last_mut().expect("a game is always in progress");
+                if rules
+                    .timeouts_per_game
+                    .is_some_and(|limit| game.timeouts[side] >= limit)
+                    || rules
+                        .timeouts_per_match
+                        .is_some_and(|limit| self.match_timeouts[side] >= limit)
+                {
+                    return Err(AppError::ValidationError(format!(
+                        "Participant {} has no timeouts left",
+                        participant
+                    )));
+                }
+                game.timeouts[side] += 1;
+                self.match_timeouts[side] += 1;
+            }
+            ScoringAction::Card {
+                participant, card, ..
+            } => {
+                let side = side(*participant)?;
+                if *card == CardColor::Red {
+                    self.award_point(rules, 1 - side);
+                }
+            }
+            ScoringAction::Substitution {
+                participant,
+                player_out,
+                player_in,
+            } => {
+                side(*participant)?;
+                if player_out == player_in {
+                    return Err(AppError::ValidationError(
+                        "A player cannot substitute for themselves".to_string(),
+                    ));
+                }
+            }
+            ScoringAction::SideSwitch => self.ends_switched = !self.ends_switched,
+            ScoringAction::Let => {}
+            ScoringAction::Correction { .. } | ScoringAction::Undo => {
+                unreachable!("corrections and undos are resolved before actions are applied")
+            }
+        }
+        Ok(())
+    }
+
+    fn award_point(&mut self, rules: &ScoringRules, side: usize) {
+        let game_number = self.game_number();
+        let game = self.games.last_mut().expect("a game is always in progress");
+        if side == 0 {
+            game.participant1 += 1;
+        } else {
+            game.participant2 += 1;
+        }
+        if let Some(winner) = rules.game_winner(game_number, game) {
+            game.winner = Some(winner);
+            if winner == 1 {
+                self.participant1_games += 1;
+            } else {
+                self.participant2_games += 1;
+            }
+            if self.participant1_games == rules.games_to_win
+                || self.participant2_games == rules.games_to_win
+            {
+                self.winner = Some(winner);
+            } else {
+                self.games.push(GameScore::default());
+            }
+        }
+    }
+}
+
+fn side(participant: i32) -> Result<usize, AppError> {
+    match participant {
+        1 | 2 => Ok(participant as usize - 1),
+        _ => Err(AppError::ValidationError(
+            "participant must be 1 or 2".to_string(),
+        )),
+    }
+}
+
+/// Rally statistics for one participant
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParticipantRallyStats {
+    pub points_won: i32,
+    pub serves: i32,
+    pub points_won_on_serve: i32,
+    /// Faults that lost a rally, by kind
+    pub faults: BTreeMap<FaultKind, i32>,
+    pub serve_types: BTreeMap<ServeType, i32>,
+    /// Most points won in a row
+    pub longest_run: i32,
+    pub timeouts: i32,
+    pub yellow_cards: i32,
+    pub red_cards: i32,
+    pub substitutions: i32,
+}
+
+/// Statistics derived from a scoring log
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RallyStats {
+    pub rallies: i32,
+    pub lets: i32,
+    pub participant1: ParticipantRallyStats,
+    pub participant2: ParticipantRallyStats,
+    #[serde(skip)]
+    run: Option<(usize, i32)>,
+}
+
+impl RallyStats {
+    fn participant(&mut self, side: usize) -> &mut ParticipantRallyStats {
+        if side == 0 {
+            &mut self.participant1
+        } else {
+            &mut self.participant2
+        }
+    }
+
+    /// Counts an action already accepted by [`LiveScore::apply`]
+    fn record(&mut self, action: &ScoringAction) {
+        match action {
+            ScoringAction::PointWon {
+                participant,
+                server,
+                serve_type,
+                fault,
+            } => {
+                let winner = *participant as usize - 1;
+                self.rallies += 1;
+                if let Some(server) = server {
+                    let server = *server as usize - 1;
+                    let stats = self.participant(server);
+                    stats.serves += 1;
+                    if server == winner {
+                        stats.points_won_on_serve += 1;
+                    }
+                    if let Some(serve_type) = serve_type {
+                        *stats.serve_types.entry(*serve_type).or_default() += 1;
+                    }
+                }
+                if let Some(fault) = fault {
+                    *self
+                        .participant(1 - winner)
+                        .faults
+                        .entry(*fault)
+                        .or_default() += 1;
+                }
+                self.point_to(winner);
+            }
+            ScoringAction::Let => self.lets += 1,
+            ScoringAction::Timeout { participant } => {
+                self.participant(*participant as usize - 1).timeouts += 1
+            }
+            ScoringAction::Card {
+                participant, card, ..
+            } => {
+                let side = *participant as usize - 1;
+                match card {
+                    CardColor::Yellow => self.participant(side).yellow_cards += 1,
+                    CardColor::Red => {
+                        self.participant(side).red_cards += 1;
+                        self.point_to(1 - side);
+                    }
+                }
+            }
+            ScoringAction::Substitution { participant, .. } => {
+                self.participant(*participant as usize - 1).substitutions += 1
+            }
+            _ => {}
+        }
+    }
+
+    fn point_to(&mut self, side: usize) {
+        let run = match self.run {
+            Some((last, run)) if last == side => run + 1,
+            _ => 1,
+        };
+        self.run = Some((side, run));
+        let stats = self.participant(side);
+        stats.points_won += 1;
+        stats.longest_run = stats.longest_run.max(run);
+    }
+}
+
+/// Every action recorded for a match, undos and corrections included
+#[derive(Debug, Clone, Default)]
+pub struct ScoringLog {
+    pub entries: Vec<ScoringEntry>,
+}
+
+impl ScoringLog {
+    pub fn new(mut entries: Vec<ScoringEntry>) -> Self {
+        entries.sort_by_key(|e| e.sequence);
+        Self { entries }
+    }
+
+    /// Replays the log, skipping undone actions and applying corrections
+    pub fn score(&self, rules: &ScoringRules) -> Result<LiveScore, AppError> {
+        self.replay(rules).map(|(score, _)| score)
+    }
+
+    pub fn rally_stats(&self, rules: &ScoringRules) -> Result<RallyStats, AppError> {
+        self.replay(rules).map(|(_, stats)| stats)
+    }
+
+    /// Validates `action` against the rules and appends it, returning the new entry and score
+    pub fn record(
+        &mut self,
+        rules: &ScoringRules,
+        action: ScoringAction,
+    ) -> Result<(ScoringEntry, LiveScore), AppError> {
+        let before = self.score(rules)?;
+        match &action {
+            ScoringAction::Undo if self.active_entries().is_empty() => {
+                return Err(AppError::ValidationError("Nothing to undo".to_string()));
+            }
+            ScoringAction::Correction { action, .. }
+                if matches!(
+                    **action,
+                    ScoringAction::Undo | ScoringAction::Correction { .. }
+                ) =>
+            {
+                return Err(AppError::ValidationError(
+                    "Undos and corrections cannot be corrected".to_string(),
+                ));
+            }
+            _ => {}
+        }
+        let entry = ScoringEntry {
+            sequence: before.sequence + 1,
+            game_number: before.game_number(),
+            action,
+            recorded_at: Utc::now(),
+        };
+        self.entries.push(entry.clone());
+        match self.score(rules) {
+            Ok(after) => Ok((entry, after)),
+            Err(e) => {
+                self.entries.pop();
+                Err(e)
+            }
+        }
+    }
+
+    fn replay(&self, rules: &ScoringRules) -> Result<(LiveScore, RallyStats), AppError> {
+        let mut score = LiveScore::new();
+        let mut stats = RallyStats::default();
+        for action in self.effective_actions()? {
+            score.apply(rules, &action)?;
+            stats.record(&action);
+        }
+        score.sequence = self.entries.last().map_or(0, |e| e.sequence);
+        Ok((score, stats))
+    }
+
+    /// Entries that have not been undone
+    fn active_entries(&self) -> Vec<&ScoringEntry> {
+        let mut active = Vec::new();
+        for entry in &self.entries {
+            if entry.action == ScoringAction::Undo {
+                active.pop();
+            } else {
+                active.push(entry);
+            }
+        }
+        active
+    }
+
+    /// Active actions in order, with corrections folded into the actions they replace
+    fn effective_actions(&self) -> Result<Vec<ScoringAction>, AppError> {
+        let mut actions: Vec<(i64, ScoringAction)> = Vec::new();
+        for entry in self.active_entries() {
+            match &entry.action {
+                ScoringAction::Correction { sequence, action } => {
+                    let target = actions
+                        .iter_mut()
+                        .find(|(seq, _)| seq == sequence)
+                        .ok_or_else(|| {
+                            AppError::ValidationError(format!(
+                                "Action {} cannot be corrected",
+                                sequence
+                            ))
+                        })?;
+                    target.1 = (**action).clone();
+                }
+                action => actions.push((entry.sequence, action.clone())),
+            }
+        }
+        Ok(actions.into_iter().map(|(_, action)| action).collect())
+    }
+}
+
+/// A match's scoring log with the score it adds up to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoringSnapshot {
+    pub score: LiveScore,
+    pub entries: Vec<ScoringEntry>,
+}
+
+/// An accepted action with the score it produced
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoringUpdate {
+    pub match_id: Uuid,
+    pub tournament_id: Uuid,
+    pub category_id: Uuid,
+    pub entry: ScoringEntry,
+    pub score: LiveScore,
+}
+
+/// Body for recording a scoring action over HTTP
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordScoringRequest {
+    #[serde(flatten)]
+    pub action: ScoringAction,
+    /// Reject the action unless this is still the latest sequence
+    pub expected_sequence: Option<i64>,
+}
@@ -33,6 +33,57 @@ pub enum MatchType {
     Playoff,
 }
 
+/// How a forfeited match was decided
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForfeitKind {
+    /// Decided before play: a side did not turn up, withdrew or was disqualified
+    Walkover,
+    /// A side stopped partway through the match, e.g. injured
+    Retirement,
+}
+
+impl ForfeitKind {
+    pub fn key(&self) -> &'static str {
+        match self {
+            ForfeitKind::Walkover => "walkover",
+            ForfeitKind::Retirement => "retirement",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "walkover" => Some(ForfeitKind::Walkover),
+            "retirement" => Some(ForfeitKind::Retirement),
+            _ => None,
+        }
+    }
+}
+
+/// A match decided by one side, or both, defaulting
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Forfeit {
+    pub kind: ForfeitKind,
+    /// 1 or 2; `None` when neither side turned up
+    pub defaulting_participant: Option<i32>,
+    pub reason: String,
+}
+
+impl Forfeit {
+    /// The side that did not default, if only one did
+    pub fn winner(&self) -> Option<i32> {
+        self.defaulting_participant.map(|side| 3 - side)
+    }
+
+    /// Recorded in the match notes
+    pub fn note(&self) -> String {
+        match self.kind {
+            ForfeitKind::Walkover => format!("Walkover: {}", self.reason),
+            ForfeitKind::Retirement => format!("Retired: {}", self.reason),
+        }
+    }
+}
+
 // ============ DTOs ============
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -50,18 +101,21 @@ pub struct NewMatch {
     pub scheduled_date: DateTime<Utc>,
     pub venue: Option<String>,
     pub court_number: Option<String>,
+    /// Sets `venue` and `court_number` from the court when given
+    pub court_id: Option<Uuid>,
     pub referee_name: Option<String>,
     pub umpire_name: Option<String>,
     pub notes: Option<String>,
     pub metadata: Option<JsonValue>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct EditableMatch {
     pub match_status: Option<MatchStatus>,
     pub scheduled_date: Option<DateTime<Utc>>,
     pub venue: Option<String>,
     pub court_number: Option<String>,
+    pub court_id: Option<Uuid>,
     pub referee_name: Option<String>,
     pub umpire_name: Option<String>,
     pub notes: Option<String>,
@@ -102,6 +156,7 @@ pub struct CreateMatchRequest {
     pub scheduled_date: DateTime<Utc>,
     pub venue: Option<String>,
     pub court_number: Option<String>,
+    pub court_id: Option<Uuid>,
     pub referee_name: Option<String>,
     pub umpire_name: Option<String>,
     pub notes: Option<String>,
@@ -113,6 +168,7 @@ pub struct UpdateMatchRequest {
     pub scheduled_date: Option<DateTime<Utc>>,
     pub venue: Option<String>,
     pub court_number: Option<String>,
+    pub court_id: Option<Uuid>,
     pub referee_name: Option<String>,
     pub umpire_name: Option<String>,
     pub notes: Option<String>,
@@ -139,6 +195,27 @@ pub struct CancelMatchRequest {
     pub notify_participants: Option<bool>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WalkoverRequest {
+    /// 1 or 2; leave out when neither side turned up
+    pub defaulting_participant: Option<i32>,
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RetirementRequest {
+    pub retiring_participant: i32,
+    pub reason: String,
+}
+
+/// Takes a team or player out of a category for the rest of the event
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WithdrawalRequest {
+    /// Team id, or player id in singles and pairs
+    pub participant_id: Uuid,
+    pub reason: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PostponeMatchRequest {
     pub new_scheduled_date: DateTime<Utc>,
@@ -151,6 +228,7 @@ pub struct RescheduleMatchRequest {
     pub new_scheduled_date: DateTime<Utc>,
     pub new_venue: Option<String>,
     pub new_court_number: Option<String>,
+    pub new_court_id: Option<Uuid>,
     pub reason: Option<String>,
 }
 
@@ -1,11 +1,21 @@
 // Domain layer - core business rules (no external dependencies)
 
+pub mod calendar;
+pub mod check_in;
+pub mod court_queue;
+pub mod import;
 pub mod match_domain;
 pub mod notification;
+pub mod outbox;
 pub mod participant;
 pub mod payment;
+pub mod realtime;
+pub mod scheduling;
 pub mod statistics;
+pub mod ticket;
 pub mod tournament;
+pub mod unit_of_work;
 pub mod user;
+pub mod venue;
 
 // Re-export domain modules for convenient access
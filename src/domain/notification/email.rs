@@ -0,0 +1,143 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::entity::Notification;
+use super::value_objects::NotificationType;
+use crate::shared::AppError;
+
+/// A rendered plain-text email
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutgoingEmail {
+    pub to: String,
+    pub to_name: Option<String>,
+    pub subject: String,
+    pub body: String,
+    /// One-click unsubscribe link, sent as `List-Unsubscribe` (RFC 8058)
+    pub unsubscribe_url: Option<String>,
+}
+
+/// Hands emails to a mail transport
+#[async_trait]
+pub trait EmailSender: Send + Sync {
+    async fn send(&self, email: &OutgoingEmail) -> Result<(), AppError>;
+}
+
+// ==================== Templates ====================
+
+/// Wording of notification emails in one language. `{name}` is replaced with the
+/// recipient's name and `{url}` with the unsubscribe link.
+struct EmailStrings {
+    greeting: &'static str,
+    anonymous_greeting: &'static str,
+    tournament_update: &'static str,
+    match_reminder: &'static str,
+    result_posted: &'static str,
+    registration_confirmed: &'static str,
+    court_call: &'static str,
+    schedule_delay: &'static str,
+    footer: &'static str,
+    unsubscribe: &'static str,
+}
+
+const EN: EmailStrings = EmailStrings {
+    greeting: "Hi {name},",
+    anonymous_greeting: "Hi,",
+    tournament_update: "There is an update for one of your tournaments.",
+    match_reminder: "You have a match coming up.",
+    result_posted: "A match result has been posted.",
+    registration_confirmed: "There is news about your registration.",
+    court_call: "You have been called to your court.",
+    schedule_delay: "Your match is running late.",
+    footer: "You are receiving this email because of your notification settings.",
+    unsubscribe: "Stop emails like this one: {url}",
+};
+
+const ES: EmailStrings = EmailStrings {
+    greeting: "Hola {name}:",
+    anonymous_greeting: "Hola:",
+    tournament_update: "Hay novedades en uno de tus torneos.",
+    match_reminder: "Tienes un partido próximamente.",
+    result_posted: "Se ha publicado el resultado de un partido.",
+    registration_confirmed: "Hay novedades sobre tu inscripción.",
+    court_call: "Te han llamado a tu pista.",
+    schedule_delay: "Tu partido va con retraso.",
+    footer: "Recibes este correo por tu configuración de notificaciones.",
+    unsubscribe: "Dejar de recibir correos como este: {url}",
+};
+
+const FR: EmailStrings = EmailStrings {
+    greeting: "Bonjour {name},",
+    anonymous_greeting: "Bonjour,",
+    tournament_update: "Il y a du nouveau dans l'un de vos tournois.",
+    match_reminder: "Vous avez bientôt un match.",
+    result_posted: "Le résultat d'un match a été publié.",
+    registration_confirmed: "Il y a du nouveau concernant votre inscription.",
+    court_call: "On vous appelle sur votre terrain.",
+    schedule_delay: "Votre match a pris du retard.",
+    footer: "Vous recevez cet e-mail en raison de vos préférences de notification.",
+    unsubscribe: "Ne plus recevoir ce type d'e-mail : {url}",
+};
+
+const DE: EmailStrings = EmailStrings {
+    greeting: "Hallo {name},",
+    anonymous_greeting: "Hallo,",
+    tournament_update: "Es gibt Neuigkeiten zu einem deiner Turniere.",
+    match_reminder: "Du hast bald ein Spiel.",
+    result_posted: "Ein Spielergebnis wurde veröffentlicht.",
+    registration_confirmed: "Es gibt Neuigkeiten zu deiner Anmeldung.",
+    court_call: "Du wurdest auf deinen Platz gerufen.",
+    schedule_delay: "Dein Spiel verspätet sich.",
+    footer: "Du erhältst diese E-Mail aufgrund deiner Benachrichtigungseinstellungen.",
+    unsubscribe: "Solche E-Mails abbestellen: {url}",
+};
+
+/// Strings for a language tag such as `es`, `es-MX` or `fr_CA`; English otherwise
+fn strings_for(language: Option<&str>) -> &'static EmailStrings {
+    let primary = language
+        .and_then(|tag| tag.split(['-', '_']).next())
+        .map(str::to_ascii_lowercase);
+    match primary.as_deref() {
+        Some("es") => &ES,
+        Some("fr") => &FR,
+        Some("de") => &DE,
+        _ => &EN,
+    }
+}
+
+/// Renders a notification as an email in the recipient's language
+pub fn render_email(
+    notification: &Notification,
+    to: &str,
+    to_name: Option<&str>,
+    language: Option<&str>,
+    unsubscribe_url: Option<&str>,
+) -> OutgoingEmail {
+    let strings = strings_for(language);
+    let greeting = match to_name {
+        Some(name) => strings.greeting.replace("{name}", name),
+        None => strings.anonymous_greeting.to_string(),
+    };
+    let intro = match notification.notification_type {
+        NotificationType::TournamentUpdate => strings.tournament_update,
+        NotificationType::MatchReminder => strings.match_reminder,
+        NotificationType::ResultPosted => strings.result_posted,
+        NotificationType::RegistrationConfirmed => strings.registration_confirmed,
+        NotificationType::CourtCall => strings.court_call,
+        NotificationType::ScheduleDelay => strings.schedule_delay,
+    };
+    let mut body = format!(
+        "{}\n\n{}\n\n{}\n{}\n\n--\n{}\n",
+        greeting, intro, notification.title, notification.message, strings.footer
+    );
+    if let Some(url) = unsubscribe_url {
+        body.push_str(&strings.unsubscribe.replace("{url}", url));
+        body.push('\n');
+    }
+    OutgoingEmail {
+        to: to.to_string(),
+        to_name: to_name.map(str::to_string),
+        subject: notification.title.clone(),
+        body,
+        unsubscribe_url: unsubscribe_url.map(str::to_string),
+    }
+}
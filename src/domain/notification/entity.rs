@@ -2,7 +2,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use super::value_objects::NotificationType;
+use super::value_objects::{DeliveryChannel, DeliveryStatus, NotificationType};
 
 /// Notification entity
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,3 +20,37 @@ pub struct Notification {
     #[serde(with = "chrono::serde::ts_milliseconds")]
     pub updated_at: DateTime<Utc>,
 }
+
+/// Delivery state of a notification on one channel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationDelivery {
+    pub id: Uuid,
+    pub notification_id: Uuid,
+    pub channel: DeliveryChannel,
+    pub status: DeliveryStatus,
+    pub recipient: Option<String>,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    #[serde(with = "chrono::serde::ts_milliseconds_option")]
+    pub delivered_at: Option<DateTime<Utc>>,
+    #[serde(with = "chrono::serde::ts_milliseconds")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "chrono::serde::ts_milliseconds")]
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A browser that receives Web Push notifications for a user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PushSubscription {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    /// Push service URL; unique per browser subscription
+    pub endpoint: String,
+    pub p256dh: String,
+    pub auth: String,
+    pub user_agent: Option<String>,
+    #[serde(with = "chrono::serde::ts_milliseconds")]
+    pub created_at: DateTime<Utc>,
+    #[serde(with = "chrono::serde::ts_milliseconds")]
+    pub updated_at: DateTime<Utc>,
+}
@@ -1,11 +1,20 @@
 // Notification domain module - core business rules for notifications
 
+pub mod email;
 pub mod entity;
+pub mod preferences;
+pub mod push;
 pub mod repository;
 pub mod value_objects;
 
-pub use entity::Notification;
-pub use repository::NotificationRepository;
+pub use email::{render_email, EmailSender, OutgoingEmail};
+pub use entity::{Notification, NotificationDelivery, PushSubscription};
+pub use preferences::{NotificationPreferences, ReminderMode, SubscriptionPreferences};
+pub use push::{push_payload, supports_push, PushOutcome, PushSender};
+pub use repository::{
+    NotificationDeliveryRepository, NotificationRepository, PushSubscriptionRepository,
+};
 pub use value_objects::{
-    NewNotification, NotificationType,
+    DeliveryAttempt, DeliveryChannel, DeliveryStatus, NewNotification, NewPushSubscription,
+    NotificationType, PushSubscriptionRequest, UnsubscribePushRequest,
 };
@@ -0,0 +1,250 @@
+use chrono::NaiveTime;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use super::value_objects::{DeliveryChannel, NotificationType};
+use crate::shared::AppError;
+
+/// A user's notification settings, stored in `UserProfile.notification_preferences`:
+///
+/// ```json
+/// {
+///   "in_app": true,
+///   "email": true,
+///   "push": false,
+///   "types": { "match_reminder": { "email": false } },
+///   "muted_tournaments": ["7f6c..."],
+///   "reminders": { "mode": "digest" },
+///   "quiet_hours": { "start": "22:00", "end": "07:00" }
+/// }
+/// ```
+///
+/// A per-type setting overrides the channel-wide one; anything unset is enabled. A muted
+/// tournament sends nothing on any channel. Quiet hours are in the user's own timezone
+/// and may span midnight.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NotificationPreferences {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub in_app: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub push: Option<bool>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub types: BTreeMap<NotificationType, ChannelSwitches>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub muted_tournaments: Vec<Uuid>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reminders: Option<ReminderSettings>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quiet_hours: Option<QuietHours>,
+}
+
+/// Per-channel switches; `None` falls back to the wider setting
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChannelSwitches {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub in_app: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub push: Option<bool>,
+}
+
+impl ChannelSwitches {
+    fn get(&self, channel: DeliveryChannel) -> Option<bool> {
+        match channel {
+            DeliveryChannel::InApp => self.in_app,
+            DeliveryChannel::Email => self.email,
+            DeliveryChannel::Push => self.push,
+        }
+    }
+
+    fn set(&mut self, channel: DeliveryChannel, enabled: bool) {
+        match channel {
+            DeliveryChannel::InApp => self.in_app = Some(enabled),
+            DeliveryChannel::Email => self.email = Some(enabled),
+            DeliveryChannel::Push => self.push = Some(enabled),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReminderSettings {
+    #[serde(default)]
+    pub mode: ReminderMode,
+}
+
+impl NotificationPreferences {
+    /// Reads stored preferences; anything that does not fit the schema counts as unset
+    pub fn from_value(settings: Option<&Value>) -> Self {
+        settings
+            .and_then(|settings| Self::deserialize(settings).ok())
+            .unwrap_or_default()
+    }
+
+    /// Validates preferences submitted by a user
+    pub fn parse(settings: &Value) -> Result<Self, AppError> {
+        Self::deserialize(settings).map_err(|e| {
+            AppError::ValidationError(format!("Invalid notification preferences: {}", e))
+        })
+    }
+
+    pub fn to_value(&self) -> Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+
+    pub fn allows(
+        &self,
+        channel: DeliveryChannel,
+        notification_type: &NotificationType,
+        tournament_id: Option<Uuid>,
+    ) -> bool {
+        if tournament_id.is_some_and(|id| self.muted_tournaments.contains(&id)) {
+            return false;
+        }
+        let channel_wide = match channel {
+            DeliveryChannel::InApp => self.in_app,
+            DeliveryChannel::Email => self.email,
+            DeliveryChannel::Push => self.push,
+        };
+        self.types
+            .get(notification_type)
+            .and_then(|switches| switches.get(channel))
+            .or(channel_wide)
+            .unwrap_or(true)
+    }
+
+    /// Whether the notification reaches the user on any channel at all
+    pub fn wants(&self, notification_type: &NotificationType, tournament_id: Option<Uuid>) -> bool {
+        DeliveryChannel::ALL
+            .into_iter()
+            .any(|channel| self.allows(channel, notification_type, tournament_id))
+    }
+
+    /// Turns a channel off for one notification type, or for all of them
+    pub fn opt_out(
+        &mut self,
+        channel: DeliveryChannel,
+        notification_type: Option<NotificationType>,
+    ) {
+        match notification_type {
+            Some(notification_type) => self
+                .types
+                .entry(notification_type)
+                .or_default()
+                .set(channel, false),
+            None => match channel {
+                DeliveryChannel::InApp => self.in_app = Some(false),
+                DeliveryChannel::Email => self.email = Some(false),
+                DeliveryChannel::Push => self.push = Some(false),
+            },
+        }
+    }
+
+    pub fn reminder_mode(&self) -> ReminderMode {
+        self.reminders
+            .as_ref()
+            .map(|reminders| reminders.mode)
+            .unwrap_or_default()
+    }
+}
+
+/// What a match subscriber wants to hear about, stored in
+/// `MatchSubscription.notification_preferences`: `{"all": true}` or e.g.
+/// `{"all": false, "types": {"match_reminder": true}}`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SubscriptionPreferences {
+    #[serde(default = "enabled")]
+    pub all: bool,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub types: BTreeMap<NotificationType, bool>,
+}
+
+fn enabled() -> bool {
+    true
+}
+
+impl Default for SubscriptionPreferences {
+    fn default() -> Self {
+        Self {
+            all: true,
+            types: BTreeMap::new(),
+        }
+    }
+}
+
+impl SubscriptionPreferences {
+    pub fn from_value(settings: &Value) -> Self {
+        Self::deserialize(settings).unwrap_or_default()
+    }
+
+    pub fn parse(settings: &Value) -> Result<Self, AppError> {
+        Self::deserialize(settings).map_err(|e| {
+            AppError::ValidationError(format!("Invalid subscription preferences: {}", e))
+        })
+    }
+
+    pub fn to_value(&self) -> Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+
+    pub fn wants(&self, notification_type: &NotificationType) -> bool {
+        self.types
+            .get(notification_type)
+            .copied()
+            .unwrap_or(self.all)
+    }
+}
+
+/// How match reminders reach the user
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReminderMode {
+    /// One notification per match and reminder offset
+    #[default]
+    Individual,
+    /// A single morning summary of the day's matches
+    Digest,
+}
+
+/// Local time window in which nothing is sent; `start` after `end` wraps past midnight
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct QuietHours {
+    #[serde(with = "hh_mm")]
+    pub start: NaiveTime,
+    #[serde(with = "hh_mm")]
+    pub end: NaiveTime,
+}
+
+impl QuietHours {
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+/// `NaiveTime` as `"HH:MM"`
+mod hh_mm {
+    use chrono::NaiveTime;
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(time: &NaiveTime, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&time.format("%H:%M"))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveTime, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        NaiveTime::parse_from_str(&value, "%H:%M").map_err(de::Error::custom)
+    }
+}
@@ -0,0 +1,88 @@
+use async_trait::async_trait;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde_json::json;
+
+use super::entity::{Notification, PushSubscription};
+use super::value_objects::{NewPushSubscription, NotificationType};
+use crate::shared::AppError;
+
+/// What the push service did with a message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushOutcome {
+    Delivered,
+    /// The subscription expired or was revoked (404/410); it should be removed
+    Gone,
+}
+
+/// Hands encrypted messages to the browser's push service
+#[async_trait]
+pub trait PushSender: Send + Sync {
+    async fn send(
+        &self,
+        subscription: &PushSubscription,
+        payload: &[u8],
+    ) -> Result<PushOutcome, AppError>;
+}
+
+/// Types worth interrupting someone for while the app is closed
+pub fn supports_push(notification_type: &NotificationType) -> bool {
+    matches!(
+        notification_type,
+        NotificationType::MatchReminder
+            | NotificationType::ResultPosted
+            | NotificationType::CourtCall
+            | NotificationType::ScheduleDelay
+    )
+}
+
+/// JSON the service worker shows; kept small, push services cap payloads at 4 KB
+pub fn push_payload(notification: &Notification) -> Vec<u8> {
+    json!({
+        "notification_id": notification.id,
+        "type": notification.notification_type.key(),
+        "title": notification.title,
+        "body": notification.message,
+        "tournament_id": notification.tournament_id,
+        "match_id": notification.match_id,
+    })
+    .to_string()
+    .into_bytes()
+}
+
+/// Decoded subscription keys: the browser's uncompressed P-256 public key and its
+/// authentication secret
+pub fn decode_subscription_keys(
+    p256dh: &str,
+    auth: &str,
+) -> Result<([u8; 65], [u8; 16]), AppError> {
+    let decode = |value: &str| {
+        URL_SAFE_NO_PAD
+            .decode(value.trim_end_matches('='))
+            .map_err(|_| {
+                AppError::ValidationError("Push subscription keys must be base64url".into())
+            })
+    };
+    let public_key: [u8; 65] = decode(p256dh)?
+        .try_into()
+        .ok()
+        .filter(|key: &[u8; 65]| key[0] == 0x04)
+        .ok_or_else(|| {
+            AppError::ValidationError("p256dh must be an uncompressed P-256 key".into())
+        })?;
+    let auth_secret = decode(auth)?
+        .try_into()
+        .map_err(|_| AppError::ValidationError("auth must be 16 bytes".into()))?;
+    Ok((public_key, auth_secret))
+}
+
+impl NewPushSubscription {
+    pub fn validate(&self) -> Result<(), AppError> {
+        let endpoint = url::Url::parse(&self.endpoint)
+            .map_err(|_| AppError::ValidationError("endpoint must be a URL".into()))?;
+        if endpoint.scheme() != "https" {
+            return Err(AppError::ValidationError("endpoint must use https".into()));
+        }
+        decode_subscription_keys(&self.p256dh, &self.auth).map(|_| ())
+    }
+}
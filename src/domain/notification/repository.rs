@@ -1,8 +1,8 @@
 use async_trait::async_trait;
 use uuid::Uuid;
 
-use super::entity::Notification;
-use super::value_objects::NewNotification;
+use super::entity::{Notification, NotificationDelivery, PushSubscription};
+use super::value_objects::{DeliveryAttempt, DeliveryChannel, NewNotification, NewPushSubscription};
 use crate::shared::AppError;
 
 /// Repository trait for Notification entity operations
@@ -17,4 +17,25 @@ pub trait NotificationRepository: Send + Sync {
     async fn get_unread_count(&self, user_id: Uuid) -> Result<i64, AppError>;
     async fn create_bulk(&self, notifications: Vec<NewNotification>) -> Result<Vec<Notification>, AppError>;
     async fn get_by_id(&self, notification_id: Uuid) -> Result<Option<Notification>, AppError>;
+    /// Remembers `key` for the user; false when it was already claimed
+    async fn claim_dedup_key(&self, user_id: Uuid, key: &str) -> Result<bool, AppError>;
+}
+
+/// Repository trait for per-channel notification delivery tracking
+#[async_trait]
+pub trait NotificationDeliveryRepository: Send + Sync {
+    /// Records an attempt, creating the channel's delivery row on the first one
+    async fn record(&self, attempt: DeliveryAttempt) -> Result<NotificationDelivery, AppError>;
+    async fn find(&self, notification_id: Uuid, channel: DeliveryChannel) -> Result<Option<NotificationDelivery>, AppError>;
+    async fn find_by_notification(&self, notification_id: Uuid) -> Result<Vec<NotificationDelivery>, AppError>;
+}
+
+/// Repository trait for Web Push subscriptions
+#[async_trait]
+pub trait PushSubscriptionRepository: Send + Sync {
+    /// Saves the subscription, taking over the endpoint if another user had it
+    async fn upsert(&self, subscription: NewPushSubscription) -> Result<PushSubscription, AppError>;
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<PushSubscription>, AppError>;
+    async fn delete_for_user(&self, user_id: Uuid, endpoint: &str) -> Result<bool, AppError>;
+    async fn delete_by_endpoint(&self, endpoint: &str) -> Result<bool, AppError>;
 }
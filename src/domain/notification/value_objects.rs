@@ -1,13 +1,107 @@
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum NotificationType {
     TournamentUpdate,
     MatchReminder,
     ResultPosted,
     RegistrationConfirmed,
+    /// Summons to a court on tournament day
+    CourtCall,
+    /// A match now expected to start well after its scheduled time
+    ScheduleDelay,
+}
+
+impl NotificationType {
+    /// Key used for the type in notification preferences and templates
+    pub fn key(&self) -> &'static str {
+        match self {
+            NotificationType::TournamentUpdate => "tournament_update",
+            NotificationType::MatchReminder => "match_reminder",
+            NotificationType::ResultPosted => "result_posted",
+            NotificationType::RegistrationConfirmed => "registration_confirmed",
+            NotificationType::CourtCall => "court_call",
+            NotificationType::ScheduleDelay => "schedule_delay",
+        }
+    }
+}
+
+/// Where a notification is delivered. Only email and push deliveries are tracked; the
+/// in-app inbox is the notification itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryChannel {
+    InApp,
+    Email,
+    Push,
+}
+
+impl DeliveryChannel {
+    pub const ALL: [DeliveryChannel; 3] = [
+        DeliveryChannel::InApp,
+        DeliveryChannel::Email,
+        DeliveryChannel::Push,
+    ];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            DeliveryChannel::InApp => "in_app",
+            DeliveryChannel::Email => "email",
+            DeliveryChannel::Push => "push",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    Pending,
+    Sent,
+    Failed,
+    /// Not sent on purpose, e.g. the user opted out or has no address
+    Skipped,
+}
+
+/// Outcome of one delivery attempt of a notification over a channel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeliveryAttempt {
+    pub notification_id: Uuid,
+    pub channel: DeliveryChannel,
+    pub status: DeliveryStatus,
+    pub recipient: Option<String>,
+    pub error: Option<String>,
+}
+
+/// A browser's Web Push subscription, as returned by `PushManager.subscribe()`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PushSubscriptionRequest {
+    pub endpoint: String,
+    pub keys: PushSubscriptionKeys,
+}
+
+/// Base64url-encoded subscription keys
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PushSubscriptionKeys {
+    /// The browser's P-256 public key
+    pub p256dh: String,
+    /// The 16-byte authentication secret
+    pub auth: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UnsubscribePushRequest {
+    pub endpoint: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NewPushSubscription {
+    pub user_id: Uuid,
+    pub endpoint: String,
+    pub p256dh: String,
+    pub auth: String,
+    pub user_agent: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
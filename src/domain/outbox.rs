@@ -0,0 +1,240 @@
+// Transactional outbox - domain events stored in the same transaction as the change
+// that raised them, then delivered to in-process subscribers by a dispatcher
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use uuid::Uuid;
+
+use crate::domain::court_queue::CourtCall;
+use crate::domain::match_domain::Match;
+use crate::domain::notification::Notification;
+use crate::domain::payment::Payment;
+use crate::domain::tournament::TournamentRegistration;
+use crate::shared::AppError;
+
+/// Something that happened in the domain that other parts of the system react to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DomainEvent {
+    MatchCompleted {
+        match_id: Uuid,
+        tournament_id: Option<Uuid>,
+        category_id: Uuid,
+        /// 1 or 2; `None` for draws and matches closed through a plain status change
+        winner_participant: Option<i32>,
+        is_draw: bool,
+        /// Teams and players on either side of the match
+        team_ids: Vec<Uuid>,
+        player_ids: Vec<Uuid>,
+    },
+    RegistrationApproved {
+        registration_id: Uuid,
+        tournament_id: Option<Uuid>,
+        category_id: Uuid,
+        team_id: Option<Uuid>,
+        player_ids: Vec<Uuid>,
+    },
+    PaymentSucceeded {
+        payment_id: Uuid,
+        user_id: Uuid,
+        tournament_id: Uuid,
+        amount: Decimal,
+        currency: String,
+    },
+    /// The participants of a match were called to its court
+    MatchCalled {
+        match_id: Uuid,
+        tournament_id: Option<Uuid>,
+        category_id: Uuid,
+        venue_name: String,
+        court_name: String,
+        /// 1 for the first call
+        call_number: i32,
+        /// No further calls follow; a no-show after this one forfeits
+        final_call: bool,
+        respond_within_minutes: i64,
+        team_ids: Vec<Uuid>,
+        player_ids: Vec<Uuid>,
+    },
+    /// A notification was written to a user's inbox; drives the delivery channels
+    NotificationCreated {
+        notification_id: Uuid,
+        user_id: Uuid,
+    },
+}
+
+impl DomainEvent {
+    pub fn match_completed(m: &Match, tournament_id: Option<Uuid>) -> Self {
+        let (team_ids, player_ids) = sides(m);
+        DomainEvent::MatchCompleted {
+            match_id: m.id,
+            tournament_id,
+            category_id: m.tournament_category_id,
+            winner_participant: m.winner_participant,
+            is_draw: m.is_draw,
+            team_ids,
+            player_ids,
+        }
+    }
+
+    pub fn match_called(
+        m: &Match,
+        tournament_id: Option<Uuid>,
+        (venue_name, court_name): (String, String),
+        call: &CourtCall,
+        final_call: bool,
+    ) -> Self {
+        let (team_ids, player_ids) = sides(m);
+        DomainEvent::MatchCalled {
+            match_id: m.id,
+            tournament_id,
+            category_id: m.tournament_category_id,
+            venue_name,
+            court_name,
+            call_number: call.call_count,
+            final_call,
+            respond_within_minutes: (call.respond_by - call.last_called_at).num_minutes(),
+            team_ids,
+            player_ids,
+        }
+    }
+
+    pub fn registration_approved(
+        registration: &TournamentRegistration,
+        tournament_id: Option<Uuid>,
+    ) -> Self {
+        DomainEvent::RegistrationApproved {
+            registration_id: registration.id,
+            tournament_id,
+            category_id: registration.tournament_category_id,
+            team_id: registration.team_id,
+            player_ids: [registration.player_id, registration.partner_player_id]
+                .into_iter()
+                .flatten()
+                .collect(),
+        }
+    }
+
+    pub fn payment_succeeded(payment: &Payment) -> Self {
+        DomainEvent::PaymentSucceeded {
+            payment_id: payment.id,
+            user_id: payment.user_id,
+            tournament_id: payment.tournament_id,
+            amount: payment.amount,
+            currency: payment.currency.clone(),
+        }
+    }
+
+    pub fn notification_created(notification: &Notification) -> Self {
+        DomainEvent::NotificationCreated {
+            notification_id: notification.id,
+            user_id: notification.user_id,
+        }
+    }
+
+    /// The `type` tag, stored alongside the payload
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            DomainEvent::MatchCompleted { .. } => "match_completed",
+            DomainEvent::RegistrationApproved { .. } => "registration_approved",
+            DomainEvent::PaymentSucceeded { .. } => "payment_succeeded",
+            DomainEvent::MatchCalled { .. } => "match_called",
+            DomainEvent::NotificationCreated { .. } => "notification_created",
+        }
+    }
+}
+
+/// Teams, and players and partners, on either side of a match
+fn sides(m: &Match) -> (Vec<Uuid>, Vec<Uuid>) {
+    let team_ids = [m.participant1_team_id, m.participant2_team_id]
+        .into_iter()
+        .flatten()
+        .collect();
+    let player_ids = [
+        m.participant1_player_id,
+        m.participant1_partner_id,
+        m.participant2_player_id,
+        m.participant2_partner_id,
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    (team_ids, player_ids)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutboxStatus {
+    /// Waiting for (re)delivery
+    Pending,
+    /// Every subscriber handled it
+    Processed,
+    /// Gave up after the last attempt
+    Failed,
+}
+
+/// A stored domain event and its delivery state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboxMessage {
+    pub id: Uuid,
+    pub event_type: String,
+    pub payload: JsonValue,
+    pub status: OutboxStatus,
+    /// Deliveries started so far, including the current one
+    pub attempts: i32,
+    /// Subscribers that already handled the event; they are skipped on retries
+    pub delivered_to: Vec<String>,
+    pub last_error: Option<String>,
+    /// Not delivered before this time, either because of a retry delay or a lease
+    pub available_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub processed_at: Option<DateTime<Utc>>,
+}
+
+impl OutboxMessage {
+    /// Decodes the payload; fails for events written by a newer version
+    pub fn event(&self) -> Result<DomainEvent, AppError> {
+        serde_json::from_value(self.payload.clone()).map_err(|e| {
+            AppError::InternalError(format!(
+                "Undecodable {} outbox event: {}",
+                self.event_type, e
+            ))
+        })
+    }
+}
+
+/// Repository trait for outbox messages. `enqueue` belongs in the unit of work of
+/// the change it describes, so the event is stored if and only if the change commits.
+#[async_trait]
+pub trait OutboxRepository: Send + Sync {
+    async fn enqueue(&self, event: &DomainEvent) -> Result<OutboxMessage, AppError>;
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<OutboxMessage>, AppError>;
+    /// Leases up to `limit` pending, due messages, oldest first: counts an attempt and
+    /// hides them until `lease_until`, so a crashed dispatcher's messages come back.
+    async fn claim_due(
+        &self,
+        limit: i64,
+        lease_until: DateTime<Utc>,
+    ) -> Result<Vec<OutboxMessage>, AppError>;
+    async fn mark_processed(&self, id: Uuid, delivered_to: &[String]) -> Result<(), AppError>;
+    /// Records a failed delivery; `retry_at` of `None` gives up on the message
+    async fn mark_failed(
+        &self,
+        id: Uuid,
+        delivered_to: &[String],
+        error: &str,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<(), AppError>;
+}
+
+/// Reacts to domain events. Delivery is at least once: a subscriber sees an event
+/// again if the dispatcher stops before recording that it was handled.
+#[async_trait]
+pub trait DomainEventSubscriber: Send + Sync {
+    /// Stable name recorded in `delivered_to`
+    fn name(&self) -> &'static str;
+    async fn handle(&self, event: &DomainEvent) -> Result<(), AppError>;
+}
@@ -0,0 +1,141 @@
+// Realtime events - typed change notifications the application services publish
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::court_queue::CourtCall;
+use crate::domain::match_domain::{LiveScore, ScoringUpdate};
+
+/// Event types for real-time updates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RealtimeEvent {
+    MatchUpdate {
+        match_id: Uuid,
+        tournament_id: Option<Uuid>,
+        category_id: Option<Uuid>,
+        status: Option<String>,
+    },
+    /// Set or period results of a match were entered, changed or removed
+    ResultUpdate {
+        match_id: Uuid,
+        tournament_id: Option<Uuid>,
+        category_id: Option<Uuid>,
+    },
+    BracketUpdate {
+        tournament_id: Uuid,
+        category_id: Option<Uuid>,
+    },
+    StandingsUpdate {
+        tournament_id: Uuid,
+        category_id: Option<Uuid>,
+    },
+    RegistrationUpdate {
+        registration_id: Uuid,
+        tournament_id: Option<Uuid>,
+        category_id: Uuid,
+        status: Option<String>,
+    },
+    /// A point-by-point scoring action from a scorekeeper
+    ScoreUpdate {
+        match_id: Uuid,
+        tournament_id: Uuid,
+        category_id: Uuid,
+        sequence: i64,
+        score: LiveScore,
+    },
+    /// A match's participants were called to its court, or the call changed
+    CourtCall {
+        tournament_id: Option<Uuid>,
+        category_id: Uuid,
+        call: CourtCall,
+    },
+    /// What is playing or next up on a court changed
+    CourtQueueUpdate {
+        court_id: Uuid,
+        tournament_id: Option<Uuid>,
+        current_match_id: Option<Uuid>,
+        next_match_id: Option<Uuid>,
+    },
+    Notification {
+        user_id: Uuid,
+        notification_id: Uuid,
+    },
+    /// Notifications were marked as read; `None` means all of the user's
+    NotificationsRead {
+        user_id: Uuid,
+        notification_id: Option<Uuid>,
+    },
+}
+
+impl RealtimeEvent {
+    pub fn score_update(update: &ScoringUpdate) -> Self {
+        RealtimeEvent::ScoreUpdate {
+            match_id: update.match_id,
+            tournament_id: update.tournament_id,
+            category_id: update.category_id,
+            sequence: update.entry.sequence,
+            score: update.score.clone(),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    /// The only user allowed to receive this event, for private events
+    pub fn owner(&self) -> Option<Uuid> {
+        match self {
+            RealtimeEvent::Notification { user_id, .. }
+            | RealtimeEvent::NotificationsRead { user_id, .. } => Some(*user_id),
+            _ => None,
+        }
+    }
+
+    pub fn tournament_id(&self) -> Option<Uuid> {
+        match self {
+            RealtimeEvent::MatchUpdate { tournament_id, .. }
+            | RealtimeEvent::ResultUpdate { tournament_id, .. }
+            | RealtimeEvent::RegistrationUpdate { tournament_id, .. }
+            | RealtimeEvent::CourtCall { tournament_id, .. }
+            | RealtimeEvent::CourtQueueUpdate { tournament_id, .. } => *tournament_id,
+            RealtimeEvent::BracketUpdate { tournament_id, .. }
+            | RealtimeEvent::StandingsUpdate { tournament_id, .. }
+            | RealtimeEvent::ScoreUpdate { tournament_id, .. } => Some(*tournament_id),
+            RealtimeEvent::Notification { .. } | RealtimeEvent::NotificationsRead { .. } => None,
+        }
+    }
+
+    pub fn category_id(&self) -> Option<Uuid> {
+        match self {
+            RealtimeEvent::MatchUpdate { category_id, .. }
+            | RealtimeEvent::ResultUpdate { category_id, .. }
+            | RealtimeEvent::BracketUpdate { category_id, .. }
+            | RealtimeEvent::StandingsUpdate { category_id, .. } => *category_id,
+            RealtimeEvent::RegistrationUpdate { category_id, .. }
+            | RealtimeEvent::ScoreUpdate { category_id, .. }
+            | RealtimeEvent::CourtCall { category_id, .. } => Some(*category_id),
+            RealtimeEvent::CourtQueueUpdate { .. }
+            | RealtimeEvent::Notification { .. }
+            | RealtimeEvent::NotificationsRead { .. } => None,
+        }
+    }
+
+    pub fn match_id(&self) -> Option<Uuid> {
+        match self {
+            RealtimeEvent::MatchUpdate { match_id, .. }
+            | RealtimeEvent::ResultUpdate { match_id, .. }
+            | RealtimeEvent::ScoreUpdate { match_id, .. } => Some(*match_id),
+            RealtimeEvent::CourtCall { call, .. } => Some(call.match_id),
+            _ => None,
+        }
+    }
+}
+
+/// Delivers realtime events to whoever is listening. Publishing is best effort:
+/// a change that was committed is never failed because its event could not be sent.
+#[async_trait]
+pub trait EventPublisher: Send + Sync {
+    async fn publish(&self, event: RealtimeEvent);
+}
@@ -0,0 +1,122 @@
+// Conflict detection - double-booked participants, courts and officials between matches
+// whose times overlap.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictKind {
+    /// A player or team, directly or through a team's members
+    Participant,
+    Court,
+    /// A referee or umpire
+    Staff,
+}
+
+/// One clash between the match being saved and another match
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduleConflict {
+    pub kind: ConflictKind,
+    /// The match being saved; `None` while it is being created
+    pub match_id: Option<Uuid>,
+    pub conflicting_match_id: Uuid,
+    /// When the other match is expected to take place
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    /// Shared players and teams, or the shared court
+    pub subject_ids: Vec<Uuid>,
+    /// Shared officials, or the shared court's name
+    pub subject_names: Vec<String>,
+}
+
+/// What a match occupies while it is played
+#[derive(Debug, Clone)]
+pub struct MatchSlot {
+    pub match_id: Option<Uuid>,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    /// Players and teams, with the members of every team
+    pub participants: Vec<Uuid>,
+    pub court_id: Option<Uuid>,
+    /// Court named only by `venue` and `court_number`, for matches without a `court_id`
+    pub court_label: Option<String>,
+    pub officials: Vec<String>,
+}
+
+impl MatchSlot {
+    /// Lower-cased, trimmed venue and court number, when both are set
+    pub fn label(venue: Option<&str>, court_number: Option<&str>) -> Option<String> {
+        let venue = venue.map(str::trim).filter(|v| !v.is_empty())?;
+        let court = court_number.map(str::trim).filter(|c| !c.is_empty())?;
+        Some(format!("{} / {}", venue, court).to_lowercase())
+    }
+
+    /// Lower-cased, trimmed official names
+    pub fn officials(names: &[Option<&str>]) -> Vec<String> {
+        let mut officials: Vec<String> = names
+            .iter()
+            .flatten()
+            .map(|n| n.trim().to_lowercase())
+            .filter(|n| !n.is_empty())
+            .collect();
+        officials.dedup();
+        officials
+    }
+
+    /// Every clash with `other`, one per kind; none when the times do not overlap
+    pub fn conflicts_with(&self, other: &MatchSlot) -> Vec<ScheduleConflict> {
+        let Some(other_id) = other.match_id else {
+            return Vec::new();
+        };
+        if self.match_id == Some(other_id)
+            || !(self.starts_at < other.ends_at && other.starts_at < self.ends_at)
+        {
+            return Vec::new();
+        }
+        let conflict = |kind, subject_ids, subject_names| ScheduleConflict {
+            kind,
+            match_id: self.match_id,
+            conflicting_match_id: other_id,
+            starts_at: other.starts_at,
+            ends_at: other.ends_at,
+            subject_ids,
+            subject_names,
+        };
+
+        let mut conflicts = Vec::new();
+        let shared: Vec<Uuid> = self
+            .participants
+            .iter()
+            .filter(|id| other.participants.contains(id))
+            .copied()
+            .collect();
+        if !shared.is_empty() {
+            conflicts.push(conflict(ConflictKind::Participant, shared, Vec::new()));
+        }
+        match (self.court_id, other.court_id) {
+            (Some(a), Some(b)) if a == b => {
+                conflicts.push(conflict(ConflictKind::Court, vec![a], Vec::new()));
+            }
+            (None, None) => {
+                if let (Some(a), Some(b)) = (&self.court_label, &other.court_label) {
+                    if a == b {
+                        conflicts.push(conflict(ConflictKind::Court, Vec::new(), vec![a.clone()]));
+                    }
+                }
+            }
+            _ => {}
+        }
+        let officials: Vec<String> = self
+            .officials
+            .iter()
+            .filter(|name| other.officials.contains(name))
+            .cloned()
+            .collect();
+        if !officials.is_empty() {
+            conflicts.push(conflict(ConflictKind::Staff, Vec::new(), officials));
+        }
+        conflicts
+    }
+}
@@ -0,0 +1,122 @@
+// Estimated start times - when each match is now likely to start, once the matches
+// before it on the same court have run over or started late.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use super::conflicts::MatchSlot;
+use crate::domain::match_domain::{Match, MatchStatus};
+
+/// Finished matches needed before their actual lengths replace the configured estimate
+pub const MIN_DURATION_SAMPLES: usize = 3;
+
+/// A match's expected start compared with its schedule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StartEstimate {
+    pub match_id: Uuid,
+    pub scheduled_date: DateTime<Utc>,
+    /// The actual start once the match has begun
+    pub estimated_start_date: DateTime<Utc>,
+    /// Whole minutes behind schedule; never negative
+    pub delay_minutes: i64,
+}
+
+impl StartEstimate {
+    fn new(m: &Match, estimated_start_date: DateTime<Utc>) -> Self {
+        Self {
+            match_id: m.id,
+            scheduled_date: m.scheduled_date,
+            estimated_start_date,
+            delay_minutes: (estimated_start_date - m.scheduled_date)
+                .num_minutes()
+                .max(0),
+        }
+    }
+}
+
+/// Median length of the matches that have both an actual start and end, or `None` with
+/// fewer than `MIN_DURATION_SAMPLES` of them
+pub fn historical_duration<'a>(matches: impl IntoIterator<Item = &'a Match>) -> Option<Duration> {
+    let mut lengths: Vec<Duration> = matches
+        .into_iter()
+        .filter_map(|m| Some(m.actual_end_date? - m.actual_start_date?))
+        .filter(|length| *length > Duration::zero())
+        .collect();
+    if lengths.len() < MIN_DURATION_SAMPLES {
+        return None;
+    }
+    lengths.sort();
+    Some(lengths[lengths.len() / 2])
+}
+
+/// Estimated starts for every match that takes up court time; cancelled, postponed,
+/// forfeited and bye matches get none.
+///
+/// Each court is walked in playing order, tracking when it comes free: a finished match
+/// frees it at its actual end, one in progress at its actual start plus `duration` (or
+/// `now`, if it has already run longer), and a scheduled match starts at the latest of
+/// its scheduled time, the court coming free and `now`. A delay early in the day
+/// therefore carries down the court's queue until a gap in the schedule absorbs it.
+/// Matches without a court are only held back by `now`.
+pub fn estimate_starts(
+    matches: &[Match],
+    duration: impl Fn(&Match) -> Duration,
+    now: DateTime<Utc>,
+) -> Vec<StartEstimate> {
+    let mut courts: BTreeMap<String, Vec<&Match>> = BTreeMap::new();
+    let mut unassigned = Vec::new();
+    for m in matches.iter().filter(|m| takes_court_time(m)) {
+        let court = match m.court_id {
+            Some(court_id) => Some(court_id.to_string()),
+            None => MatchSlot::label(m.venue.as_deref(), m.court_number.as_deref()),
+        };
+        match court {
+            Some(court) => courts.entry(court).or_default().push(m),
+            None => unassigned.push(m),
+        }
+    }
+
+    let mut estimates = Vec::new();
+    for m in unassigned {
+        let starts_at = match m.actual_start_date {
+            Some(started) => started,
+            None => m.scheduled_date.max(now),
+        };
+        estimates.push(StartEstimate::new(m, starts_at));
+    }
+    for mut court in courts.into_values() {
+        // Whatever has started goes first, in the order it started; then the queue
+        court.sort_by_key(|m| {
+            (
+                m.actual_start_date.is_none(),
+                m.actual_start_date.unwrap_or(m.scheduled_date),
+                m.match_number.unwrap_or(i32::MAX),
+                m.id,
+            )
+        });
+        let mut free_at: Option<DateTime<Utc>> = None;
+        for m in court {
+            let starts_at = match m.actual_start_date {
+                Some(started) => started,
+                None => m.scheduled_date.max(now).max(free_at.unwrap_or(now)),
+            };
+            let ends_at = match (m.actual_end_date, m.match_status) {
+                (Some(ended), _) => ended,
+                (None, MatchStatus::InProgress) => (starts_at + duration(m)).max(now),
+                (None, _) => starts_at + duration(m),
+            };
+            free_at = Some(free_at.map_or(ends_at, |free| free.max(ends_at)));
+            estimates.push(StartEstimate::new(m, starts_at));
+        }
+    }
+    estimates
+}
+
+fn takes_court_time(m: &Match) -> bool {
+    matches!(
+        m.match_status,
+        MatchStatus::Scheduled | MatchStatus::InProgress | MatchStatus::Completed
+    )
+}
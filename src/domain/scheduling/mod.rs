@@ -0,0 +1,14 @@
+// Scheduling domain module - placing matches on courts and times
+
+pub mod conflicts;
+pub mod estimates;
+pub mod planner;
+pub mod value_objects;
+
+pub use conflicts::{MatchSlot, ScheduleConflict};
+pub use estimates::{estimate_starts, historical_duration, StartEstimate};
+pub use planner::{
+    estimated_duration, occupied_interval, participants_of, stage_of, Booking, PendingMatch,
+    SchedulePlanner,
+};
+pub use value_objects::{ScheduleReport, ScheduleRequest};
@@ -0,0 +1,259 @@
+// Schedule planning - greedy placement of matches on courts, earliest slot first, subject
+// to court availability, venue opening hours, participant rest and round order.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+use super::value_objects::{ScheduledSlot, UnplacedMatch};
+use crate::domain::match_domain::{Match, MatchType};
+use crate::domain::tournament::{SportType, TournamentCategory};
+use crate::domain::venue::{Court, Venue};
+
+/// Typical length of a match in each sport, breaks and warm-up included
+fn sport_duration(sport: SportType) -> Duration {
+    Duration::minutes(match sport {
+        SportType::TableTennis => 30,
+        SportType::Badminton => 40,
+        SportType::Volleyball => 75,
+        SportType::Basketball => 75,
+        SportType::Tennis => 90,
+        SportType::Football => 105,
+        SportType::Chess => 120,
+        SportType::Cricket => 180,
+        SportType::Esports => 60,
+    })
+}
+
+/// The category's `match_duration_minutes` constraint, or the sport's typical length
+pub fn estimated_duration(sport: SportType, category: Option<&TournamentCategory>) -> Duration {
+    category
+        .and_then(|c| c.constraints.as_ref())
+        .and_then(|c| c.get("match_duration_minutes"))
+        .and_then(|v| v.as_i64())
+        .filter(|minutes| *minutes > 0)
+        .map(Duration::minutes)
+        .unwrap_or_else(|| sport_duration(sport))
+}
+
+/// Where a match falls in its category's progression; lower stages are played first
+pub fn stage_of(match_type: MatchType, round_number: Option<i32>) -> (u8, i32) {
+    let stage = match match_type {
+        MatchType::Qualifying => 0,
+        MatchType::GroupStage => 1,
+        MatchType::Playoff => 2,
+        MatchType::RoundOf128 => 3,
+        MatchType::RoundOf64 => 4,
+        MatchType::RoundOf32 => 5,
+        MatchType::RoundOf16 => 6,
+        MatchType::QuarterFinal => 7,
+        MatchType::SemiFinal => 8,
+        MatchType::ThirdPlace | MatchType::Final => 9,
+    };
+    (stage, round_number.unwrap_or(0))
+}
+
+/// Teams and players on either side of a match
+pub fn participants_of(m: &Match) -> Vec<Uuid> {
+    [
+        m.participant1_team_id,
+        m.participant1_player_id,
+        m.participant1_partner_id,
+        m.participant2_team_id,
+        m.participant2_player_id,
+        m.participant2_partner_id,
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// When a match was played, or when it is expected to be: from its actual start, or else its
+/// scheduled time, to its actual end, or else `duration` later
+pub fn occupied_interval(m: &Match, duration: Duration) -> (DateTime<Utc>, DateTime<Utc>) {
+    let starts_at = m.actual_start_date.unwrap_or(m.scheduled_date);
+    (starts_at, m.actual_end_date.unwrap_or(starts_at + duration))
+}
+
+/// Time already taken by a match, on a court and for its participants
+#[derive(Debug, Clone)]
+pub struct Booking {
+    /// Set for matches of the tournament being scheduled, to keep round order
+    pub category_id: Option<Uuid>,
+    pub stage: (u8, i32),
+    pub court_id: Option<Uuid>,
+    pub participants: Vec<Uuid>,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+}
+
+impl Booking {
+    fn overlaps(&self, starts_at: DateTime<Utc>, ends_at: DateTime<Utc>) -> bool {
+        self.starts_at < ends_at && starts_at < self.ends_at
+    }
+}
+
+/// A match waiting for a court and a time
+#[derive(Debug, Clone)]
+pub struct PendingMatch {
+    pub match_id: Uuid,
+    pub category_id: Uuid,
+    pub stage: (u8, i32),
+    pub match_number: Option<i32>,
+    pub participants: Vec<Uuid>,
+    pub duration: Duration,
+}
+
+pub struct SchedulePlanner<'a> {
+    /// Tried in order at each start time
+    courts: &'a [(Venue, Court)],
+    starts_at: DateTime<Utc>,
+    ends_at: DateTime<Utc>,
+    rest: Duration,
+    slot: Duration,
+    bookings: Vec<Booking>,
+}
+
+impl<'a> SchedulePlanner<'a> {
+    pub fn new(
+        courts: &'a [(Venue, Court)],
+        starts_at: DateTime<Utc>,
+        ends_at: DateTime<Utc>,
+        rest: Duration,
+        slot: Duration,
+    ) -> Self {
+        Self {
+            courts,
+            starts_at,
+            ends_at,
+            rest,
+            slot,
+            bookings: Vec::new(),
+        }
+    }
+
+    pub fn book(&mut self, booking: Booking) {
+        self.bookings.push(booking);
+    }
+
+    /// Places matches stage by stage, each at the earliest start time where a court is free
+    /// and available and none of its participants play within the rest period. A match is
+    /// not placed before every earlier-stage match of its category has ended, and not at all
+    /// if one of them could not be placed.
+    pub fn plan(
+        mut self,
+        mut pending: Vec<PendingMatch>,
+    ) -> (Vec<ScheduledSlot>, Vec<UnplacedMatch>) {
+        pending.sort_by_key(|p| {
+            (
+                p.stage,
+                p.match_number.unwrap_or(i32::MAX),
+                p.category_id,
+                p.match_id,
+            )
+        });
+
+        let mut slots = Vec::new();
+        let mut unplaced = Vec::new();
+        // Lowest stage per category that could not be placed
+        let mut blocked: HashMap<Uuid, (u8, i32)> = HashMap::new();
+        for p in pending {
+            if blocked
+                .get(&p.category_id)
+                .is_some_and(|stage| *stage < p.stage)
+            {
+                unplaced.push(UnplacedMatch {
+                    match_id: p.match_id,
+                    category_id: p.category_id,
+                    reason: "An earlier round of this category could not be placed".to_string(),
+                });
+                continue;
+            }
+            match self.place(&p) {
+                Some(slot) => {
+                    self.book(Booking {
+                        category_id: Some(p.category_id),
+                        stage: p.stage,
+                        court_id: Some(slot.court_id),
+                        participants: p.participants,
+                        starts_at: slot.starts_at,
+                        ends_at: slot.ends_at,
+                    });
+                    slots.push(slot);
+                }
+                None => {
+                    blocked.entry(p.category_id).or_insert(p.stage);
+                    unplaced.push(UnplacedMatch {
+                        match_id: p.match_id,
+                        category_id: p.category_id,
+                        reason: format!(
+                            "No court is free for {} minutes before the end of the scheduling window",
+                            p.duration.num_minutes()
+                        ),
+                    });
+                }
+            }
+        }
+        slots.sort_by_key(|s| (s.starts_at, s.venue_name.clone(), s.court_name.clone()));
+        (slots, unplaced)
+    }
+
+    fn place(&self, p: &PendingMatch) -> Option<ScheduledSlot> {
+        let after_earlier_rounds = self
+            .bookings
+            .iter()
+            .filter(|b| b.category_id == Some(p.category_id) && b.stage < p.stage)
+            .map(|b| b.ends_at + self.rest)
+            .max();
+        let mut t =
+            self.align(after_earlier_rounds.map_or(self.starts_at, |t| t.max(self.starts_at)));
+        while t + p.duration <= self.ends_at {
+            let end = t + p.duration;
+            let busy_until = self
+                .bookings
+                .iter()
+                .filter(|b| {
+                    b.overlaps(t - self.rest, end + self.rest)
+                        && b.participants.iter().any(|id| p.participants.contains(id))
+                })
+                .map(|b| b.ends_at + self.rest)
+                .max();
+            if let Some(busy_until) = busy_until {
+                t = self.align(busy_until);
+                continue;
+            }
+            let court = self.courts.iter().find(|(venue, court)| {
+                court.is_available_between(t, end)
+                    && venue.is_open_between(t, end)
+                    && !self
+                        .bookings
+                        .iter()
+                        .any(|b| b.court_id == Some(court.id) && b.overlaps(t, end))
+            });
+            if let Some((venue, court)) = court {
+                return Some(ScheduledSlot {
+                    match_id: p.match_id,
+                    category_id: p.category_id,
+                    court_id: court.id,
+                    court_name: court.name.clone(),
+                    venue_name: venue.name.clone(),
+                    starts_at: t,
+                    ends_at: end,
+                });
+            }
+            t += self.slot;
+        }
+        None
+    }
+
+    /// The first slot boundary at or after `t`
+    fn align(&self, t: DateTime<Utc>) -> DateTime<Utc> {
+        if t <= self.starts_at {
+            return self.starts_at;
+        }
+        let slot = self.slot.num_seconds();
+        let offset = (t - self.starts_at).num_seconds();
+        self.starts_at + Duration::seconds((offset + slot - 1) / slot * slot)
+    }
+}
@@ -0,0 +1,60 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::ScheduleConflict;
+
+/// Body of POST /tournaments/{id}/schedule; every field is optional
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScheduleRequest {
+    /// Defaults to the tournament's start date
+    pub starts_at: Option<DateTime<Utc>>,
+    /// Defaults to the tournament's end date
+    pub ends_at: Option<DateTime<Utc>>,
+    /// Courts to use; defaults to every active court at the tournament's venues
+    pub court_ids: Option<Vec<Uuid>>,
+    /// Categories to schedule; defaults to all of them
+    pub category_ids: Option<Vec<Uuid>>,
+    /// Minimum time between the end of a participant's match and the start of their next one
+    pub min_rest_minutes: Option<i64>,
+    /// Start times are multiples of this from `starts_at`
+    pub slot_minutes: Option<i64>,
+}
+
+/// A match placed on a court
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledSlot {
+    pub match_id: Uuid,
+    pub category_id: Uuid,
+    pub court_id: Uuid,
+    pub court_name: String,
+    pub venue_name: String,
+    pub starts_at: DateTime<Utc>,
+    /// Estimated from the category's match duration
+    pub ends_at: DateTime<Utc>,
+}
+
+/// A match the scheduler could not place
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnplacedMatch {
+    pub match_id: Uuid,
+    pub category_id: Uuid,
+    pub reason: String,
+}
+
+/// Proposed schedule for a tournament's matches that are not yet on a court
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleReport {
+    pub dry_run: bool,
+    pub committed: bool,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub min_rest_minutes: i64,
+    /// Sorted by start time, then court
+    pub slots: Vec<ScheduledSlot>,
+    pub unplaced: Vec<UnplacedMatch>,
+    /// Clashes of the proposed slots with other matches, such as a team member's match in
+    /// another category; the schedule is not saved while there are any
+    pub conflicts: Vec<ScheduleConflict>,
+}
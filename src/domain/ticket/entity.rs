@@ -0,0 +1,29 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A spectator's ticket to one match; it admits its holder once
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchTicket {
+    pub id: Uuid,
+    pub match_id: Uuid,
+    /// User the ticket was issued to
+    pub holder_id: Uuid,
+    pub issued_at: DateTime<Utc>,
+    pub admitted_at: Option<DateTime<Utc>>,
+    /// Staff member who scanned it at the door
+    pub admitted_by: Option<Uuid>,
+}
+
+impl MatchTicket {
+    pub fn new(match_id: Uuid, holder_id: Uuid, now: DateTime<Utc>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            match_id,
+            holder_id,
+            issued_at: now,
+            admitted_at: None,
+            admitted_by: None,
+        }
+    }
+}
@@ -0,0 +1,12 @@
+// Ticket domain module - spectator tickets for single matches, and the credentials desk
+// staff scan at the door
+
+pub mod entity;
+pub mod repository;
+pub mod value_objects;
+
+pub use entity::MatchTicket;
+pub use repository::MatchTicketRepository;
+pub use value_objects::{
+    spectator_tickets_enabled, ticket_expiry, CredentialVerification, TicketAdmission, TicketPass,
+};
@@ -0,0 +1,22 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::entity::MatchTicket;
+use crate::shared::AppError;
+
+/// Repository trait for spectator tickets
+#[async_trait]
+pub trait MatchTicketRepository: Send + Sync {
+    async fn create(&self, ticket: MatchTicket) -> Result<MatchTicket, AppError>;
+    async fn get_by_id(&self, ticket_id: Uuid) -> Result<Option<MatchTicket>, AppError>;
+    /// The holder's tickets, newest first
+    async fn get_by_holder(&self, holder_id: Uuid) -> Result<Vec<MatchTicket>, AppError>;
+    /// Marks the ticket admitted; `None` when it does not exist or was admitted before
+    async fn admit(
+        &self,
+        ticket_id: Uuid,
+        admitted_by: Uuid,
+        at: DateTime<Utc>,
+    ) -> Result<Option<MatchTicket>, AppError>;
+}
@@ -0,0 +1,49 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::entity::MatchTicket;
+use crate::domain::check_in::RegistrationVerification;
+use crate::domain::match_domain::{Match, MatchWithParticipants};
+use crate::domain::tournament::Tournament;
+
+/// Whether the organizer turned on spectator tickets (`rules.spectator_tickets`)
+pub fn spectator_tickets_enabled(tournament: &Tournament) -> bool {
+    tournament
+        .rules
+        .as_ref()
+        .and_then(|rules| rules.get("spectator_tickets"))
+        .and_then(|enabled| enabled.as_bool())
+        .unwrap_or(false)
+}
+
+/// A ticket stays valid until the day after its match, or after the tournament ends if
+/// that is later
+pub fn ticket_expiry(m: &Match, tournament: &Tournament) -> DateTime<Utc> {
+    m.scheduled_date.max(tournament.end_date) + Duration::days(1)
+}
+
+/// A ticket with the signed token for its QR code
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TicketPass {
+    pub ticket: MatchTicket,
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// What the door sees when a ticket is scanned
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TicketAdmission {
+    pub ticket: MatchTicket,
+    #[serde(rename = "match")]
+    pub match_details: Option<MatchWithParticipants>,
+    /// The ticket had already been used; `ticket` shows when and by whom
+    pub already_admitted: bool,
+}
+
+/// Response of POST /credentials/verify, by the kind of code scanned
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CredentialVerification {
+    Registration(RegistrationVerification),
+    MatchTicket(TicketAdmission),
+}
@@ -0,0 +1,174 @@
+// Bracket progression over the nodes kept in a bracket's `bracket_data`
+
+use serde_json::Value as JsonValue;
+use uuid::Uuid;
+
+use super::value_objects::BracketNode;
+
+/// The nodes in `bracket_data`: a bare list, or one under `nodes` or `matches`
+pub fn bracket_nodes(data: &JsonValue) -> Vec<BracketNode> {
+    let list = match data {
+        JsonValue::Array(_) => Some(data),
+        JsonValue::Object(map) => map.get("nodes").or_else(|| map.get("matches")),
+        _ => None,
+    };
+    list.and_then(|list| serde_json::from_value(list.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// `bracket_data` with its nodes replaced, keeping its shape and other fields
+pub fn with_nodes(data: &JsonValue, nodes: &[BracketNode]) -> JsonValue {
+    let list = serde_json::to_value(nodes).unwrap_or_default();
+    match data {
+        JsonValue::Object(map) => {
+            let mut map = map.clone();
+            let key = if map.contains_key("matches") && !map.contains_key("nodes") {
+                "matches"
+            } else {
+                "nodes"
+            };
+            map.insert(key.to_string(), list);
+            JsonValue::Object(map)
+        }
+        _ => list,
+    }
+}
+
+/// Where a winner moved to in the bracket
+#[derive(Debug, Clone, PartialEq)]
+pub struct Advancement {
+    /// The next node's match, if it has one
+    pub next_match_id: Option<Uuid>,
+    /// 1 or 2 in the next match
+    pub side: i32,
+}
+
+/// Records the winner of a bracket match and moves them into the node their win feeds.
+/// The feeders of a node fill its sides in position order. Returns `None` when the match
+/// is not in the bracket, or it is the last one.
+pub fn advance_winner(
+    nodes: &mut [BracketNode],
+    match_id: Uuid,
+    winner_id: Uuid,
+) -> Option<Advancement> {
+    let index = nodes.iter().position(|n| n.match_id == Some(match_id))?;
+    let node = &mut nodes[index];
+    node.winner_id = Some(winner_id);
+    let name = if node.participant1_id == Some(winner_id) {
+        node.participant1_name.clone()
+    } else if node.participant2_id == Some(winner_id) {
+        node.participant2_name.clone()
+    } else {
+        None
+    };
+    let next_id = node.next_match_id.clone()?;
+    let node_id = node.id.clone();
+
+    let mut feeders: Vec<&BracketNode> = nodes
+        .iter()
+        .filter(|n| n.next_match_id.as_deref() == Some(next_id.as_str()))
+        .collect();
+    feeders.sort_by_key(|n| n.position);
+    let side = match feeders.iter().position(|n| n.id == node_id) {
+        Some(0) => 1,
+        _ => 2,
+    };
+
+    let next = nodes.iter_mut().find(|n| n.id == next_id)?;
+    if side == 1 {
+        next.participant1_id = Some(winner_id);
+        next.participant1_name = name;
+    } else {
+        next.participant2_id = Some(winner_id);
+        next.participant2_name = name;
+    }
+    Some(Advancement {
+        next_match_id: next.match_id,
+        side,
+    })
+}
+
+/// First-round slots of a bracket of `size` (a power of two), as seed numbers from 1.
+/// Neighbouring slots meet first, so seeds 1 and 2 can only meet in the final.
+fn seed_slots(size: usize) -> Vec<usize> {
+    let mut slots = vec![1];
+    while slots.len() < size {
+        let total = slots.len() * 2 + 1;
+        slots = slots
+            .iter()
+            .flat_map(|&seed| [seed, total - seed])
+            .collect();
+    }
+    slots
+}
+
+/// Single-elimination nodes for entrants listed in seed order. When the field is not a
+/// power of two the top seeds get byes: their first-round node has no opponent, is already
+/// won, and puts them straight into the second round.
+pub fn single_elimination(entrants: &[(Uuid, Option<String>)]) -> Vec<BracketNode> {
+    if entrants.len() < 2 {
+        return Vec::new();
+    }
+    let size = entrants.len().next_power_of_two();
+    let rounds = size.trailing_zeros() as i32;
+    let node_id = |round: i32, position: i32| format!("r{}-m{}", round, position);
+
+    let mut nodes = Vec::new();
+    for round in 1..=rounds {
+        let count = (size >> round) as i32;
+        for position in 1..=count {
+            nodes.push(BracketNode {
+                id: node_id(round, position),
+                round,
+                match_id: None,
+                participant1_id: None,
+                participant1_name: None,
+                participant2_id: None,
+                participant2_name: None,
+                winner_id: None,
+                next_match_id: (round < rounds).then(|| node_id(round + 1, (position + 1) / 2)),
+                position,
+            });
+        }
+    }
+
+    let entrant = |seed: usize| entrants.get(seed - 1);
+    let slots = seed_slots(size);
+    let mut byes = Vec::new();
+    for (index, pair) in slots.chunks(2).enumerate() {
+        let node = &mut nodes[index];
+        if let Some((id, name)) = entrant(pair[0]) {
+            node.participant1_id = Some(*id);
+            node.participant1_name = name.clone();
+        }
+        match entrant(pair[1]) {
+            Some((id, name)) => {
+                node.participant2_id = Some(*id);
+                node.participant2_name = name.clone();
+            }
+            None => {
+                node.winner_id = node.participant1_id;
+                byes.push((
+                    node.position,
+                    node.participant1_id,
+                    node.participant1_name.clone(),
+                ));
+            }
+        }
+    }
+    if rounds > 1 {
+        for (position, id, name) in byes {
+            let next_id = node_id(2, (position + 1) / 2);
+            if let Some(next) = nodes.iter_mut().find(|n| n.id == next_id) {
+                if position % 2 == 1 {
+                    next.participant1_id = id;
+                    next.participant1_name = name;
+                } else {
+                    next.participant2_id = id;
+                    next.participant2_name = name;
+                }
+            }
+        }
+    }
+    nodes
+}
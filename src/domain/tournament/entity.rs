@@ -8,6 +8,7 @@ use super::value_objects::{
     BracketStatus, BracketType, PaymentStatus, RegistrationStatus, SportType, TeamComposition,
     TournamentFormat, TournamentStatus, TournamentStats,
 };
+use crate::domain::check_in::CheckInMethod;
 
 /// Core tournament entity
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,6 +29,8 @@ pub struct Tournament {
     pub prize_pool: Option<Decimal>,
     pub rules: Option<JsonValue>,
     pub organizer_id: Uuid,
+    /// IANA timezone the tournament is played in, e.g. `Europe/Madrid`
+    pub timezone: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -67,6 +70,11 @@ pub struct TournamentRegistration {
     pub payment_reference: Option<String>,
     pub notes: Option<String>,
     pub metadata: Option<JsonValue>,
+    /// Set once the participant is confirmed present on tournament day
+    pub checked_in_at: Option<DateTime<Utc>>,
+    pub check_in_method: Option<CheckInMethod>,
+    /// Staff member who checked them in at the desk
+    pub checked_in_by: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -86,6 +94,18 @@ pub struct RegistrationWithDetails {
     pub registration_date: DateTime<Utc>,
 }
 
+impl RegistrationWithDetails {
+    /// The team's name, or the player's with their partner's after a slash
+    pub fn display_name(&self) -> Option<String> {
+        self.team_name
+            .clone()
+            .or_else(|| match (&self.player_name, &self.partner_name) {
+                (Some(player), Some(partner)) => Some(format!("{} / {}", player, partner)),
+                (player, _) => player.clone(),
+            })
+    }
+}
+
 /// Tournament bracket structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TournamentBracket {
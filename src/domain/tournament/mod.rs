@@ -1,9 +1,12 @@
 // Tournament domain module - core business rules for tournament management
 
+pub mod bracket;
 pub mod entity;
 pub mod repository;
+pub mod standings;
 pub mod value_objects;
 
+pub use bracket::{advance_winner, bracket_nodes, single_elimination, with_nodes};
 pub use entity::{
     RegistrationWithDetails, Tournament, TournamentBracket, TournamentCategory,
     TournamentDashboard, TournamentRegistration, TournamentStandings,
@@ -12,11 +15,12 @@ pub use repository::{
     TournamentBracketRepository, TournamentCategoryRepository, TournamentRegistrationRepository,
     TournamentRepository, TournamentStandingsRepository,
 };
+pub use standings::{compute_standings, StandingsMatch, StandingsRules};
 pub use value_objects::{
-    BracketStatus, BracketType, EditableTournament, EditableTournamentBracket,
+    BracketNode, BracketStatus, BracketType, EditableTournament, EditableTournamentBracket,
     EditableTournamentCategory, EditableTournamentRegistration, EditableTournamentStandings,
-    ExportData, NewTournament, NewTournamentBracket, NewTournamentCategory,
-    NewTournamentRegistration, NewTournamentStandings, PaymentStatus,
-    RegistrationStatus, SportType, TeamComposition, TournamentFormat, TournamentSearchQuery,
-    TournamentStatus, TournamentStats, TournamentTemplate,
+    ExportData, ExportEntity, ExportFile, ExportFormat, ExportRequest, NewTournament,
+    NewTournamentBracket, NewTournamentCategory, NewTournamentRegistration, NewTournamentStandings,
+    PaymentStatus, RegistrationStatus, SportType, TeamComposition, TournamentFormat,
+    TournamentSearchQuery, TournamentStats, TournamentStatus, TournamentTemplate,
 };
@@ -11,6 +11,7 @@ use super::value_objects::{
     NewTournamentBracket, NewTournamentCategory, NewTournamentRegistration, NewTournamentStandings,
     TournamentSearchQuery, TournamentStatus, TournamentStats,
 };
+use crate::domain::check_in::CheckIn;
 use crate::shared::AppError;
 
 /// Repository trait for Tournament entity operations
@@ -27,6 +28,10 @@ pub trait TournamentRepository: Send + Sync {
     async fn get_featured(&self, limit: u32) -> Result<Vec<Tournament>, AppError>;
     async fn get_upcoming(&self) -> Result<Vec<Tournament>, AppError>;
     async fn get_tournament_stats(&self, tournament_id: Uuid) -> Result<TournamentStats, AppError>;
+    /// Users the organizer has allowed to score the tournament's matches
+    async fn get_scorekeepers(&self, tournament_id: Uuid) -> Result<Vec<Uuid>, AppError>;
+    async fn add_scorekeeper(&self, tournament_id: Uuid, user_id: Uuid) -> Result<(), AppError>;
+    async fn remove_scorekeeper(&self, tournament_id: Uuid, user_id: Uuid) -> Result<bool, AppError>;
 }
 
 /// Repository trait for TournamentCategory entity operations
@@ -50,6 +55,8 @@ pub trait TournamentRegistrationRepository: Send + Sync {
     async fn get_by_team(&self, team_id: Uuid) -> Result<Vec<RegistrationWithDetails>, AppError>;
     async fn update(&self, registration_id: Uuid, registration_data: EditableTournamentRegistration) -> Result<Option<TournamentRegistration>, AppError>;
     async fn delete(&self, registration_id: Uuid) -> Result<Option<TournamentRegistration>, AppError>;
+    /// Records the registration's check-in, or clears it with `None`
+    async fn record_check_in(&self, registration_id: Uuid, check_in: Option<CheckIn>) -> Result<Option<TournamentRegistration>, AppError>;
 }
 
 /// Repository trait for TournamentBracket entity operations
@@ -0,0 +1,275 @@
+// Standings worked out from a category's matches, with its rules for forfeits
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use super::entity::TournamentCategory;
+use super::value_objects::NewTournamentStandings;
+use crate::domain::match_domain::{ForfeitKind, Match, MatchResult, MatchStatus};
+
+/// How a mid-match retirement counts in the standings
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetirementScoring {
+    /// The sets and games played stand
+    #[default]
+    AsPlayed,
+    /// Scored like a walkover
+    Walkover,
+}
+
+/// What happens to a withdrawn participant's matches
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WithdrawalRule {
+    /// Results so far stand and the remaining matches are walkovers
+    #[default]
+    ForfeitRemaining,
+    /// None of their matches count, played or not
+    VoidAll,
+}
+
+/// The `forfeits` section of a category's constraints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ForfeitRules {
+    /// Sets a walkover is won by, e.g. 2 for a 2-0
+    pub walkover_sets: i32,
+    /// Games or points a walkover is won by
+    pub walkover_games: i32,
+    /// Points for the side that defaulted, in place of `points_loss`
+    pub defaulter_points: i32,
+    pub retirement: RetirementScoring,
+    pub withdrawal: WithdrawalRule,
+}
+
+impl Default for ForfeitRules {
+    fn default() -> Self {
+        Self {
+            walkover_sets: 2,
+            walkover_games: 0,
+            defaulter_points: 0,
+            retirement: RetirementScoring::default(),
+            withdrawal: WithdrawalRule::default(),
+        }
+    }
+}
+
+/// Points per result and forfeit handling, read from a category's constraints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StandingsRules {
+    pub points_win: i32,
+    pub points_draw: i32,
+    pub points_loss: i32,
+    pub forfeits: ForfeitRules,
+}
+
+impl Default for StandingsRules {
+    fn default() -> Self {
+        Self {
+            points_win: 3,
+            points_draw: 1,
+            points_loss: 0,
+            forfeits: ForfeitRules::default(),
+        }
+    }
+}
+
+impl StandingsRules {
+    /// The category's rules; settings that are missing or malformed take the defaults
+    pub fn for_category(category: &TournamentCategory) -> Self {
+        category
+            .constraints
+            .clone()
+            .and_then(|c| serde_json::from_value(c).ok())
+            .unwrap_or_default()
+    }
+}
+
+/// A match of the category with its set results and the names of both sides
+#[derive(Debug, Clone)]
+pub struct StandingsMatch {
+    pub m: Match,
+    pub results: Vec<MatchResult>,
+    pub names: (String, String),
+}
+
+#[derive(Debug, Default)]
+struct Tally {
+    name: String,
+    kind: &'static str,
+    points: i32,
+    played: i32,
+    won: i32,
+    lost: i32,
+    drawn: i32,
+    sets: (i32, i32),
+    games: (i32, i32),
+}
+
+impl Tally {
+    fn record(&mut self, outcome: Outcome, points: i32, sets: (i32, i32), games: (i32, i32)) {
+        self.played += 1;
+        match outcome {
+            Outcome::Won => self.won += 1,
+            Outcome::Lost => self.lost += 1,
+            Outcome::Drawn => self.drawn += 1,
+        }
+        self.points += points;
+        self.sets = (self.sets.0 + sets.0, self.sets.1 + sets.1);
+        self.games = (self.games.0 + games.0, self.games.1 + games.1);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Outcome {
+    Won,
+    Lost,
+    Drawn,
+}
+
+fn participant_kind(m: &Match, side: i32) -> &'static str {
+    let (team, partner) = if side == 1 {
+        (m.participant1_team_id, m.participant1_partner_id)
+    } else {
+        (m.participant2_team_id, m.participant2_partner_id)
+    };
+    match (team, partner) {
+        (Some(_), _) => "team",
+        (None, Some(_)) => "pair",
+        (None, None) => "player",
+    }
+}
+
+/// Sets and games won by side 1 and side 2, one result row per set
+fn played_score(results: &[MatchResult]) -> ((i32, i32), (i32, i32)) {
+    let mut sets = (0, 0);
+    let mut games = (0, 0);
+    for result in results {
+        let (a, b) = (
+            result.participant1_score.unwrap_or(0),
+            result.participant2_score.unwrap_or(0),
+        );
+        games = (games.0 + a, games.1 + b);
+        if a > b {
+            sets.0 += 1;
+        } else if b > a {
+            sets.1 += 1;
+        }
+    }
+    (sets, games)
+}
+
+/// Each participant's record over the category's completed and forfeited matches, ranked
+/// by points, then set and game difference. Withdrawn participants rank last and are
+/// marked eliminated; under `void_all` none of their matches count for anyone.
+pub fn compute_standings(
+    tournament_id: Uuid,
+    category_id: Uuid,
+    rules: &StandingsRules,
+    matches: &[StandingsMatch],
+    withdrawn: &HashSet<Uuid>,
+) -> Vec<NewTournamentStandings> {
+    let forfeits = &rules.forfeits;
+    let mut tallies: HashMap<Uuid, Tally> = HashMap::new();
+    for entry in matches {
+        let m = &entry.m;
+        let (Some(p1), Some(p2)) = (m.participant_id(1), m.participant_id(2)) else {
+            continue;
+        };
+        for (id, side, name) in [(p1, 1, &entry.names.0), (p2, 2, &entry.names.1)] {
+            tallies.entry(id).or_insert_with(|| Tally {
+                name: name.clone(),
+                kind: participant_kind(m, side),
+                ..Tally::default()
+            });
+        }
+        if forfeits.withdrawal == WithdrawalRule::VoidAll
+            && (withdrawn.contains(&p1) || withdrawn.contains(&p2))
+        {
+            continue;
+        }
+
+        let as_walkover = match (m.match_status, m.forfeit_kind) {
+            (MatchStatus::Completed, _) => false,
+            (MatchStatus::Forfeited, Some(ForfeitKind::Retirement)) => {
+                forfeits.retirement == RetirementScoring::Walkover
+            }
+            (MatchStatus::Forfeited, _) => true,
+            _ => continue,
+        };
+        let (sets, games) = if as_walkover {
+            match m.winner_participant {
+                Some(1) => ((forfeits.walkover_sets, 0), (forfeits.walkover_games, 0)),
+                Some(2) => ((0, forfeits.walkover_sets), (0, forfeits.walkover_games)),
+                _ => ((0, 0), (0, 0)),
+            }
+        } else {
+            played_score(&entry.results)
+        };
+
+        let forfeited = m.match_status == MatchStatus::Forfeited;
+        let outcome = |side: i32| match m.winner_participant {
+            _ if m.is_draw => Outcome::Drawn,
+            Some(winner) if winner == side => Outcome::Won,
+            _ => Outcome::Lost,
+        };
+        let points = |outcome: Outcome| match outcome {
+            Outcome::Won => rules.points_win,
+            Outcome::Drawn => rules.points_draw,
+            Outcome::Lost if forfeited => forfeits.defaulter_points,
+            Outcome::Lost => rules.points_loss,
+        };
+        let (o1, o2) = (outcome(1), outcome(2));
+        if let Some(tally) = tallies.get_mut(&p1) {
+            tally.record(o1, points(o1), sets, games);
+        }
+        if let Some(tally) = tallies.get_mut(&p2) {
+            tally.record(o2, points(o2), (sets.1, sets.0), (games.1, games.0));
+        }
+    }
+
+    let mut rows: Vec<(Uuid, Tally)> = tallies.into_iter().collect();
+    rows.sort_by_key(|(id, t)| {
+        (
+            withdrawn.contains(id),
+            Reverse(t.points),
+            Reverse(t.sets.0 - t.sets.1),
+            Reverse(t.games.0 - t.games.1),
+            Reverse(t.sets.0),
+            t.name.clone(),
+        )
+    });
+    rows.into_iter()
+        .enumerate()
+        .map(|(index, (participant_id, t))| {
+            let is_withdrawn = withdrawn.contains(&participant_id);
+            NewTournamentStandings {
+                tournament_id,
+                category_id: Some(category_id),
+                participant_id,
+                participant_name: t.name,
+                participant_type: t.kind.to_string(),
+                points: Some(Decimal::from(t.points)),
+                matches_played: Some(t.played),
+                matches_won: Some(t.won),
+                matches_lost: Some(t.lost),
+                matches_drawn: Some(t.drawn),
+                sets_won: Some(t.sets.0),
+                sets_lost: Some(t.sets.1),
+                games_won: Some(t.games.0),
+                games_lost: Some(t.games.1),
+                goal_difference: None,
+                bonus_points: None,
+                penalty_points: None,
+                position: Some(index as i32 + 1),
+                is_eliminated: Some(is_withdrawn),
+                elimination_round: is_withdrawn.then(|| "withdrawn".to_string()),
+            }
+        })
+        .collect()
+}
@@ -5,6 +5,7 @@ use serde_json::Value as JsonValue;
 use uuid::Uuid;
 
 use super::entity::TournamentBracket;
+use crate::shared::AppError;
 
 // ============ Enums (Value Objects) ============
 
@@ -102,8 +103,11 @@ pub struct TournamentSearchQuery {
     pub status: Option<String>,
     pub format: Option<String>,
     pub location: Option<String>,
+    /// RFC 3339 timestamps, or local dates/date-times read in `tz`
     pub date_from: Option<String>,
     pub date_to: Option<String>,
+    /// IANA timezone for `date_from`/`date_to` without an offset; defaults to UTC
+    pub tz: Option<String>,
     pub limit: Option<i64>,
     pub offset: Option<i64>,
 }
@@ -136,6 +140,82 @@ pub struct ExportData {
     pub content_type: String,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Json,
+    Csv,
+    Zip,
+    Pdf,
+}
+
+/// Data sets that can be exported for a tournament
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportEntity {
+    Registrations,
+    Matches,
+    Standings,
+    Payments,
+}
+
+impl ExportEntity {
+    pub const ALL: [ExportEntity; 4] = [
+        ExportEntity::Registrations,
+        ExportEntity::Matches,
+        ExportEntity::Standings,
+        ExportEntity::Payments,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExportEntity::Registrations => "registrations",
+            ExportEntity::Matches => "matches",
+            ExportEntity::Standings => "standings",
+            ExportEntity::Payments => "payments",
+        }
+    }
+
+    /// Parses a comma separated list such as `registrations,matches`.
+    pub fn parse_list(value: &str) -> Result<Vec<ExportEntity>, AppError> {
+        let mut entities = Vec::new();
+        for name in value.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            let entity = Self::ALL
+                .into_iter()
+                .find(|e| e.as_str() == name)
+                .ok_or_else(|| {
+                    AppError::BadRequest(format!(
+                        "Unknown export entity '{}'. Expected one of: registrations, matches, standings, payments",
+                        name
+                    ))
+                })?;
+            if !entities.contains(&entity) {
+                entities.push(entity);
+            }
+        }
+        Ok(entities)
+    }
+}
+
+/// Options for a file export of tournament data
+#[derive(Debug, Clone)]
+pub struct ExportRequest {
+    pub format: ExportFormat,
+    pub entities: Vec<ExportEntity>,
+    /// Column names, either bare (`player_name`) or entity-qualified (`matches.score`)
+    pub columns: Vec<String>,
+    /// IANA zone dates are written in; the tournament's zone when not given
+    pub timezone: Option<String>,
+}
+
+/// A rendered export ready to be sent as a download
+#[derive(Debug, Clone)]
+pub struct ExportFile {
+    pub filename: String,
+    pub content_type: String,
+    pub bytes: Vec<u8>,
+}
+
 // ============ DTOs ============
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -154,6 +234,9 @@ pub struct NewTournament {
     pub prize_pool: Option<Decimal>,
     pub rules: Option<JsonValue>,
     pub organizer_id: Uuid,
+    /// IANA timezone name; UTC when not given
+    #[serde(default)]
+    pub timezone: Option<String>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -172,6 +255,7 @@ pub struct EditableTournament {
     pub entry_fee: Option<Decimal>,
     pub prize_pool: Option<Decimal>,
     pub rules: Option<JsonValue>,
+    pub timezone: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -239,7 +323,7 @@ pub struct EditableTournamentBracket {
     pub settings: Option<JsonValue>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BracketNode {
     pub id: String,
     pub round: i32,
@@ -301,7 +385,7 @@ pub struct GenerateBracketRequest {
     pub settings: Option<JsonValue>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NewTournamentStandings {
     pub tournament_id: Uuid,
     pub category_id: Option<Uuid>,
@@ -320,6 +404,14 @@ pub struct NewTournamentStandings {
     pub goal_difference: Option<i32>,
     pub bonus_points: Option<Decimal>,
     pub penalty_points: Option<Decimal>,
+    /// Rank within the category; written with the rest of the row so a recalculation
+    /// needs no second pass
+    #[serde(default)]
+    pub position: Option<i32>,
+    #[serde(default)]
+    pub is_eliminated: Option<bool>,
+    #[serde(default)]
+    pub elimination_round: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
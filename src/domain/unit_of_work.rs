@@ -0,0 +1,42 @@
+// Unit of work - groups repository calls into one atomic database transaction
+
+use async_trait::async_trait;
+
+use crate::domain::check_in::CheckInWindowRepository;
+use crate::domain::court_queue::CourtCallRepository;
+use crate::domain::match_domain::{MatchRepository, MatchResultRepository};
+use crate::domain::notification::NotificationRepository;
+use crate::domain::outbox::OutboxRepository;
+use crate::domain::payment::PaymentRepository;
+use crate::domain::tournament::{
+    TournamentBracketRepository, TournamentCategoryRepository, TournamentRegistrationRepository,
+    TournamentRepository, TournamentStandingsRepository,
+};
+use crate::shared::AppError;
+
+/// Repositories bound to a single transaction. Work is only persisted by `commit`;
+/// dropping an uncommitted unit of work rolls it back.
+#[async_trait]
+pub trait UnitOfWork: Send + Sync {
+    fn tournaments(&self) -> &dyn TournamentRepository;
+    fn categories(&self) -> &dyn TournamentCategoryRepository;
+    fn registrations(&self) -> &dyn TournamentRegistrationRepository;
+    fn brackets(&self) -> &dyn TournamentBracketRepository;
+    fn standings(&self) -> &dyn TournamentStandingsRepository;
+    fn matches(&self) -> &dyn MatchRepository;
+    fn match_results(&self) -> &dyn MatchResultRepository;
+    fn notifications(&self) -> &dyn NotificationRepository;
+    fn payments(&self) -> &dyn PaymentRepository;
+    fn outbox(&self) -> &dyn OutboxRepository;
+    fn court_calls(&self) -> &dyn CourtCallRepository;
+    fn check_in_windows(&self) -> &dyn CheckInWindowRepository;
+
+    async fn commit(self: Box<Self>) -> Result<(), AppError>;
+    async fn rollback(self: Box<Self>) -> Result<(), AppError>;
+}
+
+/// Starts units of work
+#[async_trait]
+pub trait UnitOfWorkFactory: Send + Sync {
+    async fn begin(&self) -> Result<Box<dyn UnitOfWork>, AppError>;
+}
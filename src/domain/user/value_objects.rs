@@ -36,7 +36,7 @@ pub struct LoginResponse {
 }
 
 /// Data for creating a new user profile
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct NewUserProfile {
     pub user_id: Uuid,
     pub bio: Option<String>,
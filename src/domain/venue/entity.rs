@@ -0,0 +1,73 @@
+use chrono::{DateTime, Datelike, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::value_objects::{AvailabilityWindow, CourtKind, OpeningHours, SurfaceType};
+
+/// A place with one or more courts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Venue {
+    pub id: Uuid,
+    pub name: String,
+    pub address: Option<String>,
+    pub city: Option<String>,
+    pub country: Option<String>,
+    /// IANA timezone, e.g. `Europe/Madrid`; opening hours are local to it
+    pub timezone: String,
+    /// Weekly opening hours; empty means always open
+    pub opening_hours: Vec<OpeningHours>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Venue {
+    pub fn tz(&self) -> Tz {
+        self.timezone.parse().unwrap_or(Tz::UTC)
+    }
+
+    /// Whether `[start, end)` falls within a single opening period
+    pub fn is_open_between(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> bool {
+        if self.opening_hours.is_empty() {
+            return true;
+        }
+        let tz = self.tz();
+        let (local_start, local_end) = (start.with_timezone(&tz), end.with_timezone(&tz));
+        if local_start.date_naive() != local_end.date_naive() {
+            return false;
+        }
+        self.opening_hours.iter().any(|hours| {
+            hours.weekday == local_start.weekday()
+                && hours.opens <= local_start.time()
+                && local_end.time() <= hours.closes
+        })
+    }
+}
+
+/// A court, table or field at a venue
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Court {
+    pub id: Uuid,
+    pub venue_id: Uuid,
+    pub name: String,
+    pub kind: CourtKind,
+    pub surface: Option<SurfaceType>,
+    /// Inactive courts are kept for history but not scheduled
+    pub is_active: bool,
+    /// When the court may be used; empty means whenever the venue is open
+    pub availability: Vec<AvailabilityWindow>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Court {
+    /// Whether the court can host something from `start` to `end`
+    pub fn is_available_between(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> bool {
+        self.is_active
+            && (self.availability.is_empty()
+                || self
+                    .availability
+                    .iter()
+                    .any(|window| window.starts_at <= start && end <= window.ends_at))
+    }
+}
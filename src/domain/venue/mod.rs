@@ -0,0 +1,12 @@
+// Venue domain module - venues and the courts, tables and fields matches are played on
+
+pub mod entity;
+pub mod repository;
+pub mod value_objects;
+
+pub use entity::{Court, Venue};
+pub use repository::VenueRepository;
+pub use value_objects::{
+    AvailabilityWindow, CourtKind, EditableCourt, EditableVenue, NewCourt, NewVenue,
+    OpeningHours, SurfaceType,
+};
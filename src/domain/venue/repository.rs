@@ -0,0 +1,29 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use super::entity::{Court, Venue};
+use super::value_objects::{EditableCourt, EditableVenue, NewCourt, NewVenue};
+use crate::shared::AppError;
+
+/// Repository trait for venues and their courts
+#[async_trait]
+pub trait VenueRepository: Send + Sync {
+    // Venues
+    async fn create(&self, new_venue: NewVenue) -> Result<Venue, AppError>;
+    async fn find_by_id(&self, venue_id: Uuid) -> Result<Option<Venue>, AppError>;
+    async fn find_all(&self, limit: i64, offset: i64) -> Result<Vec<Venue>, AppError>;
+    async fn update(&self, venue_id: Uuid, venue_data: EditableVenue) -> Result<Option<Venue>, AppError>;
+    async fn delete(&self, venue_id: Uuid) -> Result<Option<Venue>, AppError>;
+
+    // Courts
+    async fn create_court(&self, venue_id: Uuid, new_court: NewCourt) -> Result<Court, AppError>;
+    async fn find_court(&self, court_id: Uuid) -> Result<Option<Court>, AppError>;
+    async fn find_courts(&self, venue_id: Uuid) -> Result<Vec<Court>, AppError>;
+    async fn update_court(&self, court_id: Uuid, court_data: EditableCourt) -> Result<Option<Court>, AppError>;
+    async fn delete_court(&self, court_id: Uuid) -> Result<Option<Court>, AppError>;
+
+    // Tournament venues
+    async fn add_to_tournament(&self, tournament_id: Uuid, venue_id: Uuid) -> Result<(), AppError>;
+    async fn remove_from_tournament(&self, tournament_id: Uuid, venue_id: Uuid) -> Result<bool, AppError>;
+    async fn find_by_tournament(&self, tournament_id: Uuid) -> Result<Vec<Venue>, AppError>;
+}
@@ -0,0 +1,108 @@
+use chrono::{DateTime, NaiveTime, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+
+/// What a court is, so a sport's matches land on the right kind of space
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CourtKind {
+    Court,
+    Table,
+    Field,
+}
+
+impl CourtKind {
+    pub fn key(&self) -> &'static str {
+        match self {
+            CourtKind::Court => "court",
+            CourtKind::Table => "table",
+            CourtKind::Field => "field",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SurfaceType {
+    Hard,
+    Clay,
+    Grass,
+    ArtificialGrass,
+    Carpet,
+    Wood,
+    Sand,
+    Other,
+}
+
+impl SurfaceType {
+    pub fn key(&self) -> &'static str {
+        match self {
+            SurfaceType::Hard => "hard",
+            SurfaceType::Clay => "clay",
+            SurfaceType::Grass => "grass",
+            SurfaceType::ArtificialGrass => "artificial_grass",
+            SurfaceType::Carpet => "carpet",
+            SurfaceType::Wood => "wood",
+            SurfaceType::Sand => "sand",
+            SurfaceType::Other => "other",
+        }
+    }
+}
+
+/// Local opening time on one day of the week, e.g.
+/// `{"weekday": "Sat", "opens": "08:00", "closes": "22:00"}`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OpeningHours {
+    pub weekday: Weekday,
+    pub opens: NaiveTime,
+    pub closes: NaiveTime,
+}
+
+/// A period in which a court can be booked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AvailabilityWindow {
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+}
+
+// ============ DTOs ============
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewVenue {
+    pub name: String,
+    pub address: Option<String>,
+    pub city: Option<String>,
+    pub country: Option<String>,
+    pub timezone: String,
+    #[serde(default)]
+    pub opening_hours: Vec<OpeningHours>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EditableVenue {
+    pub name: Option<String>,
+    pub address: Option<String>,
+    pub city: Option<String>,
+    pub country: Option<String>,
+    pub timezone: Option<String>,
+    pub opening_hours: Option<Vec<OpeningHours>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewCourt {
+    pub name: String,
+    pub kind: CourtKind,
+    pub surface: Option<SurfaceType>,
+    #[serde(default)]
+    pub availability: Vec<AvailabilityWindow>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EditableCourt {
+    pub name: Option<String>,
+    pub kind: Option<CourtKind>,
+    pub surface: Option<SurfaceType>,
+    pub is_active: Option<bool>,
+    pub availability: Option<Vec<AvailabilityWindow>>,
+}
@@ -1,8 +1,7 @@
 use actix_web::{web, HttpResponse};
 use oauth2::{CsrfToken, Scope};
 
-use crate::application::AuthServices;
-use crate::infra::db::{PgTokenRepository, PgUserRepository};
+use crate::infra::api::state::AuthServicesData;
 use crate::shared::google;
 
 /// Auth handler for Google OAuth
@@ -28,7 +27,7 @@ impl AuthHandler {
 
     /// Handle Google OAuth callback — redirects to client with token and user as query params
     pub async fn google_callback(
-        auth_services: web::Data<std::sync::Arc<AuthServices<PgUserRepository, PgTokenRepository>>>,
+        auth_services: web::Data<AuthServicesData>,
         query: web::Query<GoogleCallbackQuery>,
     ) -> HttpResponse {
         let client_redirect_url = std::env::var("CLIENT_REDIRECT_URL")
@@ -0,0 +1,121 @@
+use actix_web::{web, HttpRequest, HttpResponse, ResponseError};
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::domain::calendar::{CalendarFeed, CalendarFeedKind, CalendarLink};
+use crate::infra::api::middleware::auth::get_user_id_from_request;
+use crate::infra::api::state::CalendarServicesData;
+use crate::shared::ical;
+use crate::shared::jwt::{generate_calendar_token, validate_calendar_token};
+use crate::shared::{ApiResponse, EnvConfig};
+
+pub struct CalendarHandler;
+
+impl CalendarHandler {
+    /// Subscription link for the signed-in user's own matches
+    pub async fn my_link(
+        services: web::Data<CalendarServicesData>,
+        req: HttpRequest,
+    ) -> HttpResponse {
+        let user_id = match get_user_id_from_request(&req) {
+            Ok(id) => id,
+            Err(response) => return response,
+        };
+        Self::link(&services, &req, CalendarFeedKind::User, user_id).await
+    }
+
+    pub async fn tournament_link(
+        services: web::Data<CalendarServicesData>,
+        req: HttpRequest,
+        path: web::Path<Uuid>,
+    ) -> HttpResponse {
+        Self::link(
+            &services,
+            &req,
+            CalendarFeedKind::Tournament,
+            path.into_inner(),
+        )
+        .await
+    }
+
+    pub async fn category_link(
+        services: web::Data<CalendarServicesData>,
+        req: HttpRequest,
+        path: web::Path<Uuid>,
+    ) -> HttpResponse {
+        Self::link(
+            &services,
+            &req,
+            CalendarFeedKind::Category,
+            path.into_inner(),
+        )
+        .await
+    }
+
+    pub async fn court_link(
+        services: web::Data<CalendarServicesData>,
+        req: HttpRequest,
+        path: web::Path<Uuid>,
+    ) -> HttpResponse {
+        Self::link(&services, &req, CalendarFeedKind::Court, path.into_inner()).await
+    }
+
+    async fn link(
+        services: &CalendarServicesData,
+        req: &HttpRequest,
+        kind: CalendarFeedKind,
+        id: Uuid,
+    ) -> HttpResponse {
+        let feed = CalendarFeed { kind, id };
+        let name = match services.feed_name(&feed).await {
+            Ok(Some(name)) => name,
+            Ok(None) => return ApiResponse::not_found("Not found"),
+            Err(e) => return e.error_response(),
+        };
+        let secret = EnvConfig::from_env().jwt_secret;
+        let token = match generate_calendar_token(&secret, kind.as_str(), id) {
+            Ok(token) => token,
+            Err(_) => return ApiResponse::error("Could not sign the calendar link"),
+        };
+        let info = req.connection_info();
+        let path = format!("{}/calendar/feeds/{}.ics", info.host(), token);
+        ApiResponse::success(
+            "OK",
+            Some(CalendarLink {
+                feed,
+                name,
+                url: format!("{}://{}", info.scheme(), path),
+                webcal_url: format!("webcal://{}", path),
+            }),
+        )
+    }
+
+    /// The feed itself, for calendar apps; the token in the URL stands in for a login
+    pub async fn feed(
+        services: web::Data<CalendarServicesData>,
+        path: web::Path<String>,
+    ) -> HttpResponse {
+        let token = path.into_inner();
+        let token = token.strip_suffix(".ics").unwrap_or(&token);
+        let secret = EnvConfig::from_env().jwt_secret;
+        let feed = validate_calendar_token(&secret, token)
+            .ok()
+            .and_then(|claims| {
+                Some(CalendarFeed {
+                    kind: CalendarFeedKind::from_key(&claims.feed)?,
+                    id: claims.subject_id()?,
+                })
+            });
+        let Some(feed) = feed else {
+            return ApiResponse::not_found("Calendar not found");
+        };
+        match services.render_feed(&feed, Utc::now()).await {
+            Ok(Some(ics)) => HttpResponse::Ok()
+                .content_type(ical::CONTENT_TYPE)
+                .insert_header(("Cache-Control", "private, max-age=300"))
+                .body(ics),
+            Ok(None) => ApiResponse::not_found("Calendar not found"),
+            Err(e) => e.error_response(),
+        }
+    }
+}
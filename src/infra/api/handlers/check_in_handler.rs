@@ -0,0 +1,155 @@
+use actix_web::{web, HttpRequest, HttpResponse, ResponseError};
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::domain::check_in::{CheckInPass, CheckInWindowRequest, ScanRequest};
+use crate::infra::api::middleware::auth::get_user_id_from_request;
+use crate::infra::api::state::CheckInServicesData;
+use crate::shared::jwt::{generate_check_in_token, validate_check_in_token};
+use crate::shared::{ApiResponse, EnvConfig};
+
+pub struct CheckInHandler;
+
+impl CheckInHandler {
+    pub async fn set_window(
+        services: web::Data<CheckInServicesData>,
+        path: web::Path<Uuid>,
+        body: web::Json<CheckInWindowRequest>,
+    ) -> HttpResponse {
+        match services
+            .set_window(path.into_inner(), body.into_inner())
+            .await
+        {
+            Ok(Some(window)) => ApiResponse::success("Saved", Some(window)),
+            Ok(None) => ApiResponse::not_found("Category not found"),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn get_sheet(
+        services: web::Data<CheckInServicesData>,
+        path: web::Path<Uuid>,
+    ) -> HttpResponse {
+        match services.get_sheet(path.into_inner()).await {
+            Ok(Some(sheet)) => ApiResponse::success("OK", Some(sheet)),
+            Ok(None) => ApiResponse::not_found("Category not found"),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn delete_window(
+        services: web::Data<CheckInServicesData>,
+        path: web::Path<Uuid>,
+    ) -> HttpResponse {
+        match services.delete_window(path.into_inner()).await {
+            Ok(Some(window)) => ApiResponse::success("Deleted", Some(window)),
+            Ok(None) => ApiResponse::not_found("The category has no check-in window"),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    /// Closes the window ahead of time, settling no-shows and waitlist promotions
+    pub async fn close_window(
+        services: web::Data<CheckInServicesData>,
+        path: web::Path<Uuid>,
+    ) -> HttpResponse {
+        match services.close_window(path.into_inner(), Utc::now()).await {
+            Ok(Some(closure)) => ApiResponse::success("Closed", Some(closure)),
+            Ok(None) => ApiResponse::not_found("The category has no check-in window"),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn self_check_in(
+        services: web::Data<CheckInServicesData>,
+        req: HttpRequest,
+        path: web::Path<Uuid>,
+    ) -> HttpResponse {
+        let user_id = match get_user_id_from_request(&req) {
+            Ok(id) => id,
+            Err(response) => return response,
+        };
+        match services.self_check_in(user_id, path.into_inner()).await {
+            Ok(Some(registration)) => ApiResponse::success("Checked in", Some(registration)),
+            Ok(None) => ApiResponse::not_found("Registration not found"),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn desk_check_in(
+        services: web::Data<CheckInServicesData>,
+        req: HttpRequest,
+        path: web::Path<Uuid>,
+    ) -> HttpResponse {
+        let staff_id = match get_user_id_from_request(&req) {
+            Ok(id) => id,
+            Err(response) => return response,
+        };
+        match services.desk_check_in(staff_id, path.into_inner()).await {
+            Ok(Some(registration)) => ApiResponse::success("Checked in", Some(registration)),
+            Ok(None) => ApiResponse::not_found("Registration not found"),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn undo_check_in(
+        services: web::Data<CheckInServicesData>,
+        path: web::Path<Uuid>,
+    ) -> HttpResponse {
+        match services.undo_check_in(path.into_inner()).await {
+            Ok(Some(registration)) => ApiResponse::success("Updated", Some(registration)),
+            Ok(None) => ApiResponse::not_found("Registration not found"),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    /// The signed token for the registration's QR code, valid until check-in closes
+    pub async fn get_pass(
+        services: web::Data<CheckInServicesData>,
+        req: HttpRequest,
+        path: web::Path<Uuid>,
+    ) -> HttpResponse {
+        let user_id = match get_user_id_from_request(&req) {
+            Ok(id) => id,
+            Err(response) => return response,
+        };
+        let (registration, expires_at) =
+            match services.check_in_pass(user_id, path.into_inner()).await {
+                Ok(Some(pass)) => pass,
+                Ok(None) => return ApiResponse::not_found("Registration not found"),
+                Err(e) => return e.error_response(),
+            };
+        let secret = EnvConfig::from_env().jwt_secret;
+        match generate_check_in_token(&secret, registration.id, expires_at) {
+            Ok(token) => ApiResponse::success(
+                "OK",
+                Some(CheckInPass {
+                    registration_id: registration.id,
+                    token,
+                    expires_at,
+                }),
+            ),
+            Err(_) => ApiResponse::error("Could not sign the check-in pass"),
+        }
+    }
+
+    /// Check-in from a scanned QR code; the signed token identifies the registration
+    pub async fn scan(
+        services: web::Data<CheckInServicesData>,
+        body: web::Json<ScanRequest>,
+    ) -> HttpResponse {
+        let secret = EnvConfig::from_env().jwt_secret;
+        let claims = match validate_check_in_token(&secret, &body.token) {
+            Ok(claims) => claims,
+            Err(_) => return ApiResponse::bad_request("Invalid or expired check-in code"),
+        };
+        let Some(registration_id) = claims.registration_id() else {
+            return ApiResponse::bad_request("Invalid or expired check-in code");
+        };
+        match services.scan_check_in(registration_id).await {
+            Ok(Some(registration)) => ApiResponse::success("Checked in", Some(registration)),
+            Ok(None) => ApiResponse::not_found("Registration not found"),
+            Err(e) => e.error_response(),
+        }
+    }
+}
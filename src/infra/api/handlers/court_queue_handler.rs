@@ -0,0 +1,70 @@
+use actix_web::{web, HttpResponse, ResponseError};
+use uuid::Uuid;
+
+use crate::domain::court_queue::{CallRequest, PresenceRequest};
+use crate::infra::api::state::CourtQueueServicesData;
+use crate::shared::ApiResponse;
+
+pub struct CourtQueueHandler;
+
+impl CourtQueueHandler {
+    pub async fn get_court_queue(
+        services: web::Data<CourtQueueServicesData>,
+        path: web::Path<Uuid>,
+    ) -> HttpResponse {
+        match services.get_court_queue(path.into_inner()).await {
+            Ok(Some(queue)) => ApiResponse::success("OK", Some(queue)),
+            Ok(None) => ApiResponse::not_found("Court not found"),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn get_desk(
+        services: web::Data<CourtQueueServicesData>,
+        path: web::Path<Uuid>,
+    ) -> HttpResponse {
+        match services.get_desk(path.into_inner()).await {
+            Ok(courts) => ApiResponse::success("OK", Some(courts)),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn call_to_court(
+        services: web::Data<CourtQueueServicesData>,
+        path: web::Path<Uuid>,
+        body: Option<web::Json<CallRequest>>,
+    ) -> HttpResponse {
+        let request = body.map(|b| b.into_inner()).unwrap_or_default();
+        match services.call_to_court(path.into_inner(), request).await {
+            Ok(Some(call)) => ApiResponse::success("Called", Some(call)),
+            Ok(None) => ApiResponse::not_found("Match not found"),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn mark_present(
+        services: web::Data<CourtQueueServicesData>,
+        path: web::Path<Uuid>,
+        body: web::Json<PresenceRequest>,
+    ) -> HttpResponse {
+        match services
+            .mark_present(path.into_inner(), body.participant)
+            .await
+        {
+            Ok(Some(call)) => ApiResponse::success("Updated", Some(call)),
+            Ok(None) => ApiResponse::not_found("Match not found"),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn withdraw_call(
+        services: web::Data<CourtQueueServicesData>,
+        path: web::Path<Uuid>,
+    ) -> HttpResponse {
+        match services.withdraw_call(path.into_inner()).await {
+            Ok(Some(call)) => ApiResponse::success("Withdrawn", Some(call)),
+            Ok(None) => ApiResponse::not_found("The match has no open call"),
+            Err(e) => e.error_response(),
+        }
+    }
+}
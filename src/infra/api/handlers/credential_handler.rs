@@ -0,0 +1,179 @@
+use actix_web::{web, HttpRequest, HttpResponse, ResponseError};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::domain::check_in::ScanRequest;
+use crate::domain::ticket::{CredentialVerification, TicketPass};
+use crate::infra::api::middleware::auth::get_user_id_from_request;
+use crate::infra::api::state::{CheckInServicesData, TicketServicesData};
+use crate::shared::jwt::{
+    generate_check_in_token, generate_ticket_token, validate_check_in_token, validate_ticket_token,
+};
+use crate::shared::qr::{QrCode, QrFormat};
+use crate::shared::{ApiResponse, EnvConfig};
+
+const DEFAULT_QR_SCALE: usize = 8;
+const MAX_QR_SCALE: usize = 32;
+
+/// Query for rendered QR codes: `?format=png|svg&scale=8` (pixels per module, PNG only)
+#[derive(Debug, Deserialize)]
+pub struct QrQuery {
+    pub format: Option<QrFormat>,
+    pub scale: Option<usize>,
+}
+
+/// The token drawn as a QR code in the requested image format
+fn qr_response(token: &str, query: &QrQuery) -> HttpResponse {
+    let format = query.format.unwrap_or_default();
+    let scale = query
+        .scale
+        .unwrap_or(DEFAULT_QR_SCALE)
+        .clamp(1, MAX_QR_SCALE);
+    match QrCode::encode(token.as_bytes()) {
+        Ok(qr) => HttpResponse::Ok()
+            .content_type(format.content_type())
+            .insert_header(("Cache-Control", "private, no-store"))
+            .body(qr.render(format, scale)),
+        Err(e) => e.error_response(),
+    }
+}
+
+/// QR credentials: registration passes, spectator tickets and verifying either at the door
+pub struct CredentialHandler;
+
+impl CredentialHandler {
+    /// The registration's check-in pass drawn as a QR code
+    pub async fn registration_qr(
+        services: web::Data<CheckInServicesData>,
+        req: HttpRequest,
+        path: web::Path<Uuid>,
+        query: web::Query<QrQuery>,
+    ) -> HttpResponse {
+        let user_id = match get_user_id_from_request(&req) {
+            Ok(id) => id,
+            Err(response) => return response,
+        };
+        let (registration, expires_at) =
+            match services.check_in_pass(user_id, path.into_inner()).await {
+                Ok(Some(pass)) => pass,
+                Ok(None) => return ApiResponse::not_found("Registration not found"),
+                Err(e) => return e.error_response(),
+            };
+        let secret = EnvConfig::from_env().jwt_secret;
+        match generate_check_in_token(&secret, registration.id, expires_at) {
+            Ok(token) => qr_response(&token, &query),
+            Err(_) => ApiResponse::error("Could not sign the check-in pass"),
+        }
+    }
+
+    pub async fn issue_ticket(
+        services: web::Data<TicketServicesData>,
+        req: HttpRequest,
+        path: web::Path<Uuid>,
+    ) -> HttpResponse {
+        let user_id = match get_user_id_from_request(&req) {
+            Ok(id) => id,
+            Err(response) => return response,
+        };
+        let (ticket, expires_at) = match services.issue_ticket(user_id, path.into_inner()).await {
+            Ok(Some(issued)) => issued,
+            Ok(None) => return ApiResponse::not_found("Match not found"),
+            Err(e) => return e.error_response(),
+        };
+        let secret = EnvConfig::from_env().jwt_secret;
+        match generate_ticket_token(&secret, ticket.id, ticket.match_id, expires_at) {
+            Ok(token) => ApiResponse::created(
+                "Ticket issued",
+                TicketPass {
+                    ticket,
+                    token,
+                    expires_at,
+                },
+            ),
+            Err(_) => ApiResponse::error("Could not sign the ticket"),
+        }
+    }
+
+    pub async fn get_my_tickets(
+        services: web::Data<TicketServicesData>,
+        req: HttpRequest,
+    ) -> HttpResponse {
+        let user_id = match get_user_id_from_request(&req) {
+            Ok(id) => id,
+            Err(response) => return response,
+        };
+        match services.get_my_tickets(user_id).await {
+            Ok(tickets) => ApiResponse::success("OK", Some(tickets)),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    /// One of the user's tickets drawn as a QR code
+    pub async fn ticket_qr(
+        services: web::Data<TicketServicesData>,
+        req: HttpRequest,
+        path: web::Path<Uuid>,
+        query: web::Query<QrQuery>,
+    ) -> HttpResponse {
+        let user_id = match get_user_id_from_request(&req) {
+            Ok(id) => id,
+            Err(response) => return response,
+        };
+        let (ticket, expires_at) = match services.get_ticket(user_id, path.into_inner()).await {
+            Ok(Some(found)) => found,
+            Ok(None) => return ApiResponse::not_found("Ticket not found"),
+            Err(e) => return e.error_response(),
+        };
+        let secret = EnvConfig::from_env().jwt_secret;
+        match generate_ticket_token(&secret, ticket.id, ticket.match_id, expires_at) {
+            Ok(token) => qr_response(&token, &query),
+            Err(_) => ApiResponse::error("Could not sign the ticket"),
+        }
+    }
+
+    /// Verifies a scanned code. A registration pass checks the entry in; a spectator
+    /// ticket is admitted. Either way the response says whose credential it is and
+    /// whether it had already been used.
+    pub async fn verify(
+        check_in: web::Data<CheckInServicesData>,
+        tickets: web::Data<TicketServicesData>,
+        req: HttpRequest,
+        body: web::Json<ScanRequest>,
+    ) -> HttpResponse {
+        let staff_id = match get_user_id_from_request(&req) {
+            Ok(id) => id,
+            Err(response) => return response,
+        };
+        let secret = EnvConfig::from_env().jwt_secret;
+        if let Some(registration_id) = validate_check_in_token(&secret, &body.token)
+            .ok()
+            .and_then(|claims| claims.registration_id())
+        {
+            return match check_in
+                .verify_registration(staff_id, registration_id)
+                .await
+            {
+                Ok(Some(verified)) => ApiResponse::success(
+                    "Verified",
+                    Some(CredentialVerification::Registration(verified)),
+                ),
+                Ok(None) => ApiResponse::not_found("Registration not found"),
+                Err(e) => e.error_response(),
+            };
+        }
+        let ids = validate_ticket_token(&secret, &body.token)
+            .ok()
+            .and_then(|claims| Some((claims.ticket_id()?, claims.match_id()?)));
+        let Some((ticket_id, match_id)) = ids else {
+            return ApiResponse::bad_request("Invalid or expired code");
+        };
+        match tickets.admit(staff_id, ticket_id, match_id).await {
+            Ok(Some(admission)) => ApiResponse::success(
+                "Verified",
+                Some(CredentialVerification::MatchTicket(admission)),
+            ),
+            Ok(None) => ApiResponse::not_found("Ticket not found"),
+            Err(e) => e.error_response(),
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use actix_web::http::header::{
+    Charset, ContentDisposition, DispositionParam, DispositionType, ExtendedValue,
+};
+use actix_web::{web, HttpResponse, ResponseError};
+use chrono::NaiveDate;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::domain::tournament::{ExportEntity, ExportFile, ExportFormat, ExportRequest};
+use crate::infra::api::state::ExportServicesData;
+use crate::shared::ApiResponse;
+
+/// Query for GET /tournaments/{id}/export
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    pub format: Option<ExportFormat>,
+    /// Comma separated: registrations, matches, standings, payments
+    pub entities: Option<String>,
+    /// Comma separated column names, optionally qualified as `entity.column`
+    pub columns: Option<String>,
+    /// IANA zone for dates; defaults to the tournament's
+    pub tz: Option<String>,
+}
+
+/// Query for GET /tournaments/{id}/schedule/pdf
+#[derive(Debug, Deserialize)]
+pub struct ScheduleQuery {
+    /// Local day to print, in the zone below
+    pub date: Option<NaiveDate>,
+    /// IANA zone for days and times; defaults to the tournament's
+    pub tz: Option<String>,
+}
+
+/// Query for scoresheet PDFs; `filled=true` prints recorded scores
+#[derive(Debug, Deserialize)]
+pub struct ScoresheetQuery {
+    pub filled: Option<bool>,
+}
+
+pub struct ExportHandler;
+
+impl ExportHandler {
+    pub async fn export_tournament(
+        services: web::Data<ExportServicesData>,
+        path: web::Path<Uuid>,
+        query: web::Query<ExportQuery>,
+    ) -> HttpResponse {
+        let id = path.into_inner();
+        let query = query.into_inner();
+        let format = query.format.unwrap_or(ExportFormat::Json);
+
+        if format == ExportFormat::Json {
+            return match services.export_tournament_json(id).await {
+                Ok(data) => ApiResponse::success("OK", Some(data)),
+                Err(e) => e.error_response(),
+            };
+        }
+
+        let entities = match ExportEntity::parse_list(query.entities.as_deref().unwrap_or("")) {
+            Ok(entities) => entities,
+            Err(e) => return e.error_response(),
+        };
+        let columns = split_list(query.columns.as_deref());
+
+        let request = ExportRequest {
+            format,
+            entities,
+            columns,
+            timezone: query.tz,
+        };
+        match services.export_tournament(id, request).await {
+            Ok(file) => file_response(file),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn bracket_pdf(
+        services: web::Data<ExportServicesData>,
+        path: web::Path<Uuid>,
+    ) -> HttpResponse {
+        match services.bracket_pdf(path.into_inner()).await {
+            Ok(file) => file_response(file),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn schedule_pdf(
+        services: web::Data<ExportServicesData>,
+        path: web::Path<Uuid>,
+        query: web::Query<ScheduleQuery>,
+    ) -> HttpResponse {
+        match services
+            .schedule_pdf(path.into_inner(), query.date, query.tz.as_deref())
+            .await
+        {
+            Ok(file) => file_response(file),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn scoresheet_pdf(
+        services: web::Data<ExportServicesData>,
+        path: web::Path<Uuid>,
+        query: web::Query<ScoresheetQuery>,
+    ) -> HttpResponse {
+        let filled = query.filled.unwrap_or(false);
+        match services.scoresheet_pdf(path.into_inner(), filled).await {
+            Ok(file) => file_response(file),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn category_scoresheets_pdf(
+        services: web::Data<ExportServicesData>,
+        path: web::Path<Uuid>,
+        query: web::Query<ScoresheetQuery>,
+    ) -> HttpResponse {
+        let filled = query.filled.unwrap_or(false);
+        match services
+            .category_scoresheets_pdf(path.into_inner(), filled)
+            .await
+        {
+            Ok(file) => file_response(file),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn standings_pdf(
+        services: web::Data<ExportServicesData>,
+        path: web::Path<Uuid>,
+    ) -> HttpResponse {
+        match services.standings_pdf(path.into_inner()).await {
+            Ok(file) => file_response(file),
+            Err(e) => e.error_response(),
+        }
+    }
+}
+
+fn split_list(value: Option<&str>) -> Vec<String> {
+    value
+        .unwrap_or("")
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Sends an export as an attachment download.
+pub fn file_response(file: ExportFile) -> HttpResponse {
+    let disposition = ContentDisposition {
+        disposition: DispositionType::Attachment,
+        parameters: vec![
+            DispositionParam::Filename(file.filename.clone()),
+            DispositionParam::FilenameExt(ExtendedValue {
+                charset: Charset::Ext("UTF-8".to_string()),
+                language_tag: None,
+                value: file.filename.into_bytes(),
+            }),
+        ],
+    };
+    HttpResponse::Ok()
+        .content_type(file.content_type)
+        .insert_header(disposition)
+        .body(file.bytes)
+}
@@ -0,0 +1,57 @@
+use actix_multipart::Multipart;
+use actix_web::{web, HttpResponse, ResponseError};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::domain::import::ImportOptions;
+use crate::infra::api::multipart_util::extract_file_from_multipart;
+use crate::infra::api::state::ImportServicesData;
+use crate::shared::ApiResponse;
+
+/// Largest accepted import sheet
+const MAX_IMPORT_BYTES: usize = 10 * 1024 * 1024;
+
+/// Query for POST /tournaments/{id}/import
+#[derive(Debug, Deserialize)]
+pub struct ImportQuery {
+    /// Defaults to true: preview without writing anything
+    pub dry_run: Option<bool>,
+    /// Category for rows that do not name one
+    pub category_id: Option<Uuid>,
+}
+
+pub struct ImportHandler;
+
+impl ImportHandler {
+    /// Accepts a multipart `file` field holding a CSV or XLSX sheet of entrants.
+    pub async fn import_entrants(
+        services: web::Data<ImportServicesData>,
+        path: web::Path<Uuid>,
+        query: web::Query<ImportQuery>,
+        mut payload: Multipart,
+    ) -> HttpResponse {
+        let id = path.into_inner();
+        let bytes = match extract_file_from_multipart(&mut payload, MAX_IMPORT_BYTES).await {
+            Ok(b) => b,
+            Err(r) => return r,
+        };
+        let options = ImportOptions {
+            category_id: query.category_id,
+            dry_run: query.dry_run.unwrap_or(true),
+        };
+
+        match services.import_entrants(id, &bytes, options).await {
+            Ok(report) => {
+                let message = if report.committed {
+                    "Imported"
+                } else if report.invalid_rows > 0 {
+                    "Import has invalid rows; nothing was saved"
+                } else {
+                    "Preview"
+                };
+                ApiResponse::success(message, Some(report))
+            }
+            Err(e) => e.error_response(),
+        }
+    }
+}
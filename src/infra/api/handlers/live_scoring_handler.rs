@@ -0,0 +1,197 @@
+//! WebSocket live scoring for court-side scorekeepers.
+//!
+//! `GET /ws/matches/{match_id}/scoring` upgrades to a WebSocket. The server first sends
+//! the current score, then answers every action the scorekeeper sends, e.g.
+//! `{"type":"point_won","participant":1}`, `{"type":"let"}`, `{"type":"timeout","participant":2}`,
+//! `{"type":"side_switch"}`, `card`, `substitution`, `correction` or `{"type":"undo"}`
+//! (see [`ScoringAction`]), with an `ack` carrying the action's
+//! sequence number or an `error`. Optional `expected_sequence` rejects the action if the
+//! score moved on since the scorekeeper last saw it; `client_ref` is echoed back.
+//! Accepted actions are fanned out to SSE viewers as `score_update` events by the service.
+//! Only the tournament's organizer and the scorekeepers they assigned may score, checked
+//! again for every action, and the session is closed when the token it was opened with expires.
+
+use actix_web::{web, HttpRequest, HttpResponse};
+use actix_ws::{CloseCode, CloseReason, Message, Session};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::match_domain::{LiveScore, ScoringAction};
+use crate::infra::api::state::MatchServicesData;
+use crate::shared::{jwt, AppError};
+
+#[derive(Debug, Deserialize)]
+pub struct LiveScoringQuery {
+    /// Bearer token for clients that cannot set headers (e.g. browser `WebSocket`)
+    pub access_token: Option<String>,
+}
+
+/// A scorekeeper message
+#[derive(Debug, Deserialize)]
+struct ScoringMessage {
+    #[serde(flatten)]
+    action: ScoringAction,
+    expected_sequence: Option<i64>,
+    client_ref: Option<String>,
+}
+
+/// A server message
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ScoringReply {
+    State {
+        score: LiveScore,
+    },
+    Ack {
+        sequence: i64,
+        score: LiveScore,
+        client_ref: Option<String>,
+    },
+    Error {
+        message: String,
+        client_ref: Option<String>,
+    },
+}
+
+pub struct LiveScoringHandler;
+
+impl LiveScoringHandler {
+    /// `/ws` bypasses the auth middleware, so the token is checked here
+    pub async fn connect(
+        req: HttpRequest,
+        body: web::Payload,
+        path: web::Path<Uuid>,
+        query: web::Query<LiveScoringQuery>,
+        services: web::Data<MatchServicesData>,
+    ) -> Result<HttpResponse, AppError> {
+        let token = req
+            .headers()
+            .get("Authorization")
+            .and_then(|h| h.to_str().ok())
+            .and_then(|s| s.strip_prefix("Bearer "))
+            .map(str::to_string)
+            .or(query.into_inner().access_token)
+            .ok_or_else(|| AppError::Unauthorized("Missing token".into()))?;
+        let claims = jwt::validate_jwt(&token)
+            .map_err(|_| AppError::Unauthorized("Invalid token".into()))?;
+        let user_id = claims
+            .user_id()
+            .ok_or_else(|| AppError::Unauthorized("Invalid user ID in token".into()))?;
+        let expires_at = DateTime::from_timestamp(claims.exp as i64, 0)
+            .ok_or_else(|| AppError::Unauthorized("Invalid token".into()))?;
+
+        let match_id = path.into_inner();
+        services.ensure_can_score(user_id, match_id).await?;
+        let score = services.get_live_score(match_id).await?;
+        let (response, session, stream) = actix_ws::handle(&req, body)
+            .map_err(|e| AppError::BadRequest(format!("WebSocket handshake failed: {}", e)))?;
+
+        actix_web::rt::spawn(run_session(
+            match_id,
+            user_id,
+            expires_at,
+            score,
+            session,
+            stream,
+            services.get_ref().clone(),
+        ));
+        Ok(response)
+    }
+}
+
+async fn run_session(
+    match_id: Uuid,
+    user_id: Uuid,
+    expires_at: DateTime<Utc>,
+    score: LiveScore,
+    mut session: Session,
+    mut stream: actix_ws::MessageStream,
+    services: MatchServicesData,
+) {
+    if send(&mut session, &ScoringReply::State { score })
+        .await
+        .is_err()
+    {
+        return;
+    }
+    let expiry = tokio::time::sleep((expires_at - Utc::now()).to_std().unwrap_or_default());
+    tokio::pin!(expiry);
+    loop {
+        let message = tokio::select! {
+            message = stream.recv() => message,
+            _ = &mut expiry => {
+                let reason = CloseReason {
+                    code: CloseCode::Policy,
+                    description: Some("Token expired".to_string()),
+                };
+                let _ = session.close(Some(reason)).await;
+                return;
+            }
+        };
+        let Some(Ok(message)) = message else {
+            break;
+        };
+        let reply = match message {
+            Message::Text(text) => record(match_id, user_id, &text, &services).await,
+            Message::Ping(bytes) => {
+                if session.pong(&bytes).await.is_err() {
+                    return;
+                }
+                continue;
+            }
+            Message::Close(reason) => {
+                let _ = session.close(reason).await;
+                return;
+            }
+            _ => continue,
+        };
+        if send(&mut session, &reply).await.is_err() {
+            return;
+        }
+    }
+    let _ = session.close(None).await;
+}
+
+async fn record(
+    match_id: Uuid,
+    user_id: Uuid,
+    text: &str,
+    services: &MatchServicesData,
+) -> ScoringReply {
+    let message: ScoringMessage = match serde_json::from_str(text) {
+        Ok(message) => message,
+        Err(e) => {
+            return ScoringReply::Error {
+                message: format!("Invalid scoring message: {}", e),
+                client_ref: None,
+            }
+        }
+    };
+    // The organizer or its scorekeepers may have changed since the session opened
+    if let Err(e) = services.ensure_can_score(user_id, match_id).await {
+        return ScoringReply::Error {
+            message: e.to_string(),
+            client_ref: message.client_ref,
+        };
+    }
+    match services
+        .record_scoring_action(match_id, message.action, message.expected_sequence)
+        .await
+    {
+        Ok(update) => ScoringReply::Ack {
+            sequence: update.entry.sequence,
+            score: update.score,
+            client_ref: message.client_ref,
+        },
+        Err(e) => ScoringReply::Error {
+            message: e.to_string(),
+            client_ref: message.client_ref,
+        },
+    }
+}
+
+async fn send(session: &mut Session, reply: &ScoringReply) -> Result<(), actix_ws::Closed> {
+    let text = serde_json::to_string(reply).unwrap_or_else(|_| "{}".to_string());
+    session.text(text).await
+}
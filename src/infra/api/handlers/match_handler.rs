@@ -1,23 +1,23 @@
 use actix_multipart::Multipart;
 use actix_web::{web, HttpRequest, HttpResponse, ResponseError};
+use chrono::NaiveDate;
+use chrono_tz::Tz;
 use serde::Deserialize;
 use uuid::Uuid;
 
-use crate::application::MatchServices;
 use crate::domain::match_domain::{
     AddMatchCommentRequest, BulkCancelMatchesRequest, CancelMatchRequest, CompleteMatchRequest,
     EditableMatch, EditableMatchResult, LiveMatchUpdate, NewMatch, NewMatchResult,
-    RescheduleMatchRequest, UpdateMatchStatusRequest,
+    RecordScoringRequest, RescheduleMatchRequest, RetirementRequest, SubscribeToMatchRequest,
+    UpdateMatchStatusRequest, WalkoverRequest, WithdrawalRequest,
 };
 use crate::infra::api::middleware::auth::get_user_id_from_request;
 use crate::infra::api::multipart_util::extract_file_from_multipart;
-use crate::infra::api::sse::{Broadcaster, RealtimeEvent};
+use crate::infra::api::state::{MatchServicesData, UserServicesData};
+use crate::infra::api::timezone_util::{requested_timezone, TimezoneQuery};
 use crate::infra::cloudinary::CloudinaryClient;
-use crate::infra::db::{PgMatchRepository, PgMatchResultRepository};
 use crate::shared::ApiResponse;
 
-type MatchServicesData = std::sync::Arc<MatchServices<PgMatchRepository, PgMatchResultRepository>>;
-
 #[derive(Debug, Deserialize)]
 pub struct TournamentIdPath {
     pub tournament_id: Uuid,
@@ -46,14 +46,35 @@ pub struct BulkMatchUpdateBody {
     pub updates: EditableMatch,
 }
 
+/// Query for creating, rescheduling and bulk-updating matches
+#[derive(Debug, Deserialize)]
+pub struct ConflictQuery {
+    /// Save even when the match clashes with others
+    pub allow_conflicts: Option<bool>,
+}
+
+/// Query for GET /matches/schedule
+#[derive(Debug, Deserialize)]
+pub struct MatchScheduleQuery {
+    /// Only matches starting on this calendar day in the requested zone
+    pub date: Option<NaiveDate>,
+    /// IANA zone to show times in
+    pub tz: Option<String>,
+}
+
 pub struct MatchHandler;
 
 impl MatchHandler {
     pub async fn post(
         services: web::Data<MatchServicesData>,
+        query: web::Query<ConflictQuery>,
         body: web::Json<NewMatch>,
     ) -> HttpResponse {
-        match services.create_match(body.into_inner()).await {
+        let allow_conflicts = query.allow_conflicts.unwrap_or(false);
+        match services
+            .create_match(body.into_inner(), allow_conflicts)
+            .await
+        {
             Ok(m) => ApiResponse::created("Created", m),
             Err(e) => e.error_response(),
         }
@@ -99,16 +120,32 @@ impl MatchHandler {
     pub async fn reschedule(
         services: web::Data<MatchServicesData>,
         path: web::Path<Uuid>,
+        query: web::Query<ConflictQuery>,
         body: web::Json<RescheduleMatchRequest>,
     ) -> HttpResponse {
         let id = path.into_inner();
-        match services.reschedule_match(id, body.into_inner()).await {
+        let allow_conflicts = query.allow_conflicts.unwrap_or(false);
+        match services
+            .reschedule_match(id, body.into_inner(), allow_conflicts)
+            .await
+        {
             Ok(Some(m)) => ApiResponse::success("Match rescheduled", Some(m)),
             Ok(None) => ApiResponse::not_found("Match not found"),
             Err(e) => e.error_response(),
         }
     }
 
+    pub async fn get_conflicts(
+        services: web::Data<MatchServicesData>,
+        path: web::Path<Uuid>,
+    ) -> HttpResponse {
+        match services.get_match_conflicts(path.into_inner()).await {
+            Ok(Some(conflicts)) => ApiResponse::success("OK", Some(conflicts)),
+            Ok(None) => ApiResponse::not_found("Match not found"),
+            Err(e) => e.error_response(),
+        }
+    }
+
     pub async fn validate_result_scores(
         services: web::Data<MatchServicesData>,
         path: web::Path<Uuid>,
@@ -154,24 +191,12 @@ impl MatchHandler {
 
     pub async fn update_status(
         services: web::Data<MatchServicesData>,
-        broadcaster: web::Data<std::sync::Arc<Broadcaster>>,
         path: web::Path<Uuid>,
         body: web::Json<UpdateMatchStatusRequest>,
     ) -> HttpResponse {
         let id = path.into_inner();
         match services.update_match_status(id, body.status).await {
-            Ok(Some(m)) => {
-                let status = format!("{:?}", m.match_status);
-                broadcaster
-                    .broadcast_event(&RealtimeEvent::MatchUpdate {
-                        match_id: m.id,
-                        tournament_id: None,
-                        category_id: Some(m.tournament_category_id),
-                        status: Some(status),
-                    })
-                    .await;
-                ApiResponse::success("Updated", Some(m))
-            }
+            Ok(Some(m)) => ApiResponse::success("Updated", Some(m)),
             Ok(None) => ApiResponse::not_found("Match not found"),
             Err(e) => e.error_response(),
         }
@@ -179,22 +204,11 @@ impl MatchHandler {
 
     pub async fn start(
         services: web::Data<MatchServicesData>,
-        broadcaster: web::Data<std::sync::Arc<Broadcaster>>,
         path: web::Path<Uuid>,
     ) -> HttpResponse {
         let id = path.into_inner();
         match services.start_match(id).await {
-            Ok(Some(m)) => {
-                broadcaster
-                    .broadcast_event(&RealtimeEvent::MatchUpdate {
-                        match_id: m.id,
-                        tournament_id: None,
-                        category_id: Some(m.tournament_category_id),
-                        status: Some("InProgress".to_string()),
-                    })
-                    .await;
-                ApiResponse::success("Started", Some(m))
-            }
+            Ok(Some(m)) => ApiResponse::success("Started", Some(m)),
             Ok(None) => ApiResponse::not_found("Match not found"),
             Err(e) => e.error_response(),
         }
@@ -202,7 +216,6 @@ impl MatchHandler {
 
     pub async fn complete(
         services: web::Data<MatchServicesData>,
-        broadcaster: web::Data<std::sync::Arc<Broadcaster>>,
         path: web::Path<Uuid>,
         body: web::Json<CompleteMatchRequest>,
     ) -> HttpResponse {
@@ -211,17 +224,7 @@ impl MatchHandler {
             .complete_match(id, body.winner_participant, body.is_draw)
             .await
         {
-            Ok(Some(m)) => {
-                broadcaster
-                    .broadcast_event(&RealtimeEvent::MatchUpdate {
-                        match_id: m.id,
-                        tournament_id: None,
-                        category_id: Some(m.tournament_category_id),
-                        status: Some("Completed".to_string()),
-                    })
-                    .await;
-                ApiResponse::success("Completed", Some(m))
-            }
+            Ok(Some(m)) => ApiResponse::success("Completed", Some(m)),
             Ok(None) => ApiResponse::not_found("Match not found"),
             Err(e) => e.error_response(),
         }
@@ -241,6 +244,48 @@ impl MatchHandler {
         }
     }
 
+    pub async fn walkover(
+        services: web::Data<MatchServicesData>,
+        path: web::Path<Uuid>,
+        body: web::Json<WalkoverRequest>,
+    ) -> HttpResponse {
+        let id = path.into_inner();
+        match services.walkover(id, body.into_inner()).await {
+            Ok(Some(m)) => ApiResponse::success("Walkover recorded", Some(m)),
+            Ok(None) => ApiResponse::not_found("Match not found"),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn retire(
+        services: web::Data<MatchServicesData>,
+        path: web::Path<Uuid>,
+        body: web::Json<RetirementRequest>,
+    ) -> HttpResponse {
+        let id = path.into_inner();
+        match services.retire(id, body.into_inner()).await {
+            Ok(Some(m)) => ApiResponse::success("Retirement recorded", Some(m)),
+            Ok(None) => ApiResponse::not_found("Match not found"),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    /// Withdraws a participant from a category, turning their remaining matches into walkovers
+    pub async fn withdraw_participant(
+        services: web::Data<MatchServicesData>,
+        path: web::Path<Uuid>,
+        body: web::Json<WithdrawalRequest>,
+    ) -> HttpResponse {
+        let category_id = path.into_inner();
+        match services
+            .withdraw_participant(category_id, body.into_inner())
+            .await
+        {
+            Ok(matches) => ApiResponse::success("Withdrawn", Some(matches)),
+            Err(e) => e.error_response(),
+        }
+    }
+
     pub async fn postpone(
         services: web::Data<MatchServicesData>,
         path: web::Path<Uuid>,
@@ -253,22 +298,52 @@ impl MatchHandler {
         }
     }
 
-    pub async fn get_schedule(services: web::Data<MatchServicesData>) -> HttpResponse {
-        match services.get_match_schedule().await {
-            Ok(items) => ApiResponse::success("OK", Some(items)),
+    pub async fn get_schedule(
+        services: web::Data<MatchServicesData>,
+        users: web::Data<UserServicesData>,
+        req: HttpRequest,
+        query: web::Query<MatchScheduleQuery>,
+    ) -> HttpResponse {
+        let tz = match requested_timezone(&req, query.tz.as_deref(), &users).await {
+            Ok(tz) => tz,
+            Err(response) => return response,
+        };
+        let items = match query.date {
+            Some(date) => {
+                services
+                    .get_match_schedule_on(date, tz.unwrap_or(Tz::UTC))
+                    .await
+            }
+            None => services.get_match_schedule().await,
+        };
+        match items {
+            Ok(items) => match services.localize_schedule(items, tz).await {
+                Ok(items) => ApiResponse::success("OK", Some(items)),
+                Err(e) => e.error_response(),
+            },
             Err(e) => e.error_response(),
         }
     }
 
     pub async fn my_upcoming(
         services: web::Data<MatchServicesData>,
+        users: web::Data<UserServicesData>,
         req: HttpRequest,
+        query: web::Query<TimezoneQuery>,
     ) -> HttpResponse {
         let user_id = match get_user_id_from_request(&req) {
             Ok(id) => id,
             Err(response) => return response,
         };
-        match services.get_user_upcoming_matches(user_id).await {
+        let tz = match requested_timezone(&req, query.tz.as_deref(), &users).await {
+            Ok(tz) => tz,
+            Err(response) => return response,
+        };
+        let items = match services.get_user_upcoming_matches(user_id).await {
+            Ok(items) => items,
+            Err(e) => return e.error_response(),
+        };
+        match services.localize_schedule(items, tz).await {
             Ok(items) => ApiResponse::success("OK", Some(items)),
             Err(e) => e.error_response(),
         }
@@ -276,13 +351,23 @@ impl MatchHandler {
 
     pub async fn my_history(
         services: web::Data<MatchServicesData>,
+        users: web::Data<UserServicesData>,
         req: HttpRequest,
+        query: web::Query<TimezoneQuery>,
     ) -> HttpResponse {
         let user_id = match get_user_id_from_request(&req) {
             Ok(id) => id,
             Err(response) => return response,
         };
-        match services.get_user_match_history(user_id).await {
+        let tz = match requested_timezone(&req, query.tz.as_deref(), &users).await {
+            Ok(tz) => tz,
+            Err(response) => return response,
+        };
+        let items = match services.get_user_match_history(user_id).await {
+            Ok(items) => items,
+            Err(e) => return e.error_response(),
+        };
+        match services.localize_schedule(items, tz).await {
             Ok(items) => ApiResponse::success("OK", Some(items)),
             Err(e) => e.error_response(),
         }
@@ -297,28 +382,52 @@ impl MatchHandler {
 
     pub async fn update_live(
         services: web::Data<MatchServicesData>,
-        broadcaster: web::Data<std::sync::Arc<Broadcaster>>,
         path: web::Path<Uuid>,
         body: web::Json<LiveMatchUpdate>,
     ) -> HttpResponse {
         let id = path.into_inner();
         match services.update_live_match(id, body.into_inner()).await {
-            Ok(Some(m)) => {
-                broadcaster
-                    .broadcast_event(&RealtimeEvent::MatchUpdate {
-                        match_id: m.id,
-                        tournament_id: None,
-                        category_id: Some(m.tournament_category_id),
-                        status: Some("live_update".to_string()),
-                    })
-                    .await;
-                ApiResponse::success("Updated", Some(m))
-            }
+            Ok(Some(m)) => ApiResponse::success("Updated", Some(m)),
             Ok(None) => ApiResponse::not_found("Match not found"),
             Err(e) => e.error_response(),
         }
     }
 
+    pub async fn get_scoring(
+        services: web::Data<MatchServicesData>,
+        path: web::Path<Uuid>,
+    ) -> HttpResponse {
+        match services.get_scoring_log(path.into_inner()).await {
+            Ok(log) => ApiResponse::success("OK", Some(log)),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    /// Records one scoring action; the HTTP counterpart of the live scoring WebSocket
+    pub async fn record_scoring(
+        services: web::Data<MatchServicesData>,
+        req: HttpRequest,
+        path: web::Path<Uuid>,
+        body: web::Json<RecordScoringRequest>,
+    ) -> HttpResponse {
+        let user_id = match get_user_id_from_request(&req) {
+            Ok(id) => id,
+            Err(response) => return response,
+        };
+        let match_id = path.into_inner();
+        if let Err(e) = services.ensure_can_score(user_id, match_id).await {
+            return e.error_response();
+        }
+        let body = body.into_inner();
+        match services
+            .record_scoring_action(match_id, body.action, body.expected_sequence)
+            .await
+        {
+            Ok(update) => ApiResponse::created("Recorded", update),
+            Err(e) => e.error_response(),
+        }
+    }
+
     pub async fn get_analytics(
         services: web::Data<MatchServicesData>,
         path: web::Path<Uuid>,
@@ -446,13 +555,15 @@ impl MatchHandler {
         services: web::Data<MatchServicesData>,
         req: HttpRequest,
         path: web::Path<Uuid>,
+        body: Option<web::Json<SubscribeToMatchRequest>>,
     ) -> HttpResponse {
         let user_id = match get_user_id_from_request(&req) {
             Ok(id) => id,
             Err(response) => return response,
         };
         let id = path.into_inner();
-        match services.subscribe_to_match(id, user_id).await {
+        let preferences = body.and_then(|body| body.into_inner().notification_preferences);
+        match services.subscribe_to_match(id, user_id, preferences).await {
             Ok(sub) => ApiResponse::success("Subscribed", Some(sub)),
             Err(e) => e.error_response(),
         }
@@ -476,10 +587,16 @@ impl MatchHandler {
 
     pub async fn bulk_update(
         services: web::Data<MatchServicesData>,
+        query: web::Query<ConflictQuery>,
         body: web::Json<BulkMatchUpdateBody>,
     ) -> HttpResponse {
+        let allow_conflicts = query.allow_conflicts.unwrap_or(false);
         match services
-            .bulk_update_matches(body.match_ids.clone(), body.updates.clone())
+            .bulk_update_matches(
+                body.match_ids.clone(),
+                body.updates.clone(),
+                allow_conflicts,
+            )
             .await
         {
             Ok(matches) => ApiResponse::success("Updated", Some(matches)),
@@ -1,13 +1,22 @@
 // API handlers - HTTP request/response handling
 
 pub mod auth_handler;
+pub mod calendar_handler;
+pub mod check_in_handler;
+pub mod court_queue_handler;
+pub mod credential_handler;
+pub mod export_handler;
 pub mod health_handler;
+pub mod import_handler;
+pub mod live_scoring_handler;
 pub mod match_handler;
 pub mod notification_handler;
 pub mod participant_handler;
 pub mod payment_handler;
+pub mod scheduling_handler;
 pub mod statistics_handler;
 pub mod tournament_handler;
 pub mod user_handler;
+pub mod venue_handler;
 
 // Re-exports for convenience
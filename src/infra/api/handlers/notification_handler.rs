@@ -1,13 +1,16 @@
 use actix_web::{web, HttpRequest, HttpResponse, ResponseError};
 use uuid::Uuid;
 
-use crate::application::NotificationServices;
-use crate::domain::notification::NewNotification;
+use crate::domain::notification::{
+    NewNotification, NewPushSubscription, Notification, NotificationPreferences, NotificationType,
+    PushSubscriptionRequest, UnsubscribePushRequest,
+};
 use crate::infra::api::middleware::auth::get_user_id_from_request;
-use crate::infra::db::PgNotificationRepository;
-use crate::shared::ApiResponse;
-
-type NotificationServicesData = std::sync::Arc<NotificationServices<PgNotificationRepository>>;
+use crate::infra::api::state::NotificationServicesData;
+use crate::infra::push::VapidKeys;
+use crate::shared::config::VapidConfig;
+use crate::shared::jwt::validate_unsubscribe_token;
+use crate::shared::{ApiResponse, EnvConfig};
 
 pub struct NotificationHandler;
 
@@ -84,7 +87,71 @@ impl NotificationHandler {
         body: web::Json<NewNotification>,
     ) -> HttpResponse {
         match services.send_notification(body.into_inner()).await {
-            Ok(notification) => ApiResponse::created("Sent", notification),
+            Ok(Some(notification)) => ApiResponse::created("Sent", notification),
+            Ok(None) => ApiResponse::success(
+                "Not sent: the user has turned this notification off",
+                None::<Notification>,
+            ),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn get_preferences(
+        services: web::Data<NotificationServicesData>,
+        req: HttpRequest,
+    ) -> HttpResponse {
+        let user_id = match get_user_id_from_request(&req) {
+            Ok(id) => id,
+            Err(response) => return response,
+        };
+        match services.get_preferences(user_id).await {
+            Ok(preferences) => ApiResponse::success("OK", Some(preferences)),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn update_preferences(
+        services: web::Data<NotificationServicesData>,
+        req: HttpRequest,
+        body: web::Json<serde_json::Value>,
+    ) -> HttpResponse {
+        let user_id = match get_user_id_from_request(&req) {
+            Ok(id) => id,
+            Err(response) => return response,
+        };
+        let preferences = match NotificationPreferences::parse(&body) {
+            Ok(preferences) => preferences,
+            Err(e) => return e.error_response(),
+        };
+        match services.update_preferences(user_id, preferences).await {
+            Ok(preferences) => ApiResponse::success("Updated", Some(preferences)),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    /// One-click unsubscribe from an email link (RFC 8058); the signed token stands in
+    /// for authentication
+    pub async fn unsubscribe_email(
+        services: web::Data<NotificationServicesData>,
+        query: web::Query<UnsubscribeQuery>,
+    ) -> HttpResponse {
+        let secret = EnvConfig::from_env().jwt_secret;
+        let claims = match validate_unsubscribe_token(&secret, &query.token) {
+            Ok(claims) => claims,
+            Err(_) => return ApiResponse::bad_request("Invalid or expired unsubscribe link"),
+        };
+        let Some(user_id) = claims.user_id() else {
+            return ApiResponse::bad_request("Invalid or expired unsubscribe link");
+        };
+        let notification_type = match claims.notification_type.as_deref() {
+            Some(key) => match serde_json::from_value::<NotificationType>(key.into()) {
+                Ok(notification_type) => Some(notification_type),
+                Err(_) => return ApiResponse::bad_request("Invalid or expired unsubscribe link"),
+            },
+            None => None,
+        };
+        match services.unsubscribe_email(user_id, notification_type).await {
+            Ok(preferences) => ApiResponse::success("Unsubscribed", Some(preferences)),
             Err(e) => e.error_response(),
         }
     }
@@ -101,6 +168,79 @@ impl NotificationHandler {
         }
     }
 
+    pub async fn deliveries(
+        services: web::Data<NotificationServicesData>,
+        req: HttpRequest,
+        path: web::Path<Uuid>,
+    ) -> HttpResponse {
+        let user_id = match get_user_id_from_request(&req) {
+            Ok(id) => id,
+            Err(response) => return response,
+        };
+        match services.get_deliveries(user_id, path.into_inner()).await {
+            Ok(Some(deliveries)) => ApiResponse::success("OK", Some(deliveries)),
+            Ok(None) => ApiResponse::not_found("Notification not found"),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    /// The key browsers pass to `PushManager.subscribe()` as `applicationServerKey`
+    pub async fn vapid_public_key() -> HttpResponse {
+        let Some(config) = VapidConfig::from_env() else {
+            return ApiResponse::not_found("Web push is not configured");
+        };
+        match VapidKeys::from_config(&config) {
+            Ok(keys) => ApiResponse::success(
+                "OK",
+                Some(serde_json::json!({ "public_key": keys.public_key() })),
+            ),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn subscribe_push(
+        services: web::Data<NotificationServicesData>,
+        req: HttpRequest,
+        body: web::Json<PushSubscriptionRequest>,
+    ) -> HttpResponse {
+        let user_id = match get_user_id_from_request(&req) {
+            Ok(id) => id,
+            Err(response) => return response,
+        };
+        let body = body.into_inner();
+        let subscription = NewPushSubscription {
+            user_id,
+            endpoint: body.endpoint,
+            p256dh: body.keys.p256dh,
+            auth: body.keys.auth,
+            user_agent: req
+                .headers()
+                .get("User-Agent")
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string),
+        };
+        match services.subscribe_push(subscription).await {
+            Ok(subscription) => ApiResponse::created("Subscribed", subscription),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn unsubscribe_push(
+        services: web::Data<NotificationServicesData>,
+        req: HttpRequest,
+        body: web::Json<UnsubscribePushRequest>,
+    ) -> HttpResponse {
+        let user_id = match get_user_id_from_request(&req) {
+            Ok(id) => id,
+            Err(response) => return response,
+        };
+        match services.unsubscribe_push(user_id, &body.endpoint).await {
+            Ok(true) => ApiResponse::success("Unsubscribed", Some(serde_json::json!({}))),
+            Ok(false) => ApiResponse::not_found("Push subscription not found"),
+            Err(e) => e.error_response(),
+        }
+    }
+
     pub async fn delete(
         services: web::Data<NotificationServicesData>,
         path: web::Path<Uuid>,
@@ -119,3 +259,8 @@ pub struct NotificationQuery {
     pub limit: Option<i64>,
     pub offset: Option<i64>,
 }
+
+#[derive(serde::Deserialize)]
+pub struct UnsubscribeQuery {
+    pub token: String,
+}
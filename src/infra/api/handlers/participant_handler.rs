@@ -2,17 +2,12 @@ use actix_web::{web, HttpResponse, ResponseError};
 use serde::Deserialize;
 use uuid::Uuid;
 
-use crate::application::ParticipantServices;
 use crate::domain::participant::{
     CreatePlayer, EditablePlayer, EditableTeam, EditableTeamMember, NewTeam, NewTeamMember,
 };
-use crate::infra::db::{PgPlayerRepository, PgTeamMemberRepository, PgTeamRepository};
+use crate::infra::api::state::ParticipantServicesData;
 use crate::shared::ApiResponse;
 
-type ParticipantServicesData = std::sync::Arc<
-    ParticipantServices<PgPlayerRepository, PgTeamRepository, PgTeamMemberRepository>,
->;
-
 pub struct PlayerHandler;
 
 impl PlayerHandler {
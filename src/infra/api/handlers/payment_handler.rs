@@ -2,14 +2,11 @@ use actix_web::{web, HttpRequest, HttpResponse, ResponseError};
 use serde::Deserialize;
 use uuid::Uuid;
 
-use crate::application::PaymentServices;
 use crate::domain::payment::{NewPayment, PaymentStatus};
 use crate::infra::api::middleware::auth::get_user_id_from_request;
-use crate::infra::db::PgPaymentRepository;
+use crate::infra::api::state::PaymentServicesData;
 use crate::shared::ApiResponse;
 
-type PaymentServicesData = std::sync::Arc<PaymentServices<PgPaymentRepository>>;
-
 #[derive(Debug, Deserialize)]
 pub struct PaymentIdPath {
     pub id: Uuid,
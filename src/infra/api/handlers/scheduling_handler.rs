@@ -0,0 +1,43 @@
+use actix_web::{web, HttpResponse, ResponseError};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::domain::scheduling::ScheduleRequest;
+use crate::infra::api::state::SchedulingServicesData;
+use crate::shared::ApiResponse;
+
+/// Query for POST /tournaments/{id}/schedule
+#[derive(Debug, Deserialize)]
+pub struct ScheduleQuery {
+    /// Defaults to true: propose a schedule without saving it
+    pub dry_run: Option<bool>,
+}
+
+pub struct SchedulingHandler;
+
+impl SchedulingHandler {
+    pub async fn schedule_tournament(
+        services: web::Data<SchedulingServicesData>,
+        path: web::Path<Uuid>,
+        query: web::Query<ScheduleQuery>,
+        body: Option<web::Json<ScheduleRequest>>,
+    ) -> HttpResponse {
+        let request = body.map(|b| b.into_inner()).unwrap_or_default();
+        match services
+            .schedule_tournament(path.into_inner(), request, query.dry_run.unwrap_or(true))
+            .await
+        {
+            Ok(report) => {
+                let message = if report.committed {
+                    "Scheduled"
+                } else if !report.unplaced.is_empty() {
+                    "Some matches could not be placed; nothing was saved"
+                } else {
+                    "Preview"
+                };
+                ApiResponse::success(message, Some(report))
+            }
+            Err(e) => e.error_response(),
+        }
+    }
+}
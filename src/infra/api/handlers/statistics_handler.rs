@@ -2,14 +2,10 @@ use actix_web::{web, HttpRequest, HttpResponse, ResponseError};
 use serde::Deserialize;
 use uuid::Uuid;
 
-use crate::application::StatisticsServices;
 use crate::infra::api::middleware::auth::get_user_id_from_request;
-use crate::infra::db::{PgPlayerRepository, PgStatisticsRepository};
+use crate::infra::api::state::StatisticsServicesData;
 use crate::shared::ApiResponse;
 
-type StatisticsServicesData =
-    std::sync::Arc<StatisticsServices<PgStatisticsRepository, PgPlayerRepository>>;
-
 #[derive(Deserialize)]
 pub struct PlayerIdPath {
     pub player_id: Uuid,
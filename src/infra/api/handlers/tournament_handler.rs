@@ -2,16 +2,13 @@ use actix_web::{web, HttpRequest, HttpResponse, ResponseError};
 use serde::Deserialize;
 use uuid::Uuid;
 
-use crate::application::TournamentServices;
 use crate::domain::tournament::{
     EditableTournament, EditableTournamentCategory, EditableTournamentRegistration, NewTournament,
     NewTournamentCategory, NewTournamentRegistration, TournamentSearchQuery, TournamentStatus,
 };
 use crate::infra::api::middleware::auth::get_user_id_from_request;
-use crate::infra::db::{
-    PgTournamentBracketRepository, PgTournamentCategoryRepository,
-    PgTournamentRegistrationRepository, PgTournamentRepository, PgTournamentStandingsRepository,
-};
+use crate::infra::api::state::{TournamentServicesData, UserServicesData};
+use crate::infra::api::timezone_util::requested_timezone;
 use crate::shared::ApiResponse;
 
 #[derive(Deserialize)]
@@ -29,18 +26,14 @@ pub struct DuplicateTournamentBody {
     pub name: String,
 }
 
-type TournamentServicesData = std::sync::Arc<
-    TournamentServices<
-        PgTournamentRepository,
-        PgTournamentCategoryRepository,
-        PgTournamentRegistrationRepository,
-        PgTournamentBracketRepository,
-        PgTournamentStandingsRepository,
-    >,
->;
-
 pub struct TournamentHandler;
 
+#[derive(Deserialize)]
+pub struct ScorekeeperPath {
+    pub id: Uuid,
+    pub user_id: Uuid,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct TournamentStatusPath {
     pub status: TournamentStatus,
@@ -167,11 +160,19 @@ impl TournamentHandler {
         }
     }
 
+    /// Date filters without an offset are read in `?tz=`, else the caller's profile zone
     pub async fn search(
         services: web::Data<TournamentServicesData>,
+        users: web::Data<UserServicesData>,
+        req: HttpRequest,
         query: web::Query<TournamentSearchQuery>,
     ) -> HttpResponse {
-        match services.search_tournaments(query.into_inner()).await {
+        let mut query = query.into_inner();
+        match requested_timezone(&req, query.tz.as_deref(), &users).await {
+            Ok(tz) => query.tz = tz.map(|tz| tz.name().to_string()),
+            Err(response) => return response,
+        }
+        match services.search_tournaments(query).await {
             Ok(tournaments) => ApiResponse::success("OK", Some(tournaments)),
             Err(e) => e.error_response(),
         }
@@ -267,19 +268,6 @@ impl TournamentHandler {
         }
     }
 
-    pub async fn export(
-        services: web::Data<TournamentServicesData>,
-        path: web::Path<Uuid>,
-        query: web::Query<ExportFormatQuery>,
-    ) -> HttpResponse {
-        let id = path.into_inner();
-        let format = query.format.clone().unwrap_or_else(|| "json".to_string());
-        match services.export_tournament(id, format).await {
-            Ok(data) => ApiResponse::success("OK", Some(data)),
-            Err(e) => e.error_response(),
-        }
-    }
-
     pub async fn duplicate(
         services: web::Data<TournamentServicesData>,
         path: web::Path<Uuid>,
@@ -340,6 +328,57 @@ impl TournamentHandler {
             Err(e) => e.error_response(),
         }
     }
+    pub async fn get_scorekeepers(
+        services: web::Data<TournamentServicesData>,
+        req: HttpRequest,
+        path: web::Path<Uuid>,
+    ) -> HttpResponse {
+        let user_id = match get_user_id_from_request(&req) {
+            Ok(id) => id,
+            Err(response) => return response,
+        };
+        match services.get_scorekeepers(user_id, path.into_inner()).await {
+            Ok(scorekeepers) => ApiResponse::success("OK", Some(scorekeepers)),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn add_scorekeeper(
+        services: web::Data<TournamentServicesData>,
+        req: HttpRequest,
+        path: web::Path<ScorekeeperPath>,
+    ) -> HttpResponse {
+        let user_id = match get_user_id_from_request(&req) {
+            Ok(id) => id,
+            Err(response) => return response,
+        };
+        match services
+            .add_scorekeeper(user_id, path.id, path.user_id)
+            .await
+        {
+            Ok(()) => ApiResponse::success("Added", Some(serde_json::json!({}))),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn remove_scorekeeper(
+        services: web::Data<TournamentServicesData>,
+        req: HttpRequest,
+        path: web::Path<ScorekeeperPath>,
+    ) -> HttpResponse {
+        let user_id = match get_user_id_from_request(&req) {
+            Ok(id) => id,
+            Err(response) => return response,
+        };
+        match services
+            .remove_scorekeeper(user_id, path.id, path.user_id)
+            .await
+        {
+            Ok(true) => ApiResponse::success("Removed", Some(serde_json::json!({}))),
+            Ok(false) => ApiResponse::not_found("User is not a scorekeeper of this tournament"),
+            Err(e) => e.error_response(),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -347,11 +386,6 @@ pub struct FeaturedLimitQuery {
     pub limit: Option<u32>,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct ExportFormatQuery {
-    pub format: Option<String>,
-}
-
 /// Tournament category handlers - delegate to TournamentHandler with services
 pub struct TournamentCategoryHandler;
 
@@ -555,6 +589,19 @@ impl TournamentBracketHandler {
             Err(e) => e.error_response(),
         }
     }
+
+    /// Draws a category's bracket from the entries in the draw; once the category has a
+    /// check-in window, only checked-in entries play
+    pub async fn generate_for_category(
+        services: web::Data<TournamentServicesData>,
+        path: web::Path<Uuid>,
+    ) -> HttpResponse {
+        match services.generate_category_bracket(path.into_inner()).await {
+            Ok(Some(bracket)) => ApiResponse::success("Generated", Some(bracket)),
+            Ok(None) => ApiResponse::not_found("Category not found"),
+            Err(e) => e.error_response(),
+        }
+    }
 }
 
 /// Tournament standings handlers
@@ -583,9 +630,7 @@ impl TournamentStandingsHandler {
         path: web::Path<TournamentIdPath>,
     ) -> HttpResponse {
         match services.recalculate_standings(path.tournament_id).await {
-            Ok(count) => {
-                ApiResponse::success("Updated", Some(serde_json::json!({ "deleted": count })))
-            }
+            Ok(standings) => ApiResponse::success("Updated", Some(standings)),
             Err(e) => e.error_response(),
         }
     }
@@ -2,20 +2,16 @@ use actix_multipart::Multipart;
 use actix_web::{web, HttpRequest, HttpResponse, ResponseError};
 use uuid::Uuid;
 
-use crate::application::UserServices;
 use crate::domain::user::{
     EditableUser, NewUser, UpdateNotificationPreferences, UpdatePrivacySettings,
     UpdateUserPreferences, UpdateUserProfile,
 };
 use crate::infra::api::middleware::auth::get_user_id_from_request;
 use crate::infra::api::multipart_util::extract_file_from_multipart;
+use crate::infra::api::state::UserServicesData;
 use crate::infra::cloudinary::CloudinaryClient;
-use crate::infra::db::{PgUserProfileRepository, PgUserRepository};
 use crate::shared::ApiResponse;
 
-/// Concrete type for dependency injection
-type UserServicesData = std::sync::Arc<UserServices<PgUserRepository, PgUserProfileRepository>>;
-
 pub struct UserHandler;
 
 impl UserHandler {
@@ -0,0 +1,231 @@
+use actix_web::{web, HttpResponse, ResponseError};
+use chrono::{DateTime, Duration, Utc};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::domain::venue::{EditableCourt, EditableVenue, NewCourt, NewVenue};
+use crate::infra::api::state::{MatchServicesData, VenueServicesData};
+use crate::shared::ApiResponse;
+
+#[derive(Debug, Deserialize)]
+pub struct VenueListQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TournamentVenuePath {
+    pub id: Uuid,
+    pub venue_id: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AvailabilityQuery {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+}
+
+/// Defaults to the next 7 days
+#[derive(Debug, Deserialize)]
+pub struct CourtMatchesQuery {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+pub struct VenueHandler;
+
+impl VenueHandler {
+    pub async fn index(
+        services: web::Data<VenueServicesData>,
+        query: web::Query<VenueListQuery>,
+    ) -> HttpResponse {
+        let limit = query.limit.unwrap_or(50).min(100);
+        let offset = query.offset.unwrap_or(0).max(0);
+        match services.list_venues(limit, offset).await {
+            Ok(venues) => ApiResponse::success("OK", Some(venues)),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn post(
+        services: web::Data<VenueServicesData>,
+        body: web::Json<NewVenue>,
+    ) -> HttpResponse {
+        match services.create_venue(body.into_inner()).await {
+            Ok(venue) => ApiResponse::created("Created", venue),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn show(
+        services: web::Data<VenueServicesData>,
+        path: web::Path<Uuid>,
+    ) -> HttpResponse {
+        match services.get_venue(path.into_inner()).await {
+            Ok(Some(venue)) => ApiResponse::success("OK", Some(venue)),
+            Ok(None) => ApiResponse::not_found("Venue not found"),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn update(
+        services: web::Data<VenueServicesData>,
+        path: web::Path<Uuid>,
+        body: web::Json<EditableVenue>,
+    ) -> HttpResponse {
+        match services
+            .update_venue(path.into_inner(), body.into_inner())
+            .await
+        {
+            Ok(Some(venue)) => ApiResponse::success("Updated", Some(venue)),
+            Ok(None) => ApiResponse::not_found("Venue not found"),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn delete(
+        services: web::Data<VenueServicesData>,
+        path: web::Path<Uuid>,
+    ) -> HttpResponse {
+        match services.delete_venue(path.into_inner()).await {
+            Ok(Some(_)) => ApiResponse::success("Deleted", Some(serde_json::json!({}))),
+            Ok(None) => ApiResponse::not_found("Venue not found"),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn get_courts(
+        services: web::Data<VenueServicesData>,
+        path: web::Path<Uuid>,
+    ) -> HttpResponse {
+        match services.get_courts(path.into_inner()).await {
+            Ok(courts) => ApiResponse::success("OK", Some(courts)),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn add_court(
+        services: web::Data<VenueServicesData>,
+        path: web::Path<Uuid>,
+        body: web::Json<NewCourt>,
+    ) -> HttpResponse {
+        match services
+            .add_court(path.into_inner(), body.into_inner())
+            .await
+        {
+            Ok(Some(court)) => ApiResponse::created("Created", court),
+            Ok(None) => ApiResponse::not_found("Venue not found"),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn show_court(
+        services: web::Data<VenueServicesData>,
+        path: web::Path<Uuid>,
+    ) -> HttpResponse {
+        match services.get_court(path.into_inner()).await {
+            Ok(Some(court)) => ApiResponse::success("OK", Some(court)),
+            Ok(None) => ApiResponse::not_found("Court not found"),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn update_court(
+        services: web::Data<VenueServicesData>,
+        path: web::Path<Uuid>,
+        body: web::Json<EditableCourt>,
+    ) -> HttpResponse {
+        match services
+            .update_court(path.into_inner(), body.into_inner())
+            .await
+        {
+            Ok(Some(court)) => ApiResponse::success("Updated", Some(court)),
+            Ok(None) => ApiResponse::not_found("Court not found"),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn delete_court(
+        services: web::Data<VenueServicesData>,
+        path: web::Path<Uuid>,
+    ) -> HttpResponse {
+        match services.delete_court(path.into_inner()).await {
+            Ok(Some(_)) => ApiResponse::success("Deleted", Some(serde_json::json!({}))),
+            Ok(None) => ApiResponse::not_found("Court not found"),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn get_court_availability(
+        services: web::Data<VenueServicesData>,
+        path: web::Path<Uuid>,
+        query: web::Query<AvailabilityQuery>,
+    ) -> HttpResponse {
+        if query.to <= query.from {
+            return ApiResponse::bad_request("'to' must be after 'from'");
+        }
+        match services
+            .court_available(path.into_inner(), query.from, query.to)
+            .await
+        {
+            Ok(Some(available)) => {
+                ApiResponse::success("OK", Some(serde_json::json!({ "available": available })))
+            }
+            Ok(None) => ApiResponse::not_found("Court not found"),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn get_court_matches(
+        services: web::Data<MatchServicesData>,
+        path: web::Path<Uuid>,
+        query: web::Query<CourtMatchesQuery>,
+    ) -> HttpResponse {
+        let from = query.from.unwrap_or_else(Utc::now);
+        let to = query.to.unwrap_or(from + Duration::days(7));
+        if to <= from {
+            return ApiResponse::bad_request("'to' must be after 'from'");
+        }
+        match services
+            .get_court_matches(path.into_inner(), from, to)
+            .await
+        {
+            Ok(matches) => ApiResponse::success("OK", Some(matches)),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn get_tournament_venues(
+        services: web::Data<VenueServicesData>,
+        path: web::Path<Uuid>,
+    ) -> HttpResponse {
+        match services.get_tournament_venues(path.into_inner()).await {
+            Ok(venues) => ApiResponse::success("OK", Some(venues)),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn add_tournament_venue(
+        services: web::Data<VenueServicesData>,
+        path: web::Path<TournamentVenuePath>,
+    ) -> HttpResponse {
+        match services.add_tournament_venue(path.id, path.venue_id).await {
+            Ok(()) => ApiResponse::success("Added", Some(serde_json::json!({}))),
+            Err(e) => e.error_response(),
+        }
+    }
+
+    pub async fn remove_tournament_venue(
+        services: web::Data<VenueServicesData>,
+        path: web::Path<TournamentVenuePath>,
+    ) -> HttpResponse {
+        match services
+            .remove_tournament_venue(path.id, path.venue_id)
+            .await
+        {
+            Ok(true) => ApiResponse::success("Removed", Some(serde_json::json!({}))),
+            Ok(false) => ApiResponse::not_found("Venue is not linked to this tournament"),
+            Err(e) => e.error_response(),
+        }
+    }
+}
@@ -63,7 +63,10 @@ where
                 || req.path().starts_with("/swagger-ui")
                 || req.path().starts_with("/api-docs")
                 || req.path().starts_with("/events")
+                || req.path().starts_with("/ws")
                 || req.path().starts_with("/health")
+                || req.path() == "/notifications/unsubscribe"
+                || req.path().starts_with("/calendar/feeds/")
             {
                 return svc.call(req).await;
             }
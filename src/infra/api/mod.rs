@@ -6,5 +6,7 @@ pub mod multipart_util;
 pub mod openapi;
 pub mod routes;
 pub mod sse;
+pub mod state;
+pub mod timezone_util;
 
 pub use routes::api_routes;
@@ -2,17 +2,26 @@ use actix_web::web;
 
 use super::handlers::{
     auth_handler::AuthHandler,
+    calendar_handler::CalendarHandler,
+    check_in_handler::CheckInHandler,
+    court_queue_handler::CourtQueueHandler,
+    credential_handler::CredentialHandler,
+    export_handler::ExportHandler,
     health_handler::HealthHandler,
+    import_handler::ImportHandler,
+    live_scoring_handler::LiveScoringHandler,
     match_handler::{MatchHandler, MatchResultHandler},
     notification_handler::NotificationHandler,
     participant_handler::{PlayerHandler, TeamHandler, TeamMemberHandler},
     payment_handler::PaymentHandler,
+    scheduling_handler::SchedulingHandler,
     statistics_handler::{AnalyticsHandler, StatisticsHandler},
     tournament_handler::{
         TournamentBracketHandler, TournamentCategoryHandler, TournamentHandler,
         TournamentRegistrationHandler, TournamentStandingsHandler,
     },
     user_handler::{UserHandler, UserProfileHandler},
+    venue_handler::VenueHandler,
 };
 
 /// Configure all API routes
@@ -22,6 +31,12 @@ pub fn api_routes(cfg: &mut web::ServiceConfig) {
     // SSE events (real-time updates)
     cfg.service(web::scope("/events").route("", web::get().to(super::sse::event_stream)));
 
+    // WebSocket live scoring (checks its own token)
+    cfg.service(web::scope("/ws").route(
+        "/matches/{match_id}/scoring",
+        web::get().to(LiveScoringHandler::connect),
+    ));
+
     // Auth routes (no middleware)
     cfg.service(
         web::scope("/auth/google")
@@ -142,7 +157,26 @@ pub fn api_routes(cfg: &mut web::ServiceConfig) {
                 "/{id}/participants",
                 web::get().to(TournamentHandler::get_participants),
             )
-            .route("/{id}/export", web::get().to(TournamentHandler::export))
+            .route(
+                "/{id}/export",
+                web::get().to(ExportHandler::export_tournament),
+            )
+            .route(
+                "/{id}/import",
+                web::post().to(ImportHandler::import_entrants),
+            )
+            .route(
+                "/{id}/schedule",
+                web::post().to(SchedulingHandler::schedule_tournament),
+            )
+            .route(
+                "/{id}/schedule/pdf",
+                web::get().to(ExportHandler::schedule_pdf),
+            )
+            .route(
+                "/{id}/standings/pdf",
+                web::get().to(ExportHandler::standings_pdf),
+            )
             .route(
                 "/{id}/duplicate",
                 web::post().to(TournamentHandler::duplicate),
@@ -154,7 +188,32 @@ pub fn api_routes(cfg: &mut web::ServiceConfig) {
             .route(
                 "/{id}/settings",
                 web::put().to(TournamentHandler::update_settings),
-            ),
+            )
+            .route(
+                "/{id}/venues",
+                web::get().to(VenueHandler::get_tournament_venues),
+            )
+            .route(
+                "/{id}/venues/{venue_id}",
+                web::post().to(VenueHandler::add_tournament_venue),
+            )
+            .route(
+                "/{id}/venues/{venue_id}",
+                web::delete().to(VenueHandler::remove_tournament_venue),
+            )
+            .route(
+                "/{id}/scorekeepers",
+                web::get().to(TournamentHandler::get_scorekeepers),
+            )
+            .route(
+                "/{id}/scorekeepers/{user_id}",
+                web::post().to(TournamentHandler::add_scorekeeper),
+            )
+            .route(
+                "/{id}/scorekeepers/{user_id}",
+                web::delete().to(TournamentHandler::remove_scorekeeper),
+            )
+            .route("/{id}/desk", web::get().to(CourtQueueHandler::get_desk)),
     );
 
     // Tournament category routes
@@ -170,7 +229,21 @@ pub fn api_routes(cfg: &mut web::ServiceConfig) {
                 web::get().to(TournamentCategoryHandler::get_by_tournament),
             )
             .route("/{id}", web::put().to(TournamentCategoryHandler::update))
-            .route("/{id}", web::delete().to(TournamentCategoryHandler::delete)),
+            .route("/{id}", web::delete().to(TournamentCategoryHandler::delete))
+            .route(
+                "/{id}/withdrawals",
+                web::post().to(MatchHandler::withdraw_participant),
+            )
+            .route("/{id}/check_in", web::put().to(CheckInHandler::set_window))
+            .route("/{id}/check_in", web::get().to(CheckInHandler::get_sheet))
+            .route(
+                "/{id}/check_in",
+                web::delete().to(CheckInHandler::delete_window),
+            )
+            .route(
+                "/{id}/check_in/close",
+                web::post().to(CheckInHandler::close_window),
+            ),
     );
 
     // Tournament registration routes
@@ -207,9 +280,58 @@ pub fn api_routes(cfg: &mut web::ServiceConfig) {
             .route(
                 "/team/{team_id}",
                 web::get().to(TournamentRegistrationHandler::get_by_team),
+            )
+            .route(
+                "/{id}/check_in",
+                web::post().to(CheckInHandler::self_check_in),
+            )
+            .route(
+                "/{id}/check_in",
+                web::delete().to(CheckInHandler::undo_check_in),
+            )
+            .route(
+                "/{id}/check_in/desk",
+                web::post().to(CheckInHandler::desk_check_in),
+            )
+            .route(
+                "/{id}/check_in/pass",
+                web::get().to(CheckInHandler::get_pass),
+            )
+            .route(
+                "/{id}/check_in/qr",
+                web::get().to(CredentialHandler::registration_qr),
             ),
     );
 
+    // QR check-in (the scanned token is checked by the handler)
+    cfg.service(web::scope("/check_in").route("/scan", web::post().to(CheckInHandler::scan)));
+
+    // Calendar feeds; /calendar/feeds is public and checks the token in the URL
+    cfg.service(
+        web::scope("/calendar")
+            .route("/my", web::get().to(CalendarHandler::my_link))
+            .route(
+                "/tournaments/{id}",
+                web::get().to(CalendarHandler::tournament_link),
+            )
+            .route(
+                "/categories/{id}",
+                web::get().to(CalendarHandler::category_link),
+            )
+            .route("/courts/{id}", web::get().to(CalendarHandler::court_link))
+            .route("/feeds/{token}", web::get().to(CalendarHandler::feed)),
+    );
+
+    // Spectator tickets and verifying scanned credentials
+    cfg.service(
+        web::scope("/tickets")
+            .route("", web::get().to(CredentialHandler::get_my_tickets))
+            .route("/{id}/qr", web::get().to(CredentialHandler::ticket_qr)),
+    );
+    cfg.service(
+        web::scope("/credentials").route("/verify", web::post().to(CredentialHandler::verify)),
+    );
+
     // Bracket routes
     cfg.service(
         web::scope("/brackets")
@@ -221,9 +343,17 @@ pub fn api_routes(cfg: &mut web::ServiceConfig) {
                 "/category/{category_id}",
                 web::get().to(TournamentBracketHandler::get_by_category),
             )
+            .route(
+                "/category/{category_id}/pdf",
+                web::get().to(ExportHandler::bracket_pdf),
+            )
             .route(
                 "/generate/{tournament_id}",
                 web::put().to(TournamentBracketHandler::generate),
+            )
+            .route(
+                "/generate/category/{category_id}",
+                web::put().to(TournamentBracketHandler::generate_for_category),
             ),
     );
 
@@ -256,6 +386,10 @@ pub fn api_routes(cfg: &mut web::ServiceConfig) {
                 "/category/{category_id}",
                 web::get().to(MatchHandler::get_by_category),
             )
+            .route(
+                "/category/{category_id}/scoresheets",
+                web::get().to(ExportHandler::category_scoresheets_pdf),
+            )
             .route("/schedule", web::get().to(MatchHandler::get_schedule))
             .route("/my/upcoming", web::get().to(MatchHandler::my_upcoming))
             .route("/my/history", web::get().to(MatchHandler::my_history))
@@ -273,13 +407,40 @@ pub fn api_routes(cfg: &mut web::ServiceConfig) {
             .route("/{id}/start", web::put().to(MatchHandler::start))
             .route("/{id}/complete", web::put().to(MatchHandler::complete))
             .route("/{id}/cancel", web::put().to(MatchHandler::cancel))
+            .route("/{id}/walkover", web::post().to(MatchHandler::walkover))
+            .route("/{id}/retirement", web::post().to(MatchHandler::retire))
             .route("/{id}/postpone", web::put().to(MatchHandler::postpone))
             .route("/{id}/reschedule", web::put().to(MatchHandler::reschedule))
+            .route(
+                "/{id}/conflicts",
+                web::get().to(MatchHandler::get_conflicts),
+            )
+            .route(
+                "/{id}/call",
+                web::post().to(CourtQueueHandler::call_to_court),
+            )
+            .route(
+                "/{id}/call",
+                web::delete().to(CourtQueueHandler::withdraw_call),
+            )
+            .route(
+                "/{id}/call/present",
+                web::post().to(CourtQueueHandler::mark_present),
+            )
             .route(
                 "/{id}/results/validate",
                 web::get().to(MatchHandler::validate_result_scores),
             )
             .route("/{id}/live", web::put().to(MatchHandler::update_live))
+            .route("/{id}/scoring", web::get().to(MatchHandler::get_scoring))
+            .route(
+                "/{id}/scoring",
+                web::post().to(MatchHandler::record_scoring),
+            )
+            .route(
+                "/{id}/scoresheet",
+                web::get().to(ExportHandler::scoresheet_pdf),
+            )
             .route(
                 "/{id}/analytics",
                 web::get().to(MatchHandler::get_analytics),
@@ -294,6 +455,10 @@ pub fn api_routes(cfg: &mut web::ServiceConfig) {
             .route("/{id}/comments", web::get().to(MatchHandler::get_comments))
             .route("/{id}/comments", web::post().to(MatchHandler::add_comment))
             .route("/{id}/subscribe", web::post().to(MatchHandler::subscribe))
+            .route(
+                "/{id}/tickets",
+                web::post().to(CredentialHandler::issue_ticket),
+            )
             .route(
                 "/{id}/subscribe",
                 web::delete().to(MatchHandler::unsubscribe),
@@ -341,10 +506,69 @@ pub fn api_routes(cfg: &mut web::ServiceConfig) {
                 web::put().to(NotificationHandler::mark_all_read),
             )
             .route("/send", web::post().to(NotificationHandler::send))
+            .route(
+                "/preferences",
+                web::get().to(NotificationHandler::get_preferences),
+            )
+            .route(
+                "/preferences",
+                web::put().to(NotificationHandler::update_preferences),
+            )
+            .route(
+                "/unsubscribe",
+                web::post().to(NotificationHandler::unsubscribe_email),
+            )
+            .route(
+                "/push/vapid-public-key",
+                web::get().to(NotificationHandler::vapid_public_key),
+            )
+            .route(
+                "/push/subscriptions",
+                web::post().to(NotificationHandler::subscribe_push),
+            )
+            .route(
+                "/push/subscriptions",
+                web::delete().to(NotificationHandler::unsubscribe_push),
+            )
             .route("/{id}/read", web::put().to(NotificationHandler::mark_read))
+            .route(
+                "/{id}/deliveries",
+                web::get().to(NotificationHandler::deliveries),
+            )
             .route("/{id}", web::delete().to(NotificationHandler::delete)),
     );
 
+    // Venue and court routes
+    cfg.service(
+        web::scope("/venues")
+            .route("", web::get().to(VenueHandler::index))
+            .route("", web::post().to(VenueHandler::post))
+            .route("/{id}", web::get().to(VenueHandler::show))
+            .route("/{id}", web::put().to(VenueHandler::update))
+            .route("/{id}", web::delete().to(VenueHandler::delete))
+            .route("/{id}/courts", web::get().to(VenueHandler::get_courts))
+            .route("/{id}/courts", web::post().to(VenueHandler::add_court)),
+    );
+
+    cfg.service(
+        web::scope("/courts")
+            .route("/{id}", web::get().to(VenueHandler::show_court))
+            .route("/{id}", web::put().to(VenueHandler::update_court))
+            .route("/{id}", web::delete().to(VenueHandler::delete_court))
+            .route(
+                "/{id}/availability",
+                web::get().to(VenueHandler::get_court_availability),
+            )
+            .route(
+                "/{id}/matches",
+                web::get().to(VenueHandler::get_court_matches),
+            )
+            .route(
+                "/{id}/queue",
+                web::get().to(CourtQueueHandler::get_court_queue),
+            ),
+    );
+
     // Payment routes
     cfg.service(
         web::scope("/payments")
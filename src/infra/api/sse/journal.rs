@@ -0,0 +1,160 @@
+//! Event journal - keeps recently published events so reconnecting clients can
+//! replay what they missed.
+
+use std::collections::VecDeque;
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+
+use super::RealtimeEvent;
+use crate::infra::db::pool::DbPool;
+use crate::shared::AppError;
+
+/// A published event together with its stream id
+#[derive(Debug, Clone)]
+pub struct JournaledEvent {
+    pub id: i64,
+    pub event: RealtimeEvent,
+}
+
+/// Events published after a client's last seen id
+#[derive(Debug, Clone, Default)]
+pub struct Replay {
+    pub events: Vec<JournaledEvent>,
+    /// False when some events after the last seen id are no longer retained,
+    /// or the id was never issued by this journal
+    pub complete: bool,
+}
+
+/// Assigns increasing ids to events and retains a bounded window of them
+#[async_trait]
+pub trait EventJournal: Send + Sync {
+    /// Stores the event and returns its id, greater than every id issued before
+    async fn append(&self, event: &RealtimeEvent) -> Result<i64, AppError>;
+    /// Retained events with an id greater than `last_id`, oldest first
+    async fn since(&self, last_id: i64) -> Result<Replay, AppError>;
+}
+
+/// Where a replay for `last_id` starts, and whether it covers every event since.
+/// An id newer than anything issued (e.g. from before a restart) replays everything.
+fn replay_window(last_id: i64, oldest_retained: Option<i64>, newest_issued: i64) -> (i64, bool) {
+    if last_id > newest_issued {
+        return (0, false);
+    }
+    let oldest = oldest_retained.unwrap_or(newest_issued + 1);
+    (last_id, oldest <= last_id + 1)
+}
+
+// ==================== In-memory Journal ====================
+
+/// Ring buffer of the last `capacity` events; ids restart when the process does
+pub struct MemoryEventJournal {
+    capacity: usize,
+    state: Mutex<MemoryJournalState>,
+}
+
+#[derive(Default)]
+struct MemoryJournalState {
+    last_id: i64,
+    events: VecDeque<JournaledEvent>,
+}
+
+impl MemoryEventJournal {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::new(MemoryJournalState::default()),
+        }
+    }
+}
+
+#[async_trait]
+impl EventJournal for MemoryEventJournal {
+    async fn append(&self, event: &RealtimeEvent) -> Result<i64, AppError> {
+        let mut state = self.state.lock();
+        state.last_id += 1;
+        let id = state.last_id;
+        state.events.push_back(JournaledEvent {
+            id,
+            event: event.clone(),
+        });
+        while state.events.len() > self.capacity {
+            state.events.pop_front();
+        }
+        Ok(id)
+    }
+
+    async fn since(&self, last_id: i64) -> Result<Replay, AppError> {
+        let state = self.state.lock();
+        let (after, complete) =
+            replay_window(last_id, state.events.front().map(|e| e.id), state.last_id);
+        Ok(Replay {
+            events: state
+                .events
+                .iter()
+                .filter(|e| e.id > after)
+                .cloned()
+                .collect(),
+            complete,
+        })
+    }
+}
+
+// ==================== Postgres Journal ====================
+
+/// Journal in the `realtime_events` table; ids survive restarts and are shared by
+/// every server instance on the same database
+pub struct PgEventJournal {
+    pool: DbPool,
+    capacity: i64,
+}
+
+impl PgEventJournal {
+    pub fn new(pool: DbPool, capacity: usize) -> Self {
+        Self {
+            pool,
+            capacity: capacity as i64,
+        }
+    }
+}
+
+#[async_trait]
+impl EventJournal for PgEventJournal {
+    async fn append(&self, event: &RealtimeEvent) -> Result<i64, AppError> {
+        let payload = serde_json::to_value(event)
+            .map_err(|e| AppError::InternalError(format!("Failed to encode event: {}", e)))?;
+        let (id,): (i64,) =
+            sqlx::query_as("INSERT INTO realtime_events (payload) VALUES ($1) RETURNING id")
+                .bind(payload)
+                .fetch_one(&self.pool)
+                .await?;
+        sqlx::query("DELETE FROM realtime_events WHERE id <= $1")
+            .bind(id - self.capacity)
+            .execute(&self.pool)
+            .await?;
+        Ok(id)
+    }
+
+    async fn since(&self, last_id: i64) -> Result<Replay, AppError> {
+        let (oldest_id, newest_id): (Option<i64>, Option<i64>) =
+            sqlx::query_as("SELECT MIN(id), MAX(id) FROM realtime_events")
+                .fetch_one(&self.pool)
+                .await?;
+        let (after, complete) = replay_window(last_id, oldest_id, newest_id.unwrap_or(0));
+        let rows: Vec<(i64, serde_json::Value)> =
+            sqlx::query_as("SELECT id, payload FROM realtime_events WHERE id > $1 ORDER BY id")
+                .bind(after)
+                .fetch_all(&self.pool)
+                .await?;
+
+        let events = rows
+            .into_iter()
+            .filter_map(|(id, payload)| {
+                serde_json::from_value(payload)
+                    .ok()
+                    .map(|event| JournaledEvent { id, event })
+            })
+            .collect();
+        Ok(Replay { events, complete })
+    }
+}
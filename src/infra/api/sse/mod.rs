@@ -0,0 +1,308 @@
+//! Server-Sent Events (SSE) for real-time updates.
+//!
+//! Routes the [`RealtimeEvent`]s published by the application services (match, result,
+//! score, bracket, standings and registration changes, and notifications) to connected clients.
+//! Connect via `GET /events` for the event stream. Clients narrow the stream with
+//! `tournament_id`, `category_id` and `match_id` query parameters; `notifications=true`
+//! with a bearer token (header or `access_token` parameter) adds the caller's own
+//! notifications. Private events are never sent to anyone but their owner.
+//!
+//! Every event carries an increasing id and is kept in an [`EventJournal`]. A client
+//! reconnecting with `Last-Event-ID` (or `last_event_id`) first receives the matching
+//! events it missed; if some are no longer retained it gets a `resync` event and
+//! should reload its state.
+
+pub mod journal;
+
+pub use crate::domain::realtime::RealtimeEvent;
+pub use journal::{EventJournal, MemoryEventJournal, PgEventJournal};
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix_web::rt::time::interval;
+use actix_web::{web, HttpRequest};
+use actix_web_lab::sse::{self, Sse};
+use async_trait::async_trait;
+use futures_util::future;
+use futures_util::StreamExt;
+use parking_lot::Mutex;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use uuid::Uuid;
+
+use crate::domain::realtime::EventPublisher;
+use crate::infra::db::pool::DbPool;
+use crate::shared::config::SseConfig;
+use crate::shared::{jwt, AppError};
+
+/// What a client subscribed to
+#[derive(Debug, Clone, Default)]
+pub struct EventFilter {
+    pub tournament_id: Option<Uuid>,
+    pub category_id: Option<Uuid>,
+    pub match_id: Option<Uuid>,
+    /// Authenticated user; private events are only delivered when this is their owner
+    pub user_id: Option<Uuid>,
+    /// Deliver the user's own notifications
+    pub notifications: bool,
+}
+
+impl EventFilter {
+    fn has_topic(&self) -> bool {
+        self.tournament_id.is_some() || self.category_id.is_some() || self.match_id.is_some()
+    }
+
+    /// Whether `event` should be sent to a client subscribed with this filter.
+    /// Every topic given must match; no topics means all public events, unless the
+    /// client only asked for its notifications.
+    pub fn matches(&self, event: &RealtimeEvent) -> bool {
+        if let Some(owner) = event.owner() {
+            return self.notifications && self.user_id == Some(owner);
+        }
+        if self.notifications && !self.has_topic() {
+            return false;
+        }
+        let topic =
+            |wanted: Option<Uuid>, actual: Option<Uuid>| wanted.is_none_or(|id| actual == Some(id));
+        topic(self.tournament_id, event.tournament_id())
+            && topic(self.category_id, event.category_id())
+            && topic(self.match_id, event.match_id())
+    }
+}
+
+pub struct Broadcaster {
+    inner: Mutex<BroadcasterInner>,
+    journal: Arc<dyn EventJournal>,
+    /// Held while journaling and fanning out an event, and while a client replays,
+    /// so a reconnecting client neither misses nor duplicates an event
+    publish: tokio::sync::Mutex<()>,
+    retry: Duration,
+}
+
+#[derive(Debug, Clone)]
+struct Client {
+    filter: EventFilter,
+    sender: mpsc::Sender<sse::Event>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct BroadcasterInner {
+    clients: Vec<Client>,
+}
+
+impl Broadcaster {
+    /// Constructs new broadcaster and spawns ping loop. The journal lives in Postgres
+    /// when the config asks for it and a pool is available, in memory otherwise.
+    pub fn create(config: &SseConfig, pool: Option<DbPool>) -> Arc<Self> {
+        let journal: Arc<dyn EventJournal> = match pool {
+            Some(pool) if config.persistent_journal => {
+                Arc::new(PgEventJournal::new(pool, config.journal_capacity))
+            }
+            _ => Arc::new(MemoryEventJournal::new(config.journal_capacity)),
+        };
+        Self::with_journal(journal, config.retry)
+    }
+
+    /// Constructs new broadcaster over `journal`, telling clients to reconnect
+    /// after `retry`, and spawns ping loop.
+    pub fn with_journal(journal: Arc<dyn EventJournal>, retry: Duration) -> Arc<Self> {
+        let this = Arc::new(Broadcaster {
+            inner: Mutex::new(BroadcasterInner::default()),
+            journal,
+            publish: tokio::sync::Mutex::new(()),
+            retry,
+        });
+
+        Self::spawn_ping(Arc::clone(&this));
+
+        this
+    }
+
+    /// Pings clients every 15 seconds to remove stale connections.
+    fn spawn_ping(this: Arc<Self>) {
+        actix_web::rt::spawn(async move {
+            let mut interval = interval(Duration::from_secs(15));
+            loop {
+                interval.tick().await;
+                this.remove_stale_clients().await;
+            }
+        });
+    }
+
+    async fn remove_stale_clients(&self) {
+        let clients = self.inner.lock().clients.clone();
+        let mut ok_clients = Vec::new();
+
+        for client in clients {
+            if client
+                .sender
+                .send(sse::Event::Comment("ping".into()))
+                .await
+                .is_ok()
+            {
+                ok_clients.push(client.clone());
+            }
+        }
+
+        self.inner.lock().clients = ok_clients;
+    }
+
+    /// Registers a new SSE client that receives the events `filter` matches, after
+    /// replaying those published since `last_event_id`. Returns the SSE response body.
+    pub async fn new_client(
+        &self,
+        filter: EventFilter,
+        last_event_id: Option<i64>,
+    ) -> impl actix_web::Responder {
+        let _publishing = self.publish.lock().await;
+
+        let replay = match last_event_id {
+            Some(last_id) => match self.journal.since(last_id).await {
+                Ok(replay) => Some(replay),
+                Err(e) => {
+                    eprintln!("Event journal replay failed: {}", e);
+                    None
+                }
+            },
+            None => None,
+        };
+        let missed: Vec<sse::Event> = match &replay {
+            Some(replay) => {
+                let resync =
+                    (!replay.complete).then(|| sse::Data::new("resync").event("resync").into());
+                resync
+                    .into_iter()
+                    .chain(
+                        replay
+                            .events
+                            .iter()
+                            .filter(|e| filter.matches(&e.event))
+                            .map(|e| Self::message(e.id, &e.event)),
+                    )
+                    .collect()
+            }
+            // Nothing to replay against; the client has to reload its state
+            None if last_event_id.is_some() => {
+                vec![sse::Data::new("resync").event("resync").into()]
+            }
+            None => Vec::new(),
+        };
+
+        // Room for the whole replay, so queueing it cannot block before the stream is read
+        let (tx, rx) = mpsc::channel(32 + missed.len());
+        let _ = tx.send(sse::Data::new("connected").into()).await;
+        for event in missed {
+            let _ = tx.send(event).await;
+        }
+
+        self.inner
+            .lock()
+            .clients
+            .push(Client { filter, sender: tx });
+
+        let stream = ReceiverStream::new(rx).map(|e| Ok::<_, std::convert::Infallible>(e));
+        Sse::from_stream(stream).with_retry_duration(self.retry)
+    }
+
+    fn message(id: i64, event: &RealtimeEvent) -> sse::Event {
+        sse::Data::new(event.to_json()).id(id.to_string()).into()
+    }
+
+    /// Journals an event and sends it to the connected clients whose filter matches it.
+    pub async fn broadcast_event(&self, event: &RealtimeEvent) {
+        let (message, clients) = {
+            let _publishing = self.publish.lock().await;
+            let message = match self.journal.append(event).await {
+                Ok(id) => Self::message(id, event),
+                Err(e) => {
+                    eprintln!("Event journal append failed: {}", e);
+                    sse::Data::new(event.to_json()).into()
+                }
+            };
+            let clients: Vec<Client> = self
+                .inner
+                .lock()
+                .clients
+                .iter()
+                .filter(|client| client.filter.matches(event))
+                .cloned()
+                .collect();
+            (message, clients)
+        };
+
+        let send_futures = clients
+            .iter()
+            .map(|client| client.sender.send(message.clone()));
+
+        let _ = future::join_all(send_futures).await;
+    }
+}
+
+#[async_trait]
+impl EventPublisher for Broadcaster {
+    async fn publish(&self, event: RealtimeEvent) {
+        self.broadcast_event(&event).await;
+    }
+}
+
+/// Query parameters for GET /events
+#[derive(Debug, serde::Deserialize)]
+pub struct EventStreamQuery {
+    pub tournament_id: Option<Uuid>,
+    pub category_id: Option<Uuid>,
+    pub match_id: Option<Uuid>,
+    #[serde(default)]
+    pub notifications: bool,
+    /// Bearer token for clients that cannot set headers (e.g. `EventSource`)
+    pub access_token: Option<String>,
+    /// Fallback for the `Last-Event-ID` header
+    pub last_event_id: Option<i64>,
+}
+
+/// SSE handler for GET /events - streams real-time updates to clients.
+/// `/events` bypasses the auth middleware, so the token is checked here.
+pub async fn event_stream(
+    req: HttpRequest,
+    query: web::Query<EventStreamQuery>,
+    broadcaster: web::Data<Arc<Broadcaster>>,
+) -> Result<impl actix_web::Responder, AppError> {
+    let query = query.into_inner();
+    let last_event_id = req
+        .headers()
+        .get("Last-Event-ID")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|s| s.trim().parse::<i64>().ok())
+        .or(query.last_event_id);
+    let token = req
+        .headers()
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|s| s.strip_prefix("Bearer "))
+        .map(str::to_string)
+        .or(query.access_token);
+
+    let user_id = match token {
+        Some(token) => Some(
+            jwt::validate_jwt(&token)
+                .ok()
+                .and_then(|claims| claims.user_id())
+                .ok_or_else(|| AppError::Unauthorized("Invalid token".into()))?,
+        ),
+        None => None,
+    };
+    if query.notifications && user_id.is_none() {
+        return Err(AppError::Unauthorized(
+            "Subscribing to notifications requires a bearer token".into(),
+        ));
+    }
+
+    let filter = EventFilter {
+        tournament_id: query.tournament_id,
+        category_id: query.category_id,
+        match_id: query.match_id,
+        user_id,
+        notifications: query.notifications,
+    };
+    Ok(broadcaster.new_client(filter, last_event_id).await)
+}
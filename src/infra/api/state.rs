@@ -0,0 +1,406 @@
+// Application state - the services handlers receive as app data
+
+use actix_web::web;
+use std::sync::Arc;
+
+use crate::application::event_subscribers::{
+    EmailNotificationSubscriber, NotificationSubscriber, PushNotificationSubscriber,
+    StandingsSubscriber, UnsubscribeLinks,
+};
+use crate::application::{
+    AuthServices, CalendarServices, CheckInServices, CourtQueueServices, DelayNotifier,
+    ExportServices, ImportServices, MatchServices, NotificationServices, OutboxDispatcher,
+    ParticipantServices, PaymentServices, ReminderScheduler, SchedulingServices,
+    StatisticsServices, TicketServices, TournamentServices, UserServices, VenueServices,
+};
+use crate::domain::check_in::CheckInWindowRepository;
+use crate::domain::court_queue::CourtCallRepository;
+use crate::domain::import::ImportRepository;
+use crate::domain::match_domain::{MatchRepository, MatchResultRepository};
+use crate::domain::notification::{
+    EmailSender, NotificationDeliveryRepository, NotificationRepository, PushSender,
+    PushSubscriptionRepository,
+};
+use crate::domain::outbox::OutboxRepository;
+use crate::domain::participant::{PlayerRepository, TeamMemberRepository, TeamRepository};
+use crate::domain::payment::PaymentRepository;
+use crate::domain::realtime::EventPublisher;
+use crate::domain::statistics::StatisticsRepository;
+use crate::domain::ticket::MatchTicketRepository;
+use crate::domain::tournament::{
+    TournamentBracketRepository, TournamentCategoryRepository, TournamentRegistrationRepository,
+    TournamentRepository, TournamentStandingsRepository,
+};
+use crate::domain::unit_of_work::UnitOfWorkFactory;
+use crate::domain::user::{TokenRepository, UserProfileRepository, UserRepository};
+use crate::domain::venue::VenueRepository;
+use crate::infra::repositories::Repositories;
+use crate::shared::config::{DelayConfig, OutboxConfig, ReminderConfig};
+
+// ==================== Service Types ====================
+
+pub type AuthServicesData = Arc<AuthServices<dyn UserRepository, dyn TokenRepository>>;
+
+pub type UserServicesData = Arc<UserServices<dyn UserRepository, dyn UserProfileRepository>>;
+
+pub type ParticipantServicesData =
+    Arc<ParticipantServices<dyn PlayerRepository, dyn TeamRepository, dyn TeamMemberRepository>>;
+
+pub type TournamentServicesData = Arc<
+    TournamentServices<
+        dyn TournamentRepository,
+        dyn TournamentCategoryRepository,
+        dyn TournamentRegistrationRepository,
+        dyn TournamentBracketRepository,
+        dyn TournamentStandingsRepository,
+        dyn UnitOfWorkFactory,
+    >,
+>;
+
+pub type MatchServicesData = Arc<
+    MatchServices<
+        dyn MatchRepository,
+        dyn MatchResultRepository,
+        dyn TournamentCategoryRepository,
+        dyn TournamentRepository,
+        dyn VenueRepository,
+        dyn TeamMemberRepository,
+        dyn UnitOfWorkFactory,
+    >,
+>;
+
+pub type NotificationServicesData = Arc<
+    NotificationServices<
+        dyn NotificationRepository,
+        dyn NotificationDeliveryRepository,
+        dyn PushSubscriptionRepository,
+        dyn UserProfileRepository,
+        dyn UnitOfWorkFactory,
+    >,
+>;
+
+pub type PaymentServicesData = Arc<PaymentServices<dyn PaymentRepository, dyn UnitOfWorkFactory>>;
+
+pub type StatisticsServicesData =
+    Arc<StatisticsServices<dyn StatisticsRepository, dyn PlayerRepository>>;
+
+pub type ExportServicesData = Arc<
+    ExportServices<
+        dyn TournamentRepository,
+        dyn TournamentCategoryRepository,
+        dyn TournamentRegistrationRepository,
+        dyn TournamentBracketRepository,
+        dyn TournamentStandingsRepository,
+        dyn MatchRepository,
+        dyn MatchResultRepository,
+        dyn PaymentRepository,
+    >,
+>;
+
+pub type ImportServicesData = Arc<
+    ImportServices<
+        dyn TournamentRepository,
+        dyn TournamentCategoryRepository,
+        dyn TournamentRegistrationRepository,
+        dyn ImportRepository,
+    >,
+>;
+
+pub type VenueServicesData = Arc<VenueServices<dyn VenueRepository>>;
+
+pub type SchedulingServicesData = Arc<
+    SchedulingServices<
+        dyn TournamentRepository,
+        dyn TournamentCategoryRepository,
+        dyn MatchRepository,
+        dyn VenueRepository,
+        dyn TeamMemberRepository,
+        dyn UnitOfWorkFactory,
+    >,
+>;
+
+pub type CourtQueueServicesData = Arc<
+    CourtQueueServices<
+        dyn MatchRepository,
+        dyn CourtCallRepository,
+        dyn TournamentCategoryRepository,
+        dyn VenueRepository,
+        dyn UnitOfWorkFactory,
+    >,
+>;
+
+pub type CheckInServicesData = Arc<
+    CheckInServices<
+        dyn CheckInWindowRepository,
+        dyn TournamentRegistrationRepository,
+        dyn TournamentCategoryRepository,
+        dyn PlayerRepository,
+        dyn TeamMemberRepository,
+        dyn UnitOfWorkFactory,
+    >,
+>;
+
+pub type TicketServicesData = Arc<
+    TicketServices<
+        dyn MatchTicketRepository,
+        dyn MatchRepository,
+        dyn TournamentCategoryRepository,
+        dyn TournamentRepository,
+    >,
+>;
+
+pub type CalendarServicesData = Arc<
+    CalendarServices<
+        dyn MatchRepository,
+        dyn TournamentCategoryRepository,
+        dyn TournamentRepository,
+        dyn VenueRepository,
+    >,
+>;
+
+pub type OutboxDispatcherData = Arc<OutboxDispatcher<dyn OutboxRepository>>;
+
+pub type ReminderSchedulerData = Arc<
+    ReminderScheduler<
+        dyn MatchRepository,
+        dyn UserProfileRepository,
+        dyn NotificationRepository,
+        dyn NotificationDeliveryRepository,
+        dyn PushSubscriptionRepository,
+        dyn UnitOfWorkFactory,
+    >,
+>;
+
+pub type DelayNotifierData = Arc<
+    DelayNotifier<
+        dyn MatchRepository,
+        dyn TournamentCategoryRepository,
+        dyn TournamentRepository,
+        dyn UserProfileRepository,
+        dyn NotificationRepository,
+        dyn NotificationDeliveryRepository,
+        dyn PushSubscriptionRepository,
+        dyn UnitOfWorkFactory,
+    >,
+>;
+
+// ==================== App Services ====================
+
+/// Every application service, built once and shared across workers
+#[derive(Clone)]
+pub struct AppServices {
+    pub auth: AuthServicesData,
+    pub users: UserServicesData,
+    pub participants: ParticipantServicesData,
+    pub tournaments: TournamentServicesData,
+    pub matches: MatchServicesData,
+    pub notifications: NotificationServicesData,
+    pub payments: PaymentServicesData,
+    pub statistics: StatisticsServicesData,
+    pub exports: ExportServicesData,
+    pub imports: ImportServicesData,
+    pub venues: VenueServicesData,
+    pub scheduling: SchedulingServicesData,
+    pub court_queue: CourtQueueServicesData,
+    pub check_in: CheckInServicesData,
+    pub tickets: TicketServicesData,
+    pub calendars: CalendarServicesData,
+}
+
+impl AppServices {
+    /// `events` receives the realtime events the services publish
+    pub fn new(repos: &Repositories, events: Arc<dyn EventPublisher>) -> Self {
+        Self {
+            auth: Arc::new(AuthServices::new(
+                Arc::clone(&repos.users),
+                Arc::clone(&repos.tokens),
+            )),
+            users: Arc::new(UserServices::new(
+                Arc::clone(&repos.users),
+                Arc::clone(&repos.profiles),
+            )),
+            participants: Arc::new(ParticipantServices::new(
+                Arc::clone(&repos.players),
+                Arc::clone(&repos.teams),
+                Arc::clone(&repos.team_members),
+            )),
+            tournaments: Arc::new(TournamentServices::new(
+                Arc::clone(&repos.tournaments),
+                Arc::clone(&repos.categories),
+                Arc::clone(&repos.registrations),
+                Arc::clone(&repos.brackets),
+                Arc::clone(&repos.standings),
+                Arc::clone(&repos.unit_of_work),
+                Arc::clone(&events),
+            )),
+            matches: Arc::new(MatchServices::new(
+                Arc::clone(&repos.matches),
+                Arc::clone(&repos.match_results),
+                Arc::clone(&repos.categories),
+                Arc::clone(&repos.tournaments),
+                Arc::clone(&repos.venues),
+                Arc::clone(&repos.team_members),
+                Arc::clone(&repos.unit_of_work),
+                Arc::clone(&events),
+            )),
+            notifications: Arc::new(NotificationServices::new(
+                Arc::clone(&repos.notifications),
+                Arc::clone(&repos.notification_deliveries),
+                Arc::clone(&repos.push_subscriptions),
+                Arc::clone(&repos.profiles),
+                Arc::clone(&repos.unit_of_work),
+                Arc::clone(&events),
+            )),
+            payments: Arc::new(PaymentServices::new(
+                Arc::clone(&repos.payments),
+                Arc::clone(&repos.unit_of_work),
+            )),
+            statistics: Arc::new(StatisticsServices::new(
+                Arc::clone(&repos.statistics),
+                Arc::clone(&repos.players),
+            )),
+            exports: Arc::new(ExportServices::new(
+                Arc::clone(&repos.tournaments),
+                Arc::clone(&repos.categories),
+                Arc::clone(&repos.registrations),
+                Arc::clone(&repos.brackets),
+                Arc::clone(&repos.standings),
+                Arc::clone(&repos.matches),
+                Arc::clone(&repos.match_results),
+                Arc::clone(&repos.payments),
+            )),
+            imports: Arc::new(ImportServices::new(
+                Arc::clone(&repos.tournaments),
+                Arc::clone(&repos.categories),
+                Arc::clone(&repos.registrations),
+                Arc::clone(&repos.imports),
+                Arc::clone(&events),
+            )),
+            venues: Arc::new(VenueServices::new(Arc::clone(&repos.venues))),
+            scheduling: Arc::new(SchedulingServices::new(
+                Arc::clone(&repos.tournaments),
+                Arc::clone(&repos.categories),
+                Arc::clone(&repos.matches),
+                Arc::clone(&repos.venues),
+                Arc::clone(&repos.team_members),
+                Arc::clone(&repos.unit_of_work),
+                Arc::clone(&events),
+            )),
+            court_queue: Arc::new(CourtQueueServices::new(
+                Arc::clone(&repos.matches),
+                Arc::clone(&repos.court_calls),
+                Arc::clone(&repos.categories),
+                Arc::clone(&repos.venues),
+                Arc::clone(&repos.unit_of_work),
+                Arc::clone(&events),
+            )),
+            check_in: Arc::new(CheckInServices::new(
+                Arc::clone(&repos.check_in_windows),
+                Arc::clone(&repos.registrations),
+                Arc::clone(&repos.categories),
+                Arc::clone(&repos.players),
+                Arc::clone(&repos.team_members),
+                Arc::clone(&repos.unit_of_work),
+                events,
+            )),
+            tickets: Arc::new(TicketServices::new(
+                Arc::clone(&repos.match_tickets),
+                Arc::clone(&repos.matches),
+                Arc::clone(&repos.categories),
+                Arc::clone(&repos.tournaments),
+            )),
+            calendars: Arc::new(CalendarServices::new(
+                Arc::clone(&repos.matches),
+                Arc::clone(&repos.categories),
+                Arc::clone(&repos.tournaments),
+                Arc::clone(&repos.venues),
+            )),
+        }
+    }
+
+    /// Outbox dispatcher wired to the built-in subscribers; spawn `OutboxDispatcher::run`.
+    /// Without a `push` sender Web Push is disabled, and without `unsubscribe` emails carry
+    /// no unsubscribe link.
+    pub fn outbox_dispatcher(
+        &self,
+        repos: &Repositories,
+        events: Arc<dyn EventPublisher>,
+        email: Arc<dyn EmailSender>,
+        push: Option<Arc<dyn PushSender>>,
+        unsubscribe: Option<UnsubscribeLinks>,
+        config: OutboxConfig,
+    ) -> OutboxDispatcherData {
+        let dispatcher = OutboxDispatcher::new(Arc::clone(&repos.outbox), config)
+            .subscribe(Arc::new(NotificationSubscriber::new(
+                Arc::clone(&self.notifications),
+                Arc::clone(&repos.players),
+                Arc::clone(&repos.team_members),
+            )))
+            .subscribe(Arc::new(StandingsSubscriber::new(events)))
+            .subscribe(Arc::new(EmailNotificationSubscriber::new(
+                Arc::clone(&repos.notifications),
+                Arc::clone(&repos.notification_deliveries),
+                Arc::clone(&repos.users),
+                Arc::clone(&repos.profiles),
+                email,
+                unsubscribe,
+            )));
+        let dispatcher = match push {
+            Some(push) => dispatcher.subscribe(Arc::new(PushNotificationSubscriber::new(
+                Arc::clone(&repos.notifications),
+                Arc::clone(&repos.notification_deliveries),
+                Arc::clone(&repos.push_subscriptions),
+                Arc::clone(&repos.profiles),
+                push,
+            ))),
+            None => dispatcher,
+        };
+        Arc::new(dispatcher)
+    }
+
+    /// Match reminder job; spawn `ReminderScheduler::run`
+    pub fn reminder_scheduler(
+        &self,
+        repos: &Repositories,
+        config: ReminderConfig,
+    ) -> ReminderSchedulerData {
+        Arc::new(ReminderScheduler::new(
+            Arc::clone(&repos.matches),
+            Arc::clone(&repos.profiles),
+            Arc::clone(&self.notifications),
+            config,
+        ))
+    }
+
+    /// Running-late notice job; spawn `DelayNotifier::run`
+    pub fn delay_notifier(&self, repos: &Repositories, config: DelayConfig) -> DelayNotifierData {
+        Arc::new(DelayNotifier::new(
+            Arc::clone(&repos.matches),
+            Arc::clone(&repos.categories),
+            Arc::clone(&repos.tournaments),
+            Arc::clone(&repos.profiles),
+            Arc::clone(&self.notifications),
+            config,
+        ))
+    }
+
+    /// Registers every service as app data; use with `App::configure`
+    pub fn register(&self, cfg: &mut web::ServiceConfig) {
+        cfg.app_data(web::Data::new(Arc::clone(&self.auth)))
+            .app_data(web::Data::new(Arc::clone(&self.users)))
+            .app_data(web::Data::new(Arc::clone(&self.participants)))
+            .app_data(web::Data::new(Arc::clone(&self.tournaments)))
+            .app_data(web::Data::new(Arc::clone(&self.matches)))
+            .app_data(web::Data::new(Arc::clone(&self.notifications)))
+            .app_data(web::Data::new(Arc::clone(&self.payments)))
+            .app_data(web::Data::new(Arc::clone(&self.statistics)))
+            .app_data(web::Data::new(Arc::clone(&self.exports)))
+            .app_data(web::Data::new(Arc::clone(&self.imports)))
+            .app_data(web::Data::new(Arc::clone(&self.venues)))
+            .app_data(web::Data::new(Arc::clone(&self.scheduling)))
+            .app_data(web::Data::new(Arc::clone(&self.court_queue)))
+            .app_data(web::Data::new(Arc::clone(&self.check_in)))
+            .app_data(web::Data::new(Arc::clone(&self.tickets)))
+            .app_data(web::Data::new(Arc::clone(&self.calendars)));
+    }
+}
@@ -0,0 +1,38 @@
+//! Picks the timezone a response renders times in.
+
+use actix_web::{HttpRequest, HttpResponse, ResponseError};
+use chrono_tz::Tz;
+use serde::Deserialize;
+
+use crate::infra::api::middleware::auth::get_user_id_from_request;
+use crate::infra::api::state::UserServicesData;
+use crate::shared::timezone::parse_timezone;
+
+/// Query for endpoints that render times: `?tz=Europe/Madrid`
+#[derive(Debug, Deserialize)]
+pub struct TimezoneQuery {
+    pub tz: Option<String>,
+}
+
+/// The zone named by `?tz=`, else the caller's profile timezone. `None` leaves the choice
+/// to the endpoint, usually the tournament's own zone.
+/// Returns Err(HttpResponse) when `?tz=` is not a known IANA zone.
+pub async fn requested_timezone(
+    req: &HttpRequest,
+    tz: Option<&str>,
+    users: &UserServicesData,
+) -> Result<Option<Tz>, HttpResponse> {
+    if let Some(tz) = tz.filter(|tz| !tz.trim().is_empty()) {
+        return parse_timezone(tz).map(Some).map_err(|e| e.error_response());
+    }
+    let Ok(user_id) = get_user_id_from_request(req) else {
+        return Ok(None);
+    };
+    let profile = users
+        .find_profile_by_user_id(user_id)
+        .await
+        .map_err(|e| e.error_response())?;
+    Ok(profile
+        .and_then(|p| p.timezone)
+        .and_then(|tz| parse_timezone(&tz).ok()))
+}
@@ -0,0 +1,125 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::domain::check_in::{CheckInWindow, CheckInWindowRepository, NoShowAction};
+use crate::shared::AppError;
+
+use super::pool::{DbHandle, DbPool};
+
+#[derive(Debug, FromRow)]
+struct CheckInWindowRow {
+    category_id: Uuid,
+    opens_at: DateTime<Utc>,
+    closes_at: DateTime<Utc>,
+    no_show_action: String,
+    promote_waitlist: bool,
+    closed_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<CheckInWindowRow> for CheckInWindow {
+    fn from(row: CheckInWindowRow) -> Self {
+        CheckInWindow {
+            category_id: row.category_id,
+            opens_at: row.opens_at,
+            closes_at: row.closes_at,
+            no_show_action: NoShowAction::from_key(&row.no_show_action).unwrap_or_default(),
+            promote_waitlist: row.promote_waitlist,
+            closed_at: row.closed_at,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+const WINDOW_SELECT: &str = "category_id, opens_at, closes_at, no_show_action, \
+    promote_waitlist, closed_at, created_at, updated_at";
+
+pub struct PgCheckInWindowRepository {
+    db: DbHandle,
+}
+
+impl PgCheckInWindowRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self {
+            db: DbHandle::Pool(pool),
+        }
+    }
+
+    /// Repository bound to a unit of work's connection
+    pub fn with_handle(db: DbHandle) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl CheckInWindowRepository for PgCheckInWindowRepository {
+    async fn save(&self, window: CheckInWindow) -> Result<CheckInWindow, AppError> {
+        let sql = format!(
+            r#"
+            INSERT INTO check_in_windows (category_id, opens_at, closes_at, no_show_action,
+                promote_waitlist, closed_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (category_id) DO UPDATE SET
+                opens_at = EXCLUDED.opens_at,
+                closes_at = EXCLUDED.closes_at,
+                no_show_action = EXCLUDED.no_show_action,
+                promote_waitlist = EXCLUDED.promote_waitlist,
+                closed_at = EXCLUDED.closed_at,
+                updated_at = NOW()
+            RETURNING {}
+            "#,
+            WINDOW_SELECT
+        );
+        let row: CheckInWindowRow = sqlx::query_as(&sql)
+            .bind(window.category_id)
+            .bind(window.opens_at)
+            .bind(window.closes_at)
+            .bind(window.no_show_action.key())
+            .bind(window.promote_waitlist)
+            .bind(window.closed_at)
+            .fetch_one(&mut *self.db.conn().await?)
+            .await?;
+        Ok(CheckInWindow::from(row))
+    }
+
+    async fn find_by_category(&self, category_id: Uuid) -> Result<Option<CheckInWindow>, AppError> {
+        let sql = format!(
+            "SELECT {} FROM check_in_windows WHERE category_id = $1",
+            WINDOW_SELECT
+        );
+        let row: Option<CheckInWindowRow> = sqlx::query_as(&sql)
+            .bind(category_id)
+            .fetch_optional(&mut *self.db.conn().await?)
+            .await?;
+        Ok(row.map(CheckInWindow::from))
+    }
+
+    async fn delete(&self, category_id: Uuid) -> Result<Option<CheckInWindow>, AppError> {
+        let sql = format!(
+            "DELETE FROM check_in_windows WHERE category_id = $1 RETURNING {}",
+            WINDOW_SELECT
+        );
+        let row: Option<CheckInWindowRow> = sqlx::query_as(&sql)
+            .bind(category_id)
+            .fetch_optional(&mut *self.db.conn().await?)
+            .await?;
+        Ok(row.map(CheckInWindow::from))
+    }
+
+    async fn find_due(&self, now: DateTime<Utc>) -> Result<Vec<CheckInWindow>, AppError> {
+        let sql = format!(
+            "SELECT {} FROM check_in_windows WHERE closed_at IS NULL AND closes_at <= $1 \
+             ORDER BY closes_at",
+            WINDOW_SELECT
+        );
+        let rows: Vec<CheckInWindowRow> = sqlx::query_as(&sql)
+            .bind(now)
+            .fetch_all(&mut *self.db.conn().await?)
+            .await?;
+        Ok(rows.into_iter().map(CheckInWindow::from).collect())
+    }
+}
@@ -0,0 +1,149 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::domain::court_queue::{CallStatus, CourtCall, CourtCallRepository};
+use crate::shared::AppError;
+
+use super::pool::{DbHandle, DbPool};
+
+fn call_status_to_db(status: CallStatus) -> &'static str {
+    match status {
+        CallStatus::Called => "called",
+        CallStatus::Ready => "ready",
+        CallStatus::Forfeited => "forfeited",
+        CallStatus::Closed => "closed",
+    }
+}
+
+fn call_status_from_db(s: &str) -> CallStatus {
+    match s {
+        "ready" => CallStatus::Ready,
+        "forfeited" => CallStatus::Forfeited,
+        "closed" => CallStatus::Closed,
+        _ => CallStatus::Called,
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct CourtCallRow {
+    match_id: Uuid,
+    court_id: Uuid,
+    status: String,
+    call_count: i32,
+    max_calls: i32,
+    first_called_at: DateTime<Utc>,
+    last_called_at: DateTime<Utc>,
+    respond_by: DateTime<Utc>,
+    participant1_present: bool,
+    participant2_present: bool,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<CourtCallRow> for CourtCall {
+    fn from(row: CourtCallRow) -> Self {
+        CourtCall {
+            match_id: row.match_id,
+            court_id: row.court_id,
+            status: call_status_from_db(&row.status),
+            call_count: row.call_count,
+            max_calls: row.max_calls,
+            first_called_at: row.first_called_at,
+            last_called_at: row.last_called_at,
+            respond_by: row.respond_by,
+            participant1_present: row.participant1_present,
+            participant2_present: row.participant2_present,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+const CALL_SELECT: &str = "match_id, court_id, status, call_count, max_calls, first_called_at, \
+    last_called_at, respond_by, participant1_present, participant2_present, created_at, \
+    updated_at";
+
+pub struct PgCourtCallRepository {
+    db: DbHandle,
+}
+
+impl PgCourtCallRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self {
+            db: DbHandle::Pool(pool),
+        }
+    }
+
+    /// Repository bound to a unit of work's connection
+    pub fn with_handle(db: DbHandle) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl CourtCallRepository for PgCourtCallRepository {
+    async fn save(&self, call: CourtCall) -> Result<CourtCall, AppError> {
+        let sql = format!(
+            r#"
+            INSERT INTO court_calls (match_id, court_id, status, call_count, max_calls,
+                first_called_at, last_called_at, respond_by, participant1_present,
+                participant2_present)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            ON CONFLICT (match_id) DO UPDATE SET
+                court_id = EXCLUDED.court_id,
+                status = EXCLUDED.status,
+                call_count = EXCLUDED.call_count,
+                max_calls = EXCLUDED.max_calls,
+                first_called_at = EXCLUDED.first_called_at,
+                last_called_at = EXCLUDED.last_called_at,
+                respond_by = EXCLUDED.respond_by,
+                participant1_present = EXCLUDED.participant1_present,
+                participant2_present = EXCLUDED.participant2_present,
+                updated_at = NOW()
+            RETURNING {}
+            "#,
+            CALL_SELECT
+        );
+        let row: CourtCallRow = sqlx::query_as(&sql)
+            .bind(call.match_id)
+            .bind(call.court_id)
+            .bind(call_status_to_db(call.status))
+            .bind(call.call_count)
+            .bind(call.max_calls)
+            .bind(call.first_called_at)
+            .bind(call.last_called_at)
+            .bind(call.respond_by)
+            .bind(call.participant1_present)
+            .bind(call.participant2_present)
+            .fetch_one(&mut *self.db.conn().await?)
+            .await?;
+        Ok(CourtCall::from(row))
+    }
+
+    async fn find_by_match(&self, match_id: Uuid) -> Result<Option<CourtCall>, AppError> {
+        let sql = format!(
+            "SELECT {} FROM court_calls WHERE match_id = $1",
+            CALL_SELECT
+        );
+        let row: Option<CourtCallRow> = sqlx::query_as(&sql)
+            .bind(match_id)
+            .fetch_optional(&mut *self.db.conn().await?)
+            .await?;
+        Ok(row.map(CourtCall::from))
+    }
+
+    async fn find_expired(&self, now: DateTime<Utc>) -> Result<Vec<CourtCall>, AppError> {
+        let sql = format!(
+            "SELECT {} FROM court_calls WHERE status = 'called' AND respond_by <= $1 \
+             ORDER BY respond_by",
+            CALL_SELECT
+        );
+        let rows: Vec<CourtCallRow> = sqlx::query_as(&sql)
+            .bind(now)
+            .fetch_all(&mut *self.db.conn().await?)
+            .await?;
+        Ok(rows.into_iter().map(CourtCall::from).collect())
+    }
+}
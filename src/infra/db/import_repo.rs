@@ -0,0 +1,244 @@
+use async_trait::async_trait;
+use sea_query::{PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use sqlx::FromRow;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::domain::import::{
+    ImportCommitResult, ImportPlan, ImportRepository, PlannedRef, PlayerWithEmail,
+};
+use crate::domain::participant::{Player, Team, TeamMember, TeamPlayer, TeamWithMembers};
+use crate::domain::tournament::TournamentRegistration;
+use crate::shared::AppError;
+
+use super::player_repo::{PlayerIden, PlayerRow};
+use super::pool::DbPool;
+use super::team_member_repo::{TeamMemberIden, TeamMemberRow};
+use super::team_repo::{TeamIden, TeamPlayerRow, TeamRow};
+use super::tournament_registration_repo::{TournamentRegistrationIden, TournamentRegistrationRow};
+
+// ==================== Row Types ====================
+
+#[derive(Debug, FromRow)]
+struct PlayerWithEmailRow {
+    #[sqlx(flatten)]
+    player: PlayerRow,
+    email: String,
+}
+
+#[derive(Debug, FromRow)]
+struct TeamMemberWithTeamRow {
+    team_id: Uuid,
+    #[sqlx(flatten)]
+    member: TeamPlayerRow,
+}
+
+// ==================== Repository ====================
+
+pub struct PgImportRepository {
+    pool: DbPool,
+}
+
+impl PgImportRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ImportRepository for PgImportRepository {
+    async fn find_players_by_emails(
+        &self,
+        emails: &[String],
+    ) -> Result<Vec<PlayerWithEmail>, AppError> {
+        if emails.is_empty() {
+            return Ok(Vec::new());
+        }
+        let rows: Vec<PlayerWithEmailRow> = sqlx::query_as(
+            r#"
+            SELECT p.id, p.name, p.user_id, p.created_at, u.email
+            FROM players p
+            INNER JOIN users u ON u.id = p.user_id
+            WHERE LOWER(u.email) = ANY($1)
+            "#,
+        )
+        .bind(emails)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| PlayerWithEmail {
+                player: Player::from(r.player),
+                email: r.email,
+            })
+            .collect())
+    }
+
+    async fn find_players_by_names(&self, names: &[String]) -> Result<Vec<Player>, AppError> {
+        if names.is_empty() {
+            return Ok(Vec::new());
+        }
+        let rows: Vec<PlayerRow> = sqlx::query_as(
+            r#"
+            SELECT id, name, user_id, created_at
+            FROM players
+            WHERE LOWER(regexp_replace(TRIM(name), '\s+', ' ', 'g')) = ANY($1)
+            "#,
+        )
+        .bind(names)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Player::from).collect())
+    }
+
+    async fn find_teams_by_names(
+        &self,
+        names: &[String],
+    ) -> Result<Vec<TeamWithMembers>, AppError> {
+        if names.is_empty() {
+            return Ok(Vec::new());
+        }
+        let teams: Vec<TeamRow> = sqlx::query_as(
+            r#"
+            SELECT id, name, created_at
+            FROM teams
+            WHERE LOWER(regexp_replace(TRIM(name), '\s+', ' ', 'g')) = ANY($1)
+            "#,
+        )
+        .bind(names)
+        .fetch_all(&self.pool)
+        .await?;
+        let teams: Vec<Team> = teams.into_iter().map(Team::from).collect();
+
+        let ids: Vec<Uuid> = teams.iter().map(|t| t.id).collect();
+        let members: Vec<TeamMemberWithTeamRow> = sqlx::query_as(
+            r#"
+            SELECT tm.team_id, p.id, p.name, p.user_id, tm.is_captain, tm.jersey_number, tm.joined_at
+            FROM team_members tm
+            INNER JOIN players p ON p.id = tm.player_id
+            WHERE tm.team_id = ANY($1)
+            "#,
+        )
+        .bind(&ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut by_team: HashMap<Uuid, Vec<TeamPlayer>> = HashMap::new();
+        for row in members {
+            by_team
+                .entry(row.team_id)
+                .or_default()
+                .push(TeamPlayer::from(row.member));
+        }
+
+        Ok(teams
+            .into_iter()
+            .map(|team| TeamWithMembers {
+                members: by_team.remove(&team.id).unwrap_or_default(),
+                team,
+            })
+            .collect())
+    }
+
+    async fn commit(&self, plan: ImportPlan) -> Result<ImportCommitResult, AppError> {
+        let mut tx = self.pool.begin().await?;
+        let mut result = ImportCommitResult::default();
+
+        for new_player in plan.players {
+            let (sql, values) = Query::insert()
+                .into_table(PlayerIden::Table)
+                .columns([PlayerIden::Name, PlayerIden::UserId])
+                .values_panic([new_player.name.into(), new_player.user_id.into()])
+                .returning_all()
+                .build_sqlx(PostgresQueryBuilder);
+            let row: PlayerRow = sqlx::query_as_with(&sql, values)
+                .fetch_one(&mut *tx)
+                .await?;
+            result.players.push(Player::from(row));
+        }
+
+        for new_team in plan.teams {
+            let (sql, values) = Query::insert()
+                .into_table(TeamIden::Table)
+                .columns([TeamIden::Name])
+                .values_panic([new_team.name.into()])
+                .returning_all()
+                .build_sqlx(PostgresQueryBuilder);
+            let row: TeamRow = sqlx::query_as_with(&sql, values)
+                .fetch_one(&mut *tx)
+                .await?;
+            result.teams.push(Team::from(row));
+        }
+
+        let player_id = |r: PlannedRef, created: &[Player]| match r {
+            PlannedRef::Existing(id) => id,
+            PlannedRef::New(i) => created[i].id,
+        };
+        let team_id = |r: PlannedRef, created: &[Team]| match r {
+            PlannedRef::Existing(id) => id,
+            PlannedRef::New(i) => created[i].id,
+        };
+
+        for member in plan.members {
+            let (sql, values) = Query::insert()
+                .into_table(TeamMemberIden::Table)
+                .columns([
+                    TeamMemberIden::TeamId,
+                    TeamMemberIden::PlayerId,
+                    TeamMemberIden::IsCaptain,
+                    TeamMemberIden::JerseyNumber,
+                ])
+                .values_panic([
+                    team_id(member.team, &result.teams).into(),
+                    player_id(member.player, &result.players).into(),
+                    member.is_captain.into(),
+                    member.jersey_number.into(),
+                ])
+                .returning_all()
+                .build_sqlx(PostgresQueryBuilder);
+            let row: TeamMemberRow = sqlx::query_as_with(&sql, values)
+                .fetch_one(&mut *tx)
+                .await?;
+            result.members.push(TeamMember::from(row));
+        }
+
+        for registration in plan.registrations {
+            let (sql, values) = Query::insert()
+                .into_table(TournamentRegistrationIden::Table)
+                .columns([
+                    TournamentRegistrationIden::TournamentCategoryId,
+                    TournamentRegistrationIden::TeamId,
+                    TournamentRegistrationIden::PlayerId,
+                    TournamentRegistrationIden::PartnerPlayerId,
+                    TournamentRegistrationIden::Notes,
+                    TournamentRegistrationIden::Metadata,
+                ])
+                .values_panic([
+                    registration.tournament_category_id.into(),
+                    registration.team.map(|r| team_id(r, &result.teams)).into(),
+                    registration
+                        .player
+                        .map(|r| player_id(r, &result.players))
+                        .into(),
+                    registration
+                        .partner
+                        .map(|r| player_id(r, &result.players))
+                        .into(),
+                    registration.notes.into(),
+                    registration.metadata.into(),
+                ])
+                .returning_all()
+                .build_sqlx(PostgresQueryBuilder);
+            let row: TournamentRegistrationRow = sqlx::query_as_with(&sql, values)
+                .fetch_one(&mut *tx)
+                .await?;
+            result.registrations.push(TournamentRegistration::from(row));
+        }
+
+        tx.commit().await?;
+        Ok(result)
+    }
+}
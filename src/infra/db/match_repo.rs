@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use sea_query::{Expr, Iden, PostgresQueryBuilder, Query};
 use sea_query_binder::SqlxBinder;
 use serde_json::Value as JsonValue;
@@ -8,13 +8,13 @@ use std::fmt::Write;
 use uuid::Uuid;
 
 use crate::domain::match_domain::{
-    EditableMatch, LiveMatchUpdate, Match, MatchAnalytics, MatchComment, MatchMedia,
+    EditableMatch, Forfeit, ForfeitKind, LiveMatchUpdate, Match, MatchAnalytics, MatchComment, MatchMedia,
     MatchRepository, MatchScheduleItem, MatchStatistics, MatchStatus, MatchSubscription, MatchType,
     MatchWithParticipants, NewMatch, RescheduleMatchRequest,
 };
 use crate::shared::AppError;
 
-use super::pool::DbPool;
+use super::pool::{DbHandle, DbPool};
 
 // ==================== Sea-Query Iden Definitions ====================
 
@@ -37,8 +37,11 @@ pub enum MatchIden {
     ActualEndDate,
     Venue,
     CourtNumber,
+    CourtId,
     WinnerParticipant,
     IsDraw,
+    ForfeitKind,
+    DefaultingParticipant,
     RefereeName,
     UmpireName,
     Notes,
@@ -71,8 +74,11 @@ impl Iden for MatchIden {
                 MatchIden::ActualEndDate => "actual_end_date",
                 MatchIden::Venue => "venue",
                 MatchIden::CourtNumber => "court_number",
+                MatchIden::CourtId => "court_id",
                 MatchIden::WinnerParticipant => "winner_participant",
                 MatchIden::IsDraw => "is_draw",
+                MatchIden::ForfeitKind => "forfeit_kind",
+                MatchIden::DefaultingParticipant => "defaulting_participant",
                 MatchIden::RefereeName => "referee_name",
                 MatchIden::UmpireName => "umpire_name",
                 MatchIden::Notes => "notes",
@@ -200,8 +206,11 @@ struct MatchRow {
     actual_end_date: Option<chrono::DateTime<Utc>>,
     venue: Option<String>,
     court_number: Option<String>,
+    court_id: Option<Uuid>,
     winner_participant: Option<i32>,
     is_draw: bool,
+    forfeit_kind: Option<String>,
+    defaulting_participant: Option<i32>,
     referee_name: Option<String>,
     umpire_name: Option<String>,
     notes: Option<String>,
@@ -230,8 +239,11 @@ impl From<MatchRow> for Match {
             actual_end_date: row.actual_end_date,
             venue: row.venue,
             court_number: row.court_number,
+            court_id: row.court_id,
             winner_participant: row.winner_participant,
             is_draw: row.is_draw,
+            forfeit_kind: row.forfeit_kind.as_deref().and_then(ForfeitKind::from_key),
+            defaulting_participant: row.defaulting_participant,
             referee_name: row.referee_name,
             umpire_name: row.umpire_name,
             notes: row.notes,
@@ -253,6 +265,7 @@ struct MatchWithParticipantsRow {
     scheduled_date: chrono::DateTime<Utc>,
     venue: Option<String>,
     court_number: Option<String>,
+    court_id: Option<Uuid>,
     winner_participant: Option<i32>,
 }
 
@@ -268,15 +281,49 @@ impl From<MatchWithParticipantsRow> for MatchWithParticipants {
             scheduled_date: row.scheduled_date,
             venue: row.venue,
             court_number: row.court_number,
+            court_id: row.court_id,
             winner_participant: row.winner_participant,
         }
     }
 }
 
+/// A match with its sides' display names; callers add the WHERE clause
+const MATCH_WITH_PARTICIPANTS_SELECT: &str = r#"
+        SELECT 
+            m.id, m.tournament_category_id,
+            COALESCE(
+                CASE 
+                    WHEN m.participant1_team_id IS NOT NULL THEN t1.name
+                    ELSE CONCAT(p1.name, 
+                               CASE WHEN m.participant1_partner_id IS NOT NULL 
+                                    THEN CONCAT(' / ', p1p.name) 
+                                    ELSE '' END)
+                END, 'TBD'
+            ) as participant1_name,
+            COALESCE(
+                CASE 
+                    WHEN m.participant2_team_id IS NOT NULL THEN t2.name
+                    ELSE CONCAT(p2.name,
+                               CASE WHEN m.participant2_partner_id IS NOT NULL 
+                                    THEN CONCAT(' / ', p2p.name) 
+                                    ELSE '' END)
+                END, 'TBD'
+            ) as participant2_name,
+            m.match_type, m.match_status, m.scheduled_date, m.venue, m.court_number, m.court_id, m.winner_participant
+        FROM matches m
+        LEFT JOIN teams t1 ON m.participant1_team_id = t1.id
+        LEFT JOIN teams t2 ON m.participant2_team_id = t2.id
+        LEFT JOIN players p1 ON m.participant1_player_id = p1.id
+        LEFT JOIN players p2 ON m.participant2_player_id = p2.id
+        LEFT JOIN players p1p ON m.participant1_partner_id = p1p.id
+        LEFT JOIN players p2p ON m.participant2_partner_id = p2p.id
+"#;
+
 #[derive(Debug, FromRow)]
 struct MatchScheduleItemRow {
     id: Uuid,
     tournament_category_id: Uuid,
+    tournament_id: Uuid,
     tournament_name: String,
     category_name: String,
     participant1_name: String,
@@ -294,6 +341,7 @@ impl From<MatchScheduleItemRow> for MatchScheduleItem {
         MatchScheduleItem {
             id: row.id,
             tournament_category_id: row.tournament_category_id,
+            tournament_id: row.tournament_id,
             tournament_name: row.tournament_name,
             category_name: row.category_name,
             participant1_name: row.participant1_name,
@@ -304,6 +352,8 @@ impl From<MatchScheduleItemRow> for MatchScheduleItem {
             venue: row.venue,
             court_number: row.court_number,
             round_number: row.round_number,
+            estimated_start_date: None,
+            delay_minutes: None,
         }
     }
 }
@@ -430,12 +480,19 @@ impl From<MatchSubscriptionRow> for MatchSubscription {
 // ==================== Match Repository Implementation ====================
 
 pub struct PgMatchRepository {
-    pool: DbPool,
+    db: DbHandle,
 }
 
 impl PgMatchRepository {
     pub fn new(pool: DbPool) -> Self {
-        Self { pool }
+        Self {
+            db: DbHandle::Pool(pool),
+        }
+    }
+
+    /// Repository bound to a unit of work's connection
+    pub fn with_handle(db: DbHandle) -> Self {
+        Self { db }
     }
 
     fn match_status_to_string(status: MatchStatus) -> String {
@@ -492,6 +549,7 @@ impl MatchRepository for PgMatchRepository {
                 MatchIden::ScheduledDate,
                 MatchIden::Venue,
                 MatchIden::CourtNumber,
+                MatchIden::CourtId,
                 MatchIden::RefereeName,
                 MatchIden::UmpireName,
                 MatchIden::Notes,
@@ -513,6 +571,7 @@ impl MatchRepository for PgMatchRepository {
                 new_match.scheduled_date.into(),
                 new_match.venue.into(),
                 new_match.court_number.into(),
+                new_match.court_id.into(),
                 new_match.referee_name.into(),
                 new_match.umpire_name.into(),
                 new_match.notes.into(),
@@ -522,7 +581,7 @@ impl MatchRepository for PgMatchRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let row: MatchRow = sqlx::query_as_with(&sql, values)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *self.db.conn().await?)
             .await?;
 
         Ok(Match::from(row))
@@ -548,8 +607,11 @@ impl MatchRepository for PgMatchRepository {
                 MatchIden::ActualEndDate,
                 MatchIden::Venue,
                 MatchIden::CourtNumber,
+                MatchIden::CourtId,
                 MatchIden::WinnerParticipant,
                 MatchIden::IsDraw,
+                MatchIden::ForfeitKind,
+                MatchIden::DefaultingParticipant,
                 MatchIden::RefereeName,
                 MatchIden::UmpireName,
                 MatchIden::Notes,
@@ -562,7 +624,7 @@ impl MatchRepository for PgMatchRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let row: Option<MatchRow> = sqlx::query_as_with(&sql, values)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(Match::from))
@@ -588,6 +650,9 @@ impl MatchRepository for PgMatchRepository {
         if let Some(court_number) = match_data.court_number {
             query.value(MatchIden::CourtNumber, court_number);
         }
+        if let Some(court_id) = match_data.court_id {
+            query.value(MatchIden::CourtId, court_id);
+        }
         if let Some(referee_name) = match_data.referee_name {
             query.value(MatchIden::RefereeName, referee_name);
         }
@@ -608,7 +673,7 @@ impl MatchRepository for PgMatchRepository {
         let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
 
         let row: Option<MatchRow> = sqlx::query_as_with(&sql, values)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(Match::from))
@@ -622,7 +687,7 @@ impl MatchRepository for PgMatchRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let row: Option<MatchRow> = sqlx::query_as_with(&sql, values)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(Match::from))
@@ -636,7 +701,8 @@ impl MatchRepository for PgMatchRepository {
                 m.participant2_team_id, m.participant2_player_id, m.participant2_partner_id,
                 m.match_type, m.match_status,
                 m.round_number, m.match_number, m.scheduled_date, m.actual_start_date, m.actual_end_date,
-                m.venue, m.court_number, m.winner_participant, m.is_draw,
+                m.venue, m.court_number, m.court_id, m.winner_participant, m.is_draw,
+                m.forfeit_kind, m.defaulting_participant,
                 m.referee_name, m.umpire_name, m.notes, m.metadata, m.created_at, m.updated_at
             FROM matches m
             JOIN tournament_categories tc ON m.tournament_category_id = tc.id
@@ -646,7 +712,7 @@ impl MatchRepository for PgMatchRepository {
 
         let rows: Vec<MatchRow> = sqlx::query_as(sql)
             .bind(tournament_id)
-            .fetch_all(&self.pool)
+            .fetch_all(&mut *self.db.conn().await?)
             .await?;
 
         Ok(rows.into_iter().map(Match::from).collect())
@@ -672,8 +738,11 @@ impl MatchRepository for PgMatchRepository {
                 MatchIden::ActualEndDate,
                 MatchIden::Venue,
                 MatchIden::CourtNumber,
+                MatchIden::CourtId,
                 MatchIden::WinnerParticipant,
                 MatchIden::IsDraw,
+                MatchIden::ForfeitKind,
+                MatchIden::DefaultingParticipant,
                 MatchIden::RefereeName,
                 MatchIden::UmpireName,
                 MatchIden::Notes,
@@ -688,7 +757,7 @@ impl MatchRepository for PgMatchRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let rows: Vec<MatchRow> = sqlx::query_as_with(&sql, values)
-            .fetch_all(&self.pool)
+            .fetch_all(&mut *self.db.conn().await?)
             .await?;
 
         Ok(rows.into_iter().map(Match::from).collect())
@@ -698,7 +767,7 @@ impl MatchRepository for PgMatchRepository {
         let sql = r#"
             SELECT 
                 m.id, m.tournament_category_id,
-                t.name as tournament_name, tc.name as category_name,
+                tc.tournament_id, t.name as tournament_name, tc.name as category_name,
                 COALESCE(t1.name, CONCAT(p1.name, COALESCE(CONCAT(' / ', pp1.name), '')), 'TBD') as participant1_name,
                 COALESCE(t2.name, CONCAT(p2.name, COALESCE(CONCAT(' / ', pp2.name), '')), 'TBD') as participant2_name,
                 m.match_type, m.match_status, m.scheduled_date, m.venue, m.court_number, m.round_number
@@ -715,55 +784,209 @@ impl MatchRepository for PgMatchRepository {
             ORDER BY m.scheduled_date ASC
         "#;
 
-        let rows: Vec<MatchScheduleItemRow> = sqlx::query_as(sql).fetch_all(&self.pool).await?;
+        let rows: Vec<MatchScheduleItemRow> = sqlx::query_as(sql).fetch_all(&mut *self.db.conn().await?).await?;
 
         Ok(rows.into_iter().map(MatchScheduleItem::from).collect())
     }
 
-    async fn find_with_participants(
+    async fn find_scheduled_between(
         &self,
-        match_id: Uuid,
-    ) -> Result<Option<MatchWithParticipants>, AppError> {
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<MatchScheduleItem>, AppError> {
+        let sql = r#"
+            SELECT
+                m.id, m.tournament_category_id,
+                tc.tournament_id, t.name as tournament_name, tc.name as category_name,
+                COALESCE(t1.name, CONCAT(p1.name, COALESCE(CONCAT(' / ', pp1.name), '')), 'TBD') as participant1_name,
+                COALESCE(t2.name, CONCAT(p2.name, COALESCE(CONCAT(' / ', pp2.name), '')), 'TBD') as participant2_name,
+                m.match_type, m.match_status, m.scheduled_date, m.venue, m.court_number, m.round_number
+            FROM matches m
+            JOIN tournament_categories tc ON m.tournament_category_id = tc.id
+            JOIN tournaments t ON tc.tournament_id = t.id
+            LEFT JOIN teams t1 ON m.participant1_team_id = t1.id
+            LEFT JOIN teams t2 ON m.participant2_team_id = t2.id
+            LEFT JOIN players p1 ON m.participant1_player_id = p1.id
+            LEFT JOIN players p2 ON m.participant2_player_id = p2.id
+            LEFT JOIN players pp1 ON m.participant1_partner_id = pp1.id
+            LEFT JOIN players pp2 ON m.participant2_partner_id = pp2.id
+            WHERE m.match_status = 'scheduled'
+            AND m.scheduled_date >= $1 AND m.scheduled_date < $2
+            ORDER BY m.scheduled_date ASC
+        "#;
+
+        let rows: Vec<MatchScheduleItemRow> = sqlx::query_as(sql)
+            .bind(from)
+            .bind(until)
+            .fetch_all(&mut *self.db.conn().await?)
+            .await?;
+
+        Ok(rows.into_iter().map(MatchScheduleItem::from).collect())
+    }
+
+    async fn find_schedule_by_tournament(
+        &self,
+        tournament_id: Uuid,
+    ) -> Result<Vec<MatchScheduleItem>, AppError> {
         let sql = r#"
             SELECT 
                 m.id, m.tournament_category_id,
-                COALESCE(
-                    CASE 
-                        WHEN m.participant1_team_id IS NOT NULL THEN t1.name
-                        ELSE CONCAT(p1.name, 
-                                   CASE WHEN m.participant1_partner_id IS NOT NULL 
-                                        THEN CONCAT(' / ', p1p.name) 
-                                        ELSE '' END)
-                    END, 'TBD'
-                ) as participant1_name,
-                COALESCE(
-                    CASE 
-                        WHEN m.participant2_team_id IS NOT NULL THEN t2.name
-                        ELSE CONCAT(p2.name,
-                                   CASE WHEN m.participant2_partner_id IS NOT NULL 
-                                        THEN CONCAT(' / ', p2p.name) 
-                                        ELSE '' END)
-                    END, 'TBD'
-                ) as participant2_name,
-                m.match_type, m.match_status, m.scheduled_date, m.venue, m.court_number, m.winner_participant
+                tc.tournament_id, t.name as tournament_name, tc.name as category_name,
+                COALESCE(t1.name, CONCAT(p1.name, COALESCE(CONCAT(' / ', pp1.name), '')), 'TBD') as participant1_name,
+                COALESCE(t2.name, CONCAT(p2.name, COALESCE(CONCAT(' / ', pp2.name), '')), 'TBD') as participant2_name,
+                m.match_type, m.match_status, m.scheduled_date, m.venue, m.court_number, m.round_number
             FROM matches m
+            JOIN tournament_categories tc ON m.tournament_category_id = tc.id
+            JOIN tournaments t ON tc.tournament_id = t.id
             LEFT JOIN teams t1 ON m.participant1_team_id = t1.id
             LEFT JOIN teams t2 ON m.participant2_team_id = t2.id
             LEFT JOIN players p1 ON m.participant1_player_id = p1.id
             LEFT JOIN players p2 ON m.participant2_player_id = p2.id
-            LEFT JOIN players p1p ON m.participant1_partner_id = p1p.id
-            LEFT JOIN players p2p ON m.participant2_partner_id = p2p.id
-            WHERE m.id = $1
+            LEFT JOIN players pp1 ON m.participant1_partner_id = pp1.id
+            LEFT JOIN players pp2 ON m.participant2_partner_id = pp2.id
+            WHERE t.id = $1 AND m.scheduled_date IS NOT NULL
+            ORDER BY m.scheduled_date ASC, m.court_number ASC
         "#;
 
-        let row: Option<MatchWithParticipantsRow> = sqlx::query_as(sql)
+        let rows: Vec<MatchScheduleItemRow> = sqlx::query_as(sql)
+            .bind(tournament_id)
+            .fetch_all(&mut *self.db.conn().await?)
+            .await?;
+
+        Ok(rows.into_iter().map(MatchScheduleItem::from).collect())
+    }
+
+    async fn find_with_participants(
+        &self,
+        match_id: Uuid,
+    ) -> Result<Option<MatchWithParticipants>, AppError> {
+        let sql = format!("{} WHERE m.id = $1", MATCH_WITH_PARTICIPANTS_SELECT);
+
+        let row: Option<MatchWithParticipantsRow> = sqlx::query_as(&sql)
             .bind(match_id)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(MatchWithParticipants::from))
     }
 
+    async fn find_with_participants_by_category(
+        &self,
+        category_id: Uuid,
+    ) -> Result<Vec<MatchWithParticipants>, AppError> {
+        let sql = format!(
+            "{} WHERE m.tournament_category_id = $1",
+            MATCH_WITH_PARTICIPANTS_SELECT
+        );
+
+        let rows: Vec<MatchWithParticipantsRow> = sqlx::query_as(&sql)
+            .bind(category_id)
+            .fetch_all(&mut *self.db.conn().await?)
+            .await?;
+
+        Ok(rows.into_iter().map(MatchWithParticipants::from).collect())
+    }
+
+    async fn find_by_court(
+        &self,
+        court_id: Uuid,
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<Match>, AppError> {
+        let (sql, values) = Query::select()
+            .columns([
+                MatchIden::Id,
+                MatchIden::TournamentCategoryId,
+                MatchIden::Participant1TeamId,
+                MatchIden::Participant1PlayerId,
+                MatchIden::Participant1PartnerId,
+                MatchIden::Participant2TeamId,
+                MatchIden::Participant2PlayerId,
+                MatchIden::Participant2PartnerId,
+                MatchIden::MatchType,
+                MatchIden::MatchStatus,
+                MatchIden::RoundNumber,
+                MatchIden::MatchNumber,
+                MatchIden::ScheduledDate,
+                MatchIden::ActualStartDate,
+                MatchIden::ActualEndDate,
+                MatchIden::Venue,
+                MatchIden::CourtNumber,
+                MatchIden::CourtId,
+                MatchIden::WinnerParticipant,
+                MatchIden::IsDraw,
+                MatchIden::ForfeitKind,
+                MatchIden::DefaultingParticipant,
+                MatchIden::RefereeName,
+                MatchIden::UmpireName,
+                MatchIden::Notes,
+                MatchIden::Metadata,
+                MatchIden::CreatedAt,
+                MatchIden::UpdatedAt,
+            ])
+            .from(MatchIden::Table)
+            .and_where(Expr::col(MatchIden::CourtId).eq(court_id))
+            .and_where(Expr::col(MatchIden::ScheduledDate).gte(from))
+            .and_where(Expr::col(MatchIden::ScheduledDate).lt(until))
+            .order_by(MatchIden::ScheduledDate, sea_query::Order::Asc)
+            .build_sqlx(PostgresQueryBuilder);
+
+        let rows: Vec<MatchRow> = sqlx::query_as_with(&sql, values)
+            .fetch_all(&mut *self.db.conn().await?)
+            .await?;
+
+        Ok(rows.into_iter().map(Match::from).collect())
+    }
+
+    async fn find_in_period(
+        &self,
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<Match>, AppError> {
+        let (sql, values) = Query::select()
+            .columns([
+                MatchIden::Id,
+                MatchIden::TournamentCategoryId,
+                MatchIden::Participant1TeamId,
+                MatchIden::Participant1PlayerId,
+                MatchIden::Participant1PartnerId,
+                MatchIden::Participant2TeamId,
+                MatchIden::Participant2PlayerId,
+                MatchIden::Participant2PartnerId,
+                MatchIden::MatchType,
+                MatchIden::MatchStatus,
+                MatchIden::RoundNumber,
+                MatchIden::MatchNumber,
+                MatchIden::ScheduledDate,
+                MatchIden::ActualStartDate,
+                MatchIden::ActualEndDate,
+                MatchIden::Venue,
+                MatchIden::CourtNumber,
+                MatchIden::CourtId,
+                MatchIden::WinnerParticipant,
+                MatchIden::IsDraw,
+                MatchIden::ForfeitKind,
+                MatchIden::DefaultingParticipant,
+                MatchIden::RefereeName,
+                MatchIden::UmpireName,
+                MatchIden::Notes,
+                MatchIden::Metadata,
+                MatchIden::CreatedAt,
+                MatchIden::UpdatedAt,
+            ])
+            .from(MatchIden::Table)
+            .and_where(Expr::col(MatchIden::ScheduledDate).gte(from))
+            .and_where(Expr::col(MatchIden::ScheduledDate).lt(until))
+            .order_by(MatchIden::ScheduledDate, sea_query::Order::Asc)
+            .build_sqlx(PostgresQueryBuilder);
+
+        let rows: Vec<MatchRow> = sqlx::query_as_with(&sql, values)
+            .fetch_all(&mut *self.db.conn().await?)
+            .await?;
+
+        Ok(rows.into_iter().map(Match::from).collect())
+    }
+
     async fn update_status(
         &self,
         match_id: Uuid,
@@ -793,7 +1016,7 @@ impl MatchRepository for PgMatchRepository {
         let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
 
         let row: Option<MatchRow> = sqlx::query_as_with(&sql, values)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(Match::from))
@@ -810,7 +1033,7 @@ impl MatchRepository for PgMatchRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let row: Option<MatchRow> = sqlx::query_as_with(&sql, values)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(Match::from))
@@ -834,7 +1057,7 @@ impl MatchRepository for PgMatchRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let row: Option<MatchRow> = sqlx::query_as_with(&sql, values)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(Match::from))
@@ -854,7 +1077,75 @@ impl MatchRepository for PgMatchRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let row: Option<MatchRow> = sqlx::query_as_with(&sql, values)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
+            .await?;
+
+        Ok(row.map(Match::from))
+    }
+
+    async fn forfeit_match(
+        &self,
+        match_id: Uuid,
+        forfeit: &Forfeit,
+    ) -> Result<Option<Match>, AppError> {
+        let (sql, values) = Query::update()
+            .table(MatchIden::Table)
+            .value(MatchIden::MatchStatus, "forfeited")
+            .value(MatchIden::WinnerParticipant, forfeit.winner())
+            .value(MatchIden::IsDraw, false)
+            .value(MatchIden::ForfeitKind, forfeit.kind.key())
+            .value(MatchIden::DefaultingParticipant, forfeit.defaulting_participant)
+            .value(MatchIden::ActualEndDate, Utc::now())
+            .value(MatchIden::Notes, forfeit.note())
+            .value(MatchIden::UpdatedAt, Utc::now())
+            .and_where(Expr::col(MatchIden::Id).eq(match_id))
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        let row: Option<MatchRow> = sqlx::query_as_with(&sql, values)
+            .fetch_optional(&mut *self.db.conn().await?)
+            .await?;
+
+        Ok(row.map(Match::from))
+    }
+
+    async fn assign_participant(
+        &self,
+        match_id: Uuid,
+        side: i32,
+        team_id: Option<Uuid>,
+        player_id: Option<Uuid>,
+        partner_id: Option<Uuid>,
+    ) -> Result<Option<Match>, AppError> {
+        let (team, player, partner) = match side {
+            1 => (
+                MatchIden::Participant1TeamId,
+                MatchIden::Participant1PlayerId,
+                MatchIden::Participant1PartnerId,
+            ),
+            2 => (
+                MatchIden::Participant2TeamId,
+                MatchIden::Participant2PlayerId,
+                MatchIden::Participant2PartnerId,
+            ),
+            _ => {
+                return Err(AppError::ValidationError(
+                    "Participant must be 1 or 2".into(),
+                ))
+            }
+        };
+        let (sql, values) = Query::update()
+            .table(MatchIden::Table)
+            .value(team, team_id)
+            .value(player, player_id)
+            .value(partner, partner_id)
+            .value(MatchIden::UpdatedAt, Utc::now())
+            .and_where(Expr::col(MatchIden::Id).eq(match_id))
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        let row: Option<MatchRow> = sqlx::query_as_with(&sql, values)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(Match::from))
@@ -870,7 +1161,7 @@ impl MatchRepository for PgMatchRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let row: Option<MatchRow> = sqlx::query_as_with(&sql, values)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(Match::from))
@@ -892,6 +1183,9 @@ impl MatchRepository for PgMatchRepository {
         if let Some(court_number) = request.new_court_number {
             query.value(MatchIden::CourtNumber, court_number);
         }
+        if let Some(court_id) = request.new_court_id {
+            query.value(MatchIden::CourtId, court_id);
+        }
         if let Some(reason) = request.reason {
             query.value(MatchIden::Notes, format!("Rescheduled: {}", reason));
         }
@@ -902,7 +1196,7 @@ impl MatchRepository for PgMatchRepository {
         let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
 
         let row: Option<MatchRow> = sqlx::query_as_with(&sql, values)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(Match::from))
@@ -915,7 +1209,7 @@ impl MatchRepository for PgMatchRepository {
         let sql = r#"
             SELECT DISTINCT
                 m.id, m.tournament_category_id,
-                t.name as tournament_name, tc.name as category_name,
+                tc.tournament_id, t.name as tournament_name, tc.name as category_name,
                 COALESCE(t1.name, CONCAT(p1.name, COALESCE(CONCAT(' / ', pp1.name), '')), 'TBD') as participant1_name,
                 COALESCE(t2.name, CONCAT(p2.name, COALESCE(CONCAT(' / ', pp2.name), '')), 'TBD') as participant2_name,
                 m.match_type, m.match_status, m.scheduled_date, m.venue, m.court_number, m.round_number
@@ -942,7 +1236,7 @@ impl MatchRepository for PgMatchRepository {
 
         let rows: Vec<MatchScheduleItemRow> = sqlx::query_as(sql)
             .bind(user_id)
-            .fetch_all(&self.pool)
+            .fetch_all(&mut *self.db.conn().await?)
             .await?;
 
         Ok(rows.into_iter().map(MatchScheduleItem::from).collect())
@@ -955,7 +1249,7 @@ impl MatchRepository for PgMatchRepository {
         let sql = r#"
             SELECT DISTINCT
                 m.id, m.tournament_category_id,
-                t.name as tournament_name, tc.name as category_name,
+                tc.tournament_id, t.name as tournament_name, tc.name as category_name,
                 COALESCE(t1.name, CONCAT(p1.name, COALESCE(CONCAT(' / ', pp1.name), '')), 'TBD') as participant1_name,
                 COALESCE(t2.name, CONCAT(p2.name, COALESCE(CONCAT(' / ', pp2.name), '')), 'TBD') as participant2_name,
                 m.match_type, m.match_status, m.scheduled_date, m.venue, m.court_number, m.round_number
@@ -981,7 +1275,7 @@ impl MatchRepository for PgMatchRepository {
 
         let rows: Vec<MatchScheduleItemRow> = sqlx::query_as(sql)
             .bind(user_id)
-            .fetch_all(&self.pool)
+            .fetch_all(&mut *self.db.conn().await?)
             .await?;
 
         Ok(rows.into_iter().map(MatchScheduleItem::from).collect())
@@ -1007,8 +1301,11 @@ impl MatchRepository for PgMatchRepository {
                 MatchIden::ActualEndDate,
                 MatchIden::Venue,
                 MatchIden::CourtNumber,
+                MatchIden::CourtId,
                 MatchIden::WinnerParticipant,
                 MatchIden::IsDraw,
+                MatchIden::ForfeitKind,
+                MatchIden::DefaultingParticipant,
                 MatchIden::RefereeName,
                 MatchIden::UmpireName,
                 MatchIden::Notes,
@@ -1022,12 +1319,20 @@ impl MatchRepository for PgMatchRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let rows: Vec<MatchRow> = sqlx::query_as_with(&sql, values)
-            .fetch_all(&self.pool)
+            .fetch_all(&mut *self.db.conn().await?)
             .await?;
 
         Ok(rows.into_iter().map(Match::from).collect())
     }
 
+    async fn lock(&self, match_id: Uuid) -> Result<(), AppError> {
+        sqlx::query("SELECT id FROM matches WHERE id = $1 FOR UPDATE")
+            .bind(match_id)
+            .fetch_optional(&mut *self.db.conn().await?)
+            .await?;
+        Ok(())
+    }
+
     async fn update_live_match(
         &self,
         match_id: Uuid,
@@ -1058,7 +1363,7 @@ impl MatchRepository for PgMatchRepository {
         let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
 
         let row: Option<MatchRow> = sqlx::query_as_with(&sql, values)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(Match::from))
@@ -1083,7 +1388,7 @@ impl MatchRepository for PgMatchRepository {
 
         let row: Option<MatchAnalyticsRow> = sqlx::query_as(sql)
             .bind(match_id)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(MatchAnalytics::from))
@@ -1113,7 +1418,7 @@ impl MatchRepository for PgMatchRepository {
 
         let row: Option<MatchStatisticsRow> = sqlx::query_as(sql)
             .bind(match_id)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(MatchStatistics::from))
@@ -1128,7 +1433,7 @@ impl MatchRepository for PgMatchRepository {
         "#;
 
         let rows: Vec<MatchMediaRow> =
-            sqlx::query_as(sql).bind(match_id).fetch_all(&self.pool).await?;
+            sqlx::query_as(sql).bind(match_id).fetch_all(&mut *self.db.conn().await?).await?;
 
         Ok(rows.into_iter().map(MatchMedia::from).collect())
     }
@@ -1151,7 +1456,7 @@ impl MatchRepository for PgMatchRepository {
             .bind(media_type)
             .bind(file_url)
             .bind(user_id)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *self.db.conn().await?)
             .await?;
 
         Ok(MatchMedia::from(row))
@@ -1166,7 +1471,7 @@ impl MatchRepository for PgMatchRepository {
         "#;
 
         let rows: Vec<MatchCommentRow> =
-            sqlx::query_as(sql).bind(match_id).fetch_all(&self.pool).await?;
+            sqlx::query_as(sql).bind(match_id).fetch_all(&mut *self.db.conn().await?).await?;
 
         Ok(rows.into_iter().map(MatchComment::from).collect())
     }
@@ -1187,7 +1492,7 @@ impl MatchRepository for PgMatchRepository {
             .bind(match_id)
             .bind(user_id)
             .bind(comment)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *self.db.conn().await?)
             .await?;
 
         Ok(MatchComment::from(row))
@@ -1197,18 +1502,21 @@ impl MatchRepository for PgMatchRepository {
         &self,
         match_id: Uuid,
         user_id: Uuid,
+        notification_preferences: JsonValue,
     ) -> Result<MatchSubscription, AppError> {
         let sql = r#"
             INSERT INTO match_subscriptions (match_id, user_id, notification_preferences)
-            VALUES ($1, $2, '{"all": true}'::jsonb)
-            ON CONFLICT (match_id, user_id) DO UPDATE SET created_at = NOW()
+            VALUES ($1, $2, $3)
+            ON CONFLICT (match_id, user_id) DO UPDATE
+            SET notification_preferences = EXCLUDED.notification_preferences, created_at = NOW()
             RETURNING id, match_id, user_id, notification_preferences, created_at
         "#;
 
         let row: MatchSubscriptionRow = sqlx::query_as(sql)
             .bind(match_id)
             .bind(user_id)
-            .fetch_one(&self.pool)
+            .bind(notification_preferences)
+            .fetch_one(&mut *self.db.conn().await?)
             .await?;
 
         Ok(MatchSubscription::from(row))
@@ -1227,12 +1535,53 @@ impl MatchRepository for PgMatchRepository {
         sqlx::query(sql)
             .bind(match_id)
             .bind(user_id)
-            .execute(&self.pool)
+            .execute(&mut *self.db.conn().await?)
             .await?;
 
         Ok(())
     }
 
+    async fn find_subscriptions(&self, match_id: Uuid) -> Result<Vec<MatchSubscription>, AppError> {
+        let sql = r#"
+            SELECT id, match_id, user_id, notification_preferences, created_at
+            FROM match_subscriptions
+            WHERE match_id = $1
+            ORDER BY created_at ASC
+        "#;
+
+        let rows: Vec<MatchSubscriptionRow> = sqlx::query_as(sql)
+            .bind(match_id)
+            .fetch_all(&mut *self.db.conn().await?)
+            .await?;
+
+        Ok(rows.into_iter().map(MatchSubscription::from).collect())
+    }
+
+    async fn find_participant_users(&self, match_id: Uuid) -> Result<Vec<Uuid>, AppError> {
+        let sql = r#"
+            SELECT p.user_id
+            FROM matches m
+            JOIN players p ON p.id IN (
+                m.participant1_player_id, m.participant1_partner_id,
+                m.participant2_player_id, m.participant2_partner_id
+            )
+            WHERE m.id = $1 AND p.user_id IS NOT NULL
+            UNION
+            SELECT p.user_id
+            FROM matches m
+            JOIN team_members tm ON tm.team_id IN (m.participant1_team_id, m.participant2_team_id)
+            JOIN players p ON p.id = tm.player_id
+            WHERE m.id = $1 AND p.user_id IS NOT NULL
+        "#;
+
+        let rows: Vec<(Uuid,)> = sqlx::query_as(sql)
+            .bind(match_id)
+            .fetch_all(&mut *self.db.conn().await?)
+            .await?;
+
+        Ok(rows.into_iter().map(|(user_id,)| user_id).collect())
+    }
+
     async fn bulk_update_matches(
         &self,
         match_ids: Vec<Uuid>,
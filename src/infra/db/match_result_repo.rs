@@ -12,7 +12,7 @@ use crate::domain::match_domain::{
 };
 use crate::shared::AppError;
 
-use super::pool::DbPool;
+use super::pool::{DbHandle, DbPool};
 
 // ==================== Sea-Query Iden Definitions ====================
 
@@ -122,12 +122,19 @@ impl From<MatchScoreSummaryRow> for MatchScoreSummary {
 // ==================== Match Result Repository Implementation ====================
 
 pub struct PgMatchResultRepository {
-    pool: DbPool,
+    db: DbHandle,
 }
 
 impl PgMatchResultRepository {
     pub fn new(pool: DbPool) -> Self {
-        Self { pool }
+        Self {
+            db: DbHandle::Pool(pool),
+        }
+    }
+
+    /// Repository bound to a unit of work's connection
+    pub fn with_handle(db: DbHandle) -> Self {
+        Self { db }
     }
 }
 
@@ -162,7 +169,7 @@ impl MatchResultRepository for PgMatchResultRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let row: MatchResultRow = sqlx::query_as_with(&sql, values)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *self.db.conn().await?)
             .await?;
 
         Ok(MatchResult::from(row))
@@ -189,7 +196,7 @@ impl MatchResultRepository for PgMatchResultRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let row: Option<MatchResultRow> = sqlx::query_as_with(&sql, values)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(MatchResult::from))
@@ -226,7 +233,7 @@ impl MatchResultRepository for PgMatchResultRepository {
         let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
 
         let row: Option<MatchResultRow> = sqlx::query_as_with(&sql, values)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(MatchResult::from))
@@ -240,7 +247,7 @@ impl MatchResultRepository for PgMatchResultRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let row: Option<MatchResultRow> = sqlx::query_as_with(&sql, values)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(MatchResult::from))
@@ -270,7 +277,26 @@ impl MatchResultRepository for PgMatchResultRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let rows: Vec<MatchResultRow> = sqlx::query_as_with(&sql, values)
-            .fetch_all(&self.pool)
+            .fetch_all(&mut *self.db.conn().await?)
+            .await?;
+
+        Ok(rows.into_iter().map(MatchResult::from).collect())
+    }
+
+    async fn find_by_category(&self, category_id: Uuid) -> Result<Vec<MatchResult>, AppError> {
+        let sql = r#"
+            SELECT r.id, r.match_id, r.set_number, r.participant1_score, r.participant2_score,
+                   r.period_number, r.period_name, r.scoring_data, r.participant1_stats,
+                   r.participant2_stats, r.created_at, r.updated_at
+            FROM match_results r
+            JOIN matches m ON m.id = r.match_id
+            WHERE m.tournament_category_id = $1
+            ORDER BY r.match_id, r.set_number, r.period_number, r.created_at
+        "#;
+
+        let rows: Vec<MatchResultRow> = sqlx::query_as(sql)
+            .bind(category_id)
+            .fetch_all(&mut *self.db.conn().await?)
             .await?;
 
         Ok(rows.into_iter().map(MatchResult::from).collect())
@@ -294,7 +320,7 @@ impl MatchResultRepository for PgMatchResultRepository {
 
         let row: Option<MatchScoreSummaryRow> = sqlx::query_as(sql)
             .bind(match_id)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(MatchScoreSummary::from))
@@ -328,7 +354,7 @@ impl MatchResultRepository for PgMatchResultRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let rows: Vec<MatchResultRow> = sqlx::query_as_with(&sql, values)
-            .fetch_all(&self.pool)
+            .fetch_all(&mut *self.db.conn().await?)
             .await?;
 
         Ok(rows.into_iter().map(MatchResult::from).collect())
@@ -341,7 +367,7 @@ impl MatchResultRepository for PgMatchResultRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let result = sqlx::query_with(&sql, values)
-            .execute(&self.pool)
+            .execute(&mut *self.db.conn().await?)
             .await?;
 
         Ok(result.rows_affected())
@@ -355,9 +381,29 @@ impl MatchResultRepository for PgMatchResultRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let result: (i64,) = sqlx::query_as_with(&sql, values)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *self.db.conn().await?)
             .await?;
 
         Ok(result.0)
     }
+
+    async fn claim_scoring_sequence(
+        &self,
+        match_id: Uuid,
+        sequence: i64,
+        result_id: Uuid,
+    ) -> Result<bool, AppError> {
+        let sql = r#"
+            INSERT INTO match_scoring_sequences (match_id, sequence, match_result_id)
+            VALUES ($1, $2, $3)
+            ON CONFLICT DO NOTHING
+        "#;
+        let result = sqlx::query(sql)
+            .bind(match_id)
+            .bind(sequence)
+            .bind(result_id)
+            .execute(&mut *self.db.conn().await?)
+            .await?;
+        Ok(result.rows_affected() == 1)
+    }
 }
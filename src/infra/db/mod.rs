@@ -2,34 +2,50 @@
 
 pub mod pool;
 
+pub mod check_in_repo;
+pub mod court_call_repo;
+pub mod import_repo;
 pub mod match_repo;
 pub mod match_result_repo;
 pub mod notification_repo;
+pub mod outbox_repo;
 pub mod payment_repo;
 pub mod player_repo;
+pub mod push_subscription_repo;
 pub mod statistics_repo;
 pub mod team_member_repo;
 pub mod team_repo;
+pub mod ticket_repo;
 pub mod tournament_bracket_repo;
 pub mod tournament_category_repo;
 pub mod tournament_registration_repo;
 pub mod tournament_repo;
 pub mod tournament_standings_repo;
+pub mod unit_of_work;
 pub mod user_repo;
+pub mod venue_repo;
 
 // Re-exports
+pub use check_in_repo::PgCheckInWindowRepository;
+pub use court_call_repo::PgCourtCallRepository;
+pub use import_repo::PgImportRepository;
 pub use match_repo::PgMatchRepository;
 pub use match_result_repo::PgMatchResultRepository;
-pub use notification_repo::PgNotificationRepository;
+pub use notification_repo::{PgNotificationDeliveryRepository, PgNotificationRepository};
+pub use outbox_repo::PgOutboxRepository;
 pub use payment_repo::PgPaymentRepository;
 pub use player_repo::PgPlayerRepository;
 pub use pool::DbConfig;
+pub use push_subscription_repo::PgPushSubscriptionRepository;
 pub use statistics_repo::PgStatisticsRepository;
 pub use team_member_repo::PgTeamMemberRepository;
 pub use team_repo::PgTeamRepository;
+pub use ticket_repo::PgMatchTicketRepository;
 pub use tournament_bracket_repo::PgTournamentBracketRepository;
 pub use tournament_category_repo::PgTournamentCategoryRepository;
 pub use tournament_registration_repo::PgTournamentRegistrationRepository;
 pub use tournament_repo::PgTournamentRepository;
 pub use tournament_standings_repo::PgTournamentStandingsRepository;
+pub use unit_of_work::PgUnitOfWorkFactory;
 pub use user_repo::{PgTokenRepository, PgUserProfileRepository, PgUserRepository};
+pub use venue_repo::PgVenueRepository;
@@ -7,11 +7,12 @@ use std::fmt::Write;
 use uuid::Uuid;
 
 use crate::domain::notification::{
-    NewNotification, Notification, NotificationRepository, NotificationType,
+    DeliveryAttempt, DeliveryChannel, DeliveryStatus, NewNotification, Notification,
+    NotificationDelivery, NotificationDeliveryRepository, NotificationRepository, NotificationType,
 };
 use crate::shared::AppError;
 
-use super::pool::DbPool;
+use super::pool::{DbHandle, DbPool};
 
 // ==================== Sea-Query Iden ====================
 
@@ -60,6 +61,8 @@ fn notification_type_to_db(t: &NotificationType) -> &'static str {
         NotificationType::MatchReminder => "match_reminder",
         NotificationType::ResultPosted => "result_posted",
         NotificationType::RegistrationConfirmed => "registration_confirmed",
+        NotificationType::CourtCall => "court_call",
+        NotificationType::ScheduleDelay => "schedule_delay",
     }
 }
 
@@ -69,6 +72,8 @@ fn notification_type_from_db(s: &str) -> Option<NotificationType> {
         "match_reminder" => Some(NotificationType::MatchReminder),
         "result_posted" => Some(NotificationType::ResultPosted),
         "registration_confirmed" => Some(NotificationType::RegistrationConfirmed),
+        "court_call" => Some(NotificationType::CourtCall),
+        "schedule_delay" => Some(NotificationType::ScheduleDelay),
         _ => None,
     }
 }
@@ -113,12 +118,19 @@ const NOTIFICATION_SELECT: &str = "id, user_id, title, message, notification_typ
 // ==================== Repository ====================
 
 pub struct PgNotificationRepository {
-    pool: DbPool,
+    db: DbHandle,
 }
 
 impl PgNotificationRepository {
     pub fn new(pool: DbPool) -> Self {
-        Self { pool }
+        Self {
+            db: DbHandle::Pool(pool),
+        }
+    }
+
+    /// Repository bound to a unit of work's connection
+    pub fn with_handle(db: DbHandle) -> Self {
+        Self { db }
     }
 }
 
@@ -137,7 +149,7 @@ impl NotificationRepository for PgNotificationRepository {
             .bind(notification_type_to_db(&new_notification.notification_type))
             .bind(new_notification.tournament_id)
             .bind(new_notification.match_id)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *self.db.conn().await?)
             .await?;
         Ok(Notification::from(row))
     }
@@ -149,7 +161,7 @@ impl NotificationRepository for PgNotificationRepository {
         );
         let row: Option<NotificationRow> = sqlx::query_as(&sql)
             .bind(notification_id)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
         Ok(row.map(Notification::from))
     }
@@ -168,7 +180,7 @@ impl NotificationRepository for PgNotificationRepository {
             .bind(user_id)
             .bind(limit)
             .bind(offset)
-            .fetch_all(&self.pool)
+            .fetch_all(&mut *self.db.conn().await?)
             .await?;
         Ok(rows.into_iter().map(Notification::from).collect())
     }
@@ -187,7 +199,7 @@ impl NotificationRepository for PgNotificationRepository {
             .bind(user_id)
             .bind(limit)
             .bind(offset)
-            .fetch_all(&self.pool)
+            .fetch_all(&mut *self.db.conn().await?)
             .await?;
         Ok(rows.into_iter().map(Notification::from).collect())
     }
@@ -201,7 +213,7 @@ impl NotificationRepository for PgNotificationRepository {
         let row: Option<NotificationRow> = sqlx::query_as(sql)
             .bind(notification_id)
             .bind(Utc::now())
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
         Ok(row.map(Notification::from))
     }
@@ -217,7 +229,9 @@ impl NotificationRepository for PgNotificationRepository {
             .and_where(Expr::col(NotificationIden::IsRead).eq(false))
             .build_sqlx(PostgresQueryBuilder);
 
-        let result = sqlx::query_with(&sql, values).execute(&self.pool).await?;
+        let result = sqlx::query_with(&sql, values)
+            .execute(&mut *self.db.conn().await?)
+            .await?;
         Ok(result.rows_affected())
     }
 
@@ -228,7 +242,9 @@ impl NotificationRepository for PgNotificationRepository {
                 .from_table(NotificationIden::Table)
                 .and_where(Expr::col(NotificationIden::Id).eq(notification_id))
                 .build_sqlx(PostgresQueryBuilder);
-            sqlx::query_with(&sql, values).execute(&self.pool).await?;
+            sqlx::query_with(&sql, values)
+                .execute(&mut *self.db.conn().await?)
+                .await?;
         }
         Ok(notification)
     }
@@ -242,7 +258,7 @@ impl NotificationRepository for PgNotificationRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let row: (i64,) = sqlx::query_as_with(&sql, values)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *self.db.conn().await?)
             .await?;
         Ok(row.0)
     }
@@ -270,8 +286,158 @@ impl NotificationRepository for PgNotificationRepository {
             " RETURNING id, user_id, title, message, notification_type::text as notification_type, is_read, tournament_id, match_id, created_at, updated_at",
         );
 
-        let rows: Vec<NotificationRow> =
-            query_builder.build_query_as().fetch_all(&self.pool).await?;
+        let rows: Vec<NotificationRow> = query_builder
+            .build_query_as()
+            .fetch_all(&mut *self.db.conn().await?)
+            .await?;
         Ok(rows.into_iter().map(Notification::from).collect())
     }
+
+    async fn claim_dedup_key(&self, user_id: Uuid, key: &str) -> Result<bool, AppError> {
+        let sql = r#"
+            INSERT INTO notification_dedup_keys (user_id, dedup_key)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+        "#;
+        let result = sqlx::query(sql)
+            .bind(user_id)
+            .bind(key)
+            .execute(&mut *self.db.conn().await?)
+            .await?;
+        Ok(result.rows_affected() == 1)
+    }
+}
+
+// ==================== Delivery Repository ====================
+
+fn delivery_status_to_db(s: DeliveryStatus) -> &'static str {
+    match s {
+        DeliveryStatus::Pending => "pending",
+        DeliveryStatus::Sent => "sent",
+        DeliveryStatus::Failed => "failed",
+        DeliveryStatus::Skipped => "skipped",
+    }
+}
+
+fn delivery_status_from_db(s: &str) -> DeliveryStatus {
+    match s {
+        "sent" => DeliveryStatus::Sent,
+        "failed" => DeliveryStatus::Failed,
+        "skipped" => DeliveryStatus::Skipped,
+        _ => DeliveryStatus::Pending,
+    }
+}
+
+fn delivery_channel_from_db(s: &str) -> DeliveryChannel {
+    match s {
+        "push" => DeliveryChannel::Push,
+        _ => DeliveryChannel::Email,
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct DeliveryRow {
+    id: Uuid,
+    notification_id: Uuid,
+    channel: String,
+    status: String,
+    recipient: Option<String>,
+    attempts: i32,
+    last_error: Option<String>,
+    delivered_at: Option<chrono::DateTime<Utc>>,
+    created_at: chrono::DateTime<Utc>,
+    updated_at: chrono::DateTime<Utc>,
+}
+
+impl From<DeliveryRow> for NotificationDelivery {
+    fn from(row: DeliveryRow) -> Self {
+        NotificationDelivery {
+            id: row.id,
+            notification_id: row.notification_id,
+            channel: delivery_channel_from_db(&row.channel),
+            status: delivery_status_from_db(&row.status),
+            recipient: row.recipient,
+            attempts: row.attempts,
+            last_error: row.last_error,
+            delivered_at: row.delivered_at,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+const DELIVERY_SELECT: &str = "id, notification_id, channel, status, recipient, attempts, last_error, delivered_at, created_at, updated_at";
+
+pub struct PgNotificationDeliveryRepository {
+    db: DbHandle,
+}
+
+impl PgNotificationDeliveryRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self {
+            db: DbHandle::Pool(pool),
+        }
+    }
+}
+
+#[async_trait]
+impl NotificationDeliveryRepository for PgNotificationDeliveryRepository {
+    async fn record(&self, attempt: DeliveryAttempt) -> Result<NotificationDelivery, AppError> {
+        let sql = format!(
+            r#"
+            INSERT INTO notification_deliveries (notification_id, channel, status, recipient, last_error, delivered_at)
+            VALUES ($1, $2, $3, $4, $5, CASE WHEN $3 = 'sent' THEN NOW() END)
+            ON CONFLICT (notification_id, channel) DO UPDATE SET
+                status = EXCLUDED.status,
+                recipient = EXCLUDED.recipient,
+                last_error = EXCLUDED.last_error,
+                delivered_at = COALESCE(EXCLUDED.delivered_at, notification_deliveries.delivered_at),
+                attempts = notification_deliveries.attempts + 1,
+                updated_at = NOW()
+            RETURNING {}
+            "#,
+            DELIVERY_SELECT
+        );
+        let row: DeliveryRow = sqlx::query_as(&sql)
+            .bind(attempt.notification_id)
+            .bind(attempt.channel.key())
+            .bind(delivery_status_to_db(attempt.status))
+            .bind(attempt.recipient)
+            .bind(attempt.error)
+            .fetch_one(&mut *self.db.conn().await?)
+            .await?;
+        Ok(NotificationDelivery::from(row))
+    }
+
+    async fn find(
+        &self,
+        notification_id: Uuid,
+        channel: DeliveryChannel,
+    ) -> Result<Option<NotificationDelivery>, AppError> {
+        let sql = format!(
+            "SELECT {} FROM notification_deliveries WHERE notification_id = $1 AND channel = $2",
+            DELIVERY_SELECT
+        );
+        let row: Option<DeliveryRow> = sqlx::query_as(&sql)
+            .bind(notification_id)
+            .bind(channel.key())
+            .fetch_optional(&mut *self.db.conn().await?)
+            .await?;
+        Ok(row.map(NotificationDelivery::from))
+    }
+
+    async fn find_by_notification(
+        &self,
+        notification_id: Uuid,
+    ) -> Result<Vec<NotificationDelivery>, AppError> {
+        let sql = format!(
+            "SELECT {} FROM notification_deliveries WHERE notification_id = $1 ORDER BY channel",
+            DELIVERY_SELECT
+        );
+        let rows: Vec<DeliveryRow> = sqlx::query_as(&sql)
+            .bind(notification_id)
+            .fetch_all(&mut *self.db.conn().await?)
+            .await?;
+        Ok(rows.into_iter().map(NotificationDelivery::from).collect())
+    }
 }
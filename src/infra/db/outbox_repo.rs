@@ -0,0 +1,170 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::Value as JsonValue;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::domain::outbox::{DomainEvent, OutboxMessage, OutboxRepository, OutboxStatus};
+use crate::shared::AppError;
+
+use super::pool::{DbHandle, DbPool};
+
+// ==================== Enum mapping ====================
+
+fn outbox_status_from_db(s: &str) -> OutboxStatus {
+    match s {
+        "processed" => OutboxStatus::Processed,
+        "failed" => OutboxStatus::Failed,
+        _ => OutboxStatus::Pending,
+    }
+}
+
+// ==================== Row types ====================
+
+#[derive(Debug, FromRow)]
+struct OutboxRow {
+    id: Uuid,
+    event_type: String,
+    payload: JsonValue,
+    status: String,
+    attempts: i32,
+    delivered_to: Vec<String>,
+    last_error: Option<String>,
+    available_at: DateTime<Utc>,
+    created_at: DateTime<Utc>,
+    processed_at: Option<DateTime<Utc>>,
+}
+
+impl From<OutboxRow> for OutboxMessage {
+    fn from(row: OutboxRow) -> Self {
+        OutboxMessage {
+            id: row.id,
+            event_type: row.event_type,
+            payload: row.payload,
+            status: outbox_status_from_db(&row.status),
+            attempts: row.attempts,
+            delivered_to: row.delivered_to,
+            last_error: row.last_error,
+            available_at: row.available_at,
+            created_at: row.created_at,
+            processed_at: row.processed_at,
+        }
+    }
+}
+
+const OUTBOX_COLUMNS: &str = "id, event_type, payload, status, attempts, delivered_to, last_error, available_at, created_at, processed_at";
+
+// ==================== Repository ====================
+
+pub struct PgOutboxRepository {
+    db: DbHandle,
+}
+
+impl PgOutboxRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self {
+            db: DbHandle::Pool(pool),
+        }
+    }
+
+    /// Repository bound to a unit of work's connection
+    pub fn with_handle(db: DbHandle) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl OutboxRepository for PgOutboxRepository {
+    async fn enqueue(&self, event: &DomainEvent) -> Result<OutboxMessage, AppError> {
+        let payload = serde_json::to_value(event)
+            .map_err(|e| AppError::InternalError(format!("Unserializable event: {}", e)))?;
+        let sql = format!(
+            "INSERT INTO outbox_events (event_type, payload) VALUES ($1, $2) RETURNING {}",
+            OUTBOX_COLUMNS
+        );
+        let row: OutboxRow = sqlx::query_as(&sql)
+            .bind(event.event_type())
+            .bind(payload)
+            .fetch_one(&mut *self.db.conn().await?)
+            .await?;
+        Ok(OutboxMessage::from(row))
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<OutboxMessage>, AppError> {
+        let sql = format!("SELECT {} FROM outbox_events WHERE id = $1", OUTBOX_COLUMNS);
+        let row: Option<OutboxRow> = sqlx::query_as(&sql)
+            .bind(id)
+            .fetch_optional(&mut *self.db.conn().await?)
+            .await?;
+        Ok(row.map(OutboxMessage::from))
+    }
+
+    async fn claim_due(
+        &self,
+        limit: i64,
+        lease_until: DateTime<Utc>,
+    ) -> Result<Vec<OutboxMessage>, AppError> {
+        // SKIP LOCKED lets several dispatchers claim disjoint batches
+        let sql = format!(
+            r#"
+            UPDATE outbox_events SET attempts = attempts + 1, available_at = $2
+            WHERE id IN (
+                SELECT id FROM outbox_events
+                WHERE status = 'pending' AND available_at <= NOW()
+                ORDER BY created_at
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING {}
+            "#,
+            OUTBOX_COLUMNS
+        );
+        let rows: Vec<OutboxRow> = sqlx::query_as(&sql)
+            .bind(limit)
+            .bind(lease_until)
+            .fetch_all(&mut *self.db.conn().await?)
+            .await?;
+        let mut messages: Vec<OutboxMessage> = rows.into_iter().map(OutboxMessage::from).collect();
+        messages.sort_by_key(|m| m.created_at);
+        Ok(messages)
+    }
+
+    async fn mark_processed(&self, id: Uuid, delivered_to: &[String]) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            UPDATE outbox_events
+            SET status = 'processed', delivered_to = $2, last_error = NULL, processed_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(delivered_to)
+        .execute(&mut *self.db.conn().await?)
+        .await?;
+        Ok(())
+    }
+
+    async fn mark_failed(
+        &self,
+        id: Uuid,
+        delivered_to: &[String],
+        error: &str,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            UPDATE outbox_events
+            SET status = CASE WHEN $4::timestamptz IS NULL THEN 'failed' ELSE 'pending' END,
+                delivered_to = $2, last_error = $3, available_at = COALESCE($4, available_at)
+            WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .bind(delivered_to)
+        .bind(error)
+        .bind(retry_at)
+        .execute(&mut *self.db.conn().await?)
+        .await?;
+        Ok(())
+    }
+}
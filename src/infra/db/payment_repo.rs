@@ -12,7 +12,7 @@ use crate::domain::payment::{
 };
 use crate::shared::AppError;
 
-use super::pool::DbPool;
+use super::pool::{DbHandle, DbPool};
 
 // ==================== Sea-Query Iden ====================
 
@@ -190,12 +190,19 @@ const PAYMENT_SELECT: &str = "id, user_id, tournament_id, amount, currency, paym
 // ==================== Repository ====================
 
 pub struct PgPaymentRepository {
-    pool: DbPool,
+    db: DbHandle,
 }
 
 impl PgPaymentRepository {
     pub fn new(pool: DbPool) -> Self {
-        Self { pool }
+        Self {
+            db: DbHandle::Pool(pool),
+        }
+    }
+
+    /// Repository bound to a unit of work's connection
+    pub fn with_handle(db: DbHandle) -> Self {
+        Self { db }
     }
 }
 
@@ -217,7 +224,7 @@ impl PaymentRepository for PgPaymentRepository {
             .bind(new_payment.transaction_id)
             .bind(new_payment.payment_provider)
             .bind(new_payment.metadata)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *self.db.conn().await?)
             .await?;
         Ok(Payment::from(row))
     }
@@ -226,7 +233,7 @@ impl PaymentRepository for PgPaymentRepository {
         let sql = format!("SELECT {} FROM payments WHERE id = $1", PAYMENT_SELECT);
         let row: Option<PaymentRow> = sqlx::query_as(&sql)
             .bind(payment_id)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
         Ok(row.map(Payment::from))
     }
@@ -245,7 +252,7 @@ impl PaymentRepository for PgPaymentRepository {
             .bind(user_id)
             .bind(limit)
             .bind(offset)
-            .fetch_all(&self.pool)
+            .fetch_all(&mut *self.db.conn().await?)
             .await?;
         Ok(rows.into_iter().map(Payment::from).collect())
     }
@@ -264,7 +271,7 @@ impl PaymentRepository for PgPaymentRepository {
             .bind(tournament_id)
             .bind(limit)
             .bind(offset)
-            .fetch_all(&self.pool)
+            .fetch_all(&mut *self.db.conn().await?)
             .await?;
         Ok(rows.into_iter().map(Payment::from).collect())
     }
@@ -284,7 +291,7 @@ impl PaymentRepository for PgPaymentRepository {
             .bind(payment_id)
             .bind(payment_status_to_db(&status))
             .bind(Utc::now())
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
         Ok(row.map(Payment::from))
     }
@@ -300,7 +307,7 @@ impl PaymentRepository for PgPaymentRepository {
             .bind(payment_id)
             .bind(amount)
             .bind(Utc::now())
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
         Ok(row.map(Payment::from))
     }
@@ -326,7 +333,7 @@ impl PaymentRepository for PgPaymentRepository {
             .bind(payment_id)
             .bind(amount)
             .bind(Utc::now())
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
         Ok(row.map(Payment::from))
     }
@@ -348,7 +355,7 @@ impl PaymentRepository for PgPaymentRepository {
         "#;
         let row: PaymentSummaryRow = sqlx::query_as(sql)
             .bind(tournament_id)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *self.db.conn().await?)
             .await?;
         Ok(PaymentSummary::from(row))
     }
@@ -367,7 +374,7 @@ impl PaymentRepository for PgPaymentRepository {
         "#;
         let row: PaymentSummaryRow = sqlx::query_as(sql)
             .bind(user_id)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *self.db.conn().await?)
             .await?;
         Ok(PaymentSummary::from(row))
     }
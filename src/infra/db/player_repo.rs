@@ -43,7 +43,7 @@ impl Iden for PlayerIden {
 // ==================== Row Types ====================
 
 #[derive(Debug, FromRow)]
-struct PlayerRow {
+pub(crate) struct PlayerRow {
     id: Uuid,
     name: String,
     user_id: Option<Uuid>,
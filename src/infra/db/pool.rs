@@ -1,10 +1,19 @@
-use crate::shared::EnvConfig;
+use crate::shared::{AppError, EnvConfig};
+use futures_util::future::BoxFuture;
+use sqlx::pool::PoolConnection;
 use sqlx::postgres::PgPoolOptions;
-use sqlx::{Pool, Postgres};
+use sqlx::{PgConnection, Pool, Postgres, Transaction};
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::{Mutex, MutexGuard};
 
 pub type DbPool = Pool<Postgres>;
 
+/// An open transaction shared by every repository of one unit of work.
+/// `None` once it has been committed or rolled back.
+pub type SharedTransaction = Arc<Mutex<Option<Transaction<'static, Postgres>>>>;
+
 pub struct DbConfig;
 
 impl DbConfig {
@@ -22,12 +31,72 @@ impl DbConfig {
     }
 }
 
-/// Helper function to run operations within a transaction
-pub async fn with_transaction<F, T, Fut>(pool: &DbPool, f: F) -> Result<T, sqlx::Error>
+/// Where a repository sends its queries: the pool, or a transaction owned by a unit of work
+#[derive(Clone)]
+pub enum DbHandle {
+    Pool(DbPool),
+    Transaction(SharedTransaction),
+}
+
+impl DbHandle {
+    /// Connection for a single statement. Pool connections are returned on drop; the
+    /// transaction lock is held only while the statement runs.
+    pub async fn conn(&self) -> Result<DbConn<'_>, AppError> {
+        match self {
+            DbHandle::Pool(pool) => Ok(DbConn::Pooled(pool.acquire().await?)),
+            DbHandle::Transaction(tx) => {
+                let guard = tx.lock().await;
+                if guard.is_none() {
+                    return Err(AppError::InternalError(
+                        "Transaction has already been committed or rolled back".into(),
+                    ));
+                }
+                Ok(DbConn::Transaction(guard))
+            }
+        }
+    }
+}
+
+pub enum DbConn<'a> {
+    Pooled(PoolConnection<Postgres>),
+    Transaction(MutexGuard<'a, Option<Transaction<'static, Postgres>>>),
+}
+
+impl Deref for DbConn<'_> {
+    type Target = PgConnection;
+
+    fn deref(&self) -> &PgConnection {
+        match self {
+            DbConn::Pooled(conn) => conn,
+            // `DbHandle::conn` only hands out guards over an open transaction
+            DbConn::Transaction(guard) => guard.as_ref().expect("open transaction"),
+        }
+    }
+}
+
+impl DerefMut for DbConn<'_> {
+    fn deref_mut(&mut self) -> &mut PgConnection {
+        match self {
+            DbConn::Pooled(conn) => conn,
+            DbConn::Transaction(guard) => guard.as_mut().expect("open transaction"),
+        }
+    }
+}
+
+/// Runs `f` inside a transaction: commits when it returns `Ok`, rolls back otherwise.
+pub async fn with_transaction<F, T>(pool: &DbPool, f: F) -> Result<T, sqlx::Error>
 where
-    F: FnOnce(&mut sqlx::PgConnection) -> Fut,
-    Fut: std::future::Future<Output = Result<T, sqlx::Error>>,
+    F: for<'c> FnOnce(&'c mut PgConnection) -> BoxFuture<'c, Result<T, sqlx::Error>>,
 {
-    let mut conn = pool.acquire().await?;
-    f(&mut conn).await
+    let mut tx = pool.begin().await?;
+    match f(&mut tx).await {
+        Ok(value) => {
+            tx.commit().await?;
+            Ok(value)
+        }
+        Err(e) => {
+            tx.rollback().await?;
+            Err(e)
+        }
+    }
 }
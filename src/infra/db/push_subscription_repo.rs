@@ -0,0 +1,115 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::domain::notification::{
+    NewPushSubscription, PushSubscription, PushSubscriptionRepository,
+};
+use crate::shared::AppError;
+
+use super::pool::{DbHandle, DbPool};
+
+#[derive(Debug, FromRow)]
+struct PushSubscriptionRow {
+    id: Uuid,
+    user_id: Uuid,
+    endpoint: String,
+    p256dh: String,
+    auth: String,
+    user_agent: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<PushSubscriptionRow> for PushSubscription {
+    fn from(row: PushSubscriptionRow) -> Self {
+        PushSubscription {
+            id: row.id,
+            user_id: row.user_id,
+            endpoint: row.endpoint,
+            p256dh: row.p256dh,
+            auth: row.auth,
+            user_agent: row.user_agent,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+const PUSH_SUBSCRIPTION_SELECT: &str =
+    "id, user_id, endpoint, p256dh, auth, user_agent, created_at, updated_at";
+
+pub struct PgPushSubscriptionRepository {
+    db: DbHandle,
+}
+
+impl PgPushSubscriptionRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self {
+            db: DbHandle::Pool(pool),
+        }
+    }
+}
+
+#[async_trait]
+impl PushSubscriptionRepository for PgPushSubscriptionRepository {
+    async fn upsert(
+        &self,
+        subscription: NewPushSubscription,
+    ) -> Result<PushSubscription, AppError> {
+        let sql = format!(
+            r#"
+            INSERT INTO push_subscriptions (user_id, endpoint, p256dh, auth, user_agent)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (endpoint) DO UPDATE SET
+                user_id = EXCLUDED.user_id,
+                p256dh = EXCLUDED.p256dh,
+                auth = EXCLUDED.auth,
+                user_agent = EXCLUDED.user_agent,
+                updated_at = NOW()
+            RETURNING {}
+            "#,
+            PUSH_SUBSCRIPTION_SELECT
+        );
+        let row: PushSubscriptionRow = sqlx::query_as(&sql)
+            .bind(subscription.user_id)
+            .bind(subscription.endpoint)
+            .bind(subscription.p256dh)
+            .bind(subscription.auth)
+            .bind(subscription.user_agent)
+            .fetch_one(&mut *self.db.conn().await?)
+            .await?;
+        Ok(PushSubscription::from(row))
+    }
+
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<PushSubscription>, AppError> {
+        let sql = format!(
+            "SELECT {} FROM push_subscriptions WHERE user_id = $1 ORDER BY created_at",
+            PUSH_SUBSCRIPTION_SELECT
+        );
+        let rows: Vec<PushSubscriptionRow> = sqlx::query_as(&sql)
+            .bind(user_id)
+            .fetch_all(&mut *self.db.conn().await?)
+            .await?;
+        Ok(rows.into_iter().map(PushSubscription::from).collect())
+    }
+
+    async fn delete_for_user(&self, user_id: Uuid, endpoint: &str) -> Result<bool, AppError> {
+        let result =
+            sqlx::query("DELETE FROM push_subscriptions WHERE user_id = $1 AND endpoint = $2")
+                .bind(user_id)
+                .bind(endpoint)
+                .execute(&mut *self.db.conn().await?)
+                .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn delete_by_endpoint(&self, endpoint: &str) -> Result<bool, AppError> {
+        let result = sqlx::query("DELETE FROM push_subscriptions WHERE endpoint = $1")
+            .bind(endpoint)
+            .execute(&mut *self.db.conn().await?)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
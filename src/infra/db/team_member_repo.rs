@@ -45,7 +45,7 @@ impl Iden for TeamMemberIden {
 // ==================== Row Types ====================
 
 #[derive(Debug, FromRow)]
-struct TeamMemberRow {
+pub(crate) struct TeamMemberRow {
     team_id: Uuid,
     player_id: Uuid,
     is_captain: bool,
@@ -41,7 +41,7 @@ impl Iden for TeamIden {
 // ==================== Row Types ====================
 
 #[derive(Debug, FromRow)]
-struct TeamRow {
+pub(crate) struct TeamRow {
     id: Uuid,
     name: String,
     created_at: chrono::DateTime<Utc>,
@@ -59,7 +59,7 @@ impl From<TeamRow> for Team {
 
 /// Row from join of team_members + players for find_by_id members
 #[derive(Debug, FromRow)]
-struct TeamPlayerRow {
+pub(crate) struct TeamPlayerRow {
     id: Uuid,
     name: String,
     user_id: Option<Uuid>,
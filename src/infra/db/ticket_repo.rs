@@ -0,0 +1,106 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::domain::ticket::{MatchTicket, MatchTicketRepository};
+use crate::shared::AppError;
+
+use super::pool::{DbHandle, DbPool};
+
+#[derive(Debug, FromRow)]
+struct MatchTicketRow {
+    id: Uuid,
+    match_id: Uuid,
+    holder_id: Uuid,
+    issued_at: DateTime<Utc>,
+    admitted_at: Option<DateTime<Utc>>,
+    admitted_by: Option<Uuid>,
+}
+
+impl From<MatchTicketRow> for MatchTicket {
+    fn from(row: MatchTicketRow) -> Self {
+        MatchTicket {
+            id: row.id,
+            match_id: row.match_id,
+            holder_id: row.holder_id,
+            issued_at: row.issued_at,
+            admitted_at: row.admitted_at,
+            admitted_by: row.admitted_by,
+        }
+    }
+}
+
+const TICKET_SELECT: &str = "id, match_id, holder_id, issued_at, admitted_at, admitted_by";
+
+pub struct PgMatchTicketRepository {
+    db: DbHandle,
+}
+
+impl PgMatchTicketRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self {
+            db: DbHandle::Pool(pool),
+        }
+    }
+}
+
+#[async_trait]
+impl MatchTicketRepository for PgMatchTicketRepository {
+    async fn create(&self, ticket: MatchTicket) -> Result<MatchTicket, AppError> {
+        let sql = format!(
+            "INSERT INTO match_tickets (id, match_id, holder_id, issued_at) \
+             VALUES ($1, $2, $3, $4) RETURNING {}",
+            TICKET_SELECT
+        );
+        let row: MatchTicketRow = sqlx::query_as(&sql)
+            .bind(ticket.id)
+            .bind(ticket.match_id)
+            .bind(ticket.holder_id)
+            .bind(ticket.issued_at)
+            .fetch_one(&mut *self.db.conn().await?)
+            .await?;
+        Ok(MatchTicket::from(row))
+    }
+
+    async fn get_by_id(&self, ticket_id: Uuid) -> Result<Option<MatchTicket>, AppError> {
+        let sql = format!("SELECT {} FROM match_tickets WHERE id = $1", TICKET_SELECT);
+        let row: Option<MatchTicketRow> = sqlx::query_as(&sql)
+            .bind(ticket_id)
+            .fetch_optional(&mut *self.db.conn().await?)
+            .await?;
+        Ok(row.map(MatchTicket::from))
+    }
+
+    async fn get_by_holder(&self, holder_id: Uuid) -> Result<Vec<MatchTicket>, AppError> {
+        let sql = format!(
+            "SELECT {} FROM match_tickets WHERE holder_id = $1 ORDER BY issued_at DESC",
+            TICKET_SELECT
+        );
+        let rows: Vec<MatchTicketRow> = sqlx::query_as(&sql)
+            .bind(holder_id)
+            .fetch_all(&mut *self.db.conn().await?)
+            .await?;
+        Ok(rows.into_iter().map(MatchTicket::from).collect())
+    }
+
+    async fn admit(
+        &self,
+        ticket_id: Uuid,
+        admitted_by: Uuid,
+        at: DateTime<Utc>,
+    ) -> Result<Option<MatchTicket>, AppError> {
+        let sql = format!(
+            "UPDATE match_tickets SET admitted_at = $3, admitted_by = $2 \
+             WHERE id = $1 AND admitted_at IS NULL RETURNING {}",
+            TICKET_SELECT
+        );
+        let row: Option<MatchTicketRow> = sqlx::query_as(&sql)
+            .bind(ticket_id)
+            .bind(admitted_by)
+            .bind(at)
+            .fetch_optional(&mut *self.db.conn().await?)
+            .await?;
+        Ok(row.map(MatchTicket::from))
+    }
+}
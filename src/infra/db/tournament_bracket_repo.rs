@@ -13,7 +13,7 @@ use crate::domain::tournament::{
 };
 use crate::shared::AppError;
 
-use super::pool::DbPool;
+use super::pool::{DbHandle, DbPool};
 use super::tournament_repo::{bracket_status_to_string, bracket_type_to_string, BracketStatusDb, BracketTypeDb};
 
 // ==================== Sea-Query Iden ====================
@@ -95,12 +95,19 @@ impl From<TournamentBracketRow> for TournamentBracket {
 // ==================== Repository ====================
 
 pub struct PgTournamentBracketRepository {
-    pool: DbPool,
+    db: DbHandle,
 }
 
 impl PgTournamentBracketRepository {
     pub fn new(pool: DbPool) -> Self {
-        Self { pool }
+        Self {
+            db: DbHandle::Pool(pool),
+        }
+    }
+
+    /// Repository bound to a unit of work's connection
+    pub fn with_handle(db: DbHandle) -> Self {
+        Self { db }
     }
 }
 
@@ -132,7 +139,7 @@ impl TournamentBracketRepository for PgTournamentBracketRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let row: TournamentBracketRow = sqlx::query_as_with(&sql, values)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *self.db.conn().await?)
             .await?;
 
         Ok(TournamentBracket::from(row))
@@ -162,7 +169,7 @@ impl TournamentBracketRepository for PgTournamentBracketRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let rows: Vec<TournamentBracketRow> = sqlx::query_as_with(&sql, values)
-            .fetch_all(&self.pool)
+            .fetch_all(&mut *self.db.conn().await?)
             .await?;
 
         Ok(rows.into_iter().map(TournamentBracket::from).collect())
@@ -191,7 +198,7 @@ impl TournamentBracketRepository for PgTournamentBracketRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let row: Option<TournamentBracketRow> = sqlx::query_as_with(&sql, values)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(TournamentBracket::from))
@@ -217,7 +224,7 @@ impl TournamentBracketRepository for PgTournamentBracketRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let row: Option<TournamentBracketRow> = sqlx::query_as_with(&sql, values)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(TournamentBracket::from))
@@ -254,7 +261,7 @@ impl TournamentBracketRepository for PgTournamentBracketRepository {
         let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
 
         let row: Option<TournamentBracketRow> = sqlx::query_as_with(&sql, values)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(TournamentBracket::from))
@@ -277,7 +284,7 @@ impl TournamentBracketRepository for PgTournamentBracketRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let row: Option<TournamentBracketRow> = sqlx::query_as_with(&sql, values)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(TournamentBracket::from))
@@ -291,7 +298,7 @@ impl TournamentBracketRepository for PgTournamentBracketRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let row: Option<TournamentBracketRow> = sqlx::query_as_with(&sql, values)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(TournamentBracket::from))
@@ -305,7 +312,7 @@ impl TournamentBracketRepository for PgTournamentBracketRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let count: (i64,) = sqlx::query_as_with(&sql, values)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *self.db.conn().await?)
             .await?;
 
         Ok(count.0 > 0)
@@ -14,7 +14,7 @@ use crate::domain::tournament::{
 };
 use crate::shared::AppError;
 
-use super::pool::DbPool;
+use super::pool::{DbHandle, DbPool};
 use super::tournament_repo::{team_composition_to_string, TeamCompositionDb};
 
 // ==================== Sea-Query Iden ====================
@@ -104,12 +104,19 @@ impl From<TournamentCategoryRow> for TournamentCategory {
 // ==================== Repository ====================
 
 pub struct PgTournamentCategoryRepository {
-    pool: DbPool,
+    db: DbHandle,
 }
 
 impl PgTournamentCategoryRepository {
     pub fn new(pool: DbPool) -> Self {
-        Self { pool }
+        Self {
+            db: DbHandle::Pool(pool),
+        }
+    }
+
+    /// Repository bound to a unit of work's connection
+    pub fn with_handle(db: DbHandle) -> Self {
+        Self { db }
     }
 }
 
@@ -149,7 +156,7 @@ impl TournamentCategoryRepository for PgTournamentCategoryRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let row: TournamentCategoryRow = sqlx::query_as_with(&sql, values)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *self.db.conn().await?)
             .await?;
 
         Ok(TournamentCategory::from(row))
@@ -181,7 +188,7 @@ impl TournamentCategoryRepository for PgTournamentCategoryRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let rows: Vec<TournamentCategoryRow> = sqlx::query_as_with(&sql, values)
-            .fetch_all(&self.pool)
+            .fetch_all(&mut *self.db.conn().await?)
             .await?;
 
         Ok(rows.into_iter().map(TournamentCategory::from).collect())
@@ -209,7 +216,7 @@ impl TournamentCategoryRepository for PgTournamentCategoryRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let row: Option<TournamentCategoryRow> = sqlx::query_as_with(&sql, values)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(TournamentCategory::from))
@@ -261,7 +268,7 @@ impl TournamentCategoryRepository for PgTournamentCategoryRepository {
         let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
 
         let row: Option<TournamentCategoryRow> = sqlx::query_as_with(&sql, values)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(TournamentCategory::from))
@@ -275,7 +282,7 @@ impl TournamentCategoryRepository for PgTournamentCategoryRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let row: Option<TournamentCategoryRow> = sqlx::query_as_with(&sql, values)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(TournamentCategory::from))
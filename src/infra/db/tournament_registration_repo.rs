@@ -8,13 +8,14 @@ use sqlx::FromRow;
 use std::fmt::Write;
 use uuid::Uuid;
 
+use crate::domain::check_in::{CheckIn, CheckInMethod};
 use crate::domain::tournament::{
     EditableTournamentRegistration, NewTournamentRegistration, PaymentStatus, RegistrationStatus,
     RegistrationWithDetails, TournamentRegistration, TournamentRegistrationRepository,
 };
 use crate::shared::AppError;
 
-use super::pool::DbPool;
+use super::pool::{DbHandle, DbPool};
 use super::tournament_repo::{
     payment_status_to_string, registration_status_to_string, PaymentStatusDb, RegistrationStatusDb,
 };
@@ -37,6 +38,9 @@ pub enum TournamentRegistrationIden {
     PaymentReference,
     Notes,
     Metadata,
+    CheckedInAt,
+    CheckInMethod,
+    CheckedInBy,
     CreatedAt,
     UpdatedAt,
 }
@@ -62,6 +66,9 @@ impl Iden for TournamentRegistrationIden {
                 TournamentRegistrationIden::PaymentReference => "payment_reference",
                 TournamentRegistrationIden::Notes => "notes",
                 TournamentRegistrationIden::Metadata => "metadata",
+                TournamentRegistrationIden::CheckedInAt => "checked_in_at",
+                TournamentRegistrationIden::CheckInMethod => "check_in_method",
+                TournamentRegistrationIden::CheckedInBy => "checked_in_by",
                 TournamentRegistrationIden::CreatedAt => "created_at",
                 TournamentRegistrationIden::UpdatedAt => "updated_at",
             }
@@ -73,7 +80,7 @@ impl Iden for TournamentRegistrationIden {
 // ==================== Row Types ====================
 
 #[derive(Debug, FromRow)]
-struct TournamentRegistrationRow {
+pub(crate) struct TournamentRegistrationRow {
     id: Uuid,
     tournament_category_id: Uuid,
     team_id: Option<Uuid>,
@@ -88,6 +95,9 @@ struct TournamentRegistrationRow {
     payment_reference: Option<String>,
     notes: Option<String>,
     metadata: Option<JsonValue>,
+    checked_in_at: Option<chrono::DateTime<Utc>>,
+    check_in_method: Option<String>,
+    checked_in_by: Option<Uuid>,
     created_at: chrono::DateTime<Utc>,
     updated_at: chrono::DateTime<Utc>,
 }
@@ -109,6 +119,12 @@ impl From<TournamentRegistrationRow> for TournamentRegistration {
             payment_reference: row.payment_reference,
             notes: row.notes,
             metadata: row.metadata,
+            checked_in_at: row.checked_in_at,
+            check_in_method: row
+                .check_in_method
+                .as_deref()
+                .and_then(CheckInMethod::from_key),
+            checked_in_by: row.checked_in_by,
             created_at: row.created_at,
             updated_at: row.updated_at,
         }
@@ -149,12 +165,18 @@ impl From<RegistrationWithDetailsRow> for RegistrationWithDetails {
 // ==================== Repository ====================
 
 pub struct PgTournamentRegistrationRepository {
-    pool: DbPool,
+    db: DbHandle,
 }
 
 impl PgTournamentRegistrationRepository {
     pub fn new(pool: DbPool) -> Self {
-        Self { pool }
+        Self {
+            db: DbHandle::Pool(pool),
+        }
+    }
+    /// Repository bound to a unit of work's connection
+    pub fn with_handle(db: DbHandle) -> Self {
+        Self { db }
     }
 }
 
@@ -186,7 +208,7 @@ impl TournamentRegistrationRepository for PgTournamentRegistrationRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let row: TournamentRegistrationRow = sqlx::query_as_with(&sql, values)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *self.db.conn().await?)
             .await?;
 
         Ok(TournamentRegistration::from(row))
@@ -212,6 +234,9 @@ impl TournamentRegistrationRepository for PgTournamentRegistrationRepository {
                 TournamentRegistrationIden::PaymentReference,
                 TournamentRegistrationIden::Notes,
                 TournamentRegistrationIden::Metadata,
+                TournamentRegistrationIden::CheckedInAt,
+                TournamentRegistrationIden::CheckInMethod,
+                TournamentRegistrationIden::CheckedInBy,
                 TournamentRegistrationIden::CreatedAt,
                 TournamentRegistrationIden::UpdatedAt,
             ])
@@ -220,7 +245,7 @@ impl TournamentRegistrationRepository for PgTournamentRegistrationRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let row: Option<TournamentRegistrationRow> = sqlx::query_as_with(&sql, values)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(TournamentRegistration::from))
@@ -246,6 +271,9 @@ impl TournamentRegistrationRepository for PgTournamentRegistrationRepository {
                 TournamentRegistrationIden::PaymentReference,
                 TournamentRegistrationIden::Notes,
                 TournamentRegistrationIden::Metadata,
+                TournamentRegistrationIden::CheckedInAt,
+                TournamentRegistrationIden::CheckInMethod,
+                TournamentRegistrationIden::CheckedInBy,
                 TournamentRegistrationIden::CreatedAt,
                 TournamentRegistrationIden::UpdatedAt,
             ])
@@ -258,7 +286,7 @@ impl TournamentRegistrationRepository for PgTournamentRegistrationRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let rows: Vec<TournamentRegistrationRow> = sqlx::query_as_with(&sql, values)
-            .fetch_all(&self.pool)
+            .fetch_all(&mut *self.db.conn().await?)
             .await?;
 
         Ok(rows.into_iter().map(TournamentRegistration::from).collect())
@@ -292,7 +320,7 @@ impl TournamentRegistrationRepository for PgTournamentRegistrationRepository {
 
         let rows: Vec<RegistrationWithDetailsRow> = sqlx::query_as(sql)
             .bind(tournament_id)
-            .fetch_all(&self.pool)
+            .fetch_all(&mut *self.db.conn().await?)
             .await?;
 
         Ok(rows
@@ -329,7 +357,7 @@ impl TournamentRegistrationRepository for PgTournamentRegistrationRepository {
 
         let rows: Vec<RegistrationWithDetailsRow> = sqlx::query_as(sql)
             .bind(player_id)
-            .fetch_all(&self.pool)
+            .fetch_all(&mut *self.db.conn().await?)
             .await?;
 
         Ok(rows
@@ -363,7 +391,7 @@ impl TournamentRegistrationRepository for PgTournamentRegistrationRepository {
 
         let rows: Vec<RegistrationWithDetailsRow> = sqlx::query_as(sql)
             .bind(team_id)
-            .fetch_all(&self.pool)
+            .fetch_all(&mut *self.db.conn().await?)
             .await?;
 
         Ok(rows
@@ -421,7 +449,7 @@ impl TournamentRegistrationRepository for PgTournamentRegistrationRepository {
         let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
 
         let row: Option<TournamentRegistrationRow> = sqlx::query_as_with(&sql, values)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(TournamentRegistration::from))
@@ -438,7 +466,33 @@ impl TournamentRegistrationRepository for PgTournamentRegistrationRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let row: Option<TournamentRegistrationRow> = sqlx::query_as_with(&sql, values)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
+            .await?;
+
+        Ok(row.map(TournamentRegistration::from))
+    }
+
+    async fn record_check_in(
+        &self,
+        registration_id: Uuid,
+        check_in: Option<CheckIn>,
+    ) -> Result<Option<TournamentRegistration>, AppError> {
+        let (at, method, by) = match check_in {
+            Some(c) => (Some(c.at), Some(c.method.key()), c.by),
+            None => (None, None, None),
+        };
+        let (sql, values) = Query::update()
+            .table(TournamentRegistrationIden::Table)
+            .value(TournamentRegistrationIden::CheckedInAt, at)
+            .value(TournamentRegistrationIden::CheckInMethod, method)
+            .value(TournamentRegistrationIden::CheckedInBy, by)
+            .value(TournamentRegistrationIden::UpdatedAt, Utc::now())
+            .and_where(Expr::col(TournamentRegistrationIden::Id).eq(registration_id))
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        let row: Option<TournamentRegistrationRow> = sqlx::query_as_with(&sql, values)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(TournamentRegistration::from))
@@ -13,9 +13,10 @@ use crate::domain::tournament::{
     RegistrationStatus, SportType, TeamComposition, Tournament, TournamentFormat,
     TournamentRepository, TournamentSearchQuery, TournamentStatus, TournamentStats,
 };
+use crate::shared::timezone::DEFAULT_TIMEZONE;
 use crate::shared::AppError;
 
-use super::pool::DbPool;
+use super::pool::{DbHandle, DbPool};
 
 // ==================== Sea-Query Iden Definitions ====================
 // These are shared across tournament-related repositories
@@ -38,6 +39,7 @@ pub enum TournamentIden {
     PrizePool,
     Rules,
     OrganizerId,
+    Timezone,
     CreatedAt,
     UpdatedAt,
 }
@@ -65,6 +67,7 @@ impl Iden for TournamentIden {
                 TournamentIden::PrizePool => "prize_pool",
                 TournamentIden::Rules => "rules",
                 TournamentIden::OrganizerId => "organizer_id",
+                TournamentIden::Timezone => "timezone",
                 TournamentIden::CreatedAt => "created_at",
                 TournamentIden::UpdatedAt => "updated_at",
             }
@@ -417,6 +420,7 @@ struct TournamentRow {
     prize_pool: Option<Decimal>,
     rules: Option<JsonValue>,
     organizer_id: Uuid,
+    timezone: String,
     created_at: chrono::DateTime<Utc>,
     updated_at: chrono::DateTime<Utc>,
 }
@@ -440,6 +444,7 @@ impl From<TournamentRow> for Tournament {
             prize_pool: row.prize_pool,
             rules: row.rules,
             organizer_id: row.organizer_id,
+            timezone: row.timezone,
             created_at: row.created_at,
             updated_at: row.updated_at,
         }
@@ -449,12 +454,19 @@ impl From<TournamentRow> for Tournament {
 // ==================== Tournament Repository ====================
 
 pub struct PgTournamentRepository {
-    pool: DbPool,
+    db: DbHandle,
 }
 
 impl PgTournamentRepository {
     pub fn new(pool: DbPool) -> Self {
-        Self { pool }
+        Self {
+            db: DbHandle::Pool(pool),
+        }
+    }
+
+    /// Repository bound to a unit of work's connection
+    pub fn with_handle(db: DbHandle) -> Self {
+        Self { db }
     }
 }
 
@@ -478,6 +490,7 @@ impl TournamentRepository for PgTournamentRepository {
                 TournamentIden::PrizePool,
                 TournamentIden::Rules,
                 TournamentIden::OrganizerId,
+                TournamentIden::Timezone,
             ])
             .values_panic([
                 new_tournament.name.into(),
@@ -494,12 +507,16 @@ impl TournamentRepository for PgTournamentRepository {
                 new_tournament.prize_pool.into(),
                 new_tournament.rules.into(),
                 new_tournament.organizer_id.into(),
+                new_tournament
+                    .timezone
+                    .unwrap_or_else(|| DEFAULT_TIMEZONE.to_string())
+                    .into(),
             ])
             .returning_all()
             .build_sqlx(PostgresQueryBuilder);
 
         let row: TournamentRow = sqlx::query_as_with(&sql, values)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *self.db.conn().await?)
             .await?;
 
         Ok(Tournament::from(row))
@@ -524,6 +541,7 @@ impl TournamentRepository for PgTournamentRepository {
                 TournamentIden::PrizePool,
                 TournamentIden::Rules,
                 TournamentIden::OrganizerId,
+                TournamentIden::Timezone,
                 TournamentIden::CreatedAt,
                 TournamentIden::UpdatedAt,
             ])
@@ -532,7 +550,7 @@ impl TournamentRepository for PgTournamentRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let rows: Vec<TournamentRow> = sqlx::query_as_with(&sql, values)
-            .fetch_all(&self.pool)
+            .fetch_all(&mut *self.db.conn().await?)
             .await?;
 
         Ok(rows.into_iter().map(Tournament::from).collect())
@@ -557,6 +575,7 @@ impl TournamentRepository for PgTournamentRepository {
                 TournamentIden::PrizePool,
                 TournamentIden::Rules,
                 TournamentIden::OrganizerId,
+                TournamentIden::Timezone,
                 TournamentIden::CreatedAt,
                 TournamentIden::UpdatedAt,
             ])
@@ -565,7 +584,7 @@ impl TournamentRepository for PgTournamentRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let row: Option<TournamentRow> = sqlx::query_as_with(&sql, values)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(Tournament::from))
@@ -590,6 +609,7 @@ impl TournamentRepository for PgTournamentRepository {
                 TournamentIden::PrizePool,
                 TournamentIden::Rules,
                 TournamentIden::OrganizerId,
+                TournamentIden::Timezone,
                 TournamentIden::CreatedAt,
                 TournamentIden::UpdatedAt,
             ])
@@ -599,7 +619,7 @@ impl TournamentRepository for PgTournamentRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let rows: Vec<TournamentRow> = sqlx::query_as_with(&sql, values)
-            .fetch_all(&self.pool)
+            .fetch_all(&mut *self.db.conn().await?)
             .await?;
 
         Ok(rows.into_iter().map(Tournament::from).collect())
@@ -624,6 +644,7 @@ impl TournamentRepository for PgTournamentRepository {
                 TournamentIden::PrizePool,
                 TournamentIden::Rules,
                 TournamentIden::OrganizerId,
+                TournamentIden::Timezone,
                 TournamentIden::CreatedAt,
                 TournamentIden::UpdatedAt,
             ])
@@ -633,7 +654,7 @@ impl TournamentRepository for PgTournamentRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let rows: Vec<TournamentRow> = sqlx::query_as_with(&sql, values)
-            .fetch_all(&self.pool)
+            .fetch_all(&mut *self.db.conn().await?)
             .await?;
 
         Ok(rows.into_iter().map(Tournament::from).collect())
@@ -689,6 +710,9 @@ impl TournamentRepository for PgTournamentRepository {
         if let Some(rules) = tournament_data.rules {
             query.value(TournamentIden::Rules, rules);
         }
+        if let Some(timezone) = tournament_data.timezone {
+            query.value(TournamentIden::Timezone, timezone);
+        }
 
         query.value(TournamentIden::UpdatedAt, Utc::now());
         query.and_where(Expr::col(TournamentIden::Id).eq(tournament_id));
@@ -697,7 +721,7 @@ impl TournamentRepository for PgTournamentRepository {
         let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
 
         let row: Option<TournamentRow> = sqlx::query_as_with(&sql, values)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(Tournament::from))
@@ -711,7 +735,7 @@ impl TournamentRepository for PgTournamentRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let row: Option<TournamentRow> = sqlx::query_as_with(&sql, values)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(Tournament::from))
@@ -768,7 +792,7 @@ impl TournamentRepository for PgTournamentRepository {
         let sql = format!(
             "SELECT id, name, description, sport_type, format, status, start_date, end_date, \
              registration_start_date, registration_end_date, venue, max_participants, entry_fee, \
-             prize_pool, rules, organizer_id, created_at, updated_at FROM tournaments {} \
+             prize_pool, rules, organizer_id, timezone, created_at, updated_at FROM tournaments {} \
              ORDER BY start_date DESC {}{}",
             where_clause, limit_clause, offset_clause
         );
@@ -797,7 +821,7 @@ impl TournamentRepository for PgTournamentRepository {
             }
         }
 
-        let rows: Vec<TournamentRow> = q.fetch_all(&self.pool).await?;
+        let rows: Vec<TournamentRow> = q.fetch_all(&mut *self.db.conn().await?).await?;
         Ok(rows.into_iter().map(Tournament::from).collect())
     }
 
@@ -822,6 +846,7 @@ impl TournamentRepository for PgTournamentRepository {
                 TournamentIden::PrizePool,
                 TournamentIden::Rules,
                 TournamentIden::OrganizerId,
+                TournamentIden::Timezone,
                 TournamentIden::CreatedAt,
                 TournamentIden::UpdatedAt,
             ])
@@ -834,7 +859,7 @@ impl TournamentRepository for PgTournamentRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let rows: Vec<TournamentRow> = sqlx::query_as_with(&sql, values)
-            .fetch_all(&self.pool)
+            .fetch_all(&mut *self.db.conn().await?)
             .await?;
 
         Ok(rows.into_iter().map(Tournament::from).collect())
@@ -860,6 +885,7 @@ impl TournamentRepository for PgTournamentRepository {
                 TournamentIden::PrizePool,
                 TournamentIden::Rules,
                 TournamentIden::OrganizerId,
+                TournamentIden::Timezone,
                 TournamentIden::CreatedAt,
                 TournamentIden::UpdatedAt,
             ])
@@ -870,7 +896,7 @@ impl TournamentRepository for PgTournamentRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let rows: Vec<TournamentRow> = sqlx::query_as_with(&sql, values)
-            .fetch_all(&self.pool)
+            .fetch_all(&mut *self.db.conn().await?)
             .await?;
 
         Ok(rows.into_iter().map(Tournament::from).collect())
@@ -888,7 +914,7 @@ impl TournamentRepository for PgTournamentRepository {
              WHERE tc.tournament_id = $1",
         )
         .bind(tournament_id)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *self.db.conn().await?)
         .await
         .unwrap_or(0);
 
@@ -896,7 +922,7 @@ impl TournamentRepository for PgTournamentRepository {
             "SELECT COUNT(*) FROM tournament_registrations tr INNER JOIN tournament_categories tc ON tc.id = tr.tournament_category_id WHERE tc.tournament_id = $1",
         )
         .bind(tournament_id)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *self.db.conn().await?)
         .await
         .unwrap_or(0);
 
@@ -904,7 +930,7 @@ impl TournamentRepository for PgTournamentRepository {
             "SELECT COUNT(*) FROM tournament_categories WHERE tournament_id = $1",
         )
         .bind(tournament_id)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *self.db.conn().await?)
         .await
         .unwrap_or(0);
 
@@ -912,7 +938,7 @@ impl TournamentRepository for PgTournamentRepository {
             "SELECT COUNT(*) FROM matches m INNER JOIN tournament_categories tc ON tc.id = m.tournament_category_id WHERE tc.tournament_id = $1 AND m.match_status = 'completed'",
         )
         .bind(tournament_id)
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *self.db.conn().await?)
         .await
         .unwrap_or(0);
 
@@ -930,4 +956,39 @@ impl TournamentRepository for PgTournamentRepository {
             status: tournament.status,
         })
     }
+    async fn get_scorekeepers(&self, tournament_id: Uuid) -> Result<Vec<Uuid>, AppError> {
+        let user_ids: Vec<Uuid> = sqlx::query_scalar(
+            "SELECT user_id FROM tournament_scorekeepers WHERE tournament_id = $1 ORDER BY created_at",
+        )
+        .bind(tournament_id)
+        .fetch_all(&mut *self.db.conn().await?)
+        .await?;
+        Ok(user_ids)
+    }
+
+    async fn add_scorekeeper(&self, tournament_id: Uuid, user_id: Uuid) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO tournament_scorekeepers (tournament_id, user_id)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(tournament_id)
+        .bind(user_id)
+        .execute(&mut *self.db.conn().await?)
+        .await?;
+        Ok(())
+    }
+
+    async fn remove_scorekeeper(&self, tournament_id: Uuid, user_id: Uuid) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            "DELETE FROM tournament_scorekeepers WHERE tournament_id = $1 AND user_id = $2",
+        )
+        .bind(tournament_id)
+        .bind(user_id)
+        .execute(&mut *self.db.conn().await?)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
 }
@@ -14,7 +14,7 @@ use crate::domain::tournament::{
 };
 use crate::shared::AppError;
 
-use super::pool::DbPool;
+use super::pool::{DbHandle, DbPool};
 
 // ==================== Sea-Query Iden ====================
 
@@ -147,12 +147,19 @@ impl From<TournamentStandingsRow> for TournamentStandings {
 // ==================== Repository ====================
 
 pub struct PgTournamentStandingsRepository {
-    pool: DbPool,
+    db: DbHandle,
 }
 
 impl PgTournamentStandingsRepository {
     pub fn new(pool: DbPool) -> Self {
-        Self { pool }
+        Self {
+            db: DbHandle::Pool(pool),
+        }
+    }
+
+    /// Repository bound to a unit of work's connection
+    pub fn with_handle(db: DbHandle) -> Self {
+        Self { db }
     }
 }
 
@@ -182,6 +189,9 @@ impl TournamentStandingsRepository for PgTournamentStandingsRepository {
                 TournamentStandingsIden::GoalDifference,
                 TournamentStandingsIden::BonusPoints,
                 TournamentStandingsIden::PenaltyPoints,
+                TournamentStandingsIden::Position,
+                TournamentStandingsIden::IsEliminated,
+                TournamentStandingsIden::EliminationRound,
                 TournamentStandingsIden::LastUpdated,
             ])
             .values_panic([
@@ -202,13 +212,16 @@ impl TournamentStandingsRepository for PgTournamentStandingsRepository {
                 new_standings.goal_difference.into(),
                 new_standings.bonus_points.into(),
                 new_standings.penalty_points.into(),
+                new_standings.position.unwrap_or(0).into(),
+                new_standings.is_eliminated.unwrap_or(false).into(),
+                new_standings.elimination_round.into(),
                 Utc::now().into(),
             ])
             .returning_all()
             .build_sqlx(PostgresQueryBuilder);
 
         let row: TournamentStandingsRow = sqlx::query_as_with(&sql, values)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *self.db.conn().await?)
             .await?;
 
         Ok(TournamentStandings::from(row))
@@ -251,7 +264,7 @@ impl TournamentStandingsRepository for PgTournamentStandingsRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let rows: Vec<TournamentStandingsRow> = sqlx::query_as_with(&sql, values)
-            .fetch_all(&self.pool)
+            .fetch_all(&mut *self.db.conn().await?)
             .await?;
 
         Ok(rows.into_iter().map(TournamentStandings::from).collect())
@@ -294,7 +307,7 @@ impl TournamentStandingsRepository for PgTournamentStandingsRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let rows: Vec<TournamentStandingsRow> = sqlx::query_as_with(&sql, values)
-            .fetch_all(&self.pool)
+            .fetch_all(&mut *self.db.conn().await?)
             .await?;
 
         Ok(rows.into_iter().map(TournamentStandings::from).collect())
@@ -336,7 +349,7 @@ impl TournamentStandingsRepository for PgTournamentStandingsRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let rows: Vec<TournamentStandingsRow> = sqlx::query_as_with(&sql, values)
-            .fetch_all(&self.pool)
+            .fetch_all(&mut *self.db.conn().await?)
             .await?;
 
         Ok(rows.into_iter().map(TournamentStandings::from).collect())
@@ -406,7 +419,7 @@ impl TournamentStandingsRepository for PgTournamentStandingsRepository {
         let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
 
         let row: Option<TournamentStandingsRow> = sqlx::query_as_with(&sql, values)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
         Ok(row.map(TournamentStandings::from))
@@ -419,7 +432,7 @@ impl TournamentStandingsRepository for PgTournamentStandingsRepository {
             .build_sqlx(PostgresQueryBuilder);
 
         let result = sqlx::query_with(&sql, values)
-            .execute(&self.pool)
+            .execute(&mut *self.db.conn().await?)
             .await?;
 
         Ok(result.rows_affected())
@@ -444,7 +457,7 @@ impl TournamentStandingsRepository for PgTournamentStandingsRepository {
             .bind(standing.tournament_id)
             .bind(standing.category_id)
             .bind(standing.participant_id)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *self.db.conn().await?)
             .await?;
 
             if let Some(existing_row) = existing {
@@ -453,7 +466,7 @@ impl TournamentStandingsRepository for PgTournamentStandingsRepository {
                     .update(
                         existing_row.id,
                         EditableTournamentStandings {
-                            position: None,
+                            position: standing.position,
                             points: standing.points,
                             matches_played: standing.matches_played,
                             matches_won: standing.matches_won,
@@ -467,8 +480,8 @@ impl TournamentStandingsRepository for PgTournamentStandingsRepository {
                             head_to_head: None,
                             bonus_points: standing.bonus_points,
                             penalty_points: standing.penalty_points,
-                            is_eliminated: None,
-                            elimination_round: None,
+                            is_eliminated: standing.is_eliminated,
+                            elimination_round: standing.elimination_round,
                         },
                     )
                     .await?;
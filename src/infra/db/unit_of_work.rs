@@ -0,0 +1,148 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::domain::check_in::CheckInWindowRepository;
+use crate::domain::court_queue::CourtCallRepository;
+use crate::domain::match_domain::{MatchRepository, MatchResultRepository};
+use crate::domain::notification::NotificationRepository;
+use crate::domain::outbox::OutboxRepository;
+use crate::domain::payment::PaymentRepository;
+use crate::domain::tournament::{
+    TournamentBracketRepository, TournamentCategoryRepository, TournamentRegistrationRepository,
+    TournamentRepository, TournamentStandingsRepository,
+};
+use crate::domain::unit_of_work::{UnitOfWork, UnitOfWorkFactory};
+use crate::shared::AppError;
+
+use super::pool::{DbHandle, DbPool, SharedTransaction};
+use super::{
+    PgCheckInWindowRepository, PgCourtCallRepository, PgMatchRepository, PgMatchResultRepository,
+    PgNotificationRepository, PgOutboxRepository, PgPaymentRepository,
+    PgTournamentBracketRepository, PgTournamentCategoryRepository,
+    PgTournamentRegistrationRepository, PgTournamentRepository, PgTournamentStandingsRepository,
+};
+
+/// Begins Postgres transactions and hands out repositories bound to them
+pub struct PgUnitOfWorkFactory {
+    pool: DbPool,
+}
+
+impl PgUnitOfWorkFactory {
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl UnitOfWorkFactory for PgUnitOfWorkFactory {
+    async fn begin(&self) -> Result<Box<dyn UnitOfWork>, AppError> {
+        let tx = self.pool.begin().await?;
+        Ok(Box::new(PgUnitOfWork::new(Arc::new(Mutex::new(Some(tx))))))
+    }
+}
+
+/// One Postgres transaction with the repositories multi-step service operations need
+pub struct PgUnitOfWork {
+    tx: SharedTransaction,
+    tournaments: PgTournamentRepository,
+    categories: PgTournamentCategoryRepository,
+    registrations: PgTournamentRegistrationRepository,
+    brackets: PgTournamentBracketRepository,
+    standings: PgTournamentStandingsRepository,
+    matches: PgMatchRepository,
+    match_results: PgMatchResultRepository,
+    notifications: PgNotificationRepository,
+    payments: PgPaymentRepository,
+    outbox: PgOutboxRepository,
+    court_calls: PgCourtCallRepository,
+    check_in_windows: PgCheckInWindowRepository,
+}
+
+impl PgUnitOfWork {
+    fn new(tx: SharedTransaction) -> Self {
+        let handle = || DbHandle::Transaction(Arc::clone(&tx));
+        Self {
+            tournaments: PgTournamentRepository::with_handle(handle()),
+            categories: PgTournamentCategoryRepository::with_handle(handle()),
+            registrations: PgTournamentRegistrationRepository::with_handle(handle()),
+            brackets: PgTournamentBracketRepository::with_handle(handle()),
+            standings: PgTournamentStandingsRepository::with_handle(handle()),
+            matches: PgMatchRepository::with_handle(handle()),
+            match_results: PgMatchResultRepository::with_handle(handle()),
+            notifications: PgNotificationRepository::with_handle(handle()),
+            payments: PgPaymentRepository::with_handle(handle()),
+            outbox: PgOutboxRepository::with_handle(handle()),
+            court_calls: PgCourtCallRepository::with_handle(handle()),
+            check_in_windows: PgCheckInWindowRepository::with_handle(handle()),
+            tx,
+        }
+    }
+}
+
+#[async_trait]
+impl UnitOfWork for PgUnitOfWork {
+    fn tournaments(&self) -> &dyn TournamentRepository {
+        &self.tournaments
+    }
+
+    fn categories(&self) -> &dyn TournamentCategoryRepository {
+        &self.categories
+    }
+
+    fn registrations(&self) -> &dyn TournamentRegistrationRepository {
+        &self.registrations
+    }
+
+    fn brackets(&self) -> &dyn TournamentBracketRepository {
+        &self.brackets
+    }
+
+    fn standings(&self) -> &dyn TournamentStandingsRepository {
+        &self.standings
+    }
+
+    fn matches(&self) -> &dyn MatchRepository {
+        &self.matches
+    }
+
+    fn match_results(&self) -> &dyn MatchResultRepository {
+        &self.match_results
+    }
+
+    fn notifications(&self) -> &dyn NotificationRepository {
+        &self.notifications
+    }
+
+    fn payments(&self) -> &dyn PaymentRepository {
+        &self.payments
+    }
+
+    fn outbox(&self) -> &dyn OutboxRepository {
+        &self.outbox
+    }
+
+    fn court_calls(&self) -> &dyn CourtCallRepository {
+        &self.court_calls
+    }
+
+    fn check_in_windows(&self) -> &dyn CheckInWindowRepository {
+        &self.check_in_windows
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), AppError> {
+        match self.tx.lock().await.take() {
+            Some(tx) => Ok(tx.commit().await?),
+            None => Err(AppError::InternalError(
+                "Transaction has already been committed or rolled back".into(),
+            )),
+        }
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), AppError> {
+        match self.tx.lock().await.take() {
+            Some(tx) => Ok(tx.rollback().await?),
+            None => Ok(()),
+        }
+    }
+}
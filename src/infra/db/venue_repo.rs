@@ -0,0 +1,330 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::types::Json;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::domain::venue::{
+    AvailabilityWindow, Court, CourtKind, EditableCourt, EditableVenue, NewCourt, NewVenue,
+    OpeningHours, SurfaceType, Venue, VenueRepository,
+};
+use crate::shared::AppError;
+
+use super::pool::{DbHandle, DbPool};
+
+fn court_kind_from_db(s: &str) -> CourtKind {
+    match s {
+        "table" => CourtKind::Table,
+        "field" => CourtKind::Field,
+        _ => CourtKind::Court,
+    }
+}
+
+fn surface_from_db(s: &str) -> SurfaceType {
+    match s {
+        "hard" => SurfaceType::Hard,
+        "clay" => SurfaceType::Clay,
+        "grass" => SurfaceType::Grass,
+        "artificial_grass" => SurfaceType::ArtificialGrass,
+        "carpet" => SurfaceType::Carpet,
+        "wood" => SurfaceType::Wood,
+        "sand" => SurfaceType::Sand,
+        _ => SurfaceType::Other,
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct VenueRow {
+    id: Uuid,
+    name: String,
+    address: Option<String>,
+    city: Option<String>,
+    country: Option<String>,
+    timezone: String,
+    opening_hours: Json<Vec<OpeningHours>>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<VenueRow> for Venue {
+    fn from(row: VenueRow) -> Self {
+        Venue {
+            id: row.id,
+            name: row.name,
+            address: row.address,
+            city: row.city,
+            country: row.country,
+            timezone: row.timezone,
+            opening_hours: row.opening_hours.0,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct CourtRow {
+    id: Uuid,
+    venue_id: Uuid,
+    name: String,
+    kind: String,
+    surface: Option<String>,
+    is_active: bool,
+    availability: Json<Vec<AvailabilityWindow>>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<CourtRow> for Court {
+    fn from(row: CourtRow) -> Self {
+        Court {
+            id: row.id,
+            venue_id: row.venue_id,
+            name: row.name,
+            kind: court_kind_from_db(&row.kind),
+            surface: row.surface.as_deref().map(surface_from_db),
+            is_active: row.is_active,
+            availability: row.availability.0,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+const VENUE_SELECT: &str =
+    "id, name, address, city, country, timezone, opening_hours, created_at, updated_at";
+
+const COURT_SELECT: &str =
+    "id, venue_id, name, kind, surface, is_active, availability, created_at, updated_at";
+
+pub struct PgVenueRepository {
+    db: DbHandle,
+}
+
+impl PgVenueRepository {
+    pub fn new(pool: DbPool) -> Self {
+        Self {
+            db: DbHandle::Pool(pool),
+        }
+    }
+}
+
+#[async_trait]
+impl VenueRepository for PgVenueRepository {
+    async fn create(&self, new_venue: NewVenue) -> Result<Venue, AppError> {
+        let sql = format!(
+            r#"
+            INSERT INTO venues (name, address, city, country, timezone, opening_hours)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING {}
+            "#,
+            VENUE_SELECT
+        );
+        let row: VenueRow = sqlx::query_as(&sql)
+            .bind(new_venue.name)
+            .bind(new_venue.address)
+            .bind(new_venue.city)
+            .bind(new_venue.country)
+            .bind(new_venue.timezone)
+            .bind(Json(new_venue.opening_hours))
+            .fetch_one(&mut *self.db.conn().await?)
+            .await?;
+        Ok(Venue::from(row))
+    }
+
+    async fn find_by_id(&self, venue_id: Uuid) -> Result<Option<Venue>, AppError> {
+        let sql = format!("SELECT {} FROM venues WHERE id = $1", VENUE_SELECT);
+        let row: Option<VenueRow> = sqlx::query_as(&sql)
+            .bind(venue_id)
+            .fetch_optional(&mut *self.db.conn().await?)
+            .await?;
+        Ok(row.map(Venue::from))
+    }
+
+    async fn find_all(&self, limit: i64, offset: i64) -> Result<Vec<Venue>, AppError> {
+        let sql = format!(
+            "SELECT {} FROM venues ORDER BY name LIMIT $1 OFFSET $2",
+            VENUE_SELECT
+        );
+        let rows: Vec<VenueRow> = sqlx::query_as(&sql)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&mut *self.db.conn().await?)
+            .await?;
+        Ok(rows.into_iter().map(Venue::from).collect())
+    }
+
+    async fn update(
+        &self,
+        venue_id: Uuid,
+        venue_data: EditableVenue,
+    ) -> Result<Option<Venue>, AppError> {
+        let sql = format!(
+            r#"
+            UPDATE venues SET
+                name = COALESCE($2, name),
+                address = COALESCE($3, address),
+                city = COALESCE($4, city),
+                country = COALESCE($5, country),
+                timezone = COALESCE($6, timezone),
+                opening_hours = COALESCE($7, opening_hours),
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            VENUE_SELECT
+        );
+        let row: Option<VenueRow> = sqlx::query_as(&sql)
+            .bind(venue_id)
+            .bind(venue_data.name)
+            .bind(venue_data.address)
+            .bind(venue_data.city)
+            .bind(venue_data.country)
+            .bind(venue_data.timezone)
+            .bind(venue_data.opening_hours.map(Json))
+            .fetch_optional(&mut *self.db.conn().await?)
+            .await?;
+        Ok(row.map(Venue::from))
+    }
+
+    async fn delete(&self, venue_id: Uuid) -> Result<Option<Venue>, AppError> {
+        let sql = format!(
+            "DELETE FROM venues WHERE id = $1 RETURNING {}",
+            VENUE_SELECT
+        );
+        let row: Option<VenueRow> = sqlx::query_as(&sql)
+            .bind(venue_id)
+            .fetch_optional(&mut *self.db.conn().await?)
+            .await?;
+        Ok(row.map(Venue::from))
+    }
+
+    async fn create_court(&self, venue_id: Uuid, new_court: NewCourt) -> Result<Court, AppError> {
+        let sql = format!(
+            r#"
+            INSERT INTO courts (venue_id, name, kind, surface, availability)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING {}
+            "#,
+            COURT_SELECT
+        );
+        let row: CourtRow = sqlx::query_as(&sql)
+            .bind(venue_id)
+            .bind(new_court.name)
+            .bind(new_court.kind.key())
+            .bind(new_court.surface.map(|s| s.key()))
+            .bind(Json(new_court.availability))
+            .fetch_one(&mut *self.db.conn().await?)
+            .await?;
+        Ok(Court::from(row))
+    }
+
+    async fn find_court(&self, court_id: Uuid) -> Result<Option<Court>, AppError> {
+        let sql = format!("SELECT {} FROM courts WHERE id = $1", COURT_SELECT);
+        let row: Option<CourtRow> = sqlx::query_as(&sql)
+            .bind(court_id)
+            .fetch_optional(&mut *self.db.conn().await?)
+            .await?;
+        Ok(row.map(Court::from))
+    }
+
+    async fn find_courts(&self, venue_id: Uuid) -> Result<Vec<Court>, AppError> {
+        let sql = format!(
+            "SELECT {} FROM courts WHERE venue_id = $1 ORDER BY name",
+            COURT_SELECT
+        );
+        let rows: Vec<CourtRow> = sqlx::query_as(&sql)
+            .bind(venue_id)
+            .fetch_all(&mut *self.db.conn().await?)
+            .await?;
+        Ok(rows.into_iter().map(Court::from).collect())
+    }
+
+    async fn update_court(
+        &self,
+        court_id: Uuid,
+        court_data: EditableCourt,
+    ) -> Result<Option<Court>, AppError> {
+        let sql = format!(
+            r#"
+            UPDATE courts SET
+                name = COALESCE($2, name),
+                kind = COALESCE($3, kind),
+                surface = COALESCE($4, surface),
+                is_active = COALESCE($5, is_active),
+                availability = COALESCE($6, availability),
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING {}
+            "#,
+            COURT_SELECT
+        );
+        let row: Option<CourtRow> = sqlx::query_as(&sql)
+            .bind(court_id)
+            .bind(court_data.name)
+            .bind(court_data.kind.map(|k| k.key()))
+            .bind(court_data.surface.map(|s| s.key()))
+            .bind(court_data.is_active)
+            .bind(court_data.availability.map(Json))
+            .fetch_optional(&mut *self.db.conn().await?)
+            .await?;
+        Ok(row.map(Court::from))
+    }
+
+    async fn delete_court(&self, court_id: Uuid) -> Result<Option<Court>, AppError> {
+        let sql = format!(
+            "DELETE FROM courts WHERE id = $1 RETURNING {}",
+            COURT_SELECT
+        );
+        let row: Option<CourtRow> = sqlx::query_as(&sql)
+            .bind(court_id)
+            .fetch_optional(&mut *self.db.conn().await?)
+            .await?;
+        Ok(row.map(Court::from))
+    }
+
+    async fn add_to_tournament(&self, tournament_id: Uuid, venue_id: Uuid) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO tournament_venues (tournament_id, venue_id)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(tournament_id)
+        .bind(venue_id)
+        .execute(&mut *self.db.conn().await?)
+        .await?;
+        Ok(())
+    }
+
+    async fn remove_from_tournament(
+        &self,
+        tournament_id: Uuid,
+        venue_id: Uuid,
+    ) -> Result<bool, AppError> {
+        let result =
+            sqlx::query("DELETE FROM tournament_venues WHERE tournament_id = $1 AND venue_id = $2")
+                .bind(tournament_id)
+                .bind(venue_id)
+                .execute(&mut *self.db.conn().await?)
+                .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_by_tournament(&self, tournament_id: Uuid) -> Result<Vec<Venue>, AppError> {
+        let sql = format!(
+            r#"
+            SELECT {} FROM venues
+            WHERE id IN (SELECT venue_id FROM tournament_venues WHERE tournament_id = $1)
+            ORDER BY name
+            "#,
+            VENUE_SELECT
+        );
+        let rows: Vec<VenueRow> = sqlx::query_as(&sql)
+            .bind(tournament_id)
+            .fetch_all(&mut *self.db.conn().await?)
+            .await?;
+        Ok(rows.into_iter().map(Venue::from).collect())
+    }
+}
@@ -0,0 +1,6 @@
+//! Outgoing email transports for notification emails.
+//! Chosen with EMAIL_TRANSPORT: `smtp` (SMTP_URL), `file` (EMAIL_SINK_DIR) or `log`.
+
+mod sender;
+
+pub use sender::email_sender;
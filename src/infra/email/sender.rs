@@ -0,0 +1,129 @@
+//! Email transports: SMTP via lettre, plus file and log sinks for local development.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use lettre::message::header::{ContentType, HeaderName, HeaderValue};
+use lettre::message::Mailbox;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use std::path::PathBuf;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::domain::notification::{EmailSender, OutgoingEmail};
+use crate::shared::config::{EmailConfig, EmailTransport};
+use crate::shared::errors::AppError;
+
+/// Builds the sender the config asks for
+pub fn email_sender(config: &EmailConfig) -> Result<Arc<dyn EmailSender>, AppError> {
+    Ok(match &config.transport {
+        EmailTransport::Smtp { url } => Arc::new(SmtpEmailSender::new(url, &config.from)?),
+        EmailTransport::File { dir } => Arc::new(FileEmailSender::new(dir, &config.from)?),
+        EmailTransport::Log => Arc::new(LogEmailSender),
+    })
+}
+
+fn parse_mailbox(address: &str) -> Result<Mailbox, AppError> {
+    address
+        .parse()
+        .map_err(|e| AppError::InternalError(format!("Invalid email address {}: {}", address, e)))
+}
+
+/// RFC 5322 message for an email
+fn build_message(from: &Mailbox, email: &OutgoingEmail) -> Result<Message, AppError> {
+    let to = Mailbox::new(email.to_name.clone(), parse_mailbox(&email.to)?.email);
+    let mut builder = Message::builder()
+        .from(from.clone())
+        .to(to)
+        .subject(email.subject.as_str())
+        .header(ContentType::TEXT_PLAIN);
+    if let Some(url) = &email.unsubscribe_url {
+        builder = builder
+            .raw_header(HeaderValue::new(
+                HeaderName::new_from_ascii_str("List-Unsubscribe"),
+                format!("<{}>", url),
+            ))
+            .raw_header(HeaderValue::new(
+                HeaderName::new_from_ascii_str("List-Unsubscribe-Post"),
+                "List-Unsubscribe=One-Click".to_string(),
+            ));
+    }
+    builder
+        .body(email.body.clone())
+        .map_err(|e| AppError::InternalError(format!("Failed to build email: {}", e)))
+}
+
+/// Sends through an SMTP server
+pub struct SmtpEmailSender {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpEmailSender {
+    /// `url` is `smtp://` (plain, `?tls=required` for STARTTLS) or `smtps://`
+    pub fn new(url: &str, from: &str) -> Result<Self, AppError> {
+        let transport = AsyncSmtpTransport::<Tokio1Executor>::from_url(url)
+            .map_err(|e| AppError::InternalError(format!("Invalid SMTP_URL: {}", e)))?
+            .build();
+        Ok(Self {
+            transport,
+            from: parse_mailbox(from)?,
+        })
+    }
+}
+
+#[async_trait]
+impl EmailSender for SmtpEmailSender {
+    async fn send(&self, email: &OutgoingEmail) -> Result<(), AppError> {
+        let message = build_message(&self.from, email)?;
+        self.transport
+            .send(message)
+            .await
+            .map_err(|e| AppError::InternalError(format!("SMTP delivery failed: {}", e)))?;
+        Ok(())
+    }
+}
+
+/// Writes each email to `<dir>/<timestamp>-<id>.eml`, openable in any mail client
+pub struct FileEmailSender {
+    dir: PathBuf,
+    from: Mailbox,
+}
+
+impl FileEmailSender {
+    pub fn new(dir: impl Into<PathBuf>, from: &str) -> Result<Self, AppError> {
+        Ok(Self {
+            dir: dir.into(),
+            from: parse_mailbox(from)?,
+        })
+    }
+}
+
+#[async_trait]
+impl EmailSender for FileEmailSender {
+    async fn send(&self, email: &OutgoingEmail) -> Result<(), AppError> {
+        let message = build_message(&self.from, email)?;
+        let path = self.dir.join(format!(
+            "{}-{}.eml",
+            Utc::now().format("%Y%m%dT%H%M%S%.3f"),
+            Uuid::new_v4()
+        ));
+        let write = async {
+            tokio::fs::create_dir_all(&self.dir).await?;
+            tokio::fs::write(&path, message.formatted()).await
+        };
+        write.await.map_err(|e| {
+            AppError::InternalError(format!("Failed to write {}: {}", path.display(), e))
+        })
+    }
+}
+
+/// Prints emails to stderr
+pub struct LogEmailSender;
+
+#[async_trait]
+impl EmailSender for LogEmailSender {
+    async fn send(&self, email: &OutgoingEmail) -> Result<(), AppError> {
+        eprintln!("Email to {}: {}\n{}", email.to, email.subject, email.body);
+        Ok(())
+    }
+}
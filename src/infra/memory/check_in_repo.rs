@@ -0,0 +1,82 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::check_in::{CheckInWindow, CheckInWindowRepository};
+use crate::shared::AppError;
+
+use super::store::{foreign_key_violation, MemoryHandle, MemoryStore};
+
+pub struct InMemoryCheckInWindowRepository {
+    db: MemoryHandle,
+}
+
+impl InMemoryCheckInWindowRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self {
+            db: MemoryHandle::Store(store),
+        }
+    }
+
+    /// Repository bound to a unit of work's transaction
+    pub(crate) fn with_handle(db: MemoryHandle) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl CheckInWindowRepository for InMemoryCheckInWindowRepository {
+    async fn save(&self, mut window: CheckInWindow) -> Result<CheckInWindow, AppError> {
+        let mut tables = self.db.tables().await?;
+        if !tables
+            .categories
+            .iter()
+            .any(|c| c.id == window.category_id)
+        {
+            return Err(foreign_key_violation("check_in_windows"));
+        }
+        window.updated_at = Utc::now();
+        match tables
+            .check_in_windows
+            .iter_mut()
+            .find(|w| w.category_id == window.category_id)
+        {
+            Some(existing) => {
+                window.created_at = existing.created_at;
+                *existing = window.clone();
+            }
+            None => tables.check_in_windows.push(window.clone()),
+        }
+        Ok(window)
+    }
+
+    async fn find_by_category(&self, category_id: Uuid) -> Result<Option<CheckInWindow>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables
+            .check_in_windows
+            .iter()
+            .find(|w| w.category_id == category_id)
+            .cloned())
+    }
+
+    async fn delete(&self, category_id: Uuid) -> Result<Option<CheckInWindow>, AppError> {
+        let mut tables = self.db.tables().await?;
+        let index = tables
+            .check_in_windows
+            .iter()
+            .position(|w| w.category_id == category_id);
+        Ok(index.map(|index| tables.check_in_windows.remove(index)))
+    }
+
+    async fn find_due(&self, now: DateTime<Utc>) -> Result<Vec<CheckInWindow>, AppError> {
+        let tables = self.db.tables().await?;
+        let mut windows: Vec<CheckInWindow> = tables
+            .check_in_windows
+            .iter()
+            .filter(|w| w.closed_at.is_none() && w.closes_at <= now)
+            .cloned()
+            .collect();
+        windows.sort_by_key(|w| w.closes_at);
+        Ok(windows)
+    }
+}
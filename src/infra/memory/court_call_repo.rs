@@ -0,0 +1,71 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::court_queue::{CallStatus, CourtCall, CourtCallRepository};
+use crate::shared::AppError;
+
+use super::store::{foreign_key_violation, MemoryHandle, MemoryStore};
+
+pub struct InMemoryCourtCallRepository {
+    db: MemoryHandle,
+}
+
+impl InMemoryCourtCallRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self {
+            db: MemoryHandle::Store(store),
+        }
+    }
+
+    /// Repository bound to a unit of work's transaction
+    pub(crate) fn with_handle(db: MemoryHandle) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl CourtCallRepository for InMemoryCourtCallRepository {
+    async fn save(&self, mut call: CourtCall) -> Result<CourtCall, AppError> {
+        let mut tables = self.db.tables().await?;
+        if !tables.matches.iter().any(|m| m.id == call.match_id)
+            || !tables.courts.iter().any(|c| c.id == call.court_id)
+        {
+            return Err(foreign_key_violation("court_calls"));
+        }
+        call.updated_at = Utc::now();
+        match tables
+            .court_calls
+            .iter_mut()
+            .find(|c| c.match_id == call.match_id)
+        {
+            Some(existing) => {
+                call.created_at = existing.created_at;
+                *existing = call.clone();
+            }
+            None => tables.court_calls.push(call.clone()),
+        }
+        Ok(call)
+    }
+
+    async fn find_by_match(&self, match_id: Uuid) -> Result<Option<CourtCall>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables
+            .court_calls
+            .iter()
+            .find(|c| c.match_id == match_id)
+            .cloned())
+    }
+
+    async fn find_expired(&self, now: DateTime<Utc>) -> Result<Vec<CourtCall>, AppError> {
+        let tables = self.db.tables().await?;
+        let mut calls: Vec<CourtCall> = tables
+            .court_calls
+            .iter()
+            .filter(|c| c.status == CallStatus::Called && c.respond_by <= now)
+            .cloned()
+            .collect();
+        calls.sort_by_key(|c| c.respond_by);
+        Ok(calls)
+    }
+}
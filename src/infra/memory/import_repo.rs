@@ -0,0 +1,171 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::domain::import::{
+    ImportCommitResult, ImportPlan, ImportRepository, PlannedRef, PlayerWithEmail,
+};
+use crate::domain::participant::{Player, Team, TeamMember, TeamWithMembers};
+use crate::domain::tournament::NewTournamentRegistration;
+use crate::shared::AppError;
+
+use super::participant_repo::check_player_unique;
+use super::store::{foreign_key_violation, MemoryHandle, MemoryStore, Tables};
+use super::tournament_repo::{check_registration_unique, new_registration_row};
+
+/// `LOWER(regexp_replace(TRIM(name), '\s+', ' ', 'g'))`
+fn normalize_name(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+fn resolve(r: PlannedRef, created: &[Uuid]) -> Uuid {
+    match r {
+        PlannedRef::Existing(id) => id,
+        PlannedRef::New(i) => created[i],
+    }
+}
+
+/// Applies the plan to `tables` in dependency order, stopping at the first failure
+fn apply_plan(tables: &mut Tables, plan: ImportPlan) -> Result<ImportCommitResult, AppError> {
+    let mut result = ImportCommitResult::default();
+
+    for new_player in plan.players {
+        check_player_unique(tables, None, &new_player.name, new_player.user_id)?;
+        let player = Player {
+            id: Uuid::new_v4(),
+            name: new_player.name,
+            user_id: new_player.user_id,
+            created_at: Utc::now(),
+        };
+        tables.players.push(player.clone());
+        result.players.push(player);
+    }
+
+    for new_team in plan.teams {
+        let team = Team {
+            id: Uuid::new_v4(),
+            name: new_team.name,
+            created_at: Utc::now(),
+        };
+        tables.teams.push(team.clone());
+        result.teams.push(team);
+    }
+
+    let player_ids: Vec<Uuid> = result.players.iter().map(|p| p.id).collect();
+    let team_ids: Vec<Uuid> = result.teams.iter().map(|t| t.id).collect();
+
+    for member in plan.members {
+        let team_id = resolve(member.team, &team_ids);
+        let player_id = resolve(member.player, &player_ids);
+        if !tables.teams.iter().any(|t| t.id == team_id)
+            || !tables.players.iter().any(|p| p.id == player_id)
+        {
+            return Err(foreign_key_violation("team_members"));
+        }
+        let member = TeamMember {
+            team_id,
+            player_id,
+            is_captain: member.is_captain,
+            jersey_number: member.jersey_number,
+            joined_at: Utc::now(),
+        };
+        tables.team_members.push(member.clone());
+        result.members.push(member);
+    }
+
+    for registration in plan.registrations {
+        let new_registration = NewTournamentRegistration {
+            tournament_category_id: registration.tournament_category_id,
+            team_id: registration.team.map(|r| resolve(r, &team_ids)),
+            player_id: registration.player.map(|r| resolve(r, &player_ids)),
+            partner_player_id: registration.partner.map(|r| resolve(r, &player_ids)),
+            notes: registration.notes,
+            metadata: registration.metadata,
+        };
+        check_registration_unique(
+            tables,
+            new_registration.tournament_category_id,
+            new_registration.team_id,
+            new_registration.player_id,
+            new_registration.partner_player_id,
+        )?;
+        let registration = new_registration_row(new_registration);
+        tables.registrations.push(registration.clone());
+        result.registrations.push(registration);
+    }
+
+    Ok(result)
+}
+
+pub struct InMemoryImportRepository {
+    db: MemoryHandle,
+}
+
+impl InMemoryImportRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self {
+            db: MemoryHandle::Store(store),
+        }
+    }
+}
+
+#[async_trait]
+impl ImportRepository for InMemoryImportRepository {
+    async fn find_players_by_emails(
+        &self,
+        emails: &[String],
+    ) -> Result<Vec<PlayerWithEmail>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables
+            .players
+            .iter()
+            .filter_map(|player| {
+                let user = tables.users.iter().find(|u| Some(u.id) == player.user_id)?;
+                emails
+                    .contains(&user.email.to_lowercase())
+                    .then(|| PlayerWithEmail {
+                        player: player.clone(),
+                        email: user.email.clone(),
+                    })
+            })
+            .collect())
+    }
+
+    async fn find_players_by_names(&self, names: &[String]) -> Result<Vec<Player>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables
+            .players
+            .iter()
+            .filter(|p| names.contains(&normalize_name(&p.name)))
+            .cloned()
+            .collect())
+    }
+
+    async fn find_teams_by_names(
+        &self,
+        names: &[String],
+    ) -> Result<Vec<TeamWithMembers>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables
+            .teams
+            .iter()
+            .filter(|t| names.contains(&normalize_name(&t.name)))
+            .map(|team| TeamWithMembers {
+                team: team.clone(),
+                members: tables.team_players(team.id),
+            })
+            .collect())
+    }
+
+    async fn commit(&self, plan: ImportPlan) -> Result<ImportCommitResult, AppError> {
+        let mut tables = self.db.tables().await?;
+        // Work on a copy so a failing row leaves nothing behind
+        let mut staged = tables.clone();
+        let result = apply_plan(&mut staged, plan)?;
+        *tables = staged;
+        Ok(result)
+    }
+}
@@ -0,0 +1,1080 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::domain::match_domain::{
+    EditableMatch, EditableMatchResult, Forfeit, LiveMatchUpdate, Match, MatchAnalytics,
+    MatchComment, MatchMedia, MatchRepository, MatchResult, MatchResultRepository,
+    MatchScheduleItem, MatchScoreSummary, MatchStatistics, MatchStatus, MatchSubscription,
+    MatchWithParticipants, NewMatch, NewMatchResult, RescheduleMatchRequest,
+};
+use crate::shared::AppError;
+
+use super::store::{foreign_key_violation, MemoryHandle, MemoryStore, Tables};
+
+/// Ascending order with NULLs last, as Postgres sorts them
+fn nulls_last<T: Ord>(a: &Option<T>, b: &Option<T>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.cmp(b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+fn schedule_item(tables: &Tables, m: &Match) -> Option<MatchScheduleItem> {
+    let category = tables
+        .categories
+        .iter()
+        .find(|c| c.id == m.tournament_category_id)?;
+    let tournament = tables
+        .tournaments
+        .iter()
+        .find(|t| t.id == category.tournament_id)?;
+
+    Some(MatchScheduleItem {
+        id: m.id,
+        tournament_category_id: m.tournament_category_id,
+        tournament_id: tournament.id,
+        tournament_name: tournament.name.clone(),
+        category_name: category.name.clone(),
+        participant1_name: tables.side_name(
+            m.participant1_team_id,
+            m.participant1_player_id,
+            m.participant1_partner_id,
+        ),
+        participant2_name: tables.side_name(
+            m.participant2_team_id,
+            m.participant2_player_id,
+            m.participant2_partner_id,
+        ),
+        match_type: m.match_type,
+        match_status: m.match_status,
+        scheduled_date: m.scheduled_date,
+        venue: m.venue.clone(),
+        court_number: m.court_number.clone(),
+        round_number: m.round_number,
+        estimated_start_date: None,
+        delay_minutes: None,
+    })
+}
+
+/// Whether the user plays in the match, directly or through a team
+fn with_participants(tables: &Tables, m: &Match) -> MatchWithParticipants {
+    MatchWithParticipants {
+        id: m.id,
+        tournament_category_id: m.tournament_category_id,
+        participant1_name: tables.side_name(
+            m.participant1_team_id,
+            m.participant1_player_id,
+            m.participant1_partner_id,
+        ),
+        participant2_name: tables.side_name(
+            m.participant2_team_id,
+            m.participant2_player_id,
+            m.participant2_partner_id,
+        ),
+        match_type: m.match_type,
+        match_status: m.match_status,
+        scheduled_date: m.scheduled_date,
+        venue: m.venue.clone(),
+        court_number: m.court_number.clone(),
+        court_id: m.court_id,
+        winner_participant: m.winner_participant,
+    }
+}
+
+fn involves_user(tables: &Tables, m: &Match, user_id: Uuid) -> bool {
+    let player_ids: Vec<Uuid> = tables
+        .players
+        .iter()
+        .filter(|p| p.user_id == Some(user_id))
+        .map(|p| p.id)
+        .collect();
+    let is_player = |id: Option<Uuid>| id.is_some_and(|id| player_ids.contains(&id));
+    let is_member = |team_id: Option<Uuid>| {
+        team_id.is_some_and(|team_id| {
+            tables
+                .team_members
+                .iter()
+                .any(|tm| tm.team_id == team_id && player_ids.contains(&tm.player_id))
+        })
+    };
+
+    is_player(m.participant1_player_id)
+        || is_player(m.participant1_partner_id)
+        || is_player(m.participant2_player_id)
+        || is_player(m.participant2_partner_id)
+        || is_member(m.participant1_team_id)
+        || is_member(m.participant2_team_id)
+}
+
+// ==================== Match Repository ====================
+
+pub struct InMemoryMatchRepository {
+    db: MemoryHandle,
+}
+
+impl InMemoryMatchRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self {
+            db: MemoryHandle::Store(store),
+        }
+    }
+
+    /// Repository bound to a unit of work's transaction
+    pub(crate) fn with_handle(db: MemoryHandle) -> Self {
+        Self { db }
+    }
+
+    /// Applies `apply` to one match and bumps `updated_at`
+    async fn modify(
+        &self,
+        match_id: Uuid,
+        apply: impl FnOnce(&mut Match) + Send,
+    ) -> Result<Option<Match>, AppError> {
+        let mut tables = self.db.tables().await?;
+        Ok(tables
+            .matches
+            .iter_mut()
+            .find(|m| m.id == match_id)
+            .map(|m| {
+                apply(m);
+                m.updated_at = Utc::now();
+                m.clone()
+            }))
+    }
+
+    async fn schedule_items(
+        &self,
+        filter: impl Fn(&Tables, &Match) -> bool + Send,
+        order: impl Fn(&Match, &Match) -> Ordering + Send,
+    ) -> Result<Vec<MatchScheduleItem>, AppError> {
+        let tables = self.db.tables().await?;
+        let mut matches: Vec<&Match> = tables
+            .matches
+            .iter()
+            .filter(|m| filter(&tables, m))
+            .collect();
+        matches.sort_by(|a, b| order(a, b));
+        Ok(matches
+            .into_iter()
+            .filter_map(|m| schedule_item(&tables, m))
+            .collect())
+    }
+}
+
+fn apply_match_update(m: &mut Match, match_data: EditableMatch) {
+    if let Some(status) = match_data.match_status {
+        m.match_status = status;
+    }
+    if let Some(scheduled_date) = match_data.scheduled_date {
+        m.scheduled_date = scheduled_date;
+    }
+    if let Some(venue) = match_data.venue {
+        m.venue = Some(venue);
+    }
+    if let Some(court_number) = match_data.court_number {
+        m.court_number = Some(court_number);
+    }
+    if let Some(court_id) = match_data.court_id {
+        m.court_id = Some(court_id);
+    }
+    if let Some(referee_name) = match_data.referee_name {
+        m.referee_name = Some(referee_name);
+    }
+    if let Some(umpire_name) = match_data.umpire_name {
+        m.umpire_name = Some(umpire_name);
+    }
+    if let Some(notes) = match_data.notes {
+        m.notes = Some(notes);
+    }
+    if let Some(metadata) = match_data.metadata {
+        m.metadata = Some(metadata);
+    }
+}
+
+#[async_trait]
+impl MatchRepository for InMemoryMatchRepository {
+    async fn create(&self, new_match: NewMatch) -> Result<Match, AppError> {
+        let mut tables = self.db.tables().await?;
+        if !tables
+            .categories
+            .iter()
+            .any(|c| c.id == new_match.tournament_category_id)
+        {
+            return Err(foreign_key_violation("matches"));
+        }
+        if new_match
+            .court_id
+            .is_some_and(|court_id| !tables.courts.iter().any(|c| c.id == court_id))
+        {
+            return Err(foreign_key_violation("matches"));
+        }
+
+        let now = Utc::now();
+        let m = Match {
+            id: Uuid::new_v4(),
+            tournament_category_id: new_match.tournament_category_id,
+            participant1_team_id: new_match.participant1_team_id,
+            participant1_player_id: new_match.participant1_player_id,
+            participant1_partner_id: new_match.participant1_partner_id,
+            participant2_team_id: new_match.participant2_team_id,
+            participant2_player_id: new_match.participant2_player_id,
+            participant2_partner_id: new_match.participant2_partner_id,
+            match_type: new_match.match_type,
+            match_status: MatchStatus::Scheduled,
+            round_number: new_match.round_number,
+            match_number: new_match.match_number,
+            scheduled_date: new_match.scheduled_date,
+            actual_start_date: None,
+            actual_end_date: None,
+            venue: new_match.venue,
+            court_number: new_match.court_number,
+            court_id: new_match.court_id,
+            winner_participant: None,
+            is_draw: false,
+            forfeit_kind: None,
+            defaulting_participant: None,
+            referee_name: new_match.referee_name,
+            umpire_name: new_match.umpire_name,
+            notes: new_match.notes,
+            metadata: new_match.metadata,
+            created_at: now,
+            updated_at: now,
+        };
+        tables.matches.push(m.clone());
+        Ok(m)
+    }
+
+    async fn find_by_id(&self, match_id: Uuid) -> Result<Option<Match>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables.matches.iter().find(|m| m.id == match_id).cloned())
+    }
+
+    async fn update(
+        &self,
+        match_id: Uuid,
+        match_data: EditableMatch,
+    ) -> Result<Option<Match>, AppError> {
+        self.modify(match_id, |m| apply_match_update(m, match_data))
+            .await
+    }
+
+    async fn delete(&self, match_id: Uuid) -> Result<Option<Match>, AppError> {
+        let mut tables = self.db.tables().await?;
+        let m = tables.matches.iter().find(|m| m.id == match_id).cloned();
+        if m.is_some() {
+            tables.delete_match_cascade(match_id);
+        }
+        Ok(m)
+    }
+
+    async fn find_by_tournament(&self, tournament_id: Uuid) -> Result<Vec<Match>, AppError> {
+        let tables = self.db.tables().await?;
+        let mut matches: Vec<Match> = tables
+            .matches
+            .iter()
+            .filter(|m| tables.category_tournament(m.tournament_category_id) == Some(tournament_id))
+            .cloned()
+            .collect();
+        matches.sort_by(|a, b| {
+            a.scheduled_date
+                .cmp(&b.scheduled_date)
+                .then_with(|| nulls_last(&a.round_number, &b.round_number))
+                .then_with(|| nulls_last(&a.match_number, &b.match_number))
+        });
+        Ok(matches)
+    }
+
+    async fn find_by_category(&self, category_id: Uuid) -> Result<Vec<Match>, AppError> {
+        let tables = self.db.tables().await?;
+        let mut matches: Vec<Match> = tables
+            .matches
+            .iter()
+            .filter(|m| m.tournament_category_id == category_id)
+            .cloned()
+            .collect();
+        matches.sort_by(|a, b| {
+            nulls_last(&a.round_number, &b.round_number)
+                .then_with(|| nulls_last(&a.match_number, &b.match_number))
+        });
+        Ok(matches)
+    }
+
+    async fn find_scheduled(&self) -> Result<Vec<MatchScheduleItem>, AppError> {
+        self.schedule_items(
+            |_, m| m.match_status == MatchStatus::Scheduled,
+            |a, b| a.scheduled_date.cmp(&b.scheduled_date),
+        )
+        .await
+    }
+
+    async fn find_scheduled_between(
+        &self,
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<MatchScheduleItem>, AppError> {
+        self.schedule_items(
+            move |_, m| {
+                m.match_status == MatchStatus::Scheduled
+                    && m.scheduled_date >= from
+                    && m.scheduled_date < until
+            },
+            |a, b| a.scheduled_date.cmp(&b.scheduled_date),
+        )
+        .await
+    }
+
+    async fn find_schedule_by_tournament(
+        &self,
+        tournament_id: Uuid,
+    ) -> Result<Vec<MatchScheduleItem>, AppError> {
+        self.schedule_items(
+            move |tables, m| {
+                tables.category_tournament(m.tournament_category_id) == Some(tournament_id)
+            },
+            |a, b| {
+                a.scheduled_date
+                    .cmp(&b.scheduled_date)
+                    .then_with(|| nulls_last(&a.court_number, &b.court_number))
+            },
+        )
+        .await
+    }
+
+    async fn find_with_participants(
+        &self,
+        match_id: Uuid,
+    ) -> Result<Option<MatchWithParticipants>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables
+            .matches
+            .iter()
+            .find(|m| m.id == match_id)
+            .map(|m| with_participants(&tables, m)))
+    }
+
+    async fn find_with_participants_by_category(
+        &self,
+        category_id: Uuid,
+    ) -> Result<Vec<MatchWithParticipants>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables
+            .matches
+            .iter()
+            .filter(|m| m.tournament_category_id == category_id)
+            .map(|m| with_participants(&tables, m))
+            .collect())
+    }
+
+    async fn find_by_court(
+        &self,
+        court_id: Uuid,
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<Match>, AppError> {
+        let tables = self.db.tables().await?;
+        let mut matches: Vec<Match> = tables
+            .matches
+            .iter()
+            .filter(|m| m.court_id == Some(court_id))
+            .filter(|m| from <= m.scheduled_date && m.scheduled_date < until)
+            .cloned()
+            .collect();
+        matches.sort_by_key(|m| m.scheduled_date);
+        Ok(matches)
+    }
+
+    async fn find_in_period(
+        &self,
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<Match>, AppError> {
+        let tables = self.db.tables().await?;
+        let mut matches: Vec<Match> = tables
+            .matches
+            .iter()
+            .filter(|m| from <= m.scheduled_date && m.scheduled_date < until)
+            .cloned()
+            .collect();
+        matches.sort_by_key(|m| m.scheduled_date);
+        Ok(matches)
+    }
+
+    async fn update_status(
+        &self,
+        match_id: Uuid,
+        status: MatchStatus,
+    ) -> Result<Option<Match>, AppError> {
+        self.modify(match_id, |m| {
+            m.match_status = status;
+            if matches!(status, MatchStatus::InProgress) {
+                m.actual_start_date = Some(Utc::now());
+            }
+            if matches!(
+                status,
+                MatchStatus::Completed | MatchStatus::Cancelled | MatchStatus::Forfeited
+            ) {
+                m.actual_end_date = Some(Utc::now());
+            }
+        })
+        .await
+    }
+
+    async fn start_match(&self, match_id: Uuid) -> Result<Option<Match>, AppError> {
+        self.modify(match_id, |m| {
+            m.match_status = MatchStatus::InProgress;
+            m.actual_start_date = Some(Utc::now());
+        })
+        .await
+    }
+
+    async fn complete_match(
+        &self,
+        match_id: Uuid,
+        winner: i32,
+        is_draw: bool,
+    ) -> Result<Option<Match>, AppError> {
+        self.modify(match_id, |m| {
+            m.match_status = MatchStatus::Completed;
+            m.actual_end_date = Some(Utc::now());
+            m.winner_participant = Some(winner);
+            m.is_draw = is_draw;
+        })
+        .await
+    }
+
+    async fn cancel_match(&self, match_id: Uuid, reason: &str) -> Result<Option<Match>, AppError> {
+        let notes = format!("Cancelled: {}", reason);
+        self.modify(match_id, |m| {
+            m.match_status = MatchStatus::Cancelled;
+            m.actual_end_date = Some(Utc::now());
+            m.notes = Some(notes);
+        })
+        .await
+    }
+
+    async fn forfeit_match(
+        &self,
+        match_id: Uuid,
+        forfeit: &Forfeit,
+    ) -> Result<Option<Match>, AppError> {
+        self.modify(match_id, |m| {
+            m.match_status = MatchStatus::Forfeited;
+            m.winner_participant = forfeit.winner();
+            m.is_draw = false;
+            m.forfeit_kind = Some(forfeit.kind);
+            m.defaulting_participant = forfeit.defaulting_participant;
+            m.actual_end_date = Some(Utc::now());
+            m.notes = Some(forfeit.note());
+        })
+        .await
+    }
+
+    async fn assign_participant(
+        &self,
+        match_id: Uuid,
+        side: i32,
+        team_id: Option<Uuid>,
+        player_id: Option<Uuid>,
+        partner_id: Option<Uuid>,
+    ) -> Result<Option<Match>, AppError> {
+        if !(1..=2).contains(&side) {
+            return Err(AppError::ValidationError(
+                "Participant must be 1 or 2".into(),
+            ));
+        }
+        self.modify(match_id, |m| {
+            if side == 1 {
+                m.participant1_team_id = team_id;
+                m.participant1_player_id = player_id;
+                m.participant1_partner_id = partner_id;
+            } else {
+                m.participant2_team_id = team_id;
+                m.participant2_player_id = player_id;
+                m.participant2_partner_id = partner_id;
+            }
+        })
+        .await
+    }
+
+    async fn postpone_match(&self, match_id: Uuid) -> Result<Option<Match>, AppError> {
+        self.modify(match_id, |m| m.match_status = MatchStatus::Postponed)
+            .await
+    }
+
+    async fn reschedule_match(
+        &self,
+        match_id: Uuid,
+        request: RescheduleMatchRequest,
+    ) -> Result<Option<Match>, AppError> {
+        self.modify(match_id, |m| {
+            m.scheduled_date = request.new_scheduled_date;
+            if let Some(venue) = request.new_venue {
+                m.venue = Some(venue);
+            }
+            if let Some(court_number) = request.new_court_number {
+                m.court_number = Some(court_number);
+            }
+            if let Some(court_id) = request.new_court_id {
+                m.court_id = Some(court_id);
+            }
+            if let Some(reason) = request.reason {
+                m.notes = Some(format!("Rescheduled: {}", reason));
+            }
+        })
+        .await
+    }
+
+    async fn find_user_upcoming_matches(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<MatchScheduleItem>, AppError> {
+        let now = Utc::now();
+        self.schedule_items(
+            move |tables, m| {
+                matches!(
+                    m.match_status,
+                    MatchStatus::Scheduled | MatchStatus::InProgress
+                ) && m.scheduled_date >= now
+                    && involves_user(tables, m, user_id)
+            },
+            |a, b| a.scheduled_date.cmp(&b.scheduled_date),
+        )
+        .await
+    }
+
+    async fn find_user_match_history(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<MatchScheduleItem>, AppError> {
+        self.schedule_items(
+            move |tables, m| {
+                matches!(
+                    m.match_status,
+                    MatchStatus::Completed | MatchStatus::Cancelled | MatchStatus::Forfeited
+                ) && involves_user(tables, m, user_id)
+            },
+            |a, b| {
+                b.actual_end_date
+                    .cmp(&a.actual_end_date)
+                    .then_with(|| b.scheduled_date.cmp(&a.scheduled_date))
+            },
+        )
+        .await
+    }
+
+    async fn find_live_matches(&self) -> Result<Vec<Match>, AppError> {
+        let tables = self.db.tables().await?;
+        let mut matches: Vec<Match> = tables
+            .matches
+            .iter()
+            .filter(|m| m.match_status == MatchStatus::InProgress)
+            .cloned()
+            .collect();
+        matches.sort_by(|a, b| nulls_last(&a.actual_start_date, &b.actual_start_date));
+        Ok(matches)
+    }
+
+    async fn lock(&self, _match_id: Uuid) -> Result<(), AppError> {
+        // A unit of work already holds the whole store
+        Ok(())
+    }
+
+    async fn update_live_match(
+        &self,
+        match_id: Uuid,
+        update: LiveMatchUpdate,
+    ) -> Result<Option<Match>, AppError> {
+        let metadata = serde_json::json!({
+            "live_update": {
+                "current_score": update.current_score,
+                "game_time": update.game_time,
+                "current_set": update.current_set,
+                "timestamp": Utc::now()
+            },
+            "existing": update.metadata
+        });
+
+        self.modify(match_id, |m| {
+            m.metadata = Some(metadata);
+            if let Some(notes) = update.notes {
+                m.notes = Some(notes);
+            }
+        })
+        .await
+    }
+
+    async fn get_match_analytics(
+        &self,
+        match_id: Uuid,
+    ) -> Result<Option<MatchAnalytics>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables
+            .matches
+            .iter()
+            .find(|m| m.id == match_id && m.match_status == MatchStatus::Completed)
+            .map(|m| {
+                let duration = m
+                    .actual_start_date
+                    .zip(m.actual_end_date)
+                    .map(|(start, end)| ((end - start).num_seconds() / 60) as i32);
+                MatchAnalytics {
+                    match_id: m.id,
+                    total_duration_minutes: duration,
+                    sets_played: Some(1),
+                    participant1_score: Some(serde_json::json!({})),
+                    participant2_score: Some(serde_json::json!({})),
+                    rally_stats: Some(serde_json::json!({})),
+                    performance_metrics: Some(serde_json::json!({})),
+                }
+            }))
+    }
+
+    async fn get_match_statistics(
+        &self,
+        match_id: Uuid,
+    ) -> Result<Option<MatchStatistics>, AppError> {
+        let tables = self.db.tables().await?;
+        if !tables.matches.iter().any(|m| m.id == match_id) {
+            return Ok(None);
+        }
+
+        let results: Vec<&MatchResult> = tables
+            .match_results
+            .iter()
+            .filter(|r| r.match_id == match_id)
+            .collect();
+        let summary = score_summary(match_id, &results);
+        let now = Utc::now();
+
+        Ok(Some(MatchStatistics {
+            match_id,
+            statistics: serde_json::json!({
+                "participant1_sets_won": summary.participant1_sets_won,
+                "participant2_sets_won": summary.participant2_sets_won,
+                "participant1_total_points": summary.participant1_total_points,
+                "participant2_total_points": summary.participant2_total_points,
+                "sets_played": results.len(),
+            }),
+            created_at: results.iter().map(|r| r.created_at).min().unwrap_or(now),
+            updated_at: results.iter().map(|r| r.updated_at).max().unwrap_or(now),
+        }))
+    }
+
+    async fn get_match_media(&self, match_id: Uuid) -> Result<Vec<MatchMedia>, AppError> {
+        let tables = self.db.tables().await?;
+        let mut media: Vec<MatchMedia> = tables
+            .match_media
+            .iter()
+            .filter(|m| m.match_id == match_id)
+            .cloned()
+            .collect();
+        media.sort_by_key(|m| m.created_at);
+        Ok(media)
+    }
+
+    async fn upload_match_media(
+        &self,
+        match_id: Uuid,
+        user_id: Uuid,
+        media_type: &str,
+        file_url: &str,
+    ) -> Result<MatchMedia, AppError> {
+        let media = MatchMedia {
+            id: Uuid::new_v4(),
+            match_id,
+            media_type: media_type.to_string(),
+            file_url: file_url.to_string(),
+            thumbnail_url: None,
+            file_size: None,
+            duration: None,
+            uploaded_by: user_id,
+            created_at: Utc::now(),
+        };
+        self.db.tables().await?.match_media.push(media.clone());
+        Ok(media)
+    }
+
+    async fn get_match_comments(&self, match_id: Uuid) -> Result<Vec<MatchComment>, AppError> {
+        let tables = self.db.tables().await?;
+        let mut comments: Vec<MatchComment> = tables
+            .match_comments
+            .iter()
+            .filter(|c| c.match_id == match_id)
+            .cloned()
+            .collect();
+        comments.sort_by_key(|c| c.created_at);
+        Ok(comments)
+    }
+
+    async fn add_match_comment(
+        &self,
+        match_id: Uuid,
+        user_id: Uuid,
+        comment: &str,
+    ) -> Result<MatchComment, AppError> {
+        let now = Utc::now();
+        let comment = MatchComment {
+            id: Uuid::new_v4(),
+            match_id,
+            user_id,
+            comment: comment.to_string(),
+            created_at: now,
+            updated_at: now,
+        };
+        self.db.tables().await?.match_comments.push(comment.clone());
+        Ok(comment)
+    }
+
+    async fn subscribe_to_match(
+        &self,
+        match_id: Uuid,
+        user_id: Uuid,
+        notification_preferences: serde_json::Value,
+    ) -> Result<MatchSubscription, AppError> {
+        let mut tables = self.db.tables().await?;
+        let now = Utc::now();
+        if let Some(existing) = tables
+            .match_subscriptions
+            .iter_mut()
+            .find(|s| s.match_id == match_id && s.user_id == user_id)
+        {
+            existing.notification_preferences = notification_preferences;
+            existing.created_at = now;
+            return Ok(existing.clone());
+        }
+
+        let subscription = MatchSubscription {
+            id: Uuid::new_v4(),
+            match_id,
+            user_id,
+            notification_preferences,
+            created_at: now,
+        };
+        tables.match_subscriptions.push(subscription.clone());
+        Ok(subscription)
+    }
+
+    async fn unsubscribe_from_match(&self, match_id: Uuid, user_id: Uuid) -> Result<(), AppError> {
+        self.db
+            .tables()
+            .await?
+            .match_subscriptions
+            .retain(|s| !(s.match_id == match_id && s.user_id == user_id));
+        Ok(())
+    }
+
+    async fn find_subscriptions(&self, match_id: Uuid) -> Result<Vec<MatchSubscription>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables
+            .match_subscriptions
+            .iter()
+            .filter(|s| s.match_id == match_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn find_participant_users(&self, match_id: Uuid) -> Result<Vec<Uuid>, AppError> {
+        let tables = self.db.tables().await?;
+        let Some(m) = tables.matches.iter().find(|m| m.id == match_id) else {
+            return Ok(vec![]);
+        };
+        let teams = [m.participant1_team_id, m.participant2_team_id];
+        let mut player_ids: Vec<Uuid> = [
+            m.participant1_player_id,
+            m.participant1_partner_id,
+            m.participant2_player_id,
+            m.participant2_partner_id,
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        player_ids.extend(
+            tables
+                .team_members
+                .iter()
+                .filter(|tm| teams.contains(&Some(tm.team_id)))
+                .map(|tm| tm.player_id),
+        );
+
+        let mut users: Vec<Uuid> = tables
+            .players
+            .iter()
+            .filter(|p| player_ids.contains(&p.id))
+            .filter_map(|p| p.user_id)
+            .collect();
+        users.sort();
+        users.dedup();
+        Ok(users)
+    }
+
+    async fn bulk_update_matches(
+        &self,
+        match_ids: Vec<Uuid>,
+        updates: EditableMatch,
+    ) -> Result<Vec<Match>, AppError> {
+        let mut updated_matches = Vec::new();
+
+        for match_id in match_ids {
+            if let Some(updated_match) = self.update(match_id, updates.clone()).await? {
+                updated_matches.push(updated_match);
+            }
+        }
+
+        Ok(updated_matches)
+    }
+
+    async fn bulk_cancel_matches(
+        &self,
+        match_ids: Vec<Uuid>,
+        reason: &str,
+    ) -> Result<Vec<Match>, AppError> {
+        let mut cancelled_matches = Vec::new();
+
+        for match_id in match_ids {
+            if let Some(cancelled_match) = self.cancel_match(match_id, reason).await? {
+                cancelled_matches.push(cancelled_match);
+            }
+        }
+
+        Ok(cancelled_matches)
+    }
+}
+
+// ==================== Match Result Repository ====================
+
+fn score_summary(match_id: Uuid, results: &[&MatchResult]) -> MatchScoreSummary {
+    let won = |a: Option<i32>, b: Option<i32>| matches!((a, b), (Some(a), Some(b)) if a > b);
+    MatchScoreSummary {
+        match_id,
+        participant1_sets_won: results
+            .iter()
+            .filter(|r| won(r.participant1_score, r.participant2_score))
+            .count() as i64,
+        participant2_sets_won: results
+            .iter()
+            .filter(|r| won(r.participant2_score, r.participant1_score))
+            .count() as i64,
+        participant1_total_points: results
+            .iter()
+            .filter_map(|r| r.participant1_score)
+            .map(i64::from)
+            .sum(),
+        participant2_total_points: results
+            .iter()
+            .filter_map(|r| r.participant2_score)
+            .map(i64::from)
+            .sum(),
+    }
+}
+
+pub struct InMemoryMatchResultRepository {
+    db: MemoryHandle,
+}
+
+impl InMemoryMatchResultRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self {
+            db: MemoryHandle::Store(store),
+        }
+    }
+
+    /// Repository bound to a unit of work's transaction
+    pub(crate) fn with_handle(db: MemoryHandle) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl MatchResultRepository for InMemoryMatchResultRepository {
+    async fn create(&self, new_result: NewMatchResult) -> Result<MatchResult, AppError> {
+        let mut tables = self.db.tables().await?;
+        if !tables.matches.iter().any(|m| m.id == new_result.match_id) {
+            return Err(foreign_key_violation("match_results"));
+        }
+
+        let now = Utc::now();
+        let result = MatchResult {
+            id: Uuid::new_v4(),
+            match_id: new_result.match_id,
+            set_number: new_result.set_number,
+            participant1_score: new_result.participant1_score,
+            participant2_score: new_result.participant2_score,
+            period_number: new_result.period_number,
+            period_name: new_result.period_name,
+            scoring_data: new_result.scoring_data,
+            participant1_stats: new_result.participant1_stats,
+            participant2_stats: new_result.participant2_stats,
+            created_at: now,
+            updated_at: now,
+        };
+        tables.match_results.push(result.clone());
+        Ok(result)
+    }
+
+    async fn find_by_id(&self, result_id: Uuid) -> Result<Option<MatchResult>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables
+            .match_results
+            .iter()
+            .find(|r| r.id == result_id)
+            .cloned())
+    }
+
+    async fn update(
+        &self,
+        result_id: Uuid,
+        result_data: EditableMatchResult,
+    ) -> Result<Option<MatchResult>, AppError> {
+        let mut tables = self.db.tables().await?;
+        Ok(tables
+            .match_results
+            .iter_mut()
+            .find(|r| r.id == result_id)
+            .map(|result| {
+                if let Some(score) = result_data.participant1_score {
+                    result.participant1_score = Some(score);
+                }
+                if let Some(score) = result_data.participant2_score {
+                    result.participant2_score = Some(score);
+                }
+                if let Some(scoring_data) = result_data.scoring_data {
+                    result.scoring_data = Some(scoring_data);
+                }
+                if let Some(stats) = result_data.participant1_stats {
+                    result.participant1_stats = Some(stats);
+                }
+                if let Some(stats) = result_data.participant2_stats {
+                    result.participant2_stats = Some(stats);
+                }
+                result.updated_at = Utc::now();
+                result.clone()
+            }))
+    }
+
+    async fn delete(&self, result_id: Uuid) -> Result<Option<MatchResult>, AppError> {
+        let mut tables = self.db.tables().await?;
+        let index = tables.match_results.iter().position(|r| r.id == result_id);
+        tables.scoring_sequences.retain(|s| s.2 != result_id);
+        Ok(index.map(|index| tables.match_results.remove(index)))
+    }
+
+    async fn find_by_match(&self, match_id: Uuid) -> Result<Vec<MatchResult>, AppError> {
+        let tables = self.db.tables().await?;
+        let mut results: Vec<MatchResult> = tables
+            .match_results
+            .iter()
+            .filter(|r| r.match_id == match_id)
+            .cloned()
+            .collect();
+        results.sort_by(|a, b| {
+            nulls_last(&a.set_number, &b.set_number)
+                .then_with(|| nulls_last(&a.period_number, &b.period_number))
+                .then_with(|| a.created_at.cmp(&b.created_at))
+        });
+        Ok(results)
+    }
+
+    async fn find_by_category(&self, category_id: Uuid) -> Result<Vec<MatchResult>, AppError> {
+        let tables = self.db.tables().await?;
+        let match_ids: HashSet<Uuid> = tables
+            .matches
+            .iter()
+            .filter(|m| m.tournament_category_id == category_id)
+            .map(|m| m.id)
+            .collect();
+        let mut results: Vec<MatchResult> = tables
+            .match_results
+            .iter()
+            .filter(|r| match_ids.contains(&r.match_id))
+            .cloned()
+            .collect();
+        results.sort_by(|a, b| {
+            a.match_id
+                .cmp(&b.match_id)
+                .then_with(|| nulls_last(&a.set_number, &b.set_number))
+                .then_with(|| nulls_last(&a.period_number, &b.period_number))
+                .then_with(|| a.created_at.cmp(&b.created_at))
+        });
+        Ok(results)
+    }
+
+    async fn get_match_score_summary(
+        &self,
+        match_id: Uuid,
+    ) -> Result<Option<MatchScoreSummary>, AppError> {
+        let tables = self.db.tables().await?;
+        let results: Vec<&MatchResult> = tables
+            .match_results
+            .iter()
+            .filter(|r| r.match_id == match_id)
+            .collect();
+        if results.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(score_summary(match_id, &results)))
+    }
+
+    async fn find_by_set(
+        &self,
+        match_id: Uuid,
+        set_number: i32,
+    ) -> Result<Vec<MatchResult>, AppError> {
+        let tables = self.db.tables().await?;
+        let mut results: Vec<MatchResult> = tables
+            .match_results
+            .iter()
+            .filter(|r| r.match_id == match_id && r.set_number == Some(set_number))
+            .cloned()
+            .collect();
+        results.sort_by(|a, b| {
+            nulls_last(&a.period_number, &b.period_number)
+                .then_with(|| a.created_at.cmp(&b.created_at))
+        });
+        Ok(results)
+    }
+
+    async fn delete_by_match(&self, match_id: Uuid) -> Result<u64, AppError> {
+        let mut tables = self.db.tables().await?;
+        let before = tables.match_results.len();
+        tables.match_results.retain(|r| r.match_id != match_id);
+        tables.scoring_sequences.retain(|s| s.0 != match_id);
+        Ok((before - tables.match_results.len()) as u64)
+    }
+
+    async fn count_by_match(&self, match_id: Uuid) -> Result<i64, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables
+            .match_results
+            .iter()
+            .filter(|r| r.match_id == match_id)
+            .count() as i64)
+    }
+
+    async fn claim_scoring_sequence(
+        &self,
+        match_id: Uuid,
+        sequence: i64,
+        result_id: Uuid,
+    ) -> Result<bool, AppError> {
+        let mut tables = self.db.tables().await?;
+        if !tables.match_results.iter().any(|r| r.id == result_id) {
+            return Err(foreign_key_violation("match_scoring_sequences"));
+        }
+        if tables
+            .scoring_sequences
+            .iter()
+            .any(|s| s.0 == match_id && s.1 == sequence)
+        {
+            return Ok(false);
+        }
+        tables
+            .scoring_sequences
+            .push((match_id, sequence, result_id));
+        Ok(true)
+    }
+}
@@ -0,0 +1,46 @@
+// In-memory infrastructure - repository implementations without a database
+
+pub mod store;
+
+pub mod check_in_repo;
+pub mod court_call_repo;
+pub mod import_repo;
+pub mod match_repo;
+pub mod notification_repo;
+pub mod outbox_repo;
+pub mod participant_repo;
+pub mod payment_repo;
+pub mod statistics_repo;
+pub mod ticket_repo;
+pub mod tournament_repo;
+pub mod unit_of_work;
+pub mod user_repo;
+pub mod venue_repo;
+
+// Re-exports
+pub use check_in_repo::InMemoryCheckInWindowRepository;
+pub use court_call_repo::InMemoryCourtCallRepository;
+pub use import_repo::InMemoryImportRepository;
+pub use match_repo::{InMemoryMatchRepository, InMemoryMatchResultRepository};
+pub use notification_repo::{
+    InMemoryNotificationDeliveryRepository, InMemoryNotificationRepository,
+    InMemoryPushSubscriptionRepository,
+};
+pub use outbox_repo::InMemoryOutboxRepository;
+pub use participant_repo::{
+    InMemoryPlayerRepository, InMemoryTeamMemberRepository, InMemoryTeamRepository,
+};
+pub use payment_repo::InMemoryPaymentRepository;
+pub use statistics_repo::InMemoryStatisticsRepository;
+pub use store::MemoryStore;
+pub use ticket_repo::InMemoryMatchTicketRepository;
+pub use tournament_repo::{
+    InMemoryTournamentBracketRepository, InMemoryTournamentCategoryRepository,
+    InMemoryTournamentRegistrationRepository, InMemoryTournamentRepository,
+    InMemoryTournamentStandingsRepository,
+};
+pub use unit_of_work::InMemoryUnitOfWorkFactory;
+pub use user_repo::{
+    InMemoryTokenRepository, InMemoryUserProfileRepository, InMemoryUserRepository,
+};
+pub use venue_repo::InMemoryVenueRepository;
@@ -0,0 +1,325 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use std::cmp::Reverse;
+use uuid::Uuid;
+
+use crate::domain::notification::{
+    DeliveryAttempt, DeliveryChannel, DeliveryStatus, NewNotification, NewPushSubscription,
+    Notification, NotificationDelivery, NotificationDeliveryRepository, NotificationRepository,
+    PushSubscription, PushSubscriptionRepository,
+};
+use crate::shared::AppError;
+
+use super::store::{paginate, MemoryHandle, MemoryStore};
+
+fn new_notification_row(new_notification: NewNotification) -> Notification {
+    let now = Utc::now();
+    Notification {
+        id: Uuid::new_v4(),
+        user_id: new_notification.user_id,
+        title: new_notification.title,
+        message: new_notification.message,
+        notification_type: new_notification.notification_type,
+        is_read: false,
+        tournament_id: new_notification.tournament_id,
+        match_id: new_notification.match_id,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+pub struct InMemoryNotificationRepository {
+    db: MemoryHandle,
+}
+
+impl InMemoryNotificationRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self {
+            db: MemoryHandle::Store(store),
+        }
+    }
+
+    /// Repository bound to a unit of work's transaction
+    pub(crate) fn with_handle(db: MemoryHandle) -> Self {
+        Self { db }
+    }
+
+    async fn find_for_user(
+        &self,
+        user_id: Uuid,
+        unread_only: bool,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Notification>, AppError> {
+        let tables = self.db.tables().await?;
+        let mut notifications: Vec<Notification> = tables
+            .notifications
+            .iter()
+            .filter(|n| n.user_id == user_id && !(unread_only && n.is_read))
+            .cloned()
+            .collect();
+        notifications.sort_by_key(|n| Reverse(n.created_at));
+        Ok(paginate(notifications, limit, offset))
+    }
+}
+
+#[async_trait]
+impl NotificationRepository for InMemoryNotificationRepository {
+    async fn create(&self, new_notification: NewNotification) -> Result<Notification, AppError> {
+        let notification = new_notification_row(new_notification);
+        self.db
+            .tables()
+            .await?
+            .notifications
+            .push(notification.clone());
+        Ok(notification)
+    }
+
+    async fn get_by_user_id(
+        &self,
+        user_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Notification>, AppError> {
+        self.find_for_user(user_id, false, limit, offset).await
+    }
+
+    async fn get_unread_by_user_id(
+        &self,
+        user_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Notification>, AppError> {
+        self.find_for_user(user_id, true, limit, offset).await
+    }
+
+    async fn mark_as_read(&self, notification_id: Uuid) -> Result<Option<Notification>, AppError> {
+        let mut tables = self.db.tables().await?;
+        Ok(tables
+            .notifications
+            .iter_mut()
+            .find(|n| n.id == notification_id)
+            .map(|notification| {
+                notification.is_read = true;
+                notification.updated_at = Utc::now();
+                notification.clone()
+            }))
+    }
+
+    async fn mark_all_as_read(&self, user_id: Uuid) -> Result<u64, AppError> {
+        let mut tables = self.db.tables().await?;
+        let now = Utc::now();
+        let mut updated = 0;
+        for notification in tables
+            .notifications
+            .iter_mut()
+            .filter(|n| n.user_id == user_id && !n.is_read)
+        {
+            notification.is_read = true;
+            notification.updated_at = now;
+            updated += 1;
+        }
+        Ok(updated)
+    }
+
+    async fn delete(&self, notification_id: Uuid) -> Result<Option<Notification>, AppError> {
+        let mut tables = self.db.tables().await?;
+        let index = tables
+            .notifications
+            .iter()
+            .position(|n| n.id == notification_id);
+        Ok(index.map(|index| tables.notifications.remove(index)))
+    }
+
+    async fn get_unread_count(&self, user_id: Uuid) -> Result<i64, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables
+            .notifications
+            .iter()
+            .filter(|n| n.user_id == user_id && !n.is_read)
+            .count() as i64)
+    }
+
+    async fn create_bulk(
+        &self,
+        notifications: Vec<NewNotification>,
+    ) -> Result<Vec<Notification>, AppError> {
+        let created: Vec<Notification> = notifications
+            .into_iter()
+            .map(new_notification_row)
+            .collect();
+        self.db
+            .tables()
+            .await?
+            .notifications
+            .extend(created.iter().cloned());
+        Ok(created)
+    }
+
+    async fn get_by_id(&self, notification_id: Uuid) -> Result<Option<Notification>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables
+            .notifications
+            .iter()
+            .find(|n| n.id == notification_id)
+            .cloned())
+    }
+
+    async fn claim_dedup_key(&self, user_id: Uuid, key: &str) -> Result<bool, AppError> {
+        let mut tables = self.db.tables().await?;
+        Ok(tables
+            .notification_dedup_keys
+            .insert((user_id, key.to_string())))
+    }
+}
+
+// ==================== Delivery Repository ====================
+
+pub struct InMemoryNotificationDeliveryRepository {
+    db: MemoryHandle,
+}
+
+impl InMemoryNotificationDeliveryRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self {
+            db: MemoryHandle::Store(store),
+        }
+    }
+}
+
+#[async_trait]
+impl NotificationDeliveryRepository for InMemoryNotificationDeliveryRepository {
+    async fn record(&self, attempt: DeliveryAttempt) -> Result<NotificationDelivery, AppError> {
+        let mut tables = self.db.tables().await?;
+        let now = Utc::now();
+        let delivered_at = (attempt.status == DeliveryStatus::Sent).then_some(now);
+        if let Some(delivery) = tables
+            .notification_deliveries
+            .iter_mut()
+            .find(|d| d.notification_id == attempt.notification_id && d.channel == attempt.channel)
+        {
+            delivery.status = attempt.status;
+            delivery.recipient = attempt.recipient;
+            delivery.last_error = attempt.error;
+            delivery.delivered_at = delivered_at.or(delivery.delivered_at);
+            delivery.attempts += 1;
+            delivery.updated_at = now;
+            return Ok(delivery.clone());
+        }
+        let delivery = NotificationDelivery {
+            id: Uuid::new_v4(),
+            notification_id: attempt.notification_id,
+            channel: attempt.channel,
+            status: attempt.status,
+            recipient: attempt.recipient,
+            attempts: 1,
+            last_error: attempt.error,
+            delivered_at,
+            created_at: now,
+            updated_at: now,
+        };
+        tables.notification_deliveries.push(delivery.clone());
+        Ok(delivery)
+    }
+
+    async fn find(
+        &self,
+        notification_id: Uuid,
+        channel: DeliveryChannel,
+    ) -> Result<Option<NotificationDelivery>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables
+            .notification_deliveries
+            .iter()
+            .find(|d| d.notification_id == notification_id && d.channel == channel)
+            .cloned())
+    }
+
+    async fn find_by_notification(
+        &self,
+        notification_id: Uuid,
+    ) -> Result<Vec<NotificationDelivery>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables
+            .notification_deliveries
+            .iter()
+            .filter(|d| d.notification_id == notification_id)
+            .cloned()
+            .collect())
+    }
+}
+
+// ==================== Push Subscription Repository ====================
+
+pub struct InMemoryPushSubscriptionRepository {
+    db: MemoryHandle,
+}
+
+impl InMemoryPushSubscriptionRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self {
+            db: MemoryHandle::Store(store),
+        }
+    }
+}
+
+#[async_trait]
+impl PushSubscriptionRepository for InMemoryPushSubscriptionRepository {
+    async fn upsert(
+        &self,
+        subscription: NewPushSubscription,
+    ) -> Result<PushSubscription, AppError> {
+        let mut tables = self.db.tables().await?;
+        let now = Utc::now();
+        if let Some(existing) = tables
+            .push_subscriptions
+            .iter_mut()
+            .find(|s| s.endpoint == subscription.endpoint)
+        {
+            existing.user_id = subscription.user_id;
+            existing.p256dh = subscription.p256dh;
+            existing.auth = subscription.auth;
+            existing.user_agent = subscription.user_agent;
+            existing.updated_at = now;
+            return Ok(existing.clone());
+        }
+        let created = PushSubscription {
+            id: Uuid::new_v4(),
+            user_id: subscription.user_id,
+            endpoint: subscription.endpoint,
+            p256dh: subscription.p256dh,
+            auth: subscription.auth,
+            user_agent: subscription.user_agent,
+            created_at: now,
+            updated_at: now,
+        };
+        tables.push_subscriptions.push(created.clone());
+        Ok(created)
+    }
+
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<PushSubscription>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables
+            .push_subscriptions
+            .iter()
+            .filter(|s| s.user_id == user_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn delete_for_user(&self, user_id: Uuid, endpoint: &str) -> Result<bool, AppError> {
+        let mut tables = self.db.tables().await?;
+        let before = tables.push_subscriptions.len();
+        tables
+            .push_subscriptions
+            .retain(|s| !(s.user_id == user_id && s.endpoint == endpoint));
+        Ok(tables.push_subscriptions.len() < before)
+    }
+
+    async fn delete_by_endpoint(&self, endpoint: &str) -> Result<bool, AppError> {
+        let mut tables = self.db.tables().await?;
+        let before = tables.push_subscriptions.len();
+        tables.push_subscriptions.retain(|s| s.endpoint != endpoint);
+        Ok(tables.push_subscriptions.len() < before)
+    }
+}
@@ -0,0 +1,117 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::domain::outbox::{DomainEvent, OutboxMessage, OutboxRepository, OutboxStatus};
+use crate::shared::AppError;
+
+use super::store::{MemoryHandle, MemoryStore};
+
+pub struct InMemoryOutboxRepository {
+    db: MemoryHandle,
+}
+
+impl InMemoryOutboxRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self {
+            db: MemoryHandle::Store(store),
+        }
+    }
+
+    /// Repository bound to a unit of work's transaction
+    pub(crate) fn with_handle(db: MemoryHandle) -> Self {
+        Self { db }
+    }
+
+    async fn modify(
+        &self,
+        id: Uuid,
+        apply: impl FnOnce(&mut OutboxMessage) + Send,
+    ) -> Result<(), AppError> {
+        let mut tables = self.db.tables().await?;
+        if let Some(message) = tables.outbox.iter_mut().find(|m| m.id == id) {
+            apply(message);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl OutboxRepository for InMemoryOutboxRepository {
+    async fn enqueue(&self, event: &DomainEvent) -> Result<OutboxMessage, AppError> {
+        let payload = serde_json::to_value(event)
+            .map_err(|e| AppError::InternalError(format!("Unserializable event: {}", e)))?;
+        let now = Utc::now();
+        let message = OutboxMessage {
+            id: Uuid::new_v4(),
+            event_type: event.event_type().to_string(),
+            payload,
+            status: OutboxStatus::Pending,
+            attempts: 0,
+            delivered_to: Vec::new(),
+            last_error: None,
+            available_at: now,
+            created_at: now,
+            processed_at: None,
+        };
+        self.db.tables().await?.outbox.push(message.clone());
+        Ok(message)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<OutboxMessage>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables.outbox.iter().find(|m| m.id == id).cloned())
+    }
+
+    async fn claim_due(
+        &self,
+        limit: i64,
+        lease_until: DateTime<Utc>,
+    ) -> Result<Vec<OutboxMessage>, AppError> {
+        let now = Utc::now();
+        let mut tables = self.db.tables().await?;
+        // Insertion order is creation order
+        Ok(tables
+            .outbox
+            .iter_mut()
+            .filter(|m| m.status == OutboxStatus::Pending && m.available_at <= now)
+            .take(limit.max(0) as usize)
+            .map(|m| {
+                m.attempts += 1;
+                m.available_at = lease_until;
+                m.clone()
+            })
+            .collect())
+    }
+
+    async fn mark_processed(&self, id: Uuid, delivered_to: &[String]) -> Result<(), AppError> {
+        let delivered_to = delivered_to.to_vec();
+        self.modify(id, |m| {
+            m.status = OutboxStatus::Processed;
+            m.delivered_to = delivered_to;
+            m.last_error = None;
+            m.processed_at = Some(Utc::now());
+        })
+        .await
+    }
+
+    async fn mark_failed(
+        &self,
+        id: Uuid,
+        delivered_to: &[String],
+        error: &str,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<(), AppError> {
+        let delivered_to = delivered_to.to_vec();
+        let error = error.to_string();
+        self.modify(id, |m| {
+            m.delivered_to = delivered_to;
+            m.last_error = Some(error);
+            match retry_at {
+                Some(at) => m.available_at = at,
+                None => m.status = OutboxStatus::Failed,
+            }
+        })
+        .await
+    }
+}
@@ -0,0 +1,272 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::domain::participant::{
+    CreatePlayer, EditablePlayer, EditableTeam, EditableTeamMember, NewTeam, NewTeamMember, Player,
+    PlayerRepository, Team, TeamMember, TeamMemberRepository, TeamPlayer, TeamRepository,
+    TeamWithMembers,
+};
+use crate::shared::AppError;
+
+use super::store::{foreign_key_violation, unique_violation, MemoryHandle, MemoryStore, Tables};
+
+/// Enforces the `players` UNIQUE constraints on name and user_id
+pub(crate) fn check_player_unique(
+    tables: &Tables,
+    player_id: Option<Uuid>,
+    name: &str,
+    user_id: Option<Uuid>,
+) -> Result<(), AppError> {
+    let others = || tables.players.iter().filter(|p| Some(p.id) != player_id);
+    if others().any(|p| p.name == name) {
+        return Err(unique_violation("players_name_key"));
+    }
+    if user_id.is_some() && others().any(|p| p.user_id == user_id) {
+        return Err(unique_violation("players_user_id_key"));
+    }
+    Ok(())
+}
+
+// ==================== Player Repository ====================
+
+pub struct InMemoryPlayerRepository {
+    db: MemoryHandle,
+}
+
+impl InMemoryPlayerRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self {
+            db: MemoryHandle::Store(store),
+        }
+    }
+}
+
+#[async_trait]
+impl PlayerRepository for InMemoryPlayerRepository {
+    async fn find_all(&self) -> Result<Vec<Player>, AppError> {
+        Ok(self.db.tables().await?.players.clone())
+    }
+
+    async fn find_by_id(&self, player_id: Uuid) -> Result<Option<Player>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables.players.iter().find(|p| p.id == player_id).cloned())
+    }
+
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Option<Player>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables
+            .players
+            .iter()
+            .find(|p| p.user_id == Some(user_id))
+            .cloned())
+    }
+
+    async fn create(&self, new_player: CreatePlayer) -> Result<Player, AppError> {
+        let mut tables = self.db.tables().await?;
+        check_player_unique(&tables, None, &new_player.name, new_player.user_id)?;
+
+        let player = Player {
+            id: Uuid::new_v4(),
+            name: new_player.name,
+            user_id: new_player.user_id,
+            created_at: Utc::now(),
+        };
+        tables.players.push(player.clone());
+        Ok(player)
+    }
+
+    async fn update(
+        &self,
+        player_id: Uuid,
+        player_data: EditablePlayer,
+    ) -> Result<Option<Player>, AppError> {
+        let mut tables = self.db.tables().await?;
+        if !tables.players.iter().any(|p| p.id == player_id) {
+            return Ok(None);
+        }
+        check_player_unique(
+            &tables,
+            Some(player_id),
+            &player_data.name,
+            player_data.user_id,
+        )?;
+
+        Ok(tables
+            .players
+            .iter_mut()
+            .find(|p| p.id == player_id)
+            .map(|player| {
+                player.name = player_data.name;
+                player.user_id = player_data.user_id;
+                player.clone()
+            }))
+    }
+
+    async fn delete(&self, player_id: Uuid) -> Result<Option<Player>, AppError> {
+        let mut tables = self.db.tables().await?;
+        let player = tables.players.iter().find(|p| p.id == player_id).cloned();
+        if player.is_some() {
+            tables.delete_player_cascade(player_id);
+        }
+        Ok(player)
+    }
+}
+
+// ==================== Team Repository ====================
+
+pub struct InMemoryTeamRepository {
+    db: MemoryHandle,
+}
+
+impl InMemoryTeamRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self {
+            db: MemoryHandle::Store(store),
+        }
+    }
+}
+
+#[async_trait]
+impl TeamRepository for InMemoryTeamRepository {
+    async fn find_all(&self) -> Result<Vec<Team>, AppError> {
+        Ok(self.db.tables().await?.teams.clone())
+    }
+
+    async fn find_by_id(&self, team_id: Uuid) -> Result<Option<TeamWithMembers>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables
+            .teams
+            .iter()
+            .find(|t| t.id == team_id)
+            .map(|team| TeamWithMembers {
+                team: team.clone(),
+                members: tables.team_players(team_id),
+            }))
+    }
+
+    async fn create(&self, new_team: NewTeam) -> Result<Team, AppError> {
+        let team = Team {
+            id: Uuid::new_v4(),
+            name: new_team.name,
+            created_at: Utc::now(),
+        };
+        self.db.tables().await?.teams.push(team.clone());
+        Ok(team)
+    }
+
+    async fn update(
+        &self,
+        team_id: Uuid,
+        team_data: EditableTeam,
+    ) -> Result<Option<Team>, AppError> {
+        let mut tables = self.db.tables().await?;
+        Ok(tables
+            .teams
+            .iter_mut()
+            .find(|t| t.id == team_id)
+            .map(|team| {
+                team.name = team_data.name;
+                team.clone()
+            }))
+    }
+
+    async fn delete(&self, team_id: Uuid) -> Result<Option<Team>, AppError> {
+        let mut tables = self.db.tables().await?;
+        let team = tables.teams.iter().find(|t| t.id == team_id).cloned();
+        if team.is_some() {
+            tables.delete_team_cascade(team_id);
+        }
+        Ok(team)
+    }
+}
+
+// ==================== Team Member Repository ====================
+
+pub struct InMemoryTeamMemberRepository {
+    db: MemoryHandle,
+}
+
+impl InMemoryTeamMemberRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self {
+            db: MemoryHandle::Store(store),
+        }
+    }
+}
+
+#[async_trait]
+impl TeamMemberRepository for InMemoryTeamMemberRepository {
+    async fn create(&self, new_member: NewTeamMember) -> Result<TeamMember, AppError> {
+        let mut tables = self.db.tables().await?;
+        if !tables.teams.iter().any(|t| t.id == new_member.team_id)
+            || !tables.players.iter().any(|p| p.id == new_member.player_id)
+        {
+            return Err(foreign_key_violation("team_members"));
+        }
+
+        let member = TeamMember {
+            team_id: new_member.team_id,
+            player_id: new_member.player_id,
+            is_captain: new_member.is_captain.unwrap_or(false),
+            jersey_number: new_member.jersey_number,
+            joined_at: Utc::now(),
+        };
+        tables.team_members.push(member.clone());
+        Ok(member)
+    }
+
+    async fn get_by_team(&self, team_id: Uuid) -> Result<Vec<TeamPlayer>, AppError> {
+        Ok(self.db.tables().await?.team_players(team_id))
+    }
+
+    async fn get_by_player(&self, player_id: Uuid) -> Result<Vec<TeamMember>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables
+            .team_members
+            .iter()
+            .filter(|m| m.player_id == player_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn get_by_id(
+        &self,
+        team_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<Option<TeamMember>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables
+            .team_members
+            .iter()
+            .find(|m| m.team_id == team_id && m.player_id == player_id)
+            .cloned())
+    }
+
+    async fn update(
+        &self,
+        team_id: Uuid,
+        player_id: Uuid,
+        member_data: EditableTeamMember,
+    ) -> Result<Option<TeamMember>, AppError> {
+        let mut tables = self.db.tables().await?;
+        Ok(tables
+            .team_members
+            .iter_mut()
+            .find(|m| m.team_id == team_id && m.player_id == player_id)
+            .map(|member| {
+                member.is_captain = member_data.is_captain;
+                member.jersey_number = member_data.jersey_number;
+                member.clone()
+            }))
+    }
+
+    async fn delete(&self, team_id: Uuid, player_id: Uuid) -> Result<Option<TeamMember>, AppError> {
+        let mut tables = self.db.tables().await?;
+        let index = tables
+            .team_members
+            .iter()
+            .position(|m| m.team_id == team_id && m.player_id == player_id);
+        Ok(index.map(|index| tables.team_members.remove(index)))
+    }
+}
@@ -0,0 +1,194 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use rust_decimal::Decimal;
+use std::cmp::Reverse;
+use uuid::Uuid;
+
+use crate::domain::payment::{
+    NewPayment, Payment, PaymentRepository, PaymentStatus, PaymentSummary,
+};
+use crate::shared::AppError;
+
+use super::store::{paginate, MemoryHandle, MemoryStore};
+
+fn summarize<'a>(payments: impl Iterator<Item = &'a Payment>) -> PaymentSummary {
+    let mut summary = PaymentSummary {
+        total_payments: 0,
+        total_amount: Decimal::ZERO,
+        successful_payments: 0,
+        successful_amount: Decimal::ZERO,
+        failed_payments: 0,
+        pending_payments: 0,
+    };
+    for payment in payments {
+        summary.total_payments += 1;
+        summary.total_amount += payment.amount;
+        match payment.status {
+            PaymentStatus::Completed => {
+                summary.successful_payments += 1;
+                summary.successful_amount += payment.amount;
+            }
+            PaymentStatus::Failed => summary.failed_payments += 1,
+            PaymentStatus::Pending => summary.pending_payments += 1,
+            _ => {}
+        }
+    }
+    summary
+}
+
+pub struct InMemoryPaymentRepository {
+    db: MemoryHandle,
+}
+
+impl InMemoryPaymentRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self {
+            db: MemoryHandle::Store(store),
+        }
+    }
+
+    /// Repository bound to a unit of work's transaction
+    pub(crate) fn with_handle(db: MemoryHandle) -> Self {
+        Self { db }
+    }
+
+    async fn find_where(
+        &self,
+        filter: impl Fn(&Payment) -> bool + Send,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Payment>, AppError> {
+        let tables = self.db.tables().await?;
+        let mut payments: Vec<Payment> = tables
+            .payments
+            .iter()
+            .filter(|p| filter(p))
+            .cloned()
+            .collect();
+        payments.sort_by_key(|p| Reverse(p.created_at));
+        Ok(paginate(payments, limit, offset))
+    }
+
+    async fn modify(
+        &self,
+        payment_id: Uuid,
+        apply: impl FnOnce(&mut Payment) + Send,
+    ) -> Result<Option<Payment>, AppError> {
+        let mut tables = self.db.tables().await?;
+        Ok(tables
+            .payments
+            .iter_mut()
+            .find(|p| p.id == payment_id)
+            .map(|payment| {
+                apply(payment);
+                payment.updated_at = Utc::now();
+                payment.clone()
+            }))
+    }
+}
+
+#[async_trait]
+impl PaymentRepository for InMemoryPaymentRepository {
+    async fn create(&self, new_payment: NewPayment) -> Result<Payment, AppError> {
+        let now = Utc::now();
+        let payment = Payment {
+            id: Uuid::new_v4(),
+            user_id: new_payment.user_id,
+            tournament_id: new_payment.tournament_id,
+            amount: new_payment.amount,
+            currency: new_payment.currency,
+            payment_method: new_payment.payment_method,
+            status: PaymentStatus::Pending,
+            transaction_id: new_payment.transaction_id,
+            payment_provider: new_payment.payment_provider,
+            provider_payment_id: None,
+            failure_reason: None,
+            refunded_amount: Some(Decimal::ZERO),
+            metadata: new_payment.metadata,
+            created_at: now,
+            updated_at: now,
+            processed_at: None,
+        };
+        self.db.tables().await?.payments.push(payment.clone());
+        Ok(payment)
+    }
+
+    async fn find_by_id(&self, payment_id: Uuid) -> Result<Option<Payment>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables.payments.iter().find(|p| p.id == payment_id).cloned())
+    }
+
+    async fn find_by_user_id(
+        &self,
+        user_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Payment>, AppError> {
+        self.find_where(|p| p.user_id == user_id, limit, offset)
+            .await
+    }
+
+    async fn find_by_tournament_id(
+        &self,
+        tournament_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Payment>, AppError> {
+        self.find_where(|p| p.tournament_id == tournament_id, limit, offset)
+            .await
+    }
+
+    async fn update_status(
+        &self,
+        payment_id: Uuid,
+        status: PaymentStatus,
+    ) -> Result<Option<Payment>, AppError> {
+        self.modify(payment_id, |payment| payment.status = status)
+            .await
+    }
+
+    async fn refund(&self, payment_id: Uuid, amount: Decimal) -> Result<Option<Payment>, AppError> {
+        self.modify(payment_id, |payment| {
+            payment.refunded_amount = Some(amount);
+            payment.status = PaymentStatus::Refunded;
+        })
+        .await
+    }
+
+    async fn partial_refund(
+        &self,
+        payment_id: Uuid,
+        amount: Decimal,
+    ) -> Result<Option<Payment>, AppError> {
+        self.modify(payment_id, |payment| {
+            let refunded = payment.refunded_amount.unwrap_or(Decimal::ZERO) + amount;
+            payment.refunded_amount = Some(refunded);
+            payment.status = if refunded >= payment.amount {
+                PaymentStatus::Refunded
+            } else {
+                PaymentStatus::PartialRefund
+            };
+        })
+        .await
+    }
+
+    async fn get_summary_by_tournament(
+        &self,
+        tournament_id: Uuid,
+    ) -> Result<PaymentSummary, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(summarize(
+            tables
+                .payments
+                .iter()
+                .filter(|p| p.tournament_id == tournament_id),
+        ))
+    }
+
+    async fn get_summary_by_user(&self, user_id: Uuid) -> Result<PaymentSummary, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(summarize(
+            tables.payments.iter().filter(|p| p.user_id == user_id),
+        ))
+    }
+}
@@ -0,0 +1,568 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, Months, Utc};
+use rust_decimal::Decimal;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use crate::domain::match_domain::{Match, MatchStatus};
+use crate::domain::statistics::{
+    AnalyticsDashboard, GameRecord, GrowthMetrics, LeaderboardEntry, PlayerStatistics,
+    StatisticsFilters, StatisticsRepository, TeamStatistics, TournamentStatistics,
+};
+use crate::domain::tournament::{RegistrationStatus, TournamentRegistration, TournamentStatus};
+use crate::infra::db::tournament_repo::sport_type_to_string;
+use crate::shared::AppError;
+
+use super::store::{MemoryHandle, MemoryStore, Tables};
+
+#[derive(Clone, Copy)]
+enum EntityKind {
+    Player,
+    Team,
+}
+
+/// Per-player or per-team aggregates every statistics query is derived from
+struct EntityRecord {
+    id: Uuid,
+    name: String,
+    total_tournaments: i64,
+    total_matches: i64,
+    matches_won: i64,
+    matches_lost: i64,
+    total_earnings: Decimal,
+    last_registration_update: Option<DateTime<Utc>>,
+}
+
+impl EntityRecord {
+    fn win_rate(&self) -> Decimal {
+        percentage(self.matches_won, self.total_matches)
+    }
+
+    /// Leaderboard points: 50 per tournament entered plus 10 per match won
+    fn leaderboard_points(&self) -> i64 {
+        self.total_tournaments * 50 + self.matches_won * 10
+    }
+}
+
+/// `ROUND(part / total * 100, 2)`, or zero when there is nothing to divide by
+fn percentage(part: i64, total: i64) -> Decimal {
+    if total == 0 {
+        return Decimal::ZERO;
+    }
+    (Decimal::from(part) / Decimal::from(total) * Decimal::from(100)).round_dp(2)
+}
+
+fn registered(kind: EntityKind, registration: &TournamentRegistration, id: Uuid) -> bool {
+    match kind {
+        EntityKind::Player => registration.player_id == Some(id),
+        EntityKind::Team => registration.team_id == Some(id),
+    }
+}
+
+/// Which side of the match the entity plays on, if any
+fn side_of(kind: EntityKind, m: &Match, id: Uuid) -> Option<i32> {
+    let (side1, side2) = match kind {
+        EntityKind::Player => (m.participant1_player_id, m.participant2_player_id),
+        EntityKind::Team => (m.participant1_team_id, m.participant2_team_id),
+    };
+    if side1 == Some(id) {
+        Some(1)
+    } else if side2 == Some(id) {
+        Some(2)
+    } else {
+        None
+    }
+}
+
+fn entity_record(tables: &Tables, kind: EntityKind, id: Uuid, name: &str) -> EntityRecord {
+    let registrations: Vec<&TournamentRegistration> = tables
+        .registrations
+        .iter()
+        .filter(|r| registered(kind, r, id))
+        .collect();
+    let approved: Vec<&&TournamentRegistration> = registrations
+        .iter()
+        .filter(|r| r.registration_status == RegistrationStatus::Approved)
+        .collect();
+    let tournaments: HashSet<Uuid> = approved
+        .iter()
+        .filter_map(|r| tables.category_tournament(r.tournament_category_id))
+        .collect();
+
+    let mut total_matches = 0;
+    let mut matches_won = 0;
+    let mut matches_lost = 0;
+    for m in tables
+        .matches
+        .iter()
+        .filter(|m| m.match_status == MatchStatus::Completed)
+    {
+        let Some(side) = side_of(kind, m, id) else {
+            continue;
+        };
+        total_matches += 1;
+        match m.winner_participant {
+            Some(winner) if winner == side => matches_won += 1,
+            Some(_) => matches_lost += 1,
+            None => {}
+        }
+    }
+
+    EntityRecord {
+        id,
+        name: name.to_string(),
+        total_tournaments: tournaments.len() as i64,
+        total_matches,
+        matches_won,
+        matches_lost,
+        total_earnings: approved.iter().filter_map(|r| r.payment_amount).sum(),
+        last_registration_update: registrations.iter().map(|r| r.updated_at).max(),
+    }
+}
+
+fn player_records(tables: &Tables) -> Vec<(EntityRecord, DateTime<Utc>)> {
+    tables
+        .players
+        .iter()
+        .map(|p| {
+            (
+                entity_record(tables, EntityKind::Player, p.id, &p.name),
+                p.created_at,
+            )
+        })
+        .collect()
+}
+
+fn team_records(tables: &Tables) -> Vec<(EntityRecord, DateTime<Utc>)> {
+    tables
+        .teams
+        .iter()
+        .map(|t| {
+            (
+                entity_record(tables, EntityKind::Team, t.id, &t.name),
+                t.created_at,
+            )
+        })
+        .collect()
+}
+
+fn leaderboard(
+    tables: &Tables,
+    category: &str,
+    entity_type: &str,
+    limit: i64,
+    offset: i64,
+) -> Vec<LeaderboardEntry> {
+    let limit = limit.clamp(1, 100);
+    let entry =
+        |record: &EntityRecord, created_at: DateTime<Utc>, points: Decimal| LeaderboardEntry {
+            rank: 0,
+            id: record.id,
+            name: record.name.clone(),
+            points,
+            tournaments_won: record.matches_won,
+            win_rate: record.win_rate(),
+            total_earnings: record.total_earnings,
+            last_active: record.last_registration_update.unwrap_or(created_at),
+        };
+
+    let mut entries: Vec<LeaderboardEntry> = match (entity_type, category) {
+        ("player", "points") | (_, "points") => {
+            let records = if entity_type == "player" {
+                player_records(tables)
+            } else {
+                team_records(tables)
+            };
+            let mut entries: Vec<LeaderboardEntry> = records
+                .iter()
+                .filter(|(r, _)| r.leaderboard_points() > 0)
+                .map(|(r, created)| entry(r, *created, Decimal::from(r.leaderboard_points())))
+                .collect();
+            entries.sort_by(|a, b| {
+                b.points
+                    .cmp(&a.points)
+                    .then(b.tournaments_won.cmp(&a.tournaments_won))
+            });
+            entries
+        }
+        ("player", "wins") => {
+            let mut entries: Vec<LeaderboardEntry> = player_records(tables)
+                .iter()
+                .filter(|(r, _)| r.matches_won > 0)
+                .map(|(r, created)| entry(r, *created, Decimal::from(r.matches_won)))
+                .collect();
+            entries.sort_by(|a, b| {
+                b.tournaments_won
+                    .cmp(&a.tournaments_won)
+                    .then(b.win_rate.cmp(&a.win_rate))
+            });
+            entries
+        }
+        ("player", "earnings") => {
+            let mut entries: Vec<LeaderboardEntry> = player_records(tables)
+                .iter()
+                .filter(|(r, _)| r.total_earnings > Decimal::ZERO)
+                .map(|(r, created)| entry(r, *created, r.total_earnings))
+                .collect();
+            entries.sort_by(|a, b| {
+                b.total_earnings
+                    .cmp(&a.total_earnings)
+                    .then(b.tournaments_won.cmp(&a.tournaments_won))
+            });
+            entries
+        }
+        ("player", _) => {
+            let mut entries: Vec<LeaderboardEntry> = player_records(tables)
+                .iter()
+                .filter(|(r, _)| r.matches_won > 0)
+                .map(|(r, created)| entry(r, *created, r.win_rate()))
+                .collect();
+            entries.sort_by(|a, b| {
+                b.win_rate
+                    .cmp(&a.win_rate)
+                    .then(b.tournaments_won.cmp(&a.tournaments_won))
+            });
+            entries
+        }
+        // Only the points board exists for teams
+        _ => Vec::new(),
+    };
+
+    entries = entries
+        .into_iter()
+        .skip(offset.max(0) as usize)
+        .take(limit as usize)
+        .collect();
+    // ROW_NUMBER() is assigned before OFFSET applies
+    for (index, entry) in entries.iter_mut().enumerate() {
+        entry.rank = offset.max(0) + index as i64 + 1;
+    }
+    entries
+}
+
+fn tournament_statistics(tables: &Tables, tournament_id: Uuid) -> Option<TournamentStatistics> {
+    let tournament = tables.tournaments.iter().find(|t| t.id == tournament_id)?;
+    let in_tournament =
+        |category_id: Uuid| tables.category_tournament(category_id) == Some(tournament_id);
+
+    let approved: Vec<&TournamentRegistration> = tables
+        .registrations
+        .iter()
+        .filter(|r| {
+            in_tournament(r.tournament_category_id)
+                && r.registration_status == RegistrationStatus::Approved
+        })
+        .collect();
+    let matches: Vec<&Match> = tables
+        .matches
+        .iter()
+        .filter(|m| in_tournament(m.tournament_category_id))
+        .collect();
+    let completed = matches
+        .iter()
+        .filter(|m| m.match_status == MatchStatus::Completed)
+        .count() as i64;
+    let pending = matches
+        .iter()
+        .filter(|m| {
+            matches!(
+                m.match_status,
+                MatchStatus::Scheduled | MatchStatus::InProgress
+            )
+        })
+        .count() as i64;
+
+    Some(TournamentStatistics {
+        tournament_id,
+        tournament_name: tournament.name.clone(),
+        total_participants: approved.iter().filter(|r| r.player_id.is_some()).count() as i64,
+        total_teams: approved.iter().filter(|r| r.team_id.is_some()).count() as i64,
+        total_matches: matches.len() as i64,
+        completed_matches: completed,
+        pending_matches: pending,
+        total_prize_pool: tournament.prize_pool.unwrap_or(Decimal::ZERO),
+        total_registrations: approved.len() as i64,
+        completion_rate: percentage(completed, matches.len() as i64),
+        average_match_duration: None,
+        most_wins_player: None,
+        most_wins_team: None,
+        start_date: tournament.start_date,
+        end_date: Some(tournament.end_date),
+    })
+}
+
+fn growth_metrics(tables: &Tables) -> GrowthMetrics {
+    let now = Utc::now();
+    let month_of = |date: DateTime<Utc>| (date.year(), date.month());
+    let this_month = month_of(now);
+    let previous_month = month_of(now.checked_sub_months(Months::new(1)).unwrap_or(now));
+
+    let new_players_this_month = tables
+        .players
+        .iter()
+        .filter(|p| month_of(p.created_at) == this_month)
+        .count() as i64;
+    let prev_players = tables
+        .players
+        .iter()
+        .filter(|p| month_of(p.created_at) == previous_month)
+        .count() as i64;
+    let tournaments_this_month: Vec<_> = tables
+        .tournaments
+        .iter()
+        .filter(|t| month_of(t.created_at) == this_month)
+        .collect();
+    let prev_tournaments = tables
+        .tournaments
+        .iter()
+        .filter(|t| month_of(t.created_at) == previous_month)
+        .count() as i64;
+
+    let growth_rate = |current: i64, previous: i64| {
+        if previous == 0 {
+            Decimal::ZERO
+        } else {
+            (Decimal::from(current - previous) / Decimal::from(previous) * Decimal::from(100))
+                .round_dp(2)
+        }
+    };
+
+    GrowthMetrics {
+        new_players_this_month,
+        new_teams_this_month: tables
+            .teams
+            .iter()
+            .filter(|t| month_of(t.created_at) == this_month)
+            .count() as i64,
+        tournaments_this_month: tournaments_this_month.len() as i64,
+        matches_this_month: 0,
+        revenue_this_month: tournaments_this_month
+            .iter()
+            .filter_map(|t| t.prize_pool)
+            .sum(),
+        player_growth_rate: growth_rate(new_players_this_month, prev_players),
+        tournament_growth_rate: growth_rate(tournaments_this_month.len() as i64, prev_tournaments),
+    }
+}
+
+pub struct InMemoryStatisticsRepository {
+    db: MemoryHandle,
+}
+
+impl InMemoryStatisticsRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self {
+            db: MemoryHandle::Store(store),
+        }
+    }
+}
+
+#[async_trait]
+impl StatisticsRepository for InMemoryStatisticsRepository {
+    async fn get_player_statistics(
+        &self,
+        player_id: Uuid,
+        _filters: Option<StatisticsFilters>,
+    ) -> Result<Option<PlayerStatistics>, AppError> {
+        let tables = self.db.tables().await?;
+        let Some(player) = tables.players.iter().find(|p| p.id == player_id) else {
+            return Ok(None);
+        };
+        let record = entity_record(&tables, EntityKind::Player, player.id, &player.name);
+
+        Ok(Some(PlayerStatistics {
+            player_id,
+            player_name: record.name.clone(),
+            total_tournaments: record.total_tournaments,
+            tournaments_won: 0,
+            tournaments_runner_up: 0,
+            total_matches: record.total_matches,
+            matches_won: record.matches_won,
+            matches_lost: record.matches_lost,
+            win_rate: record.win_rate(),
+            total_earnings: record.total_earnings,
+            average_placement: Decimal::ZERO,
+            best_placement: 0,
+            current_ranking: None,
+            ranking_points: Decimal::from(record.matches_won * 10),
+            last_active: record.last_registration_update.unwrap_or_else(Utc::now),
+        }))
+    }
+
+    async fn get_team_statistics(
+        &self,
+        team_id: Uuid,
+        _filters: Option<StatisticsFilters>,
+    ) -> Result<Option<TeamStatistics>, AppError> {
+        let tables = self.db.tables().await?;
+        let Some(team) = tables.teams.iter().find(|t| t.id == team_id) else {
+            return Ok(None);
+        };
+        let record = entity_record(&tables, EntityKind::Team, team.id, &team.name);
+
+        Ok(Some(TeamStatistics {
+            team_id,
+            team_name: record.name.clone(),
+            total_tournaments: record.total_tournaments,
+            tournaments_won: 0,
+            tournaments_runner_up: 0,
+            total_matches: record.total_matches,
+            matches_won: record.matches_won,
+            matches_lost: record.matches_lost,
+            win_rate: record.win_rate(),
+            total_earnings: record.total_earnings,
+            average_placement: Decimal::ZERO,
+            best_placement: 0,
+            current_ranking: None,
+            ranking_points: Decimal::from(record.matches_won * 10),
+            members_count: tables
+                .team_members
+                .iter()
+                .filter(|m| m.team_id == team_id)
+                .count() as i64,
+            last_active: record.last_registration_update.unwrap_or_else(Utc::now),
+        }))
+    }
+
+    async fn get_tournament_statistics(
+        &self,
+        tournament_id: Uuid,
+    ) -> Result<Option<TournamentStatistics>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tournament_statistics(&tables, tournament_id))
+    }
+
+    async fn get_leaderboard(
+        &self,
+        category: &str,
+        entity_type: &str,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<LeaderboardEntry>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(leaderboard(&tables, category, entity_type, limit, offset))
+    }
+
+    async fn get_game_records(&self, limit: i64) -> Result<Vec<GameRecord>, AppError> {
+        let tables = self.db.tables().await?;
+        let mut records: Vec<GameRecord> = tables
+            .players
+            .iter()
+            .filter_map(|p| {
+                let approved: Vec<&TournamentRegistration> = tables
+                    .registrations
+                    .iter()
+                    .filter(|r| {
+                        r.player_id == Some(p.id)
+                            && r.registration_status == RegistrationStatus::Approved
+                            && tables
+                                .category_tournament(r.tournament_category_id)
+                                .is_some()
+                    })
+                    .collect();
+                let achieved_date = approved.iter().map(|r| r.updated_at).max()?;
+                Some(GameRecord {
+                    id: Uuid::new_v4(),
+                    category: "most_tournament_participations".to_string(),
+                    record_type: "player".to_string(),
+                    holder_id: p.id,
+                    holder_name: p.name.clone(),
+                    value: Decimal::from(approved.len()),
+                    description: "Most tournament participations".to_string(),
+                    achieved_date,
+                    tournament_id: None,
+                    tournament_name: None,
+                })
+            })
+            .collect();
+        records.sort_by_key(|r| Reverse(r.value));
+        records.truncate(limit.clamp(1, 100) as usize);
+        Ok(records)
+    }
+
+    async fn get_growth_metrics(&self) -> Result<GrowthMetrics, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(growth_metrics(&tables))
+    }
+
+    async fn get_analytics_dashboard(&self) -> Result<AnalyticsDashboard, AppError> {
+        let tables = self.db.tables().await?;
+
+        let mut approved_per_tournament: HashMap<Uuid, i64> = HashMap::new();
+        for registration in tables
+            .registrations
+            .iter()
+            .filter(|r| r.registration_status == RegistrationStatus::Approved)
+        {
+            if let Some(tournament_id) =
+                tables.category_tournament(registration.tournament_category_id)
+            {
+                *approved_per_tournament.entry(tournament_id).or_default() += 1;
+            }
+        }
+        let average_tournament_size = if approved_per_tournament.is_empty() {
+            Decimal::ZERO
+        } else {
+            Decimal::from(approved_per_tournament.values().sum::<i64>())
+                / Decimal::from(approved_per_tournament.len())
+        };
+
+        let mut sport_counts: HashMap<String, i64> = HashMap::new();
+        for tournament in &tables.tournaments {
+            *sport_counts
+                .entry(sport_type_to_string(tournament.sport_type))
+                .or_default() += 1;
+        }
+        let most_popular_sport = sport_counts
+            .into_iter()
+            .max_by_key(|(_, count)| *count)
+            .map(|(sport, _)| sport);
+
+        let mut recent: Vec<_> = tables.tournaments.iter().collect();
+        recent.sort_by_key(|r| Reverse(r.created_at));
+        let recent_tournaments = recent
+            .into_iter()
+            .take(5)
+            .filter_map(|t| {
+                let mut stats = tournament_statistics(&tables, t.id)?;
+                // The dashboard counts every registration, not only approved ones
+                stats.total_registrations = tables
+                    .registrations
+                    .iter()
+                    .filter(|r| tables.category_tournament(r.tournament_category_id) == Some(t.id))
+                    .count() as i64;
+                Some(stats)
+            })
+            .collect();
+
+        Ok(AnalyticsDashboard {
+            total_players: tables.players.len() as i64,
+            total_teams: tables.teams.len() as i64,
+            total_tournaments: tables.tournaments.len() as i64,
+            active_tournaments: tables
+                .tournaments
+                .iter()
+                .filter(|t| {
+                    matches!(
+                        t.status,
+                        TournamentStatus::InProgress | TournamentStatus::RegistrationOpen
+                    )
+                })
+                .count() as i64,
+            total_matches: tables.matches.len() as i64,
+            total_earnings_distributed: tables
+                .tournaments
+                .iter()
+                .filter(|t| t.status == TournamentStatus::Completed)
+                .filter_map(|t| t.prize_pool)
+                .sum(),
+            average_tournament_size,
+            most_popular_sport,
+            top_players: leaderboard(&tables, "points", "player", 5, 0),
+            top_teams: leaderboard(&tables, "points", "team", 5, 0),
+            recent_tournaments,
+            growth_metrics: growth_metrics(&tables),
+        })
+    }
+}
@@ -0,0 +1,348 @@
+use std::collections::HashSet;
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+use tokio::sync::{Mutex, MutexGuard, OwnedMutexGuard};
+use uuid::Uuid;
+
+use crate::domain::check_in::CheckInWindow;
+use crate::domain::court_queue::CourtCall;
+use crate::domain::match_domain::{
+    Match, MatchComment, MatchMedia, MatchResult, MatchSubscription,
+};
+use crate::domain::notification::{Notification, NotificationDelivery, PushSubscription};
+use crate::domain::outbox::OutboxMessage;
+use crate::domain::participant::{Player, Team, TeamMember, TeamPlayer};
+use crate::domain::payment::Payment;
+use crate::domain::ticket::MatchTicket;
+use crate::domain::tournament::{
+    Tournament, TournamentBracket, TournamentCategory, TournamentRegistration, TournamentStandings,
+};
+use crate::domain::user::{User, UserProfile, UserToken};
+use crate::domain::venue::{Court, Venue};
+use crate::shared::AppError;
+
+// ==================== Tables ====================
+
+/// Every "table", kept in insertion order like a heap table without an ORDER BY
+#[derive(Clone, Default)]
+pub(crate) struct Tables {
+    pub users: Vec<User>,
+    pub user_profiles: Vec<UserProfile>,
+    pub user_tokens: Vec<UserToken>,
+    pub players: Vec<Player>,
+    pub teams: Vec<Team>,
+    pub team_members: Vec<TeamMember>,
+    pub tournaments: Vec<Tournament>,
+    pub categories: Vec<TournamentCategory>,
+    pub registrations: Vec<TournamentRegistration>,
+    pub brackets: Vec<TournamentBracket>,
+    pub standings: Vec<TournamentStandings>,
+    pub matches: Vec<Match>,
+    pub match_results: Vec<MatchResult>,
+    /// (match_id, sequence, match_result_id); removed with their result
+    pub scoring_sequences: Vec<(Uuid, i64, Uuid)>,
+    pub match_media: Vec<MatchMedia>,
+    pub match_comments: Vec<MatchComment>,
+    pub match_subscriptions: Vec<MatchSubscription>,
+    pub notifications: Vec<Notification>,
+    pub notification_deliveries: Vec<NotificationDelivery>,
+    pub push_subscriptions: Vec<PushSubscription>,
+    pub notification_dedup_keys: HashSet<(Uuid, String)>,
+    pub payments: Vec<Payment>,
+    pub outbox: Vec<OutboxMessage>,
+    pub venues: Vec<Venue>,
+    pub courts: Vec<Court>,
+    /// (tournament_id, venue_id)
+    pub tournament_venues: Vec<(Uuid, Uuid)>,
+    /// (tournament_id, user_id), in the order they were added
+    pub tournament_scorekeepers: Vec<(Uuid, Uuid)>,
+    pub court_calls: Vec<CourtCall>,
+    pub check_in_windows: Vec<CheckInWindow>,
+    pub match_tickets: Vec<MatchTicket>,
+}
+
+impl Tables {
+    pub fn player_name(&self, player_id: Uuid) -> Option<&str> {
+        self.players
+            .iter()
+            .find(|p| p.id == player_id)
+            .map(|p| p.name.as_str())
+    }
+
+    pub fn team_name(&self, team_id: Uuid) -> Option<&str> {
+        self.teams
+            .iter()
+            .find(|t| t.id == team_id)
+            .map(|t| t.name.as_str())
+    }
+
+    /// Display name of a match side: the team, "player / partner", or "TBD"
+    pub fn side_name(
+        &self,
+        team_id: Option<Uuid>,
+        player_id: Option<Uuid>,
+        partner_id: Option<Uuid>,
+    ) -> String {
+        if let Some(name) = team_id.and_then(|id| self.team_name(id)) {
+            return name.to_string();
+        }
+        match player_id.and_then(|id| self.player_name(id)) {
+            Some(player) => match partner_id.and_then(|id| self.player_name(id)) {
+                Some(partner) => format!("{} / {}", player, partner),
+                None => player.to_string(),
+            },
+            None => "TBD".to_string(),
+        }
+    }
+
+    /// Members of a team joined with their player rows
+    pub fn team_players(&self, team_id: Uuid) -> Vec<TeamPlayer> {
+        self.team_members
+            .iter()
+            .filter(|m| m.team_id == team_id)
+            .filter_map(|m| {
+                let player = self.players.iter().find(|p| p.id == m.player_id)?;
+                Some(TeamPlayer {
+                    id: player.id,
+                    name: player.name.clone(),
+                    user_id: player.user_id,
+                    is_captain: m.is_captain,
+                    jersey_number: m.jersey_number,
+                    joined_at: m.joined_at,
+                })
+            })
+            .collect()
+    }
+
+    /// Tournament a category belongs to
+    pub fn category_tournament(&self, category_id: Uuid) -> Option<Uuid> {
+        self.categories
+            .iter()
+            .find(|c| c.id == category_id)
+            .map(|c| c.tournament_id)
+    }
+
+    /// Removes a category together with everything that cascades from it
+    pub fn delete_category_cascade(&mut self, category_id: Uuid) {
+        self.categories.retain(|c| c.id != category_id);
+        self.registrations
+            .retain(|r| r.tournament_category_id != category_id);
+        self.check_in_windows
+            .retain(|w| w.category_id != category_id);
+        let match_ids: Vec<Uuid> = self
+            .matches
+            .iter()
+            .filter(|m| m.tournament_category_id == category_id)
+            .map(|m| m.id)
+            .collect();
+        for match_id in match_ids {
+            self.delete_match_cascade(match_id);
+        }
+    }
+
+    /// Removes a player together with everything that cascades from it
+    pub fn delete_player_cascade(&mut self, player_id: Uuid) {
+        let is_player = |id: Option<Uuid>| id == Some(player_id);
+        self.players.retain(|p| p.id != player_id);
+        self.team_members.retain(|m| m.player_id != player_id);
+        self.registrations
+            .retain(|r| !is_player(r.player_id) && !is_player(r.partner_player_id));
+        let match_ids: Vec<Uuid> = self
+            .matches
+            .iter()
+            .filter(|m| {
+                is_player(m.participant1_player_id)
+                    || is_player(m.participant1_partner_id)
+                    || is_player(m.participant2_player_id)
+                    || is_player(m.participant2_partner_id)
+            })
+            .map(|m| m.id)
+            .collect();
+        for match_id in match_ids {
+            self.delete_match_cascade(match_id);
+        }
+    }
+
+    /// Removes a team together with everything that cascades from it
+    pub fn delete_team_cascade(&mut self, team_id: Uuid) {
+        self.teams.retain(|t| t.id != team_id);
+        self.team_members.retain(|m| m.team_id != team_id);
+        self.registrations.retain(|r| r.team_id != Some(team_id));
+        let match_ids: Vec<Uuid> = self
+            .matches
+            .iter()
+            .filter(|m| {
+                m.participant1_team_id == Some(team_id) || m.participant2_team_id == Some(team_id)
+            })
+            .map(|m| m.id)
+            .collect();
+        for match_id in match_ids {
+            self.delete_match_cascade(match_id);
+        }
+    }
+
+    /// Removes a match together with everything that cascades from it
+    pub fn delete_match_cascade(&mut self, match_id: Uuid) {
+        self.matches.retain(|m| m.id != match_id);
+        self.match_results.retain(|r| r.match_id != match_id);
+        self.scoring_sequences.retain(|s| s.0 != match_id);
+        self.match_media.retain(|m| m.match_id != match_id);
+        self.match_comments.retain(|c| c.match_id != match_id);
+        self.match_subscriptions.retain(|s| s.match_id != match_id);
+        self.notifications.retain(|n| n.match_id != Some(match_id));
+        self.court_calls.retain(|c| c.match_id != match_id);
+        self.match_tickets.retain(|t| t.match_id != match_id);
+    }
+
+    /// Removes a court; its matches keep their venue and court names
+    pub fn delete_court_cascade(&mut self, court_id: Uuid) {
+        self.courts.retain(|c| c.id != court_id);
+        self.court_calls.retain(|c| c.court_id != court_id);
+        for m in self
+            .matches
+            .iter_mut()
+            .filter(|m| m.court_id == Some(court_id))
+        {
+            m.court_id = None;
+        }
+    }
+
+    /// Removes a venue together with its courts
+    pub fn delete_venue_cascade(&mut self, venue_id: Uuid) {
+        self.venues.retain(|v| v.id != venue_id);
+        self.tournament_venues
+            .retain(|(_, venue)| *venue != venue_id);
+        let court_ids: Vec<Uuid> = self
+            .courts
+            .iter()
+            .filter(|c| c.venue_id == venue_id)
+            .map(|c| c.id)
+            .collect();
+        for court_id in court_ids {
+            self.delete_court_cascade(court_id);
+        }
+    }
+}
+
+/// Error matching what Postgres reports for a unique constraint violation
+pub(crate) fn unique_violation(constraint: &str) -> AppError {
+    AppError::DatabaseError(format!(
+        "error returned from database: duplicate key value violates unique constraint \"{}\"",
+        constraint
+    ))
+}
+
+/// Error matching what Postgres reports when a referenced row does not exist
+pub(crate) fn foreign_key_violation(table: &str) -> AppError {
+    AppError::DatabaseError(format!(
+        "error returned from database: insert or update on table \"{}\" violates foreign key constraint",
+        table
+    ))
+}
+
+// ==================== Store ====================
+
+/// Shared in-process storage behind every in-memory repository
+#[derive(Clone, Default)]
+pub struct MemoryStore {
+    tables: Arc<Mutex<Tables>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Locks the store for a transaction; writes are undone unless it is committed
+    pub(crate) async fn begin(&self) -> MemoryTransaction {
+        let tables = Arc::clone(&self.tables).lock_owned().await;
+        let snapshot = Some(tables.clone());
+        MemoryTransaction { tables, snapshot }
+    }
+}
+
+/// An open in-memory transaction. Holds the store lock, so other writers wait for it
+/// just as they would for row locks.
+pub(crate) struct MemoryTransaction {
+    tables: OwnedMutexGuard<Tables>,
+    snapshot: Option<Tables>,
+}
+
+impl MemoryTransaction {
+    pub fn commit(mut self) {
+        self.snapshot = None;
+    }
+}
+
+impl Drop for MemoryTransaction {
+    fn drop(&mut self) {
+        if let Some(snapshot) = self.snapshot.take() {
+            *self.tables = snapshot;
+        }
+    }
+}
+
+/// An open transaction shared by every repository of one unit of work.
+/// `None` once it has been committed or rolled back.
+pub(crate) type SharedMemoryTransaction = Arc<Mutex<Option<MemoryTransaction>>>;
+
+/// Where a repository reads and writes: the store, or a transaction owned by a unit of work
+#[derive(Clone)]
+pub(crate) enum MemoryHandle {
+    Store(MemoryStore),
+    Transaction(SharedMemoryTransaction),
+}
+
+impl MemoryHandle {
+    /// Tables for a single statement; the lock is held only while the statement runs
+    pub async fn tables(&self) -> Result<TablesGuard<'_>, AppError> {
+        match self {
+            MemoryHandle::Store(store) => Ok(TablesGuard::Store(store.tables.lock().await)),
+            MemoryHandle::Transaction(tx) => {
+                let guard = tx.lock().await;
+                if guard.is_none() {
+                    return Err(AppError::InternalError(
+                        "Transaction has already been committed or rolled back".into(),
+                    ));
+                }
+                Ok(TablesGuard::Transaction(guard))
+            }
+        }
+    }
+}
+
+pub(crate) enum TablesGuard<'a> {
+    Store(MutexGuard<'a, Tables>),
+    Transaction(MutexGuard<'a, Option<MemoryTransaction>>),
+}
+
+impl Deref for TablesGuard<'_> {
+    type Target = Tables;
+
+    fn deref(&self) -> &Tables {
+        match self {
+            TablesGuard::Store(guard) => guard,
+            // `MemoryHandle::tables` only hands out guards over an open transaction
+            TablesGuard::Transaction(guard) => &guard.as_ref().expect("open transaction").tables,
+        }
+    }
+}
+
+impl DerefMut for TablesGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Tables {
+        match self {
+            TablesGuard::Store(guard) => guard,
+            TablesGuard::Transaction(guard) => {
+                &mut guard.as_mut().expect("open transaction").tables
+            }
+        }
+    }
+}
+
+/// `LIMIT`/`OFFSET` over already-sorted rows; negative values behave like zero
+pub(crate) fn paginate<T>(rows: Vec<T>, limit: i64, offset: i64) -> Vec<T> {
+    rows.into_iter()
+        .skip(offset.max(0) as usize)
+        .take(limit.max(0) as usize)
+        .collect()
+}
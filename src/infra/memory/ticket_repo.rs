@@ -0,0 +1,73 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::cmp::Reverse;
+use uuid::Uuid;
+
+use crate::domain::ticket::{MatchTicket, MatchTicketRepository};
+use crate::shared::AppError;
+
+use super::store::{foreign_key_violation, MemoryHandle, MemoryStore};
+
+pub struct InMemoryMatchTicketRepository {
+    db: MemoryHandle,
+}
+
+impl InMemoryMatchTicketRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self {
+            db: MemoryHandle::Store(store),
+        }
+    }
+}
+
+#[async_trait]
+impl MatchTicketRepository for InMemoryMatchTicketRepository {
+    async fn create(&self, ticket: MatchTicket) -> Result<MatchTicket, AppError> {
+        let mut tables = self.db.tables().await?;
+        if !tables.matches.iter().any(|m| m.id == ticket.match_id) {
+            return Err(foreign_key_violation("match_tickets"));
+        }
+        tables.match_tickets.push(ticket.clone());
+        Ok(ticket)
+    }
+
+    async fn get_by_id(&self, ticket_id: Uuid) -> Result<Option<MatchTicket>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables
+            .match_tickets
+            .iter()
+            .find(|t| t.id == ticket_id)
+            .cloned())
+    }
+
+    async fn get_by_holder(&self, holder_id: Uuid) -> Result<Vec<MatchTicket>, AppError> {
+        let tables = self.db.tables().await?;
+        let mut tickets: Vec<MatchTicket> = tables
+            .match_tickets
+            .iter()
+            .filter(|t| t.holder_id == holder_id)
+            .cloned()
+            .collect();
+        tickets.sort_by_key(|t| Reverse(t.issued_at));
+        Ok(tickets)
+    }
+
+    async fn admit(
+        &self,
+        ticket_id: Uuid,
+        admitted_by: Uuid,
+        at: DateTime<Utc>,
+    ) -> Result<Option<MatchTicket>, AppError> {
+        let mut tables = self.db.tables().await?;
+        let Some(ticket) = tables
+            .match_tickets
+            .iter_mut()
+            .find(|t| t.id == ticket_id && t.admitted_at.is_none())
+        else {
+            return Ok(None);
+        };
+        ticket.admitted_at = Some(at);
+        ticket.admitted_by = Some(admitted_by);
+        Ok(Some(ticket.clone()))
+    }
+}
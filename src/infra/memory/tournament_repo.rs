@@ -0,0 +1,1196 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use rust_decimal::Decimal;
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::domain::check_in::CheckIn;
+use crate::domain::match_domain::MatchStatus;
+use crate::domain::tournament::{
+    BracketStatus, EditableTournament, EditableTournamentBracket, EditableTournamentCategory,
+    EditableTournamentRegistration, EditableTournamentStandings, NewTournament,
+    NewTournamentBracket, NewTournamentCategory, NewTournamentRegistration, NewTournamentStandings,
+    PaymentStatus, RegistrationStatus, RegistrationWithDetails, Tournament, TournamentBracket,
+    TournamentBracketRepository, TournamentCategory, TournamentCategoryRepository,
+    TournamentRegistration, TournamentRegistrationRepository, TournamentRepository,
+    TournamentSearchQuery, TournamentStandings, TournamentStandingsRepository, TournamentStats,
+    TournamentStatus,
+};
+use crate::infra::db::tournament_repo::{sport_type_to_string, status_to_string};
+use crate::shared::timezone::DEFAULT_TIMEZONE;
+use crate::shared::AppError;
+
+use super::store::{foreign_key_violation, unique_violation, MemoryHandle, MemoryStore, Tables};
+
+/// Case-insensitive substring match, the in-memory counterpart of `ILIKE '%needle%'`
+fn ilike(haystack: &str, needle: &str) -> bool {
+    haystack.to_lowercase().contains(&needle.to_lowercase())
+}
+
+fn registration_details(
+    tables: &Tables,
+    registration: &TournamentRegistration,
+) -> Option<RegistrationWithDetails> {
+    let category = tables
+        .categories
+        .iter()
+        .find(|c| c.id == registration.tournament_category_id)?;
+    let tournament = tables
+        .tournaments
+        .iter()
+        .find(|t| t.id == category.tournament_id)?;
+
+    Some(RegistrationWithDetails {
+        id: registration.id,
+        tournament_category_id: registration.tournament_category_id,
+        tournament_name: tournament.name.clone(),
+        category_name: category.name.clone(),
+        team_name: registration
+            .team_id
+            .and_then(|id| tables.team_name(id))
+            .map(str::to_string),
+        player_name: registration
+            .player_id
+            .and_then(|id| tables.player_name(id))
+            .map(str::to_string),
+        partner_name: registration
+            .partner_player_id
+            .and_then(|id| tables.player_name(id))
+            .map(str::to_string),
+        registration_status: registration.registration_status,
+        payment_status: registration.payment_status,
+        registration_date: registration.registration_date,
+    })
+}
+
+/// Registration details matching `filter`, newest first
+fn registrations_with_details(
+    tables: &Tables,
+    filter: impl Fn(&TournamentRegistration) -> bool,
+) -> Vec<RegistrationWithDetails> {
+    let mut registrations: Vec<&TournamentRegistration> =
+        tables.registrations.iter().filter(|r| filter(r)).collect();
+    registrations.sort_by_key(|r| Reverse(r.registration_date));
+    registrations
+        .into_iter()
+        .filter_map(|r| registration_details(tables, r))
+        .collect()
+}
+
+// ==================== Tournament Repository ====================
+
+pub struct InMemoryTournamentRepository {
+    db: MemoryHandle,
+}
+
+impl InMemoryTournamentRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self {
+            db: MemoryHandle::Store(store),
+        }
+    }
+
+    /// Repository bound to a unit of work's transaction
+    pub(crate) fn with_handle(db: MemoryHandle) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl TournamentRepository for InMemoryTournamentRepository {
+    async fn create(&self, new_tournament: NewTournament) -> Result<Tournament, AppError> {
+        let now = Utc::now();
+        let tournament = Tournament {
+            id: Uuid::new_v4(),
+            name: new_tournament.name,
+            description: new_tournament.description,
+            sport_type: new_tournament.sport_type,
+            format: new_tournament.format,
+            status: TournamentStatus::Draft,
+            start_date: new_tournament.start_date,
+            end_date: new_tournament.end_date,
+            registration_start_date: new_tournament.registration_start_date,
+            registration_end_date: new_tournament.registration_end_date,
+            venue: new_tournament.venue,
+            max_participants: new_tournament.max_participants,
+            entry_fee: new_tournament.entry_fee,
+            prize_pool: new_tournament.prize_pool,
+            rules: new_tournament.rules,
+            organizer_id: new_tournament.organizer_id,
+            timezone: new_tournament
+                .timezone
+                .unwrap_or_else(|| DEFAULT_TIMEZONE.to_string()),
+            created_at: now,
+            updated_at: now,
+        };
+        self.db.tables().await?.tournaments.push(tournament.clone());
+        Ok(tournament)
+    }
+
+    async fn get_all(&self) -> Result<Vec<Tournament>, AppError> {
+        let mut tournaments = self.db.tables().await?.tournaments.clone();
+        tournaments.sort_by_key(|t| Reverse(t.start_date));
+        Ok(tournaments)
+    }
+
+    async fn get_by_id(&self, tournament_id: Uuid) -> Result<Option<Tournament>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables
+            .tournaments
+            .iter()
+            .find(|t| t.id == tournament_id)
+            .cloned())
+    }
+
+    async fn get_by_status(&self, status: TournamentStatus) -> Result<Vec<Tournament>, AppError> {
+        let tables = self.db.tables().await?;
+        let mut tournaments: Vec<Tournament> = tables
+            .tournaments
+            .iter()
+            .filter(|t| t.status == status)
+            .cloned()
+            .collect();
+        tournaments.sort_by_key(|t| t.start_date);
+        Ok(tournaments)
+    }
+
+    async fn get_by_organizer(&self, organizer_id: Uuid) -> Result<Vec<Tournament>, AppError> {
+        let tables = self.db.tables().await?;
+        let mut tournaments: Vec<Tournament> = tables
+            .tournaments
+            .iter()
+            .filter(|t| t.organizer_id == organizer_id)
+            .cloned()
+            .collect();
+        tournaments.sort_by_key(|t| Reverse(t.start_date));
+        Ok(tournaments)
+    }
+
+    async fn update(
+        &self,
+        tournament_id: Uuid,
+        tournament_data: EditableTournament,
+    ) -> Result<Option<Tournament>, AppError> {
+        let mut tables = self.db.tables().await?;
+        let Some(tournament) = tables
+            .tournaments
+            .iter_mut()
+            .find(|t| t.id == tournament_id)
+        else {
+            return Ok(None);
+        };
+
+        if let Some(name) = tournament_data.name {
+            tournament.name = name;
+        }
+        if let Some(description) = tournament_data.description {
+            tournament.description = Some(description);
+        }
+        if let Some(sport_type) = tournament_data.sport_type {
+            tournament.sport_type = sport_type;
+        }
+        if let Some(format) = tournament_data.format {
+            tournament.format = format;
+        }
+        if let Some(status) = tournament_data.status {
+            tournament.status = status;
+        }
+        if let Some(start_date) = tournament_data.start_date {
+            tournament.start_date = start_date;
+        }
+        if let Some(end_date) = tournament_data.end_date {
+            tournament.end_date = end_date;
+        }
+        if let Some(date) = tournament_data.registration_start_date {
+            tournament.registration_start_date = Some(date);
+        }
+        if let Some(date) = tournament_data.registration_end_date {
+            tournament.registration_end_date = Some(date);
+        }
+        if let Some(venue) = tournament_data.venue {
+            tournament.venue = Some(venue);
+        }
+        if let Some(max_participants) = tournament_data.max_participants {
+            tournament.max_participants = Some(max_participants);
+        }
+        if let Some(entry_fee) = tournament_data.entry_fee {
+            tournament.entry_fee = Some(entry_fee);
+        }
+        if let Some(prize_pool) = tournament_data.prize_pool {
+            tournament.prize_pool = Some(prize_pool);
+        }
+        if let Some(rules) = tournament_data.rules {
+            tournament.rules = Some(rules);
+        }
+        if let Some(timezone) = tournament_data.timezone {
+            tournament.timezone = timezone;
+        }
+        tournament.updated_at = Utc::now();
+
+        Ok(Some(tournament.clone()))
+    }
+
+    async fn delete(&self, tournament_id: Uuid) -> Result<Option<Tournament>, AppError> {
+        let mut tables = self.db.tables().await?;
+        let Some(index) = tables
+            .tournaments
+            .iter()
+            .position(|t| t.id == tournament_id)
+        else {
+            return Ok(None);
+        };
+        let tournament = tables.tournaments.remove(index);
+
+        let category_ids: Vec<Uuid> = tables
+            .categories
+            .iter()
+            .filter(|c| c.tournament_id == tournament_id)
+            .map(|c| c.id)
+            .collect();
+        for category_id in category_ids {
+            tables.delete_category_cascade(category_id);
+        }
+        tables.brackets.retain(|b| b.tournament_id != tournament_id);
+        tables
+            .standings
+            .retain(|s| s.tournament_id != tournament_id);
+        tables.payments.retain(|p| p.tournament_id != tournament_id);
+        tables
+            .tournament_venues
+            .retain(|(tournament, _)| *tournament != tournament_id);
+        tables
+            .tournament_scorekeepers
+            .retain(|(tournament, _)| *tournament != tournament_id);
+        tables
+            .notifications
+            .retain(|n| n.tournament_id != Some(tournament_id));
+
+        Ok(Some(tournament))
+    }
+
+    async fn search(&self, query: TournamentSearchQuery) -> Result<Vec<Tournament>, AppError> {
+        let date_from = query
+            .date_from
+            .as_deref()
+            .and_then(|d| chrono::DateTime::parse_from_rfc3339(d).ok());
+        let date_to = query
+            .date_to
+            .as_deref()
+            .and_then(|d| chrono::DateTime::parse_from_rfc3339(d).ok());
+
+        let tables = self.db.tables().await?;
+        let mut tournaments: Vec<Tournament> = tables
+            .tournaments
+            .iter()
+            .filter(|t| {
+                query
+                    .name
+                    .as_deref()
+                    .is_none_or(|name| ilike(&t.name, name))
+            })
+            .filter(|t| {
+                query
+                    .sport_type
+                    .as_deref()
+                    .is_none_or(|st| sport_type_to_string(t.sport_type) == st)
+            })
+            .filter(|t| {
+                query
+                    .status
+                    .as_deref()
+                    .is_none_or(|st| status_to_string(t.status) == st)
+            })
+            .filter(|t| {
+                query.location.as_deref().is_none_or(|location| {
+                    t.venue
+                        .as_deref()
+                        .is_some_and(|venue| ilike(venue, location))
+                })
+            })
+            .filter(|t| date_from.is_none_or(|from| t.start_date >= from))
+            .filter(|t| date_to.is_none_or(|to| t.end_date <= to))
+            .cloned()
+            .collect();
+        tournaments.sort_by_key(|t| Reverse(t.start_date));
+
+        let offset = query.offset.map(|o| o.max(0) as usize).unwrap_or(0);
+        let limit = query
+            .limit
+            .map(|l| l.clamp(0, 100) as usize)
+            .unwrap_or(usize::MAX);
+        Ok(tournaments.into_iter().skip(offset).take(limit).collect())
+    }
+
+    async fn get_featured(&self, limit: u32) -> Result<Vec<Tournament>, AppError> {
+        let tables = self.db.tables().await?;
+        let mut tournaments: Vec<Tournament> = tables
+            .tournaments
+            .iter()
+            .filter(|t| {
+                !matches!(
+                    t.status,
+                    TournamentStatus::Draft | TournamentStatus::Cancelled
+                )
+            })
+            .cloned()
+            .collect();
+        // Postgres sorts NULLs first in descending order
+        tournaments.sort_by(|a, b| match (a.prize_pool, b.prize_pool) {
+            (None, None) => a.start_date.cmp(&b.start_date),
+            (None, Some(_)) => std::cmp::Ordering::Less,
+            (Some(_), None) => std::cmp::Ordering::Greater,
+            (Some(x), Some(y)) => y.cmp(&x).then(a.start_date.cmp(&b.start_date)),
+        });
+        tournaments.truncate(limit as usize);
+        Ok(tournaments)
+    }
+
+    async fn get_upcoming(&self) -> Result<Vec<Tournament>, AppError> {
+        let now = Utc::now();
+        let tables = self.db.tables().await?;
+        let mut tournaments: Vec<Tournament> = tables
+            .tournaments
+            .iter()
+            .filter(|t| t.start_date > now && t.status != TournamentStatus::Cancelled)
+            .cloned()
+            .collect();
+        tournaments.sort_by_key(|t| t.start_date);
+        Ok(tournaments)
+    }
+
+    async fn get_tournament_stats(&self, tournament_id: Uuid) -> Result<TournamentStats, AppError> {
+        let tables = self.db.tables().await?;
+        let tournament = tables
+            .tournaments
+            .iter()
+            .find(|t| t.id == tournament_id)
+            .ok_or_else(|| AppError::NotFound("Tournament not found".into()))?;
+
+        let category_ids: HashSet<Uuid> = tables
+            .categories
+            .iter()
+            .filter(|c| c.tournament_id == tournament_id)
+            .map(|c| c.id)
+            .collect();
+        let registrations: Vec<&TournamentRegistration> = tables
+            .registrations
+            .iter()
+            .filter(|r| category_ids.contains(&r.tournament_category_id))
+            .collect();
+        let participants: HashSet<Uuid> = registrations
+            .iter()
+            .filter_map(|r| r.team_id.or(r.player_id))
+            .collect();
+        let matches_played = tables
+            .matches
+            .iter()
+            .filter(|m| {
+                category_ids.contains(&m.tournament_category_id)
+                    && m.match_status == MatchStatus::Completed
+            })
+            .count();
+
+        Ok(TournamentStats {
+            participants_count: participants.len() as i64,
+            registrations_count: registrations.len() as i64,
+            categories_count: category_ids.len() as i64,
+            matches_played: matches_played as i64,
+            prize_pool_total: tournament
+                .prize_pool
+                .map(|p| p.to_string())
+                .unwrap_or_else(|| "0.00".to_string()),
+            status: tournament.status,
+        })
+    }
+
+    async fn get_scorekeepers(&self, tournament_id: Uuid) -> Result<Vec<Uuid>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables
+            .tournament_scorekeepers
+            .iter()
+            .filter(|(tournament, _)| *tournament == tournament_id)
+            .map(|(_, user)| *user)
+            .collect())
+    }
+
+    async fn add_scorekeeper(&self, tournament_id: Uuid, user_id: Uuid) -> Result<(), AppError> {
+        let mut tables = self.db.tables().await?;
+        if !tables.tournaments.iter().any(|t| t.id == tournament_id) {
+            return Err(foreign_key_violation("tournament_scorekeepers"));
+        }
+        if !tables
+            .tournament_scorekeepers
+            .contains(&(tournament_id, user_id))
+        {
+            tables
+                .tournament_scorekeepers
+                .push((tournament_id, user_id));
+        }
+        Ok(())
+    }
+
+    async fn remove_scorekeeper(
+        &self,
+        tournament_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<bool, AppError> {
+        let mut tables = self.db.tables().await?;
+        let before = tables.tournament_scorekeepers.len();
+        tables
+            .tournament_scorekeepers
+            .retain(|link| *link != (tournament_id, user_id));
+        Ok(tables.tournament_scorekeepers.len() < before)
+    }
+}
+
+// ==================== Tournament Category Repository ====================
+
+pub struct InMemoryTournamentCategoryRepository {
+    db: MemoryHandle,
+}
+
+impl InMemoryTournamentCategoryRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self {
+            db: MemoryHandle::Store(store),
+        }
+    }
+
+    /// Repository bound to a unit of work's transaction
+    pub(crate) fn with_handle(db: MemoryHandle) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl TournamentCategoryRepository for InMemoryTournamentCategoryRepository {
+    async fn create(
+        &self,
+        new_category: NewTournamentCategory,
+    ) -> Result<TournamentCategory, AppError> {
+        let mut tables = self.db.tables().await?;
+        if tables
+            .categories
+            .iter()
+            .any(|c| c.tournament_id == new_category.tournament_id && c.name == new_category.name)
+        {
+            return Err(unique_violation(
+                "tournament_categories_tournament_id_name_key",
+            ));
+        }
+
+        let now = Utc::now();
+        let category = TournamentCategory {
+            id: Uuid::new_v4(),
+            tournament_id: new_category.tournament_id,
+            name: new_category.name,
+            description: new_category.description,
+            team_composition: new_category.team_composition,
+            min_participants: new_category.min_participants.unwrap_or(2),
+            max_participants: new_category.max_participants,
+            entry_fee: new_category.entry_fee,
+            prize_distribution: new_category.prize_distribution,
+            rules: new_category.rules,
+            constraints: new_category.constraints,
+            created_at: now,
+            updated_at: now,
+        };
+        tables.categories.push(category.clone());
+        Ok(category)
+    }
+
+    async fn get_by_tournament(
+        &self,
+        tournament_id: Uuid,
+    ) -> Result<Vec<TournamentCategory>, AppError> {
+        let tables = self.db.tables().await?;
+        let mut categories: Vec<TournamentCategory> = tables
+            .categories
+            .iter()
+            .filter(|c| c.tournament_id == tournament_id)
+            .cloned()
+            .collect();
+        categories.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(categories)
+    }
+
+    async fn get_by_id(&self, category_id: Uuid) -> Result<Option<TournamentCategory>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables
+            .categories
+            .iter()
+            .find(|c| c.id == category_id)
+            .cloned())
+    }
+
+    async fn update(
+        &self,
+        category_id: Uuid,
+        category_data: EditableTournamentCategory,
+    ) -> Result<Option<TournamentCategory>, AppError> {
+        let mut tables = self.db.tables().await?;
+        let Some(existing) = tables.categories.iter().find(|c| c.id == category_id) else {
+            return Ok(None);
+        };
+        if let Some(name) = &category_data.name {
+            let tournament_id = existing.tournament_id;
+            if tables
+                .categories
+                .iter()
+                .any(|c| c.id != category_id && c.tournament_id == tournament_id && &c.name == name)
+            {
+                return Err(unique_violation(
+                    "tournament_categories_tournament_id_name_key",
+                ));
+            }
+        }
+
+        let category = tables
+            .categories
+            .iter_mut()
+            .find(|c| c.id == category_id)
+            .expect("category checked above");
+        if let Some(name) = category_data.name {
+            category.name = name;
+        }
+        if let Some(description) = category_data.description {
+            category.description = Some(description);
+        }
+        if let Some(team_composition) = category_data.team_composition {
+            category.team_composition = team_composition;
+        }
+        if let Some(min_participants) = category_data.min_participants {
+            category.min_participants = min_participants;
+        }
+        if let Some(max_participants) = category_data.max_participants {
+            category.max_participants = Some(max_participants);
+        }
+        if let Some(entry_fee) = category_data.entry_fee {
+            category.entry_fee = Some(entry_fee);
+        }
+        if let Some(prize_distribution) = category_data.prize_distribution {
+            category.prize_distribution = Some(prize_distribution);
+        }
+        if let Some(rules) = category_data.rules {
+            category.rules = Some(rules);
+        }
+        if let Some(constraints) = category_data.constraints {
+            category.constraints = Some(constraints);
+        }
+        category.updated_at = Utc::now();
+
+        Ok(Some(category.clone()))
+    }
+
+    async fn delete(&self, category_id: Uuid) -> Result<Option<TournamentCategory>, AppError> {
+        let mut tables = self.db.tables().await?;
+        let category = tables
+            .categories
+            .iter()
+            .find(|c| c.id == category_id)
+            .cloned();
+        if category.is_some() {
+            tables.delete_category_cascade(category_id);
+        }
+        Ok(category)
+    }
+}
+
+// ==================== Tournament Registration Repository ====================
+
+pub struct InMemoryTournamentRegistrationRepository {
+    db: MemoryHandle,
+}
+
+impl InMemoryTournamentRegistrationRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self {
+            db: MemoryHandle::Store(store),
+        }
+    }
+
+    /// Repository bound to a unit of work's transaction
+    pub(crate) fn with_handle(db: MemoryHandle) -> Self {
+        Self { db }
+    }
+}
+
+/// Enforces the registration UNIQUE constraints; like Postgres, rows with a NULL
+/// in the key never conflict
+pub(crate) fn check_registration_unique(
+    tables: &Tables,
+    category_id: Uuid,
+    team_id: Option<Uuid>,
+    player_id: Option<Uuid>,
+    partner_player_id: Option<Uuid>,
+) -> Result<(), AppError> {
+    let in_category = || {
+        tables
+            .registrations
+            .iter()
+            .filter(move |r| r.tournament_category_id == category_id)
+    };
+    if team_id.is_some() && in_category().any(|r| r.team_id == team_id) {
+        return Err(unique_violation(
+            "tournament_registrations_tournament_category_id_team_id_key",
+        ));
+    }
+    if player_id.is_some()
+        && partner_player_id.is_some()
+        && in_category()
+            .any(|r| r.player_id == player_id && r.partner_player_id == partner_player_id)
+    {
+        return Err(unique_violation(
+            "tournament_registrations_tournament_category_id_player_id_partner_player_id_key",
+        ));
+    }
+    Ok(())
+}
+
+/// Builds a pending registration row, as the column defaults would
+pub(crate) fn new_registration_row(
+    new_registration: NewTournamentRegistration,
+) -> TournamentRegistration {
+    let now = Utc::now();
+    TournamentRegistration {
+        id: Uuid::new_v4(),
+        tournament_category_id: new_registration.tournament_category_id,
+        team_id: new_registration.team_id,
+        player_id: new_registration.player_id,
+        partner_player_id: new_registration.partner_player_id,
+        registration_status: RegistrationStatus::Pending,
+        payment_status: PaymentStatus::Pending,
+        registration_date: now,
+        approval_date: None,
+        payment_date: None,
+        payment_amount: None,
+        payment_reference: None,
+        notes: new_registration.notes,
+        metadata: new_registration.metadata,
+        checked_in_at: None,
+        check_in_method: None,
+        checked_in_by: None,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+#[async_trait]
+impl TournamentRegistrationRepository for InMemoryTournamentRegistrationRepository {
+    async fn create(
+        &self,
+        new_registration: NewTournamentRegistration,
+    ) -> Result<TournamentRegistration, AppError> {
+        let mut tables = self.db.tables().await?;
+        check_registration_unique(
+            &tables,
+            new_registration.tournament_category_id,
+            new_registration.team_id,
+            new_registration.player_id,
+            new_registration.partner_player_id,
+        )?;
+
+        let registration = new_registration_row(new_registration);
+        tables.registrations.push(registration.clone());
+        Ok(registration)
+    }
+
+    async fn get_by_id(
+        &self,
+        registration_id: Uuid,
+    ) -> Result<Option<TournamentRegistration>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables
+            .registrations
+            .iter()
+            .find(|r| r.id == registration_id)
+            .cloned())
+    }
+
+    async fn get_by_tournament_category(
+        &self,
+        category_id: Uuid,
+    ) -> Result<Vec<TournamentRegistration>, AppError> {
+        let tables = self.db.tables().await?;
+        let mut registrations: Vec<TournamentRegistration> = tables
+            .registrations
+            .iter()
+            .filter(|r| r.tournament_category_id == category_id)
+            .cloned()
+            .collect();
+        registrations.sort_by_key(|r| Reverse(r.registration_date));
+        Ok(registrations)
+    }
+
+    async fn get_by_tournament(
+        &self,
+        tournament_id: Uuid,
+    ) -> Result<Vec<RegistrationWithDetails>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(registrations_with_details(&tables, |r| {
+            tables.category_tournament(r.tournament_category_id) == Some(tournament_id)
+        }))
+    }
+
+    async fn get_by_player(
+        &self,
+        player_id: Uuid,
+    ) -> Result<Vec<RegistrationWithDetails>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(registrations_with_details(&tables, |r| {
+            r.player_id == Some(player_id) || r.partner_player_id == Some(player_id)
+        }))
+    }
+
+    async fn get_by_team(&self, team_id: Uuid) -> Result<Vec<RegistrationWithDetails>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(registrations_with_details(&tables, |r| {
+            r.team_id == Some(team_id)
+        }))
+    }
+
+    async fn update(
+        &self,
+        registration_id: Uuid,
+        registration_data: EditableTournamentRegistration,
+    ) -> Result<Option<TournamentRegistration>, AppError> {
+        let mut tables = self.db.tables().await?;
+        let Some(registration) = tables
+            .registrations
+            .iter_mut()
+            .find(|r| r.id == registration_id)
+        else {
+            return Ok(None);
+        };
+
+        let now = Utc::now();
+        if let Some(status) = registration_data.registration_status {
+            registration.registration_status = status;
+            if status == RegistrationStatus::Approved {
+                registration.approval_date = Some(now);
+            }
+        }
+        if let Some(status) = registration_data.payment_status {
+            registration.payment_status = status;
+            if status == PaymentStatus::Completed {
+                registration.payment_date = Some(now);
+            }
+        }
+        if let Some(amount) = registration_data.payment_amount {
+            registration.payment_amount = Some(amount);
+        }
+        if let Some(reference) = registration_data.payment_reference {
+            registration.payment_reference = Some(reference);
+        }
+        if let Some(notes) = registration_data.notes {
+            registration.notes = Some(notes);
+        }
+        if let Some(metadata) = registration_data.metadata {
+            registration.metadata = Some(metadata);
+        }
+        registration.updated_at = now;
+
+        Ok(Some(registration.clone()))
+    }
+
+    async fn delete(
+        &self,
+        registration_id: Uuid,
+    ) -> Result<Option<TournamentRegistration>, AppError> {
+        let mut tables = self.db.tables().await?;
+        let index = tables
+            .registrations
+            .iter()
+            .position(|r| r.id == registration_id);
+        Ok(index.map(|index| tables.registrations.remove(index)))
+    }
+
+    async fn record_check_in(
+        &self,
+        registration_id: Uuid,
+        check_in: Option<CheckIn>,
+    ) -> Result<Option<TournamentRegistration>, AppError> {
+        let mut tables = self.db.tables().await?;
+        let Some(registration) = tables
+            .registrations
+            .iter_mut()
+            .find(|r| r.id == registration_id)
+        else {
+            return Ok(None);
+        };
+        registration.checked_in_at = check_in.as_ref().map(|c| c.at);
+        registration.check_in_method = check_in.as_ref().map(|c| c.method);
+        registration.checked_in_by = check_in.and_then(|c| c.by);
+        registration.updated_at = Utc::now();
+        Ok(Some(registration.clone()))
+    }
+}
+
+// ==================== Tournament Bracket Repository ====================
+
+pub struct InMemoryTournamentBracketRepository {
+    db: MemoryHandle,
+}
+
+impl InMemoryTournamentBracketRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self {
+            db: MemoryHandle::Store(store),
+        }
+    }
+
+    /// Repository bound to a unit of work's transaction
+    pub(crate) fn with_handle(db: MemoryHandle) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl TournamentBracketRepository for InMemoryTournamentBracketRepository {
+    async fn create(
+        &self,
+        new_bracket: NewTournamentBracket,
+    ) -> Result<TournamentBracket, AppError> {
+        let now = Utc::now();
+        let bracket = TournamentBracket {
+            id: Uuid::new_v4(),
+            tournament_id: new_bracket.tournament_id,
+            category_id: new_bracket.category_id,
+            bracket_type: new_bracket.bracket_type,
+            status: BracketStatus::NotGenerated,
+            total_rounds: new_bracket.total_rounds,
+            current_round: 1,
+            bracket_data: new_bracket.bracket_data,
+            settings: new_bracket.settings,
+            created_at: now,
+            updated_at: now,
+        };
+        self.db.tables().await?.brackets.push(bracket.clone());
+        Ok(bracket)
+    }
+
+    async fn get_by_tournament_id(
+        &self,
+        tournament_id: Uuid,
+    ) -> Result<Vec<TournamentBracket>, AppError> {
+        let tables = self.db.tables().await?;
+        let mut brackets: Vec<TournamentBracket> = tables
+            .brackets
+            .iter()
+            .filter(|b| b.tournament_id == tournament_id)
+            .cloned()
+            .collect();
+        brackets.sort_by_key(|b| b.created_at);
+        Ok(brackets)
+    }
+
+    async fn get_by_category_id(
+        &self,
+        category_id: Uuid,
+    ) -> Result<Option<TournamentBracket>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables
+            .brackets
+            .iter()
+            .find(|b| b.category_id == Some(category_id))
+            .cloned())
+    }
+
+    async fn get_by_id(&self, bracket_id: Uuid) -> Result<Option<TournamentBracket>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables.brackets.iter().find(|b| b.id == bracket_id).cloned())
+    }
+
+    async fn update(
+        &self,
+        bracket_id: Uuid,
+        bracket_data: EditableTournamentBracket,
+    ) -> Result<Option<TournamentBracket>, AppError> {
+        let mut tables = self.db.tables().await?;
+        Ok(tables
+            .brackets
+            .iter_mut()
+            .find(|b| b.id == bracket_id)
+            .map(|bracket| {
+                if let Some(status) = bracket_data.status {
+                    bracket.status = status;
+                }
+                if let Some(current_round) = bracket_data.current_round {
+                    bracket.current_round = current_round;
+                }
+                if let Some(bracket_data) = bracket_data.bracket_data {
+                    bracket.bracket_data = Some(bracket_data);
+                }
+                if let Some(settings) = bracket_data.settings {
+                    bracket.settings = Some(settings);
+                }
+                bracket.updated_at = Utc::now();
+                bracket.clone()
+            }))
+    }
+
+    async fn update_status(
+        &self,
+        bracket_id: Uuid,
+        status: BracketStatus,
+    ) -> Result<Option<TournamentBracket>, AppError> {
+        let mut tables = self.db.tables().await?;
+        Ok(tables
+            .brackets
+            .iter_mut()
+            .find(|b| b.id == bracket_id)
+            .map(|bracket| {
+                bracket.status = status;
+                bracket.updated_at = Utc::now();
+                bracket.clone()
+            }))
+    }
+
+    async fn delete(&self, bracket_id: Uuid) -> Result<Option<TournamentBracket>, AppError> {
+        let mut tables = self.db.tables().await?;
+        let index = tables.brackets.iter().position(|b| b.id == bracket_id);
+        Ok(index.map(|index| tables.brackets.remove(index)))
+    }
+
+    async fn exists_for_tournament(&self, tournament_id: Uuid) -> Result<bool, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables
+            .brackets
+            .iter()
+            .any(|b| b.tournament_id == tournament_id))
+    }
+}
+
+// ==================== Tournament Standings Repository ====================
+
+pub struct InMemoryTournamentStandingsRepository {
+    db: MemoryHandle,
+}
+
+impl InMemoryTournamentStandingsRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self {
+            db: MemoryHandle::Store(store),
+        }
+    }
+
+    /// Repository bound to a unit of work's transaction
+    pub(crate) fn with_handle(db: MemoryHandle) -> Self {
+        Self { db }
+    }
+}
+
+// The server binary never constructs standings directly, only the library API does
+#[allow(dead_code)]
+fn new_standings_row(new_standings: NewTournamentStandings) -> TournamentStandings {
+    let now = Utc::now();
+    TournamentStandings {
+        id: Uuid::new_v4(),
+        tournament_id: new_standings.tournament_id,
+        category_id: new_standings.category_id,
+        participant_id: new_standings.participant_id,
+        participant_name: new_standings.participant_name,
+        participant_type: new_standings.participant_type,
+        position: new_standings.position.unwrap_or(0),
+        points: new_standings.points.unwrap_or(Decimal::ZERO),
+        matches_played: new_standings.matches_played.unwrap_or(0),
+        matches_won: new_standings.matches_won.unwrap_or(0),
+        matches_lost: new_standings.matches_lost.unwrap_or(0),
+        matches_drawn: new_standings.matches_drawn.unwrap_or(0),
+        sets_won: new_standings.sets_won.unwrap_or(0),
+        sets_lost: new_standings.sets_lost.unwrap_or(0),
+        games_won: new_standings.games_won.unwrap_or(0),
+        games_lost: new_standings.games_lost.unwrap_or(0),
+        goal_difference: new_standings.goal_difference,
+        head_to_head: None,
+        bonus_points: new_standings.bonus_points,
+        penalty_points: new_standings.penalty_points,
+        is_eliminated: new_standings.is_eliminated.unwrap_or(false),
+        elimination_round: new_standings.elimination_round,
+        last_updated: now,
+        created_at: now,
+    }
+}
+
+#[allow(dead_code)]
+fn apply_standings_update(standings: &mut TournamentStandings, data: EditableTournamentStandings) {
+    if let Some(position) = data.position {
+        standings.position = position;
+    }
+    if let Some(points) = data.points {
+        standings.points = points;
+    }
+    if let Some(matches_played) = data.matches_played {
+        standings.matches_played = matches_played;
+    }
+    if let Some(matches_won) = data.matches_won {
+        standings.matches_won = matches_won;
+    }
+    if let Some(matches_lost) = data.matches_lost {
+        standings.matches_lost = matches_lost;
+    }
+    if let Some(matches_drawn) = data.matches_drawn {
+        standings.matches_drawn = matches_drawn;
+    }
+    if let Some(sets_won) = data.sets_won {
+        standings.sets_won = sets_won;
+    }
+    if let Some(sets_lost) = data.sets_lost {
+        standings.sets_lost = sets_lost;
+    }
+    if let Some(games_won) = data.games_won {
+        standings.games_won = games_won;
+    }
+    if let Some(games_lost) = data.games_lost {
+        standings.games_lost = games_lost;
+    }
+    if let Some(goal_difference) = data.goal_difference {
+        standings.goal_difference = Some(goal_difference);
+    }
+    if let Some(head_to_head) = data.head_to_head {
+        standings.head_to_head = Some(head_to_head);
+    }
+    if let Some(bonus_points) = data.bonus_points {
+        standings.bonus_points = Some(bonus_points);
+    }
+    if let Some(penalty_points) = data.penalty_points {
+        standings.penalty_points = Some(penalty_points);
+    }
+    if let Some(is_eliminated) = data.is_eliminated {
+        standings.is_eliminated = is_eliminated;
+    }
+    if let Some(elimination_round) = data.elimination_round {
+        standings.elimination_round = Some(elimination_round);
+    }
+    standings.last_updated = Utc::now();
+}
+
+#[async_trait]
+impl TournamentStandingsRepository for InMemoryTournamentStandingsRepository {
+    async fn create(
+        &self,
+        new_standings: NewTournamentStandings,
+    ) -> Result<TournamentStandings, AppError> {
+        let standings = new_standings_row(new_standings);
+        self.db.tables().await?.standings.push(standings.clone());
+        Ok(standings)
+    }
+
+    async fn get_by_tournament_id(
+        &self,
+        tournament_id: Uuid,
+    ) -> Result<Vec<TournamentStandings>, AppError> {
+        let tables = self.db.tables().await?;
+        let mut standings: Vec<TournamentStandings> = tables
+            .standings
+            .iter()
+            .filter(|s| s.tournament_id == tournament_id)
+            .cloned()
+            .collect();
+        standings.sort_by_key(|s| s.position);
+        Ok(standings)
+    }
+
+    async fn get_by_category_id(
+        &self,
+        category_id: Uuid,
+    ) -> Result<Vec<TournamentStandings>, AppError> {
+        let tables = self.db.tables().await?;
+        let mut standings: Vec<TournamentStandings> = tables
+            .standings
+            .iter()
+            .filter(|s| s.category_id == Some(category_id))
+            .cloned()
+            .collect();
+        standings.sort_by_key(|s| s.position);
+        Ok(standings)
+    }
+
+    async fn get_by_participant(
+        &self,
+        participant_id: Uuid,
+    ) -> Result<Vec<TournamentStandings>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables
+            .standings
+            .iter()
+            .filter(|s| s.participant_id == participant_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn update(
+        &self,
+        standings_id: Uuid,
+        standings_data: EditableTournamentStandings,
+    ) -> Result<Option<TournamentStandings>, AppError> {
+        let mut tables = self.db.tables().await?;
+        Ok(tables
+            .standings
+            .iter_mut()
+            .find(|s| s.id == standings_id)
+            .map(|standings| {
+                apply_standings_update(standings, standings_data);
+                standings.clone()
+            }))
+    }
+
+    async fn delete_by_tournament(&self, tournament_id: Uuid) -> Result<u64, AppError> {
+        let mut tables = self.db.tables().await?;
+        let before = tables.standings.len();
+        tables
+            .standings
+            .retain(|s| s.tournament_id != tournament_id);
+        Ok((before - tables.standings.len()) as u64)
+    }
+
+    async fn bulk_upsert(
+        &self,
+        standings: Vec<NewTournamentStandings>,
+    ) -> Result<Vec<TournamentStandings>, AppError> {
+        let mut tables = self.db.tables().await?;
+        let mut results = Vec::with_capacity(standings.len());
+
+        for entry in standings {
+            let existing = tables.standings.iter_mut().find(|s| {
+                s.tournament_id == entry.tournament_id
+                    && s.category_id == entry.category_id
+                    && s.participant_id == entry.participant_id
+            });
+            match existing {
+                Some(existing) => {
+                    apply_standings_update(
+                        existing,
+                        EditableTournamentStandings {
+                            position: entry.position,
+                            points: entry.points,
+                            matches_played: entry.matches_played,
+                            matches_won: entry.matches_won,
+                            matches_lost: entry.matches_lost,
+                            matches_drawn: entry.matches_drawn,
+                            sets_won: entry.sets_won,
+                            sets_lost: entry.sets_lost,
+                            games_won: entry.games_won,
+                            games_lost: entry.games_lost,
+                            goal_difference: entry.goal_difference,
+                            head_to_head: None,
+                            bonus_points: entry.bonus_points,
+                            penalty_points: entry.penalty_points,
+                            is_eliminated: entry.is_eliminated,
+                            elimination_round: entry.elimination_round,
+                        },
+                    );
+                    results.push(existing.clone());
+                }
+                None => {
+                    let created = new_standings_row(entry);
+                    tables.standings.push(created.clone());
+                    results.push(created);
+                }
+            }
+        }
+
+        Ok(results)
+    }
+}
@@ -0,0 +1,153 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+use crate::domain::check_in::CheckInWindowRepository;
+use crate::domain::court_queue::CourtCallRepository;
+use crate::domain::match_domain::{MatchRepository, MatchResultRepository};
+use crate::domain::notification::NotificationRepository;
+use crate::domain::outbox::OutboxRepository;
+use crate::domain::payment::PaymentRepository;
+use crate::domain::tournament::{
+    TournamentBracketRepository, TournamentCategoryRepository, TournamentRegistrationRepository,
+    TournamentRepository, TournamentStandingsRepository,
+};
+use crate::domain::unit_of_work::{UnitOfWork, UnitOfWorkFactory};
+use crate::shared::AppError;
+
+use super::store::{MemoryHandle, MemoryStore, SharedMemoryTransaction};
+use super::{
+    InMemoryCheckInWindowRepository, InMemoryCourtCallRepository, InMemoryMatchRepository,
+    InMemoryMatchResultRepository, InMemoryNotificationRepository, InMemoryOutboxRepository,
+    InMemoryPaymentRepository, InMemoryTournamentBracketRepository,
+    InMemoryTournamentCategoryRepository, InMemoryTournamentRegistrationRepository,
+    InMemoryTournamentRepository, InMemoryTournamentStandingsRepository,
+};
+
+/// Locks the store for the duration of each unit of work
+pub struct InMemoryUnitOfWorkFactory {
+    store: MemoryStore,
+}
+
+impl InMemoryUnitOfWorkFactory {
+    pub fn new(store: MemoryStore) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl UnitOfWorkFactory for InMemoryUnitOfWorkFactory {
+    async fn begin(&self) -> Result<Box<dyn UnitOfWork>, AppError> {
+        let tx = self.store.begin().await;
+        Ok(Box::new(InMemoryUnitOfWork::new(Arc::new(Mutex::new(
+            Some(tx),
+        )))))
+    }
+}
+
+/// One in-memory transaction; changes are undone unless committed
+pub struct InMemoryUnitOfWork {
+    tx: SharedMemoryTransaction,
+    tournaments: InMemoryTournamentRepository,
+    categories: InMemoryTournamentCategoryRepository,
+    registrations: InMemoryTournamentRegistrationRepository,
+    brackets: InMemoryTournamentBracketRepository,
+    standings: InMemoryTournamentStandingsRepository,
+    matches: InMemoryMatchRepository,
+    match_results: InMemoryMatchResultRepository,
+    notifications: InMemoryNotificationRepository,
+    payments: InMemoryPaymentRepository,
+    outbox: InMemoryOutboxRepository,
+    court_calls: InMemoryCourtCallRepository,
+    check_in_windows: InMemoryCheckInWindowRepository,
+}
+
+impl InMemoryUnitOfWork {
+    fn new(tx: SharedMemoryTransaction) -> Self {
+        let handle = || MemoryHandle::Transaction(Arc::clone(&tx));
+        Self {
+            tournaments: InMemoryTournamentRepository::with_handle(handle()),
+            categories: InMemoryTournamentCategoryRepository::with_handle(handle()),
+            registrations: InMemoryTournamentRegistrationRepository::with_handle(handle()),
+            brackets: InMemoryTournamentBracketRepository::with_handle(handle()),
+            standings: InMemoryTournamentStandingsRepository::with_handle(handle()),
+            matches: InMemoryMatchRepository::with_handle(handle()),
+            match_results: InMemoryMatchResultRepository::with_handle(handle()),
+            notifications: InMemoryNotificationRepository::with_handle(handle()),
+            payments: InMemoryPaymentRepository::with_handle(handle()),
+            outbox: InMemoryOutboxRepository::with_handle(handle()),
+            court_calls: InMemoryCourtCallRepository::with_handle(handle()),
+            check_in_windows: InMemoryCheckInWindowRepository::with_handle(handle()),
+            tx,
+        }
+    }
+}
+
+#[async_trait]
+impl UnitOfWork for InMemoryUnitOfWork {
+    fn tournaments(&self) -> &dyn TournamentRepository {
+        &self.tournaments
+    }
+
+    fn categories(&self) -> &dyn TournamentCategoryRepository {
+        &self.categories
+    }
+
+    fn registrations(&self) -> &dyn TournamentRegistrationRepository {
+        &self.registrations
+    }
+
+    fn brackets(&self) -> &dyn TournamentBracketRepository {
+        &self.brackets
+    }
+
+    fn standings(&self) -> &dyn TournamentStandingsRepository {
+        &self.standings
+    }
+
+    fn matches(&self) -> &dyn MatchRepository {
+        &self.matches
+    }
+
+    fn match_results(&self) -> &dyn MatchResultRepository {
+        &self.match_results
+    }
+
+    fn notifications(&self) -> &dyn NotificationRepository {
+        &self.notifications
+    }
+
+    fn payments(&self) -> &dyn PaymentRepository {
+        &self.payments
+    }
+
+    fn outbox(&self) -> &dyn OutboxRepository {
+        &self.outbox
+    }
+
+    fn court_calls(&self) -> &dyn CourtCallRepository {
+        &self.court_calls
+    }
+
+    fn check_in_windows(&self) -> &dyn CheckInWindowRepository {
+        &self.check_in_windows
+    }
+
+    async fn commit(self: Box<Self>) -> Result<(), AppError> {
+        match self.tx.lock().await.take() {
+            Some(tx) => {
+                tx.commit();
+                Ok(())
+            }
+            None => Err(AppError::InternalError(
+                "Transaction has already been committed or rolled back".into(),
+            )),
+        }
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<(), AppError> {
+        // Dropping the transaction restores the snapshot taken by `begin`
+        drop(self.tx.lock().await.take());
+        Ok(())
+    }
+}
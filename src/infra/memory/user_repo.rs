@@ -0,0 +1,325 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::domain::user::{
+    EditableUser, NewUser, NewUserProfile, PublicUserProfile, TokenRepository, UpdateUserProfile,
+    User, UserProfile, UserProfileRepository, UserRepository, UserToken,
+};
+use crate::shared::AppError;
+
+use super::store::{unique_violation, MemoryHandle, MemoryStore};
+
+// ==================== User Repository ====================
+
+pub struct InMemoryUserRepository {
+    db: MemoryHandle,
+}
+
+impl InMemoryUserRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self {
+            db: MemoryHandle::Store(store),
+        }
+    }
+}
+
+#[async_trait]
+impl UserRepository for InMemoryUserRepository {
+    async fn find_all(&self) -> Result<Vec<User>, AppError> {
+        Ok(self.db.tables().await?.users.clone())
+    }
+
+    async fn find_by_id(&self, user_id: Uuid) -> Result<Option<User>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables.users.iter().find(|u| u.id == user_id).cloned())
+    }
+
+    async fn find_by_google_id(&self, google_id: &str) -> Result<Option<User>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables
+            .users
+            .iter()
+            .find(|u| u.google_id == google_id)
+            .cloned())
+    }
+
+    async fn create(&self, new_user: NewUser) -> Result<User, AppError> {
+        let mut tables = self.db.tables().await?;
+        if tables
+            .users
+            .iter()
+            .any(|u| u.google_id == new_user.google_id)
+        {
+            return Err(unique_violation("users_google_id_key"));
+        }
+        if tables.users.iter().any(|u| u.email == new_user.email) {
+            return Err(unique_violation("users_email_key"));
+        }
+
+        let now = Utc::now();
+        let user = User {
+            id: Uuid::new_v4(),
+            google_id: new_user.google_id,
+            email: new_user.email,
+            name: new_user.name,
+            created_at: now,
+            updated_at: now,
+        };
+        tables.users.push(user.clone());
+        Ok(user)
+    }
+
+    async fn update(
+        &self,
+        user_id: Uuid,
+        user_data: EditableUser,
+    ) -> Result<Option<User>, AppError> {
+        let mut tables = self.db.tables().await?;
+        Ok(tables
+            .users
+            .iter_mut()
+            .find(|u| u.id == user_id)
+            .map(|user| {
+                user.name = Some(user_data.name);
+                user.updated_at = Utc::now();
+                user.clone()
+            }))
+    }
+
+    async fn delete(&self, user_id: Uuid) -> Result<Option<User>, AppError> {
+        let mut tables = self.db.tables().await?;
+        let Some(index) = tables.users.iter().position(|u| u.id == user_id) else {
+            return Ok(None);
+        };
+        let user = tables.users.remove(index);
+        tables.user_profiles.retain(|p| p.user_id != user_id);
+        tables.user_tokens.retain(|t| t.user_id != user_id);
+        tables.notifications.retain(|n| n.user_id != user_id);
+        tables.payments.retain(|p| p.user_id != user_id);
+        tables.match_comments.retain(|c| c.user_id != user_id);
+        tables.match_subscriptions.retain(|s| s.user_id != user_id);
+        Ok(Some(user))
+    }
+}
+
+// ==================== User Profile Repository ====================
+
+pub struct InMemoryUserProfileRepository {
+    db: MemoryHandle,
+}
+
+impl InMemoryUserProfileRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self {
+            db: MemoryHandle::Store(store),
+        }
+    }
+
+    async fn modify(
+        &self,
+        user_id: Uuid,
+        apply: impl FnOnce(&mut UserProfile) + Send,
+    ) -> Result<Option<UserProfile>, AppError> {
+        let mut tables = self.db.tables().await?;
+        Ok(tables
+            .user_profiles
+            .iter_mut()
+            .find(|p| p.user_id == user_id)
+            .map(|profile| {
+                apply(profile);
+                profile.updated_at = Utc::now();
+                profile.clone()
+            }))
+    }
+}
+
+#[async_trait]
+impl UserProfileRepository for InMemoryUserProfileRepository {
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Option<UserProfile>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables
+            .user_profiles
+            .iter()
+            .find(|p| p.user_id == user_id)
+            .cloned())
+    }
+
+    async fn find_public_profile_by_user_id(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Option<PublicUserProfile>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables
+            .user_profiles
+            .iter()
+            .find(|p| p.user_id == user_id && p.is_public == Some(true))
+            .map(|p| PublicUserProfile {
+                id: p.id,
+                user_id: p.user_id,
+                bio: p.bio.clone(),
+                avatar_url: p.avatar_url.clone(),
+                location: p.location.clone(),
+                website: p.website.clone(),
+                social_links: p.social_links.clone(),
+                created_at: p.created_at,
+                updated_at: p.updated_at,
+            }))
+    }
+
+    async fn create(&self, new_profile: NewUserProfile) -> Result<UserProfile, AppError> {
+        let mut tables = self.db.tables().await?;
+        if tables
+            .user_profiles
+            .iter()
+            .any(|p| p.user_id == new_profile.user_id)
+        {
+            return Err(unique_violation("user_profiles_user_id_key"));
+        }
+
+        let now = Utc::now();
+        let profile = UserProfile {
+            id: Uuid::new_v4(),
+            user_id: new_profile.user_id,
+            bio: new_profile.bio,
+            avatar_url: new_profile.avatar_url,
+            phone: new_profile.phone,
+            date_of_birth: new_profile.date_of_birth,
+            timezone: new_profile.timezone,
+            language: new_profile.language,
+            notification_preferences: new_profile.notification_preferences,
+            privacy_settings: new_profile.privacy_settings,
+            location: new_profile.location,
+            website: new_profile.website,
+            social_links: new_profile.social_links,
+            preferences: new_profile.preferences,
+            is_public: new_profile.is_public,
+            created_at: now,
+            updated_at: now,
+        };
+        tables.user_profiles.push(profile.clone());
+        Ok(profile)
+    }
+
+    async fn update(
+        &self,
+        user_id: Uuid,
+        profile_data: UpdateUserProfile,
+    ) -> Result<Option<UserProfile>, AppError> {
+        self.modify(user_id, |profile| {
+            if let Some(bio) = profile_data.bio {
+                profile.bio = Some(bio);
+            }
+            if let Some(phone) = profile_data.phone {
+                profile.phone = Some(phone);
+            }
+            if let Some(date_of_birth) = profile_data.date_of_birth {
+                profile.date_of_birth = Some(date_of_birth);
+            }
+            if let Some(timezone) = profile_data.timezone {
+                profile.timezone = Some(timezone);
+            }
+            if let Some(language) = profile_data.language {
+                profile.language = Some(language);
+            }
+            if let Some(location) = profile_data.location {
+                profile.location = Some(location);
+            }
+            if let Some(website) = profile_data.website {
+                profile.website = Some(website);
+            }
+            if let Some(social_links) = profile_data.social_links {
+                profile.social_links = Some(social_links);
+            }
+            if let Some(is_public) = profile_data.is_public {
+                profile.is_public = Some(is_public);
+            }
+        })
+        .await
+    }
+
+    async fn update_preferences(
+        &self,
+        user_id: Uuid,
+        preferences: Value,
+    ) -> Result<Option<UserProfile>, AppError> {
+        self.modify(user_id, |profile| profile.preferences = Some(preferences))
+            .await
+    }
+
+    async fn update_notification_preferences(
+        &self,
+        user_id: Uuid,
+        notification_preferences: Value,
+    ) -> Result<Option<UserProfile>, AppError> {
+        self.modify(user_id, |profile| {
+            profile.notification_preferences = Some(notification_preferences)
+        })
+        .await
+    }
+
+    async fn update_privacy_settings(
+        &self,
+        user_id: Uuid,
+        privacy_settings: Value,
+    ) -> Result<Option<UserProfile>, AppError> {
+        self.modify(user_id, |profile| {
+            profile.privacy_settings = Some(privacy_settings)
+        })
+        .await
+    }
+
+    async fn update_avatar(
+        &self,
+        user_id: Uuid,
+        avatar_url: String,
+    ) -> Result<Option<UserProfile>, AppError> {
+        self.modify(user_id, |profile| profile.avatar_url = Some(avatar_url))
+            .await
+    }
+
+    async fn remove_avatar(&self, user_id: Uuid) -> Result<Option<UserProfile>, AppError> {
+        self.modify(user_id, |profile| profile.avatar_url = None)
+            .await
+    }
+
+    async fn delete_by_user_id(&self, user_id: Uuid) -> Result<Option<UserProfile>, AppError> {
+        let mut tables = self.db.tables().await?;
+        let index = tables
+            .user_profiles
+            .iter()
+            .position(|p| p.user_id == user_id);
+        Ok(index.map(|index| tables.user_profiles.remove(index)))
+    }
+}
+
+// ==================== Token Repository ====================
+
+pub struct InMemoryTokenRepository {
+    db: MemoryHandle,
+}
+
+impl InMemoryTokenRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self {
+            db: MemoryHandle::Store(store),
+        }
+    }
+}
+
+#[async_trait]
+impl TokenRepository for InMemoryTokenRepository {
+    async fn upsert_refresh_token(&self, token_data: UserToken) -> Result<(), AppError> {
+        let mut tables = self.db.tables().await?;
+        match tables
+            .user_tokens
+            .iter_mut()
+            .find(|t| t.user_id == token_data.user_id)
+        {
+            Some(existing) => *existing = token_data,
+            None => tables.user_tokens.push(token_data),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,235 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::domain::venue::{
+    Court, EditableCourt, EditableVenue, NewCourt, NewVenue, Venue, VenueRepository,
+};
+use crate::shared::AppError;
+
+use super::store::{foreign_key_violation, paginate, unique_violation, MemoryHandle, MemoryStore};
+
+pub struct InMemoryVenueRepository {
+    db: MemoryHandle,
+}
+
+impl InMemoryVenueRepository {
+    pub fn new(store: MemoryStore) -> Self {
+        Self {
+            db: MemoryHandle::Store(store),
+        }
+    }
+}
+
+#[async_trait]
+impl VenueRepository for InMemoryVenueRepository {
+    async fn create(&self, new_venue: NewVenue) -> Result<Venue, AppError> {
+        let mut tables = self.db.tables().await?;
+        let now = Utc::now();
+        let venue = Venue {
+            id: Uuid::new_v4(),
+            name: new_venue.name,
+            address: new_venue.address,
+            city: new_venue.city,
+            country: new_venue.country,
+            timezone: new_venue.timezone,
+            opening_hours: new_venue.opening_hours,
+            created_at: now,
+            updated_at: now,
+        };
+        tables.venues.push(venue.clone());
+        Ok(venue)
+    }
+
+    async fn find_by_id(&self, venue_id: Uuid) -> Result<Option<Venue>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables.venues.iter().find(|v| v.id == venue_id).cloned())
+    }
+
+    async fn find_all(&self, limit: i64, offset: i64) -> Result<Vec<Venue>, AppError> {
+        let tables = self.db.tables().await?;
+        let mut venues = tables.venues.clone();
+        venues.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(paginate(venues, limit, offset))
+    }
+
+    async fn update(
+        &self,
+        venue_id: Uuid,
+        venue_data: EditableVenue,
+    ) -> Result<Option<Venue>, AppError> {
+        let mut tables = self.db.tables().await?;
+        let Some(venue) = tables.venues.iter_mut().find(|v| v.id == venue_id) else {
+            return Ok(None);
+        };
+        if let Some(name) = venue_data.name {
+            venue.name = name;
+        }
+        if let Some(address) = venue_data.address {
+            venue.address = Some(address);
+        }
+        if let Some(city) = venue_data.city {
+            venue.city = Some(city);
+        }
+        if let Some(country) = venue_data.country {
+            venue.country = Some(country);
+        }
+        if let Some(timezone) = venue_data.timezone {
+            venue.timezone = timezone;
+        }
+        if let Some(opening_hours) = venue_data.opening_hours {
+            venue.opening_hours = opening_hours;
+        }
+        venue.updated_at = Utc::now();
+        Ok(Some(venue.clone()))
+    }
+
+    async fn delete(&self, venue_id: Uuid) -> Result<Option<Venue>, AppError> {
+        let mut tables = self.db.tables().await?;
+        let venue = tables.venues.iter().find(|v| v.id == venue_id).cloned();
+        if venue.is_some() {
+            tables.delete_venue_cascade(venue_id);
+        }
+        Ok(venue)
+    }
+
+    async fn create_court(&self, venue_id: Uuid, new_court: NewCourt) -> Result<Court, AppError> {
+        let mut tables = self.db.tables().await?;
+        if !tables.venues.iter().any(|v| v.id == venue_id) {
+            return Err(foreign_key_violation("courts"));
+        }
+        if tables
+            .courts
+            .iter()
+            .any(|c| c.venue_id == venue_id && c.name == new_court.name)
+        {
+            return Err(unique_violation("courts_venue_id_name_key"));
+        }
+        let now = Utc::now();
+        let court = Court {
+            id: Uuid::new_v4(),
+            venue_id,
+            name: new_court.name,
+            kind: new_court.kind,
+            surface: new_court.surface,
+            is_active: true,
+            availability: new_court.availability,
+            created_at: now,
+            updated_at: now,
+        };
+        tables.courts.push(court.clone());
+        Ok(court)
+    }
+
+    async fn find_court(&self, court_id: Uuid) -> Result<Option<Court>, AppError> {
+        let tables = self.db.tables().await?;
+        Ok(tables.courts.iter().find(|c| c.id == court_id).cloned())
+    }
+
+    async fn find_courts(&self, venue_id: Uuid) -> Result<Vec<Court>, AppError> {
+        let tables = self.db.tables().await?;
+        let mut courts: Vec<Court> = tables
+            .courts
+            .iter()
+            .filter(|c| c.venue_id == venue_id)
+            .cloned()
+            .collect();
+        courts.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(courts)
+    }
+
+    async fn update_court(
+        &self,
+        court_id: Uuid,
+        court_data: EditableCourt,
+    ) -> Result<Option<Court>, AppError> {
+        let mut tables = self.db.tables().await?;
+        let Some(venue_id) = tables
+            .courts
+            .iter()
+            .find(|c| c.id == court_id)
+            .map(|c| c.venue_id)
+        else {
+            return Ok(None);
+        };
+        if let Some(name) = &court_data.name {
+            if tables
+                .courts
+                .iter()
+                .any(|c| c.venue_id == venue_id && c.id != court_id && &c.name == name)
+            {
+                return Err(unique_violation("courts_venue_id_name_key"));
+            }
+        }
+        let Some(court) = tables.courts.iter_mut().find(|c| c.id == court_id) else {
+            return Ok(None);
+        };
+        if let Some(name) = court_data.name {
+            court.name = name;
+        }
+        if let Some(kind) = court_data.kind {
+            court.kind = kind;
+        }
+        if let Some(surface) = court_data.surface {
+            court.surface = Some(surface);
+        }
+        if let Some(is_active) = court_data.is_active {
+            court.is_active = is_active;
+        }
+        if let Some(availability) = court_data.availability {
+            court.availability = availability;
+        }
+        court.updated_at = Utc::now();
+        Ok(Some(court.clone()))
+    }
+
+    async fn delete_court(&self, court_id: Uuid) -> Result<Option<Court>, AppError> {
+        let mut tables = self.db.tables().await?;
+        let court = tables.courts.iter().find(|c| c.id == court_id).cloned();
+        if court.is_some() {
+            tables.delete_court_cascade(court_id);
+        }
+        Ok(court)
+    }
+
+    async fn add_to_tournament(&self, tournament_id: Uuid, venue_id: Uuid) -> Result<(), AppError> {
+        let mut tables = self.db.tables().await?;
+        if !tables.tournaments.iter().any(|t| t.id == tournament_id)
+            || !tables.venues.iter().any(|v| v.id == venue_id)
+        {
+            return Err(foreign_key_violation("tournament_venues"));
+        }
+        if !tables
+            .tournament_venues
+            .contains(&(tournament_id, venue_id))
+        {
+            tables.tournament_venues.push((tournament_id, venue_id));
+        }
+        Ok(())
+    }
+
+    async fn remove_from_tournament(
+        &self,
+        tournament_id: Uuid,
+        venue_id: Uuid,
+    ) -> Result<bool, AppError> {
+        let mut tables = self.db.tables().await?;
+        let before = tables.tournament_venues.len();
+        tables
+            .tournament_venues
+            .retain(|link| *link != (tournament_id, venue_id));
+        Ok(tables.tournament_venues.len() < before)
+    }
+
+    async fn find_by_tournament(&self, tournament_id: Uuid) -> Result<Vec<Venue>, AppError> {
+        let tables = self.db.tables().await?;
+        let mut venues: Vec<Venue> = tables
+            .venues
+            .iter()
+            .filter(|v| tables.tournament_venues.contains(&(tournament_id, v.id)))
+            .cloned()
+            .collect();
+        venues.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(venues)
+    }
+}
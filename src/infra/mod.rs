@@ -3,3 +3,8 @@
 pub mod api;
 pub mod cloudinary;
 pub mod db;
+pub mod email;
+pub mod push;
+#[cfg(feature = "in-memory")]
+pub mod memory;
+pub mod repositories;
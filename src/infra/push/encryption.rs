@@ -0,0 +1,84 @@
+//! Message encryption for Web Push (RFC 8291), using the `aes128gcm` content coding
+//! from RFC 8188 with a single record.
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes128Gcm, Nonce};
+use hkdf::Hkdf;
+use p256::{PublicKey, SecretKey};
+use sha2::Sha256;
+
+use crate::domain::notification::push::decode_subscription_keys;
+use crate::shared::errors::AppError;
+
+/// Record size written in the header; the whole message fits in one record
+const RECORD_SIZE: u32 = 4096;
+
+/// Encrypts `payload` for a subscription with a fresh key pair and salt
+pub fn encrypt(payload: &[u8], p256dh: &str, auth: &str) -> Result<Vec<u8>, AppError> {
+    // Retry the negligible chance of bytes outside the curve order
+    let server_key = loop {
+        if let Ok(key) = SecretKey::from_slice(&rand::random::<[u8; 32]>()) {
+            break key;
+        }
+    };
+    encrypt_with_keys(
+        payload,
+        p256dh,
+        auth,
+        &server_key.to_bytes().into(),
+        rand::random(),
+    )
+}
+
+/// Encrypts `payload` with the given application server private key and salt.
+/// Only use fixed keys to reproduce test vectors; both must be fresh per message.
+pub fn encrypt_with_keys(
+    payload: &[u8],
+    p256dh: &str,
+    auth: &str,
+    server_private_key: &[u8; 32],
+    salt: [u8; 16],
+) -> Result<Vec<u8>, AppError> {
+    let (ua_public_bytes, auth_secret) = decode_subscription_keys(p256dh, auth)?;
+    let ua_public = PublicKey::from_sec1_bytes(&ua_public_bytes)
+        .map_err(|_| AppError::ValidationError("p256dh is not a valid P-256 key".into()))?;
+    let server_secret = SecretKey::from_slice(server_private_key)
+        .map_err(|_| AppError::InternalError("Invalid push encryption key".into()))?;
+    let server_public = server_secret.public_key().to_sec1_bytes();
+
+    let shared =
+        p256::ecdh::diffie_hellman(server_secret.to_nonzero_scalar(), ua_public.as_affine());
+
+    // IKM = HKDF(auth_secret, ecdh_secret, "WebPush: info" || 0x00 || ua_public || as_public)
+    let mut key_info = b"WebPush: info\0".to_vec();
+    key_info.extend_from_slice(&ua_public_bytes);
+    key_info.extend_from_slice(&server_public);
+    let mut ikm = [0u8; 32];
+    Hkdf::<Sha256>::new(Some(&auth_secret), shared.raw_secret_bytes().as_slice())
+        .expand(&key_info, &mut ikm)
+        .map_err(|_| AppError::InternalError("Push key derivation failed".into()))?;
+
+    let content = Hkdf::<Sha256>::new(Some(&salt), &ikm);
+    let mut cek = [0u8; 16];
+    let mut nonce = [0u8; 12];
+    content
+        .expand(b"Content-Encoding: aes128gcm\0", &mut cek)
+        .and_then(|_| content.expand(b"Content-Encoding: nonce\0", &mut nonce))
+        .map_err(|_| AppError::InternalError("Push key derivation failed".into()))?;
+
+    // A single record ends with the 0x02 delimiter and no padding
+    let mut plaintext = payload.to_vec();
+    plaintext.push(0x02);
+    let ciphertext = Aes128Gcm::new(&cek.into())
+        .encrypt(Nonce::from_slice(&nonce), plaintext.as_slice())
+        .map_err(|_| AppError::InternalError("Push payload encryption failed".into()))?;
+
+    // Header: salt || record size || key id length || key id (the server public key)
+    let mut message = Vec::with_capacity(16 + 4 + 1 + server_public.len() + ciphertext.len());
+    message.extend_from_slice(&salt);
+    message.extend_from_slice(&RECORD_SIZE.to_be_bytes());
+    message.push(server_public.len() as u8);
+    message.extend_from_slice(&server_public);
+    message.extend_from_slice(&ciphertext);
+    Ok(message)
+}
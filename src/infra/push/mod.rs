@@ -0,0 +1,9 @@
+//! Web Push delivery: RFC 8291 payload encryption and RFC 8292 (VAPID) authentication.
+//! Enabled by VAPID_PRIVATE_KEY and VAPID_SUBJECT.
+
+pub mod encryption;
+mod sender;
+mod vapid;
+
+pub use sender::WebPushSender;
+pub use vapid::VapidKeys;
@@ -0,0 +1,62 @@
+//! Sends encrypted messages to browser push services.
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use reqwest::StatusCode;
+
+use super::encryption::encrypt;
+use super::vapid::VapidKeys;
+use crate::domain::notification::{PushOutcome, PushSender, PushSubscription};
+use crate::shared::errors::AppError;
+
+/// How long a push service keeps a message for an offline browser, in seconds
+const TTL_SECS: u32 = 24 * 60 * 60;
+
+/// Delivers Web Push messages with VAPID authentication
+pub struct WebPushSender {
+    client: reqwest::Client,
+    vapid: VapidKeys,
+}
+
+impl WebPushSender {
+    pub fn new(vapid: VapidKeys) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            vapid,
+        }
+    }
+}
+
+#[async_trait]
+impl PushSender for WebPushSender {
+    async fn send(
+        &self,
+        subscription: &PushSubscription,
+        payload: &[u8],
+    ) -> Result<PushOutcome, AppError> {
+        let body = encrypt(payload, &subscription.p256dh, &subscription.auth)?;
+        let authorization = self
+            .vapid
+            .authorization(&subscription.endpoint, Utc::now() + Duration::hours(12))?;
+        let response = self
+            .client
+            .post(&subscription.endpoint)
+            .header("Authorization", authorization)
+            .header("Content-Encoding", "aes128gcm")
+            .header("Content-Type", "application/octet-stream")
+            .header("TTL", TTL_SECS.to_string())
+            .header("Urgency", "high")
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| AppError::InternalError(format!("Push request failed: {}", e)))?;
+        match response.status() {
+            status if status.is_success() => Ok(PushOutcome::Delivered),
+            StatusCode::NOT_FOUND | StatusCode::GONE => Ok(PushOutcome::Gone),
+            status => Err(AppError::InternalError(format!(
+                "Push service responded {}",
+                status
+            ))),
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! Voluntary Application Server Identification (RFC 8292): an ES256 JWT per push
+//! service origin, signed with the key browsers subscribed with.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use p256::ecdsa::{signature::Signer, Signature, SigningKey};
+use serde_json::json;
+
+use crate::shared::config::VapidConfig;
+use crate::shared::errors::AppError;
+
+/// The application server's VAPID key pair and contact
+pub struct VapidKeys {
+    signing_key: SigningKey,
+    public_key: String,
+    subject: String,
+}
+
+impl VapidKeys {
+    pub fn from_config(config: &VapidConfig) -> Result<Self, AppError> {
+        let invalid = || {
+            AppError::InternalError(
+                "VAPID_PRIVATE_KEY must be a base64url P-256 private key".into(),
+            )
+        };
+        let bytes = URL_SAFE_NO_PAD
+            .decode(config.private_key.trim().trim_end_matches('='))
+            .map_err(|_| invalid())?;
+        let signing_key = SigningKey::from_slice(&bytes).map_err(|_| invalid())?;
+        let public_key = URL_SAFE_NO_PAD.encode(
+            signing_key
+                .verifying_key()
+                .to_encoded_point(false)
+                .as_bytes(),
+        );
+        Ok(Self {
+            signing_key,
+            public_key,
+            subject: config.subject.clone(),
+        })
+    }
+
+    /// Uncompressed public key, base64url; the `applicationServerKey` for browsers
+    pub fn public_key(&self) -> &str {
+        &self.public_key
+    }
+
+    /// `Authorization` header value for a push to `endpoint`
+    pub fn authorization(
+        &self,
+        endpoint: &str,
+        expires: DateTime<Utc>,
+    ) -> Result<String, AppError> {
+        let url = url::Url::parse(endpoint)
+            .map_err(|_| AppError::InternalError(format!("Invalid push endpoint {}", endpoint)))?;
+        let header = URL_SAFE_NO_PAD.encode(json!({ "typ": "JWT", "alg": "ES256" }).to_string());
+        let claims = URL_SAFE_NO_PAD.encode(
+            json!({
+                "aud": url.origin().ascii_serialization(),
+                "exp": expires.timestamp(),
+                "sub": self.subject,
+            })
+            .to_string(),
+        );
+        let signing_input = format!("{}.{}", header, claims);
+        let signature: Signature = self.signing_key.sign(signing_input.as_bytes());
+        Ok(format!(
+            "vapid t={}.{}, k={}",
+            signing_input,
+            URL_SAFE_NO_PAD.encode(signature.to_bytes()),
+            self.public_key
+        ))
+    }
+}
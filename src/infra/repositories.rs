@@ -0,0 +1,141 @@
+// Repository wiring - picks the storage backend the services run on
+
+use std::sync::Arc;
+
+use crate::domain::check_in::CheckInWindowRepository;
+use crate::domain::court_queue::CourtCallRepository;
+use crate::domain::import::ImportRepository;
+use crate::domain::match_domain::{MatchRepository, MatchResultRepository};
+use crate::domain::notification::{
+    NotificationDeliveryRepository, NotificationRepository, PushSubscriptionRepository,
+};
+use crate::domain::outbox::OutboxRepository;
+use crate::domain::participant::{PlayerRepository, TeamMemberRepository, TeamRepository};
+use crate::domain::payment::PaymentRepository;
+use crate::domain::statistics::StatisticsRepository;
+use crate::domain::ticket::MatchTicketRepository;
+use crate::domain::tournament::{
+    TournamentBracketRepository, TournamentCategoryRepository, TournamentRegistrationRepository,
+    TournamentRepository, TournamentStandingsRepository,
+};
+use crate::domain::unit_of_work::UnitOfWorkFactory;
+use crate::domain::user::{TokenRepository, UserProfileRepository, UserRepository};
+use crate::domain::venue::VenueRepository;
+use crate::infra::db::{self, pool::DbPool};
+
+/// One implementation of every repository trait, shared by all services
+#[derive(Clone)]
+pub struct Repositories {
+    pub users: Arc<dyn UserRepository>,
+    pub tokens: Arc<dyn TokenRepository>,
+    pub profiles: Arc<dyn UserProfileRepository>,
+    pub players: Arc<dyn PlayerRepository>,
+    pub teams: Arc<dyn TeamRepository>,
+    pub team_members: Arc<dyn TeamMemberRepository>,
+    pub tournaments: Arc<dyn TournamentRepository>,
+    pub categories: Arc<dyn TournamentCategoryRepository>,
+    pub registrations: Arc<dyn TournamentRegistrationRepository>,
+    pub brackets: Arc<dyn TournamentBracketRepository>,
+    pub standings: Arc<dyn TournamentStandingsRepository>,
+    pub matches: Arc<dyn MatchRepository>,
+    pub match_results: Arc<dyn MatchResultRepository>,
+    pub notifications: Arc<dyn NotificationRepository>,
+    pub notification_deliveries: Arc<dyn NotificationDeliveryRepository>,
+    pub push_subscriptions: Arc<dyn PushSubscriptionRepository>,
+    pub payments: Arc<dyn PaymentRepository>,
+    pub statistics: Arc<dyn StatisticsRepository>,
+    pub imports: Arc<dyn ImportRepository>,
+    pub outbox: Arc<dyn OutboxRepository>,
+    pub venues: Arc<dyn VenueRepository>,
+    pub court_calls: Arc<dyn CourtCallRepository>,
+    pub check_in_windows: Arc<dyn CheckInWindowRepository>,
+    pub match_tickets: Arc<dyn MatchTicketRepository>,
+    pub unit_of_work: Arc<dyn UnitOfWorkFactory>,
+}
+
+impl Repositories {
+    /// Postgres-backed repositories
+    pub fn postgres(pool: DbPool) -> Self {
+        Self {
+            users: Arc::new(db::PgUserRepository::new(pool.clone())),
+            tokens: Arc::new(db::PgTokenRepository::new(pool.clone())),
+            profiles: Arc::new(db::PgUserProfileRepository::new(pool.clone())),
+            players: Arc::new(db::PgPlayerRepository::new(pool.clone())),
+            teams: Arc::new(db::PgTeamRepository::new(pool.clone())),
+            team_members: Arc::new(db::PgTeamMemberRepository::new(pool.clone())),
+            tournaments: Arc::new(db::PgTournamentRepository::new(pool.clone())),
+            categories: Arc::new(db::PgTournamentCategoryRepository::new(pool.clone())),
+            registrations: Arc::new(db::PgTournamentRegistrationRepository::new(pool.clone())),
+            brackets: Arc::new(db::PgTournamentBracketRepository::new(pool.clone())),
+            standings: Arc::new(db::PgTournamentStandingsRepository::new(pool.clone())),
+            matches: Arc::new(db::PgMatchRepository::new(pool.clone())),
+            match_results: Arc::new(db::PgMatchResultRepository::new(pool.clone())),
+            notifications: Arc::new(db::PgNotificationRepository::new(pool.clone())),
+            notification_deliveries: Arc::new(db::PgNotificationDeliveryRepository::new(
+                pool.clone(),
+            )),
+            push_subscriptions: Arc::new(db::PgPushSubscriptionRepository::new(pool.clone())),
+            payments: Arc::new(db::PgPaymentRepository::new(pool.clone())),
+            statistics: Arc::new(db::PgStatisticsRepository::new(pool.clone())),
+            imports: Arc::new(db::PgImportRepository::new(pool.clone())),
+            outbox: Arc::new(db::PgOutboxRepository::new(pool.clone())),
+            venues: Arc::new(db::PgVenueRepository::new(pool.clone())),
+            court_calls: Arc::new(db::PgCourtCallRepository::new(pool.clone())),
+            check_in_windows: Arc::new(db::PgCheckInWindowRepository::new(pool.clone())),
+            match_tickets: Arc::new(db::PgMatchTicketRepository::new(pool.clone())),
+            unit_of_work: Arc::new(db::PgUnitOfWorkFactory::new(pool)),
+        }
+    }
+
+    /// Repositories over one shared in-process store - no database required
+    #[cfg(feature = "in-memory")]
+    pub fn in_memory() -> Self {
+        Self::in_memory_with(crate::infra::memory::MemoryStore::new())
+    }
+
+    /// In-memory repositories over an existing store, e.g. one seeded by a test
+    #[cfg(feature = "in-memory")]
+    pub fn in_memory_with(store: crate::infra::memory::MemoryStore) -> Self {
+        use crate::infra::memory;
+
+        Self {
+            users: Arc::new(memory::InMemoryUserRepository::new(store.clone())),
+            tokens: Arc::new(memory::InMemoryTokenRepository::new(store.clone())),
+            profiles: Arc::new(memory::InMemoryUserProfileRepository::new(store.clone())),
+            players: Arc::new(memory::InMemoryPlayerRepository::new(store.clone())),
+            teams: Arc::new(memory::InMemoryTeamRepository::new(store.clone())),
+            team_members: Arc::new(memory::InMemoryTeamMemberRepository::new(store.clone())),
+            tournaments: Arc::new(memory::InMemoryTournamentRepository::new(store.clone())),
+            categories: Arc::new(memory::InMemoryTournamentCategoryRepository::new(
+                store.clone(),
+            )),
+            registrations: Arc::new(memory::InMemoryTournamentRegistrationRepository::new(
+                store.clone(),
+            )),
+            brackets: Arc::new(memory::InMemoryTournamentBracketRepository::new(
+                store.clone(),
+            )),
+            standings: Arc::new(memory::InMemoryTournamentStandingsRepository::new(
+                store.clone(),
+            )),
+            matches: Arc::new(memory::InMemoryMatchRepository::new(store.clone())),
+            match_results: Arc::new(memory::InMemoryMatchResultRepository::new(store.clone())),
+            notifications: Arc::new(memory::InMemoryNotificationRepository::new(store.clone())),
+            notification_deliveries: Arc::new(memory::InMemoryNotificationDeliveryRepository::new(
+                store.clone(),
+            )),
+            push_subscriptions: Arc::new(memory::InMemoryPushSubscriptionRepository::new(
+                store.clone(),
+            )),
+            payments: Arc::new(memory::InMemoryPaymentRepository::new(store.clone())),
+            statistics: Arc::new(memory::InMemoryStatisticsRepository::new(store.clone())),
+            imports: Arc::new(memory::InMemoryImportRepository::new(store.clone())),
+            outbox: Arc::new(memory::InMemoryOutboxRepository::new(store.clone())),
+            venues: Arc::new(memory::InMemoryVenueRepository::new(store.clone())),
+            court_calls: Arc::new(memory::InMemoryCourtCallRepository::new(store.clone())),
+            check_in_windows: Arc::new(memory::InMemoryCheckInWindowRepository::new(store.clone())),
+            match_tickets: Arc::new(memory::InMemoryMatchTicketRepository::new(store.clone())),
+            unit_of_work: Arc::new(memory::InMemoryUnitOfWorkFactory::new(store)),
+        }
+    }
+}
@@ -6,7 +6,9 @@ use utoipa_swagger_ui::SwaggerUi;
 
 use crate::infra::api::openapi::ApiDoc;
 use crate::infra::api::sse::Broadcaster;
+use crate::infra::api::state::AppServices;
 use crate::infra::cloudinary::{CloudinaryClient, CloudinaryConfig};
+use crate::infra::repositories::Repositories;
 
 // ==================== DDD ARCHITECTURE ====================
 mod application;
@@ -21,72 +23,52 @@ async fn main() -> std::io::Result<()> {
     let app_config = shared::AppConfig::from_env();
     let bind_address = app_config.bind_address();
 
-    let pool = infra::db::DbConfig::create_db_pool()
-        .await
-        .expect("Failed to create pool");
-
-    let user_repo = Arc::new(infra::db::PgUserRepository::new(pool.clone()));
-    let token_repo = Arc::new(infra::db::PgTokenRepository::new(pool.clone()));
-    let profile_repo = Arc::new(infra::db::PgUserProfileRepository::new(pool.clone()));
-    let auth_services = Arc::new(application::AuthServices::new(
-        Arc::clone(&user_repo),
-        Arc::clone(&token_repo),
-    ));
-    let user_services = Arc::new(application::UserServices::new(
-        Arc::clone(&user_repo),
-        Arc::clone(&profile_repo),
-    ));
-
-    let player_repo = Arc::new(infra::db::PgPlayerRepository::new(pool.clone()));
-    let team_repo = Arc::new(infra::db::PgTeamRepository::new(pool.clone()));
-    let team_member_repo = Arc::new(infra::db::PgTeamMemberRepository::new(pool.clone()));
-    let participant_services = Arc::new(application::ParticipantServices::new(
-        Arc::clone(&player_repo),
-        Arc::clone(&team_repo),
-        Arc::clone(&team_member_repo),
-    ));
-
-    let tournament_repo = Arc::new(infra::db::PgTournamentRepository::new(pool.clone()));
-    let tournament_category_repo =
-        Arc::new(infra::db::PgTournamentCategoryRepository::new(pool.clone()));
-    let tournament_registration_repo = Arc::new(
-        infra::db::PgTournamentRegistrationRepository::new(pool.clone()),
+    let (repositories, pool) = if std::env::args().any(|arg| arg == "--in-memory") {
+        (in_memory_repositories().await, None)
+    } else {
+        let pool = infra::db::DbConfig::create_db_pool()
+            .await
+            .expect("Failed to create pool");
+        (Repositories::postgres(pool.clone()), Some(pool))
+    };
+    let broadcaster = Broadcaster::create(&shared::config::SseConfig::from_env(), pool);
+    let services = AppServices::new(&repositories, broadcaster.clone());
+    let env_config = shared::EnvConfig::from_env();
+    let email_sender = infra::email::email_sender(&env_config.email)
+        .expect("Email transport must be configured correctly");
+    let push_sender = env_config.vapid.as_ref().map(|vapid| {
+        let keys = infra::push::VapidKeys::from_config(vapid).expect("Invalid VAPID_PRIVATE_KEY");
+        Arc::new(infra::push::WebPushSender::new(keys)) as Arc<dyn domain::notification::PushSender>
+    });
+    let unsubscribe_links = env_config.email.unsubscribe_url.clone().map(|url| {
+        application::event_subscribers::UnsubscribeLinks {
+            url,
+            secret: env_config.jwt_secret.clone(),
+        }
+    });
+    let dispatcher = services.outbox_dispatcher(
+        &repositories,
+        broadcaster.clone(),
+        email_sender,
+        push_sender,
+        unsubscribe_links,
+        shared::config::OutboxConfig::from_env(),
+    );
+    actix_web::rt::spawn(dispatcher.run());
+    let reminders = services.reminder_scheduler(
+        &repositories,
+        shared::config::ReminderConfig::from_env(),
+    );
+    actix_web::rt::spawn(reminders.run());
+    let delays =
+        services.delay_notifier(&repositories, shared::config::DelayConfig::from_env());
+    actix_web::rt::spawn(delays.run());
+    actix_web::rt::spawn(
+        Arc::clone(&services.court_queue).run(shared::config::CourtCallConfig::from_env()),
+    );
+    actix_web::rt::spawn(
+        Arc::clone(&services.check_in).run(shared::config::CheckInConfig::from_env()),
     );
-    let tournament_bracket_repo =
-        Arc::new(infra::db::PgTournamentBracketRepository::new(pool.clone()));
-    let tournament_standings_repo = Arc::new(infra::db::PgTournamentStandingsRepository::new(
-        pool.clone(),
-    ));
-    let tournament_services = Arc::new(application::TournamentServices::new(
-        Arc::clone(&tournament_repo),
-        Arc::clone(&tournament_category_repo),
-        Arc::clone(&tournament_registration_repo),
-        Arc::clone(&tournament_bracket_repo),
-        Arc::clone(&tournament_standings_repo),
-    ));
-
-    let match_repo = Arc::new(infra::db::PgMatchRepository::new(pool.clone()));
-    let match_result_repo = Arc::new(infra::db::PgMatchResultRepository::new(pool.clone()));
-    let match_services = Arc::new(application::MatchServices::new(
-        Arc::clone(&match_repo),
-        Arc::clone(&match_result_repo),
-    ));
-
-    let notification_repo = Arc::new(infra::db::PgNotificationRepository::new(pool.clone()));
-    let notification_services = Arc::new(application::NotificationServices::new(Arc::clone(
-        &notification_repo,
-    )));
-
-    let payment_repo = Arc::new(infra::db::PgPaymentRepository::new(pool.clone()));
-    let payment_services = Arc::new(application::PaymentServices::new(Arc::clone(&payment_repo)));
-
-    let statistics_repo = Arc::new(infra::db::PgStatisticsRepository::new(pool.clone()));
-    let statistics_services = Arc::new(application::StatisticsServices::new(
-        Arc::clone(&statistics_repo),
-        Arc::clone(&player_repo),
-    ));
-
-    let broadcaster = Broadcaster::create();
 
     let cloudinary_config =
         CloudinaryConfig::from_env().expect("CLOUDINARY_URL must be set and valid");
@@ -103,18 +85,36 @@ async fn main() -> std::io::Result<()> {
             )
             .app_data(web::Data::new(Arc::clone(&broadcaster)))
             .app_data(web::Data::new(Arc::clone(&cloudinary_client)))
-            .app_data(web::Data::new(pool.clone()))
-            .app_data(web::Data::new(Arc::clone(&auth_services)))
-            .app_data(web::Data::new(Arc::clone(&user_services)))
-            .app_data(web::Data::new(Arc::clone(&participant_services)))
-            .app_data(web::Data::new(Arc::clone(&tournament_services)))
-            .app_data(web::Data::new(Arc::clone(&match_services)))
-            .app_data(web::Data::new(Arc::clone(&notification_services)))
-            .app_data(web::Data::new(Arc::clone(&payment_services)))
-            .app_data(web::Data::new(Arc::clone(&statistics_services)))
+            .configure(|cfg| services.register(cfg))
             .configure(infra::api::api_routes)
     })
     .bind(&bind_address)?
     .run()
     .await
 }
+
+/// Repositories for `--in-memory` demo mode, seeded with a demo user whose token is printed
+#[cfg(feature = "in-memory")]
+async fn in_memory_repositories() -> Repositories {
+    let repositories = Repositories::in_memory();
+    let demo_user = repositories
+        .users
+        .create(domain::user::NewUser {
+            name: Some("Demo Organizer".to_string()),
+            email: "demo@example.com".to_string(),
+            google_id: "demo".to_string(),
+        })
+        .await
+        .expect("Failed to seed demo user");
+    let token = shared::jwt::generate_jwt(demo_user.id, &demo_user.email)
+        .expect("Failed to sign demo token");
+
+    println!("Using in-memory storage; all data is lost on shutdown");
+    println!("Demo bearer token: {}", token);
+    repositories
+}
+
+#[cfg(not(feature = "in-memory"))]
+async fn in_memory_repositories() -> Repositories {
+    panic!("--in-memory requires the server to be built with the `in-memory` feature")
+}
@@ -75,6 +75,15 @@ impl ApiResponse {
         error_response(StatusCode::CONFLICT, message, "CONFLICT")
     }
 
+    /// 409 whose `error` is `{"message": ..., "details": ...}`
+    pub fn conflict_with_details<T: Serialize>(message: &str, details: T) -> HttpResponse {
+        error_response(
+            StatusCode::CONFLICT,
+            serde_json::json!({ "message": message, "details": details }),
+            "CONFLICT",
+        )
+    }
+
     pub fn payload_too_large(message: &str) -> HttpResponse {
         error_response(
             StatusCode::from_u16(413).unwrap_or(StatusCode::BAD_REQUEST),
@@ -0,0 +1,159 @@
+//! Minimal RFC 4180 CSV reader and writer used by the import and export endpoints.
+//!
+//! Tables keep their headers in a fixed order so exported files stay stable between
+//! releases; callers can narrow them with [`CsvTable::select_columns`].
+
+use serde::Serialize;
+
+use crate::shared::AppError;
+
+/// A header row plus data rows, all rendered as strings
+#[derive(Debug, Clone, Default)]
+pub struct CsvTable {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl CsvTable {
+    pub fn new(headers: &[&str]) -> Self {
+        Self {
+            headers: headers.iter().map(|h| h.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Appends a row. Short rows are padded and long rows truncated to the header width.
+    pub fn push_row(&mut self, mut row: Vec<String>) {
+        row.resize(self.headers.len(), String::new());
+        self.rows.push(row);
+    }
+
+    /// Keeps only the requested columns, in the order they were requested.
+    pub fn select_columns(&self, columns: &[String]) -> Result<CsvTable, AppError> {
+        let mut indexes = Vec::with_capacity(columns.len());
+        for column in columns {
+            let idx = self
+                .headers
+                .iter()
+                .position(|h| h == column)
+                .ok_or_else(|| {
+                    AppError::BadRequest(format!(
+                        "Unknown column '{}'. Available columns: {}",
+                        column,
+                        self.headers.join(", ")
+                    ))
+                })?;
+            indexes.push(idx);
+        }
+
+        Ok(CsvTable {
+            headers: indexes.iter().map(|&i| self.headers[i].clone()).collect(),
+            rows: self
+                .rows
+                .iter()
+                .map(|row| indexes.iter().map(|&i| row[i].clone()).collect())
+                .collect(),
+        })
+    }
+
+    /// Renders the table as CSV with CRLF line endings.
+    pub fn to_csv_string(&self) -> String {
+        let mut out = String::new();
+        write_record(&mut out, &self.headers);
+        for row in &self.rows {
+            write_record(&mut out, row);
+        }
+        out
+    }
+}
+
+fn write_record(out: &mut String, fields: &[String]) {
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        out.push_str(&escape_field(field));
+    }
+    out.push_str("\r\n");
+}
+
+/// Quotes a field when it contains a delimiter, quote, line break or surrounding whitespace.
+pub fn escape_field(field: &str) -> String {
+    let needs_quotes = field.contains([',', '"', '\r', '\n'])
+        || field.starts_with(char::is_whitespace)
+        || field.ends_with(char::is_whitespace);
+
+    if needs_quotes {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Renders a serde-serializable value (typically a snake_case enum) as a plain cell value.
+pub fn serde_label<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(s)) => s,
+        Ok(serde_json::Value::Null) | Err(_) => String::new(),
+        Ok(other) => other.to_string(),
+    }
+}
+
+/// Renders an optional value, leaving the cell empty for `None`.
+pub fn opt_cell<T: ToString>(value: &Option<T>) -> String {
+    value.as_ref().map(|v| v.to_string()).unwrap_or_default()
+}
+
+/// Parses CSV text into records. Accepts `,` or `;` delimiters (whichever appears first
+/// in the header line), quoted fields with embedded line breaks, CRLF or LF line endings
+/// and a leading UTF-8 BOM. Blank lines are skipped.
+pub fn parse_csv(input: &str) -> Vec<Vec<String>> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let first_line = input.lines().next().unwrap_or("");
+    let delimiter = match (first_line.find(','), first_line.find(';')) {
+        (Some(c), Some(s)) if s < c => ';',
+        (None, Some(_)) => ';',
+        _ => ',',
+    };
+
+    let mut records = Vec::new();
+    let mut record: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            '\r' => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                push_record(&mut records, std::mem::take(&mut record));
+            }
+            c if c == delimiter => record.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        push_record(&mut records, record);
+    }
+    records
+}
+
+fn push_record(records: &mut Vec<Vec<String>>, record: Vec<String>) {
+    if record.iter().any(|f| !f.trim().is_empty()) {
+        records.push(record);
+    }
+}
@@ -13,11 +13,24 @@ pub enum AppError {
     Unauthorized(String),
     Forbidden(String),
     Conflict(String),
+    /// A conflict with structured details for the client, e.g. the clashing records
+    ConflictWithDetails(String, serde_json::Value),
     InternalError(String),
     DatabaseError(String),
     ValidationError(String),
 }
 
+impl AppError {
+    /// Whether the database rejected a write for breaking a unique constraint
+    pub fn is_unique_violation(&self) -> bool {
+        matches!(
+            self,
+            AppError::DatabaseError(msg)
+                if msg.contains("duplicate key value violates unique constraint")
+        )
+    }
+}
+
 impl fmt::Display for AppError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -26,6 +39,7 @@ impl fmt::Display for AppError {
             AppError::Unauthorized(msg) => write!(f, "Unauthorized: {}", msg),
             AppError::Forbidden(msg) => write!(f, "Forbidden: {}", msg),
             AppError::Conflict(msg) => write!(f, "Conflict: {}", msg),
+            AppError::ConflictWithDetails(msg, _) => write!(f, "Conflict: {}", msg),
             AppError::InternalError(msg) => write!(f, "Internal error: {}", msg),
             AppError::DatabaseError(msg) => write!(f, "Database error: {}", msg),
             AppError::ValidationError(msg) => write!(f, "Validation error: {}", msg),
@@ -43,6 +57,9 @@ impl ResponseError for AppError {
             AppError::Unauthorized(msg) => ApiResponse::unauthorized(msg),
             AppError::Forbidden(msg) => ApiResponse::forbidden(msg),
             AppError::Conflict(msg) => ApiResponse::conflict(msg),
+            AppError::ConflictWithDetails(msg, details) => {
+                ApiResponse::conflict_with_details(msg, details)
+            }
             AppError::InternalError(msg) => ApiResponse::error(msg),
             AppError::DatabaseError(msg) => ApiResponse::error(msg),
             AppError::ValidationError(msg) => ApiResponse::bad_request(msg),
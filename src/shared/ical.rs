@@ -0,0 +1,153 @@
+//! Minimal RFC 5545 iCalendar writer for the schedule feeds calendar apps subscribe to.
+//!
+//! Only what a read-only feed needs: one VCALENDAR of VEVENTs in UTC, with text escaped
+//! and lines folded at 75 octets.
+
+use chrono::{DateTime, Utc};
+
+pub const CONTENT_TYPE: &str = "text/calendar; charset=utf-8";
+
+const PRODUCT_ID: &str = "-//Tournament Server//Schedule Feed//EN";
+
+/// How often subscribers are asked to fetch the feed again
+const REFRESH_INTERVAL: &str = "PT15M";
+
+const MAX_LINE_OCTETS: usize = 75;
+
+/// STATUS of an event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventStatus {
+    Confirmed,
+    Tentative,
+    Cancelled,
+}
+
+impl EventStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            EventStatus::Confirmed => "CONFIRMED",
+            EventStatus::Tentative => "TENTATIVE",
+            EventStatus::Cancelled => "CANCELLED",
+        }
+    }
+}
+
+/// One VEVENT
+#[derive(Debug, Clone)]
+pub struct CalendarEvent {
+    /// Stays the same across fetches so apps update the event instead of adding another
+    pub uid: String,
+    pub summary: String,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub status: EventStatus,
+}
+
+/// A VCALENDAR named `name`
+#[derive(Debug, Clone)]
+pub struct Calendar {
+    pub name: String,
+    pub events: Vec<CalendarEvent>,
+}
+
+impl Calendar {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            events: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, event: CalendarEvent) {
+        self.events.push(event);
+    }
+
+    /// The calendar as an .ics document, stamped `now`
+    pub fn to_ics(&self, now: DateTime<Utc>) -> String {
+        let mut out = String::new();
+        let stamp = timestamp(now);
+        write_line(&mut out, "BEGIN:VCALENDAR");
+        write_line(&mut out, "VERSION:2.0");
+        write_line(&mut out, &format!("PRODID:{}", PRODUCT_ID));
+        write_line(&mut out, "CALSCALE:GREGORIAN");
+        write_line(&mut out, "METHOD:PUBLISH");
+        write_line(
+            &mut out,
+            &format!("X-WR-CALNAME:{}", escape_text(&self.name)),
+        );
+        write_line(&mut out, &format!("NAME:{}", escape_text(&self.name)));
+        write_line(
+            &mut out,
+            &format!("REFRESH-INTERVAL;VALUE=DURATION:{}", REFRESH_INTERVAL),
+        );
+        write_line(&mut out, &format!("X-PUBLISHED-TTL:{}", REFRESH_INTERVAL));
+        for event in &self.events {
+            write_line(&mut out, "BEGIN:VEVENT");
+            write_line(&mut out, &format!("UID:{}", escape_text(&event.uid)));
+            write_line(&mut out, &format!("DTSTAMP:{}", stamp));
+            write_line(&mut out, &format!("DTSTART:{}", timestamp(event.starts_at)));
+            write_line(&mut out, &format!("DTEND:{}", timestamp(event.ends_at)));
+            write_line(
+                &mut out,
+                &format!("SUMMARY:{}", escape_text(&event.summary)),
+            );
+            if let Some(description) = &event.description {
+                write_line(
+                    &mut out,
+                    &format!("DESCRIPTION:{}", escape_text(description)),
+                );
+            }
+            if let Some(location) = &event.location {
+                write_line(&mut out, &format!("LOCATION:{}", escape_text(location)));
+            }
+            write_line(&mut out, &format!("STATUS:{}", event.status.as_str()));
+            write_line(&mut out, "END:VEVENT");
+        }
+        write_line(&mut out, "END:VCALENDAR");
+        out
+    }
+}
+
+/// UTC date-time in the basic format, e.g. `20261019T143000Z`
+fn timestamp(at: DateTime<Utc>) -> String {
+    at.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escapes a TEXT value: backslashes, commas, semicolons and line breaks
+pub fn escape_text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\r' => {
+                if chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+                escaped.push_str("\\n");
+            }
+            '\n' => escaped.push_str("\\n"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Appends a content line, folded so no physical line exceeds 75 octets. Continuation
+/// lines start with a space, and multi-byte characters are never split.
+fn write_line(out: &mut String, line: &str) {
+    let mut octets = 0;
+    for c in line.chars() {
+        if octets + c.len_utf8() > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            octets = 1;
+        }
+        out.push(c);
+        octets += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
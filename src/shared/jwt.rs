@@ -53,3 +53,235 @@ pub fn validate_jwt(token: &str) -> Result<Claims, jsonwebtoken::errors::Error>
 
     Ok(token_data.claims)
 }
+
+/// Audience of unsubscribe tokens, which keeps them apart from login tokens
+const UNSUBSCRIBE_AUDIENCE: &str = "unsubscribe";
+
+/// How long an unsubscribe link in an email keeps working
+const UNSUBSCRIBE_TOKEN_DAYS: i64 = 90;
+
+/// Claims of the token in an email's one-click unsubscribe link
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UnsubscribeClaims {
+    pub sub: String, // user id
+    pub aud: String,
+    pub exp: usize,
+    /// Notification type to stop emailing; `None` stops all notification emails
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notification_type: Option<String>,
+}
+
+impl UnsubscribeClaims {
+    pub fn user_id(&self) -> Option<Uuid> {
+        Uuid::parse_str(&self.sub).ok()
+    }
+}
+
+/// Sign an unsubscribe token for a user's emails of one type, or all of them
+pub fn generate_unsubscribe_token(
+    secret: &str,
+    user_id: Uuid,
+    notification_type: Option<&str>,
+) -> Result<String, jsonwebtoken::errors::Error> {
+    let expiration = chrono::Utc::now() + chrono::Duration::days(UNSUBSCRIBE_TOKEN_DAYS);
+    let claims = UnsubscribeClaims {
+        sub: user_id.to_string(),
+        aud: UNSUBSCRIBE_AUDIENCE.to_string(),
+        exp: expiration.timestamp() as usize,
+        notification_type: notification_type.map(str::to_string),
+    };
+
+    encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(secret.as_bytes()),
+    )
+}
+
+/// Validate and decode an unsubscribe token
+pub fn validate_unsubscribe_token(
+    secret: &str,
+    token: &str,
+) -> Result<UnsubscribeClaims, jsonwebtoken::errors::Error> {
+    let mut validation = Validation::default();
+    validation.set_audience(&[UNSUBSCRIBE_AUDIENCE]);
+    validation.set_required_spec_claims(&["exp", "aud"]);
+
+    let token_data = decode::<UnsubscribeClaims>(
+        token,
+        &DecodingKey::from_secret(secret.as_bytes()),
+        &validation,
+    )?;
+
+    Ok(token_data.claims)
+}
+
+/// Audience of the tokens in registration QR codes
+const CHECK_IN_AUDIENCE: &str = "check_in";
+
+/// Claims of the token a registration's QR code carries
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CheckInClaims {
+    pub sub: String, // registration id
+    pub aud: String,
+    pub exp: usize,
+}
+
+impl CheckInClaims {
+    pub fn registration_id(&self) -> Option<Uuid> {
+        Uuid::parse_str(&self.sub).ok()
+    }
+}
+
+/// Sign a check-in token for a registration, valid until `expires_at`
+pub fn generate_check_in_token(
+    secret: &str,
+    registration_id: Uuid,
+    expires_at: chrono::DateTime<chrono::Utc>,
+) -> Result<String, jsonwebtoken::errors::Error> {
+    let claims = CheckInClaims {
+        sub: registration_id.to_string(),
+        aud: CHECK_IN_AUDIENCE.to_string(),
+        exp: expires_at.timestamp() as usize,
+    };
+
+    encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(secret.as_bytes()),
+    )
+}
+
+/// Validate and decode a check-in token
+pub fn validate_check_in_token(
+    secret: &str,
+    token: &str,
+) -> Result<CheckInClaims, jsonwebtoken::errors::Error> {
+    let mut validation = Validation::default();
+    validation.set_audience(&[CHECK_IN_AUDIENCE]);
+    validation.set_required_spec_claims(&["exp", "aud"]);
+
+    let token_data = decode::<CheckInClaims>(
+        token,
+        &DecodingKey::from_secret(secret.as_bytes()),
+        &validation,
+    )?;
+
+    Ok(token_data.claims)
+}
+
+const TICKET_AUDIENCE: &str = "match_ticket";
+
+/// Claims of the token a spectator ticket's QR code carries
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TicketClaims {
+    pub sub: String, // ticket id
+    pub mid: String, // match id
+    pub aud: String,
+    pub exp: usize,
+}
+
+impl TicketClaims {
+    pub fn ticket_id(&self) -> Option<Uuid> {
+        Uuid::parse_str(&self.sub).ok()
+    }
+
+    pub fn match_id(&self) -> Option<Uuid> {
+        Uuid::parse_str(&self.mid).ok()
+    }
+}
+
+/// Sign a spectator ticket's token, valid until `expires_at`
+pub fn generate_ticket_token(
+    secret: &str,
+    ticket_id: Uuid,
+    match_id: Uuid,
+    expires_at: chrono::DateTime<chrono::Utc>,
+) -> Result<String, jsonwebtoken::errors::Error> {
+    let claims = TicketClaims {
+        sub: ticket_id.to_string(),
+        mid: match_id.to_string(),
+        aud: TICKET_AUDIENCE.to_string(),
+        exp: expires_at.timestamp() as usize,
+    };
+
+    encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(secret.as_bytes()),
+    )
+}
+
+/// Validate a spectator ticket's token
+pub fn validate_ticket_token(
+    secret: &str,
+    token: &str,
+) -> Result<TicketClaims, jsonwebtoken::errors::Error> {
+    let mut validation = Validation::default();
+    validation.set_audience(&[TICKET_AUDIENCE]);
+    validation.set_required_spec_claims(&["exp", "aud"]);
+
+    let token_data = decode::<TicketClaims>(
+        token,
+        &DecodingKey::from_secret(secret.as_bytes()),
+        &validation,
+    )?;
+
+    Ok(token_data.claims)
+}
+
+/// Audience of calendar feed tokens
+const CALENDAR_AUDIENCE: &str = "calendar";
+
+/// Claims of the token in a calendar feed's URL. Calendar apps keep polling the same URL,
+/// so these tokens do not expire; they stop working when `JWT_SECRET` changes.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CalendarClaims {
+    pub sub: String,  // id of the user, tournament, category or court
+    pub feed: String, // kind of feed
+    pub aud: String,
+}
+
+impl CalendarClaims {
+    pub fn subject_id(&self) -> Option<Uuid> {
+        Uuid::parse_str(&self.sub).ok()
+    }
+}
+
+/// Sign the token for a calendar feed
+pub fn generate_calendar_token(
+    secret: &str,
+    feed: &str,
+    subject_id: Uuid,
+) -> Result<String, jsonwebtoken::errors::Error> {
+    let claims = CalendarClaims {
+        sub: subject_id.to_string(),
+        feed: feed.to_string(),
+        aud: CALENDAR_AUDIENCE.to_string(),
+    };
+
+    encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(secret.as_bytes()),
+    )
+}
+
+/// Validate a calendar feed token
+pub fn validate_calendar_token(
+    secret: &str,
+    token: &str,
+) -> Result<CalendarClaims, jsonwebtoken::errors::Error> {
+    let mut validation = Validation::default();
+    validation.set_audience(&[CALENDAR_AUDIENCE]);
+    validation.set_required_spec_claims(&["aud"]);
+    validation.validate_exp = false;
+
+    let token_data = decode::<CalendarClaims>(
+        token,
+        &DecodingKey::from_secret(secret.as_bytes()),
+        &validation,
+    )?;
+
+    Ok(token_data.claims)
+}
@@ -2,10 +2,17 @@
 
 pub mod api_response;
 pub mod config;
+pub mod csv;
 pub mod errors;
 pub mod google;
+pub mod ical;
 pub mod jwt;
+pub mod pdf;
+pub mod qr;
+pub mod timezone;
 pub mod types;
+pub mod xlsx;
+pub mod zip;
 
 // Re-export commonly used items
 pub use api_response::ApiResponse;
@@ -0,0 +1,532 @@
+//! Small PDF 1.4 writer for printable tournament sheets.
+//!
+//! Only what the print endpoints need: the standard Helvetica fonts, text, lines and
+//! rectangles, plus a [`PdfBuilder`] that flows headings and tables across pages.
+//! Text is encoded as WinAnsi; characters outside Latin-1 are replaced with `?`.
+
+use crate::shared::csv::CsvTable;
+
+pub const A4_PORTRAIT: (f32, f32) = (595.0, 842.0);
+pub const A4_LANDSCAPE: (f32, f32) = (842.0, 595.0);
+
+const MARGIN: f32 = 40.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource(&self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+        }
+    }
+}
+
+/// A single page and its content stream
+pub struct PdfPage {
+    pub width: f32,
+    pub height: f32,
+    content: Vec<u8>,
+}
+
+impl PdfPage {
+    pub fn new(size: (f32, f32)) -> Self {
+        Self {
+            width: size.0,
+            height: size.1,
+            content: Vec::new(),
+        }
+    }
+
+    /// Draws text with its baseline at (`x`, `y`), measured from the bottom-left corner.
+    pub fn text(&mut self, x: f32, y: f32, size: f32, font: Font, text: &str) {
+        self.content.extend_from_slice(
+            format!("BT /{} {} Tf {:.2} {:.2} Td (", font.resource(), size, x, y).as_bytes(),
+        );
+        self.content.extend_from_slice(&encode_text(text));
+        self.content.extend_from_slice(b") Tj ET\n");
+    }
+
+    pub fn line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32) {
+        self.content.extend_from_slice(
+            format!("{:.2} {:.2} m {:.2} {:.2} l S\n", x1, y1, x2, y2).as_bytes(),
+        );
+    }
+
+    pub fn rect(&mut self, x: f32, y: f32, width: f32, height: f32) {
+        self.content.extend_from_slice(
+            format!("{:.2} {:.2} {:.2} {:.2} re S\n", x, y, width, height).as_bytes(),
+        );
+    }
+}
+
+/// A PDF document made of pages
+pub struct PdfDocument {
+    title: String,
+    pages: Vec<PdfPage>,
+}
+
+impl PdfDocument {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            pages: Vec::new(),
+        }
+    }
+
+    pub fn add_page(&mut self, page: PdfPage) {
+        self.pages.push(page);
+    }
+
+    /// Serializes the document. An empty document gets a single blank page.
+    pub fn finish(mut self) -> Vec<u8> {
+        if self.pages.is_empty() {
+            self.pages.push(PdfPage::new(A4_PORTRAIT));
+        }
+
+        // Object numbers: 1 catalog, 2 pages, 3-4 fonts, 5 info, then page/content pairs.
+        let page_ids: Vec<usize> = (0..self.pages.len()).map(|i| 6 + i * 2).collect();
+        let mut out: Vec<u8> = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        let mut offsets = Vec::new();
+
+        let mut write_obj = |out: &mut Vec<u8>, id: usize, body: &[u8]| {
+            offsets.push((id, out.len()));
+            out.extend_from_slice(format!("{} 0 obj\n", id).as_bytes());
+            out.extend_from_slice(body);
+            out.extend_from_slice(b"\nendobj\n");
+        };
+
+        write_obj(&mut out, 1, b"<< /Type /Catalog /Pages 2 0 R >>");
+        let kids = page_ids
+            .iter()
+            .map(|id| format!("{} 0 R", id))
+            .collect::<Vec<_>>()
+            .join(" ");
+        write_obj(
+            &mut out,
+            2,
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {} >>",
+                kids,
+                page_ids.len()
+            )
+            .as_bytes(),
+        );
+        write_obj(
+            &mut out,
+            3,
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>",
+        );
+        write_obj(
+            &mut out,
+            4,
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>",
+        );
+        let mut info = b"<< /Producer (Tournamint) /Title (".to_vec();
+        info.extend_from_slice(&encode_text(&self.title));
+        info.extend_from_slice(b") >>");
+        write_obj(&mut out, 5, &info);
+
+        for (page, &page_id) in self.pages.iter().zip(&page_ids) {
+            let content_id = page_id + 1;
+            write_obj(
+                &mut out,
+                page_id,
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                    page.width, page.height, content_id
+                )
+                .as_bytes(),
+            );
+            let mut stream = format!("<< /Length {} >>\nstream\n", page.content.len()).into_bytes();
+            stream.extend_from_slice(&page.content);
+            stream.extend_from_slice(b"\nendstream");
+            write_obj(&mut out, content_id, &stream);
+        }
+
+        offsets.sort_by_key(|(id, _)| *id);
+        let xref_offset = out.len();
+        let object_count = offsets.len() + 1;
+        out.extend_from_slice(
+            format!("xref\n0 {}\n0000000000 65535 f \n", object_count).as_bytes(),
+        );
+        for (_, offset) in &offsets {
+            out.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+        }
+        out.extend_from_slice(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R /Info 5 0 R >>\nstartxref\n{}\n%%EOF\n",
+                object_count, xref_offset
+            )
+            .as_bytes(),
+        );
+        out
+    }
+}
+
+/// Encodes text as a PDF literal string body in WinAnsi.
+fn encode_text(text: &str) -> Vec<u8> {
+    let mut out = Vec::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                out.push(b'\\');
+                out.push(c as u8);
+            }
+            '\n' | '\r' | '\t' => out.push(b' '),
+            c if (c as u32) < 0x20 => {}
+            c if (c as u32) < 0x7F || (0xA0..=0xFF).contains(&(c as u32)) => out.push(c as u8),
+            _ => out.push(b'?'),
+        }
+    }
+    out
+}
+
+/// Approximate Helvetica text width; good enough for truncation and centering.
+pub fn text_width(text: &str, size: f32) -> f32 {
+    text.chars().count() as f32 * size * 0.52
+}
+
+/// Shortens text with an ellipsis so it fits in `max_width`.
+pub fn fit_text(text: &str, size: f32, max_width: f32) -> String {
+    if text_width(text, size) <= max_width {
+        return text.to_string();
+    }
+    let max_chars = ((max_width / (size * 0.52)) as usize).saturating_sub(3);
+    let truncated: String = text.chars().take(max_chars).collect();
+    format!("{}...", truncated)
+}
+
+/// One match box in a bracket diagram
+#[derive(Debug, Clone)]
+pub struct BracketSlot {
+    /// 1-based round number
+    pub round: i32,
+    /// 0-based position within the round, top to bottom
+    pub position: i32,
+    pub top: String,
+    pub bottom: String,
+    /// 1 or 2 when a winner is known
+    pub winner: Option<i32>,
+}
+
+/// Flows content top-to-bottom, starting new pages as needed
+pub struct PdfBuilder {
+    doc: PdfDocument,
+    page: Option<PdfPage>,
+    size: (f32, f32),
+    cursor: f32,
+    footer: String,
+}
+
+impl PdfBuilder {
+    pub fn new(title: &str, size: (f32, f32)) -> Self {
+        Self {
+            doc: PdfDocument::new(title),
+            page: None,
+            size,
+            cursor: 0.0,
+            footer: title.to_string(),
+        }
+    }
+
+    fn content_width(&self) -> f32 {
+        self.size.0 - MARGIN * 2.0
+    }
+
+    pub fn new_page(&mut self) {
+        self.flush_page();
+        let mut page = PdfPage::new(self.size);
+        let page_number = self.doc.pages.len() + 1;
+        page.text(
+            MARGIN,
+            MARGIN / 2.0,
+            7.0,
+            Font::Regular,
+            &format!("{} - page {}", self.footer, page_number),
+        );
+        self.page = Some(page);
+        self.cursor = self.size.1 - MARGIN;
+    }
+
+    fn flush_page(&mut self) {
+        if let Some(page) = self.page.take() {
+            self.doc.add_page(page);
+        }
+    }
+
+    fn page(&mut self) -> &mut PdfPage {
+        if self.page.is_none() {
+            self.new_page();
+        }
+        self.page.as_mut().expect("page was just created")
+    }
+
+    /// Starts a new page when less than `height` points remain.
+    pub fn ensure_space(&mut self, height: f32) {
+        if self.page.is_none() || self.cursor - height < MARGIN {
+            self.new_page();
+        }
+    }
+
+    pub fn heading(&mut self, text: &str) {
+        self.ensure_space(30.0);
+        let y = self.cursor - 16.0;
+        let width = self.content_width();
+        self.page()
+            .text(MARGIN, y, 16.0, Font::Bold, &fit_text(text, 16.0, width));
+        self.cursor -= 26.0;
+    }
+
+    pub fn subheading(&mut self, text: &str) {
+        self.ensure_space(24.0);
+        let y = self.cursor - 12.0;
+        let width = self.content_width();
+        self.page()
+            .text(MARGIN, y, 12.0, Font::Bold, &fit_text(text, 12.0, width));
+        self.cursor -= 20.0;
+    }
+
+    pub fn text_line(&mut self, text: &str) {
+        self.ensure_space(14.0);
+        let y = self.cursor - 10.0;
+        let width = self.content_width();
+        self.page()
+            .text(MARGIN, y, 10.0, Font::Regular, &fit_text(text, 10.0, width));
+        self.cursor -= 14.0;
+    }
+
+    pub fn spacer(&mut self, height: f32) {
+        self.cursor -= height;
+    }
+
+    /// Draws a ruled table, repeating the header row on each new page. Column widths
+    /// are proportional to the longest value in each column.
+    pub fn table(&mut self, table: &CsvTable) {
+        const ROW: f32 = 16.0;
+        const SIZE: f32 = 8.0;
+
+        if table.headers.is_empty() {
+            return;
+        }
+        let widths = self.column_widths(table, SIZE);
+
+        self.ensure_space(ROW * 2.0);
+        self.table_row(&table.headers, &widths, Font::Bold, SIZE, ROW);
+        for row in &table.rows {
+            if self.cursor - ROW < MARGIN {
+                self.new_page();
+                self.table_row(&table.headers, &widths, Font::Bold, SIZE, ROW);
+            }
+            self.table_row(row, &widths, Font::Regular, SIZE, ROW);
+        }
+        self.cursor -= 8.0;
+    }
+
+    fn column_widths(&self, table: &CsvTable, size: f32) -> Vec<f32> {
+        let natural: Vec<f32> = (0..table.headers.len())
+            .map(|i| {
+                let longest = table
+                    .rows
+                    .iter()
+                    .map(|r| text_width(&r[i], size))
+                    .fold(text_width(&table.headers[i], size), f32::max);
+                longest.clamp(24.0, 220.0) + 8.0
+            })
+            .collect();
+        let total: f32 = natural.iter().sum();
+        let scale = (self.content_width() / total).min(1.5);
+        natural.iter().map(|w| w * scale).collect()
+    }
+
+    fn table_row(&mut self, cells: &[String], widths: &[f32], font: Font, size: f32, height: f32) {
+        let top = self.cursor;
+        let page = self.page();
+        let mut x = MARGIN;
+        for (cell, width) in cells.iter().zip(widths) {
+            page.rect(x, top - height, *width, height);
+            page.text(
+                x + 3.0,
+                top - height + 5.0,
+                size,
+                font,
+                &fit_text(cell, size, width - 6.0),
+            );
+            x += width;
+        }
+        self.cursor -= height;
+    }
+
+    /// Draws a knockout bracket on its own page, one column per round.
+    pub fn bracket(&mut self, title: &str, slots: &[BracketSlot]) {
+        self.new_page();
+        self.heading(title);
+
+        let rounds = slots.iter().map(|s| s.round).max().unwrap_or(0).max(1);
+        let top = self.cursor - 10.0;
+        let bottom = MARGIN + 10.0;
+        let column = self.content_width() / rounds as f32;
+        let box_width = (column - 20.0).max(40.0);
+
+        // Slots per round halve each round; honour explicit positions beyond that.
+        let first_round = slots
+            .iter()
+            .filter(|s| s.round == 1)
+            .map(|s| s.position + 1)
+            .max()
+            .unwrap_or(1)
+            .max(1);
+        let slots_in = |round: i32| -> f32 {
+            let halved = (first_round as f32 / 2f32.powi(round - 1)).ceil();
+            let explicit = slots
+                .iter()
+                .filter(|s| s.round == round)
+                .map(|s| s.position + 1)
+                .max()
+                .unwrap_or(0) as f32;
+            halved.max(explicit).max(1.0)
+        };
+
+        for round in 1..=rounds {
+            let count = slots_in(round);
+            let next_count = slots_in(round + 1);
+            let spacing = (top - bottom) / count;
+            let next_spacing = (top - bottom) / next_count;
+            let box_height = (spacing * 0.8).clamp(14.0, 34.0);
+            let size = (box_height / 2.0 - 4.0).clamp(5.0, 9.0);
+            let x = MARGIN + (round - 1) as f32 * column;
+
+            let page = self.page();
+            page.text(x, top + 2.0, 9.0, Font::Bold, &format!("Round {}", round));
+            for slot in slots.iter().filter(|s| s.round == round) {
+                let center = top - spacing * (slot.position as f32 + 0.5);
+                let y = center - box_height / 2.0;
+                page.rect(x, y, box_width, box_height);
+                page.line(x, center, x + box_width, center);
+
+                let top_font = if slot.winner == Some(1) {
+                    Font::Bold
+                } else {
+                    Font::Regular
+                };
+                let bottom_font = if slot.winner == Some(2) {
+                    Font::Bold
+                } else {
+                    Font::Regular
+                };
+                page.text(
+                    x + 3.0,
+                    center + 3.0,
+                    size,
+                    top_font,
+                    &fit_text(&slot.top, size, box_width - 6.0),
+                );
+                page.text(
+                    x + 3.0,
+                    y + 3.0,
+                    size,
+                    bottom_font,
+                    &fit_text(&slot.bottom, size, box_width - 6.0),
+                );
+
+                if round < rounds {
+                    let next_center = top - next_spacing * ((slot.position / 2) as f32 + 0.5);
+                    let mid_x = x + box_width + 10.0;
+                    page.line(x + box_width, center, mid_x, center);
+                    page.line(mid_x, center, mid_x, next_center);
+                    page.line(mid_x, next_center, x + column, next_center);
+                }
+            }
+        }
+        self.cursor = bottom;
+    }
+
+    /// Draws a score grid with one row per participant and one column per set.
+    pub fn score_grid(
+        &mut self,
+        participants: [&str; 2],
+        sets: &[(String, String)],
+        totals: [&str; 2],
+    ) {
+        const ROW: f32 = 28.0;
+        let name_width = 180.0;
+        let set_count = sets.len().max(1);
+        let cell = ((self.content_width() - name_width) / (set_count + 1) as f32).min(60.0);
+
+        self.ensure_space(ROW * 3.0 + 10.0);
+        let top = self.cursor;
+        let page = self.page();
+
+        page.rect(MARGIN, top - ROW, name_width, ROW);
+        page.text(
+            MARGIN + 4.0,
+            top - ROW + 10.0,
+            9.0,
+            Font::Bold,
+            "Participant",
+        );
+        for i in 0..set_count {
+            let x = MARGIN + name_width + i as f32 * cell;
+            page.rect(x, top - ROW, cell, ROW);
+            page.text(
+                x + 4.0,
+                top - ROW + 10.0,
+                9.0,
+                Font::Bold,
+                &format!("Set {}", i + 1),
+            );
+        }
+        let total_x = MARGIN + name_width + set_count as f32 * cell;
+        page.rect(total_x, top - ROW, cell, ROW);
+        page.text(total_x + 4.0, top - ROW + 10.0, 9.0, Font::Bold, "Sets");
+
+        for (row, name) in participants.iter().enumerate() {
+            let y = top - ROW * (row as f32 + 2.0);
+            page.rect(MARGIN, y, name_width, ROW);
+            page.text(
+                MARGIN + 4.0,
+                y + 10.0,
+                10.0,
+                Font::Regular,
+                &fit_text(name, 10.0, name_width - 8.0),
+            );
+            for i in 0..set_count {
+                let x = MARGIN + name_width + i as f32 * cell;
+                page.rect(x, y, cell, ROW);
+                if let Some(scores) = sets.get(i) {
+                    let value = if row == 0 { &scores.0 } else { &scores.1 };
+                    page.text(x + 6.0, y + 9.0, 12.0, Font::Regular, value);
+                }
+            }
+            page.rect(total_x, y, cell, ROW);
+            page.text(total_x + 6.0, y + 9.0, 12.0, Font::Bold, totals[row]);
+        }
+        self.cursor = top - ROW * 3.0 - 10.0;
+    }
+
+    /// Draws labelled signature lines side by side.
+    pub fn signature_lines(&mut self, labels: &[&str]) {
+        if labels.is_empty() {
+            return;
+        }
+        self.ensure_space(50.0);
+        let width = self.content_width() / labels.len() as f32;
+        let y = self.cursor - 30.0;
+        let page = self.page();
+        for (i, label) in labels.iter().enumerate() {
+            let x = MARGIN + i as f32 * width;
+            page.line(x, y, x + width - 20.0, y);
+            page.text(x, y - 10.0, 8.0, Font::Regular, label);
+        }
+        self.cursor -= 50.0;
+    }
+
+    pub fn finish(mut self) -> Vec<u8> {
+        self.flush_page();
+        self.doc.finish()
+    }
+}
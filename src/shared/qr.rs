@@ -0,0 +1,526 @@
+//! Small QR code encoder for the credentials desk staff scan, rendered as PNG or SVG.
+//!
+//! Only what signed tokens need: byte mode at error correction level M, versions 1 to 20
+//! (up to 666 bytes). The mask is chosen by the standard penalty rules so codes stay easy
+//! to scan from a phone screen.
+
+use std::io::Write;
+
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use serde::Deserialize;
+
+use crate::shared::zip::crc32;
+use crate::shared::AppError;
+
+const MAX_VERSION: usize = 20;
+
+/// Modules of light border around the symbol, as the standard requires
+const QUIET_ZONE: usize = 4;
+
+/// Error correction at level M per version: (codewords per block, short blocks and their
+/// data codewords, long blocks and theirs)
+const BLOCKS_M: [(usize, usize, usize, usize, usize); MAX_VERSION] = [
+    (10, 1, 16, 0, 0),
+    (16, 1, 28, 0, 0),
+    (26, 1, 44, 0, 0),
+    (18, 2, 32, 0, 0),
+    (24, 2, 43, 0, 0),
+    (16, 4, 27, 0, 0),
+    (18, 4, 31, 0, 0),
+    (22, 2, 38, 2, 39),
+    (22, 3, 36, 2, 37),
+    (26, 4, 43, 1, 44),
+    (30, 1, 50, 4, 51),
+    (22, 6, 36, 2, 37),
+    (22, 8, 37, 1, 38),
+    (24, 4, 40, 5, 41),
+    (24, 5, 41, 5, 42),
+    (28, 7, 45, 3, 46),
+    (28, 10, 46, 1, 47),
+    (26, 9, 43, 4, 44),
+    (26, 3, 44, 11, 45),
+    (26, 3, 41, 13, 42),
+];
+
+/// Image format of a rendered code
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QrFormat {
+    #[default]
+    Png,
+    Svg,
+}
+
+impl QrFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            QrFormat::Png => "image/png",
+            QrFormat::Svg => "image/svg+xml",
+        }
+    }
+}
+
+/// An encoded QR symbol
+#[derive(Debug, Clone)]
+pub struct QrCode {
+    version: usize,
+    size: usize,
+    modules: Vec<bool>,
+    function: Vec<bool>,
+}
+
+impl QrCode {
+    /// Encodes `data` in the smallest version that holds it
+    pub fn encode(data: &[u8]) -> Result<Self, AppError> {
+        let version = (1..=MAX_VERSION)
+            .find(|&v| data.len() <= capacity(v))
+            .ok_or_else(|| AppError::BadRequest("Too much data for a QR code".into()))?;
+        let codewords = add_error_correction(version, &data_codewords(version, data));
+
+        let mut qr = QrCode::blank(version);
+        qr.draw_function_patterns();
+        qr.draw_codewords(&codewords);
+
+        let mut best: Option<(u32, u8)> = None;
+        for mask in 0..8 {
+            qr.apply_mask(mask);
+            qr.draw_format_bits(mask);
+            let penalty = qr.penalty();
+            if best.is_none_or(|(lowest, _)| penalty < lowest) {
+                best = Some((penalty, mask));
+            }
+            qr.apply_mask(mask);
+        }
+        let mask = best.map(|(_, mask)| mask).unwrap_or_default();
+        qr.apply_mask(mask);
+        qr.draw_format_bits(mask);
+        Ok(qr)
+    }
+
+    /// Whether the module at column `x`, row `y` is dark
+    pub fn is_dark(&self, x: usize, y: usize) -> bool {
+        x < self.size && y < self.size && self.modules[y * self.size + x]
+    }
+
+    pub fn render(&self, format: QrFormat, scale: usize) -> Vec<u8> {
+        match format {
+            QrFormat::Png => self.to_png(scale),
+            QrFormat::Svg => self.to_svg().into_bytes(),
+        }
+    }
+
+    /// Scalable image, one unit per module, drawn as a single path
+    pub fn to_svg(&self) -> String {
+        let side = self.size + QUIET_ZONE * 2;
+        let mut path = String::new();
+        for y in 0..self.size {
+            for x in 0..self.size {
+                if self.is_dark(x, y) {
+                    path.push_str(&format!("M{},{}h1v1h-1z", x + QUIET_ZONE, y + QUIET_ZONE));
+                }
+            }
+        }
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {side} {side}\" \
+             shape-rendering=\"crispEdges\"><rect width=\"{side}\" height=\"{side}\" \
+             fill=\"#fff\"/><path d=\"{path}\" fill=\"#000\"/></svg>"
+        )
+    }
+
+    /// 8-bit grayscale PNG with `scale` pixels per module
+    pub fn to_png(&self, scale: usize) -> Vec<u8> {
+        let scale = scale.max(1);
+        let side = (self.size + QUIET_ZONE * 2) * scale;
+        let mut raw = Vec::with_capacity((side + 1) * side);
+        for row in 0..side {
+            raw.push(0); // filter: none
+            let y = (row / scale).wrapping_sub(QUIET_ZONE);
+            for col in 0..side {
+                let x = (col / scale).wrapping_sub(QUIET_ZONE);
+                raw.push(if self.is_dark(x, y) { 0 } else { 255 });
+            }
+        }
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        let _ = encoder.write_all(&raw);
+        let pixels = encoder.finish().unwrap_or_default();
+
+        let mut header = Vec::with_capacity(13);
+        header.extend_from_slice(&(side as u32).to_be_bytes());
+        header.extend_from_slice(&(side as u32).to_be_bytes());
+        header.extend_from_slice(&[8, 0, 0, 0, 0]); // 8-bit grayscale, no interlace
+
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        png_chunk(&mut png, b"IHDR", &header);
+        png_chunk(&mut png, b"IDAT", &pixels);
+        png_chunk(&mut png, b"IEND", &[]);
+        png
+    }
+
+    // ==================== Layout ====================
+
+    fn blank(version: usize) -> Self {
+        let size = version * 4 + 17;
+        Self {
+            version,
+            size,
+            modules: vec![false; size * size],
+            function: vec![false; size * size],
+        }
+    }
+
+    fn set_function(&mut self, x: usize, y: usize, dark: bool) {
+        let index = y * self.size + x;
+        self.modules[index] = dark;
+        self.function[index] = true;
+    }
+
+    fn draw_function_patterns(&mut self) {
+        let size = self.size;
+        for i in 0..size {
+            self.set_function(6, i, i % 2 == 0);
+            self.set_function(i, 6, i % 2 == 0);
+        }
+        for (x, y) in [(3, 3), (size - 4, 3), (3, size - 4)] {
+            self.draw_finder(x, y);
+        }
+        let positions = alignment_positions(self.version);
+        let last = positions.len().saturating_sub(1);
+        for (i, &x) in positions.iter().enumerate() {
+            for (j, &y) in positions.iter().enumerate() {
+                let corner = (i == 0 && (j == 0 || j == last)) || (i == last && j == 0);
+                if !corner {
+                    self.draw_alignment(x, y);
+                }
+            }
+        }
+        // Reserve the format areas; the real bits are drawn once the mask is known
+        self.draw_format_bits(0);
+        self.draw_version_bits();
+    }
+
+    fn draw_finder(&mut self, cx: usize, cy: usize) {
+        for dy in -4i32..=4 {
+            for dx in -4i32..=4 {
+                let (x, y) = (cx as i32 + dx, cy as i32 + dy);
+                if (0..self.size as i32).contains(&x) && (0..self.size as i32).contains(&y) {
+                    let distance = dx.abs().max(dy.abs());
+                    self.set_function(x as usize, y as usize, distance != 2 && distance != 4);
+                }
+            }
+        }
+    }
+
+    fn draw_alignment(&mut self, cx: usize, cy: usize) {
+        for dy in -2i32..=2 {
+            for dx in -2i32..=2 {
+                let distance = dx.abs().max(dy.abs());
+                let (x, y) = ((cx as i32 + dx) as usize, (cy as i32 + dy) as usize);
+                self.set_function(x, y, distance != 1);
+            }
+        }
+    }
+
+    /// Error correction level M and the mask, BCH-protected, in both copies
+    fn draw_format_bits(&mut self, mask: u8) {
+        let data = mask as u32; // level M is 00
+        let mut remainder = data;
+        for _ in 0..10 {
+            remainder = (remainder << 1) ^ ((remainder >> 9) * 0x537);
+        }
+        let bits = ((data << 10) | remainder) ^ 0x5412;
+        let bit = |i: usize| (bits >> i) & 1 == 1;
+        let size = self.size;
+
+        for i in 0..=5 {
+            self.set_function(8, i, bit(i));
+        }
+        self.set_function(8, 7, bit(6));
+        self.set_function(8, 8, bit(7));
+        self.set_function(7, 8, bit(8));
+        for i in 9..15 {
+            self.set_function(14 - i, 8, bit(i));
+        }
+        for i in 0..8 {
+            self.set_function(size - 1 - i, 8, bit(i));
+        }
+        for i in 8..15 {
+            self.set_function(8, size - 15 + i, bit(i));
+        }
+        self.set_function(8, size - 8, true);
+    }
+
+    fn draw_version_bits(&mut self) {
+        if self.version < 7 {
+            return;
+        }
+        let mut remainder = self.version as u32;
+        for _ in 0..12 {
+            remainder = (remainder << 1) ^ ((remainder >> 11) * 0x1F25);
+        }
+        let bits = ((self.version as u32) << 12) | remainder;
+        for i in 0..18 {
+            let dark = (bits >> i) & 1 == 1;
+            let (a, b) = (self.size - 11 + i % 3, i / 3);
+            self.set_function(a, b, dark);
+            self.set_function(b, a, dark);
+        }
+    }
+
+    /// Places the codewords in the zigzag column pairs, right to left
+    fn draw_codewords(&mut self, codewords: &[u8]) {
+        let size = self.size;
+        let total_bits = codewords.len() * 8;
+        let mut bit = 0;
+        let mut right = size - 1;
+        loop {
+            if right == 6 {
+                right = 5;
+            }
+            let upward = (right + 1) & 2 == 0;
+            for vertical in 0..size {
+                let y = if upward {
+                    size - 1 - vertical
+                } else {
+                    vertical
+                };
+                for x in [right, right - 1] {
+                    let index = y * size + x;
+                    if !self.function[index] && bit < total_bits {
+                        self.modules[index] = (codewords[bit / 8] >> (7 - bit % 8)) & 1 == 1;
+                        bit += 1;
+                    }
+                }
+            }
+            if right < 2 {
+                break;
+            }
+            right -= 2;
+        }
+    }
+
+    /// XORs the mask pattern over the data modules; applying it twice undoes it
+    fn apply_mask(&mut self, mask: u8) {
+        for y in 0..self.size {
+            for x in 0..self.size {
+                let index = y * self.size + x;
+                if self.function[index] {
+                    continue;
+                }
+                let flip = match mask {
+                    0 => (x + y) % 2 == 0,
+                    1 => y % 2 == 0,
+                    2 => x % 3 == 0,
+                    3 => (x + y) % 3 == 0,
+                    4 => (x / 3 + y / 2) % 2 == 0,
+                    5 => x * y % 2 + x * y % 3 == 0,
+                    6 => (x * y % 2 + x * y % 3) % 2 == 0,
+                    _ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+                };
+                self.modules[index] ^= flip;
+            }
+        }
+    }
+
+    // ==================== Mask penalty ====================
+
+    fn penalty(&self) -> u32 {
+        let size = self.size;
+        let mut penalty = 0;
+        for line in 0..size {
+            let row: Vec<bool> = (0..size).map(|x| self.is_dark(x, line)).collect();
+            let column: Vec<bool> = (0..size).map(|y| self.is_dark(line, y)).collect();
+            penalty += line_penalty(&row) + line_penalty(&column);
+        }
+        for y in 0..size - 1 {
+            for x in 0..size - 1 {
+                let dark = self.is_dark(x, y);
+                if dark == self.is_dark(x + 1, y)
+                    && dark == self.is_dark(x, y + 1)
+                    && dark == self.is_dark(x + 1, y + 1)
+                {
+                    penalty += 3;
+                }
+            }
+        }
+        let dark = self.modules.iter().filter(|&&m| m).count();
+        let total = self.modules.len();
+        // Steps of 5% away from an even balance
+        let deviation = (dark * 20).abs_diff(total * 10);
+        penalty += (deviation.div_ceil(total).saturating_sub(1) * 10) as u32;
+        penalty
+    }
+}
+
+/// Runs of five or more modules of one colour, and finder-like 1:1:3:1:1 patterns
+fn line_penalty(line: &[bool]) -> u32 {
+    let mut penalty = 0;
+    let mut run = 1;
+    for i in 1..=line.len() {
+        if i < line.len() && line[i] == line[i - 1] {
+            run += 1;
+            continue;
+        }
+        if run >= 5 {
+            penalty += 3 + (run - 5) as u32;
+        }
+        run = 1;
+    }
+    const FINDER: [bool; 7] = [true, false, true, true, true, false, true];
+    for start in 0..line.len().saturating_sub(6) {
+        if line[start..start + 7] != FINDER {
+            continue;
+        }
+        // Past either end is the quiet zone, which is light
+        let light = |from: usize, to: usize| (from..to).all(|i| line.get(i) != Some(&true));
+        if light(start.saturating_sub(4), start) || light(start + 7, start + 11) {
+            penalty += 40;
+        }
+    }
+    penalty
+}
+
+// ==================== Codewords ====================
+
+/// Data codewords of version `version`
+fn data_capacity(version: usize) -> usize {
+    let (_, short, short_len, long, long_len) = BLOCKS_M[version - 1];
+    short * short_len + long * long_len
+}
+
+/// Bytes version `version` holds in byte mode
+fn capacity(version: usize) -> usize {
+    let count_bits = if version < 10 { 8 } else { 16 };
+    (data_capacity(version) * 8 - 4 - count_bits) / 8
+}
+
+/// Mode indicator, length, data, terminator and padding
+fn data_codewords(version: usize, data: &[u8]) -> Vec<u8> {
+    let mut bits = BitBuffer::default();
+    bits.push(0b0100, 4);
+    bits.push(data.len() as u32, if version < 10 { 8 } else { 16 });
+    for &byte in data {
+        bits.push(byte as u32, 8);
+    }
+    let capacity_bits = data_capacity(version) * 8;
+    let terminator = (capacity_bits - bits.len).min(4);
+    bits.push(0, terminator);
+    let padding = (8 - bits.len % 8) % 8;
+    bits.push(0, padding);
+    let mut codewords = bits.bytes;
+    for pad in [0xEC, 0x11].into_iter().cycle() {
+        if codewords.len() >= data_capacity(version) {
+            break;
+        }
+        codewords.push(pad);
+    }
+    codewords
+}
+
+/// Splits the data into blocks, appends each block's error correction, and interleaves
+fn add_error_correction(version: usize, data: &[u8]) -> Vec<u8> {
+    let (ec_len, short, short_len, long, long_len) = BLOCKS_M[version - 1];
+    let divisor = rs_divisor(ec_len);
+    let mut blocks = Vec::with_capacity(short + long);
+    let mut offset = 0;
+    for len in std::iter::repeat_n(short_len, short).chain(std::iter::repeat_n(long_len, long)) {
+        let block = &data[offset..offset + len];
+        blocks.push((block, rs_remainder(block, &divisor)));
+        offset += len;
+    }
+    let mut result = Vec::with_capacity(data.len() + ec_len * blocks.len());
+    for i in 0..short_len.max(long_len) {
+        result.extend(blocks.iter().filter_map(|(block, _)| block.get(i)));
+    }
+    for i in 0..ec_len {
+        result.extend(blocks.iter().map(|(_, ec)| ec[i]));
+    }
+    result
+}
+
+#[derive(Default)]
+struct BitBuffer {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl BitBuffer {
+    fn push(&mut self, value: u32, count: usize) {
+        for i in (0..count).rev() {
+            if self.len.is_multiple_of(8) {
+                self.bytes.push(0);
+            }
+            if (value >> i) & 1 == 1 {
+                let last = self.bytes.len() - 1;
+                self.bytes[last] |= 0x80 >> (self.len % 8);
+            }
+            self.len += 1;
+        }
+    }
+}
+
+// ==================== Reed-Solomon ====================
+
+/// Product in GF(2^8) modulo x^8 + x^4 + x^3 + x^2 + 1
+fn gf_multiply(x: u8, y: u8) -> u8 {
+    let mut z: u8 = 0;
+    for i in (0..8).rev() {
+        z = (z << 1) ^ ((z >> 7) * 0x1D);
+        z ^= ((y >> i) & 1) * x;
+    }
+    z
+}
+
+/// Generator polynomial of the given degree, highest coefficient first and the leading 1
+/// left out
+fn rs_divisor(degree: usize) -> Vec<u8> {
+    let mut result = vec![0u8; degree];
+    result[degree - 1] = 1;
+    let mut root: u8 = 1;
+    for _ in 0..degree {
+        for j in 0..degree {
+            result[j] = gf_multiply(result[j], root);
+            if j + 1 < degree {
+                result[j] ^= result[j + 1];
+            }
+        }
+        root = gf_multiply(root, 0x02);
+    }
+    result
+}
+
+fn rs_remainder(data: &[u8], divisor: &[u8]) -> Vec<u8> {
+    let mut result = vec![0u8; divisor.len()];
+    for &byte in data {
+        let factor = byte ^ result[0];
+        result.remove(0);
+        result.push(0);
+        for (r, &d) in result.iter_mut().zip(divisor) {
+            *r ^= gf_multiply(d, factor);
+        }
+    }
+    result
+}
+
+/// Centre coordinates of the alignment patterns, in both directions
+fn alignment_positions(version: usize) -> Vec<usize> {
+    if version == 1 {
+        return Vec::new();
+    }
+    let count = version / 7 + 2;
+    let size = version * 4 + 17;
+    let step = (version * 4 + count * 2 + 1) / (count * 2 - 2) * 2;
+    let mut positions: Vec<usize> = (0..count - 1).map(|i| size - 7 - i * step).collect();
+    positions.push(6);
+    positions.reverse();
+    positions
+}
+
+fn png_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
@@ -0,0 +1,101 @@
+//! IANA timezone helpers.
+//!
+//! Everything is stored in UTC. These turn zone names from requests into `Tz`, read dates
+//! given without an offset as local time in a zone, and render instants back in it.
+
+use chrono::{DateTime, Duration, LocalResult, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+use crate::shared::AppError;
+
+/// Zone used when a tournament or caller has none
+pub const DEFAULT_TIMEZONE: &str = "UTC";
+
+/// Longest stretch of local time a DST transition skips; Lord Howe's is 30 minutes, the
+/// common case an hour
+const MAX_GAP_MINUTES: i64 = 120;
+
+/// Parses an IANA zone name such as `Europe/Madrid`
+pub fn parse_timezone(name: &str) -> Result<Tz, AppError> {
+    name.trim()
+        .parse::<Tz>()
+        .map_err(|_| AppError::ValidationError(format!("Unknown timezone '{}'", name)))
+}
+
+/// A zone name read back from storage. Names are validated when saved, so the UTC
+/// fallback only covers rows written before that.
+pub fn stored_timezone(name: &str) -> Tz {
+    name.parse().unwrap_or(Tz::UTC)
+}
+
+/// Which instant a bare date stands for in a range filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DayBound {
+    /// Local midnight at the start of the day
+    Start,
+    /// The last instant before the next local midnight
+    End,
+}
+
+/// The instant a local wall-clock time in `tz` refers to. A time repeated when clocks go
+/// back resolves to its first occurrence; one skipped when they go forward resolves to
+/// the first valid time after the gap.
+pub fn local_to_utc(naive: NaiveDateTime, tz: Tz) -> DateTime<Utc> {
+    for minutes in 0..=MAX_GAP_MINUTES {
+        match tz.from_local_datetime(&(naive + Duration::minutes(minutes))) {
+            LocalResult::Single(at) | LocalResult::Ambiguous(at, _) => {
+                return at.with_timezone(&Utc)
+            }
+            LocalResult::None => continue,
+        }
+    }
+    // Unreachable for real zones; fall back to reading the time as UTC
+    Utc.from_utc_datetime(&naive)
+}
+
+/// The UTC range `[start, end)` covering the local calendar day `date` in `tz`. Days with
+/// a DST transition are 23 or 25 hours long.
+pub fn local_day(date: NaiveDate, tz: Tz) -> (DateTime<Utc>, DateTime<Utc>) {
+    let start = local_to_utc(date.and_time(Default::default()), tz);
+    let end = match date.succ_opt() {
+        Some(next) => local_to_utc(next.and_time(Default::default()), tz),
+        None => DateTime::<Utc>::MAX_UTC,
+    };
+    (start, end)
+}
+
+/// Parses a date filter. RFC 3339 timestamps name an exact instant and ignore `tz`;
+/// `YYYY-MM-DDTHH:MM[:SS]` is local time in `tz`, and a bare `YYYY-MM-DD` is the start
+/// or end of that local day depending on `bound`.
+pub fn parse_date_filter(value: &str, tz: Tz, bound: DayBound) -> Result<DateTime<Utc>, AppError> {
+    let value = value.trim();
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Ok(at.with_timezone(&Utc));
+    }
+    for format in [
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+    ] {
+        if let Ok(naive) = NaiveDateTime::parse_from_str(value, format) {
+            return Ok(local_to_utc(naive, tz));
+        }
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        let (start, end) = local_day(date, tz);
+        return Ok(match bound {
+            DayBound::Start => start,
+            DayBound::End => end - Duration::microseconds(1),
+        });
+    }
+    Err(AppError::ValidationError(format!(
+        "Invalid date '{}'; expected RFC 3339, YYYY-MM-DDTHH:MM or YYYY-MM-DD",
+        value
+    )))
+}
+
+/// `at` as RFC 3339 with the offset `tz` has at that instant
+pub fn to_local_rfc3339(at: DateTime<Utc>, tz: Tz) -> String {
+    at.with_timezone(&tz).to_rfc3339()
+}
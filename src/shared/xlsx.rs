@@ -0,0 +1,338 @@
+//! Minimal XLSX reader: extracts the first worksheet of a workbook as rows of strings.
+//!
+//! Only what an entrant sheet needs is supported - shared strings, inline strings,
+//! numbers and booleans. Formulas yield their cached value; styling is ignored.
+
+use crate::shared::zip::ZipReader;
+use crate::shared::AppError;
+
+/// Returns `true` when the bytes look like a ZIP container (XLSX, ODS, ...).
+pub fn is_zip(bytes: &[u8]) -> bool {
+    bytes.starts_with(&[0x50, 0x4b, 0x03, 0x04])
+}
+
+/// Reads the first worksheet. Empty trailing cells are trimmed; gaps inside a row are
+/// filled with empty strings so cell positions line up with the header row.
+pub fn read_first_sheet(bytes: &[u8]) -> Result<Vec<Vec<String>>, AppError> {
+    let archive = ZipReader::new(bytes)?;
+    let shared = match archive.read("xl/sharedStrings.xml")? {
+        Some(xml) => parse_shared_strings(&String::from_utf8_lossy(&xml)),
+        None => Vec::new(),
+    };
+
+    let sheet_path = first_sheet_path(&archive)?;
+    let sheet = archive
+        .read(&sheet_path)?
+        .ok_or_else(|| AppError::BadRequest("Workbook has no worksheets".into()))?;
+
+    parse_sheet(&String::from_utf8_lossy(&sheet), &shared)
+}
+
+/// Resolves the first `<sheet>` in workbook.xml through the workbook relationships,
+/// falling back to the conventional `sheet1.xml`.
+fn first_sheet_path(archive: &ZipReader) -> Result<String, AppError> {
+    let fallback = "xl/worksheets/sheet1.xml".to_string();
+    let (Some(workbook), Some(rels)) = (
+        archive.read("xl/workbook.xml")?,
+        archive.read("xl/_rels/workbook.xml.rels")?,
+    ) else {
+        return Ok(fallback);
+    };
+
+    let workbook = String::from_utf8_lossy(&workbook);
+    let rel_id = XmlScanner::new(&workbook).find_map(|ev| match ev {
+        XmlEvent::Start {
+            name: "sheet",
+            attrs,
+            ..
+        } => attr(attrs, "id"),
+        _ => None,
+    });
+    let Some(rel_id) = rel_id else {
+        return Ok(fallback);
+    };
+
+    let rels = String::from_utf8_lossy(&rels);
+    let target = XmlScanner::new(&rels).find_map(|ev| match ev {
+        XmlEvent::Start {
+            name: "Relationship",
+            attrs,
+            ..
+        } if attr(attrs, "Id").as_deref() == Some(rel_id.as_str()) => attr(attrs, "Target"),
+        _ => None,
+    });
+
+    Ok(match target {
+        Some(t) if t.starts_with('/') => t.trim_start_matches('/').to_string(),
+        Some(t) => format!("xl/{}", t),
+        None => fallback,
+    })
+}
+
+fn parse_shared_strings(xml: &str) -> Vec<String> {
+    let mut strings = Vec::new();
+    let mut current: Option<String> = None;
+    let mut in_text = false;
+    let mut in_phonetic = false;
+
+    for ev in XmlScanner::new(xml) {
+        match ev {
+            XmlEvent::Start { name: "si", .. } => current = Some(String::new()),
+            XmlEvent::End("si") => strings.push(current.take().unwrap_or_default()),
+            XmlEvent::Start { name: "rPh", .. } => in_phonetic = true,
+            XmlEvent::End("rPh") => in_phonetic = false,
+            XmlEvent::Start {
+                name: "t",
+                self_closing: false,
+                ..
+            } => in_text = !in_phonetic,
+            XmlEvent::End("t") => in_text = false,
+            XmlEvent::Text(text) if in_text => {
+                if let Some(s) = current.as_mut() {
+                    s.push_str(&unescape(text));
+                }
+            }
+            _ => {}
+        }
+    }
+    strings
+}
+
+fn parse_sheet(xml: &str, shared: &[String]) -> Result<Vec<Vec<String>>, AppError> {
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut row: Vec<String> = Vec::new();
+    let mut cell_col = 0usize;
+    let mut cell_type = String::new();
+    let mut value = String::new();
+    let mut capture = false;
+
+    for ev in XmlScanner::new(xml) {
+        match ev {
+            XmlEvent::Start { name: "row", .. } => row.clear(),
+            XmlEvent::End("row") => {
+                while row.last().is_some_and(|c| c.is_empty()) {
+                    row.pop();
+                }
+                rows.push(std::mem::take(&mut row));
+            }
+            XmlEvent::Start {
+                name: "c",
+                attrs,
+                self_closing,
+            } => {
+                cell_col = match attr(attrs, "r") {
+                    Some(r) => column_index(&r)?.unwrap_or(row.len()),
+                    None => row.len(),
+                };
+                cell_type = attr(attrs, "t").unwrap_or_default();
+                value.clear();
+                if self_closing {
+                    set_cell(&mut row, cell_col, String::new());
+                }
+            }
+            XmlEvent::End("c") => {
+                let text = match cell_type.as_str() {
+                    "s" => value
+                        .trim()
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|i| shared.get(i).cloned())
+                        .unwrap_or_default(),
+                    "b" => if value.trim() == "1" { "TRUE" } else { "FALSE" }.to_string(),
+                    _ => value.clone(),
+                };
+                set_cell(&mut row, cell_col, text);
+            }
+            XmlEvent::Start {
+                name: "v" | "t",
+                self_closing: false,
+                ..
+            } => capture = true,
+            XmlEvent::End("v" | "t") => capture = false,
+            XmlEvent::Text(text) if capture => value.push_str(&unescape(text)),
+            _ => {}
+        }
+    }
+
+    // Drop blank rows at the end of the used range
+    while rows
+        .last()
+        .is_some_and(|r| r.iter().all(|c| c.trim().is_empty()))
+    {
+        rows.pop();
+    }
+    Ok(rows)
+}
+
+fn set_cell(row: &mut Vec<String>, col: usize, text: String) {
+    if row.len() <= col {
+        row.resize(col + 1, String::new());
+    }
+    row[col] = text;
+}
+
+/// Last column a worksheet can have (XFD)
+const MAX_COLUMN: usize = 16_383;
+
+/// Converts the letters of a cell reference ("C12") to a zero-based column index.
+/// References past XFD are rejected so a crafted sheet cannot force huge rows.
+fn column_index(reference: &str) -> Result<Option<usize>, AppError> {
+    let letters: String = reference
+        .chars()
+        .take_while(|c| c.is_ascii_alphabetic())
+        .collect();
+    if letters.is_empty() {
+        return Ok(None);
+    }
+    letters
+        .chars()
+        .try_fold(0usize, |acc, c| {
+            acc.checked_mul(26)?
+                .checked_add(c.to_ascii_uppercase() as usize - 'A' as usize + 1)
+        })
+        .map(|n| n - 1)
+        .filter(|&col| col <= MAX_COLUMN)
+        .map(Some)
+        .ok_or_else(|| {
+            AppError::BadRequest(format!("Cell reference {} is out of range", reference))
+        })
+}
+
+// ==================== XML scanning ====================
+
+enum XmlEvent<'a> {
+    Start {
+        name: &'a str,
+        attrs: &'a str,
+        self_closing: bool,
+    },
+    End(&'a str),
+    Text(&'a str),
+}
+
+/// Forward-only tag scanner. Namespace prefixes are stripped from element names;
+/// comments, processing instructions and declarations are skipped.
+struct XmlScanner<'a> {
+    xml: &'a str,
+    pos: usize,
+}
+
+impl<'a> XmlScanner<'a> {
+    fn new(xml: &'a str) -> Self {
+        Self { xml, pos: 0 }
+    }
+}
+
+impl<'a> Iterator for XmlScanner<'a> {
+    type Item = XmlEvent<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let rest = &self.xml[self.pos..];
+            if rest.is_empty() {
+                return None;
+            }
+            if !rest.starts_with('<') {
+                let end = rest.find('<').unwrap_or(rest.len());
+                self.pos += end;
+                return Some(XmlEvent::Text(&rest[..end]));
+            }
+
+            let (close, skip) = if rest.starts_with("<!--") {
+                ("-->", true)
+            } else if rest.starts_with("<![CDATA[") {
+                let end = rest.find("]]>")?;
+                self.pos += end + 3;
+                return Some(XmlEvent::Text(&rest[9..end]));
+            } else if rest.starts_with("<?") || rest.starts_with("<!") {
+                (">", true)
+            } else {
+                (">", false)
+            };
+            let end = rest.find(close)? + close.len();
+            self.pos += end;
+            if skip {
+                continue;
+            }
+
+            let inner = &rest[1..end - 1];
+            if let Some(name) = inner.strip_prefix('/') {
+                return Some(XmlEvent::End(local_name(name.trim())));
+            }
+            let self_closing = inner.ends_with('/');
+            let inner = inner.trim_end_matches('/');
+            let (name, attrs) = match inner.find(char::is_whitespace) {
+                Some(i) => (&inner[..i], &inner[i..]),
+                None => (inner, ""),
+            };
+            return Some(XmlEvent::Start {
+                name: local_name(name),
+                attrs,
+                self_closing,
+            });
+        }
+    }
+}
+
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+/// Looks up an attribute by local name (`r:id` matches `id`).
+fn attr(attrs: &str, wanted: &str) -> Option<String> {
+    let mut rest = attrs;
+    while let Some(eq) = rest.find('=') {
+        let key = rest[..eq].trim();
+        let after = rest[eq + 1..].trim_start();
+        let quote = after.chars().next()?;
+        if quote != '"' && quote != '\'' {
+            return None;
+        }
+        let close = after[1..].find(quote)? + 1;
+        if local_name(key) == wanted {
+            return Some(unescape(&after[1..close]));
+        }
+        rest = &after[close + 1..];
+    }
+    None
+}
+
+fn unescape(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let Some(semi) = rest.find(';') else {
+            break;
+        };
+        let entity = &rest[1..semi];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|d| d.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                out.push(c);
+                rest = &rest[semi + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
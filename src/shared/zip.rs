@@ -0,0 +1,255 @@
+//! Tiny ZIP archive writer for bundling several export files into one download, and a
+//! reader for the stored/deflated archives produced by spreadsheet tools (XLSX).
+//!
+//! Written entries are stored uncompressed; exports are small text files and this keeps
+//! the archive readable by every unzip tool.
+
+use std::io::Read;
+
+use chrono::{Datelike, Timelike, Utc};
+use flate2::read::DeflateDecoder;
+
+use crate::shared::AppError;
+
+struct ZipEntry {
+    name: String,
+    crc32: u32,
+    size: u32,
+    offset: u32,
+}
+
+/// In-memory ZIP builder
+#[derive(Default)]
+pub struct ZipWriter {
+    buffer: Vec<u8>,
+    entries: Vec<ZipEntry>,
+}
+
+impl ZipWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a file to the archive under `name`.
+    pub fn add_file(&mut self, name: &str, data: &[u8]) {
+        let (dos_time, dos_date) = dos_timestamp();
+        let crc32 = crc32(data);
+        let size = data.len() as u32;
+        let offset = self.buffer.len() as u32;
+
+        // Local file header
+        self.put_u32(0x0403_4b50);
+        self.put_u16(20); // version needed to extract
+        self.put_u16(0x0800); // UTF-8 file names
+        self.put_u16(0); // stored
+        self.put_u16(dos_time);
+        self.put_u16(dos_date);
+        self.put_u32(crc32);
+        self.put_u32(size);
+        self.put_u32(size);
+        self.put_u16(name.len() as u16);
+        self.put_u16(0);
+        self.buffer.extend_from_slice(name.as_bytes());
+        self.buffer.extend_from_slice(data);
+
+        self.entries.push(ZipEntry {
+            name: name.to_string(),
+            crc32,
+            size,
+            offset,
+        });
+    }
+
+    /// Writes the central directory and returns the archive bytes.
+    pub fn finish(mut self) -> Vec<u8> {
+        let (dos_time, dos_date) = dos_timestamp();
+        let directory_offset = self.buffer.len() as u32;
+        let entries = std::mem::take(&mut self.entries);
+
+        for entry in &entries {
+            self.put_u32(0x0201_4b50);
+            self.put_u16(20); // version made by
+            self.put_u16(20); // version needed to extract
+            self.put_u16(0x0800);
+            self.put_u16(0);
+            self.put_u16(dos_time);
+            self.put_u16(dos_date);
+            self.put_u32(entry.crc32);
+            self.put_u32(entry.size);
+            self.put_u32(entry.size);
+            self.put_u16(entry.name.len() as u16);
+            self.put_u16(0); // extra field length
+            self.put_u16(0); // comment length
+            self.put_u16(0); // disk number
+            self.put_u16(0); // internal attributes
+            self.put_u32(0); // external attributes
+            self.put_u32(entry.offset);
+            self.buffer.extend_from_slice(entry.name.as_bytes());
+        }
+
+        let directory_size = self.buffer.len() as u32 - directory_offset;
+
+        // End of central directory record
+        self.put_u32(0x0605_4b50);
+        self.put_u16(0);
+        self.put_u16(0);
+        self.put_u16(entries.len() as u16);
+        self.put_u16(entries.len() as u16);
+        self.put_u32(directory_size);
+        self.put_u32(directory_offset);
+        self.put_u16(0);
+
+        self.buffer
+    }
+
+    fn put_u16(&mut self, value: u16) {
+        self.buffer.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn put_u32(&mut self, value: u32) {
+        self.buffer.extend_from_slice(&value.to_le_bytes());
+    }
+}
+
+fn dos_timestamp() -> (u16, u16) {
+    let now = Utc::now();
+    let time =
+        ((now.hour() as u16) << 11) | ((now.minute() as u16) << 5) | (now.second() as u16 / 2);
+    let date =
+        (((now.year() - 1980).max(0) as u16) << 9) | ((now.month() as u16) << 5) | now.day() as u16;
+    (time, date)
+}
+
+/// CRC-32 (IEEE 802.3) as required by the ZIP format.
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// Read-only view over an in-memory ZIP archive
+pub struct ZipReader<'a> {
+    data: &'a [u8],
+    entries: Vec<ZipReadEntry>,
+}
+
+struct ZipReadEntry {
+    name: String,
+    method: u16,
+    compressed_size: usize,
+    size: usize,
+    local_offset: usize,
+}
+
+impl<'a> ZipReader<'a> {
+    /// Parses the central directory. Fails on anything that is not a ZIP archive.
+    pub fn new(data: &'a [u8]) -> Result<Self, AppError> {
+        let invalid = || AppError::BadRequest("File is not a valid ZIP/XLSX archive".into());
+
+        // The end of central directory record is at least 22 bytes and may be followed
+        // by a comment of up to 64 KiB.
+        let search_start = data.len().saturating_sub(22 + u16::MAX as usize);
+        let eocd = (search_start..=data.len().saturating_sub(22))
+            .rev()
+            .find(|&i| read_u32(data, i) == Some(0x0605_4b50))
+            .ok_or_else(invalid)?;
+
+        let count = read_u16(data, eocd + 10).ok_or_else(invalid)? as usize;
+        let mut pos = read_u32(data, eocd + 16).ok_or_else(invalid)? as usize;
+        let mut entries = Vec::with_capacity(count);
+
+        for _ in 0..count {
+            if read_u32(data, pos) != Some(0x0201_4b50) {
+                return Err(invalid());
+            }
+            let field = |offset: usize| read_u16(data, pos + offset).ok_or_else(invalid);
+            let word = |offset: usize| read_u32(data, pos + offset).ok_or_else(invalid);
+            let method = field(10)?;
+            let compressed_size = word(20)? as usize;
+            let size = word(24)? as usize;
+            let name_len = field(28)? as usize;
+            let extra_len = field(30)? as usize;
+            let comment_len = field(32)? as usize;
+            let local_offset = word(42)? as usize;
+            let name = data
+                .get(pos + 46..pos + 46 + name_len)
+                .ok_or_else(invalid)?;
+
+            entries.push(ZipReadEntry {
+                name: String::from_utf8_lossy(name).into_owned(),
+                method,
+                compressed_size,
+                size,
+                local_offset,
+            });
+            pos += 46 + name_len + extra_len + comment_len;
+        }
+
+        Ok(Self { data, entries })
+    }
+
+    /// Returns the uncompressed contents of `name`, or `None` when the entry is absent.
+    pub fn read(&self, name: &str) -> Result<Option<Vec<u8>>, AppError> {
+        let Some(entry) = self.entries.iter().find(|e| e.name == name) else {
+            return Ok(None);
+        };
+        let invalid = || AppError::BadRequest(format!("Corrupt archive entry '{}'", name));
+
+        if entry.size > MAX_ENTRY_SIZE {
+            return Err(AppError::BadRequest(format!(
+                "Archive entry '{}' is too large",
+                name
+            )));
+        }
+
+        let base = entry.local_offset;
+        if read_u32(self.data, base) != Some(0x0403_4b50) {
+            return Err(invalid());
+        }
+        let name_len = read_u16(self.data, base + 26).ok_or_else(invalid)? as usize;
+        let extra_len = read_u16(self.data, base + 28).ok_or_else(invalid)? as usize;
+        let start = base + 30 + name_len + extra_len;
+        let raw = self
+            .data
+            .get(start..start + entry.compressed_size)
+            .ok_or_else(invalid)?;
+
+        match entry.method {
+            0 => Ok(Some(raw.to_vec())),
+            8 => {
+                let mut out = Vec::with_capacity(entry.size);
+                DeflateDecoder::new(raw)
+                    .take(MAX_ENTRY_SIZE as u64 + 1)
+                    .read_to_end(&mut out)
+                    .map_err(|_| invalid())?;
+                if out.len() > MAX_ENTRY_SIZE {
+                    return Err(invalid());
+                }
+                Ok(Some(out))
+            }
+            other => Err(AppError::BadRequest(format!(
+                "Unsupported compression method {} in '{}'",
+                other, name
+            ))),
+        }
+    }
+}
+
+/// Upper bound for a single decompressed entry, guarding against zip bombs
+const MAX_ENTRY_SIZE: usize = 64 * 1024 * 1024;
+
+fn read_u16(data: &[u8], at: usize) -> Option<u16> {
+    data.get(at..at + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+}
+
+fn read_u32(data: &[u8], at: usize) -> Option<u32> {
+    data.get(at..at + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
@@ -0,0 +1,276 @@
+//! iCalendar feeds for users, tournaments, categories and courts, run against the in-memory
+//! repositories.
+
+mod common;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use serde_json::json;
+use uuid::Uuid;
+
+use server::domain::calendar::{CalendarFeed, CalendarFeedKind};
+use server::domain::match_domain::{MatchType, NewMatch, RescheduleMatchRequest};
+use server::domain::participant::CreatePlayer;
+use server::domain::tournament::{NewTournamentCategory, SportType, TeamComposition};
+use server::domain::venue::{NewCourt, NewVenue};
+use server::infra::repositories::Repositories;
+use server::shared::ical::{Calendar, CalendarEvent, EventStatus};
+use server::shared::jwt::{
+    generate_calendar_token, validate_calendar_token, validate_check_in_token,
+};
+
+use common::{new_tournament, services};
+
+fn at(days: i64, hour: u32) -> DateTime<Utc> {
+    let today = Utc::now().date_naive() + Duration::days(days);
+    Utc.from_utc_datetime(&today.and_hms_opt(hour, 0, 0).unwrap())
+}
+
+/// Joins folded lines back together
+fn unfold(ics: &str) -> String {
+    ics.replace("\r\n ", "")
+}
+
+/// The VEVENT with the given UID, unfolded
+fn event(ics: &str, match_id: Uuid) -> Option<&str> {
+    let uid = format!("UID:match-{}@tournament-server", match_id);
+    ics.split("BEGIN:VEVENT")
+        .find(|e| e.contains(&uid))
+        .map(|e| e.split("END:VEVENT").next().unwrap())
+}
+
+#[test]
+fn test_calendar_escapes_and_folds_lines() {
+    let starts_at = Utc.with_ymd_and_hms(2026, 10, 19, 14, 30, 0).unwrap();
+    let mut calendar = Calendar::new("Club Night; Finals");
+    calendar.push(CalendarEvent {
+        uid: "match-1@tournament-server".to_string(),
+        summary: "Zoë Ångström, Jr. vs Ünal Çelik".repeat(3),
+        description: Some("Open Singles\nFinal".to_string()),
+        location: None,
+        starts_at,
+        ends_at: starts_at + Duration::minutes(45),
+        status: EventStatus::Cancelled,
+    });
+    let ics = calendar.to_ics(starts_at);
+
+    assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+    assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    assert!(!ics.replace("\r\n", "").contains('\n'));
+    for line in ics.split("\r\n") {
+        assert!(line.len() <= 75, "line too long: {line}");
+    }
+
+    let ics = unfold(&ics);
+    assert!(ics.contains("X-WR-CALNAME:Club Night\\; Finals\r\n"));
+    assert!(ics.contains("DTSTART:20261019T143000Z\r\nDTEND:20261019T151500Z\r\n"));
+    assert!(ics.contains(&format!(
+        "SUMMARY:{}\r\n",
+        "Zoë Ångström\\, Jr. vs Ünal Çelik".repeat(3)
+    )));
+    assert!(ics.contains("DESCRIPTION:Open Singles\\nFinal\r\n"));
+    assert!(ics.contains("STATUS:CANCELLED\r\n"));
+    assert!(!ics.contains("LOCATION"));
+}
+
+#[actix_web::test]
+async fn test_feeds_follow_the_schedule() {
+    let repos = Repositories::in_memory();
+    let services = services(&repos);
+    let now = Utc::now();
+    let tournament = repos
+        .tournaments
+        .create(new_tournament(
+            "Autumn Open",
+            SportType::Badminton,
+            at(1, 0),
+            at(3, 0),
+        ))
+        .await
+        .unwrap();
+    let category = repos
+        .categories
+        .create(NewTournamentCategory {
+            tournament_id: tournament.id,
+            name: "Open Singles".to_string(),
+            description: None,
+            team_composition: TeamComposition::Singles,
+            min_participants: None,
+            max_participants: None,
+            entry_fee: None,
+            prize_distribution: None,
+            rules: None,
+            constraints: Some(json!({ "match_duration_minutes": 45 })),
+        })
+        .await
+        .unwrap();
+    let venue: NewVenue = serde_json::from_value(json!({
+        "name": "Sports Hall",
+        "timezone": "UTC",
+        "opening_hours": [],
+    }))
+    .unwrap();
+    let hall = services.venues.create_venue(venue).await.unwrap();
+    let court: NewCourt =
+        serde_json::from_value(json!({ "name": "Court 1", "kind": "court" })).unwrap();
+    let court = services
+        .venues
+        .add_court(hall.id, court)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let ana_user = Uuid::new_v4();
+    let mut players = Vec::new();
+    for (name, user_id) in [("Ana Lee", Some(ana_user)), ("Ben Ortiz", None)] {
+        let player = repos
+            .players
+            .create(CreatePlayer {
+                name: name.to_string(),
+                user_id,
+            })
+            .await
+            .unwrap();
+        players.push(player.id);
+    }
+    let mut match_ids = Vec::new();
+    for (hour, court_id) in [(10, Some(court.id)), (12, None)] {
+        let m = services
+            .matches
+            .create_match(
+                NewMatch {
+                    tournament_category_id: category.id,
+                    participant1_team_id: None,
+                    participant1_player_id: Some(players[0]),
+                    participant1_partner_id: None,
+                    participant2_team_id: None,
+                    participant2_player_id: Some(players[1]),
+                    participant2_partner_id: None,
+                    match_type: MatchType::QuarterFinal,
+                    round_number: Some(1),
+                    match_number: None,
+                    scheduled_date: at(1, hour),
+                    venue: None,
+                    court_number: None,
+                    court_id,
+                    referee_name: None,
+                    umpire_name: None,
+                    notes: None,
+                    metadata: None,
+                },
+                true,
+            )
+            .await
+            .unwrap();
+        match_ids.push(m.id);
+    }
+    let (first, second) = (match_ids[0], match_ids[1]);
+    let feed = |kind, id| CalendarFeed { kind, id };
+
+    // Ana's own feed has both matches, lasting the category's 45 minutes
+    let mine = feed(CalendarFeedKind::User, ana_user);
+    let ics = unfold(
+        &services
+            .calendars
+            .render_feed(&mine, now)
+            .await
+            .unwrap()
+            .unwrap(),
+    );
+    assert!(ics.contains("X-WR-CALNAME:My matches"));
+    let event_ics = event(&ics, first).unwrap();
+    assert!(event_ics.contains("SUMMARY:Ana Lee vs Ben Ortiz\r\n"));
+    assert!(event_ics.contains(&format!(
+        "DTEND:{}\r\n",
+        (at(1, 10) + Duration::minutes(45)).format("%Y%m%dT%H%M%SZ")
+    )));
+    assert!(event_ics.contains("LOCATION:Court 1\\, Sports Hall\r\n"));
+    assert!(
+        event_ics.contains("DESCRIPTION:Autumn Open - Open Singles\\nquarter final\\, round 1\r\n")
+    );
+    assert!(event(&ics, second).is_some());
+
+    // A rescheduled match moves in the feed; a postponed one stays, marked tentative
+    services
+        .matches
+        .reschedule_match(
+            second,
+            RescheduleMatchRequest {
+                new_scheduled_date: at(2, 9),
+                new_venue: None,
+                new_court_number: None,
+                new_court_id: None,
+                reason: None,
+            },
+            true,
+        )
+        .await
+        .unwrap();
+    services.matches.postpone_match(first).await.unwrap();
+    let tournament_feed = feed(CalendarFeedKind::Tournament, tournament.id);
+    let ics = unfold(
+        &services
+            .calendars
+            .render_feed(&tournament_feed, now)
+            .await
+            .unwrap()
+            .unwrap(),
+    );
+    assert!(ics.contains("X-WR-CALNAME:Autumn Open"));
+    assert!(event(&ics, second).unwrap().contains(&format!(
+        "DTSTART:{}\r\n",
+        at(2, 9).format("%Y%m%dT%H%M%SZ")
+    )));
+    let postponed = event(&ics, first).unwrap();
+    assert!(postponed.contains("STATUS:TENTATIVE"));
+    assert!(postponed.contains("SUMMARY:Postponed: Ana Lee vs Ben Ortiz"));
+
+    // Cancelled matches stay in the category feed as cancelled events
+    services.matches.cancel_match(second, "Rain").await.unwrap();
+    let category_feed = feed(CalendarFeedKind::Category, category.id);
+    let ics = unfold(
+        &services
+            .calendars
+            .render_feed(&category_feed, now)
+            .await
+            .unwrap()
+            .unwrap(),
+    );
+    assert!(ics.contains("X-WR-CALNAME:Autumn Open - Open Singles"));
+    assert!(event(&ics, second).unwrap().contains("STATUS:CANCELLED"));
+
+    // The court's feed only has the match played on it
+    let court_feed = feed(CalendarFeedKind::Court, court.id);
+    let ics = unfold(
+        &services
+            .calendars
+            .render_feed(&court_feed, now)
+            .await
+            .unwrap()
+            .unwrap(),
+    );
+    assert!(ics.contains("X-WR-CALNAME:Sports Hall - Court 1"));
+    assert!(event(&ics, first).is_some());
+    assert!(event(&ics, second).is_none());
+
+    let missing = feed(CalendarFeedKind::Court, Uuid::new_v4());
+    assert!(services
+        .calendars
+        .render_feed(&missing, now)
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[test]
+fn test_calendar_tokens_name_the_feed() {
+    let tournament_id = Uuid::new_v4();
+    let token = generate_calendar_token("secret", "tournament", tournament_id).unwrap();
+    let claims = validate_calendar_token("secret", &token).unwrap();
+    assert_eq!(claims.subject_id(), Some(tournament_id));
+    assert_eq!(
+        CalendarFeedKind::from_key(&claims.feed),
+        Some(CalendarFeedKind::Tournament)
+    );
+    assert!(validate_calendar_token("other", &token).is_err());
+    assert!(validate_check_in_token("secret", &token).is_err());
+}
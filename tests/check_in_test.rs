@@ -0,0 +1,370 @@
+//! Check-in windows, self, QR and desk check-in, closing windows on no-shows, and drawing
+//! brackets from checked-in entries, run against the in-memory repositories.
+
+mod common;
+
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use server::domain::check_in::{CheckInMethod, CheckInWindowRequest, NoShowAction};
+use server::domain::outbox::DomainEvent;
+use server::domain::participant::CreatePlayer;
+use server::domain::tournament::{
+    bracket_nodes, EditableTournamentRegistration, NewTournamentRegistration, RegistrationStatus,
+};
+use server::infra::repositories::Repositories;
+use server::shared::jwt::{generate_check_in_token, validate_check_in_token};
+use server::shared::AppError;
+
+use common::services;
+
+async fn seed_category(repos: &Repositories) -> Uuid {
+    common::seed_category(repos, None).await.id
+}
+
+/// A registration with the given status for a new player; returns (registration, user)
+async fn entry(
+    repos: &Repositories,
+    category_id: Uuid,
+    name: &str,
+    status: RegistrationStatus,
+) -> (Uuid, Uuid) {
+    let user_id = Uuid::new_v4();
+    let player = repos
+        .players
+        .create(CreatePlayer {
+            name: name.to_string(),
+            user_id: Some(user_id),
+        })
+        .await
+        .unwrap();
+    let registration = repos
+        .registrations
+        .create(NewTournamentRegistration {
+            tournament_category_id: category_id,
+            team_id: None,
+            player_id: Some(player.id),
+            partner_player_id: None,
+            notes: None,
+            metadata: None,
+        })
+        .await
+        .unwrap();
+    repos
+        .registrations
+        .update(
+            registration.id,
+            EditableTournamentRegistration {
+                registration_status: Some(status),
+                payment_status: None,
+                payment_amount: None,
+                payment_reference: None,
+                notes: None,
+                metadata: None,
+            },
+        )
+        .await
+        .unwrap();
+    (registration.id, user_id)
+}
+
+fn window(opens_in: Duration, closes_in: Duration) -> CheckInWindowRequest {
+    let now = Utc::now();
+    CheckInWindowRequest {
+        opens_at: now + opens_in,
+        closes_at: now + closes_in,
+        no_show_action: None,
+        promote_waitlist: None,
+    }
+}
+
+#[actix_web::test]
+async fn test_check_in_follows_the_window() {
+    let repos = Repositories::in_memory();
+    let services = services(&repos);
+    let category_id = seed_category(&repos).await;
+    let (ana, ana_user) = entry(&repos, category_id, "Ana Lee", RegistrationStatus::Approved).await;
+    let (ben, _) = entry(
+        &repos,
+        category_id,
+        "Ben Ortiz",
+        RegistrationStatus::Approved,
+    )
+    .await;
+    let (cara, _) = entry(
+        &repos,
+        category_id,
+        "Cara Diaz",
+        RegistrationStatus::Pending,
+    )
+    .await;
+
+    // Without a window only the desk can check anyone in
+    let err = services
+        .check_in
+        .self_check_in(ana_user, ana)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::ValidationError(_)));
+
+    let err = services
+        .check_in
+        .set_window(category_id, window(Duration::hours(1), Duration::zero()))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::ValidationError(_)));
+
+    // Not open yet: self-service waits, the desk does not
+    services
+        .check_in
+        .set_window(category_id, window(Duration::hours(1), Duration::hours(2)))
+        .await
+        .unwrap()
+        .unwrap();
+    let err = services
+        .check_in
+        .self_check_in(ana_user, ana)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::ValidationError(_)));
+    let staff = Uuid::new_v4();
+    let desk = services
+        .check_in
+        .desk_check_in(staff, ben)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(desk.check_in_method, Some(CheckInMethod::Desk));
+    assert_eq!(desk.checked_in_by, Some(staff));
+
+    services
+        .check_in
+        .set_window(
+            category_id,
+            window(-Duration::minutes(5), Duration::hours(1)),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    // Only the registered player may check themselves in
+    let err = services
+        .check_in
+        .self_check_in(Uuid::new_v4(), ana)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::Forbidden(_)));
+    let checked_in = services
+        .check_in
+        .self_check_in(ana_user, ana)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(checked_in.check_in_method, Some(CheckInMethod::SelfService));
+
+    // A pending entry has nothing to check in for
+    let err = services
+        .check_in
+        .desk_check_in(staff, cara)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::ValidationError(_)));
+
+    // The QR pass names the registration and runs out when the window closes
+    let (_, expires_at) = services
+        .check_in
+        .check_in_pass(ana_user, ana)
+        .await
+        .unwrap()
+        .unwrap();
+    let token = generate_check_in_token("secret", ana, expires_at).unwrap();
+    let claims = validate_check_in_token("secret", &token).unwrap();
+    assert_eq!(claims.registration_id(), Some(ana));
+    assert!(validate_check_in_token("other", &token).is_err());
+
+    // Scanning after undoing records the QR method
+    services.check_in.undo_check_in(ana).await.unwrap().unwrap();
+    let scanned = services.check_in.scan_check_in(ana).await.unwrap().unwrap();
+    assert_eq!(scanned.check_in_method, Some(CheckInMethod::Qr));
+
+    let sheet = services
+        .check_in
+        .get_sheet(category_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(sheet.expected, 2);
+    assert_eq!(sheet.checked_in, 2);
+    assert_eq!(sheet.entries.len(), 2);
+    assert_eq!(sheet.entries[0].participant_name, "Ana Lee");
+}
+
+#[actix_web::test]
+async fn test_closing_the_window_settles_no_shows() {
+    let repos = Repositories::in_memory();
+    let services = services(&repos);
+    let category_id = seed_category(&repos).await;
+    let (ana, _) = entry(&repos, category_id, "Ana Lee", RegistrationStatus::Approved).await;
+    let (ben, _) = entry(
+        &repos,
+        category_id,
+        "Ben Ortiz",
+        RegistrationStatus::Approved,
+    )
+    .await;
+    let (cara, _) = entry(
+        &repos,
+        category_id,
+        "Cara Diaz",
+        RegistrationStatus::Waitlisted,
+    )
+    .await;
+    let (dev, _) = entry(
+        &repos,
+        category_id,
+        "Dev Patel",
+        RegistrationStatus::Waitlisted,
+    )
+    .await;
+
+    let mut request = window(-Duration::hours(1), Duration::hours(1));
+    request.no_show_action = Some(NoShowAction::Waitlist);
+    services
+        .check_in
+        .set_window(category_id, request)
+        .await
+        .unwrap()
+        .unwrap();
+    let staff = Uuid::new_v4();
+    for id in [ana, cara, dev] {
+        services.check_in.desk_check_in(staff, id).await.unwrap();
+    }
+
+    // Nothing is due yet
+    assert_eq!(
+        services
+            .check_in
+            .close_due_windows(Utc::now())
+            .await
+            .unwrap(),
+        0
+    );
+
+    // Ben never showed, so Ben goes to the waitlist and Cara, first in line, is approved
+    let closure = services
+        .check_in
+        .close_due_windows(Utc::now() + Duration::hours(2))
+        .await
+        .unwrap();
+    assert_eq!(closure, 1);
+    let status = |id| {
+        let repos = &repos;
+        async move {
+            repos
+                .registrations
+                .get_by_id(id)
+                .await
+                .unwrap()
+                .unwrap()
+                .registration_status
+        }
+    };
+    assert_eq!(status(ana).await, RegistrationStatus::Approved);
+    assert_eq!(status(ben).await, RegistrationStatus::Waitlisted);
+    assert_eq!(status(cara).await, RegistrationStatus::Approved);
+    assert_eq!(status(dev).await, RegistrationStatus::Waitlisted);
+
+    let messages = repos
+        .outbox
+        .claim_due(10, Utc::now() + Duration::minutes(5))
+        .await
+        .unwrap();
+    assert_eq!(messages.len(), 1);
+    assert!(matches!(
+        messages[0].event().unwrap(),
+        DomainEvent::RegistrationApproved { registration_id, .. } if registration_id == cara
+    ));
+
+    // A closed window stays closed, and check-ins are frozen
+    let err = services
+        .check_in
+        .close_window(category_id, Utc::now())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::Conflict(_)));
+    let err = services.check_in.undo_check_in(ana).await.unwrap_err();
+    assert!(matches!(err, AppError::ValidationError(_)));
+}
+
+#[actix_web::test]
+async fn test_bracket_draws_checked_in_entries() {
+    let repos = Repositories::in_memory();
+    let services = services(&repos);
+    let category_id = seed_category(&repos).await;
+    let mut entries = Vec::new();
+    for name in ["Ana Lee", "Ben Ortiz", "Cara Diaz", "Dev Patel", "Eli Moss"] {
+        entries.push(
+            entry(&repos, category_id, name, RegistrationStatus::Approved)
+                .await
+                .0,
+        );
+    }
+    services
+        .check_in
+        .set_window(category_id, window(-Duration::hours(1), Duration::hours(1)))
+        .await
+        .unwrap()
+        .unwrap();
+
+    // One entry in the draw is not enough
+    let staff = Uuid::new_v4();
+    services
+        .check_in
+        .desk_check_in(staff, entries[0])
+        .await
+        .unwrap();
+    let err = services
+        .tournaments
+        .generate_category_bracket(category_id)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::ValidationError(_)));
+
+    // Three checked in: two rounds, with the top seed getting the bye
+    for id in &entries[1..3] {
+        services.check_in.desk_check_in(staff, *id).await.unwrap();
+    }
+    let bracket = services
+        .tournaments
+        .generate_category_bracket(category_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(bracket.category_id, Some(category_id));
+    assert_eq!(bracket.total_rounds, 2);
+
+    let nodes = bracket_nodes(bracket.bracket_data.as_ref().unwrap());
+    assert_eq!(nodes.len(), 3);
+    let names: Vec<&str> = nodes
+        .iter()
+        .flat_map(|n| [&n.participant1_name, &n.participant2_name])
+        .filter_map(|name| name.as_deref())
+        .collect();
+    assert!(!names.contains(&"Dev Patel") && !names.contains(&"Eli Moss"));
+
+    let bye = &nodes[0];
+    assert_eq!(bye.participant1_name.as_deref(), Some("Ana Lee"));
+    assert!(bye.participant2_id.is_none());
+    assert_eq!(bye.winner_id, bye.participant1_id);
+    let final_node = nodes.iter().find(|n| n.round == 2).unwrap();
+    assert_eq!(final_node.participant1_id, bye.participant1_id);
+    assert_eq!(nodes[1].participant1_name.as_deref(), Some("Ben Ortiz"));
+    assert_eq!(nodes[1].participant2_name.as_deref(), Some("Cara Diaz"));
+
+    let err = services
+        .tournaments
+        .generate_category_bracket(category_id)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::Conflict(_)));
+}
@@ -0,0 +1,163 @@
+//! Fixtures shared by the integration tests; each test crate uses only some of them.
+#![allow(dead_code)]
+
+use chrono::{DateTime, TimeZone, Utc};
+use uuid::Uuid;
+
+use server::domain::participant::CreatePlayer;
+use server::domain::tournament::{
+    EditableTournament, NewTournament, NewTournamentCategory, NewTournamentRegistration, SportType,
+    TeamComposition, TournamentCategory, TournamentFormat,
+};
+use server::infra::api::sse::Broadcaster;
+use server::infra::api::state::AppServices;
+use server::infra::repositories::Repositories;
+use server::shared::config::SseConfig;
+
+/// Initialises the API routes over `$repos`, with the SSE broadcaster they publish to
+#[allow(unused_macros)]
+macro_rules! init_app {
+    ($repos:expr) => {{
+        let broadcaster = ::server::infra::api::sse::Broadcaster::create(
+            &::server::shared::config::SseConfig::default(),
+            None,
+        );
+        let services = ::server::infra::api::state::AppServices::new(&$repos, broadcaster.clone());
+        ::actix_web::test::init_service(
+            ::actix_web::App::new()
+                .app_data(::actix_web::web::Data::new(broadcaster))
+                .configure(|cfg| services.register(cfg))
+                .configure(::server::infra::api::api_routes),
+        )
+        .await
+    }};
+}
+#[allow(unused_imports)]
+pub(crate) use init_app;
+
+pub fn services(repos: &Repositories) -> AppServices {
+    AppServices::new(repos, Broadcaster::create(&SseConfig::default(), None))
+}
+
+/// An elimination tournament with every optional field left empty; tests that need more
+/// override fields with struct update syntax
+pub fn new_tournament(
+    name: &str,
+    sport_type: SportType,
+    start_date: DateTime<Utc>,
+    end_date: DateTime<Utc>,
+) -> NewTournament {
+    NewTournament {
+        name: name.to_string(),
+        description: None,
+        sport_type,
+        format: TournamentFormat::Elimination,
+        start_date,
+        end_date,
+        registration_start_date: None,
+        registration_end_date: None,
+        venue: None,
+        max_participants: None,
+        entry_fee: None,
+        prize_pool: None,
+        rules: None,
+        organizer_id: Uuid::new_v4(),
+        timezone: None,
+    }
+}
+
+/// Saturday 2 May 2026
+pub fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 5, 2, hour, minute, 0).unwrap()
+}
+
+/// "Open Singles" of "Club Singles", a table tennis tournament from 09:00 to 18:00 on
+/// `at`'s day
+pub async fn seed_category(
+    repos: &Repositories,
+    constraints: Option<serde_json::Value>,
+) -> TournamentCategory {
+    let tournament = repos
+        .tournaments
+        .create(new_tournament(
+            "Club Singles",
+            SportType::TableTennis,
+            at(9, 0),
+            at(18, 0),
+        ))
+        .await
+        .unwrap();
+    repos
+        .categories
+        .create(NewTournamentCategory {
+            tournament_id: tournament.id,
+            name: "Open Singles".to_string(),
+            description: None,
+            team_composition: TeamComposition::Singles,
+            min_participants: None,
+            max_participants: None,
+            entry_fee: None,
+            prize_distribution: None,
+            rules: None,
+            constraints,
+        })
+        .await
+        .unwrap()
+}
+
+/// Replaces the rules of the tournament `category` belongs to
+pub async fn set_tournament_rules(
+    repos: &Repositories,
+    category: &TournamentCategory,
+    rules: serde_json::Value,
+) {
+    repos
+        .tournaments
+        .update(
+            category.tournament_id,
+            EditableTournament {
+                rules: Some(rules),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+}
+
+/// A player without an account
+pub async fn player(repos: &Repositories) -> Uuid {
+    repos
+        .players
+        .create(CreatePlayer {
+            name: format!("Player {}", Uuid::new_v4()),
+            user_id: None,
+        })
+        .await
+        .unwrap()
+        .id
+}
+
+/// A registered player
+pub async fn entrant(repos: &Repositories, category_id: Uuid, name: &str) -> Uuid {
+    let player = repos
+        .players
+        .create(CreatePlayer {
+            name: name.to_string(),
+            user_id: None,
+        })
+        .await
+        .unwrap();
+    repos
+        .registrations
+        .create(NewTournamentRegistration {
+            tournament_category_id: category_id,
+            team_id: None,
+            player_id: Some(player.id),
+            partner_player_id: None,
+            notes: None,
+            metadata: None,
+        })
+        .await
+        .unwrap();
+    player.id
+}
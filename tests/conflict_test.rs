@@ -0,0 +1,242 @@
+//! Scheduling conflict checks on match creation, rescheduling and bulk updates, run against
+//! the in-memory repositories.
+
+mod common;
+
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use uuid::Uuid;
+
+use server::domain::match_domain::{
+    EditableMatch, MatchStatus, MatchType, NewMatch, RescheduleMatchRequest,
+};
+use server::domain::participant::{NewTeam, NewTeamMember};
+use server::domain::scheduling::conflicts::ConflictKind;
+use server::domain::scheduling::ScheduleConflict;
+use server::domain::tournament::{NewTournamentCategory, SportType, TeamComposition};
+use server::infra::repositories::Repositories;
+use server::shared::AppError;
+
+use common::{at, new_tournament, player, services};
+
+/// A badminton tournament (40-minute matches) with a singles and a team category
+async fn seed_categories(repos: &Repositories) -> (Uuid, Uuid) {
+    let tournament = repos
+        .tournaments
+        .create(new_tournament(
+            "County Badminton",
+            SportType::Badminton,
+            at(8, 0),
+            at(20, 0),
+        ))
+        .await
+        .unwrap();
+    let mut ids = Vec::new();
+    for (name, composition) in [
+        ("Singles", TeamComposition::Singles),
+        ("Club Teams", TeamComposition::Team),
+    ] {
+        let category = repos
+            .categories
+            .create(NewTournamentCategory {
+                tournament_id: tournament.id,
+                name: name.to_string(),
+                description: None,
+                team_composition: composition,
+                min_participants: None,
+                max_participants: None,
+                entry_fee: None,
+                prize_distribution: None,
+                rules: None,
+                constraints: None,
+            })
+            .await
+            .unwrap();
+        ids.push(category.id);
+    }
+    (ids[0], ids[1])
+}
+
+fn new_match(category_id: Uuid, scheduled_date: DateTime<Utc>, court: &str) -> NewMatch {
+    NewMatch {
+        tournament_category_id: category_id,
+        participant1_team_id: None,
+        participant1_player_id: None,
+        participant1_partner_id: None,
+        participant2_team_id: None,
+        participant2_player_id: None,
+        participant2_partner_id: None,
+        match_type: MatchType::GroupStage,
+        round_number: None,
+        match_number: None,
+        scheduled_date,
+        venue: Some("Leisure Centre".to_string()),
+        court_number: Some(court.to_string()),
+        court_id: None,
+        referee_name: None,
+        umpire_name: None,
+        notes: None,
+        metadata: None,
+    }
+}
+
+fn conflicts_of(result: Result<impl std::fmt::Debug, AppError>) -> Vec<ScheduleConflict> {
+    match result {
+        Err(AppError::ConflictWithDetails(_, details)) => {
+            serde_json::from_value(details["conflicts"].clone()).unwrap()
+        }
+        other => panic!("expected a scheduling conflict, got {:?}", other),
+    }
+}
+
+#[actix_web::test]
+async fn test_team_members_cannot_play_two_matches_at_once() {
+    let repos = Repositories::in_memory();
+    let matches = services(&repos).matches;
+    let (singles, teams) = seed_categories(&repos).await;
+    let (ana, ben, cai) = (
+        player(&repos).await,
+        player(&repos).await,
+        player(&repos).await,
+    );
+    let team = repos
+        .teams
+        .create(NewTeam {
+            name: format!("Shuttlers {}", Uuid::new_v4()),
+        })
+        .await
+        .unwrap();
+    repos
+        .team_members
+        .create(NewTeamMember {
+            team_id: team.id,
+            player_id: ana,
+            is_captain: None,
+            jersey_number: None,
+        })
+        .await
+        .unwrap();
+
+    let mut single = new_match(singles, at(10, 0), "1");
+    single.participant1_player_id = Some(ana);
+    single.participant2_player_id = Some(ben);
+    let single = matches.create_match(single, false).await.unwrap();
+
+    // Ana plays for the team on another court while her singles match is still running
+    let team_match = || NewMatch {
+        participant1_team_id: Some(team.id),
+        ..new_match(teams, at(10, 30), "2")
+    };
+    let conflicts = conflicts_of(matches.create_match(team_match(), false).await);
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].kind, ConflictKind::Participant);
+    assert_eq!(conflicts[0].match_id, None);
+    assert_eq!(conflicts[0].conflicting_match_id, single.id);
+    assert_eq!(conflicts[0].subject_ids, vec![ana]);
+    assert_eq!(conflicts[0].ends_at, at(10, 40));
+
+    // After the singles match and its 40 minutes, the team match is fine
+    let mut later = new_match(teams, at(10, 40), "2");
+    later.participant1_team_id = Some(team.id);
+    later.participant2_player_id = Some(cai);
+    let later = matches.create_match(later, false).await.unwrap();
+
+    // The organizer can still save the clash deliberately
+    let forced = matches.create_match(team_match(), true).await.unwrap();
+    let saved = matches
+        .get_match_conflicts(forced.id)
+        .await
+        .unwrap()
+        .unwrap();
+    let mut found: Vec<(Uuid, ConflictKind)> = saved
+        .iter()
+        .map(|c| (c.conflicting_match_id, c.kind))
+        .collect();
+    found.sort_by_key(|(id, kind)| (*id != single.id, *kind as u8));
+    assert_eq!(
+        found,
+        vec![
+            (single.id, ConflictKind::Participant),
+            (later.id, ConflictKind::Participant),
+            (later.id, ConflictKind::Court),
+        ]
+    );
+    assert!(saved.iter().all(|c| c.match_id == Some(forced.id)));
+}
+
+#[actix_web::test]
+async fn test_reschedule_reports_court_and_official_clashes() {
+    let repos = Repositories::in_memory();
+    let matches = services(&repos).matches;
+    let (singles, _) = seed_categories(&repos).await;
+
+    let mut first = new_match(singles, at(10, 0), "1");
+    first.referee_name = Some("Dana Ortiz".to_string());
+    let first = matches.create_match(first, false).await.unwrap();
+    let mut second = new_match(singles, at(14, 0), "2");
+    second.umpire_name = Some(" dana ortiz ".to_string());
+    let second = matches.create_match(second, false).await.unwrap();
+
+    let request = || RescheduleMatchRequest {
+        new_scheduled_date: at(10, 20),
+        new_venue: None,
+        new_court_number: Some("1".to_string()),
+        new_court_id: None,
+        reason: None,
+    };
+    let conflicts = conflicts_of(matches.reschedule_match(second.id, request(), false).await);
+    let kinds: Vec<ConflictKind> = conflicts.iter().map(|c| c.kind).collect();
+    assert_eq!(kinds, vec![ConflictKind::Court, ConflictKind::Staff]);
+    assert!(conflicts.iter().all(|c| c.conflicting_match_id == first.id));
+    assert_eq!(conflicts[1].subject_names, vec!["dana ortiz".to_string()]);
+    let unchanged = matches.get_match(second.id).await.unwrap().unwrap();
+    assert_eq!(unchanged.scheduled_date, at(14, 0));
+
+    let moved = matches
+        .reschedule_match(second.id, request(), true)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(moved.scheduled_date, at(10, 20));
+}
+
+#[actix_web::test]
+async fn test_bulk_update_checks_matches_against_each_other() {
+    let repos = Repositories::in_memory();
+    let matches = services(&repos).matches;
+    let (singles, _) = seed_categories(&repos).await;
+    let a = matches
+        .create_match(new_match(singles, at(9, 0), "1"), false)
+        .await
+        .unwrap();
+    let b = matches
+        .create_match(new_match(singles, at(11, 0), "2"), false)
+        .await
+        .unwrap();
+
+    let same_slot: EditableMatch = serde_json::from_value(json!({
+        "scheduled_date": at(15, 0),
+        "court_number": "3",
+    }))
+    .unwrap();
+    let conflicts = conflicts_of(
+        matches
+            .bulk_update_matches(vec![a.id, b.id], same_slot.clone(), false)
+            .await,
+    );
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].kind, ConflictKind::Court);
+    assert_eq!(conflicts[0].match_id, Some(b.id));
+    assert_eq!(conflicts[0].conflicting_match_id, a.id);
+
+    // Cancelled matches hold no court
+    let cancel = EditableMatch {
+        match_status: Some(MatchStatus::Cancelled),
+        ..same_slot
+    };
+    let updated = matches
+        .bulk_update_matches(vec![a.id, b.id], cancel, false)
+        .await
+        .unwrap();
+    assert_eq!(updated.len(), 2);
+}
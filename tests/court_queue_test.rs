@@ -0,0 +1,361 @@
+//! Court queues, calls to court and no-show escalation, run against the in-memory
+//! repositories.
+
+mod common;
+
+use chrono::{DateTime, Duration, Utc};
+use serde_json::json;
+use uuid::Uuid;
+
+use server::domain::court_queue::{CallRequest, CallStatus};
+use server::domain::match_domain::{MatchStatus, MatchType, NewMatch};
+use server::domain::outbox::DomainEvent;
+use server::domain::tournament::{NewTournamentCategory, SportType, TeamComposition};
+use server::domain::venue::{Court, NewCourt, NewVenue};
+use server::infra::api::state::AppServices;
+use server::infra::repositories::Repositories;
+use server::shared::AppError;
+
+use common::{new_tournament, player, services};
+
+struct Seed {
+    tournament_id: Uuid,
+    category_id: Uuid,
+    court1: Court,
+    court2: Court,
+}
+
+/// A tournament playing today at one hall with two courts
+async fn seed(repos: &Repositories, services: &AppServices) -> Seed {
+    let now = Utc::now();
+    let tournament = repos
+        .tournaments
+        .create(new_tournament(
+            "Club Night",
+            SportType::Badminton,
+            now - Duration::hours(1),
+            now + Duration::hours(12),
+        ))
+        .await
+        .unwrap();
+    let category = repos
+        .categories
+        .create(NewTournamentCategory {
+            tournament_id: tournament.id,
+            name: "Open Singles".to_string(),
+            description: None,
+            team_composition: TeamComposition::Singles,
+            min_participants: None,
+            max_participants: None,
+            entry_fee: None,
+            prize_distribution: None,
+            rules: None,
+            constraints: None,
+        })
+        .await
+        .unwrap();
+    let venue: NewVenue = serde_json::from_value(json!({
+        "name": "Sports Hall",
+        "timezone": "UTC",
+        "opening_hours": [],
+    }))
+    .unwrap();
+    let hall = services.venues.create_venue(venue).await.unwrap();
+    services
+        .venues
+        .add_tournament_venue(tournament.id, hall.id)
+        .await
+        .unwrap();
+    let mut courts = Vec::new();
+    for name in ["Court 1", "Court 2"] {
+        let court: NewCourt =
+            serde_json::from_value(json!({ "name": name, "kind": "court" })).unwrap();
+        courts.push(
+            services
+                .venues
+                .add_court(hall.id, court)
+                .await
+                .unwrap()
+                .unwrap(),
+        );
+    }
+    let court2 = courts.pop().unwrap();
+    let court1 = courts.pop().unwrap();
+    Seed {
+        tournament_id: tournament.id,
+        category_id: category.id,
+        court1,
+        court2,
+    }
+}
+
+async fn schedule(
+    services: &AppServices,
+    seed: &Seed,
+    court: &Court,
+    scheduled_date: DateTime<Utc>,
+    match_number: i32,
+    players: (Option<Uuid>, Option<Uuid>),
+) -> Uuid {
+    services
+        .matches
+        .create_match(
+            NewMatch {
+                tournament_category_id: seed.category_id,
+                participant1_team_id: None,
+                participant1_player_id: players.0,
+                participant1_partner_id: None,
+                participant2_team_id: None,
+                participant2_player_id: players.1,
+                participant2_partner_id: None,
+                match_type: MatchType::GroupStage,
+                round_number: Some(1),
+                match_number: Some(match_number),
+                scheduled_date,
+                venue: None,
+                court_number: None,
+                court_id: Some(court.id),
+                referee_name: None,
+                umpire_name: None,
+                notes: None,
+                metadata: None,
+            },
+            true,
+        )
+        .await
+        .unwrap()
+        .id
+}
+
+#[actix_web::test]
+async fn test_queue_order_and_desk_view() {
+    let repos = Repositories::in_memory();
+    let services = services(&repos);
+    let seed = seed(&repos, &services).await;
+    let soon = Utc::now() + Duration::minutes(30);
+    let third = schedule(&services, &seed, &seed.court1, soon, 3, (None, None)).await;
+    let second = schedule(&services, &seed, &seed.court1, soon, 2, (None, None)).await;
+    let first = schedule(
+        &services,
+        &seed,
+        &seed.court1,
+        soon - Duration::minutes(40),
+        1,
+        (None, None),
+    )
+    .await;
+    // Outside the queue's horizon
+    schedule(
+        &services,
+        &seed,
+        &seed.court1,
+        Utc::now() + Duration::days(3),
+        4,
+        (None, None),
+    )
+    .await;
+
+    let queue = services
+        .court_queue
+        .get_court_queue(seed.court1.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(queue.current.is_none());
+    let order: Vec<Uuid> = queue.queue.iter().map(|q| q.details.id).collect();
+    assert_eq!(order, vec![first, second, third]);
+    assert!(services
+        .court_queue
+        .get_court_queue(Uuid::new_v4())
+        .await
+        .unwrap()
+        .is_none());
+
+    // Starting the first match puts the second up next; completing it leaves the court free
+    services.matches.start_match(first).await.unwrap();
+    let desk = services
+        .court_queue
+        .get_desk(seed.tournament_id)
+        .await
+        .unwrap();
+    assert_eq!(desk.len(), 2);
+    let court1 = desk.iter().find(|c| c.court_id == seed.court1.id).unwrap();
+    assert_eq!(court1.current.as_ref().map(|q| q.details.id), Some(first));
+    assert_eq!(court1.next.as_ref().map(|q| q.details.id), Some(second));
+    assert_eq!(court1.waiting, 1);
+    let court2 = desk.iter().find(|c| c.court_id == seed.court2.id).unwrap();
+    assert!(court2.current.is_none() && court2.next.is_none());
+
+    services
+        .matches
+        .complete_match(first, 1, false)
+        .await
+        .unwrap();
+    let queue = services
+        .court_queue
+        .get_court_queue(seed.court1.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(queue.current.is_none());
+    assert_eq!(queue.queue[0].details.id, second);
+}
+
+#[actix_web::test]
+async fn test_call_to_court_notifies_and_tracks_presence() {
+    let repos = Repositories::in_memory();
+    let services = services(&repos);
+    let seed = seed(&repos, &services).await;
+    let soon = Utc::now() + Duration::minutes(10);
+    let players = (Some(player(&repos).await), Some(player(&repos).await));
+    let playing = schedule(&services, &seed, &seed.court1, soon, 1, (None, None)).await;
+    let waiting = schedule(&services, &seed, &seed.court1, soon, 2, (None, None)).await;
+    let called = schedule(&services, &seed, &seed.court2, soon, 3, players).await;
+
+    // A court that is still in use cannot take the next match
+    services.matches.start_match(playing).await.unwrap();
+    assert!(matches!(
+        services
+            .court_queue
+            .call_to_court(waiting, CallRequest::default())
+            .await,
+        Err(AppError::Conflict(_))
+    ));
+    assert!(matches!(
+        services
+            .court_queue
+            .call_to_court(
+                called,
+                CallRequest {
+                    respond_within_minutes: Some(0),
+                    max_calls: None,
+                }
+            )
+            .await,
+        Err(AppError::ValidationError(_))
+    ));
+
+    let call = services
+        .court_queue
+        .call_to_court(called, CallRequest::default())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(call.status, CallStatus::Called);
+    assert_eq!((call.call_count, call.max_calls), (1, 3));
+    let again = services
+        .court_queue
+        .call_to_court(called, CallRequest::default())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(again.call_count, 2);
+    assert!(again.respond_by >= call.respond_by);
+
+    let messages = repos
+        .outbox
+        .claim_due(10, Utc::now() + Duration::minutes(5))
+        .await
+        .unwrap();
+    assert_eq!(messages.len(), 2);
+    assert!(matches!(
+        messages[1].event().unwrap(),
+        DomainEvent::MatchCalled { call_number: 2, ref court_name, ref player_ids, .. }
+            if court_name == "Court 2" && player_ids.len() == 2
+    ));
+
+    assert!(services.court_queue.mark_present(called, 3).await.is_err());
+    let call = services
+        .court_queue
+        .mark_present(called, 1)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(call.status, CallStatus::Called);
+    let call = services
+        .court_queue
+        .mark_present(called, 2)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(call.status, CallStatus::Ready);
+
+    let withdrawn = services
+        .court_queue
+        .withdraw_call(called)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(withdrawn.status, CallStatus::Closed);
+    assert!(matches!(
+        services.court_queue.mark_present(called, 1).await,
+        Err(AppError::NotFound(_))
+    ));
+}
+
+#[actix_web::test]
+async fn test_no_shows_are_called_again_then_forfeited() {
+    let repos = Repositories::in_memory();
+    let services = services(&repos);
+    let seed = seed(&repos, &services).await;
+    let soon = Utc::now() + Duration::minutes(10);
+    let players = (Some(player(&repos).await), Some(player(&repos).await));
+    let match_id = schedule(&services, &seed, &seed.court1, soon, 1, players).await;
+    let request = CallRequest {
+        respond_within_minutes: Some(5),
+        max_calls: Some(2),
+    };
+    services
+        .court_queue
+        .call_to_court(match_id, request)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let now = Utc::now();
+    assert_eq!(
+        services.court_queue.escalate_no_shows(now).await.unwrap(),
+        0
+    );
+    let later = now + Duration::minutes(6);
+    assert_eq!(
+        services.court_queue.escalate_no_shows(later).await.unwrap(),
+        1
+    );
+    let call = repos
+        .court_calls
+        .find_by_match(match_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(call.call_count, 2);
+    assert!(call.is_final());
+    assert_eq!(call.respond_by, later + Duration::minutes(5));
+
+    services
+        .court_queue
+        .mark_present(match_id, 2)
+        .await
+        .unwrap();
+    let last = later + Duration::minutes(6);
+    assert_eq!(
+        services.court_queue.escalate_no_shows(last).await.unwrap(),
+        1
+    );
+    let call = repos
+        .court_calls
+        .find_by_match(match_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(call.status, CallStatus::Forfeited);
+    let m = services.matches.get_match(match_id).await.unwrap().unwrap();
+    assert_eq!(m.match_status, MatchStatus::Forfeited);
+    assert_eq!(m.winner_participant, Some(2));
+    assert!(m.notes.unwrap().starts_with("Walkover: participant 1"));
+    // Nothing left to escalate
+    assert_eq!(
+        services.court_queue.escalate_no_shows(last).await.unwrap(),
+        0
+    );
+}
@@ -0,0 +1,289 @@
+//! QR credentials: rendering codes, verifying registration passes at the desk, and issuing
+//! and admitting spectator tickets, run against the in-memory repositories.
+
+mod common;
+
+use chrono::{Duration, Utc};
+use serde_json::json;
+use uuid::Uuid;
+
+use server::domain::check_in::CheckInMethod;
+use server::domain::match_domain::{MatchType, NewMatch};
+use server::domain::participant::CreatePlayer;
+use server::domain::tournament::{
+    EditableTournamentRegistration, NewTournamentRegistration, RegistrationStatus,
+};
+use server::infra::api::state::AppServices;
+use server::infra::repositories::Repositories;
+use server::shared::jwt::{
+    generate_check_in_token, generate_ticket_token, validate_check_in_token, validate_ticket_token,
+};
+use server::shared::qr::{QrCode, QrFormat};
+use server::shared::AppError;
+
+use common::{services, set_tournament_rules};
+
+async fn seed_category(repos: &Repositories, rules: Option<serde_json::Value>) -> Uuid {
+    let category = common::seed_category(repos, None).await;
+    if let Some(rules) = rules {
+        set_tournament_rules(repos, &category, rules).await;
+    }
+    category.id
+}
+
+async fn seed_match(services: &AppServices, category_id: Uuid) -> Uuid {
+    services
+        .matches
+        .create_match(
+            NewMatch {
+                tournament_category_id: category_id,
+                participant1_team_id: None,
+                participant1_player_id: None,
+                participant1_partner_id: None,
+                participant2_team_id: None,
+                participant2_player_id: None,
+                participant2_partner_id: None,
+                match_type: MatchType::Final,
+                round_number: Some(1),
+                match_number: None,
+                scheduled_date: Utc::now() + Duration::hours(3),
+                venue: None,
+                court_number: None,
+                court_id: None,
+                referee_name: None,
+                umpire_name: None,
+                notes: None,
+                metadata: None,
+            },
+            true,
+        )
+        .await
+        .unwrap()
+        .id
+}
+
+/// Reads one copy of the format information back out of the symbol
+fn format_bits(qr: &QrCode, size: usize, second_copy: bool) -> u32 {
+    let module = |x: usize, y: usize| qr.is_dark(x, y) as u32;
+    let bits: Vec<u32> = if second_copy {
+        (0..8)
+            .map(|i| module(size - 1 - i, 8))
+            .chain((8..15).map(|i| module(8, size - 15 + i)))
+            .collect()
+    } else {
+        (0..=5)
+            .map(|i| module(8, i))
+            .chain([module(8, 7), module(8, 8), module(7, 8)])
+            .chain((9..15).map(|i| module(14 - i, 8)))
+            .collect()
+    };
+    bits.iter()
+        .enumerate()
+        .fold(0, |acc, (i, bit)| acc | (bit << i))
+}
+
+#[test]
+fn test_qr_code_renders_png_and_svg() {
+    let token = generate_check_in_token("secret", Uuid::new_v4(), Utc::now()).unwrap();
+    let qr = QrCode::encode(token.as_bytes()).unwrap();
+
+    let svg = String::from_utf8(qr.render(QrFormat::Svg, 1)).unwrap();
+    let side: usize = svg
+        .split("viewBox=\"0 0 ")
+        .nth(1)
+        .and_then(|rest| rest.split(' ').next())
+        .unwrap()
+        .parse()
+        .unwrap();
+    let size = side - 8;
+    assert_eq!((size - 17) % 4, 0);
+    assert!(size > 21, "a signed token needs more than version 1");
+
+    // Both copies of the format information agree and name error correction level M
+    let format = format_bits(&qr, size, false);
+    assert_eq!(format, format_bits(&qr, size, true));
+    assert_eq!((format ^ 0x5412) >> 13, 0);
+    // Finder pattern corners and the always-dark module
+    assert!(qr.is_dark(0, 0) && qr.is_dark(size - 1, 0) && qr.is_dark(0, size - 1));
+    assert!(qr.is_dark(8, size - 8));
+
+    let png = qr.render(QrFormat::Png, 4);
+    assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    assert_eq!(&png[12..16], b"IHDR");
+    let width = u32::from_be_bytes(png[16..20].try_into().unwrap());
+    assert_eq!(width as usize, side * 4);
+    assert_eq!(QrFormat::default().content_type(), "image/png");
+
+    let err = QrCode::encode(&[b'x'; 1000]).unwrap_err();
+    assert!(matches!(err, AppError::BadRequest(_)));
+}
+
+#[actix_web::test]
+async fn test_verifying_a_registration_checks_it_in() {
+    let repos = Repositories::in_memory();
+    let services = services(&repos);
+    let category_id = seed_category(&repos, None).await;
+    let player = repos
+        .players
+        .create(CreatePlayer {
+            name: "Ana Lee".to_string(),
+            user_id: Some(Uuid::new_v4()),
+        })
+        .await
+        .unwrap();
+    let registration = repos
+        .registrations
+        .create(NewTournamentRegistration {
+            tournament_category_id: category_id,
+            team_id: None,
+            player_id: Some(player.id),
+            partner_player_id: None,
+            notes: None,
+            metadata: None,
+        })
+        .await
+        .unwrap();
+
+    // The code's token names the registration and only verifies with the server's secret
+    let token = generate_check_in_token("secret", registration.id, Utc::now() + Duration::hours(1))
+        .unwrap();
+    let claims = validate_check_in_token("secret", &token).unwrap();
+    assert_eq!(claims.registration_id(), Some(registration.id));
+    assert!(validate_ticket_token("secret", &token).is_err());
+
+    // A pending entry cannot be checked in
+    let staff = Uuid::new_v4();
+    let err = services
+        .check_in
+        .verify_registration(staff, registration.id)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::ValidationError(_)));
+
+    repos
+        .registrations
+        .update(
+            registration.id,
+            EditableTournamentRegistration {
+                registration_status: Some(RegistrationStatus::Approved),
+                payment_status: None,
+                payment_amount: None,
+                payment_reference: None,
+                notes: None,
+                metadata: None,
+            },
+        )
+        .await
+        .unwrap();
+    let verified = services
+        .check_in
+        .verify_registration(staff, registration.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!verified.already_checked_in);
+    assert_eq!(verified.participant_name, "Ana Lee");
+    assert_eq!(verified.tournament_name, "Club Singles");
+    assert_eq!(verified.category_name, "Open Singles");
+    assert_eq!(
+        verified.registration.check_in_method,
+        Some(CheckInMethod::Qr)
+    );
+    assert_eq!(verified.registration.checked_in_by, Some(staff));
+
+    // Scanning again changes nothing and says so
+    let again = services
+        .check_in
+        .verify_registration(Uuid::new_v4(), registration.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(again.already_checked_in);
+    assert_eq!(again.registration.checked_in_by, Some(staff));
+}
+
+#[actix_web::test]
+async fn test_spectator_tickets_admit_once() {
+    let repos = Repositories::in_memory();
+    let services = services(&repos);
+    let spectator = Uuid::new_v4();
+
+    // Tickets are off unless the tournament's rules turn them on
+    let closed = seed_category(&repos, None).await;
+    let closed_match = seed_match(&services, closed).await;
+    let err = services
+        .tickets
+        .issue_ticket(spectator, closed_match)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::Forbidden(_)));
+
+    let open = seed_category(&repos, Some(json!({ "spectator_tickets": true }))).await;
+    let match_id = seed_match(&services, open).await;
+    let (ticket, expires_at) = services
+        .tickets
+        .issue_ticket(spectator, match_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(expires_at > Utc::now() + Duration::days(1));
+    assert!(services
+        .tickets
+        .issue_ticket(spectator, Uuid::new_v4())
+        .await
+        .unwrap()
+        .is_none());
+
+    let token = generate_ticket_token("secret", ticket.id, match_id, expires_at).unwrap();
+    let claims = validate_ticket_token("secret", &token).unwrap();
+    assert_eq!(claims.ticket_id(), Some(ticket.id));
+    assert_eq!(claims.match_id(), Some(match_id));
+    assert!(validate_check_in_token("secret", &token).is_err());
+
+    // Only the holder can fetch it
+    let err = services
+        .tickets
+        .get_ticket(Uuid::new_v4(), ticket.id)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::Forbidden(_)));
+    let mine = services.tickets.get_my_tickets(spectator).await.unwrap();
+    assert_eq!(mine.len(), 1);
+
+    // A token naming another match is refused
+    let staff = Uuid::new_v4();
+    let err = services
+        .tickets
+        .admit(staff, ticket.id, closed_match)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::BadRequest(_)));
+
+    let admission = services
+        .tickets
+        .admit(staff, ticket.id, match_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!admission.already_admitted);
+    assert_eq!(admission.ticket.admitted_by, Some(staff));
+    assert_eq!(admission.match_details.map(|m| m.id), Some(match_id));
+
+    let again = services
+        .tickets
+        .admit(Uuid::new_v4(), ticket.id, match_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(again.already_admitted);
+    assert_eq!(again.ticket.admitted_by, Some(staff));
+
+    // Deleting the match takes its tickets with it
+    services.matches.delete_match(match_id).await.unwrap();
+    assert!(services
+        .tickets
+        .get_my_tickets(spectator)
+        .await
+        .unwrap()
+        .is_empty());
+}
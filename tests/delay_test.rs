@@ -0,0 +1,261 @@
+//! Estimated start times, delays carried down court queues and running-late notices, run
+//! against the in-memory repositories.
+
+mod common;
+
+use chrono::{DateTime, Duration, Utc};
+use serde_json::json;
+use uuid::Uuid;
+
+use server::domain::match_domain::{Match, MatchStatus, MatchType, NewMatch};
+use server::domain::notification::NotificationType;
+use server::domain::participant::CreatePlayer;
+use server::domain::scheduling::{estimate_starts, historical_duration};
+use server::infra::repositories::Repositories;
+use server::shared::config::DelayConfig;
+
+use common::{at, services};
+
+/// A category whose matches are expected to take 45 minutes
+async fn seed_category(repos: &Repositories) -> Uuid {
+    common::seed_category(repos, Some(json!({ "match_duration_minutes": 45 })))
+        .await
+        .id
+}
+
+/// Two players with accounts, as (player id, user id) pairs
+async fn seed_players(repos: &Repositories, names: [&str; 2]) -> ((Uuid, Uuid), (Uuid, Uuid)) {
+    let mut seeded = Vec::new();
+    for name in names {
+        let user_id = Uuid::new_v4();
+        let player = repos
+            .players
+            .create(CreatePlayer {
+                name: name.to_string(),
+                user_id: Some(user_id),
+            })
+            .await
+            .unwrap();
+        seeded.push((player.id, user_id));
+    }
+    (seeded[0], seeded[1])
+}
+
+async fn seed_match(
+    repos: &Repositories,
+    category_id: Uuid,
+    players: (Uuid, Uuid),
+    court: &str,
+    scheduled_date: DateTime<Utc>,
+) -> Match {
+    repos
+        .matches
+        .create(NewMatch {
+            tournament_category_id: category_id,
+            participant1_team_id: None,
+            participant1_player_id: Some(players.0),
+            participant1_partner_id: None,
+            participant2_team_id: None,
+            participant2_player_id: Some(players.1),
+            participant2_partner_id: None,
+            match_type: MatchType::GroupStage,
+            round_number: Some(1),
+            match_number: None,
+            scheduled_date,
+            venue: Some("Sports Hall".to_string()),
+            court_number: Some(court.to_string()),
+            court_id: None,
+            referee_name: None,
+            umpire_name: None,
+            notes: None,
+            metadata: None,
+        })
+        .await
+        .unwrap()
+}
+
+#[actix_web::test]
+async fn test_overruns_carry_down_the_court_queue() {
+    let repos = Repositories::in_memory();
+    let category_id = seed_category(&repos).await;
+    let players = (Uuid::new_v4(), Uuid::new_v4());
+    let mut first = seed_match(&repos, category_id, players, "1", at(10, 0)).await;
+    let second = seed_match(&repos, category_id, players, "1", at(10, 45)).await;
+    let third = seed_match(&repos, category_id, players, "1", at(11, 30)).await;
+    let evening = seed_match(&repos, category_id, players, "1", at(16, 0)).await;
+    let other_court = seed_match(&repos, category_id, players, "2", at(10, 45)).await;
+    let mut cancelled = seed_match(&repos, category_id, players, "1", at(10, 30)).await;
+    cancelled.match_status = MatchStatus::Cancelled;
+
+    // The first match went on 20 minutes late and is still running at 11:10
+    first.match_status = MatchStatus::InProgress;
+    first.actual_start_date = Some(at(10, 20));
+    let now = at(11, 10);
+    let matches = vec![
+        first.clone(),
+        second.clone(),
+        third.clone(),
+        evening.clone(),
+        other_court.clone(),
+        cancelled.clone(),
+    ];
+    let estimates = estimate_starts(&matches, |_| Duration::minutes(45), now);
+    let estimate = |id: Uuid| estimates.iter().find(|e| e.match_id == id).copied();
+
+    assert_eq!(estimate(first.id).unwrap().delay_minutes, 20);
+    assert_eq!(
+        estimate(second.id).unwrap().estimated_start_date,
+        at(11, 10)
+    );
+    assert_eq!(estimate(third.id).unwrap().estimated_start_date, at(11, 55));
+    assert_eq!(estimate(third.id).unwrap().delay_minutes, 25);
+    // A gap in the schedule absorbs the delay; other courts are unaffected
+    assert_eq!(estimate(evening.id).unwrap().delay_minutes, 0);
+    assert_eq!(
+        estimate(other_court.id).unwrap().estimated_start_date,
+        at(11, 10)
+    );
+    assert!(estimate(cancelled.id).is_none());
+
+    // Running past its expected length holds the court until now
+    let late = estimate_starts(&matches, |_| Duration::minutes(45), at(11, 40));
+    let second_late = late.iter().find(|e| e.match_id == second.id).unwrap();
+    assert_eq!(second_late.estimated_start_date, at(11, 40));
+
+    // Actual lengths replace the configured one once enough matches have finished
+    let finished: Vec<Match> = [30, 50, 40]
+        .into_iter()
+        .map(|minutes| {
+            let mut m = first.clone();
+            m.match_status = MatchStatus::Completed;
+            m.actual_start_date = Some(at(9, 0));
+            m.actual_end_date = Some(at(9, 0) + Duration::minutes(minutes));
+            m
+        })
+        .collect();
+    assert_eq!(historical_duration(&finished[..2]), None);
+    assert_eq!(historical_duration(&finished), Some(Duration::minutes(40)));
+}
+
+#[actix_web::test]
+async fn test_schedule_shows_estimated_against_scheduled_starts() {
+    let repos = Repositories::in_memory();
+    let services = services(&repos);
+    let category_id = seed_category(&repos).await;
+    let players = (Uuid::new_v4(), Uuid::new_v4());
+    let now = Utc::now();
+    let running = seed_match(
+        &repos,
+        category_id,
+        players,
+        "1",
+        now - Duration::minutes(30),
+    )
+    .await;
+    let next = seed_match(
+        &repos,
+        category_id,
+        players,
+        "1",
+        now + Duration::minutes(5),
+    )
+    .await;
+    let later = seed_match(&repos, category_id, players, "1", now + Duration::hours(3)).await;
+    services.matches.start_match(running.id).await.unwrap();
+
+    let schedule = services.matches.get_match_schedule().await.unwrap();
+    let item = |id: Uuid| schedule.iter().find(|item| item.id == id).unwrap();
+    // The running match started 30 minutes late and holds the court for 45
+    let next_item = item(next.id);
+    let delay = next_item.delay_minutes.unwrap();
+    assert!((39..=40).contains(&delay), "delay was {}", delay);
+    assert!(next_item.estimated_start_date.unwrap() > next_item.scheduled_date);
+    assert_eq!(item(later.id).delay_minutes, Some(0));
+    assert_eq!(
+        item(later.id).estimated_start_date,
+        Some(item(later.id).scheduled_date)
+    );
+
+    let local = services
+        .matches
+        .localize_schedule(vec![next_item.clone()], Some(chrono_tz::Tz::UTC))
+        .await
+        .unwrap();
+    let json = serde_json::to_value(&local[0]).unwrap();
+    assert_eq!(json["delay_minutes"], delay);
+    assert_eq!(
+        json["local_estimated_start_date"],
+        next_item.estimated_start_date.unwrap().to_rfc3339()
+    );
+}
+
+#[actix_web::test]
+async fn test_delay_notices_go_out_once_per_threshold_step() {
+    let repos = Repositories::in_memory();
+    let services = services(&repos);
+    let category_id = seed_category(&repos).await;
+    let ((ana, ana_user), (ben, ben_user)) = seed_players(&repos, ["Ana Lee", "Ben Ortiz"]).await;
+    let ((cara, cara_user), (dan, _)) = seed_players(&repos, ["Cara Diaz", "Dan Wu"]).await;
+    let first = seed_match(&repos, category_id, (ana, ben), "3", at(10, 0)).await;
+    let second = seed_match(&repos, category_id, (cara, dan), "3", at(10, 45)).await;
+    seed_match(&repos, category_id, (ana, cara), "3", at(16, 0)).await;
+    let fan = Uuid::new_v4();
+    repos
+        .matches
+        .subscribe_to_match(second.id, fan, json!({ "all": true }))
+        .await
+        .unwrap();
+    let notifier = services.delay_notifier(&repos, DelayConfig::default());
+
+    // Nobody is waiting yet at 10:10
+    assert_eq!(notifier.scan(at(10, 10)).await.unwrap(), 0);
+    // At 10:20 the first match is 20 minutes late and pushes the second to 11:05
+    assert_eq!(notifier.scan(at(10, 20)).await.unwrap(), 5);
+    assert_eq!(notifier.scan(at(10, 25)).await.unwrap(), 0);
+    // Passing 30 minutes late is worth another notice
+    assert_eq!(notifier.scan(at(10, 30)).await.unwrap(), 5);
+
+    let sent = repos
+        .notifications
+        .get_by_user_id(cara_user, 50, 0)
+        .await
+        .unwrap();
+    assert_eq!(sent.len(), 2);
+    let notice = sent
+        .iter()
+        .find(|n| n.message.ends_with("(20 min late)."))
+        .unwrap();
+    assert_eq!(notice.notification_type, NotificationType::ScheduleDelay);
+    assert_eq!(notice.match_id, Some(second.id));
+    assert_eq!(notice.title, "Running late: Cara Diaz vs Dan Wu");
+    assert_eq!(
+        notice.message,
+        "Club Singles - Open Singles is now expected to start around Sat 2 May 11:05 UTC \
+         instead of Sat 2 May 10:45 UTC (20 min late)."
+    );
+    let ana_sent = repos
+        .notifications
+        .get_by_user_id(ana_user, 50, 0)
+        .await
+        .unwrap();
+    assert_eq!(ana_sent.len(), 2);
+    assert!(ana_sent.iter().all(|n| n.match_id == Some(first.id)));
+    assert_eq!(
+        repos
+            .notifications
+            .get_by_user_id(ben_user, 50, 0)
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+    assert_eq!(
+        repos
+            .notifications
+            .get_by_user_id(fan, 50, 0)
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+}
@@ -0,0 +1,258 @@
+//! Email notification channel, run against the in-memory repositories.
+
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde_json::json;
+use uuid::Uuid;
+
+use server::application::event_subscribers::EmailNotificationSubscriber;
+use server::application::OutboxDispatcher;
+use server::domain::notification::{
+    DeliveryChannel, DeliveryStatus, EmailSender, NewNotification, NotificationType, OutgoingEmail,
+};
+use server::domain::outbox::OutboxRepository;
+use server::domain::user::{NewUser, NewUserProfile};
+use server::infra::email::email_sender;
+use server::infra::repositories::Repositories;
+use server::shared::config::{EmailConfig, EmailTransport, OutboxConfig};
+use server::shared::AppError;
+
+use common::services;
+
+/// Keeps sent emails, or fails every send while `down` is set
+#[derive(Default)]
+struct RecordingSender {
+    sent: Mutex<Vec<OutgoingEmail>>,
+    down: Mutex<bool>,
+}
+
+#[async_trait]
+impl EmailSender for RecordingSender {
+    async fn send(&self, email: &OutgoingEmail) -> Result<(), AppError> {
+        if *self.down.lock() {
+            return Err(AppError::InternalError("connection refused".into()));
+        }
+        self.sent.lock().push(email.clone());
+        Ok(())
+    }
+}
+
+fn dispatcher(
+    repos: &Repositories,
+    sender: Arc<dyn EmailSender>,
+) -> OutboxDispatcher<dyn OutboxRepository> {
+    let config = OutboxConfig {
+        retry_base: Duration::ZERO,
+        ..OutboxConfig::default()
+    };
+    OutboxDispatcher::new(Arc::clone(&repos.outbox), config).subscribe(Arc::new(
+        EmailNotificationSubscriber::new(
+            Arc::clone(&repos.notifications),
+            Arc::clone(&repos.notification_deliveries),
+            Arc::clone(&repos.users),
+            Arc::clone(&repos.profiles),
+            sender,
+            None,
+        ),
+    ))
+}
+
+async fn seed_user(
+    repos: &Repositories,
+    language: Option<&str>,
+    preferences: Option<serde_json::Value>,
+) -> Uuid {
+    let user = repos
+        .users
+        .create(NewUser {
+            name: Some("Ana Lee".to_string()),
+            email: "ana@example.com".to_string(),
+            google_id: Uuid::new_v4().to_string(),
+        })
+        .await
+        .unwrap();
+    repos
+        .profiles
+        .create(NewUserProfile {
+            user_id: user.id,
+            bio: None,
+            avatar_url: None,
+            phone: None,
+            date_of_birth: None,
+            timezone: None,
+            language: language.map(str::to_string),
+            notification_preferences: preferences,
+            privacy_settings: None,
+            location: None,
+            website: None,
+            social_links: None,
+            preferences: None,
+            is_public: None,
+        })
+        .await
+        .unwrap();
+    user.id
+}
+
+fn reminder(user_id: Uuid) -> NewNotification {
+    NewNotification {
+        user_id,
+        title: "Court 3 at 10:30".to_string(),
+        message: "Your quarter-final starts at 10:30.".to_string(),
+        notification_type: NotificationType::MatchReminder,
+        tournament_id: None,
+        match_id: None,
+    }
+}
+
+#[actix_web::test]
+async fn test_notification_is_emailed_in_the_users_language() {
+    let repos = Repositories::in_memory();
+    let user_id = seed_user(&repos, Some("es-MX"), None).await;
+    let sender = Arc::new(RecordingSender::default());
+
+    let notification = services(&repos)
+        .notifications
+        .send_notification(reminder(user_id))
+        .await
+        .unwrap()
+        .unwrap();
+    let dispatcher = dispatcher(&repos, sender.clone());
+    assert_eq!(dispatcher.dispatch_batch().await.unwrap(), 1);
+    assert_eq!(dispatcher.dispatch_batch().await.unwrap(), 0);
+
+    let sent = sender.sent.lock().clone();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].to, "ana@example.com");
+    assert_eq!(sent[0].subject, "Court 3 at 10:30");
+    assert!(sent[0].body.starts_with("Hola Ana Lee:"));
+    assert!(sent[0].body.contains("Tienes un partido"));
+
+    let delivery = repos
+        .notification_deliveries
+        .find(notification.id, DeliveryChannel::Email)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(delivery.status, DeliveryStatus::Sent);
+    assert_eq!(delivery.attempts, 1);
+    assert!(delivery.delivered_at.is_some());
+}
+
+#[actix_web::test]
+async fn test_opted_out_types_are_skipped() {
+    let repos = Repositories::in_memory();
+    let preferences = json!({ "email": true, "types": { "match_reminder": { "email": false } } });
+    let user_id = seed_user(&repos, None, Some(preferences)).await;
+    let sender = Arc::new(RecordingSender::default());
+
+    let notifications = services(&repos)
+        .notifications
+        .send_bulk_notifications(vec![
+            reminder(user_id),
+            NewNotification {
+                notification_type: NotificationType::ResultPosted,
+                ..reminder(user_id)
+            },
+        ])
+        .await
+        .unwrap();
+    assert_eq!(
+        dispatcher(&repos, sender.clone())
+            .dispatch_batch()
+            .await
+            .unwrap(),
+        2
+    );
+
+    let sent = sender.sent.lock().clone();
+    assert_eq!(sent.len(), 1);
+    assert!(sent[0].body.contains("A match result has been posted."));
+    let skipped = repos
+        .notification_deliveries
+        .find_by_notification(notifications[0].id)
+        .await
+        .unwrap();
+    assert_eq!(skipped.len(), 1);
+    assert_eq!(skipped[0].status, DeliveryStatus::Skipped);
+}
+
+#[actix_web::test]
+async fn test_failed_sends_are_recorded_and_retried() {
+    let repos = Repositories::in_memory();
+    let user_id = seed_user(&repos, None, None).await;
+    let sender = Arc::new(RecordingSender::default());
+    *sender.down.lock() = true;
+
+    let notification = services(&repos)
+        .notifications
+        .send_notification(reminder(user_id))
+        .await
+        .unwrap()
+        .unwrap();
+    let dispatcher = dispatcher(&repos, sender.clone());
+    assert_eq!(dispatcher.dispatch_batch().await.unwrap(), 1);
+
+    let delivery = repos
+        .notification_deliveries
+        .find(notification.id, DeliveryChannel::Email)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(delivery.status, DeliveryStatus::Failed);
+    assert!(delivery.last_error.unwrap().contains("connection refused"));
+
+    *sender.down.lock() = false;
+    assert_eq!(dispatcher.dispatch_batch().await.unwrap(), 1);
+    let delivery = repos
+        .notification_deliveries
+        .find(notification.id, DeliveryChannel::Email)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(delivery.status, DeliveryStatus::Sent);
+    assert_eq!(delivery.attempts, 2);
+    assert_eq!(sender.sent.lock().len(), 1);
+
+    // Nothing is left to deliver
+    assert_eq!(dispatcher.dispatch_batch().await.unwrap(), 0);
+}
+
+#[actix_web::test]
+async fn test_file_transport_writes_eml_files() {
+    let dir = std::env::temp_dir().join(format!("email-sink-{}", Uuid::new_v4()));
+    let sender = email_sender(&EmailConfig {
+        transport: EmailTransport::File {
+            dir: dir.to_string_lossy().into_owned(),
+        },
+        from: "Tournaments <noreply@example.com>".to_string(),
+        unsubscribe_url: None,
+    })
+    .unwrap();
+
+    sender
+        .send(&OutgoingEmail {
+            to: "ana@example.com".to_string(),
+            to_name: Some("Ana Lee".to_string()),
+            subject: "Registration approved".to_string(),
+            body: "See you on court.".to_string(),
+            unsubscribe_url: Some("https://example.com/unsubscribe?token=abc".to_string()),
+        })
+        .await
+        .unwrap();
+
+    let files: Vec<_> = std::fs::read_dir(&dir).unwrap().collect();
+    assert_eq!(files.len(), 1);
+    let eml = std::fs::read_to_string(files[0].as_ref().unwrap().path()).unwrap();
+    assert!(eml.contains("To: \"Ana Lee\" <ana@example.com>"));
+    assert!(eml.contains("Subject: Registration approved"));
+    assert!(eml.contains("See you on court."));
+    assert!(eml.contains("List-Unsubscribe: <https://example.com/unsubscribe?token=abc>"));
+    assert!(eml.contains("List-Unsubscribe-Post: List-Unsubscribe=One-Click"));
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,250 @@
+//! Export format tests (CSV escaping, column selection, ZIP bundling, PDF output) and the
+//! printable PDFs, run against the in-memory repositories.
+
+mod common;
+
+use actix_web::test::{call_and_read_body, call_service, read_body, TestRequest};
+use uuid::Uuid;
+
+use server::domain::match_domain::{MatchType, NewMatch, NewMatchResult};
+use server::domain::tournament::{ExportEntity, ExportFormat, ExportRequest};
+use server::infra::repositories::Repositories;
+use server::shared::csv::{escape_field, CsvTable};
+use server::shared::pdf::{PdfBuilder, A4_PORTRAIT};
+use server::shared::zip::{crc32, ZipWriter};
+
+use common::{at, entrant, init_app, seed_category, services};
+
+#[test]
+fn test_csv_escapes_delimiters_quotes_and_newlines() {
+    assert_eq!(escape_field("plain"), "plain");
+    assert_eq!(escape_field("Smith, John"), "\"Smith, John\"");
+    assert_eq!(escape_field("The \"Rocket\""), "\"The \"\"Rocket\"\"\"");
+    assert_eq!(escape_field("line1\nline2"), "\"line1\nline2\"");
+    assert_eq!(escape_field(" padded"), "\" padded\"");
+}
+
+#[test]
+fn test_csv_table_renders_stable_headers_with_crlf() {
+    let mut table = CsvTable::new(&["id", "name", "score"]);
+    table.push_row(vec!["1".into(), "Ana, B".into(), "21-19".into()]);
+    table.push_row(vec!["2".into(), "Lee".into()]);
+
+    assert_eq!(
+        table.to_csv_string(),
+        "id,name,score\r\n1,\"Ana, B\",21-19\r\n2,Lee,\r\n"
+    );
+}
+
+#[test]
+fn test_csv_select_columns_reorders_and_rejects_unknown() {
+    let mut table = CsvTable::new(&["id", "name", "score"]);
+    table.push_row(vec!["1".into(), "Ana".into(), "21".into()]);
+
+    let selected = table
+        .select_columns(&["score".to_string(), "id".to_string()])
+        .unwrap();
+    assert_eq!(selected.to_csv_string(), "score,id\r\n21,1\r\n");
+
+    assert!(table.select_columns(&["nope".to_string()]).is_err());
+}
+
+#[test]
+fn test_zip_contains_entries_and_end_record() {
+    assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+
+    let mut zip = ZipWriter::new();
+    zip.add_file("registrations.csv", b"id\r\n");
+    zip.add_file("matches.csv", b"id\r\n");
+    let bytes = zip.finish();
+
+    assert_eq!(&bytes[0..4], &[0x50, 0x4b, 0x03, 0x04]);
+    let eocd = &bytes[bytes.len() - 22..];
+    assert_eq!(&eocd[0..4], &[0x50, 0x4b, 0x05, 0x06]);
+    assert_eq!(u16::from_le_bytes([eocd[10], eocd[11]]), 2);
+}
+
+#[test]
+fn test_pdf_document_structure_and_text_escaping() {
+    let mut pdf = PdfBuilder::new("Finals (Day 1)", A4_PORTRAIT);
+    pdf.heading("Court 1 \\ Centre (Main)");
+    let mut table = CsvTable::new(&["Time", "Match"]);
+    table.push_row(vec!["09:00".into(), "Ana vs Lee".into()]);
+    pdf.table(&table);
+    let bytes = pdf.finish();
+    let text = String::from_utf8_lossy(&bytes);
+
+    assert!(text.starts_with("%PDF-1.4"));
+    assert!(text.trim_end().ends_with("%%EOF"));
+    assert!(text.contains("(Court 1 \\\\ Centre \\(Main\\)) Tj"));
+    assert!(text.contains("/Count 1"));
+}
+
+/// Seeds a final Ana Lee won 11-7 11-9 on court 1, with standings; returns the
+/// tournament, category and match
+async fn seed_final(repos: &Repositories) -> (Uuid, Uuid, Uuid) {
+    let category = seed_category(repos, None).await;
+    let ana = entrant(repos, category.id, "Ana Lee").await;
+    let ben = entrant(repos, category.id, "Ben Ortiz").await;
+    let m = repos
+        .matches
+        .create(NewMatch {
+            tournament_category_id: category.id,
+            participant1_team_id: None,
+            participant1_player_id: Some(ana),
+            participant1_partner_id: None,
+            participant2_team_id: None,
+            participant2_player_id: Some(ben),
+            participant2_partner_id: None,
+            match_type: MatchType::Final,
+            round_number: Some(1),
+            match_number: Some(1),
+            scheduled_date: at(10, 0),
+            venue: None,
+            court_number: Some("1".to_string()),
+            court_id: None,
+            referee_name: None,
+            umpire_name: None,
+            notes: None,
+            metadata: None,
+        })
+        .await
+        .unwrap();
+    for (set, (p1, p2)) in [(11, 7), (11, 9)].into_iter().enumerate() {
+        repos
+            .match_results
+            .create(NewMatchResult {
+                match_id: m.id,
+                set_number: Some(set as i32 + 1),
+                participant1_score: Some(p1),
+                participant2_score: Some(p2),
+                period_number: None,
+                period_name: None,
+                scoring_data: None,
+                participant1_stats: None,
+                participant2_stats: None,
+            })
+            .await
+            .unwrap();
+    }
+    let services = services(repos);
+    repos.matches.start_match(m.id).await.unwrap();
+    services
+        .matches
+        .complete_match(m.id, 1, false)
+        .await
+        .unwrap();
+    services
+        .tournaments
+        .recalculate_standings(category.tournament_id)
+        .await
+        .unwrap();
+    (category.tournament_id, category.id, m.id)
+}
+
+#[actix_web::test]
+async fn test_matches_export_lists_sides_and_set_scores() {
+    let repos = Repositories::in_memory();
+    let (tournament_id, _, _) = seed_final(&repos).await;
+
+    let csv = services(&repos)
+        .exports
+        .export_tournament(
+            tournament_id,
+            ExportRequest {
+                format: ExportFormat::Csv,
+                entities: vec![ExportEntity::Matches],
+                columns: [
+                    "participant1_name",
+                    "participant2_name",
+                    "participant1_sets_won",
+                    "set_scores",
+                    "winner",
+                ]
+                .map(str::to_string)
+                .to_vec(),
+                timezone: None,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(csv.bytes).unwrap(),
+        "participant1_name,participant2_name,participant1_sets_won,set_scores,winner\r\n\
+         Ana Lee,Ben Ortiz,2,11-7 11-9,Ana Lee\r\n"
+    );
+}
+
+#[actix_web::test]
+async fn test_printable_pdfs_show_participants_and_rounds() {
+    let repos = Repositories::in_memory();
+    let (tournament_id, category_id, match_id) = seed_final(&repos).await;
+    let app = init_app!(repos);
+
+    let cases = [
+        (
+            format!("/brackets/category/{}/pdf", category_id),
+            vec![
+                "Club Singles - Open Singles bracket",
+                "Round 1",
+                "Ana Lee",
+                "Ben Ortiz",
+            ],
+        ),
+        (
+            format!("/tournaments/{}/schedule/pdf", tournament_id),
+            vec!["Saturday 02 May 2026 - Court 1", "Ana Lee", "Open Singles"],
+        ),
+        (
+            format!("/matches/{}/scoresheet?filled=true", match_id),
+            vec![
+                "Open Singles - Final",
+                "Round: 1",
+                "Winner: Ana Lee",
+                "Ben Ortiz",
+            ],
+        ),
+        (
+            format!("/matches/category/{}/scoresheets", category_id),
+            vec!["Open Singles - Final", "Round: 1", "Ana Lee", "Ben Ortiz"],
+        ),
+        (
+            format!("/tournaments/{}/standings/pdf", tournament_id),
+            vec![
+                "Club Singles - final standings",
+                "Open Singles",
+                "Ana Lee",
+                "Ben Ortiz",
+            ],
+        ),
+    ];
+    for (uri, expected) in cases {
+        let req = TestRequest::get().uri(&uri).to_request();
+        let resp = call_service(&app, req).await;
+        assert_eq!(resp.status(), 200, "{}", uri);
+        assert_eq!(
+            resp.headers().get("content-type").unwrap(),
+            "application/pdf",
+            "{}",
+            uri
+        );
+        let body = read_body(resp).await;
+        let text = String::from_utf8_lossy(&body);
+        assert!(text.starts_with("%PDF-1.4"), "{}", uri);
+        for line in expected {
+            assert!(text.contains(line), "{} lacks {:?}", uri, line);
+        }
+    }
+
+    // A blank scoresheet leaves the result for the referee
+    let req = TestRequest::get()
+        .uri(&format!("/matches/{}/scoresheet", match_id))
+        .to_request();
+    let body = call_and_read_body(&app, req).await;
+    assert!(!String::from_utf8_lossy(&body).contains("Winner: Ana Lee"));
+
+    let req = TestRequest::get()
+        .uri(&format!("/brackets/category/{}/pdf", Uuid::new_v4()))
+        .to_request();
+    assert_eq!(call_service(&app, req).await.status(), 404);
+}
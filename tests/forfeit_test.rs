@@ -0,0 +1,400 @@
+//! Walkovers, retirements and withdrawals, and how they count in standings and brackets,
+//! run against the in-memory repositories.
+
+mod common;
+
+use chrono::{Duration, Utc};
+use rust_decimal::Decimal;
+use serde_json::json;
+use uuid::Uuid;
+
+use server::domain::match_domain::{
+    ForfeitKind, MatchStatus, MatchType, NewMatch, NewMatchResult, RetirementRequest,
+    WalkoverRequest, WithdrawalRequest,
+};
+use server::domain::outbox::DomainEvent;
+use server::domain::tournament::{
+    bracket_nodes, BracketType, NewTournamentBracket, RegistrationStatus, TournamentStandings,
+};
+use server::infra::api::state::AppServices;
+use server::infra::repositories::Repositories;
+use server::shared::AppError;
+
+use common::{at, entrant, services};
+
+/// A table tennis category with the given constraints; returns (tournament, category)
+async fn seed_category(repos: &Repositories, constraints: serde_json::Value) -> (Uuid, Uuid) {
+    let category = common::seed_category(repos, Some(constraints)).await;
+    (category.tournament_id, category.id)
+}
+
+async fn schedule(
+    services: &AppServices,
+    category_id: Uuid,
+    match_type: MatchType,
+    hour: u32,
+    players: (Option<Uuid>, Option<Uuid>),
+) -> Uuid {
+    services
+        .matches
+        .create_match(
+            NewMatch {
+                tournament_category_id: category_id,
+                participant1_team_id: None,
+                participant1_player_id: players.0,
+                participant1_partner_id: None,
+                participant2_team_id: None,
+                participant2_player_id: players.1,
+                participant2_partner_id: None,
+                match_type,
+                round_number: Some(1),
+                match_number: None,
+                scheduled_date: at(hour, 0),
+                venue: None,
+                court_number: None,
+                court_id: None,
+                referee_name: None,
+                umpire_name: None,
+                notes: None,
+                metadata: None,
+            },
+            true,
+        )
+        .await
+        .unwrap()
+        .id
+}
+
+async fn set(repos: &Repositories, match_id: Uuid, number: i32, score: (i32, i32)) {
+    repos
+        .match_results
+        .create(NewMatchResult {
+            match_id,
+            set_number: Some(number),
+            participant1_score: Some(score.0),
+            participant2_score: Some(score.1),
+            period_number: None,
+            period_name: None,
+            scoring_data: None,
+            participant1_stats: None,
+            participant2_stats: None,
+        })
+        .await
+        .unwrap();
+}
+
+fn row(standings: &[TournamentStandings], participant_id: Uuid) -> &TournamentStandings {
+    standings
+        .iter()
+        .find(|s| s.participant_id == participant_id)
+        .unwrap()
+}
+
+#[actix_web::test]
+async fn test_walkovers_and_retirements_record_who_defaulted() {
+    let repos = Repositories::in_memory();
+    let services = services(&repos);
+    let (_, category_id) = seed_category(&repos, json!({})).await;
+    let ana = entrant(&repos, category_id, "Ana").await;
+    let ben = entrant(&repos, category_id, "Ben").await;
+    let unplayed = schedule(
+        &services,
+        category_id,
+        MatchType::GroupStage,
+        10,
+        (Some(ana), Some(ben)),
+    )
+    .await;
+    let started = schedule(
+        &services,
+        category_id,
+        MatchType::GroupStage,
+        11,
+        (Some(ben), Some(ana)),
+    )
+    .await;
+    services.matches.start_match(started).await.unwrap();
+
+    let retire = |side| RetirementRequest {
+        retiring_participant: side,
+        reason: "ankle injury".to_string(),
+    };
+    assert!(matches!(
+        services.matches.retire(unplayed, retire(1)).await,
+        Err(AppError::ValidationError(_))
+    ));
+    assert!(matches!(
+        services.matches.retire(started, retire(3)).await,
+        Err(AppError::ValidationError(_))
+    ));
+    let walkover = |side| WalkoverRequest {
+        defaulting_participant: side,
+        reason: "did not arrive".to_string(),
+    };
+    assert!(matches!(
+        services.matches.walkover(started, walkover(Some(1))).await,
+        Err(AppError::ValidationError(_))
+    ));
+    assert!(services
+        .matches
+        .walkover(Uuid::new_v4(), walkover(Some(1)))
+        .await
+        .unwrap()
+        .is_none());
+
+    let m = services
+        .matches
+        .walkover(unplayed, walkover(Some(2)))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(m.match_status, MatchStatus::Forfeited);
+    assert_eq!(m.forfeit_kind, Some(ForfeitKind::Walkover));
+    assert_eq!(m.defaulting_participant, Some(2));
+    assert_eq!(m.winner_participant, Some(1));
+    assert_eq!(m.notes.as_deref(), Some("Walkover: did not arrive"));
+
+    let m = services
+        .matches
+        .retire(started, retire(1))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(m.forfeit_kind, Some(ForfeitKind::Retirement));
+    assert_eq!(m.winner_participant, Some(2));
+
+    // Both results go out like completed matches
+    let messages = repos
+        .outbox
+        .claim_due(10, Utc::now() + Duration::minutes(5))
+        .await
+        .unwrap();
+    assert_eq!(messages.len(), 2);
+    assert!(messages.iter().all(|message| matches!(
+        message.event().unwrap(),
+        DomainEvent::MatchCompleted {
+            winner_participant: Some(_),
+            ..
+        }
+    )));
+}
+
+#[actix_web::test]
+async fn test_forfeits_score_by_category_rules() {
+    let repos = Repositories::in_memory();
+    let services = services(&repos);
+    let (tournament_id, category_id) = seed_category(
+        &repos,
+        json!({
+            "match_duration_minutes": 30,
+            "points_win": 2,
+            "points_loss": 1,
+            "forfeits": { "walkover_sets": 3, "walkover_games": 33, "defaulter_points": 0 },
+        }),
+    )
+    .await;
+    let ana = entrant(&repos, category_id, "Ana").await;
+    let ben = entrant(&repos, category_id, "Ben").await;
+    let cai = entrant(&repos, category_id, "Cai").await;
+    let group = MatchType::GroupStage;
+
+    // Ana beats Ben 2-1 on the table
+    let played = schedule(&services, category_id, group, 9, (Some(ana), Some(ben))).await;
+    set(&repos, played, 1, (11, 7)).await;
+    set(&repos, played, 2, (9, 11)).await;
+    set(&repos, played, 3, (11, 4)).await;
+    services
+        .matches
+        .complete_match(played, 1, false)
+        .await
+        .unwrap();
+    // Cai does not turn up against Ana
+    let walkover = schedule(&services, category_id, group, 10, (Some(cai), Some(ana))).await;
+    services
+        .matches
+        .walkover(
+            walkover,
+            WalkoverRequest {
+                defaulting_participant: Some(1),
+                reason: "no show".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+    // Ben retires a set down against Cai; the games played stand
+    let retired = schedule(&services, category_id, group, 11, (Some(ben), Some(cai))).await;
+    services.matches.start_match(retired).await.unwrap();
+    set(&repos, retired, 1, (6, 11)).await;
+    set(&repos, retired, 2, (3, 2)).await;
+    services
+        .matches
+        .retire(
+            retired,
+            RetirementRequest {
+                retiring_participant: 1,
+                reason: "cramp".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+
+    let standings = services
+        .tournaments
+        .recalculate_standings(tournament_id)
+        .await
+        .unwrap();
+    assert_eq!(standings.len(), 3);
+    let ana_row = row(&standings, ana);
+    assert_eq!(ana_row.position, 1);
+    assert_eq!(ana_row.points, Decimal::from(4));
+    assert_eq!((ana_row.matches_won, ana_row.matches_lost), (2, 0));
+    assert_eq!((ana_row.sets_won, ana_row.sets_lost), (5, 1));
+    assert_eq!((ana_row.games_won, ana_row.games_lost), (64, 22));
+    let cai_row = row(&standings, cai);
+    assert_eq!(cai_row.position, 2);
+    // A win, and no points for the walkover Cai gave away
+    assert_eq!(cai_row.points, Decimal::from(2));
+    assert_eq!((cai_row.sets_won, cai_row.sets_lost), (1, 4));
+    let ben_row = row(&standings, ben);
+    assert_eq!(ben_row.position, 3);
+    // The loss on the table earns a point, the retirement does not
+    assert_eq!(ben_row.points, Decimal::from(1));
+    assert_eq!(ben_row.matches_played, 2);
+    assert_eq!((ben_row.sets_won, ben_row.sets_lost), (2, 3));
+}
+
+#[actix_web::test]
+async fn test_withdrawal_voids_results_and_advances_bracket() {
+    let repos = Repositories::in_memory();
+    let services = services(&repos);
+    let (tournament_id, category_id) =
+        seed_category(&repos, json!({ "forfeits": { "withdrawal": "void_all" } })).await;
+    let ana = entrant(&repos, category_id, "Ana").await;
+    let ben = entrant(&repos, category_id, "Ben").await;
+    let cai = entrant(&repos, category_id, "Cai").await;
+    let dev = entrant(&repos, category_id, "Dev").await;
+    let group = MatchType::GroupStage;
+
+    let played = schedule(&services, category_id, group, 9, (Some(ben), Some(ana))).await;
+    services
+        .matches
+        .complete_match(played, 1, false)
+        .await
+        .unwrap();
+    let semi1 = schedule(
+        &services,
+        category_id,
+        MatchType::SemiFinal,
+        12,
+        (Some(ana), Some(cai)),
+    )
+    .await;
+    let semi2 = schedule(
+        &services,
+        category_id,
+        MatchType::SemiFinal,
+        13,
+        (Some(ben), Some(dev)),
+    )
+    .await;
+    let final_match = schedule(&services, category_id, MatchType::Final, 16, (None, None)).await;
+    services
+        .tournaments
+        .create_bracket(NewTournamentBracket {
+            tournament_id,
+            category_id: Some(category_id),
+            bracket_type: BracketType::SingleElimination,
+            total_rounds: 2,
+            bracket_data: Some(json!({
+                "nodes": [
+                    { "id": "sf1", "round": 1, "position": 0, "match_id": semi1,
+                      "participant1_id": ana, "participant1_name": "Ana",
+                      "participant2_id": cai, "participant2_name": "Cai",
+                      "next_match_id": "f" },
+                    { "id": "sf2", "round": 1, "position": 1, "match_id": semi2,
+                      "participant1_id": ben, "participant1_name": "Ben",
+                      "participant2_id": dev, "participant2_name": "Dev",
+                      "next_match_id": "f" },
+                    { "id": "f", "round": 2, "position": 0, "match_id": final_match },
+                ],
+            })),
+            settings: None,
+        })
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        services
+            .matches
+            .withdraw_participant(
+                category_id,
+                WithdrawalRequest {
+                    participant_id: Uuid::new_v4(),
+                    reason: "ill".to_string(),
+                },
+            )
+            .await,
+        Err(AppError::NotFound(_))
+    ));
+    let forfeited = services
+        .matches
+        .withdraw_participant(
+            category_id,
+            WithdrawalRequest {
+                participant_id: ben,
+                reason: "ill".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(forfeited.len(), 1);
+    assert_eq!(forfeited[0].id, semi2);
+    assert_eq!(forfeited[0].defaulting_participant, Some(1));
+    let registrations = repos
+        .registrations
+        .get_by_tournament_category(category_id)
+        .await
+        .unwrap();
+    let ben_registration = registrations
+        .iter()
+        .find(|r| r.player_id == Some(ben))
+        .unwrap();
+    assert_eq!(
+        ben_registration.registration_status,
+        RegistrationStatus::Withdrawn
+    );
+
+    // Dev goes through to the final, in the second slot
+    let final_match = services
+        .matches
+        .get_match(final_match)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(final_match.participant1_player_id, None);
+    assert_eq!(final_match.participant2_player_id, Some(dev));
+    let bracket = services
+        .tournaments
+        .get_bracket_by_category(category_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let nodes = bracket_nodes(&bracket.bracket_data.unwrap());
+    assert_eq!(nodes[1].winner_id, Some(dev));
+    assert_eq!(nodes[2].participant2_id, Some(dev));
+    assert_eq!(nodes[2].participant2_name.as_deref(), Some("Dev"));
+
+    // Ben's win over Ana no longer counts, and Ben drops to the bottom
+    let standings = services
+        .tournaments
+        .recalculate_standings(tournament_id)
+        .await
+        .unwrap();
+    let ana_row = row(&standings, ana);
+    assert_eq!(ana_row.matches_played, 0);
+    let ben_row = row(&standings, ben);
+    assert_eq!(ben_row.matches_played, 0);
+    assert!(ben_row.is_eliminated);
+    assert_eq!(ben_row.elimination_round.as_deref(), Some("withdrawn"));
+    assert_eq!(ben_row.position, standings.len() as i32);
+}
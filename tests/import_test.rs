@@ -0,0 +1,144 @@
+//! Import sheet parsing tests (CSV/XLSX reading and entrant column mapping).
+
+use server::domain::import::EntrantRow;
+use server::shared::csv::parse_csv;
+use server::shared::xlsx::{is_zip, read_first_sheet};
+use server::shared::zip::ZipWriter;
+use server::shared::AppError;
+
+#[test]
+fn test_parse_csv_handles_quotes_bom_and_semicolons() {
+    let rows = parse_csv(
+        "\u{feff}name,notes\r\n\"Smith, Ana\",\"said \"\"hi\"\"\nthen left\"\r\n\r\nLee,\n",
+    );
+    assert_eq!(
+        rows,
+        vec![
+            vec!["name".to_string(), "notes".to_string()],
+            vec![
+                "Smith, Ana".to_string(),
+                "said \"hi\"\nthen left".to_string()
+            ],
+            vec!["Lee".to_string(), String::new()],
+        ]
+    );
+
+    let rows = parse_csv("name;team\nAna;Reds");
+    assert_eq!(rows[1], vec!["Ana".to_string(), "Reds".to_string()]);
+}
+
+#[test]
+fn test_read_first_sheet_resolves_shared_and_inline_strings() {
+    let mut zip = ZipWriter::new();
+    zip.add_file(
+        "xl/workbook.xml",
+        br#"<workbook xmlns:r="r"><sheets><sheet name="Entrants" sheetId="1" r:id="rId7"/></sheets></workbook>"#,
+    );
+    zip.add_file(
+        "xl/_rels/workbook.xml.rels",
+        br#"<Relationships><Relationship Id="rId7" Target="worksheets/entrants.xml"/></Relationships>"#,
+    );
+    zip.add_file(
+        "xl/sharedStrings.xml",
+        br#"<sst><si><t>player_name</t></si><si><r><t>Ana </t></r><r><t>&amp; Co</t></r></si></sst>"#,
+    );
+    zip.add_file(
+        "xl/worksheets/entrants.xml",
+        br#"<worksheet><sheetData>
+            <row r="1"><c r="A1" t="s"><v>0</v></c><c r="C1" t="inlineStr"><is><t>jersey</t></is></c></row>
+            <row r="2"><c r="A2" t="s"><v>1</v></c><c r="C2"><v>7</v></c></row>
+        </sheetData></worksheet>"#,
+    );
+    let bytes = zip.finish();
+
+    assert!(is_zip(&bytes));
+    let rows = read_first_sheet(&bytes).unwrap();
+    assert_eq!(rows[0], vec!["player_name", "", "jersey"]);
+    assert_eq!(rows[1], vec!["Ana & Co", "", "7"]);
+}
+
+fn single_sheet(sheet: &str) -> Vec<u8> {
+    let mut zip = ZipWriter::new();
+    zip.add_file("xl/worksheets/sheet1.xml", sheet.as_bytes());
+    zip.finish()
+}
+
+#[test]
+fn test_read_first_sheet_rejects_columns_past_xfd() {
+    let last = single_sheet(
+        r#"<worksheet><sheetData><row r="1"><c r="XFD1"><v>1</v></c></row></sheetData></worksheet>"#,
+    );
+    assert_eq!(read_first_sheet(&last).unwrap()[0].len(), 16_384);
+
+    let oversized = single_sheet(
+        r#"<worksheet><sheetData><row r="1"><c r="ZZZZZZ1"><v>1</v></c></row></sheetData></worksheet>"#,
+    );
+    assert!(matches!(
+        read_first_sheet(&oversized),
+        Err(AppError::BadRequest(_))
+    ));
+}
+
+#[test]
+fn test_read_first_sheet_rejects_overflowing_references() {
+    let reference = format!("{}1", "Z".repeat(40));
+    let sheet = single_sheet(&format!(
+        r#"<worksheet><sheetData><row r="1"><c r="{}"><v>1</v></c></row></sheetData></worksheet>"#,
+        reference
+    ));
+    assert!(matches!(
+        read_first_sheet(&sheet),
+        Err(AppError::BadRequest(_))
+    ));
+}
+
+#[test]
+fn test_entrant_rows_map_aliases_and_collect_cell_errors() {
+    let sheet = vec![
+        vec![
+            "Player Name".into(),
+            "Email".into(),
+            "Team".into(),
+            "Jersey".into(),
+            "Captain".into(),
+            "ignored".into(),
+        ],
+        vec![
+            "Ana".into(),
+            "ana@example.com".into(),
+            "Reds".into(),
+            "7.0".into(),
+            "yes".into(),
+            "x".into(),
+        ],
+        vec![
+            "".into(),
+            "".into(),
+            "".into(),
+            "".into(),
+            "".into(),
+            "".into(),
+        ],
+        vec![
+            "Lee".into(),
+            "".into(),
+            "Reds".into(),
+            "seven".into(),
+            "maybe".into(),
+        ],
+    ];
+
+    let rows = EntrantRow::from_sheet(sheet).unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].row, 2);
+    assert_eq!(rows[0].player_email.as_deref(), Some("ana@example.com"));
+    assert_eq!(rows[0].jersey_number, Some(7));
+    assert_eq!(rows[0].is_captain, Some(true));
+    assert!(rows[0].errors.is_empty());
+
+    assert_eq!(rows[1].row, 4);
+    assert_eq!(rows[1].errors.len(), 2);
+
+    let missing = EntrantRow::from_sheet(vec![vec!["team".into()]]);
+    assert!(missing.is_err());
+}
@@ -0,0 +1,441 @@
+//! Live scoring rules and persistence, run against the in-memory repositories.
+
+mod common;
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{TimeZone, Utc};
+use parking_lot::Mutex;
+use uuid::Uuid;
+
+use server::domain::match_domain::scoring::{CardColor, FaultKind, ServeType};
+use server::domain::match_domain::{
+    EditableMatchResult, MatchType, NewMatch, ScoringAction, ScoringLog, ScoringRules,
+};
+use server::domain::participant::CreatePlayer;
+use server::domain::realtime::{EventPublisher, RealtimeEvent};
+use server::domain::tournament::{NewTournamentCategory, SportType, TeamComposition};
+use server::infra::api::state::AppServices;
+use server::infra::repositories::Repositories;
+use server::shared::AppError;
+
+use common::new_tournament;
+
+/// Keeps every published event for inspection
+#[derive(Default)]
+struct RecordingPublisher {
+    events: Mutex<Vec<RealtimeEvent>>,
+}
+
+#[async_trait]
+impl EventPublisher for RecordingPublisher {
+    async fn publish(&self, event: RealtimeEvent) {
+        self.events.lock().push(event);
+    }
+}
+
+fn point(participant: i32) -> ScoringAction {
+    ScoringAction::PointWon {
+        participant,
+        server: None,
+        serve_type: None,
+        fault: None,
+    }
+}
+
+/// Seeds a started table tennis match
+async fn seed(repos: &Repositories) -> Uuid {
+    let tournament = repos
+        .tournaments
+        .create(new_tournament(
+            "Club Singles",
+            SportType::TableTennis,
+            Utc.with_ymd_and_hms(2026, 5, 1, 9, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 5, 2, 18, 0, 0).unwrap(),
+        ))
+        .await
+        .unwrap();
+    let category = repos
+        .categories
+        .create(NewTournamentCategory {
+            tournament_id: tournament.id,
+            name: "Open Singles".to_string(),
+            description: None,
+            team_composition: TeamComposition::Singles,
+            min_participants: None,
+            max_participants: None,
+            entry_fee: None,
+            prize_distribution: None,
+            rules: None,
+            constraints: None,
+        })
+        .await
+        .unwrap();
+    let mut players = Vec::new();
+    for name in ["Ana Lee", "Ben Ortiz"] {
+        let player = repos
+            .players
+            .create(CreatePlayer {
+                name: name.to_string(),
+                user_id: None,
+            })
+            .await
+            .unwrap();
+        players.push(player.id);
+    }
+    let m = repos
+        .matches
+        .create(NewMatch {
+            tournament_category_id: category.id,
+            participant1_team_id: None,
+            participant1_player_id: Some(players[0]),
+            participant1_partner_id: None,
+            participant2_team_id: None,
+            participant2_player_id: Some(players[1]),
+            participant2_partner_id: None,
+            match_type: MatchType::Final,
+            round_number: None,
+            match_number: None,
+            scheduled_date: Utc.with_ymd_and_hms(2026, 5, 2, 15, 0, 0).unwrap(),
+            venue: None,
+            court_number: None,
+            court_id: None,
+            referee_name: None,
+            umpire_name: None,
+            notes: None,
+            metadata: None,
+        })
+        .await
+        .unwrap();
+    repos.matches.start_match(m.id).await.unwrap();
+    m.id
+}
+
+#[test]
+fn test_games_need_a_two_point_lead() {
+    let rules = ScoringRules::for_sport(SportType::TableTennis).unwrap();
+    let mut log = ScoringLog::default();
+    for _ in 0..10 {
+        log.record(&rules, point(1)).unwrap();
+        log.record(&rules, point(2)).unwrap();
+    }
+    let (_, score) = log.record(&rules, point(1)).unwrap();
+    assert_eq!(score.games[0].winner, None);
+
+    let (_, score) = log.record(&rules, point(1)).unwrap();
+    assert_eq!(score.games[0].winner, Some(1));
+    assert_eq!(score.participant1_games, 1);
+    assert_eq!(score.game_number(), 2);
+
+    // Undoing the winning point reopens the game
+    let (_, score) = log.record(&rules, ScoringAction::Undo).unwrap();
+    assert_eq!(score.games.len(), 1);
+    assert_eq!(
+        (score.games[0].participant1, score.games[0].participant2),
+        (11, 10)
+    );
+    assert_eq!(score.sequence, 23);
+}
+
+#[test]
+fn test_rules_reject_invalid_actions() {
+    let rules = ScoringRules::for_sport(SportType::TableTennis).unwrap();
+    let mut log = ScoringLog::default();
+    assert!(log.record(&rules, ScoringAction::Undo).is_err());
+    assert!(log.record(&rules, point(3)).is_err());
+
+    log.record(&rules, ScoringAction::Timeout { participant: 2 })
+        .unwrap();
+    assert!(log
+        .record(&rules, ScoringAction::Timeout { participant: 2 })
+        .is_err());
+    assert_eq!(log.entries.len(), 1);
+
+    for _ in 0..33 {
+        log.record(&rules, point(1)).unwrap();
+    }
+    let score = log.score(&rules).unwrap();
+    assert_eq!(score.winner, Some(1));
+    assert!(log.record(&rules, point(2)).is_err());
+    assert!(ScoringRules::for_sport(SportType::Chess).is_none());
+}
+
+#[test]
+fn test_corrections_and_cards_are_replayed() {
+    let rules = ScoringRules::for_sport(SportType::Badminton).unwrap();
+    let mut log = ScoringLog::default();
+    log.record(
+        &rules,
+        ScoringAction::PointWon {
+            participant: 1,
+            server: Some(1),
+            serve_type: Some(ServeType::Short),
+            fault: None,
+        },
+    )
+    .unwrap();
+    log.record(
+        &rules,
+        ScoringAction::PointWon {
+            participant: 1,
+            server: Some(1),
+            serve_type: None,
+            fault: Some(FaultKind::Net),
+        },
+    )
+    .unwrap();
+    // The second rally went to the other side
+    let (_, score) = log
+        .record(
+            &rules,
+            ScoringAction::Correction {
+                sequence: 2,
+                action: Box::new(ScoringAction::PointWon {
+                    participant: 2,
+                    server: Some(1),
+                    serve_type: None,
+                    fault: Some(FaultKind::Net),
+                }),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        (score.games[0].participant1, score.games[0].participant2),
+        (1, 1)
+    );
+
+    let (_, score) = log
+        .record(
+            &rules,
+            ScoringAction::Card {
+                participant: 1,
+                card: CardColor::Red,
+                player_id: None,
+                reason: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(score.games[0].participant2, 2);
+    assert!(log
+        .record(
+            &rules,
+            ScoringAction::Correction {
+                sequence: 9,
+                action: Box::new(ScoringAction::Let),
+            },
+        )
+        .is_err());
+
+    let stats = log.rally_stats(&rules).unwrap();
+    assert_eq!(stats.rallies, 2);
+    assert_eq!(stats.participant1.serves, 2);
+    assert_eq!(stats.participant1.points_won_on_serve, 1);
+    assert_eq!(stats.participant1.faults.get(&FaultKind::Net), Some(&1));
+    assert_eq!(stats.participant1.red_cards, 1);
+    assert_eq!(stats.participant2.longest_run, 2);
+
+    // Undoing the correction's successor, then the correction, restores the original call
+    log.record(&rules, ScoringAction::Undo).unwrap();
+    let (_, score) = log.record(&rules, ScoringAction::Undo).unwrap();
+    assert_eq!(
+        (score.games[0].participant1, score.games[0].participant2),
+        (2, 0)
+    );
+}
+
+#[actix_web::test]
+async fn test_actions_are_persisted_per_game() {
+    let repos = Repositories::in_memory();
+    let match_id = seed(&repos).await;
+    let events = Arc::new(RecordingPublisher::default());
+    let services = AppServices::new(&repos, events.clone());
+
+    for _ in 0..11 {
+        services
+            .matches
+            .record_scoring_action(match_id, point(2), None)
+            .await
+            .unwrap();
+    }
+    let update = services
+        .matches
+        .record_scoring_action(match_id, point(1), Some(11))
+        .await
+        .unwrap();
+    assert_eq!(update.entry.sequence, 12);
+    assert_eq!(update.entry.game_number, 2);
+
+    let stale = services
+        .matches
+        .record_scoring_action(match_id, point(1), Some(11))
+        .await;
+    assert!(matches!(stale, Err(AppError::Conflict(_))));
+    // A sequence number already taken for the match is refused and nothing is saved
+    let game2 = repos.match_results.find_by_set(match_id, 2).await.unwrap();
+    assert!(repos
+        .match_results
+        .claim_scoring_sequence(match_id, 13, game2[0].id)
+        .await
+        .unwrap());
+    let taken = services
+        .matches
+        .record_scoring_action(match_id, point(1), None)
+        .await;
+    assert!(matches!(taken, Err(AppError::Conflict(_))));
+
+    // Every accepted action is published, a rejected one is not
+    let published = events.events.lock().clone();
+    assert_eq!(published.len(), 12);
+    assert!(matches!(
+        published.last(),
+        Some(RealtimeEvent::ScoreUpdate { match_id: id, sequence: 12, .. }) if *id == match_id
+    ));
+
+    let mut results = repos.match_results.find_by_match(match_id).await.unwrap();
+    results.sort_by_key(|r| r.set_number);
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].participant2_score, Some(11));
+    assert_eq!(results[1].participant1_score, Some(1));
+
+    let score = services.matches.get_live_score(match_id).await.unwrap();
+    assert_eq!(score.sequence, 12);
+    assert_eq!(score.participant2_games, 1);
+
+    // Game scores belong to the log, not to manual edits
+    let edit = services
+        .matches
+        .update_match_result(
+            results[0].id,
+            EditableMatchResult {
+                participant1_score: Some(11),
+                participant2_score: Some(0),
+                scoring_data: None,
+                participant1_stats: None,
+                participant2_stats: None,
+            },
+        )
+        .await;
+    assert!(matches!(edit, Err(AppError::ValidationError(_))));
+
+    let analytics = services
+        .matches
+        .get_match_analytics(match_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(analytics.sets_played, Some(2));
+    assert_eq!(analytics.participant2_score.unwrap()["points"], 11);
+    assert_eq!(analytics.rally_stats.unwrap()["rallies"], 12);
+}
+
+#[actix_web::test]
+async fn test_only_the_organizer_may_score() {
+    let repos = Repositories::in_memory();
+    let match_id = seed(&repos).await;
+    let services = AppServices::new(&repos, Arc::new(RecordingPublisher::default()));
+    let m = repos.matches.find_by_id(match_id).await.unwrap().unwrap();
+    let category = repos
+        .categories
+        .get_by_id(m.tournament_category_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let tournament = repos
+        .tournaments
+        .get_by_id(category.tournament_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    services
+        .matches
+        .ensure_can_score(tournament.organizer_id, match_id)
+        .await
+        .unwrap();
+    assert!(matches!(
+        services
+            .matches
+            .ensure_can_score(Uuid::new_v4(), match_id)
+            .await,
+        Err(AppError::Forbidden(_))
+    ));
+    assert!(matches!(
+        services
+            .matches
+            .ensure_can_score(tournament.organizer_id, Uuid::new_v4())
+            .await,
+        Err(AppError::NotFound(_))
+    ));
+}
+
+#[actix_web::test]
+async fn test_assigned_scorekeepers_may_score() {
+    let repos = Repositories::in_memory();
+    let match_id = seed(&repos).await;
+    let services = AppServices::new(&repos, Arc::new(RecordingPublisher::default()));
+    let m = repos.matches.find_by_id(match_id).await.unwrap().unwrap();
+    let category = repos
+        .categories
+        .get_by_id(m.tournament_category_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let tournament = repos
+        .tournaments
+        .get_by_id(category.tournament_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let scorekeeper = Uuid::new_v4();
+
+    // Only the organizer hands out scoring rights
+    assert!(matches!(
+        services
+            .tournaments
+            .add_scorekeeper(scorekeeper, tournament.id, scorekeeper)
+            .await,
+        Err(AppError::Forbidden(_))
+    ));
+    services
+        .tournaments
+        .add_scorekeeper(tournament.organizer_id, tournament.id, scorekeeper)
+        .await
+        .unwrap();
+    services
+        .tournaments
+        .add_scorekeeper(tournament.organizer_id, tournament.id, scorekeeper)
+        .await
+        .unwrap();
+    assert_eq!(
+        services
+            .tournaments
+            .get_scorekeepers(tournament.organizer_id, tournament.id)
+            .await
+            .unwrap(),
+        vec![scorekeeper]
+    );
+    services
+        .matches
+        .ensure_can_score(scorekeeper, match_id)
+        .await
+        .unwrap();
+
+    assert!(services
+        .tournaments
+        .remove_scorekeeper(tournament.organizer_id, tournament.id, scorekeeper)
+        .await
+        .unwrap());
+    assert!(!services
+        .tournaments
+        .remove_scorekeeper(tournament.organizer_id, tournament.id, scorekeeper)
+        .await
+        .unwrap());
+    assert!(matches!(
+        services
+            .matches
+            .ensure_can_score(scorekeeper, match_id)
+            .await,
+        Err(AppError::Forbidden(_))
+    ));
+}
@@ -1,14 +1,99 @@
-//! Match API integration tests.
-//!
-//! These tests previously targeted the legacy API (config::DbPool, models::match_model, routes::match_routes).
-//! They have been removed as part of legacy cleanup. Replace with integration tests against the
-//! DDD API (server::infra::api::api_routes, server::domain::match_domain::*, service app_data) when needed.
-
-#[cfg(test)]
-mod tests {
-    #[actix_web::test]
-    async fn test_match_api_placeholder() {
-        // Placeholder until DDD API integration tests are added.
-        assert!(true);
-    }
+//! Match API integration tests, run against the in-memory repositories.
+
+mod common;
+
+use actix_web::test;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use server::infra::repositories::Repositories;
+
+use common::{entrant, init_app, seed_category};
+
+/// Seeds a singles category with two entrants
+async fn seed(repos: &Repositories) -> (Uuid, Uuid, Uuid) {
+    let category_id = seed_category(repos, None).await.id;
+    let player1 = entrant(repos, category_id, "Ana Lee").await;
+    let player2 = entrant(repos, category_id, "Ben Ortiz").await;
+    (category_id, player1, player2)
+}
+
+#[actix_web::test]
+async fn test_match_lifecycle() {
+    let repos = Repositories::in_memory();
+    let (category_id, player1, player2) = seed(&repos).await;
+    let app = init_app!(repos);
+
+    let req = test::TestRequest::post()
+        .uri("/matches")
+        .set_json(json!({
+            "tournament_category_id": category_id,
+            "participant1_player_id": player1,
+            "participant2_player_id": player2,
+            "match_type": "final",
+            "scheduled_date": "2026-05-02T15:00:00Z",
+            "court_number": "1",
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 201);
+    let body: Value = test::read_body_json(resp).await;
+    let match_id = body["result"]["id"].as_str().unwrap().to_string();
+    assert_eq!(body["result"]["match_status"], "scheduled");
+
+    let req = test::TestRequest::put()
+        .uri(&format!("/matches/{}/start", match_id))
+        .to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["result"]["match_status"], "in_progress");
+
+    let req = test::TestRequest::put()
+        .uri(&format!("/matches/{}/complete", match_id))
+        .set_json(json!({ "winner_participant": 2, "is_draw": false }))
+        .to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["result"]["match_status"], "completed");
+    assert_eq!(body["result"]["winner_participant"], 2);
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/matches/{}/participants", match_id))
+        .to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["result"]["participant2_name"], "Ben Ortiz");
+}
+
+#[actix_web::test]
+async fn test_bulk_results_roll_back_when_one_fails() {
+    let repos = Repositories::in_memory();
+    let (category_id, player1, player2) = seed(&repos).await;
+    let app = init_app!(repos);
+
+    let req = test::TestRequest::post()
+        .uri("/matches")
+        .set_json(json!({
+            "tournament_category_id": category_id,
+            "participant1_player_id": player1,
+            "participant2_player_id": player2,
+            "match_type": "final",
+            "scheduled_date": "2026-05-02T15:00:00Z",
+        }))
+        .to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    let match_id = body["result"]["id"].as_str().unwrap().to_string();
+
+    let req = test::TestRequest::post()
+        .uri("/match-results/bulk")
+        .set_json(json!([
+            { "match_id": match_id, "set_number": 1, "participant1_score": 11, "participant2_score": 7 },
+            { "match_id": Uuid::new_v4(), "set_number": 2, "participant1_score": 9, "participant2_score": 11 },
+        ]))
+        .to_request();
+    assert!(!test::call_service(&app, req).await.status().is_success());
+
+    let results = repos
+        .match_results
+        .find_by_match(match_id.parse().unwrap())
+        .await
+        .unwrap();
+    assert!(results.is_empty());
 }
@@ -0,0 +1,265 @@
+//! Transactional outbox and dispatcher, run against the in-memory repositories.
+
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{TimeZone, Utc};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use server::application::event_subscribers::NotificationSubscriber;
+use server::application::OutboxDispatcher;
+use server::domain::match_domain::{MatchType, NewMatch};
+use server::domain::outbox::{DomainEvent, DomainEventSubscriber, OutboxStatus};
+use server::domain::participant::CreatePlayer;
+use server::domain::payment::{NewPayment, PaymentMethod, PaymentStatus};
+use server::domain::tournament::{NewTournamentCategory, SportType, TeamComposition};
+use server::infra::api::sse::Broadcaster;
+use server::infra::email::email_sender;
+use server::infra::repositories::Repositories;
+use server::shared::config::{EmailConfig, OutboxConfig, SseConfig};
+use server::shared::AppError;
+
+use common::{new_tournament, services};
+
+/// A payment subscriber whose downstream is always unavailable
+struct Unavailable;
+
+#[async_trait]
+impl DomainEventSubscriber for Unavailable {
+    fn name(&self) -> &'static str {
+        "unavailable"
+    }
+
+    async fn handle(&self, event: &DomainEvent) -> Result<(), AppError> {
+        match event {
+            DomainEvent::PaymentSucceeded { .. } => {
+                Err(AppError::InternalError("mail server down".into()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Retries are due immediately, so tests can dispatch back to back
+fn config(max_attempts: i32) -> OutboxConfig {
+    OutboxConfig {
+        max_attempts,
+        retry_base: Duration::ZERO,
+        ..OutboxConfig::default()
+    }
+}
+
+/// Seeds a started singles match between two players with accounts
+async fn seed_match(repos: &Repositories) -> (Uuid, Vec<Uuid>) {
+    let tournament = repos
+        .tournaments
+        .create(new_tournament(
+            "Club Singles",
+            SportType::TableTennis,
+            Utc.with_ymd_and_hms(2026, 5, 1, 9, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 5, 2, 18, 0, 0).unwrap(),
+        ))
+        .await
+        .unwrap();
+    let category = repos
+        .categories
+        .create(NewTournamentCategory {
+            tournament_id: tournament.id,
+            name: "Open Singles".to_string(),
+            description: None,
+            team_composition: TeamComposition::Singles,
+            min_participants: None,
+            max_participants: None,
+            entry_fee: None,
+            prize_distribution: None,
+            rules: None,
+            constraints: None,
+        })
+        .await
+        .unwrap();
+    let mut players = Vec::new();
+    let mut users = Vec::new();
+    for name in ["Ana Lee", "Ben Ortiz"] {
+        let user_id = Uuid::new_v4();
+        let player = repos
+            .players
+            .create(CreatePlayer {
+                name: name.to_string(),
+                user_id: Some(user_id),
+            })
+            .await
+            .unwrap();
+        players.push(player.id);
+        users.push(user_id);
+    }
+    let m = repos
+        .matches
+        .create(NewMatch {
+            tournament_category_id: category.id,
+            participant1_team_id: None,
+            participant1_player_id: Some(players[0]),
+            participant1_partner_id: None,
+            participant2_team_id: None,
+            participant2_player_id: Some(players[1]),
+            participant2_partner_id: None,
+            match_type: MatchType::Final,
+            round_number: None,
+            match_number: None,
+            scheduled_date: Utc.with_ymd_and_hms(2026, 5, 2, 15, 0, 0).unwrap(),
+            venue: None,
+            court_number: None,
+            court_id: None,
+            referee_name: None,
+            umpire_name: None,
+            notes: None,
+            metadata: None,
+        })
+        .await
+        .unwrap();
+    repos.matches.start_match(m.id).await.unwrap();
+    (m.id, users)
+}
+
+#[actix_web::test]
+async fn test_completed_match_is_delivered_once() {
+    let repos = Repositories::in_memory();
+    let (match_id, users) = seed_match(&repos).await;
+    let services = services(&repos);
+
+    services
+        .matches
+        .complete_match(match_id, 1, false)
+        .await
+        .unwrap();
+    // Completing it again is not a new completion
+    services
+        .matches
+        .complete_match(match_id, 1, false)
+        .await
+        .unwrap();
+
+    let dispatcher = services.outbox_dispatcher(
+        &repos,
+        Broadcaster::create(&SseConfig::default(), None),
+        email_sender(&EmailConfig::default()).unwrap(),
+        None,
+        None,
+        config(3),
+    );
+    assert_eq!(dispatcher.dispatch_batch().await.unwrap(), 1);
+    // The two notifications it created, handed to the email channel
+    assert_eq!(dispatcher.dispatch_batch().await.unwrap(), 2);
+    assert_eq!(dispatcher.dispatch_batch().await.unwrap(), 0);
+
+    for user_id in users {
+        let inbox = services
+            .notifications
+            .get_notifications(user_id, 10, 0)
+            .await
+            .unwrap();
+        assert_eq!(inbox.len(), 1);
+        assert_eq!(inbox[0].match_id, Some(match_id));
+    }
+}
+
+#[actix_web::test]
+async fn test_failed_subscribers_are_retried_then_given_up() {
+    let repos = Repositories::in_memory();
+    let services = services(&repos);
+    let user_id = Uuid::new_v4();
+    let payment = repos
+        .payments
+        .create(NewPayment {
+            user_id,
+            tournament_id: Uuid::new_v4(),
+            amount: Decimal::new(2500, 2),
+            currency: "USD".to_string(),
+            payment_method: PaymentMethod::Stripe,
+            transaction_id: None,
+            payment_provider: None,
+            metadata: None,
+        })
+        .await
+        .unwrap();
+    services
+        .payments
+        .update_payment_status(payment.id, PaymentStatus::Completed)
+        .await
+        .unwrap();
+
+    let dispatcher = OutboxDispatcher::new(Arc::clone(&repos.outbox), config(2))
+        .subscribe(Arc::new(Unavailable))
+        .subscribe(Arc::new(NotificationSubscriber::new(
+            Arc::clone(&services.notifications),
+            Arc::clone(&repos.players),
+            Arc::clone(&repos.team_members),
+        )));
+
+    assert_eq!(dispatcher.dispatch_batch().await.unwrap(), 1);
+    // The retry, and the notification the first attempt created
+    assert_eq!(dispatcher.dispatch_batch().await.unwrap(), 2);
+    assert_eq!(dispatcher.dispatch_batch().await.unwrap(), 0);
+
+    // The subscriber that succeeded the first time was not run again
+    let inbox = services
+        .notifications
+        .get_notifications(user_id, 10, 0)
+        .await
+        .unwrap();
+    assert_eq!(inbox.len(), 1);
+    assert!(inbox[0].message.contains("25.00 USD"));
+
+    let work = repos.unit_of_work.begin().await.unwrap();
+    let message = work
+        .outbox()
+        .enqueue(&DomainEvent::payment_succeeded(&payment))
+        .await
+        .unwrap();
+    work.rollback().await.unwrap();
+    assert!(repos.outbox.find_by_id(message.id).await.unwrap().is_none());
+}
+
+#[actix_web::test]
+async fn test_claimed_events_are_leased() {
+    let repos = Repositories::in_memory();
+    let (match_id, _) = seed_match(&repos).await;
+    services(&repos)
+        .matches
+        .complete_match(match_id, 2, false)
+        .await
+        .unwrap();
+
+    let lease_until = Utc::now() + chrono::Duration::minutes(1);
+    let claimed = repos.outbox.claim_due(10, lease_until).await.unwrap();
+    assert_eq!(claimed.len(), 1);
+    assert_eq!(claimed[0].attempts, 1);
+    assert!(matches!(
+        claimed[0].event().unwrap(),
+        DomainEvent::MatchCompleted { winner_participant: Some(2), ref player_ids, .. }
+            if player_ids.len() == 2
+    ));
+    // A second dispatcher does not see it until the lease runs out
+    assert!(repos
+        .outbox
+        .claim_due(10, lease_until)
+        .await
+        .unwrap()
+        .is_empty());
+
+    repos
+        .outbox
+        .mark_failed(claimed[0].id, &[], "crashed", None)
+        .await
+        .unwrap();
+    let message = repos
+        .outbox
+        .find_by_id(claimed[0].id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(message.status, OutboxStatus::Failed);
+}
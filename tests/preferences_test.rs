@@ -0,0 +1,239 @@
+//! Notification preferences and email unsubscribe links, run against the in-memory
+//! repositories.
+
+mod common;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde_json::json;
+use uuid::Uuid;
+
+use server::application::event_subscribers::{EmailNotificationSubscriber, UnsubscribeLinks};
+use server::application::OutboxDispatcher;
+use server::domain::notification::{
+    DeliveryChannel, EmailSender, NewNotification, NotificationPreferences, NotificationType,
+    OutgoingEmail, SubscriptionPreferences,
+};
+use server::domain::user::{NewUser, NewUserProfile};
+use server::infra::repositories::Repositories;
+use server::shared::config::OutboxConfig;
+use server::shared::jwt::validate_unsubscribe_token;
+use server::shared::AppError;
+
+use common::services;
+
+const SECRET: &str = "unsubscribe-test-secret";
+
+#[derive(Default)]
+struct RecordingSender {
+    sent: Mutex<Vec<OutgoingEmail>>,
+}
+
+#[async_trait]
+impl EmailSender for RecordingSender {
+    async fn send(&self, email: &OutgoingEmail) -> Result<(), AppError> {
+        self.sent.lock().push(email.clone());
+        Ok(())
+    }
+}
+
+async fn seed_user(repos: &Repositories, preferences: Option<serde_json::Value>) -> Uuid {
+    let user = repos
+        .users
+        .create(NewUser {
+            name: Some("Ana Lee".to_string()),
+            email: format!("{}@example.com", Uuid::new_v4()),
+            google_id: Uuid::new_v4().to_string(),
+        })
+        .await
+        .unwrap();
+    repos
+        .profiles
+        .create(NewUserProfile {
+            user_id: user.id,
+            notification_preferences: preferences,
+            ..NewUserProfile::default()
+        })
+        .await
+        .unwrap();
+    user.id
+}
+
+fn notification(
+    user_id: Uuid,
+    notification_type: NotificationType,
+    tournament_id: Option<Uuid>,
+) -> NewNotification {
+    NewNotification {
+        user_id,
+        title: "Court 3 at 10:30".to_string(),
+        message: "Your quarter-final starts at 10:30.".to_string(),
+        notification_type,
+        tournament_id,
+        match_id: None,
+    }
+}
+
+#[test]
+fn test_preferences_schema() {
+    let tournament = Uuid::new_v4();
+    let preferences = NotificationPreferences::parse(&json!({
+        "push": false,
+        "types": { "match_reminder": { "push": true, "email": false } },
+        "muted_tournaments": [tournament],
+    }))
+    .unwrap();
+
+    let reminder = NotificationType::MatchReminder;
+    assert!(preferences.allows(DeliveryChannel::Push, &reminder, None));
+    assert!(!preferences.allows(DeliveryChannel::Email, &reminder, None));
+    assert!(!preferences.allows(DeliveryChannel::Push, &NotificationType::ResultPosted, None));
+    assert!(preferences.allows(
+        DeliveryChannel::InApp,
+        &NotificationType::ResultPosted,
+        None
+    ));
+    assert!(!preferences.wants(&reminder, Some(tournament)));
+
+    assert!(NotificationPreferences::parse(&json!({ "sms": false })).is_err());
+    assert!(NotificationPreferences::parse(&json!({ "types": { "gossip": {} } })).is_err());
+
+    let subscription = SubscriptionPreferences::parse(
+        &json!({ "all": false, "types": { "result_posted": true } }),
+    )
+    .unwrap();
+    assert!(subscription.wants(&NotificationType::ResultPosted));
+    assert!(!subscription.wants(&NotificationType::MatchReminder));
+    assert!(SubscriptionPreferences::from_value(&json!({})).wants(&NotificationType::MatchReminder));
+}
+
+#[actix_web::test]
+async fn test_sends_respect_channel_and_tournament_opt_outs() {
+    let repos = Repositories::in_memory();
+    let muted = Uuid::new_v4();
+    let user_id = seed_user(
+        &repos,
+        Some(json!({
+            "types": {
+                "tournament_update": { "in_app": false },
+                "registration_confirmed": { "in_app": false, "email": false, "push": false },
+            },
+            "muted_tournaments": [muted],
+        })),
+    )
+    .await;
+    let notifications = services(&repos).notifications;
+
+    // Inbox off but email on: stored already read, for the other channels to deliver
+    let update = notifications
+        .send_notification(notification(
+            user_id,
+            NotificationType::TournamentUpdate,
+            None,
+        ))
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(update.is_read);
+    assert_eq!(notifications.get_unread_count(user_id).await.unwrap(), 0);
+
+    // Off everywhere, or about a muted tournament: not sent at all
+    assert!(notifications
+        .send_notification(notification(
+            user_id,
+            NotificationType::RegistrationConfirmed,
+            None
+        ))
+        .await
+        .unwrap()
+        .is_none());
+    let sent = notifications
+        .send_bulk_notifications(vec![
+            notification(user_id, NotificationType::MatchReminder, Some(muted)),
+            notification(user_id, NotificationType::MatchReminder, None),
+            notification(user_id, NotificationType::RegistrationConfirmed, None),
+        ])
+        .await
+        .unwrap();
+    assert_eq!(sent.len(), 1);
+    assert!(!sent[0].is_read);
+    assert_eq!(
+        notifications
+            .get_notifications(user_id, 10, 0)
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+}
+
+#[actix_web::test]
+async fn test_unsubscribe_link_turns_off_that_email_type() {
+    let repos = Repositories::in_memory();
+    let user_id = seed_user(&repos, None).await;
+    let services = services(&repos);
+    services
+        .notifications
+        .send_notification(notification(user_id, NotificationType::MatchReminder, None))
+        .await
+        .unwrap();
+
+    let sender = Arc::new(RecordingSender::default());
+    let links = UnsubscribeLinks {
+        url: "https://example.com/unsubscribe?lang=en".to_string(),
+        secret: SECRET.to_string(),
+    };
+    let config = OutboxConfig {
+        retry_base: Duration::ZERO,
+        ..OutboxConfig::default()
+    };
+    let dispatcher = OutboxDispatcher::new(Arc::clone(&repos.outbox), config).subscribe(Arc::new(
+        EmailNotificationSubscriber::new(
+            Arc::clone(&repos.notifications),
+            Arc::clone(&repos.notification_deliveries),
+            Arc::clone(&repos.users),
+            Arc::clone(&repos.profiles),
+            sender.clone(),
+            Some(links),
+        ),
+    ));
+    assert_eq!(dispatcher.dispatch_batch().await.unwrap(), 1);
+
+    let email = sender.sent.lock()[0].clone();
+    let url = email.unsubscribe_url.unwrap();
+    assert!(email.body.contains(&url));
+    let token = url
+        .strip_prefix("https://example.com/unsubscribe?lang=en&token=")
+        .unwrap();
+    assert!(validate_unsubscribe_token("another-secret", token).is_err());
+    let claims = validate_unsubscribe_token(SECRET, token).unwrap();
+    assert_eq!(claims.user_id(), Some(user_id));
+    assert_eq!(claims.notification_type.as_deref(), Some("match_reminder"));
+
+    let preferences = services
+        .notifications
+        .unsubscribe_email(user_id, Some(NotificationType::MatchReminder))
+        .await
+        .unwrap();
+    assert!(!preferences.allows(
+        DeliveryChannel::Email,
+        &NotificationType::MatchReminder,
+        None
+    ));
+    assert!(preferences.allows(
+        DeliveryChannel::Email,
+        &NotificationType::ResultPosted,
+        None
+    ));
+    assert_eq!(
+        services
+            .notifications
+            .get_preferences(user_id)
+            .await
+            .unwrap(),
+        preferences
+    );
+}
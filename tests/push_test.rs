@@ -0,0 +1,217 @@
+//! Web Push channel: RFC 8291 encryption, VAPID signing and subscription pruning.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{TimeZone, Utc};
+use p256::ecdsa::signature::Verifier;
+use p256::ecdsa::{Signature, VerifyingKey};
+use parking_lot::Mutex;
+use uuid::Uuid;
+
+use server::application::event_subscribers::PushNotificationSubscriber;
+use server::application::OutboxDispatcher;
+use server::domain::notification::{
+    DeliveryChannel, DeliveryStatus, NewNotification, NewPushSubscription, NotificationType,
+    PushOutcome, PushSender, PushSubscription,
+};
+use server::infra::api::sse::Broadcaster;
+use server::infra::api::state::AppServices;
+use server::infra::push::encryption::encrypt_with_keys;
+use server::infra::push::VapidKeys;
+use server::infra::repositories::Repositories;
+use server::shared::config::{OutboxConfig, SseConfig, VapidConfig};
+use server::shared::AppError;
+
+// Keys from RFC 8291, Appendix A
+const UA_PUBLIC_KEY: &str =
+    "BCVxsr7N_eNgVRqvHtD0zTZsEc6-VV-JvLexhqUzORcxaOzi6-AYWXvTBHm4bjyPjs7Vd8pZGH6SRpkNtoIAiw4";
+const AUTH_SECRET: &str = "BTBZMqHH6r4Tts7J_aSIgg";
+
+/// Pushes to endpoints ending in `/gone` report the subscription as expired
+#[derive(Default)]
+struct RecordingPush {
+    sent: Mutex<Vec<(String, Vec<u8>)>>,
+}
+
+#[async_trait]
+impl PushSender for RecordingPush {
+    async fn send(
+        &self,
+        subscription: &PushSubscription,
+        payload: &[u8],
+    ) -> Result<PushOutcome, AppError> {
+        if subscription.endpoint.ends_with("/gone") {
+            return Ok(PushOutcome::Gone);
+        }
+        self.sent
+            .lock()
+            .push((subscription.endpoint.clone(), payload.to_vec()));
+        Ok(PushOutcome::Delivered)
+    }
+}
+
+fn decode(value: &str) -> Vec<u8> {
+    URL_SAFE_NO_PAD.decode(value).unwrap()
+}
+
+fn subscription(user_id: Uuid, endpoint: &str) -> NewPushSubscription {
+    NewPushSubscription {
+        user_id,
+        endpoint: endpoint.to_string(),
+        p256dh: UA_PUBLIC_KEY.to_string(),
+        auth: AUTH_SECRET.to_string(),
+        user_agent: None,
+    }
+}
+
+#[test]
+fn test_encryption_matches_rfc_8291_example() {
+    let server_private_key: [u8; 32] = decode("yfWPiYE-n46HLnH0KqZOF1fJJU3MYrct3AELtAQ-oRw")
+        .try_into()
+        .unwrap();
+    let salt: [u8; 16] = decode("DGv6ra1nlYgDCS1FRnbzlw").try_into().unwrap();
+
+    let message = encrypt_with_keys(
+        b"When I grow up, I want to be a watermelon",
+        UA_PUBLIC_KEY,
+        AUTH_SECRET,
+        &server_private_key,
+        salt,
+    )
+    .unwrap();
+
+    assert_eq!(
+        URL_SAFE_NO_PAD.encode(message),
+        "DGv6ra1nlYgDCS1FRnbzlwAAEABBBP4z9KsN6nGRTbVYI_c7VJSPQTBtkgcy27mlmlMoZIIgDll6e3vCYLocInmYWAmS6TlzAC8wEqKK6PBru3jl7A_yl95bQpu6cVPTpK4Mqgkf1CXztLVBSt2Ks3oZwbuwXPXLWyouBWLVWGNWQexSgSxsj_Qulcy4a-fN"
+    );
+}
+
+#[test]
+fn test_vapid_authorization_is_signed_for_the_push_service_origin() {
+    let keys = VapidKeys::from_config(&VapidConfig {
+        private_key: URL_SAFE_NO_PAD.encode([7u8; 32]),
+        subject: "mailto:ops@example.com".to_string(),
+    })
+    .unwrap();
+    let expires = Utc.with_ymd_and_hms(2026, 5, 1, 12, 0, 0).unwrap();
+
+    let header = keys
+        .authorization("https://push.example.net/wpush/v2/abc?x=1", expires)
+        .unwrap();
+    let (token, key) = header
+        .strip_prefix("vapid t=")
+        .and_then(|rest| rest.split_once(", k="))
+        .unwrap();
+    assert_eq!(key, keys.public_key());
+
+    let (signing_input, signature) = token.rsplit_once('.').unwrap();
+    let claims: serde_json::Value =
+        serde_json::from_slice(&decode(signing_input.split_once('.').unwrap().1)).unwrap();
+    assert_eq!(claims["aud"], "https://push.example.net");
+    assert_eq!(claims["exp"], expires.timestamp());
+    assert_eq!(claims["sub"], "mailto:ops@example.com");
+
+    let verifying_key = VerifyingKey::from_sec1_bytes(&decode(key)).unwrap();
+    let signature = Signature::from_slice(&decode(signature)).unwrap();
+    assert!(verifying_key
+        .verify(signing_input.as_bytes(), &signature)
+        .is_ok());
+}
+
+#[actix_web::test]
+async fn test_reminders_are_pushed_and_expired_subscriptions_pruned() {
+    let repos = Repositories::in_memory();
+    let services = AppServices::new(&repos, Broadcaster::create(&SseConfig::default(), None));
+    let user_id = Uuid::new_v4();
+    for endpoint in [
+        "https://push.example.net/live",
+        "https://push.example.net/gone",
+    ] {
+        services
+            .notifications
+            .subscribe_push(subscription(user_id, endpoint))
+            .await
+            .unwrap();
+    }
+    assert!(services
+        .notifications
+        .subscribe_push(subscription(user_id, "http://push.example.net/plain"))
+        .await
+        .is_err());
+
+    let reminder = services
+        .notifications
+        .send_notification(NewNotification {
+            user_id,
+            title: "Court 3 at 10:30".to_string(),
+            message: "Your quarter-final starts at 10:30.".to_string(),
+            notification_type: NotificationType::MatchReminder,
+            tournament_id: None,
+            match_id: None,
+        })
+        .await
+        .unwrap()
+        .unwrap();
+    let update = services
+        .notifications
+        .send_notification(NewNotification {
+            user_id,
+            title: "Schedule published".to_string(),
+            message: "The schedule is out.".to_string(),
+            notification_type: NotificationType::TournamentUpdate,
+            tournament_id: None,
+            match_id: None,
+        })
+        .await
+        .unwrap()
+        .unwrap();
+
+    let push = Arc::new(RecordingPush::default());
+    let config = OutboxConfig {
+        retry_base: Duration::ZERO,
+        ..OutboxConfig::default()
+    };
+    let dispatcher = OutboxDispatcher::new(Arc::clone(&repos.outbox), config).subscribe(Arc::new(
+        PushNotificationSubscriber::new(
+            Arc::clone(&repos.notifications),
+            Arc::clone(&repos.notification_deliveries),
+            Arc::clone(&repos.push_subscriptions),
+            Arc::clone(&repos.profiles),
+            push.clone(),
+        ),
+    ));
+    assert_eq!(dispatcher.dispatch_batch().await.unwrap(), 2);
+
+    let sent = push.sent.lock().clone();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].0, "https://push.example.net/live");
+    let payload: serde_json::Value = serde_json::from_slice(&sent[0].1).unwrap();
+    assert_eq!(payload["type"], "match_reminder");
+    assert_eq!(payload["title"], "Court 3 at 10:30");
+
+    let remaining = repos
+        .push_subscriptions
+        .find_by_user_id(user_id)
+        .await
+        .unwrap();
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].endpoint, "https://push.example.net/live");
+
+    let delivery = repos
+        .notification_deliveries
+        .find(reminder.id, DeliveryChannel::Push)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(delivery.status, DeliveryStatus::Sent);
+    assert!(repos
+        .notification_deliveries
+        .find(update.id, DeliveryChannel::Push)
+        .await
+        .unwrap()
+        .is_none());
+}
@@ -0,0 +1,205 @@
+//! Scheduled match reminders and digests, run against the in-memory repositories.
+
+mod common;
+
+use chrono::{DateTime, Duration, Utc};
+use serde_json::json;
+use uuid::Uuid;
+
+use server::domain::match_domain::{MatchType, NewMatch};
+use server::domain::notification::{Notification, NotificationType};
+use server::domain::participant::CreatePlayer;
+use server::domain::user::NewUserProfile;
+use server::infra::api::state::ReminderSchedulerData;
+use server::infra::repositories::Repositories;
+use server::shared::config::ReminderConfig;
+
+use common::{at, seed_category, services};
+
+fn scheduler(repos: &Repositories) -> ReminderSchedulerData {
+    services(repos).reminder_scheduler(repos, ReminderConfig::default())
+}
+
+/// A player with an account, optionally with a timezone and notification preferences
+async fn seed_player(
+    repos: &Repositories,
+    name: &str,
+    timezone: Option<&str>,
+    preferences: Option<serde_json::Value>,
+) -> (Uuid, Uuid) {
+    let user_id = Uuid::new_v4();
+    let player = repos
+        .players
+        .create(CreatePlayer {
+            name: name.to_string(),
+            user_id: Some(user_id),
+        })
+        .await
+        .unwrap();
+    repos
+        .profiles
+        .create(NewUserProfile {
+            user_id,
+            bio: None,
+            avatar_url: None,
+            phone: None,
+            date_of_birth: None,
+            timezone: timezone.map(str::to_string),
+            language: None,
+            notification_preferences: preferences,
+            privacy_settings: None,
+            location: None,
+            website: None,
+            social_links: None,
+            preferences: None,
+            is_public: None,
+        })
+        .await
+        .unwrap();
+    (player.id, user_id)
+}
+
+async fn seed_match(
+    repos: &Repositories,
+    category_id: Uuid,
+    players: (Uuid, Uuid),
+    scheduled_date: DateTime<Utc>,
+) -> Uuid {
+    repos
+        .matches
+        .create(NewMatch {
+            tournament_category_id: category_id,
+            participant1_team_id: None,
+            participant1_player_id: Some(players.0),
+            participant1_partner_id: None,
+            participant2_team_id: None,
+            participant2_player_id: Some(players.1),
+            participant2_partner_id: None,
+            match_type: MatchType::Final,
+            round_number: None,
+            match_number: None,
+            scheduled_date,
+            venue: Some("Sports Hall".to_string()),
+            court_number: Some("3".to_string()),
+            court_id: None,
+            referee_name: None,
+            umpire_name: None,
+            notes: None,
+            metadata: None,
+        })
+        .await
+        .unwrap()
+        .id
+}
+
+async fn notifications(repos: &Repositories, user_id: Uuid) -> Vec<Notification> {
+    repos
+        .notifications
+        .get_by_user_id(user_id, 50, 0)
+        .await
+        .unwrap()
+}
+
+#[actix_web::test]
+async fn test_reminders_go_out_once_per_due_offset() {
+    let repos = Repositories::in_memory();
+    let category_id = seed_category(&repos, None).await.id;
+    let (ana, ana_user) = seed_player(&repos, "Ana Lee", Some("Europe/Madrid"), None).await;
+    let (ben, ben_user) = seed_player(&repos, "Ben Ortiz", None, None).await;
+    let start = at(15, 0) + Duration::days(1);
+    let match_id = seed_match(&repos, category_id, (ana, ben), start).await;
+    let fan = Uuid::new_v4();
+    repos
+        .matches
+        .subscribe_to_match(match_id, fan, json!({ "all": true }))
+        .await
+        .unwrap();
+    let scheduler = scheduler(&repos);
+
+    assert_eq!(
+        scheduler.scan(start - Duration::hours(25)).await.unwrap(),
+        0
+    );
+    assert_eq!(
+        scheduler.scan(start - Duration::hours(23)).await.unwrap(),
+        3
+    );
+    assert_eq!(
+        scheduler.scan(start - Duration::hours(22)).await.unwrap(),
+        0
+    );
+
+    let sent = notifications(&repos, ana_user).await;
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].notification_type, NotificationType::MatchReminder);
+    assert_eq!(sent[0].match_id, Some(match_id));
+    assert_eq!(sent[0].title, "Upcoming match: Ana Lee vs Ben Ortiz");
+    assert_eq!(
+        sent[0].message,
+        "Club Singles - Open Singles starts on Sun 3 May 17:00 CEST at Sports Hall, court 3."
+    );
+    assert!(notifications(&repos, ben_user).await[0]
+        .message
+        .contains("15:00 UTC"));
+
+    // Starting the job late sends a single reminder for the 1h and 15m offsets together
+    assert_eq!(
+        scheduler.scan(start - Duration::minutes(10)).await.unwrap(),
+        3
+    );
+    assert_eq!(
+        scheduler.scan(start - Duration::minutes(5)).await.unwrap(),
+        0
+    );
+    assert_eq!(notifications(&repos, fan).await.len(), 2);
+}
+
+#[actix_web::test]
+async fn test_quiet_hours_defer_reminders() {
+    let repos = Repositories::in_memory();
+    let category_id = seed_category(&repos, None).await.id;
+    let quiet = json!({ "quiet_hours": { "start": "22:00", "end": "07:00" } });
+    let (ana, ana_user) =
+        seed_player(&repos, "Ana Lee", Some("America/New_York"), Some(quiet)).await;
+    let (ben, ben_user) = seed_player(&repos, "Ben Ortiz", None, None).await;
+    // 08:00 in New York
+    let start = at(12, 0);
+    seed_match(&repos, category_id, (ana, ben), start).await;
+    let scheduler = scheduler(&repos);
+
+    // 23:30 the night before in New York, 03:30 UTC
+    assert_eq!(scheduler.scan(at(3, 30)).await.unwrap(), 1);
+    assert!(notifications(&repos, ana_user).await.is_empty());
+    assert_eq!(notifications(&repos, ben_user).await.len(), 1);
+
+    // 07:00 in New York: quiet hours are over
+    assert_eq!(scheduler.scan(at(11, 0)).await.unwrap(), 2);
+    assert_eq!(notifications(&repos, ana_user).await.len(), 1);
+}
+
+#[actix_web::test]
+async fn test_digest_users_get_one_summary_per_day() {
+    let repos = Repositories::in_memory();
+    let category_id = seed_category(&repos, None).await.id;
+    let digest = json!({ "reminders": { "mode": "digest" } });
+    let (ana, ana_user) = seed_player(&repos, "Ana Lee", None, Some(digest)).await;
+    let (ben, _) = seed_player(&repos, "Ben Ortiz", None, None).await;
+    let (cleo, _) = seed_player(&repos, "Cleo Park", None, None).await;
+    seed_match(&repos, category_id, (ana, ben), at(10, 0)).await;
+    seed_match(&repos, category_id, (cleo, ana), at(16, 30)).await;
+    let scheduler = scheduler(&repos);
+
+    scheduler.scan(at(6, 30)).await.unwrap();
+    assert!(notifications(&repos, ana_user).await.is_empty());
+
+    scheduler.scan(at(7, 0)).await.unwrap();
+    scheduler.scan(at(9, 30)).await.unwrap();
+    let sent = notifications(&repos, ana_user).await;
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].title, "Your 2 matches in the next 24 hours");
+    assert_eq!(
+        sent[0].message,
+        "Sat 2 May 10:00 UTC: Ana Lee vs Ben Ortiz (Club Singles - Open Singles) at Sports Hall, court 3\n\
+         Sat 2 May 16:30 UTC: Cleo Park vs Ana Lee (Club Singles - Open Singles) at Sports Hall, court 3"
+    );
+}
@@ -0,0 +1,453 @@
+//! Automatic match scheduling, run against the in-memory repositories.
+
+mod common;
+
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use uuid::Uuid;
+
+use server::domain::match_domain::{MatchType, NewMatch};
+use server::domain::participant::{NewTeam, NewTeamMember};
+use server::domain::scheduling::conflicts::ConflictKind;
+use server::domain::scheduling::{ScheduleReport, ScheduleRequest};
+use server::domain::tournament::{NewTournamentCategory, SportType, TeamComposition};
+use server::infra::api::state::AppServices;
+use server::infra::repositories::Repositories;
+use server::shared::AppError;
+
+use common::{at, new_tournament, player, services};
+
+struct Fixture {
+    tournament_id: Uuid,
+    singles: Uuid,
+    doubles: Uuid,
+    tables: Vec<Uuid>,
+    players: Vec<Uuid>,
+}
+
+async fn seed_tournament(repos: &Repositories, services: &AppServices) -> Fixture {
+    let tournament = repos
+        .tournaments
+        .create(new_tournament(
+            "Club Open",
+            SportType::TableTennis,
+            at(6, 0),
+            at(20, 0),
+        ))
+        .await
+        .unwrap();
+    let mut categories = Vec::new();
+    for (name, composition, constraints) in [
+        ("Singles", TeamComposition::Singles, None),
+        (
+            "Doubles",
+            TeamComposition::Doubles,
+            Some(json!({ "match_duration_minutes": 45 })),
+        ),
+    ] {
+        let category = repos
+            .categories
+            .create(NewTournamentCategory {
+                tournament_id: tournament.id,
+                name: name.to_string(),
+                description: None,
+                team_composition: composition,
+                min_participants: None,
+                max_participants: None,
+                entry_fee: None,
+                prize_distribution: None,
+                rules: None,
+                constraints,
+            })
+            .await
+            .unwrap();
+        categories.push(category.id);
+    }
+
+    // The hall opens at 08:00 Madrid time, 06:00 UTC
+    let hall = services
+        .venues
+        .create_venue(
+            serde_json::from_value(json!({
+                "name": "Sports Hall",
+                "timezone": "Europe/Madrid",
+                "opening_hours": [{ "weekday": "Sat", "opens": "08:00", "closes": "22:00" }],
+            }))
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+    let mut tables = Vec::new();
+    for name in ["Table 1", "Table 2"] {
+        let court = services
+            .venues
+            .add_court(
+                hall.id,
+                serde_json::from_value(json!({ "name": name, "kind": "table" })).unwrap(),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        tables.push(court.id);
+    }
+    services
+        .venues
+        .add_tournament_venue(tournament.id, hall.id)
+        .await
+        .unwrap();
+
+    let mut players = Vec::new();
+    for _ in 0..6 {
+        players.push(player(repos).await);
+    }
+    Fixture {
+        tournament_id: tournament.id,
+        singles: categories[0],
+        doubles: categories[1],
+        tables,
+        players,
+    }
+}
+
+async fn seed_match(
+    repos: &Repositories,
+    category_id: Uuid,
+    match_type: MatchType,
+    match_number: i32,
+    sides: Option<(Uuid, Uuid)>,
+    court_id: Option<Uuid>,
+) -> Uuid {
+    repos
+        .matches
+        .create(NewMatch {
+            tournament_category_id: category_id,
+            participant1_team_id: None,
+            participant1_player_id: sides.map(|s| s.0),
+            participant1_partner_id: None,
+            participant2_team_id: None,
+            participant2_player_id: sides.map(|s| s.1),
+            participant2_partner_id: None,
+            match_type,
+            round_number: None,
+            match_number: Some(match_number),
+            scheduled_date: at(6, 0),
+            venue: None,
+            court_number: None,
+            court_id,
+            referee_name: None,
+            umpire_name: None,
+            notes: None,
+            metadata: None,
+        })
+        .await
+        .unwrap()
+        .id
+}
+
+async fn seed_team(repos: &Repositories, members: &[Uuid]) -> Uuid {
+    let team = repos
+        .teams
+        .create(NewTeam {
+            name: format!("Team {}", Uuid::new_v4()),
+        })
+        .await
+        .unwrap();
+    for player_id in members {
+        repos
+            .team_members
+            .create(NewTeamMember {
+                team_id: team.id,
+                player_id: *player_id,
+                is_captain: None,
+                jersey_number: None,
+            })
+            .await
+            .unwrap();
+    }
+    team.id
+}
+
+async fn seed_team_match(
+    repos: &Repositories,
+    category_id: Uuid,
+    teams: (Uuid, Uuid),
+    court_id: Option<Uuid>,
+) -> Uuid {
+    repos
+        .matches
+        .create(NewMatch {
+            tournament_category_id: category_id,
+            participant1_team_id: Some(teams.0),
+            participant1_player_id: None,
+            participant1_partner_id: None,
+            participant2_team_id: Some(teams.1),
+            participant2_player_id: None,
+            participant2_partner_id: None,
+            match_type: MatchType::GroupStage,
+            round_number: None,
+            match_number: Some(1),
+            scheduled_date: at(6, 0),
+            venue: None,
+            court_number: None,
+            court_id,
+            referee_name: None,
+            umpire_name: None,
+            notes: None,
+            metadata: None,
+        })
+        .await
+        .unwrap()
+        .id
+}
+
+fn slot_of(report: &ScheduleReport, match_id: Uuid) -> (DateTime<Utc>, Uuid) {
+    let slot = report
+        .slots
+        .iter()
+        .find(|s| s.match_id == match_id)
+        .unwrap();
+    (slot.starts_at, slot.court_id)
+}
+
+#[actix_web::test]
+async fn test_schedule_respects_rest_and_round_order() {
+    let repos = Repositories::in_memory();
+    let services = services(&repos);
+    let f = seed_tournament(&repos, &services).await;
+    let p = &f.players;
+    let semi1 = seed_match(
+        &repos,
+        f.singles,
+        MatchType::SemiFinal,
+        1,
+        Some((p[0], p[1])),
+        None,
+    )
+    .await;
+    let semi2 = seed_match(
+        &repos,
+        f.singles,
+        MatchType::SemiFinal,
+        2,
+        Some((p[2], p[3])),
+        None,
+    )
+    .await;
+    let final_match = seed_match(&repos, f.singles, MatchType::Final, 3, None, None).await;
+    // Player 0 is entered in both categories
+    let group = seed_match(
+        &repos,
+        f.doubles,
+        MatchType::GroupStage,
+        1,
+        Some((p[0], p[4])),
+        None,
+    )
+    .await;
+
+    let preview = services
+        .scheduling
+        .schedule_tournament(f.tournament_id, ScheduleRequest::default(), true)
+        .await
+        .unwrap();
+    assert!(!preview.committed);
+    assert!(preview.unplaced.is_empty());
+    // Group stage first, 45 minutes as the doubles category sets
+    assert_eq!(slot_of(&preview, group), (at(6, 0), f.tables[0]));
+    assert_eq!(
+        preview
+            .slots
+            .iter()
+            .find(|s| s.match_id == group)
+            .unwrap()
+            .ends_at,
+        at(6, 45)
+    );
+    assert_eq!(slot_of(&preview, semi2), (at(6, 0), f.tables[1]));
+    // Player 0 rests 30 minutes after the group match
+    assert_eq!(slot_of(&preview, semi1), (at(7, 15), f.tables[0]));
+    // The final waits for both semi-finals plus rest
+    assert_eq!(slot_of(&preview, final_match), (at(8, 15), f.tables[0]));
+    let untouched = repos.matches.find_by_id(semi1).await.unwrap().unwrap();
+    assert_eq!(untouched.court_id, None);
+
+    let saved = services
+        .scheduling
+        .schedule_tournament(f.tournament_id, ScheduleRequest::default(), false)
+        .await
+        .unwrap();
+    assert!(saved.committed);
+    let semi1 = repos.matches.find_by_id(semi1).await.unwrap().unwrap();
+    assert_eq!(semi1.scheduled_date, at(7, 15));
+    assert_eq!(semi1.court_id, Some(f.tables[0]));
+    assert_eq!(semi1.court_number.as_deref(), Some("Table 1"));
+    assert_eq!(semi1.venue.as_deref(), Some("Sports Hall"));
+
+    // Everything is on a court now, so there is nothing left to place
+    let again = services
+        .scheduling
+        .schedule_tournament(f.tournament_id, ScheduleRequest::default(), true)
+        .await
+        .unwrap();
+    assert!(again.slots.is_empty());
+}
+
+#[actix_web::test]
+async fn test_schedule_works_around_booked_courts_and_reports_unplaced() {
+    let repos = Repositories::in_memory();
+    let services = services(&repos);
+    let f = seed_tournament(&repos, &services).await;
+    let other = seed_tournament(&repos, &services).await;
+    let p = &f.players;
+    // Another tournament already has Table 2 from 06:00 to 06:30
+    seed_match(
+        &repos,
+        other.singles,
+        MatchType::Final,
+        1,
+        None,
+        Some(f.tables[1]),
+    )
+    .await;
+    let group = seed_match(
+        &repos,
+        f.doubles,
+        MatchType::GroupStage,
+        1,
+        Some((p[0], p[4])),
+        None,
+    )
+    .await;
+    let semi1 = seed_match(
+        &repos,
+        f.singles,
+        MatchType::SemiFinal,
+        1,
+        Some((p[0], p[1])),
+        None,
+    )
+    .await;
+    let semi2 = seed_match(
+        &repos,
+        f.singles,
+        MatchType::SemiFinal,
+        2,
+        Some((p[2], p[3])),
+        None,
+    )
+    .await;
+    let final_match = seed_match(&repos, f.singles, MatchType::Final, 3, None, None).await;
+
+    let request = ScheduleRequest {
+        ends_at: Some(at(7, 0)),
+        ..ScheduleRequest::default()
+    };
+    let report = services
+        .scheduling
+        .schedule_tournament(f.tournament_id, request, false)
+        .await
+        .unwrap();
+    assert_eq!(slot_of(&report, group), (at(6, 0), f.tables[0]));
+    assert_eq!(slot_of(&report, semi2), (at(6, 30), f.tables[1]));
+    let unplaced: Vec<Uuid> = report.unplaced.iter().map(|u| u.match_id).collect();
+    assert_eq!(unplaced, vec![semi1, final_match]);
+    assert!(!report.committed);
+    assert_eq!(
+        repos
+            .matches
+            .find_by_id(group)
+            .await
+            .unwrap()
+            .unwrap()
+            .court_id,
+        None
+    );
+
+    let request = ScheduleRequest {
+        min_rest_minutes: Some(-5),
+        ..ScheduleRequest::default()
+    };
+    assert!(matches!(
+        services
+            .scheduling
+            .schedule_tournament(f.tournament_id, request, true)
+            .await,
+        Err(AppError::ValidationError(_))
+    ));
+}
+
+#[actix_web::test]
+async fn test_schedule_expands_teams_and_refuses_clashes_elsewhere() {
+    let repos = Repositories::in_memory();
+    let services = services(&repos);
+    let f = seed_tournament(&repos, &services).await;
+    let other = seed_tournament(&repos, &services).await;
+    let p = &f.players;
+    // Player 0 plays singles and for a team in the doubles category
+    let home = seed_team(&repos, &[p[0], p[4]]).await;
+    let away = seed_team(&repos, &[p[5]]).await;
+    let team_match = seed_team_match(&repos, f.doubles, (home, away), None).await;
+    let semi1 = seed_match(
+        &repos,
+        f.singles,
+        MatchType::SemiFinal,
+        1,
+        Some((p[0], p[1])),
+        None,
+    )
+    .await;
+
+    let preview = services
+        .scheduling
+        .schedule_tournament(f.tournament_id, ScheduleRequest::default(), true)
+        .await
+        .unwrap();
+    assert_eq!(slot_of(&preview, team_match), (at(6, 0), f.tables[0]));
+    // Not alongside the team match on Table 2, but after it and a rest
+    assert_eq!(slot_of(&preview, semi1), (at(7, 15), f.tables[0]));
+    assert!(preview.conflicts.is_empty());
+
+    // Player 2 is busy from 06:00 with a team in another tournament, on a court the
+    // scheduler does not use
+    let semi2 = seed_match(
+        &repos,
+        f.singles,
+        MatchType::SemiFinal,
+        2,
+        Some((p[2], p[3])),
+        None,
+    )
+    .await;
+    let elsewhere = seed_team(&repos, &[p[2]]).await;
+    let rivals = seed_team(&repos, &[other.players[0]]).await;
+    let busy = seed_team_match(
+        &repos,
+        other.doubles,
+        (elsewhere, rivals),
+        Some(other.tables[0]),
+    )
+    .await;
+    let report = services
+        .scheduling
+        .schedule_tournament(f.tournament_id, ScheduleRequest::default(), false)
+        .await
+        .unwrap();
+    assert_eq!(slot_of(&report, semi2), (at(6, 0), f.tables[1]));
+    assert_eq!(report.conflicts.len(), 1);
+    let conflict = &report.conflicts[0];
+    assert_eq!(conflict.kind, ConflictKind::Participant);
+    assert_eq!(conflict.match_id, Some(semi2));
+    assert_eq!(conflict.conflicting_match_id, busy);
+    assert_eq!(conflict.subject_ids, vec![p[2]]);
+    assert!(!report.committed);
+    assert_eq!(
+        repos
+            .matches
+            .find_by_id(team_match)
+            .await
+            .unwrap()
+            .unwrap()
+            .court_id,
+        None
+    );
+}
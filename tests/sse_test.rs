@@ -0,0 +1,175 @@
+//! SSE subscription routing tests.
+
+use actix_web::body::MessageBody;
+use actix_web::{web, App};
+use std::time::Duration;
+use uuid::Uuid;
+
+use server::infra::api::sse::{
+    event_stream, Broadcaster, EventFilter, EventJournal, MemoryEventJournal, RealtimeEvent,
+};
+use server::shared::config::SseConfig;
+
+fn match_update(tournament_id: Uuid, category_id: Uuid, match_id: Uuid) -> RealtimeEvent {
+    RealtimeEvent::MatchUpdate {
+        match_id,
+        tournament_id: Some(tournament_id),
+        category_id: Some(category_id),
+        status: Some("in_progress".to_string()),
+    }
+}
+
+#[test]
+fn test_topic_filters_must_all_match() {
+    let (tournament, category, m) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    let event = match_update(tournament, category, m);
+
+    assert!(EventFilter::default().matches(&event));
+    let by_tournament = EventFilter {
+        tournament_id: Some(tournament),
+        ..Default::default()
+    };
+    assert!(by_tournament.matches(&event));
+    assert!(!by_tournament.matches(&match_update(Uuid::new_v4(), category, m)));
+
+    let by_match = EventFilter {
+        tournament_id: Some(tournament),
+        match_id: Some(Uuid::new_v4()),
+        ..Default::default()
+    };
+    assert!(!by_match.matches(&event));
+
+    let bracket = RealtimeEvent::BracketUpdate {
+        tournament_id: tournament,
+        category_id: None,
+    };
+    assert!(by_tournament.matches(&bracket));
+    let by_category = EventFilter {
+        category_id: Some(category),
+        ..Default::default()
+    };
+    assert!(!by_category.matches(&bracket));
+}
+
+#[test]
+fn test_notifications_only_reach_their_owner() {
+    let owner = Uuid::new_v4();
+    let event = RealtimeEvent::Notification {
+        user_id: owner,
+        notification_id: Uuid::new_v4(),
+    };
+
+    assert!(!EventFilter::default().matches(&event));
+    let someone_else = EventFilter {
+        user_id: Some(Uuid::new_v4()),
+        notifications: true,
+        ..Default::default()
+    };
+    assert!(!someone_else.matches(&event));
+
+    let mine = EventFilter {
+        user_id: Some(owner),
+        notifications: true,
+        ..Default::default()
+    };
+    assert!(mine.matches(&event));
+    // A notifications-only subscription skips public events
+    assert!(!mine.matches(&match_update(
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        Uuid::new_v4()
+    )));
+}
+
+#[actix_web::test]
+async fn test_notification_subscription_requires_token() {
+    let app = actix_web::test::init_service(
+        App::new()
+            .app_data(web::Data::new(Broadcaster::create(
+                &SseConfig::default(),
+                None,
+            )))
+            .route("/events", web::get().to(event_stream)),
+    )
+    .await;
+
+    let req = actix_web::test::TestRequest::get()
+        .uri("/events?notifications=true")
+        .to_request();
+    assert_eq!(actix_web::test::call_service(&app, req).await.status(), 401);
+}
+
+#[actix_web::test]
+async fn test_memory_journal_replays_and_reports_gaps() {
+    let journal = MemoryEventJournal::new(2);
+    let event = RealtimeEvent::BracketUpdate {
+        tournament_id: Uuid::new_v4(),
+        category_id: None,
+    };
+    for expected in 1..=3 {
+        assert_eq!(journal.append(&event).await.unwrap(), expected);
+    }
+
+    let replay = journal.since(2).await.unwrap();
+    assert!(replay.complete);
+    assert_eq!(
+        replay.events.iter().map(|e| e.id).collect::<Vec<_>>(),
+        vec![3]
+    );
+
+    // Event 2 was dropped when the third arrived
+    let replay = journal.since(0).await.unwrap();
+    assert!(!replay.complete);
+    assert_eq!(replay.events.len(), 2);
+
+    // An id this journal never issued, e.g. from before a restart
+    let replay = journal.since(99).await.unwrap();
+    assert!(!replay.complete);
+    assert_eq!(replay.events.len(), 2);
+}
+
+#[actix_web::test]
+async fn test_reconnect_replays_missed_matching_events() {
+    let broadcaster = Broadcaster::create(&SseConfig::default(), None);
+    let tournament_id = Uuid::new_v4();
+    let other = Uuid::new_v4();
+    for id in [tournament_id, other, tournament_id] {
+        broadcaster
+            .broadcast_event(&RealtimeEvent::BracketUpdate {
+                tournament_id: id,
+                category_id: None,
+            })
+            .await;
+    }
+
+    let app = actix_web::test::init_service(
+        App::new()
+            .app_data(web::Data::new(broadcaster))
+            .route("/events", web::get().to(event_stream)),
+    )
+    .await;
+    let req = actix_web::test::TestRequest::get()
+        .uri(&format!("/events?tournament_id={}", tournament_id))
+        .insert_header(("Last-Event-ID", "1"))
+        .to_request();
+    let resp = actix_web::test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+
+    let mut body = resp.into_body();
+    let mut received = String::new();
+    while !received.contains("id: 3") {
+        let chunk = tokio::time::timeout(
+            Duration::from_secs(2),
+            std::future::poll_fn(|cx| std::pin::Pin::new(&mut body).poll_next(cx)),
+        )
+        .await
+        .expect("replayed event was not streamed");
+        match chunk {
+            Some(Ok(bytes)) => received.push_str(&String::from_utf8_lossy(&bytes)),
+            _ => break,
+        }
+    }
+    assert!(received.contains("retry: 3000"));
+    assert!(received.contains("id: 3"));
+    assert!(!received.contains("id: 2"));
+}
@@ -0,0 +1,360 @@
+//! Tournament timezones: local date filters, localized schedules and exports, run against
+//! the in-memory repositories.
+
+mod common;
+
+use actix_web::body::to_bytes;
+use actix_web::test::TestRequest;
+use actix_web::{web, HttpMessage};
+use chrono::{NaiveDate, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde_json::Value;
+use uuid::Uuid;
+
+use server::domain::match_domain::{MatchType, NewMatch};
+use server::domain::participant::CreatePlayer;
+use server::domain::tournament::{
+    ExportEntity, ExportFormat, ExportRequest, NewTournament, NewTournamentCategory, SportType,
+    TeamComposition, TournamentSearchQuery,
+};
+use server::domain::user::{NewUser, NewUserProfile};
+use server::infra::api::handlers::tournament_handler::TournamentHandler;
+use server::infra::repositories::Repositories;
+use server::shared::jwt::Claims;
+use server::shared::timezone::{local_day, parse_date_filter, parse_timezone, DayBound};
+use server::shared::AppError;
+
+use common::{new_tournament, services};
+
+fn tournament(name: &str, timezone: Option<&str>) -> NewTournament {
+    NewTournament {
+        timezone: timezone.map(str::to_string),
+        ..new_tournament(
+            name,
+            SportType::Tennis,
+            // 01:30 on 20 October in Madrid, still the 19th in UTC
+            Utc.with_ymd_and_hms(2026, 10, 19, 23, 30, 0).unwrap(),
+            Utc.with_ymd_and_hms(2026, 10, 21, 18, 0, 0).unwrap(),
+        )
+    }
+}
+
+#[test]
+fn test_date_filters_read_local_times() {
+    let madrid = parse_timezone("Europe/Madrid").unwrap();
+    let new_york: Tz = "America/New_York".parse().unwrap();
+    let utc = |y, m, d, h, min| Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap();
+
+    // Bare dates cover the whole local day
+    assert_eq!(
+        parse_date_filter("2026-10-19", madrid, DayBound::Start).unwrap(),
+        utc(2026, 10, 18, 22, 0)
+    );
+    assert_eq!(
+        parse_date_filter("2026-10-19", madrid, DayBound::End).unwrap(),
+        utc(2026, 10, 19, 22, 0) - chrono::Duration::microseconds(1)
+    );
+    // Offsets win over the zone; local date-times use it
+    assert_eq!(
+        parse_date_filter("2026-10-19T10:00:00+00:00", madrid, DayBound::Start).unwrap(),
+        utc(2026, 10, 19, 10, 0)
+    );
+    assert_eq!(
+        parse_date_filter("2026-10-19T10:00", madrid, DayBound::Start).unwrap(),
+        utc(2026, 10, 19, 8, 0)
+    );
+
+    // 02:30 does not exist when New York springs forward; 01:30 happens twice in autumn
+    assert_eq!(
+        parse_date_filter("2026-03-08T02:30", new_york, DayBound::Start).unwrap(),
+        utc(2026, 3, 8, 7, 0)
+    );
+    assert_eq!(
+        parse_date_filter("2026-11-01T01:30", new_york, DayBound::Start).unwrap(),
+        utc(2026, 11, 1, 5, 30)
+    );
+    let (start, end) = local_day(NaiveDate::from_ymd_opt(2026, 10, 25).unwrap(), madrid);
+    assert_eq!((end - start).num_hours(), 25);
+
+    assert!(matches!(
+        parse_date_filter("19/10/2026", madrid, DayBound::Start),
+        Err(AppError::ValidationError(_))
+    ));
+    assert!(matches!(
+        parse_timezone("Mars/Olympus"),
+        Err(AppError::ValidationError(_))
+    ));
+}
+
+#[actix_web::test]
+async fn test_tournament_search_reads_dates_in_the_requested_zone() {
+    let repos = Repositories::in_memory();
+    let services = services(&repos);
+
+    let open = services
+        .tournaments
+        .create_tournament(tournament("Madrid Open", Some("Europe/Madrid")))
+        .await
+        .unwrap();
+    assert_eq!(open.timezone, "Europe/Madrid");
+    let plain = services
+        .tournaments
+        .create_tournament(tournament("Plain Cup", None))
+        .await
+        .unwrap();
+    assert_eq!(plain.timezone, "UTC");
+    assert!(matches!(
+        services
+            .tournaments
+            .create_tournament(tournament("Nowhere Cup", Some("Mars/Olympus")))
+            .await,
+        Err(AppError::ValidationError(_))
+    ));
+
+    let search = |tz: Option<&str>| TournamentSearchQuery {
+        name: Some("Madrid".to_string()),
+        date_from: Some("2026-10-20".to_string()),
+        tz: tz.map(str::to_string),
+        ..Default::default()
+    };
+    // The 20th starts two hours earlier in Madrid than in UTC
+    let found = services
+        .tournaments
+        .search_tournaments(search(Some("Europe/Madrid")))
+        .await
+        .unwrap();
+    assert_eq!(found.len(), 1);
+    assert!(services
+        .tournaments
+        .search_tournaments(search(None))
+        .await
+        .unwrap()
+        .is_empty());
+
+    let bad_date = TournamentSearchQuery {
+        date_to: Some("next week".to_string()),
+        ..Default::default()
+    };
+    assert!(matches!(
+        services.tournaments.search_tournaments(bad_date).await,
+        Err(AppError::ValidationError(_))
+    ));
+}
+
+/// A user whose profile is set to `timezone`
+async fn seed_user(repos: &Repositories, timezone: Option<&str>) -> Uuid {
+    let user = repos
+        .users
+        .create(NewUser {
+            name: Some("Ana Lee".to_string()),
+            email: format!("{}@example.com", Uuid::new_v4()),
+            google_id: Uuid::new_v4().to_string(),
+        })
+        .await
+        .unwrap();
+    repos
+        .profiles
+        .create(NewUserProfile {
+            user_id: user.id,
+            bio: None,
+            avatar_url: None,
+            phone: None,
+            date_of_birth: None,
+            timezone: timezone.map(str::to_string),
+            language: None,
+            notification_preferences: None,
+            privacy_settings: None,
+            location: None,
+            website: None,
+            social_links: None,
+            preferences: None,
+            is_public: None,
+        })
+        .await
+        .unwrap();
+    user.id
+}
+
+#[actix_web::test]
+async fn test_tournament_search_falls_back_to_the_profile_zone() {
+    let repos = Repositories::in_memory();
+    let services = services(&repos);
+    services
+        .tournaments
+        .create_tournament(tournament("Madrid Open", Some("Europe/Madrid")))
+        .await
+        .unwrap();
+    let in_madrid = seed_user(&repos, Some("Europe/Madrid")).await;
+    let in_utc = seed_user(&repos, None).await;
+
+    // Searches as `user_id` would through the API, signed in
+    let search = |user_id: Uuid, query: &str| {
+        let req = TestRequest::get()
+            .uri(&format!("/tournaments/search?{}", query))
+            .to_http_request();
+        req.extensions_mut().insert(Claims {
+            sub: user_id.to_string(),
+            exp: usize::MAX,
+            email: "ana@example.com".to_string(),
+        });
+        let query = web::Query::<TournamentSearchQuery>::from_query(req.query_string()).unwrap();
+        let tournaments = web::Data::new(services.tournaments.clone());
+        let users = web::Data::new(services.users.clone());
+        async move {
+            let resp = TournamentHandler::search(tournaments, users, req, query).await;
+            assert_eq!(resp.status(), 200);
+            let body: Value =
+                serde_json::from_slice(&to_bytes(resp.into_body()).await.unwrap()).unwrap();
+            body["result"].as_array().unwrap().len()
+        }
+    };
+
+    // The 20th starts at 22:00 UTC on the 19th for a caller in Madrid
+    assert_eq!(
+        search(in_madrid, "name=Madrid&date_from=2026-10-20").await,
+        1
+    );
+    assert_eq!(search(in_utc, "name=Madrid&date_from=2026-10-20").await, 0);
+    // `tz` still wins over the profile
+    assert_eq!(
+        search(in_madrid, "name=Madrid&date_from=2026-10-20&tz=UTC").await,
+        0
+    );
+}
+
+#[actix_web::test]
+async fn test_schedule_and_exports_show_local_times() {
+    let repos = Repositories::in_memory();
+    let services = services(&repos);
+    let tournament = services
+        .tournaments
+        .create_tournament(tournament("Madrid Open", Some("Europe/Madrid")))
+        .await
+        .unwrap();
+    let category = repos
+        .categories
+        .create(NewTournamentCategory {
+            tournament_id: tournament.id,
+            name: "Open Singles".to_string(),
+            description: None,
+            team_composition: TeamComposition::Singles,
+            min_participants: None,
+            max_participants: None,
+            entry_fee: None,
+            prize_distribution: None,
+            rules: None,
+            constraints: None,
+        })
+        .await
+        .unwrap();
+    let mut players = Vec::new();
+    for name in ["Ana Lee", "Ben Ortiz"] {
+        let player = repos
+            .players
+            .create(CreatePlayer {
+                name: name.to_string(),
+                user_id: None,
+            })
+            .await
+            .unwrap();
+        players.push(player.id);
+    }
+    services
+        .matches
+        .create_match(
+            NewMatch {
+                tournament_category_id: category.id,
+                participant1_team_id: None,
+                participant1_player_id: Some(players[0]),
+                participant1_partner_id: None,
+                participant2_team_id: None,
+                participant2_player_id: Some(players[1]),
+                participant2_partner_id: None,
+                match_type: MatchType::Final,
+                round_number: Some(1),
+                match_number: None,
+                scheduled_date: Utc.with_ymd_and_hms(2026, 10, 19, 22, 30, 0).unwrap(),
+                venue: None,
+                court_number: None,
+                court_id: None,
+                referee_name: None,
+                umpire_name: None,
+                notes: None,
+                metadata: None,
+            },
+            true,
+        )
+        .await
+        .unwrap();
+
+    // 22:30 UTC is half past midnight on the 20th in Madrid
+    let madrid: Tz = "Europe/Madrid".parse().unwrap();
+    let day = NaiveDate::from_ymd_opt(2026, 10, 20).unwrap();
+    let items = services
+        .matches
+        .get_match_schedule_on(day, madrid)
+        .await
+        .unwrap();
+    assert_eq!(items.len(), 1);
+    assert!(services
+        .matches
+        .get_match_schedule_on(day, Tz::UTC)
+        .await
+        .unwrap()
+        .is_empty());
+
+    // Without a requested zone, items are shown in their tournament's zone
+    let local = services
+        .matches
+        .localize_schedule(items.clone(), None)
+        .await
+        .unwrap();
+    let json = serde_json::to_value(&local[0]).unwrap();
+    assert_eq!(json["timezone"], "Europe/Madrid");
+    assert_eq!(json["local_scheduled_date"], "2026-10-20T00:30:00+02:00");
+    assert_eq!(json["participant1_name"], "Ana Lee");
+    let utc = services
+        .matches
+        .localize_schedule(items, Some(Tz::UTC))
+        .await
+        .unwrap();
+    assert_eq!(utc[0].local_scheduled_date, "2026-10-19T22:30:00+00:00");
+
+    let export = |timezone: Option<&str>| ExportRequest {
+        format: ExportFormat::Csv,
+        entities: vec![ExportEntity::Matches],
+        columns: vec!["scheduled_date".to_string()],
+        timezone: timezone.map(str::to_string),
+    };
+    let csv = services
+        .exports
+        .export_tournament(tournament.id, export(None))
+        .await
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(csv.bytes).unwrap(),
+        "scheduled_date\r\n2026-10-20T00:30:00+02:00\r\n"
+    );
+    let csv = services
+        .exports
+        .export_tournament(tournament.id, export(Some("America/New_York")))
+        .await
+        .unwrap();
+    assert!(String::from_utf8(csv.bytes)
+        .unwrap()
+        .contains("2026-10-19T18:30:00-04:00"));
+
+    let pdf = services
+        .exports
+        .schedule_pdf(tournament.id, Some(day), None)
+        .await
+        .unwrap();
+    assert!(pdf.filename.ends_with("_schedule_2026-10-20.pdf"));
+    assert!(matches!(
+        services
+            .exports
+            .schedule_pdf(tournament.id, None, Some("Mars/Olympus"))
+            .await,
+        Err(AppError::ValidationError(_))
+    ));
+}
@@ -1,14 +1,139 @@
-//! Tournament API integration tests.
-//!
-//! These tests previously targeted the legacy API (config::DbPool, models::tournament, routes::tournament).
-//! They have been removed as part of legacy cleanup. Replace with integration tests against the
-//! DDD API (server::infra::api::api_routes, server::domain::tournament::*, service app_data) when needed.
-
-#[cfg(test)]
-mod tournament_tests {
-    #[actix_web::test]
-    async fn test_tournament_api_placeholder() {
-        // Placeholder until DDD API integration tests are added.
-        assert!(true);
-    }
+//! Tournament API integration tests, run against the in-memory repositories.
+
+mod common;
+
+use actix_web::test;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use server::infra::repositories::Repositories;
+
+use common::init_app;
+
+fn new_tournament(name: &str) -> Value {
+    json!({
+        "name": name,
+        "description": "Club championship",
+        "sport_type": "basketball",
+        "format": "elimination",
+        "start_date": "2026-06-01T09:00:00Z",
+        "end_date": "2026-06-03T18:00:00Z",
+        "venue": "Main Hall",
+        "max_participants": 16,
+        "prize_pool": "500.00",
+        "organizer_id": Uuid::new_v4(),
+    })
+}
+
+#[actix_web::test]
+async fn test_tournament_crud_round_trip() {
+    let app = init_app!(Repositories::in_memory());
+
+    let req = test::TestRequest::post()
+        .uri("/tournaments")
+        .set_json(new_tournament("Spring Open"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 201);
+    let body: Value = test::read_body_json(resp).await;
+    let id = body["result"]["id"].as_str().unwrap().to_string();
+    assert_eq!(body["result"]["status"], "draft");
+
+    let req = test::TestRequest::put()
+        .uri(&format!("/tournaments/{}", id))
+        .set_json(json!({ "name": "Spring Open 2026" }))
+        .to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["result"]["name"], "Spring Open 2026");
+
+    let req = test::TestRequest::get().uri("/tournaments").to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["result"].as_array().unwrap().len(), 1);
+
+    let req = test::TestRequest::delete()
+        .uri(&format!("/tournaments/{}", id))
+        .to_request();
+    assert!(test::call_service(&app, req).await.status().is_success());
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/tournaments/{}", id))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 404);
+}
+
+#[actix_web::test]
+async fn test_team_can_only_register_once_per_category() {
+    let repos = Repositories::in_memory();
+    let app = init_app!(repos);
+
+    let req = test::TestRequest::post()
+        .uri("/tournaments")
+        .set_json(new_tournament("Summer Cup"))
+        .to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    let tournament_id = body["result"]["id"].as_str().unwrap().to_string();
+
+    let req = test::TestRequest::post()
+        .uri("/tournament_categories")
+        .set_json(json!({
+            "tournament_id": tournament_id,
+            "name": "Open",
+            "team_composition": "team",
+        }))
+        .to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    let category_id = body["result"]["id"].as_str().unwrap().to_string();
+
+    let req = test::TestRequest::post()
+        .uri("/teams")
+        .set_json(json!({ "name": "Riverside Hawks" }))
+        .to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    let team_id = body["result"]["id"].as_str().unwrap().to_string();
+
+    let registration = json!({ "tournament_category_id": category_id, "team_id": team_id });
+    let req = test::TestRequest::post()
+        .uri("/tournament_registrations")
+        .set_json(&registration)
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 201);
+
+    let req = test::TestRequest::post()
+        .uri("/tournament_registrations")
+        .set_json(&registration)
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 409);
+
+    let req = test::TestRequest::get()
+        .uri(&format!(
+            "/tournament_registrations/tournament/{}",
+            tournament_id
+        ))
+        .to_request();
+    let body: Value = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(body["result"].as_array().unwrap().len(), 1);
+    assert_eq!(
+        repos
+            .registrations
+            .get_by_team(team_id.parse().unwrap())
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[actix_web::test]
+async fn test_generate_bracket_for_unknown_tournament_is_not_found() {
+    let repos = Repositories::in_memory();
+    let app = init_app!(repos);
+
+    let req = test::TestRequest::put()
+        .uri(&format!("/brackets/generate/{}", Uuid::new_v4()))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 404);
+
+    // The rolled-back unit of work released the store
+    let req = test::TestRequest::get().uri("/tournaments").to_request();
+    assert!(test::call_service(&app, req).await.status().is_success());
 }
@@ -0,0 +1,241 @@
+//! Units of work: multi-step writes are kept only when committed, run against the in-memory
+//! repositories.
+
+mod common;
+
+use uuid::Uuid;
+
+use server::domain::match_domain::{MatchType, NewMatch, NewMatchResult};
+use server::domain::tournament::{
+    BracketType, EditableTournamentRegistration, NewTournamentBracket, RegistrationStatus,
+};
+use server::infra::repositories::Repositories;
+use server::shared::AppError;
+
+use common::{at, entrant, seed_category, services};
+
+/// Registers and approves a player; returns the player
+async fn approved_entrant(repos: &Repositories, category_id: Uuid, name: &str) -> Uuid {
+    let player_id = entrant(repos, category_id, name).await;
+    let registration = repos
+        .registrations
+        .get_by_player(player_id)
+        .await
+        .unwrap()
+        .remove(0);
+    repos
+        .registrations
+        .update(
+            registration.id,
+            EditableTournamentRegistration {
+                registration_status: Some(RegistrationStatus::Approved),
+                payment_status: None,
+                payment_amount: None,
+                payment_reference: None,
+                notes: None,
+                metadata: None,
+            },
+        )
+        .await
+        .unwrap();
+    player_id
+}
+
+async fn seed_match(repos: &Repositories, category_id: Uuid) -> Uuid {
+    let ana = entrant(repos, category_id, "Ana Lee").await;
+    let ben = entrant(repos, category_id, "Ben Ortiz").await;
+    repos
+        .matches
+        .create(NewMatch {
+            tournament_category_id: category_id,
+            participant1_team_id: None,
+            participant1_player_id: Some(ana),
+            participant1_partner_id: None,
+            participant2_team_id: None,
+            participant2_player_id: Some(ben),
+            participant2_partner_id: None,
+            match_type: MatchType::Final,
+            round_number: None,
+            match_number: None,
+            scheduled_date: at(15, 0),
+            venue: None,
+            court_number: None,
+            court_id: None,
+            referee_name: None,
+            umpire_name: None,
+            notes: None,
+            metadata: None,
+        })
+        .await
+        .unwrap()
+        .id
+}
+
+fn set(match_id: Uuid, set_number: i32, scores: (i32, i32)) -> NewMatchResult {
+    NewMatchResult {
+        match_id,
+        set_number: Some(set_number),
+        participant1_score: Some(scores.0),
+        participant2_score: Some(scores.1),
+        period_number: None,
+        period_name: None,
+        scoring_data: None,
+        participant1_stats: None,
+        participant2_stats: None,
+    }
+}
+
+#[actix_web::test]
+async fn test_only_committed_work_is_kept() {
+    let repos = Repositories::in_memory();
+    let category = seed_category(&repos, None).await;
+    let match_id = seed_match(&repos, category.id).await;
+    let bracket = || NewTournamentBracket {
+        tournament_id: category.tournament_id,
+        category_id: Some(category.id),
+        bracket_type: BracketType::SingleElimination,
+        total_rounds: 1,
+        bracket_data: None,
+        settings: None,
+    };
+
+    // Rolled back, and dropped half way as a failing `?` would
+    let work = repos.unit_of_work.begin().await.unwrap();
+    work.brackets().create(bracket()).await.unwrap();
+    work.match_results()
+        .create(set(match_id, 1, (11, 7)))
+        .await
+        .unwrap();
+    work.rollback().await.unwrap();
+    let work = repos.unit_of_work.begin().await.unwrap();
+    work.match_results()
+        .create(set(match_id, 1, (11, 7)))
+        .await
+        .unwrap();
+    drop(work);
+    assert!(repos
+        .brackets
+        .get_by_category_id(category.id)
+        .await
+        .unwrap()
+        .is_none());
+    assert!(repos
+        .match_results
+        .find_by_match(match_id)
+        .await
+        .unwrap()
+        .is_empty());
+
+    let work = repos.unit_of_work.begin().await.unwrap();
+    work.brackets().create(bracket()).await.unwrap();
+    work.match_results()
+        .create(set(match_id, 1, (11, 7)))
+        .await
+        .unwrap();
+    work.commit().await.unwrap();
+    assert!(repos
+        .brackets
+        .get_by_category_id(category.id)
+        .await
+        .unwrap()
+        .is_some());
+    assert_eq!(
+        repos
+            .match_results
+            .find_by_match(match_id)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[actix_web::test]
+async fn test_bulk_results_are_saved_together() {
+    let repos = Repositories::in_memory();
+    let services = services(&repos);
+    let category = seed_category(&repos, None).await;
+    let match_id = seed_match(&repos, category.id).await;
+
+    // The second set points at a missing match, so the first is not kept either
+    let failed = services
+        .matches
+        .bulk_create_match_results(vec![
+            set(match_id, 1, (11, 7)),
+            set(Uuid::new_v4(), 2, (11, 9)),
+        ])
+        .await;
+    assert!(failed.is_err());
+    assert!(repos
+        .match_results
+        .find_by_match(match_id)
+        .await
+        .unwrap()
+        .is_empty());
+
+    let saved = services
+        .matches
+        .bulk_create_match_results(vec![set(match_id, 1, (11, 7)), set(match_id, 2, (11, 9))])
+        .await
+        .unwrap();
+    assert_eq!(saved.len(), 2);
+    assert_eq!(
+        repos
+            .match_results
+            .find_by_match(match_id)
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+}
+
+#[actix_web::test]
+async fn test_bracket_generation_saves_nothing_when_refused() {
+    let repos = Repositories::in_memory();
+    let services = services(&repos);
+    let category = seed_category(&repos, None).await;
+    approved_entrant(&repos, category.id, "Ana Lee").await;
+
+    let refused = services
+        .tournaments
+        .generate_category_bracket(category.id)
+        .await;
+    assert!(matches!(refused, Err(AppError::ValidationError(_))));
+    assert!(repos
+        .brackets
+        .get_by_category_id(category.id)
+        .await
+        .unwrap()
+        .is_none());
+
+    approved_entrant(&repos, category.id, "Ben Ortiz").await;
+    let bracket = services
+        .tournaments
+        .generate_category_bracket(category.id)
+        .await
+        .unwrap()
+        .unwrap();
+    let stored = repos
+        .brackets
+        .get_by_category_id(category.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.id, bracket.id);
+
+    let again = services
+        .tournaments
+        .generate_category_bracket(category.id)
+        .await;
+    assert!(matches!(again, Err(AppError::Conflict(_))));
+    assert_eq!(
+        repos
+            .brackets
+            .get_by_tournament_id(category.tournament_id)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
@@ -0,0 +1,252 @@
+//! Venues, courts and court assignment, run against the in-memory repositories.
+
+mod common;
+
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use uuid::Uuid;
+
+use server::domain::match_domain::{EditableMatch, MatchType, NewMatch};
+use server::domain::venue::{EditableCourt, NewCourt, NewVenue};
+use server::infra::repositories::Repositories;
+use server::shared::AppError;
+
+use common::{at, seed_category, services};
+
+fn venue(opening_hours: serde_json::Value) -> NewVenue {
+    serde_json::from_value(json!({
+        "name": "Sports Hall",
+        "address": "Calle Mayor 1",
+        "city": "Madrid",
+        "country": "ES",
+        "timezone": "Europe/Madrid",
+        "opening_hours": opening_hours,
+    }))
+    .unwrap()
+}
+
+fn court(name: &str) -> NewCourt {
+    serde_json::from_value(json!({ "name": name, "kind": "table", "surface": "wood" })).unwrap()
+}
+
+fn new_match(category_id: Uuid, scheduled_date: DateTime<Utc>, court_id: Option<Uuid>) -> NewMatch {
+    NewMatch {
+        tournament_category_id: category_id,
+        participant1_team_id: None,
+        participant1_player_id: None,
+        participant1_partner_id: None,
+        participant2_team_id: None,
+        participant2_player_id: None,
+        participant2_partner_id: None,
+        match_type: MatchType::GroupStage,
+        round_number: None,
+        match_number: None,
+        scheduled_date,
+        venue: Some("Somewhere else".to_string()),
+        court_number: None,
+        court_id,
+        referee_name: None,
+        umpire_name: None,
+        notes: None,
+        metadata: None,
+    }
+}
+
+#[actix_web::test]
+async fn test_venue_validation_and_opening_hours() {
+    let repos = Repositories::in_memory();
+    let venues = services(&repos).venues;
+
+    let mut bad = venue(json!([]));
+    bad.timezone = "Mars/Olympus_Mons".to_string();
+    assert!(matches!(
+        venues.create_venue(bad).await,
+        Err(AppError::ValidationError(_))
+    ));
+    let backwards = venue(json!([{ "weekday": "Sat", "opens": "22:00", "closes": "08:00" }]));
+    assert!(venues.create_venue(backwards).await.is_err());
+
+    let hall = venues
+        .create_venue(venue(json!([
+            { "weekday": "Sat", "opens": "08:00", "closes": "22:00" },
+        ])))
+        .await
+        .unwrap();
+    // Local 08:00-22:00 is 06:00-20:00 UTC
+    assert!(hall.is_open_between(at(6, 0), at(7, 0)));
+    assert!(!hall.is_open_between(at(5, 30), at(6, 30)));
+    assert!(!hall.is_open_between(at(19, 30), at(20, 30)));
+    assert!(!hall.is_open_between(
+        at(6, 0) + chrono::Duration::days(1),
+        at(7, 0) + chrono::Duration::days(1)
+    ));
+
+    let tournament_id = Uuid::new_v4();
+    assert!(venues
+        .add_tournament_venue(tournament_id, hall.id)
+        .await
+        .is_err());
+    let category_id = seed_category(&repos, None).await.id;
+    let tournament_id = repos
+        .categories
+        .get_by_id(category_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .tournament_id;
+    venues
+        .add_tournament_venue(tournament_id, hall.id)
+        .await
+        .unwrap();
+    let linked = venues.get_tournament_venues(tournament_id).await.unwrap();
+    assert_eq!(linked.len(), 1);
+    assert_eq!(linked[0].id, hall.id);
+    assert!(venues
+        .remove_tournament_venue(tournament_id, hall.id)
+        .await
+        .unwrap());
+    assert!(venues
+        .get_tournament_venues(tournament_id)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+#[actix_web::test]
+async fn test_court_availability() {
+    let repos = Repositories::in_memory();
+    let venues = services(&repos).venues;
+    let hall = venues.create_venue(venue(json!([]))).await.unwrap();
+
+    assert!(venues
+        .add_court(Uuid::new_v4(), court("Table 1"))
+        .await
+        .unwrap()
+        .is_none());
+    let mut morning = court("Table 1");
+    morning.availability = serde_json::from_value(json!([
+        { "starts_at": at(8, 0), "ends_at": at(12, 0) },
+    ]))
+    .unwrap();
+    let table = venues.add_court(hall.id, morning).await.unwrap().unwrap();
+    assert!(venues.add_court(hall.id, court("Table 1")).await.is_err());
+    assert_eq!(venues.get_courts(hall.id).await.unwrap().len(), 1);
+
+    assert_eq!(
+        venues
+            .court_available(table.id, at(9, 0), at(10, 0))
+            .await
+            .unwrap(),
+        Some(true)
+    );
+    assert_eq!(
+        venues
+            .court_available(table.id, at(11, 30), at(12, 30))
+            .await
+            .unwrap(),
+        Some(false)
+    );
+    assert_eq!(
+        venues
+            .court_available(Uuid::new_v4(), at(9, 0), at(10, 0))
+            .await
+            .unwrap(),
+        None
+    );
+
+    venues
+        .update_court(
+            table.id,
+            EditableCourt {
+                is_active: Some(false),
+                ..EditableCourt::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        venues
+            .court_available(table.id, at(9, 0), at(10, 0))
+            .await
+            .unwrap(),
+        Some(false)
+    );
+}
+
+#[actix_web::test]
+async fn test_matches_take_their_court_location() {
+    let repos = Repositories::in_memory();
+    let services = services(&repos);
+    let hall = services
+        .venues
+        .create_venue(venue(json!([])))
+        .await
+        .unwrap();
+    let table1 = services
+        .venues
+        .add_court(hall.id, court("Table 1"))
+        .await
+        .unwrap()
+        .unwrap();
+    let table2 = services
+        .venues
+        .add_court(hall.id, court("Table 2"))
+        .await
+        .unwrap()
+        .unwrap();
+    let category_id = seed_category(&repos, None).await.id;
+
+    let first = services
+        .matches
+        .create_match(new_match(category_id, at(9, 0), Some(table1.id)), false)
+        .await
+        .unwrap();
+    assert_eq!(first.court_id, Some(table1.id));
+    assert_eq!(first.venue.as_deref(), Some("Sports Hall"));
+    assert_eq!(first.court_number.as_deref(), Some("Table 1"));
+    let second = services
+        .matches
+        .create_match(new_match(category_id, at(10, 0), Some(table1.id)), false)
+        .await
+        .unwrap();
+    services
+        .matches
+        .create_match(new_match(category_id, at(9, 0), Some(table2.id)), false)
+        .await
+        .unwrap();
+    assert!(services
+        .matches
+        .create_match(
+            new_match(category_id, at(9, 0), Some(Uuid::new_v4())),
+            false
+        )
+        .await
+        .is_err());
+
+    let on_table1 = services
+        .matches
+        .get_court_matches(table1.id, at(0, 0), at(23, 0))
+        .await
+        .unwrap();
+    assert_eq!(
+        on_table1.iter().map(|m| m.id).collect::<Vec<_>>(),
+        vec![first.id, second.id]
+    );
+
+    let moved = services
+        .matches
+        .update_match(
+            second.id,
+            serde_json::from_value::<EditableMatch>(json!({ "court_id": table2.id })).unwrap(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(moved.court_number.as_deref(), Some("Table 2"));
+
+    // Deleting a court keeps the match, and the names it was played under
+    services.venues.delete_court(table1.id).await.unwrap();
+    let first = services.matches.get_match(first.id).await.unwrap().unwrap();
+    assert_eq!(first.court_id, None);
+    assert_eq!(first.court_number.as_deref(), Some("Table 1"));
+}